@@ -0,0 +1,83 @@
+//! Compare the flush latency of [`UringLogStore`] with a plain `pwrite()` + `fdatasync()` writer.
+//!
+//! Every iteration appends one entry and waits until it is flushed, which is what a single
+//! client-write costs on a leader that has no other pending IO.
+
+use std::fs::OpenOptions;
+use std::os::unix::fs::FileExt;
+use std::time::Duration;
+use std::time::Instant;
+
+use openraft::storage::RaftLogStorageExt;
+use openraft::testing::blank_ent;
+use openraft_log_store_uring::UringLogStore;
+use openraft_memstore::TypeConfig;
+use tempfile::TempDir;
+
+const N: u64 = 2_000;
+
+#[tokio::main(flavor = "multi_thread", worker_threads = 2)]
+async fn main() {
+    let td = TempDir::new().expect("couldn't create temp dir");
+
+    let uring = {
+        let mut store = UringLogStore::<TypeConfig>::open(td.path().join("uring.log")).unwrap();
+        let mut lat = Vec::with_capacity(N as usize);
+
+        for i in 1..=N {
+            let start = Instant::now();
+            store.blocking_append([blank_ent::<TypeConfig>(1, 1, i)]).await.unwrap();
+            lat.push(start.elapsed());
+        }
+        lat
+    };
+
+    let pwrite = {
+        let f = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(td.path().join("pwrite.log"))
+            .unwrap();
+        let mut offset = 0;
+        let mut lat = Vec::with_capacity(N as usize);
+
+        for i in 1..=N {
+            let start = Instant::now();
+
+            let payload = serde_json::to_vec(&blank_ent::<TypeConfig>(1, 1, i)).unwrap();
+            let mut buf = (payload.len() as u32).to_le_bytes().to_vec();
+            buf.extend_from_slice(&payload);
+
+            f.write_all_at(&buf, offset).unwrap();
+            f.sync_data().unwrap();
+            offset += buf.len() as u64;
+
+            lat.push(start.elapsed());
+        }
+        lat
+    };
+
+    println!(
+        "{:<16} {:>10} {:>10} {:>10} {:>10}",
+        "store", "avg", "p50", "p99", "max"
+    );
+    report("uring", uring);
+    report("pwrite+fdatasync", pwrite);
+}
+
+fn report(name: &str, mut lat: Vec<Duration>) {
+    lat.sort();
+    let avg = lat.iter().sum::<Duration>() / lat.len() as u32;
+    let p = |q: f64| lat[((lat.len() as f64 * q) as usize).min(lat.len() - 1)];
+
+    println!(
+        "{:<16} {:>10.1?} {:>10.1?} {:>10.1?} {:>10.1?}",
+        name,
+        avg,
+        p(0.5),
+        p(0.99),
+        lat[lat.len() - 1]
+    );
+}
@@ -0,0 +1,16 @@
+//! An [`RaftLogStorage`] implementation that writes the log file with `io_uring`.
+//!
+//! Appended entries are batched by a dedicated IO thread, written from registered buffers and
+//! persisted with a linked `fdatasync`, all in one submission. See the README for details.
+//!
+//! [`RaftLogStorage`]: openraft::storage::RaftLogStorage
+#![deny(unused_crate_dependencies)]
+#![deny(unused_qualifications)]
+
+pub mod log_store;
+mod ring;
+
+#[cfg(test)]
+mod test;
+
+pub use log_store::UringLogStore;
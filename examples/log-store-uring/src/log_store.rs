@@ -0,0 +1,296 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Read;
+use std::ops::RangeBounds;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use openraft::alias::EntryOf;
+use openraft::alias::LogIdOf;
+use openraft::alias::VoteOf;
+use openraft::entry::RaftEntry;
+use openraft::storage::IOFlushed;
+use openraft::storage::LogStats;
+use openraft::storage::RaftLogStorage;
+use openraft::LogState;
+use openraft::OptionalSend;
+use openraft::RaftLogReader;
+use openraft::RaftTypeConfig;
+use openraft::StorageError;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::ring::Ring;
+
+/// A record in the log file.
+///
+/// The file is replayed from the start when opened, so a later record overrides an earlier one.
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+enum Record<C>
+where C: RaftTypeConfig
+{
+    Vote(VoteOf<C>),
+    Entry(EntryOf<C>),
+    /// Remove entries since this index(inclusive).
+    Truncate(u64),
+    /// Remove entries upto this log id(inclusive).
+    Purge(LogIdOf<C>),
+}
+
+impl<C> Record<C>
+where C: RaftTypeConfig
+{
+    /// Append the encoded record to `buf`: a little-endian `u32` length followed by json payload.
+    fn encode_to(&self, buf: &mut Vec<u8>) -> Result<(), serde_json::Error> {
+        let payload = serde_json::to_vec(self)?;
+        buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&payload);
+        Ok(())
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, serde_json::Error> {
+        let mut buf = Vec::new();
+        self.encode_to(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+/// In memory copy of the log file.
+struct LogData<C>
+where C: RaftTypeConfig
+{
+    vote: Option<VoteOf<C>>,
+    last_purged_log_id: Option<LogIdOf<C>>,
+
+    /// Log entries, stored in serialized json.
+    logs: BTreeMap<u64, Vec<u8>>,
+
+    /// The total size of `logs`, maintained when entries are added or removed, so that `stats()`
+    /// does not have to scan the logs.
+    bytes: u64,
+}
+
+impl<C> LogData<C>
+where C: RaftTypeConfig
+{
+    fn apply(&mut self, record: Record<C>) -> Result<(), serde_json::Error> {
+        match record {
+            Record::Vote(v) => self.vote = Some(v),
+            Record::Entry(ent) => {
+                let buf = serde_json::to_vec(&ent)?;
+                self.bytes += buf.len() as u64;
+                if let Some(prev) = self.logs.insert(ent.index(), buf) {
+                    self.bytes -= prev.len() as u64;
+                }
+            }
+            Record::Truncate(since) => {
+                let removed = self.logs.split_off(&since);
+                self.bytes -= Self::size_of(&removed);
+            }
+            Record::Purge(upto) => {
+                let kept = self.logs.split_off(&(upto.index() + 1));
+                let removed = std::mem::replace(&mut self.logs, kept);
+                self.bytes -= Self::size_of(&removed);
+                self.last_purged_log_id = Some(upto);
+            }
+        }
+        Ok(())
+    }
+
+    fn size_of(logs: &BTreeMap<u64, Vec<u8>>) -> u64 {
+        logs.values().map(|x| x.len() as u64).sum()
+    }
+}
+
+/// A [`RaftLogStorage`] that persists the log with `io_uring`.
+pub struct UringLogStore<C>
+where C: RaftTypeConfig
+{
+    data: Arc<Mutex<LogData<C>>>,
+    ring: Ring,
+}
+
+impl<C> Clone for UringLogStore<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            ring: self.ring.clone(),
+        }
+    }
+}
+
+impl<C> UringLogStore<C>
+where C: RaftTypeConfig
+{
+    /// Open or create the log file at `path`, replay it, and start the IO thread.
+    ///
+    /// A partially written record at the end of the file, left by a crash, is discarded.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+
+        let (data, valid_len) = Self::replay(&mut file)?;
+
+        if valid_len < file.metadata()?.len() {
+            tracing::warn!("discard torn log file tail since offset {}", valid_len);
+            file.set_len(valid_len)?;
+            file.sync_all()?;
+        }
+
+        let ring = Ring::spawn(file, valid_len)?;
+
+        Ok(Self {
+            data: Arc::new(Mutex::new(data)),
+            ring,
+        })
+    }
+
+    /// Load all records and return the state and the length of the valid part of the file.
+    fn replay(file: &mut File) -> io::Result<(LogData<C>, u64)> {
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+
+        let mut data = LogData {
+            vote: None,
+            last_purged_log_id: None,
+            logs: BTreeMap::new(),
+            bytes: 0,
+        };
+
+        let mut pos = 0;
+        while pos + 4 <= buf.len() {
+            let len = u32::from_le_bytes(buf[pos..pos + 4].try_into().unwrap()) as usize;
+            let end = pos + 4 + len;
+            if end > buf.len() {
+                break;
+            }
+
+            let Ok(record) = serde_json::from_slice::<Record<C>>(&buf[pos + 4..end]) else {
+                break;
+            };
+
+            data.apply(record).map_err(io::Error::other)?;
+            pos = end;
+        }
+
+        Ok((data, pos as u64))
+    }
+}
+
+impl<C> RaftLogReader<C> for UringLogStore<C>
+where C: RaftTypeConfig
+{
+    async fn try_get_log_entries<RB: RangeBounds<u64> + Clone + Debug + OptionalSend>(
+        &mut self,
+        range: RB,
+    ) -> Result<Vec<C::Entry>, StorageError<C>> {
+        let data = self.data.lock().unwrap();
+
+        let mut entries = vec![];
+        for (_, serialized) in data.logs.range(range) {
+            let ent = serde_json::from_slice(serialized).map_err(|e| StorageError::read_logs(&e))?;
+            entries.push(ent);
+        }
+        Ok(entries)
+    }
+
+    async fn read_vote(&mut self) -> Result<Option<VoteOf<C>>, StorageError<C>> {
+        Ok(self.data.lock().unwrap().vote.clone())
+    }
+}
+
+impl<C> RaftLogStorage<C> for UringLogStore<C>
+where C: RaftTypeConfig
+{
+    type LogReader = Self;
+
+    async fn get_log_state(&mut self) -> Result<LogState<C>, StorageError<C>> {
+        let data = self.data.lock().unwrap();
+
+        let last_purged_log_id = data.last_purged_log_id.clone();
+        let last_log_id = match data.logs.values().next_back() {
+            None => last_purged_log_id.clone(),
+            Some(serialized) => {
+                let ent: EntryOf<C> = serde_json::from_slice(serialized).map_err(|e| StorageError::read_logs(&e))?;
+                Some(ent.log_id())
+            }
+        };
+
+        Ok(LogState {
+            last_purged_log_id,
+            last_log_id,
+        })
+    }
+
+    async fn get_log_reader(&mut self) -> Self::LogReader {
+        self.clone()
+    }
+
+    async fn save_vote(&mut self, vote: &VoteOf<C>) -> Result<(), StorageError<C>> {
+        let buf = Record::<C>::Vote(vote.clone()).encode().map_err(|e| StorageError::write_vote(&e))?;
+        self.ring.write(buf).await.map_err(|e| StorageError::write_vote(&e))?;
+
+        self.data.lock().unwrap().vote = Some(vote.clone());
+        Ok(())
+    }
+
+    async fn append<I>(&mut self, entries: I, callback: IOFlushed<C>) -> Result<(), StorageError<C>>
+    where I: IntoIterator<Item = EntryOf<C>> + Send {
+        let mut buf = Vec::new();
+
+        {
+            let mut data = self.data.lock().unwrap();
+            for entry in entries {
+                let record = Record::<C>::Entry(entry);
+                record.encode_to(&mut buf).map_err(|e| StorageError::write_logs(&e))?;
+
+                // Entries must be readable once `append()` returns, even before being flushed.
+                data.apply(record).map_err(|e| StorageError::write_logs(&e))?;
+            }
+        }
+
+        self.ring
+            .submit(buf, Box::new(move |res| callback.io_completed(res)))
+            .map_err(|e| StorageError::write_logs(&e))?;
+        Ok(())
+    }
+
+    async fn truncate(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        tracing::debug!("truncate: [{:?}, +oo)", log_id);
+
+        let record = Record::<C>::Truncate(log_id.index());
+        let buf = record.encode().map_err(|e| StorageError::write_logs(&e))?;
+
+        self.data.lock().unwrap().apply(record).map_err(|e| StorageError::write_logs(&e))?;
+
+        self.ring.write(buf).await.map_err(|e| StorageError::write_logs(&e))?;
+        Ok(())
+    }
+
+    async fn purge(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        tracing::debug!("delete_log: [0, {:?}]", log_id);
+
+        let record = Record::<C>::Purge(log_id);
+        let buf = record.encode().map_err(|e| StorageError::write_logs(&e))?;
+
+        self.ring.write(buf).await.map_err(|e| StorageError::write_logs(&e))?;
+
+        self.data.lock().unwrap().apply(record).map_err(|e| StorageError::write_logs(&e))?;
+        Ok(())
+    }
+
+    async fn stats(&mut self) -> Result<Option<LogStats>, StorageError<C>> {
+        let data = self.data.lock().unwrap();
+        Ok(Some(LogStats {
+            entries: data.logs.len() as u64,
+            bytes: data.bytes,
+            segments: Some(1),
+        }))
+    }
+}
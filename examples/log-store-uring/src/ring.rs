@@ -0,0 +1,203 @@
+//! The IO thread that owns the `io_uring` instance.
+//!
+//! Writes are sent to the thread through a channel. The thread drains the channel, copies every
+//! record into a registered buffer and submits the whole batch as a chain of linked writes
+//! followed by one `fdatasync`.
+
+use std::fs::File;
+use std::io;
+use std::os::fd::AsRawFd;
+use std::sync::mpsc;
+use std::thread;
+
+use io_uring::opcode;
+use io_uring::squeue;
+use io_uring::types;
+use io_uring::IoUring;
+
+/// Max number of writes in a batch, which is also the number of registered buffers.
+const BATCH_SIZE: usize = 32;
+
+/// Size of every registered buffer. A record larger than this is written from its own memory.
+const BUF_SIZE: usize = 64 * 1024;
+
+/// The callback to call when a write is flushed or failed.
+pub(crate) type Done = Box<dyn FnOnce(io::Result<()>) + Send + 'static>;
+
+struct Write {
+    data: Vec<u8>,
+    done: Done,
+}
+
+/// A handle to send writes to the IO thread.
+///
+/// The IO thread quits when all handles are dropped.
+#[derive(Clone)]
+pub(crate) struct Ring {
+    tx: mpsc::Sender<Write>,
+}
+
+impl Ring {
+    /// Spawn an IO thread that appends data to `file`, starting at `offset`.
+    pub(crate) fn spawn(file: File, offset: u64) -> io::Result<Self> {
+        let worker = Worker::new(file, offset)?;
+
+        let (tx, rx) = mpsc::channel();
+
+        thread::Builder::new().name("log-store-uring".to_string()).spawn(move || worker.run(rx))?;
+
+        Ok(Ring { tx })
+    }
+
+    /// Queue `data` to write and call `done` when it is flushed.
+    pub(crate) fn submit(&self, data: Vec<u8>, done: Done) -> io::Result<()> {
+        self.tx
+            .send(Write { data, done })
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "uring IO thread quit"))
+    }
+
+    /// Write `data` and wait until it is flushed.
+    pub(crate) async fn write(&self, data: Vec<u8>) -> io::Result<()> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        self.submit(
+            data,
+            Box::new(move |res| {
+                let _ = tx.send(res);
+            }),
+        )?;
+
+        rx.await.map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "uring IO thread quit"))?
+    }
+}
+
+struct Worker {
+    ring: IoUring,
+
+    /// Keep the file open while it is registered in the ring.
+    _file: File,
+
+    /// Position in the file where the next write goes.
+    offset: u64,
+
+    /// The registered buffers. Their heap memory must not move or be freed while registered.
+    bufs: Vec<Vec<u8>>,
+}
+
+impl Worker {
+    fn new(file: File, offset: u64) -> io::Result<Self> {
+        // A batch uses one entry per write plus one for the fsync.
+        let ring = IoUring::new((BATCH_SIZE + 1).next_power_of_two() as u32)?;
+
+        let mut bufs = (0..BATCH_SIZE).map(|_| vec![0u8; BUF_SIZE]).collect::<Vec<_>>();
+
+        let iovecs = bufs
+            .iter_mut()
+            .map(|b| libc::iovec {
+                iov_base: b.as_mut_ptr() as *mut libc::c_void,
+                iov_len: b.len(),
+            })
+            .collect::<Vec<_>>();
+
+        // Safety: the buffers are owned by the worker and outlive the ring. They are never
+        // resized, so the registered memory stays valid.
+        unsafe { ring.submitter().register_buffers(&iovecs)? };
+        ring.submitter().register_files(&[file.as_raw_fd()])?;
+
+        Ok(Worker {
+            ring,
+            _file: file,
+            offset,
+            bufs,
+        })
+    }
+
+    fn run(mut self, rx: mpsc::Receiver<Write>) {
+        while let Ok(first) = rx.recv() {
+            let mut batch = vec![first];
+            while batch.len() < BATCH_SIZE {
+                match rx.try_recv() {
+                    Ok(w) => batch.push(w),
+                    Err(_) => break,
+                }
+            }
+
+            let res = self.write_batch(&batch);
+            if let Err(e) = &res {
+                tracing::error!("uring write batch of {} failed: {}", batch.len(), e);
+            }
+
+            for w in batch {
+                let r = match &res {
+                    Ok(()) => Ok(()),
+                    Err(e) => Err(io::Error::new(e.kind(), e.to_string())),
+                };
+                (w.done)(r);
+            }
+        }
+
+        tracing::info!("uring IO thread quit");
+    }
+
+    /// Write all records in `batch` and fsync them with the same submission.
+    fn write_batch(&mut self, batch: &[Write]) -> io::Result<()> {
+        let fd = types::Fixed(0);
+        let mut offset = self.offset;
+
+        for (i, w) in batch.iter().enumerate() {
+            let len = w.data.len();
+
+            let sqe = if len <= BUF_SIZE {
+                let buf = &mut self.bufs[i];
+                buf[..len].copy_from_slice(&w.data);
+                opcode::WriteFixed::new(fd, buf.as_ptr(), len as u32, i as u16).offset(offset).build()
+            } else {
+                opcode::Write::new(fd, w.data.as_ptr(), len as u32).offset(offset).build()
+            };
+
+            // Chain every write to the next one, and the last one to the fsync: a failed or short
+            // write cancels the fsync.
+            let sqe = sqe.flags(squeue::Flags::IO_LINK).user_data(i as u64);
+
+            // Safety: the buffer or `w.data` stays alive until all completions are reaped below.
+            unsafe { self.ring.submission().push(&sqe).expect("submission queue is large enough") };
+
+            offset += len as u64;
+        }
+
+        let fsync = opcode::Fsync::new(fd).flags(types::FsyncFlags::DATASYNC).build().user_data(batch.len() as u64);
+        unsafe { self.ring.submission().push(&fsync).expect("submission queue is large enough") };
+
+        let want = batch.len() + 1;
+        let mut results = vec![None; want];
+        let mut got = 0;
+
+        while got < want {
+            self.ring.submit_and_wait(want - got)?;
+
+            for cqe in self.ring.completion() {
+                results[cqe.user_data() as usize] = Some(cqe.result());
+                got += 1;
+            }
+        }
+
+        for (i, res) in results.into_iter().enumerate() {
+            // Safe unwrap: every submitted entry has been completed.
+            let res = res.unwrap();
+
+            if res < 0 {
+                return Err(io::Error::from_raw_os_error(-res));
+            }
+
+            if i < batch.len() && res as usize != batch[i].data.len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    format!("short write: {} of {} bytes", res, batch[i].data.len()),
+                ));
+            }
+        }
+
+        self.offset = offset;
+        Ok(())
+    }
+}
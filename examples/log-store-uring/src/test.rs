@@ -0,0 +1,79 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use openraft::storage::RaftLogStorage;
+use openraft::storage::RaftLogStorageExt;
+use openraft::testing::blank_ent;
+use openraft::testing::log::StoreBuilder;
+use openraft::testing::log::Suite;
+use openraft::testing::log_id;
+use openraft::RaftLogReader;
+use openraft::StorageError;
+use openraft::Vote;
+use openraft_memstore::BlockConfig;
+use openraft_memstore::MemStateMachine;
+use openraft_memstore::TypeConfig;
+use tempfile::TempDir;
+
+use crate::UringLogStore;
+
+struct UringBuilder {}
+
+impl StoreBuilder<TypeConfig, UringLogStore<TypeConfig>, Arc<MemStateMachine>, TempDir> for UringBuilder {
+    async fn build(
+        &self,
+    ) -> Result<(TempDir, UringLogStore<TypeConfig>, Arc<MemStateMachine>), StorageError<TypeConfig>> {
+        let td = TempDir::new().expect("couldn't create temp dir");
+        let log_store = UringLogStore::open(td.path().join("raft.log")).map_err(|e| StorageError::write_logs(&e))?;
+        let sm = Arc::new(MemStateMachine::new(BlockConfig::default()));
+        Ok((td, log_store, sm))
+    }
+}
+
+#[tokio::test]
+pub async fn test_uring_log_store() -> Result<(), StorageError<TypeConfig>> {
+    Suite::test_all(UringBuilder {}).await?;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn test_reopen_replays_log() -> Result<(), StorageError<TypeConfig>> {
+    let td = TempDir::new().expect("couldn't create temp dir");
+    let path = td.path().join("raft.log");
+
+    {
+        let mut store = UringLogStore::<TypeConfig>::open(&path).unwrap();
+        store.save_vote(&Vote::new(1, 2)).await?;
+        store.blocking_append((1..6).map(|i| blank_ent(1, 1, i))).await?;
+        store.truncate(log_id(1, 1, 4)).await?;
+        store.purge(log_id(1, 1, 1)).await?;
+    }
+
+    // Emulate a crash while writing the last record.
+    {
+        let mut f = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        f.write_all(&[100, 0, 0, 0, b'{']).unwrap();
+    }
+
+    let mut store = UringLogStore::<TypeConfig>::open(&path).unwrap();
+
+    assert_eq!(Some(Vote::new(1, 2)), store.read_vote().await?);
+
+    let state = store.get_log_state().await?;
+    assert_eq!(Some(log_id(1, 1, 1)), state.last_purged_log_id);
+    assert_eq!(Some(log_id(1, 1, 3)), state.last_log_id);
+
+    let logs = store.try_get_log_entries(0..10).await?;
+    let log_ids = logs.iter().map(|ent| ent.log_id).collect::<Vec<_>>();
+    assert_eq!(vec![log_id(1, 1, 2), log_id(1, 1, 3)], log_ids);
+
+    let stats = store.stats().await?.unwrap();
+    let bytes = logs.iter().map(|ent| serde_json::to_vec(ent).unwrap().len() as u64).sum::<u64>();
+    assert_eq!(2, stats.entries);
+    assert_eq!(bytes, stats.bytes, "the size is maintained by truncate and purge");
+
+    store.blocking_append([blank_ent(1, 1, 4)]).await?;
+    assert_eq!(Some(log_id(1, 1, 4)), store.get_log_state().await?.last_log_id);
+
+    Ok(())
+}
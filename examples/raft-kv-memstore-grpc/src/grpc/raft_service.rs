@@ -89,8 +89,10 @@ impl RaftService for RaftServiceImpl {
             .await
             .map_err(|e| Status::internal(format!("Append entries operation failed: {}", e)))?;
 
+        let resp = pb::AppendEntriesResponse::try_from(append_resp)?;
+
         debug!("Append entries request processed successfully");
-        Ok(Response::new(append_resp.into()))
+        Ok(Response::new(resp))
     }
 
     /// Handles snapshot installation requests for state transfer using streaming.
@@ -122,14 +124,15 @@ impl RaftService for RaftServiceImpl {
 
             vote = meta.vote.unwrap();
 
-            snapshot_meta = SnapshotMeta {
-                last_log_id: meta.last_log_id.map(|log_id| log_id.into()),
-                last_membership: StoredMembership::new(
+            snapshot_meta = SnapshotMeta::new(
+                meta.last_log_id.map(|log_id| log_id.into()),
+                StoredMembership::new(
                     meta.last_membership_log_id.map(|x| x.into()),
                     meta.last_membership.unwrap().into(),
                 ),
-                snapshot_id: meta.snapshot_id,
-            };
+                meta.snapshot_id,
+            )
+            .with_version(meta.version);
         }
 
         // Collect snapshot data
@@ -153,6 +156,10 @@ impl RaftService for RaftServiceImpl {
             .await
             .map_err(|e| Status::internal(format!("Snapshot installation failed: {}", e)))?;
 
+        if let Some(reason) = &snapshot_resp.malformed {
+            return Err(Status::invalid_argument(format!("Malformed snapshot: {}", reason)));
+        }
+
         debug!("Streaming snapshot installation request processed successfully");
         Ok(Response::new(pb::SnapshotResponse {
             vote: Some(snapshot_resp.vote),
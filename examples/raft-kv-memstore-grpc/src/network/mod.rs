@@ -102,6 +102,7 @@ impl RaftNetworkV2<TypeConfig> for NetworkConnection {
                 last_membership_log_id: meta.last_membership.log_id().map(|log_id| log_id.into()),
                 last_membership: Some(meta.last_membership.membership().clone().into()),
                 snapshot_id: meta.snapshot_id.to_string(),
+                version: meta.version(),
             })),
         };
 
@@ -121,11 +122,10 @@ impl RaftNetworkV2<TypeConfig> for NetworkConnection {
 
         let message = response.into_inner();
 
-        Ok(SnapshotResponse {
-            vote: message
-                .vote
-                .ok_or_else(|| NetworkError::new(&AnyError::error("Missing `vote` in snapshot response")))?,
-        })
+        let vote = message
+            .vote
+            .ok_or_else(|| NetworkError::new(&AnyError::error("Missing `vote` in snapshot response")))?;
+        Ok(SnapshotResponse::new(vote))
     }
 
     async fn vote(&mut self, req: VoteRequest, _option: RPCOption) -> Result<VoteResponse, RPCError> {
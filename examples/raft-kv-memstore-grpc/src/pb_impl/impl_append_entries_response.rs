@@ -1,3 +1,5 @@
+use tonic::Status;
+
 use crate::pb;
 use crate::typ::AppendEntriesResponse;
 
@@ -19,9 +21,12 @@ impl From<pb::AppendEntriesResponse> for AppendEntriesResponse {
     }
 }
 
-impl From<AppendEntriesResponse> for pb::AppendEntriesResponse {
-    fn from(r: AppendEntriesResponse) -> Self {
-        match r {
+/// A malformed request has no protobuf response: it is returned as an `invalid_argument` status.
+impl TryFrom<AppendEntriesResponse> for pb::AppendEntriesResponse {
+    type Error = Status;
+
+    fn try_from(r: AppendEntriesResponse) -> Result<Self, Self::Error> {
+        let resp = match r {
             AppendEntriesResponse::Success => pb::AppendEntriesResponse {
                 rejected_by: None,
                 conflict: false,
@@ -42,6 +47,13 @@ impl From<AppendEntriesResponse> for pb::AppendEntriesResponse {
                 conflict: false,
                 last_log_id: None,
             },
-        }
+            AppendEntriesResponse::Malformed(reason) => {
+                return Err(Status::invalid_argument(format!(
+                    "Malformed append entries request: {}",
+                    reason
+                )));
+            }
+        };
+        Ok(resp)
     }
 }
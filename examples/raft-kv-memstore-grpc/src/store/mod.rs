@@ -65,11 +65,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<StateMachineStore> {
             format!("--{}", snapshot_idx)
         };
 
-        let meta = SnapshotMeta {
-            last_log_id: last_applied,
-            last_membership,
-            snapshot_id,
-        };
+        let meta = SnapshotMeta::new(last_applied, last_membership, snapshot_id);
 
         let stored = StoredSnapshot {
             meta: meta.clone(),
@@ -117,11 +117,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<StateMachineStore> {
         // Users can design their own logic for this like using uuid.
         self.storage.write(&snapshot_id, encode(&data)).await.unwrap();
 
-        let meta = SnapshotMeta {
-            last_log_id: last_applied_log,
-            last_membership,
-            snapshot_id: snapshot_id.clone(),
-        };
+        let meta = SnapshotMeta::new(last_applied_log, last_membership, snapshot_id.clone());
 
         let snapshot = StoredSnapshot {
             meta: meta.clone(),
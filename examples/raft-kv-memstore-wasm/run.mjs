@@ -0,0 +1,5 @@
+// Run the example built with `wasm-pack build --target nodejs`.
+import pkg from "./pkg/raft_kv_memstore_wasm.js";
+
+const output = await pkg.run();
+console.log(output);
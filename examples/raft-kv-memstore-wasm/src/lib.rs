@@ -0,0 +1,85 @@
+#![allow(clippy::uninlined_format_args)]
+#![deny(unused_qualifications)]
+
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use openraft::async_runtime::watch::WatchReceiver;
+use openraft::Config;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+use crate::network::Network;
+use crate::runtime::WasmRuntime;
+use crate::store::Request;
+use crate::store::Response;
+
+pub mod network;
+pub mod runtime;
+
+/// Reuse the in-memory, non-`Send` log store and state machine of the singlethreaded example.
+#[path = "../../raft-kv-memstore-singlethreaded/src/store.rs"]
+pub mod store;
+
+pub type NodeId = u64;
+
+openraft::declare_raft_types!(
+    /// Declare the type configuration for example K/V store running in WebAssembly.
+    pub TypeConfig:
+        D = Request,
+        R = Response,
+        AsyncRuntime = WasmRuntime,
+);
+
+pub type LogStore = store::LogStore;
+pub type StateMachineStore = store::StateMachineStore;
+
+#[path = "../../utils/declare_types.rs"]
+pub mod typ;
+
+/// Start a single node cluster, write a key and return the value read from the state machine
+/// along with the metrics of the node.
+///
+/// It is called from JavaScript as `await run()`.
+#[wasm_bindgen]
+pub async fn run() -> Result<String, JsValue> {
+    let node_id = 1;
+
+    let config = Config {
+        heartbeat_interval: 100,
+        election_timeout_min: 300,
+        election_timeout_max: 600,
+        ..Default::default()
+    };
+    let config = Arc::new(config.validate().map_err(to_js)?);
+
+    let log_store = Rc::new(LogStore::default());
+    let state_machine_store = Rc::new(StateMachineStore::default());
+
+    let raft = typ::Raft::new(node_id, config, Network {}, log_store, state_machine_store.clone())
+        .await
+        .map_err(to_js)?;
+
+    raft.initialize(BTreeMap::from([(node_id, openraft::BasicNode::default())])).await.map_err(to_js)?;
+
+    // Electing a single node as leader takes an election timeout.
+    raft.wait(Some(Duration::from_secs(5)))
+        .current_leader(node_id, "leader elected")
+        .await
+        .map_err(to_js)?;
+
+    raft.client_write(Request::set("foo", "bar")).await.map_err(to_js)?;
+
+    let value = state_machine_store.state_machine.borrow().data.get("foo").cloned();
+    let metrics = raft.metrics().borrow_watched().clone();
+
+    raft.shutdown().await.map_err(to_js)?;
+
+    Ok(format!("foo={:?}, metrics: {}", value, metrics))
+}
+
+fn to_js(e: impl std::fmt::Display) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
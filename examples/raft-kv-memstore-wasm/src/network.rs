@@ -0,0 +1,62 @@
+use std::future::Future;
+use std::io;
+
+use openraft::error::ReplicationClosed;
+use openraft::error::Unreachable;
+use openraft::network::v2::RaftNetworkV2;
+use openraft::network::RPCOption;
+use openraft::BasicNode;
+use openraft::OptionalSend;
+use openraft::RaftNetworkFactory;
+
+use crate::typ::*;
+use crate::NodeId;
+use crate::TypeConfig;
+
+/// A network for a single node cluster, in which there is no other node to connect to.
+///
+/// A multi-node cluster in a browser would send RPCs with `fetch()` or a `WebSocket` here.
+pub struct Network {}
+
+pub struct Connection {
+    target: NodeId,
+}
+
+impl Connection {
+    fn unreachable(&self) -> Unreachable {
+        let err = io::Error::new(io::ErrorKind::NotFound, format!("no node {}", self.target));
+        Unreachable::new(&err)
+    }
+}
+
+impl RaftNetworkFactory<TypeConfig> for Network {
+    type Network = Connection;
+
+    async fn new_client(&mut self, target: NodeId, _node: &BasicNode) -> Self::Network {
+        Connection { target }
+    }
+}
+
+impl RaftNetworkV2<TypeConfig> for Connection {
+    async fn append_entries(
+        &mut self,
+        _req: AppendEntriesRequest,
+        _option: RPCOption,
+    ) -> Result<AppendEntriesResponse, RPCError> {
+        Err(RPCError::Unreachable(self.unreachable()))
+    }
+
+    async fn full_snapshot(
+        &mut self,
+        _vote: Vote,
+        _snapshot: Snapshot,
+        _cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse, StreamingError> {
+        Err(StreamingError::Unreachable(self.unreachable()))
+    }
+
+    async fn vote(&mut self, _req: VoteRequest, _option: RPCOption) -> Result<VoteResponse, RPCError> {
+        Err(RPCError::Unreachable(self.unreachable()))
+    }
+}
@@ -0,0 +1,616 @@
+//! An [`AsyncRuntime`] running on the JavaScript event loop.
+
+use std::future::Future;
+use std::time::Duration;
+
+use openraft::instant::Instant;
+use openraft::AsyncRuntime;
+use openraft::OptionalSend;
+use tokio::sync::oneshot;
+
+/// [`AsyncRuntime`] implementation for `wasm32-unknown-unknown`, upon `wasm-bindgen`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct WasmRuntime;
+
+impl AsyncRuntime for WasmRuntime {
+    type JoinError = oneshot::error::RecvError;
+    type JoinHandle<T: OptionalSend + 'static> = join_handle_mod::WasmJoinHandle<T>;
+    type Sleep = timer_mod::WasmSleep;
+    type Instant = instant_mod::WasmInstant;
+    type TimeoutError = timer_mod::Elapsed;
+    type Timeout<R, T: Future<Output = R> + OptionalSend> = timer_mod::WasmTimeout<T>;
+    type ThreadLocalRng = rand::rngs::ThreadRng;
+
+    #[inline]
+    fn spawn<T>(future: T) -> Self::JoinHandle<T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        // A task spawned with `spawn_local()` is always detached, its output is sent back to the
+        // join handle if the handle is not yet dropped.
+        let (tx, rx) = oneshot::channel();
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = tx.send(future.await);
+        });
+        join_handle_mod::WasmJoinHandle(rx)
+    }
+
+    #[inline]
+    fn sleep(duration: Duration) -> Self::Sleep {
+        timer_mod::WasmSleep::new(duration)
+    }
+
+    #[inline]
+    fn sleep_until(deadline: Self::Instant) -> Self::Sleep {
+        Self::sleep(deadline.saturating_duration_since(Self::Instant::now()))
+    }
+
+    #[inline]
+    fn timeout<R, F: Future<Output = R> + OptionalSend>(duration: Duration, future: F) -> Self::Timeout<R, F> {
+        timer_mod::WasmTimeout {
+            future,
+            sleep: Self::sleep(duration),
+        }
+    }
+
+    #[inline]
+    fn timeout_at<R, F: Future<Output = R> + OptionalSend>(deadline: Self::Instant, future: F) -> Self::Timeout<R, F> {
+        timer_mod::WasmTimeout {
+            future,
+            sleep: Self::sleep_until(deadline),
+        }
+    }
+
+    #[inline]
+    fn is_panic(_join_error: &Self::JoinError) -> bool {
+        // The output sender is dropped without sending only when the task panics.
+        true
+    }
+
+    #[inline]
+    fn thread_rng() -> Self::ThreadLocalRng {
+        rand::thread_rng()
+    }
+
+    type Mpsc = mpsc_mod::TokioMpsc;
+    type MpscUnbounded = mpsc_unbounded_mod::TokioMpscUnbounded;
+    type Watch = watch_mod::TokioWatch;
+    type Oneshot = oneshot_mod::TokioOneshot;
+    type Mutex<T: OptionalSend + 'static> = mutex_mod::TokioMutex<T>;
+}
+
+mod js {
+    //! Bindings of the JavaScript timer functions, available in both browsers and Node.js.
+
+    use wasm_bindgen::prelude::wasm_bindgen;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_name = setTimeout)]
+        pub fn set_timeout(handler: &js_sys::Function, timeout: i32) -> wasm_bindgen::JsValue;
+
+        #[wasm_bindgen(js_namespace = performance, js_name = now)]
+        pub fn performance_now() -> f64;
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod instant_mod {
+    //! Instant wrapper type and its trait impl.
+
+    use std::ops::Add;
+    use std::ops::AddAssign;
+    use std::ops::Sub;
+    use std::ops::SubAssign;
+    use std::time::Duration;
+    use std::time::SystemTime;
+    use std::time::UNIX_EPOCH;
+
+    use openraft::instant;
+
+    use super::js;
+
+    /// The time since the time origin of the page or process, read from `performance.now()`.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct WasmInstant(Duration);
+
+    impl Add<Duration> for WasmInstant {
+        type Output = Self;
+
+        #[inline]
+        fn add(self, rhs: Duration) -> Self::Output {
+            Self(self.0 + rhs)
+        }
+    }
+
+    impl AddAssign<Duration> for WasmInstant {
+        #[inline]
+        fn add_assign(&mut self, rhs: Duration) {
+            self.0 += rhs
+        }
+    }
+
+    impl Sub<Duration> for WasmInstant {
+        type Output = Self;
+
+        #[inline]
+        fn sub(self, rhs: Duration) -> Self::Output {
+            Self(self.0.saturating_sub(rhs))
+        }
+    }
+
+    impl Sub<Self> for WasmInstant {
+        type Output = Duration;
+
+        #[inline]
+        fn sub(self, rhs: Self) -> Self::Output {
+            self.0.saturating_sub(rhs.0)
+        }
+    }
+
+    impl SubAssign<Duration> for WasmInstant {
+        #[inline]
+        fn sub_assign(&mut self, rhs: Duration) {
+            self.0 = self.0.saturating_sub(rhs)
+        }
+    }
+
+    impl instant::Instant for WasmInstant {
+        #[inline]
+        fn now() -> Self {
+            Self(Duration::from_secs_f64(js::performance_now() / 1000.0))
+        }
+
+        /// `SystemTime::now()` panics on `wasm32-unknown-unknown`, read `Date.now()` instead.
+        #[inline]
+        fn system_now() -> SystemTime {
+            UNIX_EPOCH + Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod timer_mod {
+    //! Sleep and timeout types built upon `setTimeout()`, and their trait impl.
+
+    use std::error::Error;
+    use std::fmt;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::Context;
+    use std::task::Poll;
+    use std::time::Duration;
+
+    use wasm_bindgen_futures::JsFuture;
+
+    use super::js;
+
+    /// A future that resolves when the `setTimeout()` callback is called.
+    pub struct WasmSleep(JsFuture);
+
+    impl WasmSleep {
+        pub(crate) fn new(duration: Duration) -> Self {
+            let millis = duration.as_millis().min(i32::MAX as u128) as i32;
+            let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+                js::set_timeout(&resolve, millis);
+            });
+            Self(JsFuture::from(promise))
+        }
+    }
+
+    impl Future for WasmSleep {
+        type Output = ();
+
+        #[inline]
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut self.0).poll(cx).map(|_| ())
+        }
+    }
+
+    pin_project_lite::pin_project! {
+        /// A future that resolves to [`Elapsed`] if the inner future does not finish before the
+        /// sleep.
+        pub struct WasmTimeout<F> {
+            #[pin]
+            pub(crate) future: F,
+            pub(crate) sleep: WasmSleep,
+        }
+    }
+
+    impl<F> Future for WasmTimeout<F>
+    where F: Future
+    {
+        type Output = Result<F::Output, Elapsed>;
+
+        #[inline]
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+
+            if let Poll::Ready(output) = this.future.poll(cx) {
+                return Poll::Ready(Ok(output));
+            }
+
+            match Pin::new(this.sleep).poll(cx) {
+                Poll::Ready(_) => Poll::Ready(Err(Elapsed(()))),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    /// The error returned by [`WasmTimeout`] when the deadline has elapsed.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Elapsed(());
+
+    impl fmt::Display for Elapsed {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "deadline has elapsed")
+        }
+    }
+
+    impl Error for Elapsed {}
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod join_handle_mod {
+    //! Join handle wrapper type and its trait impl.
+
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::Context;
+    use std::task::Poll;
+
+    use tokio::sync::oneshot;
+
+    /// Receives the output of a task spawned with `spawn_local()`.
+    pub struct WasmJoinHandle<T>(pub(crate) oneshot::Receiver<T>);
+
+    impl<T> Future for WasmJoinHandle<T> {
+        type Output = Result<T, oneshot::error::RecvError>;
+
+        #[inline]
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut self.0).poll(cx)
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod oneshot_mod {
+    //! Oneshot channel wrapper types and their trait impl.
+
+    use openraft::type_config::async_runtime::oneshot;
+    use openraft::OptionalSend;
+    use tokio::sync::oneshot as tokio_oneshot;
+
+    pub struct TokioOneshot;
+
+    pub struct TokioOneshotSender<T>(tokio_oneshot::Sender<T>);
+
+    impl oneshot::Oneshot for TokioOneshot {
+        type Sender<T: OptionalSend> = TokioOneshotSender<T>;
+        type Receiver<T: OptionalSend> = tokio_oneshot::Receiver<T>;
+        type ReceiverError = tokio_oneshot::error::RecvError;
+
+        #[inline]
+        fn channel<T>() -> (Self::Sender<T>, Self::Receiver<T>)
+        where T: OptionalSend {
+            let (tx, rx) = tokio_oneshot::channel();
+            let tx_wrapper = TokioOneshotSender(tx);
+
+            (tx_wrapper, rx)
+        }
+    }
+
+    impl<T> oneshot::OneshotSender<T> for TokioOneshotSender<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn send(self, t: T) -> Result<(), T> {
+            self.0.send(t)
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod mpsc_mod {
+    //! MPSC channel wrapper types and their trait impl.
+
+    use std::future::Future;
+
+    use futures::TryFutureExt;
+    use openraft::async_runtime::Mpsc;
+    use openraft::async_runtime::MpscReceiver;
+    use openraft::async_runtime::MpscSender;
+    use openraft::async_runtime::MpscWeakSender;
+    use openraft::async_runtime::SendError;
+    use openraft::async_runtime::TryRecvError;
+    use openraft::OptionalSend;
+    use tokio::sync::mpsc as tokio_mpsc;
+
+    pub struct TokioMpsc;
+
+    pub struct TokioMpscSender<T>(tokio_mpsc::Sender<T>);
+
+    impl<T> Clone for TokioMpscSender<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    pub struct TokioMpscReceiver<T>(tokio_mpsc::Receiver<T>);
+
+    pub struct TokioMpscWeakSender<T>(tokio_mpsc::WeakSender<T>);
+
+    impl<T> Clone for TokioMpscWeakSender<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    impl Mpsc for TokioMpsc {
+        type Sender<T: OptionalSend> = TokioMpscSender<T>;
+        type Receiver<T: OptionalSend> = TokioMpscReceiver<T>;
+        type WeakSender<T: OptionalSend> = TokioMpscWeakSender<T>;
+
+        #[inline]
+        fn channel<T: OptionalSend>(buffer: usize) -> (Self::Sender<T>, Self::Receiver<T>) {
+            let (tx, rx) = tokio_mpsc::channel(buffer);
+            let tx_wrapper = TokioMpscSender(tx);
+            let rx_wrapper = TokioMpscReceiver(rx);
+
+            (tx_wrapper, rx_wrapper)
+        }
+    }
+
+    impl<T> MpscSender<TokioMpsc, T> for TokioMpscSender<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn send(&self, msg: T) -> impl Future<Output = Result<(), SendError<T>>> {
+            self.0.send(msg).map_err(|e| SendError(e.0))
+        }
+
+        #[inline]
+        fn downgrade(&self) -> <TokioMpsc as Mpsc>::WeakSender<T> {
+            let inner = self.0.downgrade();
+            TokioMpscWeakSender(inner)
+        }
+    }
+
+    impl<T> MpscReceiver<T> for TokioMpscReceiver<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn recv(&mut self) -> impl Future<Output = Option<T>> {
+            self.0.recv()
+        }
+
+        #[inline]
+        fn try_recv(&mut self) -> Result<T, TryRecvError> {
+            self.0.try_recv().map_err(|e| match e {
+                tokio_mpsc::error::TryRecvError::Empty => TryRecvError::Empty,
+                tokio_mpsc::error::TryRecvError::Disconnected => TryRecvError::Disconnected,
+            })
+        }
+    }
+
+    impl<T> MpscWeakSender<TokioMpsc, T> for TokioMpscWeakSender<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn upgrade(&self) -> Option<<TokioMpsc as Mpsc>::Sender<T>> {
+            self.0.upgrade().map(TokioMpscSender)
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod mpsc_unbounded_mod {
+    //! Unbounded MPSC channel wrapper types and their trait impl.
+
+    use openraft::type_config::async_runtime::mpsc_unbounded;
+    use openraft::OptionalSend;
+    use tokio::sync::mpsc as tokio_mpsc;
+
+    pub struct TokioMpscUnbounded;
+
+    pub struct TokioMpscUnboundedSender<T>(tokio_mpsc::UnboundedSender<T>);
+
+    impl<T> Clone for TokioMpscUnboundedSender<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    pub struct TokioMpscUnboundedReceiver<T>(tokio_mpsc::UnboundedReceiver<T>);
+
+    pub struct TokioMpscUnboundedWeakSender<T>(tokio_mpsc::WeakUnboundedSender<T>);
+
+    impl<T> Clone for TokioMpscUnboundedWeakSender<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    impl mpsc_unbounded::MpscUnbounded for TokioMpscUnbounded {
+        type Sender<T: OptionalSend> = TokioMpscUnboundedSender<T>;
+        type Receiver<T: OptionalSend> = TokioMpscUnboundedReceiver<T>;
+        type WeakSender<T: OptionalSend> = TokioMpscUnboundedWeakSender<T>;
+
+        #[inline]
+        fn channel<T: OptionalSend>() -> (Self::Sender<T>, Self::Receiver<T>) {
+            let (tx, rx) = tokio_mpsc::unbounded_channel();
+            let tx_wrapper = TokioMpscUnboundedSender(tx);
+            let rx_wrapper = TokioMpscUnboundedReceiver(rx);
+
+            (tx_wrapper, rx_wrapper)
+        }
+    }
+
+    impl<T> mpsc_unbounded::MpscUnboundedSender<TokioMpscUnbounded, T> for TokioMpscUnboundedSender<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn send(&self, msg: T) -> Result<(), mpsc_unbounded::SendError<T>> {
+            self.0.send(msg).map_err(|e| mpsc_unbounded::SendError(e.0))
+        }
+
+        #[inline]
+        fn downgrade(&self) -> <TokioMpscUnbounded as mpsc_unbounded::MpscUnbounded>::WeakSender<T> {
+            let inner = self.0.downgrade();
+            TokioMpscUnboundedWeakSender(inner)
+        }
+    }
+
+    impl<T> mpsc_unbounded::MpscUnboundedReceiver<T> for TokioMpscUnboundedReceiver<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        async fn recv(&mut self) -> Option<T> {
+            self.0.recv().await
+        }
+
+        #[inline]
+        fn try_recv(&mut self) -> Result<T, mpsc_unbounded::TryRecvError> {
+            self.0.try_recv().map_err(|e| match e {
+                tokio_mpsc::error::TryRecvError::Empty => mpsc_unbounded::TryRecvError::Empty,
+                tokio_mpsc::error::TryRecvError::Disconnected => mpsc_unbounded::TryRecvError::Disconnected,
+            })
+        }
+    }
+
+    impl<T> mpsc_unbounded::MpscUnboundedWeakSender<TokioMpscUnbounded, T> for TokioMpscUnboundedWeakSender<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn upgrade(&self) -> Option<<TokioMpscUnbounded as mpsc_unbounded::MpscUnbounded>::Sender<T>> {
+            self.0.upgrade().map(TokioMpscUnboundedSender)
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod watch_mod {
+    //! Watch channel wrapper types and their trait impl.
+
+    use std::ops::Deref;
+
+    use openraft::async_runtime::watch::RecvError;
+    use openraft::async_runtime::watch::SendError;
+    use openraft::type_config::async_runtime::watch;
+    use openraft::OptionalSend;
+    use openraft::OptionalSync;
+    use tokio::sync::watch as tokio_watch;
+
+    pub struct TokioWatch;
+    pub struct TokioWatchSender<T>(tokio_watch::Sender<T>);
+    pub struct TokioWatchReceiver<T>(tokio_watch::Receiver<T>);
+    pub struct TokioWatchRef<'a, T>(tokio_watch::Ref<'a, T>);
+
+    impl watch::Watch for TokioWatch {
+        type Sender<T: OptionalSend + OptionalSync> = TokioWatchSender<T>;
+        type Receiver<T: OptionalSend + OptionalSync> = TokioWatchReceiver<T>;
+        type Ref<'a, T: OptionalSend + 'a> = TokioWatchRef<'a, T>;
+
+        #[inline]
+        fn channel<T: OptionalSend + OptionalSync>(init: T) -> (Self::Sender<T>, Self::Receiver<T>) {
+            let (tx, rx) = tokio_watch::channel(init);
+            let tx_wrapper = TokioWatchSender(tx);
+            let rx_wrapper = TokioWatchReceiver(rx);
+
+            (tx_wrapper, rx_wrapper)
+        }
+    }
+
+    impl<T> watch::WatchSender<TokioWatch, T> for TokioWatchSender<T>
+    where T: OptionalSend + OptionalSync
+    {
+        #[inline]
+        fn send(&self, value: T) -> Result<(), SendError<T>> {
+            self.0.send(value).map_err(|e| SendError(e.0))
+        }
+
+        #[inline]
+        fn send_if_modified<F>(&self, modify: F) -> bool
+        where F: FnOnce(&mut T) -> bool {
+            self.0.send_if_modified(modify)
+        }
+
+        #[inline]
+        fn borrow_watched(&self) -> <TokioWatch as watch::Watch>::Ref<'_, T> {
+            let inner = self.0.borrow();
+            TokioWatchRef(inner)
+        }
+    }
+
+    impl<T> Clone for TokioWatchReceiver<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    impl<T> watch::WatchReceiver<TokioWatch, T> for TokioWatchReceiver<T>
+    where T: OptionalSend + OptionalSync
+    {
+        #[inline]
+        async fn changed(&mut self) -> Result<(), RecvError> {
+            self.0.changed().await.map_err(|_| RecvError(()))
+        }
+
+        #[inline]
+        fn borrow_watched(&self) -> <TokioWatch as watch::Watch>::Ref<'_, T> {
+            TokioWatchRef(self.0.borrow())
+        }
+    }
+
+    impl<T> Deref for TokioWatchRef<'_, T> {
+        type Target = T;
+
+        #[inline]
+        fn deref(&self) -> &Self::Target {
+            self.0.deref()
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod mutex_mod {
+    //! Mutex wrapper type and its trait impl.
+
+    use std::future::Future;
+
+    use openraft::type_config::async_runtime::mutex;
+    use openraft::OptionalSend;
+
+    pub struct TokioMutex<T>(tokio::sync::Mutex<T>);
+
+    impl<T> mutex::Mutex<T> for TokioMutex<T>
+    where T: OptionalSend + 'static
+    {
+        type Guard<'a> = tokio::sync::MutexGuard<'a, T>;
+
+        #[inline]
+        fn new(value: T) -> Self {
+            TokioMutex(tokio::sync::Mutex::new(value))
+        }
+
+        #[inline]
+        fn lock(&self) -> impl Future<Output = Self::Guard<'_>> + OptionalSend {
+            self.0.lock()
+        }
+    }
+}
@@ -94,11 +94,7 @@ impl RaftSnapshotBuilder<TypeConfig> for StateMachineStore {
             format!("--{}", self.snapshot_idx)
         };
 
-        let meta = SnapshotMeta {
-            last_log_id: last_applied_log,
-            last_membership,
-            snapshot_id,
-        };
+        let meta = SnapshotMeta::new(last_applied_log, last_membership, snapshot_id);
 
         let snapshot = StoredSnapshot {
             meta: meta.clone(),
@@ -135,11 +135,7 @@ impl RaftSnapshotBuilder<TypeConfig> for RocksStateMachine {
             format!("--{}", snapshot_idx)
         };
 
-        let meta = SnapshotMeta {
-            last_log_id: last_applied_log,
-            last_membership,
-            snapshot_id,
-        };
+        let meta = SnapshotMeta::new(last_applied_log, last_membership, snapshot_id);
 
         let snapshot = RocksSnapshot {
             meta: meta.clone(),
@@ -3,10 +3,21 @@ use openraft::testing::log::Suite;
 use openraft::StorageError;
 use tempfile::TempDir;
 
+use openraft_kv::KvRequest;
+use openraft_kv::KvResponse;
+use openraft_kv::KvStateMachine;
+
 use crate::log_store::RocksLogStore;
 use crate::RocksStateMachine;
 use crate::TypeConfig;
 
+openraft::declare_raft_types!(
+    /// A type config to run the `openraft-kv` state machine on a rocksdb log store.
+    pub KvTypeConfig:
+        D = KvRequest,
+        R = KvResponse,
+);
+
 struct RocksBuilder {}
 
 impl StoreBuilder<TypeConfig, RocksLogStore<TypeConfig>, RocksStateMachine, TempDir> for RocksBuilder {
@@ -22,3 +33,25 @@ pub async fn test_rocks_store() -> Result<(), StorageError<TypeConfig>> {
     Suite::test_all(RocksBuilder {}).await?;
     Ok(())
 }
+
+struct RocksKvBuilder {}
+
+impl StoreBuilder<KvTypeConfig, RocksLogStore<KvTypeConfig>, KvStateMachine<KvTypeConfig>, TempDir>
+    for RocksKvBuilder
+{
+    async fn build(
+        &self,
+    ) -> Result<(TempDir, RocksLogStore<KvTypeConfig>, KvStateMachine<KvTypeConfig>), StorageError<KvTypeConfig>>
+    {
+        let td = TempDir::new().expect("couldn't create temp dir");
+        let (log_store, _sm) = crate::new(td.path()).await;
+        Ok((td, log_store, KvStateMachine::new()))
+    }
+}
+
+/// The rocksdb log store works with the `openraft-kv` state machine.
+#[tokio::test]
+pub async fn test_rocks_log_store_with_kv_state_machine() -> Result<(), StorageError<KvTypeConfig>> {
+    Suite::test_all(RocksKvBuilder {}).await?;
+    Ok(())
+}
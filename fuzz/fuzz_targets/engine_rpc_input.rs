@@ -0,0 +1,12 @@
+//! Feed the AppendEntries, Vote and InstallSnapshot requests chosen by the fuzzer input to a
+//! follower `Engine`, see [`openraft::testing::fuzz::engine_rpc_input()`].
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Err(e) = openraft::testing::fuzz::engine_rpc_input(data) {
+        panic!("{}", e);
+    }
+});
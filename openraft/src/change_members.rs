@@ -26,6 +26,15 @@ where C: RaftTypeConfig
     /// Replace voter ids with a new set. The node of every new voter has to already be a learner.
     ReplaceAllVoters(BTreeSet<C::NodeId>),
 
+    /// Replace voter ids with the last set, going through the other sets as intermediate configs.
+    ///
+    /// The first step proposes a **joint** config of the current voters, every intermediate set
+    /// and the last set, e.g., `[c_old, c_mid, c_new]`, and the second step proposes the uniform
+    /// config of the last set. Thus a large reconfiguration takes only two quorum transitions,
+    /// while a quorum of every set has to accept the joint config. The node of every voter has to
+    /// already be a learner.
+    ReplaceAllVotersVia(Vec<BTreeSet<C::NodeId>>),
+
     /// Add nodes to membership, as learners.
     ///
     /// it **WONT** replace existing node.
@@ -44,6 +53,15 @@ where C: RaftTypeConfig
     /// See: [Update-Node](`crate::docs::cluster_control::dynamic_membership#update-node`)
     SetNodes(BTreeMap<C::NodeId, C::Node>),
 
+    /// Replace the nodes of existing voters or learners, without changing the voter or learner
+    /// sets.
+    ///
+    /// Every node has to already be in the membership config, otherwise it returns
+    /// [`error::NodeNotFound`](`crate::error::NodeNotFound`) error. The same brain split risk as
+    /// `SetNodes` applies.
+    /// See: [Update-Node](`crate::docs::cluster_control::dynamic_membership#update-node`)
+    UpdateNodes(BTreeMap<C::NodeId, C::Node>),
+
     /// Remove nodes from membership.
     ///
     /// If a node is still a voter, it returns
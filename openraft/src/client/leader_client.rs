@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use crate::error::CheckIsLeaderError;
+use crate::error::ClientWriteError;
+use crate::error::ForwardToLeader;
+use crate::error::RaftError;
+use crate::raft::ClientWriteResponse;
+use crate::raft::ClientWriteResult;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::ResponderReceiverOf;
+use crate::OptionalSend;
+use crate::Raft;
+use crate::RaftTypeConfig;
+use crate::TryAsRef;
+
+/// The default max number of times a request is retried on another target by
+/// [`LeaderClient::send()`].
+const DEFAULT_MAX_RETRIES: usize = 3;
+
+/// An error returned by [`LeaderClient::send()`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LeaderClientError<E>
+where E: Error
+{
+    /// There is no target to send the request to.
+    #[error("no target to send the request to")]
+    NoTarget,
+
+    /// The error returned by the last request.
+    #[error(transparent)]
+    Request(E),
+}
+
+/// Builds a target for a node that is not yet known by a [`LeaderClient`].
+type Connect<C, T> =
+    Arc<dyn Fn(&<C as RaftTypeConfig>::NodeId, &<C as RaftTypeConfig>::Node) -> Option<T> + Send + Sync + 'static>;
+
+struct ClientState<C, T>
+where C: RaftTypeConfig
+{
+    /// The targets to send requests to, by node id.
+    targets: BTreeMap<C::NodeId, T>,
+
+    /// The last known leader.
+    leader: Option<C::NodeId>,
+}
+
+/// Sends requests to the leader of a cluster, following [`ForwardToLeader`] errors.
+///
+/// A target `T` is what a request is sent with, e.g., a [`Raft`] handle in the same process, or an
+/// application defined client of a remote node. A request is sent to the last known leader, or to
+/// the first target if the leader is unknown. The leader hint is updated by every response:
+///
+/// - A successful response makes the target the leader.
+/// - A [`ForwardToLeader`] error that tells the new leader makes it the leader, and the request is
+///   sent to it. If the new leader is not a known target, it is built with the function set by
+///   [`LeaderClient::with_connect()`], from the node in the error.
+/// - A [`ForwardToLeader`] error that does not tell the new leader, e.g., when the cluster is
+///   electing, clears the hint, and the request is sent to the next target.
+///
+/// A request is retried at most [`LeaderClient::with_max_retries()`] times. Any other error is
+/// returned at once.
+pub struct LeaderClient<C, T>
+where C: RaftTypeConfig
+{
+    state: Arc<RwLock<ClientState<C, T>>>,
+    connect: Option<Connect<C, T>>,
+    max_retries: usize,
+}
+
+impl<C, T> Clone for LeaderClient<C, T>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            connect: self.connect.clone(),
+            max_retries: self.max_retries,
+        }
+    }
+}
+
+impl<C, T> LeaderClient<C, T>
+where
+    C: RaftTypeConfig,
+    T: Clone,
+{
+    /// Create a client that sends requests to `targets`, by node id.
+    pub fn new(targets: impl IntoIterator<Item = (C::NodeId, T)>) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(ClientState {
+                targets: targets.into_iter().collect(),
+                leader: None,
+            })),
+            connect: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+
+    /// Set the max number of times a request is retried on another target.
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the function to build a target for a leader that is not a known target, from the node
+    /// in a [`ForwardToLeader`] error.
+    pub fn with_connect(mut self, connect: impl Fn(&C::NodeId, &C::Node) -> Option<T> + Send + Sync + 'static) -> Self {
+        self.connect = Some(Arc::new(connect));
+        self
+    }
+
+    /// Add or replace the target of a node.
+    pub fn add_target(&self, id: C::NodeId, target: T) {
+        self.state.write().unwrap().targets.insert(id, target);
+    }
+
+    /// Remove the target of a node, and clear the leader hint if it is the leader.
+    pub fn remove_target(&self, id: &C::NodeId) -> Option<T> {
+        let mut state = self.state.write().unwrap();
+        if state.leader.as_ref() == Some(id) {
+            state.leader = None;
+        }
+        state.targets.remove(id)
+    }
+
+    /// Return the ids of the known targets.
+    pub fn target_ids(&self) -> Vec<C::NodeId> {
+        self.state.read().unwrap().targets.keys().cloned().collect()
+    }
+
+    /// Return the last known leader.
+    pub fn leader(&self) -> Option<C::NodeId> {
+        self.state.read().unwrap().leader.clone()
+    }
+
+    /// Set or clear the leader hint.
+    pub fn set_leader(&self, leader: Option<C::NodeId>) {
+        self.state.write().unwrap().leader = leader;
+    }
+
+    /// Send a request with `send` to the leader, and retry it on the new leader or the next target
+    /// if it fails with a [`ForwardToLeader`] error.
+    ///
+    /// `send` is called with the node id and the target to send the request to.
+    pub async fn send<R, E, F, Fut>(&self, mut send: F) -> Result<R, LeaderClientError<E>>
+    where
+        E: Error + TryAsRef<ForwardToLeader<C>>,
+        F: FnMut(C::NodeId, T) -> Fut,
+        Fut: Future<Output = Result<R, E>>,
+    {
+        let (mut id, mut target) = self.first_target().ok_or(LeaderClientError::NoTarget)?;
+        let mut retries = 0;
+
+        loop {
+            let err = match send(id.clone(), target).await {
+                Ok(x) => {
+                    self.set_leader(Some(id));
+                    return Ok(x);
+                }
+                Err(e) => e,
+            };
+
+            let Some(forward) = err.try_as_ref() else {
+                return Err(LeaderClientError::Request(err));
+            };
+
+            tracing::debug!(
+                "request to {} is forwarded to {:?}, retries: {}",
+                id,
+                forward.leader_id,
+                retries
+            );
+
+            let next = match &forward.leader_id {
+                Some(leader_id) => {
+                    self.set_leader(Some(leader_id.clone()));
+                    self.target_of(leader_id, forward.leader_node.as_ref()).map(|t| (leader_id.clone(), t))
+                }
+                None => {
+                    self.set_leader(None);
+                    self.target_after(&id)
+                }
+            };
+
+            let Some(next) = next else {
+                return Err(LeaderClientError::Request(err));
+            };
+
+            if retries >= self.max_retries {
+                return Err(LeaderClientError::Request(err));
+            }
+
+            retries += 1;
+            (id, target) = next;
+        }
+    }
+
+    /// The leader if it is a known target, otherwise the first target.
+    fn first_target(&self) -> Option<(C::NodeId, T)> {
+        let state = self.state.read().unwrap();
+
+        if let Some(leader) = &state.leader {
+            if let Some(t) = state.targets.get(leader) {
+                return Some((leader.clone(), t.clone()));
+            }
+        }
+
+        state.targets.iter().next().map(|(id, t)| (id.clone(), t.clone()))
+    }
+
+    /// The target after `id`, wrapping around to the first one.
+    fn target_after(&self, id: &C::NodeId) -> Option<(C::NodeId, T)> {
+        let state = self.state.read().unwrap();
+
+        let mut after = state.targets.range(id..).filter(|(k, _)| *k != id);
+        let (k, t) = after.next().or_else(|| state.targets.iter().next())?;
+        Some((k.clone(), t.clone()))
+    }
+
+    /// The target of `id`, built from `node` with the connect function if it is not known.
+    fn target_of(&self, id: &C::NodeId, node: Option<&C::Node>) -> Option<T> {
+        if let Some(t) = self.state.read().unwrap().targets.get(id) {
+            return Some(t.clone());
+        }
+
+        let target = (self.connect.as_ref()?)(id, node?)?;
+        self.add_target(id.clone(), target.clone());
+        Some(target)
+    }
+}
+
+impl<C> LeaderClient<C, Raft<C>>
+where C: RaftTypeConfig
+{
+    /// Submit a mutating client request to the leader, see [`Raft::client_write()`].
+    pub async fn client_write<E>(
+        &self,
+        app_data: C::D,
+    ) -> Result<ClientWriteResponse<C>, LeaderClientError<RaftError<C, ClientWriteError<C>>>>
+    where
+        C::D: Clone,
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        self.send(|_id, raft| {
+            let app_data = app_data.clone();
+            async move { raft.client_write(app_data).await }
+        })
+        .await
+    }
+
+    /// Ensure a linearizable read on the leader, see [`Raft::ensure_linearizable()`].
+    ///
+    /// It returns the id of the leader, on which the state machine can be read, and the log id up
+    /// to which it is applied.
+    pub async fn ensure_linearizable(
+        &self,
+    ) -> Result<(C::NodeId, Option<LogIdOf<C>>), LeaderClientError<RaftError<C, CheckIsLeaderError<C>>>> {
+        self.send(|id, raft| async move { raft.ensure_linearizable().await.map(|log_id| (id, log_id)) })
+            .await
+    }
+}
+
+impl<C, T> fmt::Debug for LeaderClient<C, T>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.read().unwrap();
+        f.debug_struct("LeaderClient")
+            .field("targets", &state.targets.keys().collect::<Vec<_>>())
+            .field("leader", &state.leader)
+            .field("max_retries", &self.max_retries)
+            .finish()
+    }
+}
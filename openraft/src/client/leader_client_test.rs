@@ -0,0 +1,151 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use pretty_assertions::assert_eq;
+
+use crate::client::LeaderClient;
+use crate::client::LeaderClientError;
+use crate::engine::testing::UTConfig;
+use crate::error::ClientWriteError;
+use crate::error::Fatal;
+use crate::error::ForwardToLeader;
+use crate::error::RaftError;
+
+type C = UTConfig;
+type WriteError = RaftError<C, ClientWriteError<C>>;
+
+fn forward(leader_id: Option<u64>) -> WriteError {
+    RaftError::APIError(ClientWriteError::ForwardToLeader(ForwardToLeader {
+        leader_id,
+        leader_node: leader_id.map(|_| ()),
+    }))
+}
+
+/// A cluster whose leader is `leader`: other nodes respond with `ForwardToLeader` to `hint`.
+#[derive(Default)]
+struct MockCluster {
+    leader: u64,
+    hint: Option<u64>,
+    sent_to: Arc<Mutex<Vec<u64>>>,
+}
+
+impl MockCluster {
+    fn new(leader: u64, hint: Option<u64>) -> Self {
+        Self {
+            leader,
+            hint,
+            sent_to: Default::default(),
+        }
+    }
+
+    async fn send(&self, id: u64, target: &'static str) -> Result<&'static str, WriteError> {
+        self.sent_to.lock().unwrap().push(id);
+        if id == self.leader {
+            Ok(target)
+        } else {
+            Err(forward(self.hint))
+        }
+    }
+
+    fn sent_to(&self) -> Vec<u64> {
+        self.sent_to.lock().unwrap().clone()
+    }
+}
+
+#[tokio::test]
+async fn test_leader_client_no_target() -> anyhow::Result<()> {
+    let c = LeaderClient::<C, &'static str>::new([]);
+
+    let m = MockCluster::new(1, Some(1));
+    let res = c.send(|id, t| m.send(id, t)).await;
+    assert_eq!(Err(LeaderClientError::NoTarget), res);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_leader_client_follow_forward_to_leader() -> anyhow::Result<()> {
+    let c = LeaderClient::<C, &'static str>::new([(1, "n1"), (2, "n2"), (3, "n3")]);
+
+    tracing::info!("--- the leader is unknown, send to the first target and follow the hint");
+    {
+        let m = MockCluster::new(3, Some(3));
+        let res = c.send(|id, t| m.send(id, t)).await;
+        assert_eq!(Ok("n3"), res);
+        assert_eq!(vec![1, 3], m.sent_to());
+        assert_eq!(Some(3), c.leader());
+    }
+
+    tracing::info!("--- send to the known leader");
+    {
+        let m = MockCluster::new(3, Some(3));
+        let res = c.send(|id, t| m.send(id, t)).await;
+        assert_eq!(Ok("n3"), res);
+        assert_eq!(vec![3], m.sent_to());
+    }
+
+    tracing::info!("--- the new leader is unknown, try the next targets");
+    {
+        let m = MockCluster::new(2, None);
+        let res = c.send(|id, t| m.send(id, t)).await;
+        assert_eq!(Ok("n2"), res);
+        assert_eq!(vec![3, 1, 2], m.sent_to());
+        assert_eq!(Some(2), c.leader());
+    }
+
+    tracing::info!("--- a non-forward error is returned at once");
+    {
+        let sent = Arc::new(Mutex::new(0));
+        let res = c
+            .send(|_id, _t| {
+                *sent.lock().unwrap() += 1;
+                async { Err::<(), WriteError>(RaftError::Fatal(Fatal::Stopped)) }
+            })
+            .await;
+        assert_eq!(Err(LeaderClientError::Request(RaftError::Fatal(Fatal::Stopped))), res);
+        assert_eq!(1, *sent.lock().unwrap());
+    }
+
+    tracing::info!("--- stop retrying after max retries");
+    {
+        let c = c.with_max_retries(2);
+
+        let m = MockCluster::new(5, None);
+        let res = c.send(|id, t| m.send(id, t)).await;
+        assert_eq!(Err(LeaderClientError::Request(forward(None))), res);
+        assert_eq!(vec![2, 3, 1], m.sent_to());
+        assert_eq!(None, c.leader());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_leader_client_connect_new_leader() -> anyhow::Result<()> {
+    let c = LeaderClient::<C, &'static str>::new([(1, "n1")]);
+
+    tracing::info!("--- without connect, an unknown leader can not be reached");
+    {
+        let m = MockCluster::new(4, Some(4));
+        let res = c.send(|id, t| m.send(id, t)).await;
+        assert_eq!(Err(LeaderClientError::Request(forward(Some(4)))), res);
+        assert_eq!(Some(4), c.leader());
+    }
+
+    tracing::info!("--- connect to the unknown leader");
+    {
+        let c = c.with_connect(|id, _node| (*id == 4).then_some("n4"));
+
+        let m = MockCluster::new(4, Some(4));
+        let res = c.send(|id, t| m.send(id, t)).await;
+        assert_eq!(Ok("n4"), res);
+        assert_eq!(vec![1, 4], m.sent_to());
+        assert_eq!(vec![1, 4], c.target_ids());
+
+        // The leader is removed, the hint is cleared.
+        assert_eq!(Some("n4"), c.remove_target(&4));
+        assert_eq!(None, c.leader());
+    }
+
+    Ok(())
+}
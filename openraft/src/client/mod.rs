@@ -0,0 +1,20 @@
+//! Client side helpers to send requests to the leader of a cluster.
+//!
+//! - [`LeaderClient`] sends a request to the known leader among a set of targets, which are
+//!   [`Raft`](crate::Raft) handles in the same process or application defined network endpoints,
+//!   and follows [`ForwardToLeader`](crate::error::ForwardToLeader) errors to a new leader.
+//!
+//! ```ignore
+//! let client = LeaderClient::new([(1, raft1), (2, raft2), (3, raft3)]);
+//!
+//! let resp = client.client_write(req).await?;
+//! let (leader_id, read_log_id) = client.ensure_linearizable().await?;
+//! ```
+
+mod leader_client;
+
+#[cfg(test)]
+mod leader_client_test;
+
+pub use leader_client::LeaderClient;
+pub use leader_client::LeaderClientError;
@@ -0,0 +1,231 @@
+//! Data types written by openraft 0.7 and their upgrade to the current types.
+//!
+//! In openraft 0.7 a log id is `(term, index)` and a vote is `(term, node_id, committed)`. These
+//! are upgraded with a leader id built from the term and node id, via [`RaftLeaderId::new`].
+//! A log id written by 0.7 does not record the node id of its leader, `NodeId::default()` is used.
+//!
+//! Since openraft 0.7 stores `u64` terms, the current `Term` type must be able to be built from a
+//! `u64`.
+//!
+//! [`RaftLeaderId::new`]: crate::vote::RaftLeaderId::new
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::compat::Upgrade;
+use crate::vote::RaftLeaderId;
+use crate::vote::RaftLeaderIdExt;
+use crate::RaftTypeConfig;
+use crate::StoredMembership;
+
+/// Log id in openraft 0.7: `(term, index)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct LogId {
+    pub term: u64,
+    pub index: u64,
+}
+
+impl<C> Upgrade<crate::LogId<C>> for LogId
+where
+    C: RaftTypeConfig,
+    C::Term: From<u64>,
+{
+    fn upgrade(self) -> crate::LogId<C> {
+        let leader_id = C::LeaderId::new_committed(C::Term::from(self.term), C::NodeId::default());
+        crate::LogId::new(leader_id, self.index)
+    }
+}
+
+/// Vote in openraft 0.7.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(bound = "")]
+pub struct Vote<C>
+where C: RaftTypeConfig
+{
+    pub term: u64,
+    pub node_id: C::NodeId,
+    pub committed: bool,
+}
+
+impl<C> Upgrade<crate::Vote<C>> for Vote<C>
+where
+    C: RaftTypeConfig,
+    C::Term: From<u64>,
+{
+    fn upgrade(self) -> crate::Vote<C> {
+        crate::Vote {
+            leader_id: C::LeaderId::new(C::Term::from(self.term), self.node_id),
+            committed: self.committed,
+        }
+    }
+}
+
+/// Membership config in openraft 0.7, which has no node info.
+///
+/// The upgraded membership has a default `Node` for every node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(bound = "")]
+pub struct Membership<C>
+where C: RaftTypeConfig
+{
+    /// Voters of every config in a joint config.
+    pub configs: Vec<BTreeSet<C::NodeId>>,
+
+    /// Ids of all nodes, including voters and learners.
+    pub all_nodes: BTreeSet<C::NodeId>,
+}
+
+impl<C> Upgrade<crate::Membership<C>> for Membership<C>
+where
+    C: RaftTypeConfig,
+    C::Node: Default,
+{
+    fn upgrade(self) -> crate::Membership<C> {
+        let nodes = self.all_nodes.into_iter().map(|id| (id, C::Node::default())).collect::<BTreeMap<_, _>>();
+        crate::Membership::new_unchecked(self.configs, nodes)
+    }
+
+    fn try_upgrade(self) -> Result<crate::Membership<C>, (Self, &'static str)> {
+        if self.configs.iter().flatten().any(|id| !self.all_nodes.contains(id)) {
+            return Err((self, "a voter is not in all_nodes"));
+        }
+        Ok(self.upgrade())
+    }
+}
+
+/// `EffectiveMembership` in openraft 0.7, which is stored as [`StoredMembership`] now.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(bound = "")]
+pub struct EffectiveMembership<C>
+where C: RaftTypeConfig
+{
+    /// The id of the log that proposed this membership.
+    pub log_id: LogId,
+    pub membership: Membership<C>,
+}
+
+impl<C> Upgrade<StoredMembership<C>> for EffectiveMembership<C>
+where
+    C: RaftTypeConfig,
+    C::Term: From<u64>,
+    C::Node: Default,
+{
+    fn upgrade(self) -> StoredMembership<C> {
+        StoredMembership::new(Some(self.log_id.upgrade()), self.membership.upgrade())
+    }
+}
+
+/// Snapshot meta in openraft 0.7.
+///
+/// It does not store the membership included in the snapshot. Thus it can only be upgraded with a
+/// membership loaded from elsewhere, such as the state machine data, with
+/// [`upgrade_with_membership()`](Self::upgrade_with_membership).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct SnapshotMeta {
+    pub last_log_id: LogId,
+    pub snapshot_id: String,
+}
+
+impl SnapshotMeta {
+    /// Upgrade to the current [`SnapshotMeta`](crate::SnapshotMeta) with the last membership
+    /// included in the snapshot.
+    pub fn upgrade_with_membership<C>(self, last_membership: StoredMembership<C>) -> crate::SnapshotMeta<C>
+    where
+        C: RaftTypeConfig,
+        C::Term: From<u64>,
+    {
+        crate::SnapshotMeta::new(Some(self.last_log_id.upgrade()), last_membership, self.snapshot_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreeset;
+
+    use crate::compat::compat07;
+    use crate::compat::Compat;
+    use crate::compat::Upgrade;
+    use crate::engine::testing::UTConfig;
+    use crate::testing::log_id;
+    use crate::Membership;
+    use crate::StoredMembership;
+    use crate::Vote;
+
+    #[test]
+    fn test_upgrade_log_id() -> anyhow::Result<()> {
+        let old: compat07::LogId = serde_json::from_str(r#"{"term":3,"index":5}"#)?;
+        let new: crate::LogId<UTConfig> = old.upgrade();
+        assert_eq!(log_id(3, 0, 5), new);
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_vote() -> anyhow::Result<()> {
+        let old: compat07::Vote<UTConfig> = serde_json::from_str(r#"{"term":3,"node_id":2,"committed":true}"#)?;
+        let new: Vote<UTConfig> = old.upgrade();
+        assert_eq!(Vote::new_committed(3, 2), new);
+
+        let old: compat07::Vote<UTConfig> = serde_json::from_str(r#"{"term":4,"node_id":1,"committed":false}"#)?;
+        let new: Vote<UTConfig> = old.upgrade();
+        assert_eq!(Vote::new(4, 1), new);
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_membership() -> anyhow::Result<()> {
+        let s = r#"{"log_id":{"term":1,"index":2},"membership":{"configs":[[1,2]],"all_nodes":[1,2,3]}}"#;
+        let old: compat07::EffectiveMembership<UTConfig> = serde_json::from_str(s)?;
+        let new: StoredMembership<UTConfig> = old.upgrade();
+
+        assert_eq!(
+            StoredMembership::new(
+                Some(log_id(1, 0, 2)),
+                Membership::new_with_defaults(vec![btreeset! {1,2}], [3])
+            ),
+            new
+        );
+
+        let s = r#"{"configs":[[1,2]],"all_nodes":[1]}"#;
+        let old: compat07::Membership<UTConfig> = serde_json::from_str(s)?;
+        let res: Result<Membership<UTConfig>, _> = old.try_upgrade();
+        assert_eq!("a voter is not in all_nodes", res.unwrap_err().1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_snapshot_meta() -> anyhow::Result<()> {
+        let old: compat07::SnapshotMeta =
+            serde_json::from_str(r#"{"last_log_id":{"term":1,"index":9},"snapshot_id":"1-9-3"}"#)?;
+        let m = StoredMembership::new(
+            Some(log_id(1, 0, 2)),
+            Membership::new_with_defaults(vec![btreeset! {1}], []),
+        );
+        let new = old.upgrade_with_membership::<UTConfig>(m.clone());
+
+        assert_eq!(Some(log_id(1, 0, 9)), new.last_log_id);
+        assert_eq!(m, new.last_membership);
+        assert_eq!("1-9-3", new.snapshot_id);
+        Ok(())
+    }
+
+    /// Data in either format is read with `Compat`, and only the old format needs to be rewritten.
+    #[test]
+    fn test_compat_read_both() -> anyhow::Result<()> {
+        type C = Compat<compat07::Vote<UTConfig>, Vote<UTConfig>>;
+
+        let old: C = serde_json::from_str(r#"{"term":3,"node_id":2,"committed":true}"#)?;
+        assert!(old.is_old());
+        assert_eq!((Vote::new_committed(3, 2), true), old.upgrade_with_status());
+
+        let current = serde_json::to_string(&Vote::<UTConfig>::new_committed(3, 2))?;
+        let new: C = serde_json::from_str(&current)?;
+        assert!(!new.is_old());
+        assert_eq!((Vote::new_committed(3, 2), false), new.upgrade_with_status());
+        Ok(())
+    }
+}
@@ -1,6 +1,21 @@
 //! This mod is a upgrade helper that provides functionalities for a newer openraft application to
 //! read data written by an older application.
+//!
+//! To upgrade a cluster in place, on the first start after upgrading, an application decodes every
+//! stored vote, log id, membership and snapshot meta as a [`Compat`] of the old type in
+//! `compat07` (feature `compat-07`) and the current type, and writes back the ones that are in the
+//! old format:
+//!
+//! ```ignore
+//! let vote: Compat<compat07::Vote<C>, Vote<C>> = serde_json::from_slice(&stored)?;
+//! let (vote, is_old) = vote.upgrade_with_status();
+//! if is_old {
+//!     store.put(VOTE_KEY, serde_json::to_vec(&vote)?)?;
+//! }
+//! ```
 
+#[cfg(feature = "compat-07")]
+pub mod compat07;
 mod upgrade;
 
 pub use upgrade::Compat;
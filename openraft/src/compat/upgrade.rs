@@ -17,8 +17,7 @@ pub trait Upgrade<To> {
 /// and a newer type. It serves as an intermediate type container for newer programs to read old
 /// data.
 #[derive(Debug)]
-#[derive(serde::Deserialize, serde::Serialize)]
-#[serde(untagged)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(untagged))]
 pub enum Compat<From, To>
 where From: Upgrade<To>
 {
@@ -28,6 +27,24 @@ where From: Upgrade<To>
     New(To),
 }
 
+impl<From, To> Compat<From, To>
+where From: Upgrade<To>
+{
+    /// Returns `true` if the data is decoded from the older format.
+    pub fn is_old(&self) -> bool {
+        matches!(self, Self::Old(_))
+    }
+
+    /// Upgrade to `To` and return whether the data was in the older format.
+    ///
+    /// An application migrates its storage in place by writing back every value for which it
+    /// returns `true`.
+    pub fn upgrade_with_status(self) -> (To, bool) {
+        let is_old = self.is_old();
+        (self.upgrade(), is_old)
+    }
+}
+
 /// A compatible type can be upgraded to `To` if `From` can be upgraded to `To`.
 impl<From, To> Upgrade<To> for Compat<From, To>
 where From: Upgrade<To>
@@ -1,5 +1,6 @@
 //! Raft runtime configuration.
 
+use std::fmt;
 use std::ops::Deref;
 use std::str::FromStr;
 use std::sync::atomic::AtomicBool;
@@ -48,6 +49,36 @@ impl SnapshotPolicy {
     }
 }
 
+/// What a leader does after it commits a membership config in which it is not a voter.
+///
+/// See: [`Config::removed_leader_action`].
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum RemovedLeaderAction {
+    /// Convert to a learner at once. The remaining voters elect a new leader after the election
+    /// timeout.
+    StepDown,
+
+    /// Transfer the leadership to the most up-to-date voter in the new config, then convert to a
+    /// learner.
+    TransferLeader,
+
+    /// Transfer the leadership the same way as [`Self::TransferLeader`], then shut down this
+    /// node.
+    Shutdown,
+}
+
+impl fmt::Display for RemovedLeaderAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemovedLeaderAction::StepDown => write!(f, "step_down"),
+            RemovedLeaderAction::TransferLeader => write!(f, "transfer_leader"),
+            RemovedLeaderAction::Shutdown => write!(f, "shutdown"),
+        }
+    }
+}
+
 /// Parse number with unit such as 5.3 KB
 fn parse_bytes_with_unit(src: &str) -> Result<u64, ConfigError> {
     let res = byte_unit::Byte::from_str(src).map_err(|e| ConfigError::InvalidNumber {
@@ -85,6 +116,18 @@ fn parse_snapshot_policy(src: &str) -> Result<SnapshotPolicy, ConfigError> {
     Ok(SnapshotPolicy::LogsSinceLast(n_logs))
 }
 
+fn parse_removed_leader_action(src: &str) -> Result<RemovedLeaderAction, ConfigError> {
+    match src {
+        "step_down" => Ok(RemovedLeaderAction::StepDown),
+        "transfer_leader" => Ok(RemovedLeaderAction::TransferLeader),
+        "shutdown" => Ok(RemovedLeaderAction::Shutdown),
+        _ => Err(ConfigError::InvalidRemovedLeaderAction {
+            syntax: "step_down|transfer_leader|shutdown".to_string(),
+            invalid: src.to_string(),
+        }),
+    }
+}
+
 /// The runtime configuration for a Raft node.
 ///
 /// The default values used by this type should generally work well for Raft clusters which will
@@ -110,6 +153,7 @@ fn parse_snapshot_policy(src: &str) -> Result<SnapshotPolicy, ConfigError> {
 /// performance of your network will not cause election timeouts, but don't keep it so high that
 /// a real leader crash would cause prolonged downtime. See the Raft spec §5.6 for more details.
 #[derive(Clone, Debug, Parser)]
+#[derive(PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Config {
     /// The application specific name of this Raft cluster
@@ -180,10 +224,297 @@ pub struct Config {
     #[clap(long, default_value = "1000")]
     pub max_in_snapshot_log_to_keep: u64,
 
+    /// The max [`SnapshotMeta::version`] of a snapshot this node is able to install.
+    ///
+    /// A snapshot with a greater version, i.e., built by a node with a newer state machine data
+    /// format, is refused with [`SnapshotVersionUnsupported`]. The leader does not send the
+    /// refused snapshot to this node again; it sends the next snapshot it builds, which may be in
+    /// a format this node reads, or this node is upgraded by then.
+    /// `None`, the default, accepts a snapshot of any version.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`SnapshotMeta::version`]: crate::storage::SnapshotMeta::version()
+    /// [`SnapshotVersionUnsupported`]: crate::error::SnapshotVersionUnsupported
+    #[clap(long)]
+    pub max_snapshot_version: Option<u64>,
+
     /// The minimal number of applied logs to purge in a batch.
     #[clap(long, default_value = "1")]
     pub purge_batch_size: u64,
 
+    /// The max number of apply batches queued to the state machine worker.
+    ///
+    /// Committed entries are applied by a separate state machine worker, so that appending and
+    /// replicating logs do not wait for a slow state machine. When this many batches are queued,
+    /// entries committed later are merged into one batch, which is queued when a queued batch is
+    /// applied.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "16")]
+    pub apply_queue_size: u64,
+
+    /// The max number of committed entries passed to one [`RaftStateMachine::apply()`] call.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`RaftStateMachine::apply()`]: crate::storage::RaftStateMachine::apply
+    #[clap(long, default_value = "4096")]
+    pub apply_batch_size: u64,
+
+    /// The max delay in milliseconds to wait for [`apply_batch_size`] committed entries before
+    /// applying fewer of them.
+    ///
+    /// A state machine that batches its own writes may use it to receive larger batches, at the
+    /// cost of a higher apply latency. `0`, the default, applies committed entries at once.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`apply_batch_size`]: Self::apply_batch_size
+    #[clap(long, default_value = "0")]
+    pub apply_batch_max_delay: u64,
+
+    /// Whether the leader serves linearizable reads locally while its leader lease is valid.
+    ///
+    /// A follower does not vote for another candidate until `election_timeout_max` after it has
+    /// seen the leader. Thus, if a quorum has acknowledged a heartbeat sent at time `T`, no other
+    /// leader can be elected before `T + election_timeout_max`. Until then, minus
+    /// [`lease_read_max_clock_drift`], a read skips the quorum confirmation round trip. When the
+    /// lease is stale, a read confirms the leadership with a quorum as usual.
+    ///
+    /// It relies on bounded clock drift between nodes. It is disabled by default.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`lease_read_max_clock_drift`]: Self::lease_read_max_clock_drift
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub enable_lease_read: bool,
+
+    /// The max clock drift between nodes in milliseconds, by which a leader shortens its lease for
+    /// lease reads.
+    ///
+    /// It must be smaller than `election_timeout_max`.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "50")]
+    pub lease_read_max_clock_drift: u64,
+
+    /// The interval in milliseconds to poll [`RaftLogStorage::stats()`] for
+    /// [`RaftDataMetrics::log_stats`].
+    ///
+    /// `0` disables polling.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`RaftLogStorage::stats()`]: crate::storage::RaftLogStorage::stats
+    /// [`RaftDataMetrics::log_stats`]: crate::metrics::RaftDataMetrics::log_stats
+    #[clap(long, default_value = "1000")]
+    pub log_stats_interval: u64,
+
+    /// The upper bounds in milliseconds of the buckets of the RPC latency histograms in
+    /// [`RaftNetworkMetrics`], in ascending order, separated by comma.
+    ///
+    /// A latency greater than the last bound is counted in an extra overflow bucket.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`RaftNetworkMetrics`]: crate::metrics::RaftNetworkMetrics
+    #[clap(long, value_delimiter = ',', default_value = "1,2,5,10,20,50,100,200,500,1000")]
+    pub rpc_latency_buckets: Vec<u64>,
+
+    /// The threshold in milliseconds after which appending entries to the log store is reported
+    /// as a slow operation: a warning is logged and counted in [`SlowOpMetrics`].
+    ///
+    /// `0` disables it.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`SlowOpMetrics`]: crate::metrics::SlowOpMetrics
+    #[clap(long, default_value = "500")]
+    pub slow_append_threshold: u64,
+
+    /// The threshold in milliseconds after which applying entries to the state machine is
+    /// reported as a slow operation.
+    ///
+    /// `0` disables it.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "500")]
+    pub slow_apply_threshold: u64,
+
+    /// The threshold in milliseconds after which building a snapshot is reported as a slow
+    /// operation.
+    ///
+    /// `0` disables it.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "10000")]
+    pub slow_snapshot_build_threshold: u64,
+
+    /// The threshold in milliseconds after which a replication RPC is reported as a slow
+    /// operation.
+    ///
+    /// `0` disables it.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "1000")]
+    pub slow_rpc_threshold: u64,
+
+    /// The minimum interval in milliseconds between two metrics flushes.
+    ///
+    /// By default, `0`, the metrics are flushed in every iteration of the `RaftCore` loop. A
+    /// greater value batches the changes made in this interval into one update, to wake up the
+    /// metrics subscribers less often in a busy cluster. The changes postponed are flushed when
+    /// the interval elapses, even if the node is idle.
+    ///
+    /// Whatever the interval is, a metrics channel is notified only when its value changes.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub metrics_flush_interval: u64,
+
+    /// The minimum interval in milliseconds between two flushes of the replication and heartbeat
+    /// metrics of a leader, see [`RaftReplicationMetrics`].
+    ///
+    /// These metrics change with every replication and heartbeat response, thus they are flushed
+    /// on their own schedule, independent of [`metrics_flush_interval`]. By default, `0`, they are
+    /// flushed along with the other metrics. The changes postponed are flushed when the interval
+    /// elapses, even if the node is idle. The replication and heartbeat fields of
+    /// [`RaftMetrics`] and the [`RaftNetworkMetrics`] are updated at the same pace.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`RaftReplicationMetrics`]: crate::metrics::RaftReplicationMetrics
+    /// [`RaftNetworkMetrics`]: crate::metrics::RaftNetworkMetrics
+    /// [`metrics_flush_interval`]: Self::metrics_flush_interval
+    /// [`RaftMetrics`]: crate::metrics::RaftMetrics
+    #[clap(long, default_value = "0")]
+    pub replication_metrics_flush_interval: u64,
+
+    /// Whether the leader promotes a learner to a voter automatically, once the replication lag
+    /// of the learner stays within [`auto_promote_max_lag`] for [`auto_promote_stable_period`].
+    ///
+    /// The promotion is proposed as a membership change through a joint config, the same as
+    /// [`Raft::promote_learner()`], and a [`RaftEvent::LearnerAutoPromoted`] is sent. It is
+    /// disabled by default.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`auto_promote_max_lag`]: Self::auto_promote_max_lag
+    /// [`auto_promote_stable_period`]: Self::auto_promote_stable_period
+    /// [`Raft::promote_learner()`]: crate::Raft::promote_learner
+    /// [`RaftEvent::LearnerAutoPromoted`]: crate::raft::RaftEvent::LearnerAutoPromoted
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub enable_auto_promote: bool,
+
+    /// The max number of log entries a learner can be behind the leader to be promoted
+    /// automatically.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "100")]
+    pub auto_promote_max_lag: u64,
+
+    /// The time in milliseconds the replication lag of a learner has to stay within
+    /// [`auto_promote_max_lag`](Self::auto_promote_max_lag) to be promoted automatically.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "10000")]
+    pub auto_promote_stable_period: u64,
+
+    /// If set, the leader demotes a voter to a learner automatically, once the voter has not
+    /// acknowledged the leader for this time in milliseconds.
+    ///
+    /// The demotion is proposed as a membership change through a joint config, the same as
+    /// [`Raft::demote_voter()`], and a [`RaftEvent::VoterAutoDemoted`] is sent. It must be greater
+    /// than `election_timeout_max`. It is disabled by default.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`Raft::demote_voter()`]: crate::Raft::demote_voter
+    /// [`RaftEvent::VoterAutoDemoted`]: crate::raft::RaftEvent::VoterAutoDemoted
+    #[clap(long)]
+    pub auto_demote_unreachable_period: Option<u64>,
+
+    /// The max number of membership changes that wait for a previous one to finish.
+    ///
+    /// When it is greater than 0, a call to [`Raft::change_membership()`], or another API built on
+    /// it, waits for the membership changes submitted before it on the same [`Raft`] handle to
+    /// finish, instead of failing with [`InProgress`]. The changes are applied one by one, in the
+    /// order they are submitted, and every call returns its own result. If there are already so
+    /// many changes waiting, it fails with [`QueueFull`].
+    ///
+    /// When it is 0, the default, changes are not queued.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`Raft`]: crate::Raft
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    /// [`InProgress`]: crate::error::InProgress
+    /// [`QueueFull`]: crate::error::QueueFull
+    #[clap(long, default_value = "0")]
+    pub membership_change_queue_depth: u64,
+
+    /// What a leader does after it commits a membership config in which it is not a voter, one
+    /// of `step_down`, `transfer_leader` or `shutdown`. See [`RemovedLeaderAction`].
+    ///
+    /// The leader keeps working until the membership config is applied, so that the response of
+    /// the membership change is sent. Then it takes the action and sends a
+    /// [`RaftEvent::RemovedFromMembership`]. The default is `step_down`.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`RaftEvent::RemovedFromMembership`]: crate::raft::RaftEvent::RemovedFromMembership
+    #[clap(
+        long,
+        default_value = "step_down",
+        value_parser=parse_removed_leader_action
+    )]
+    pub removed_leader_action: RemovedLeaderAction,
+
+    /// The number of the last decisions made by the Raft core to keep for diagnosis, 0 to disable.
+    ///
+    /// A decision is an input event, such as an API message or a notification, and the commands
+    /// emitted for it. The decisions are dumped in the log when `RaftCore` quits on a fatal
+    /// error, and can be read with [`Raft::decision_trace()`].
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`Raft::decision_trace()`]: crate::Raft::decision_trace
+    #[clap(long, default_value = "0")]
+    pub decision_trace_capacity: u64,
+
+    /// Whether to panic when an internal invariant is found violated, such as a replication
+    /// progress update for a target that is not replicated to.
+    ///
+    /// A violated invariant indicates a bug. By default it panics at once. If disabled, `RaftCore`
+    /// quits with [`Fatal::InvariantViolation`] describing the violation instead, so that an
+    /// application that can not tolerate an abort shuts the node down cleanly.
+    ///
+    /// It only covers the checks of the leader on a replication progress update: updating the
+    /// matching log id, updating a conflicting log id and resetting the inflight data on a
+    /// replication error. Any other violated invariant still panics.
+    ///
+    /// Since: 0.10.0
+    ///
+    /// [`Fatal::InvariantViolation`]: crate::error::Fatal::InvariantViolation
+    #[clap(long,
+           default_value_t = true,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub panic_on_invariant_violation: bool,
+
     /// Enable or disable tick.
     ///
     /// If ticking is disabled, timeout based events are all disabled:
@@ -296,6 +627,11 @@ impl Config {
         }
     }
 
+    /// The duration a leader lease lasts for lease reads, after the time a quorum acknowledged.
+    pub(crate) fn lease_read_duration(&self) -> Duration {
+        Duration::from_millis(self.election_timeout_max - self.lease_read_max_clock_drift)
+    }
+
     /// Whether to allow the replication to reset the state to `None` when a log state reversion is
     /// detected.
     ///
@@ -335,6 +671,36 @@ impl Config {
             return Err(ConfigError::MaxPayloadIs0);
         }
 
+        if self.apply_queue_size == 0 {
+            return Err(ConfigError::ApplyQueueSizeIs0);
+        }
+
+        if self.apply_batch_size == 0 {
+            return Err(ConfigError::ApplyBatchSizeIs0);
+        }
+
+        if self.enable_lease_read && self.lease_read_max_clock_drift >= self.election_timeout_max {
+            return Err(ConfigError::LeaseReadClockDrift {
+                lease_read_max_clock_drift: self.lease_read_max_clock_drift,
+                election_timeout_max: self.election_timeout_max,
+            });
+        }
+
+        if let Some(period) = self.auto_demote_unreachable_period {
+            if period <= self.election_timeout_max {
+                return Err(ConfigError::AutoDemotePeriod {
+                    auto_demote_unreachable_period: period,
+                    election_timeout_max: self.election_timeout_max,
+                });
+            }
+        }
+
+        if self.rpc_latency_buckets.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(ConfigError::RPCLatencyBuckets {
+                buckets: self.rpc_latency_buckets,
+            });
+        }
+
         Ok(self)
     }
 }
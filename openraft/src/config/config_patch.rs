@@ -0,0 +1,74 @@
+use crate::config::error::ConfigError;
+use crate::Config;
+use crate::SnapshotPolicy;
+
+/// A set of [`Config`] values to update on a running Raft node.
+///
+/// A `None` field keeps the current value.
+/// The patched config is validated as a whole, thus either every field of a patch is applied, or
+/// none is.
+///
+/// See [`Raft::update_config()`](crate::Raft::update_config) for when each value takes effect.
+#[derive(Clone, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ConfigPatch {
+    /// See: [`Config::election_timeout_min`].
+    ///
+    /// It can not be changed if [`Config::enable_lease_read`] is set.
+    pub election_timeout_min: Option<u64>,
+
+    /// See: [`Config::election_timeout_max`].
+    ///
+    /// It can not be changed if [`Config::enable_lease_read`] is set.
+    pub election_timeout_max: Option<u64>,
+
+    /// See: [`Config::max_payload_entries`].
+    pub max_payload_entries: Option<u64>,
+
+    /// See: [`Config::snapshot_policy`].
+    pub snapshot_policy: Option<SnapshotPolicy>,
+
+    /// See: [`Config::max_in_snapshot_log_to_keep`].
+    pub max_in_snapshot_log_to_keep: Option<u64>,
+
+    /// See: [`Config::purge_batch_size`].
+    pub purge_batch_size: Option<u64>,
+}
+
+impl ConfigPatch {
+    /// Build a new [`Config`] by replacing the values in `config` with the ones in this patch, and
+    /// validate it.
+    pub(crate) fn apply(&self, config: &Config) -> Result<Config, ConfigError> {
+        let mut c = config.clone();
+
+        // The lease is based on the election timeout of every node in the cluster, but a patch
+        // only updates this node.
+        let changes_timeout = self.election_timeout_min.is_some_and(|v| v != config.election_timeout_min)
+            || self.election_timeout_max.is_some_and(|v| v != config.election_timeout_max);
+        if config.enable_lease_read && changes_timeout {
+            return Err(ConfigError::ElectionTimeoutUpdateWithLeaseRead);
+        }
+
+        if let Some(v) = self.election_timeout_min {
+            c.election_timeout_min = v;
+        }
+        if let Some(v) = self.election_timeout_max {
+            c.election_timeout_max = v;
+        }
+        if let Some(v) = self.max_payload_entries {
+            c.max_payload_entries = v;
+        }
+        if let Some(v) = &self.snapshot_policy {
+            c.snapshot_policy = v.clone();
+        }
+        if let Some(v) = self.max_in_snapshot_log_to_keep {
+            c.max_in_snapshot_log_to_keep = v;
+        }
+        if let Some(v) = self.purge_batch_size {
+            c.purge_batch_size = v;
+        }
+
+        c.validate()
+    }
+}
@@ -2,6 +2,8 @@ use core::time::Duration;
 
 use crate::config::error::ConfigError;
 use crate::Config;
+use crate::ConfigPatch;
+use crate::RemovedLeaderAction;
 use crate::SnapshotPolicy;
 
 #[test]
@@ -46,6 +48,70 @@ fn test_invalid_election_timeout_config_produces_expected_error() {
     });
 }
 
+#[test]
+fn test_invalid_apply_queue_size() {
+    let config = Config {
+        apply_queue_size: 0,
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(res.unwrap_err(), ConfigError::ApplyQueueSizeIs0);
+}
+
+#[test]
+fn test_invalid_apply_batch_size() {
+    let config = Config {
+        apply_batch_size: 0,
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(res.unwrap_err(), ConfigError::ApplyBatchSizeIs0);
+}
+
+#[test]
+fn test_invalid_lease_read_clock_drift() {
+    let config = Config {
+        enable_lease_read: true,
+        lease_read_max_clock_drift: 300,
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(res.unwrap_err(), ConfigError::LeaseReadClockDrift {
+        lease_read_max_clock_drift: 300,
+        election_timeout_max: 300,
+    });
+}
+
+#[test]
+fn test_invalid_auto_demote_unreachable_period() {
+    let config = Config {
+        auto_demote_unreachable_period: Some(300),
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(res.unwrap_err(), ConfigError::AutoDemotePeriod {
+        auto_demote_unreachable_period: 300,
+        election_timeout_max: 300,
+    });
+}
+
+#[test]
+fn test_invalid_rpc_latency_buckets() {
+    let config = Config {
+        rpc_latency_buckets: vec![1, 5, 5],
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(res.unwrap_err(), ConfigError::RPCLatencyBuckets {
+        buckets: vec![1, 5, 5]
+    });
+}
+
 #[test]
 fn test_build() -> anyhow::Result<()> {
     let config = Config::build(&[
@@ -61,7 +127,29 @@ fn test_build() -> anyhow::Result<()> {
         "--replication-lag-threshold=203",
         "--snapshot-max-chunk-size=204",
         "--max-in-snapshot-log-to-keep=205",
+        "--max-snapshot-version=206",
         "--purge-batch-size=207",
+        "--log-stats-interval=208",
+        "--apply-queue-size=209",
+        "--apply-batch-size=210",
+        "--apply-batch-max-delay=211",
+        "--enable-lease-read",
+        "--lease-read-max-clock-drift=12",
+        "--rpc-latency-buckets=3,30,300",
+        "--slow-append-threshold=213",
+        "--slow-apply-threshold=214",
+        "--slow-snapshot-build-threshold=215",
+        "--slow-rpc-threshold=216",
+        "--metrics-flush-interval=217",
+        "--replication-metrics-flush-interval=223",
+        "--enable-auto-promote",
+        "--auto-promote-max-lag=218",
+        "--auto-promote-stable-period=219",
+        "--auto-demote-unreachable-period=220",
+        "--membership-change-queue-depth=221",
+        "--removed-leader-action=transfer_leader",
+        "--decision-trace-capacity=222",
+        "--panic-on-invariant-violation=false",
     ])?;
 
     assert_eq!("bar", config.cluster_name);
@@ -79,7 +167,29 @@ fn test_build() -> anyhow::Result<()> {
     assert_eq!(203, config.replication_lag_threshold);
     assert_eq!(204, config.snapshot_max_chunk_size);
     assert_eq!(205, config.max_in_snapshot_log_to_keep);
+    assert_eq!(Some(206), config.max_snapshot_version);
     assert_eq!(207, config.purge_batch_size);
+    assert_eq!(208, config.log_stats_interval);
+    assert_eq!(209, config.apply_queue_size);
+    assert_eq!(210, config.apply_batch_size);
+    assert_eq!(211, config.apply_batch_max_delay);
+    assert!(config.enable_lease_read);
+    assert_eq!(12, config.lease_read_max_clock_drift);
+    assert_eq!(vec![3, 30, 300], config.rpc_latency_buckets);
+    assert_eq!(213, config.slow_append_threshold);
+    assert_eq!(214, config.slow_apply_threshold);
+    assert_eq!(215, config.slow_snapshot_build_threshold);
+    assert_eq!(216, config.slow_rpc_threshold);
+    assert_eq!(217, config.metrics_flush_interval);
+    assert_eq!(223, config.replication_metrics_flush_interval);
+    assert!(config.enable_auto_promote);
+    assert_eq!(218, config.auto_promote_max_lag);
+    assert_eq!(219, config.auto_promote_stable_period);
+    assert_eq!(Some(220), config.auto_demote_unreachable_period);
+    assert_eq!(221, config.membership_change_queue_depth);
+    assert_eq!(RemovedLeaderAction::TransferLeader, config.removed_leader_action);
+    assert_eq!(222, config.decision_trace_capacity);
+    assert!(!config.panic_on_invariant_violation);
 
     // Test config methods
     #[allow(deprecated)]
@@ -112,6 +222,20 @@ fn test_config_snapshot_policy() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_config_removed_leader_action() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(RemovedLeaderAction::StepDown, config.removed_leader_action);
+
+    let config = Config::build(&["foo", "--removed-leader-action=shutdown"])?;
+    assert_eq!(RemovedLeaderAction::Shutdown, config.removed_leader_action);
+
+    let res = Config::build(&["foo", "--removed-leader-action=bar"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_config_enable_tick() -> anyhow::Result<()> {
     let config = Config::build(&["foo", "--enable-tick=false"])?;
@@ -190,3 +314,63 @@ fn test_config_allow_log_reversion() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_config_patch_apply() -> anyhow::Result<()> {
+    let config = Config::default();
+
+    let patched = ConfigPatch {
+        max_payload_entries: Some(10),
+        snapshot_policy: Some(SnapshotPolicy::Never),
+        ..Default::default()
+    }
+    .apply(&config)?;
+
+    assert_eq!(10, patched.max_payload_entries);
+    assert_eq!(SnapshotPolicy::Never, patched.snapshot_policy);
+    assert_eq!(config.election_timeout_min, patched.election_timeout_min);
+    assert_eq!(config.purge_batch_size, patched.purge_batch_size);
+
+    // An invalid patch is rejected as a whole.
+    let res = ConfigPatch {
+        max_payload_entries: Some(10),
+        election_timeout_min: Some(config.election_timeout_max),
+        ..Default::default()
+    }
+    .apply(&config);
+    assert_eq!(
+        ConfigError::ElectionTimeout {
+            min: config.election_timeout_max,
+            max: config.election_timeout_max
+        },
+        res.unwrap_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_config_patch_election_timeout_with_lease_read() -> anyhow::Result<()> {
+    let config = Config {
+        enable_lease_read: true,
+        ..Default::default()
+    };
+
+    let res = ConfigPatch {
+        election_timeout_max: Some(config.election_timeout_max + 100),
+        ..Default::default()
+    }
+    .apply(&config);
+    assert_eq!(ConfigError::ElectionTimeoutUpdateWithLeaseRead, res.unwrap_err());
+
+    // Setting the current value is not a change.
+    let patched = ConfigPatch {
+        election_timeout_min: Some(config.election_timeout_min),
+        max_payload_entries: Some(10),
+        ..Default::default()
+    }
+    .apply(&config)?;
+    assert_eq!(10, patched.max_payload_entries);
+
+    Ok(())
+}
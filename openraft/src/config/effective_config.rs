@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::Config;
+use crate::SnapshotPolicy;
+
+/// The configuration a running Raft node actually uses.
+///
+/// It includes the validated [`Config`] with the updates by
+/// [`Raft::update_config()`](crate::Raft::update_config), and the values derived from it or changed
+/// at runtime.
+///
+/// See: [`Raft::effective_config()`](crate::Raft::effective_config).
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct EffectiveConfig {
+    /// The validated config in use.
+    pub config: Arc<Config>,
+
+    /// The election timeout in use, chosen at random in the range
+    /// `[election_timeout_min, election_timeout_max)` of the config.
+    pub election_timeout: Duration,
+
+    /// The election timeout in use when this node has a smaller last log id than another node.
+    pub smaller_log_election_timeout: Duration,
+
+    /// The duration of a leader lease, during which a follower does not grant another candidate.
+    pub leader_lease: Duration,
+
+    /// The snapshot policy in use.
+    pub snapshot_policy: SnapshotPolicy,
+
+    /// Whether the internal ticker is enabled, which drives election and heartbeat.
+    pub enable_tick: bool,
+
+    /// Whether a leader sends heartbeat, as changed by
+    /// [`RuntimeConfigHandle::heartbeat()`](crate::raft::RuntimeConfigHandle::heartbeat).
+    pub enable_heartbeat: bool,
+
+    /// Whether a follower starts election when the leader lease expires, as changed by
+    /// [`RuntimeConfigHandle::elect()`](crate::raft::RuntimeConfigHandle::elect).
+    pub enable_elect: bool,
+}
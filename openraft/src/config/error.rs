@@ -14,15 +14,50 @@ pub enum ConfigError {
     #[error("max_payload_entries must be > 0")]
     MaxPayloadIs0,
 
+    #[error("apply_queue_size must be > 0")]
+    ApplyQueueSizeIs0,
+
+    #[error("apply_batch_size must be > 0")]
+    ApplyBatchSizeIs0,
+
     #[error("election_timeout_min({election_timeout_min}) must be > heartbeat_interval({heartbeat_interval})")]
     ElectionTimeoutLTHeartBeat {
         election_timeout_min: u64,
         heartbeat_interval: u64,
     },
 
+    #[error(
+        "lease_read_max_clock_drift({lease_read_max_clock_drift}) must be < election_timeout_max({election_timeout_max})"
+    )]
+    LeaseReadClockDrift {
+        lease_read_max_clock_drift: u64,
+        election_timeout_max: u64,
+    },
+
+    /// The election timeout of a running node can not be changed while lease read is enabled.
+    ///
+    /// The other nodes still use the old election timeout, a leader with a longer lease than
+    /// them may serve a stale read after a new leader is elected.
+    #[error("election timeout can not be updated when enable_lease_read is set")]
+    ElectionTimeoutUpdateWithLeaseRead,
+
+    #[error(
+        "auto_demote_unreachable_period({auto_demote_unreachable_period}) must be > election_timeout_max({election_timeout_max})"
+    )]
+    AutoDemotePeriod {
+        auto_demote_unreachable_period: u64,
+        election_timeout_max: u64,
+    },
+
+    #[error("rpc_latency_buckets({buckets:?}) must be in strictly ascending order")]
+    RPCLatencyBuckets { buckets: Vec<u64> },
+
     #[error("snapshot policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
     InvalidSnapshotPolicy { invalid: String, syntax: String },
 
+    #[error("removed leader action string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidRemovedLeaderAction { invalid: String, syntax: String },
+
     #[error("{reason} when parsing {invalid:?}")]
     InvalidNumber { invalid: String, reason: String },
 }
@@ -1,11 +1,16 @@
 #[allow(clippy::module_inception)]
 mod config;
+mod config_patch;
+mod effective_config;
 mod error;
 
 #[cfg(test)]
 mod config_test;
 
 pub use config::Config;
+pub use config::RemovedLeaderAction;
 pub(crate) use config::RuntimeConfig;
 pub use config::SnapshotPolicy;
+pub use config_patch::ConfigPatch;
+pub use effective_config::EffectiveConfig;
 pub use error::ConfigError;
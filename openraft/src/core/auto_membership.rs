@@ -0,0 +1,297 @@
+//! Promote learners and demote voters automatically on the leader.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::type_config::alias::InstantOf;
+use crate::ChangeMembers;
+use crate::Config;
+use crate::LogIndexOptionExt;
+use crate::Membership;
+use crate::RaftTypeConfig;
+
+/// A membership change decided by [`AutoMembership`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AutoChange<C>
+where C: RaftTypeConfig
+{
+    /// Promote a learner whose replication lag is small enough for long enough.
+    Promote(C::NodeId),
+
+    /// Demote a voter that has not acknowledged the leader for long enough.
+    Demote(C::NodeId),
+}
+
+impl<C> AutoChange<C>
+where C: RaftTypeConfig
+{
+    /// The changes to propose and whether to retain the removed voters as learners.
+    pub(crate) fn to_changes(&self) -> (ChangeMembers<C>, bool) {
+        match self {
+            AutoChange::Promote(id) => (ChangeMembers::AddVoterIds([id.clone()].into()), false),
+            AutoChange::Demote(id) => (ChangeMembers::RemoveVoters([id.clone()].into()), true),
+        }
+    }
+}
+
+/// Tracks the learners and voters on the leader to decide when to promote or demote one.
+///
+/// See: [`Config::enable_auto_promote`] and [`Config::auto_demote_unreachable_period`].
+pub(crate) struct AutoMembership<C>
+where C: RaftTypeConfig
+{
+    /// Since when the replication lag of a learner stays within the max lag.
+    caught_up_since: BTreeMap<C::NodeId, InstantOf<C>>,
+
+    /// Since when a voter that has never acknowledged this leader is tracked.
+    silent_since: BTreeMap<C::NodeId, InstantOf<C>>,
+
+    /// The change whose joint config is proposed, the uniform config is proposed when the joint
+    /// config is committed.
+    pub(crate) pending: Option<(ChangeMembers<C>, bool)>,
+}
+
+impl<C> Default for AutoMembership<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            caught_up_since: BTreeMap::new(),
+            silent_since: BTreeMap::new(),
+            pending: None,
+        }
+    }
+}
+
+impl<C> AutoMembership<C>
+where C: RaftTypeConfig
+{
+    /// Forget everything tracked, e.g., when the leader changes.
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Update the tracked nodes in the committed uniform `membership` and return the change to
+    /// propose, if any.
+    ///
+    /// - `matching` returns the last log index replicated to a node.
+    /// - `acked` returns the last time a node acknowledged the leader.
+    ///
+    /// A learner is caught up only if its lag is within the max lag and it has acknowledged the
+    /// leader within `election_timeout_max`. An unreachable voter is demoted before a learner is
+    /// promoted. The leader itself and the last voter are never demoted.
+    pub(crate) fn decide(
+        &mut self,
+        now: InstantOf<C>,
+        config: &Config,
+        leader_id: &C::NodeId,
+        membership: &Membership<C>,
+        last_log_index: Option<u64>,
+        matching: impl Fn(&C::NodeId) -> Option<u64>,
+        acked: impl Fn(&C::NodeId) -> Option<InstantOf<C>>,
+    ) -> Option<AutoChange<C>> {
+        let demote = self.decide_demote(now, config, leader_id, membership, &acked);
+        let promote = self.decide_promote(now, config, membership, last_log_index, matching, &acked);
+
+        demote.or(promote)
+    }
+
+    fn decide_promote(
+        &mut self,
+        now: InstantOf<C>,
+        config: &Config,
+        membership: &Membership<C>,
+        last_log_index: Option<u64>,
+        matching: impl Fn(&C::NodeId) -> Option<u64>,
+        acked: &impl Fn(&C::NodeId) -> Option<InstantOf<C>>,
+    ) -> Option<AutoChange<C>> {
+        if !config.enable_auto_promote {
+            self.caught_up_since.clear();
+            return None;
+        }
+
+        let stable_period = Duration::from_millis(config.auto_promote_stable_period);
+        let ack_timeout = Duration::from_millis(config.election_timeout_max);
+        let mut caught_up_since = BTreeMap::new();
+        let mut promote = None;
+
+        for id in membership.learner_ids() {
+            let lag = last_log_index.next_index().saturating_sub(matching(&id).next_index());
+            if lag > config.auto_promote_max_lag {
+                continue;
+            }
+
+            // A learner that stops responding is not caught up, even if no log is appended since.
+            let reachable = acked(&id).is_some_and(|t| now - t <= ack_timeout);
+            if !reachable {
+                continue;
+            }
+
+            let since = self.caught_up_since.get(&id).copied().unwrap_or(now);
+            if promote.is_none() && now - since >= stable_period {
+                promote = Some(AutoChange::Promote(id.clone()));
+            }
+            caught_up_since.insert(id, since);
+        }
+
+        self.caught_up_since = caught_up_since;
+        promote
+    }
+
+    fn decide_demote(
+        &mut self,
+        now: InstantOf<C>,
+        config: &Config,
+        leader_id: &C::NodeId,
+        membership: &Membership<C>,
+        acked: &impl Fn(&C::NodeId) -> Option<InstantOf<C>>,
+    ) -> Option<AutoChange<C>> {
+        let Some(period) = config.auto_demote_unreachable_period else {
+            self.silent_since.clear();
+            return None;
+        };
+
+        let period = Duration::from_millis(period);
+        let voters = membership.voter_ids().collect::<Vec<_>>();
+        let mut silent_since = BTreeMap::new();
+        let mut demote = None;
+
+        for id in voters.iter() {
+            if id == leader_id {
+                continue;
+            }
+
+            let last_seen = match acked(id) {
+                Some(t) => t,
+                None => {
+                    let since = self.silent_since.get(id).copied().unwrap_or(now);
+                    silent_since.insert(id.clone(), since);
+                    since
+                }
+            };
+
+            if demote.is_none() && voters.len() > 1 && now - last_seen >= period {
+                demote = Some(AutoChange::Demote(id.clone()));
+            }
+        }
+
+        self.silent_since = silent_since;
+        demote
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use maplit::btreeset;
+
+    use crate::core::auto_membership::AutoChange;
+    use crate::core::auto_membership::AutoMembership;
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::TypeConfigExt;
+    use crate::Config;
+    use crate::Membership;
+
+    type C = UTConfig;
+
+    fn config(promote: bool, demote: Option<u64>) -> Config {
+        Config {
+            enable_auto_promote: promote,
+            auto_promote_max_lag: 5,
+            auto_promote_stable_period: 100,
+            auto_demote_unreachable_period: demote,
+            ..Default::default()
+        }
+    }
+
+    fn m12_learner3() -> Membership<C> {
+        Membership::<C>::new_with_defaults(vec![btreeset! {1,2}], [3])
+    }
+
+    #[test]
+    fn test_auto_promote() {
+        let mut am = AutoMembership::<C>::default();
+        let c = config(true, None);
+        let m = m12_learner3();
+        let t0 = C::now();
+        let ms = |n: u64| t0 + Duration::from_millis(n);
+
+        let acked_at = |n: u64| move |_: &u64| Some(ms(n));
+
+        // Lagging behind: not tracked.
+        let got = am.decide(t0, &c, &1, &m, Some(20), |_| Some(10), acked_at(0));
+        assert_eq!(None, got);
+
+        // Caught up, but not for long enough.
+        let got = am.decide(ms(10), &c, &1, &m, Some(20), |_| Some(15), acked_at(10));
+        assert_eq!(None, got);
+        let got = am.decide(ms(100), &c, &1, &m, Some(20), |_| Some(15), acked_at(100));
+        assert_eq!(None, got);
+
+        // Lagging again resets the period.
+        let got = am.decide(ms(105), &c, &1, &m, Some(30), |_| Some(15), acked_at(105));
+        assert_eq!(None, got);
+        let got = am.decide(ms(150), &c, &1, &m, Some(30), |_| Some(29), acked_at(150));
+        assert_eq!(None, got);
+
+        // Not responding resets the period.
+        let got = am.decide(ms(500), &c, &1, &m, Some(30), |_| Some(30), acked_at(150));
+        assert_eq!(None, got);
+        let got = am.decide(ms(550), &c, &1, &m, Some(30), |_| Some(30), acked_at(550));
+        assert_eq!(None, got);
+
+        let got = am.decide(ms(650), &c, &1, &m, Some(30), |_| Some(30), acked_at(650));
+        assert_eq!(Some(AutoChange::Promote(3)), got);
+
+        // Disabled.
+        let got = am.decide(
+            ms(700),
+            &config(false, None),
+            &1,
+            &m,
+            Some(30),
+            |_| Some(30),
+            acked_at(700),
+        );
+        assert_eq!(None, got);
+    }
+
+    #[test]
+    fn test_auto_demote() {
+        let mut am = AutoMembership::<C>::default();
+        let c = config(true, Some(400));
+        let m = m12_learner3();
+        let t0 = C::now();
+        let ms = |n: u64| t0 + Duration::from_millis(n);
+
+        // Node 2 never acknowledges this leader.
+        let got = am.decide(t0, &c, &1, &m, Some(20), |_| None, |_| None);
+        assert_eq!(None, got);
+        let got = am.decide(ms(399), &c, &1, &m, Some(20), |_| None, |_| None);
+        assert_eq!(None, got);
+
+        // Demoting goes before promoting.
+        let got = am.decide(
+            ms(400),
+            &c,
+            &1,
+            &m,
+            Some(20),
+            |_| Some(20),
+            |id| (*id == 3).then(|| ms(400)),
+        );
+        assert_eq!(Some(AutoChange::Demote(2)), got);
+
+        // Node 2 acknowledged recently.
+        let got = am.decide(ms(500), &c, &1, &m, Some(20), |_| None, |_| Some(ms(200)));
+        assert_eq!(None, got);
+        let got = am.decide(ms(600), &c, &1, &m, Some(20), |_| None, |_| Some(ms(200)));
+        assert_eq!(Some(AutoChange::Demote(2)), got);
+
+        // The leader, the only voter, is never demoted.
+        let m1 = Membership::<C>::new_with_defaults(vec![btreeset! {1}], []);
+        let got = am.decide(ms(5000), &c, &1, &m1, Some(20), |_| None, |_| None);
+        assert_eq!(None, got);
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::raft::CorrelationId;
+
+/// The correlation ids of the client writes that are not yet applied, keyed by log index.
+///
+/// It is owned by `RaftCore`. The ids of the logs to replicate are passed to a replication stream
+/// along with the replication request, so that a replication RPC can tell the client writes it
+/// carries.
+#[derive(Debug, Default)]
+pub(crate) struct CorrelationIds {
+    ids: BTreeMap<u64, CorrelationId>,
+}
+
+impl CorrelationIds {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn insert(&mut self, index: u64, id: CorrelationId) {
+        self.ids.insert(index, id);
+    }
+
+    pub(crate) fn remove(&mut self, index: u64) -> Option<CorrelationId> {
+        self.ids.remove(&index)
+    }
+
+    /// Remove the ids of the log entries at or after `since`, which are truncated.
+    pub(crate) fn truncate(&mut self, since: u64) {
+        self.ids.split_off(&since);
+    }
+
+    /// Remove the ids of the log entries before `upto`, which are included in an installed
+    /// snapshot and will never be applied one by one.
+    pub(crate) fn purge(&mut self, upto: u64) {
+        self.ids = self.ids.split_off(&upto);
+    }
+
+    /// Return the `(index, id)` of the client writes in the log index range.
+    pub(crate) fn range(&self, range: Range<u64>) -> Vec<(u64, CorrelationId)> {
+        if range.is_empty() {
+            return vec![];
+        }
+        self.ids.range(range).map(|(index, id)| (*index, *id)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::correlation_ids::CorrelationIds;
+    use crate::raft::CorrelationId;
+
+    #[test]
+    fn test_correlation_ids() {
+        let mut ids = CorrelationIds::new();
+        ids.insert(3, CorrelationId::new(30));
+        ids.insert(5, CorrelationId::new(50));
+        ids.insert(6, CorrelationId::new(60));
+
+        assert_eq!(
+            vec![(3, CorrelationId::new(30)), (5, CorrelationId::new(50))],
+            ids.range(1..6)
+        );
+        assert_eq!(Vec::<(u64, CorrelationId)>::new(), ids.range(6..6));
+
+        assert_eq!(Some(CorrelationId::new(30)), ids.remove(3));
+        assert_eq!(None, ids.remove(3));
+
+        ids.truncate(6);
+        assert_eq!(vec![(5, CorrelationId::new(50))], ids.range(0..10));
+
+        assert_eq!("cid-50", CorrelationId::new(50).to_string());
+    }
+
+    #[test]
+    fn test_correlation_ids_purge() {
+        let mut ids = CorrelationIds::new();
+        ids.insert(3, CorrelationId::new(30));
+        ids.insert(5, CorrelationId::new(50));
+        ids.insert(6, CorrelationId::new(60));
+
+        ids.purge(6);
+        assert_eq!(vec![(6, CorrelationId::new(60))], ids.range(0..10));
+    }
+}
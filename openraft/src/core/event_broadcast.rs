@@ -0,0 +1,207 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::async_runtime::MpscUnboundedSender;
+use crate::error::Fatal;
+use crate::raft::RaftEvent;
+use crate::raft::RaftEventReceiver;
+use crate::raft::EVENT_QUEUE_SIZE;
+use crate::type_config::alias::MpscUnboundedSenderOf;
+use crate::type_config::TypeConfigExt;
+use crate::RaftTypeConfig;
+
+/// An item queued for a subscriber.
+pub(crate) enum EventItem<C>
+where C: RaftTypeConfig
+{
+    Event(RaftEvent<C>),
+
+    /// The number of events dropped before the next event, because the queue was full.
+    Lagged(u64),
+}
+
+/// Sends every [`RaftEvent`] to all of the subscribers.
+///
+/// At most [`EVENT_QUEUE_SIZE`] items are queued for a subscriber, the events that do not fit are
+/// dropped for it, except [`RaftEvent::Fatal`]. A subscriber is removed once its receiver is
+/// dropped.
+pub(crate) struct EventBroadcast<C>
+where C: RaftTypeConfig
+{
+    subscribers: Mutex<Vec<Subscriber<C>>>,
+}
+
+struct Subscriber<C>
+where C: RaftTypeConfig
+{
+    tx: MpscUnboundedSenderOf<C, EventItem<C>>,
+
+    /// The number of items sent but not yet received.
+    queued: Arc<AtomicUsize>,
+
+    /// The number of events dropped since the last one sent.
+    dropped: u64,
+}
+
+impl<C> EventBroadcast<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> RaftEventReceiver<C> {
+        let (tx, rx) = C::mpsc_unbounded();
+        let queued = Arc::new(AtomicUsize::new(0));
+        self.subscribers.lock().unwrap().push(Subscriber {
+            tx,
+            queued: queued.clone(),
+            dropped: 0,
+        });
+        RaftEventReceiver { rx, queued }
+    }
+
+    pub(crate) fn send(&self, event: RaftEvent<C>) {
+        tracing::debug!(event = display(&event), "broadcast RaftEvent");
+
+        let is_fatal = matches!(event, RaftEvent::Fatal { .. });
+
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain_mut(|sub| {
+            if sub.queued.load(Ordering::Relaxed) >= EVENT_QUEUE_SIZE && !is_fatal {
+                tracing::warn!(
+                    event = display(&event),
+                    "RaftEvent subscriber queue is full, drop the event for it"
+                );
+                sub.dropped += 1;
+                return true;
+            }
+
+            if sub.dropped > 0 {
+                sub.queued.fetch_add(1, Ordering::Relaxed);
+                if sub.tx.send(EventItem::Lagged(sub.dropped)).is_err() {
+                    return false;
+                }
+                sub.dropped = 0;
+            }
+
+            sub.queued.fetch_add(1, Ordering::Relaxed);
+            sub.tx.send(EventItem::Event(event.clone())).is_ok()
+        });
+    }
+}
+
+/// Broadcasts [`RaftEvent::Fatal`] with [`Fatal::Panicked`] if it is dropped during a panic.
+///
+/// `RaftCore` holds it while running, so that a panic is reported to the subscribers, which is
+/// otherwise only found out when the core task is joined.
+pub(crate) struct FatalOnPanic<C>
+where C: RaftTypeConfig
+{
+    events: Arc<EventBroadcast<C>>,
+}
+
+impl<C> FatalOnPanic<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(events: Arc<EventBroadcast<C>>) -> Self {
+        Self { events }
+    }
+}
+
+impl<C> Drop for FatalOnPanic<C>
+where C: RaftTypeConfig
+{
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.events.send(RaftEvent::Fatal { error: Fatal::Panicked });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::core::event_broadcast::EventBroadcast;
+    use crate::core::event_broadcast::FatalOnPanic;
+    use crate::engine::testing::log_id;
+    use crate::engine::testing::UTConfig;
+    use crate::error::Fatal;
+    use crate::raft::RaftEvent;
+    use crate::raft::RecvEventError;
+    use crate::raft::EVENT_QUEUE_SIZE;
+
+    fn purged(index: u64) -> RaftEvent<UTConfig> {
+        RaftEvent::PurgeCompleted {
+            upto: log_id(1, 0, index),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_lagged_if_full() {
+        let events = EventBroadcast::<UTConfig>::new();
+        let mut rx = events.subscribe();
+
+        // The last 2 events are dropped
+        for i in 0..EVENT_QUEUE_SIZE as u64 + 2 {
+            events.send(purged(i));
+        }
+
+        assert_eq!(Ok(purged(0)), rx.recv().await);
+
+        // One slot is freed, the lag is reported before the next event.
+        events.send(purged(2000));
+
+        for i in 1..EVENT_QUEUE_SIZE as u64 {
+            assert_eq!(Ok(purged(i)), rx.recv().await);
+        }
+        assert_eq!(Err(RecvEventError::Lagged(2)), rx.recv().await);
+        assert_eq!(Ok(purged(2000)), rx.recv().await);
+
+        // Closed
+        drop(events);
+        assert_eq!(Err(RecvEventError::Closed), rx.recv().await);
+    }
+
+    #[tokio::test]
+    async fn test_fatal_is_not_dropped() {
+        let events = EventBroadcast::<UTConfig>::new();
+        let mut rx = events.subscribe();
+
+        for i in 0..EVENT_QUEUE_SIZE as u64 {
+            events.send(purged(i));
+        }
+        events.send(RaftEvent::Fatal { error: Fatal::Panicked });
+
+        for i in 0..EVENT_QUEUE_SIZE as u64 {
+            assert_eq!(Ok(purged(i)), rx.recv().await);
+        }
+        assert_eq!(Ok(RaftEvent::Fatal { error: Fatal::Panicked }), rx.recv().await);
+    }
+
+    #[tokio::test]
+    async fn test_fatal_on_panic() {
+        let events = Arc::new(EventBroadcast::<UTConfig>::new());
+        let mut rx = events.subscribe();
+
+        // Dropped without panicking
+        drop(FatalOnPanic::new(events.clone()));
+
+        let ev = events.clone();
+        let res = std::panic::catch_unwind(move || {
+            let _guard = FatalOnPanic::new(ev);
+            panic!("foo");
+        });
+        assert!(res.is_err());
+
+        assert_eq!(Ok(RaftEvent::Fatal { error: Fatal::Panicked }), rx.recv().await);
+
+        drop(events);
+        assert_eq!(Err(RecvEventError::Closed), rx.recv().await);
+    }
+}
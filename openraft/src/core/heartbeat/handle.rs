@@ -8,6 +8,7 @@ use tracing::Span;
 use crate::async_runtime::watch::WatchSender;
 use crate::core::heartbeat::event::HeartbeatEvent;
 use crate::core::heartbeat::worker::HeartbeatWorker;
+use crate::core::io_counters::IoCounters;
 use crate::core::notification::Notification;
 use crate::type_config::alias::JoinHandleOf;
 use crate::type_config::alias::MpscUnboundedSenderOf;
@@ -26,6 +27,8 @@ where C: RaftTypeConfig
 
     pub(crate) config: Arc<Config>,
 
+    pub(crate) io_counters: Arc<IoCounters>,
+
     /// Inform the heartbeat task to broadcast heartbeat message.
     ///
     /// A Leader will periodically update this value to trigger sending heartbeat messages.
@@ -42,12 +45,13 @@ where C: RaftTypeConfig
 impl<C> HeartbeatWorkersHandle<C>
 where C: RaftTypeConfig
 {
-    pub(crate) fn new(id: C::NodeId, config: Arc<Config>) -> Self {
+    pub(crate) fn new(id: C::NodeId, config: Arc<Config>, io_counters: Arc<IoCounters>) -> Self {
         let (tx, rx) = C::watch_channel(None);
 
         Self {
             id,
             config,
+            io_counters,
             tx,
             rx,
             workers: Default::default(),
@@ -78,6 +82,7 @@ where C: RaftTypeConfig
                 target: target.clone(),
                 node,
                 config: self.config.clone(),
+                io_counters: self.io_counters.clone(),
                 tx_notification: tx_notification.clone(),
             };
 
@@ -7,9 +7,11 @@ use futures::FutureExt;
 use crate::async_runtime::watch::WatchReceiver;
 use crate::async_runtime::MpscUnboundedSender;
 use crate::core::heartbeat::event::HeartbeatEvent;
+use crate::core::io_counters::IoCounters;
 use crate::core::notification::Notification;
 use crate::network::v2::RaftNetworkV2;
 use crate::network::RPCOption;
+use crate::network::RPCTypes;
 use crate::raft::AppendEntriesRequest;
 use crate::type_config::alias::MpscUnboundedSenderOf;
 use crate::type_config::alias::OneshotReceiverOf;
@@ -38,6 +40,8 @@ where
 
     pub(crate) config: Arc<Config>,
 
+    pub(crate) io_counters: Arc<IoCounters>,
+
     /// For sending back result to the [`RaftCore`].
     ///
     /// [`RaftCore`]: crate::core::RaftCore
@@ -89,6 +93,7 @@ where
             };
 
             let res = C::timeout(timeout, self.network.append_entries(payload, option)).await;
+            self.io_counters.record_rpc(RPCTypes::AppendEntries, &res);
             tracing::debug!("{} sent a heartbeat: {}, result: {:?}", self, heartbeat, res);
 
             match res {
@@ -0,0 +1,169 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use crate::error::StreamingError;
+use crate::metrics::IoMetrics;
+use crate::metrics::RpcCounts;
+use crate::RPCTypes;
+use crate::RaftTypeConfig;
+
+const OK: usize = 0;
+const ERROR: usize = 1;
+const TIMEOUT: usize = 2;
+
+const RPC_TYPES: [RPCTypes; 5] = [
+    RPCTypes::Vote,
+    RPCTypes::AppendEntries,
+    RPCTypes::InstallSnapshot,
+    RPCTypes::TransferLeader,
+    RPCTypes::ReadIndex,
+];
+
+/// The IO counters shared by `RaftCore`, the replication streams, the heartbeat workers and the
+/// `Raft` handle, from which [`IoMetrics`] is built.
+#[derive(Debug, Default)]
+pub(crate) struct IoCounters {
+    log_entries_appended: AtomicU64,
+    log_bytes_appended: AtomicU64,
+    log_entries_purged: AtomicU64,
+    snapshots_sent: AtomicU64,
+    snapshot_bytes_sent: AtomicU64,
+    snapshots_received: AtomicU64,
+    snapshot_bytes_received: AtomicU64,
+
+    /// `[ok, error, timeout]` of every RPC type, in the order of `RPC_TYPES`.
+    rpcs: [[AtomicU64; 3]; RPC_TYPES.len()],
+}
+
+impl IoCounters {
+    pub(crate) fn add_log_entries_appended(&self, n: u64) {
+        self.log_entries_appended.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_log_bytes_appended(&self, n: u64) {
+        self.log_bytes_appended.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_log_entries_purged(&self, n: u64) {
+        self.log_entries_purged.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub(crate) fn incr_snapshots_received(&self) {
+        self.snapshots_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_snapshot_bytes_sent(&self, n: u64) {
+        self.snapshot_bytes_sent.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_snapshot_bytes_received(&self, n: u64) {
+        self.snapshot_bytes_received.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Count an RPC by the result of `C::timeout(ttl, rpc)`: the outer error is a timeout and the
+    /// inner error is returned by the network.
+    pub(crate) fn record_rpc<T, E, TO>(&self, rpc_type: RPCTypes, res: &Result<Result<T, E>, TO>) {
+        let i = match res {
+            Ok(Ok(_)) => OK,
+            Ok(Err(_)) => ERROR,
+            Err(_) => TIMEOUT,
+        };
+        self.rpcs[Self::rpc_index(rpc_type)][i].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a snapshot sending by its result, which is counted as an `InstallSnapshot` RPC.
+    ///
+    /// A sending canceled by the replication stream is not counted.
+    pub(crate) fn record_snapshot_sent<C, T>(&self, res: &Result<T, StreamingError<C>>)
+    where C: RaftTypeConfig {
+        let i = match res {
+            Ok(_) => {
+                self.snapshots_sent.fetch_add(1, Ordering::Relaxed);
+                OK
+            }
+            Err(StreamingError::Closed(_)) => return,
+            Err(StreamingError::Timeout(_)) => TIMEOUT,
+            Err(_) => ERROR,
+        };
+        self.rpcs[Self::rpc_index(RPCTypes::InstallSnapshot)][i].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn metrics(&self) -> IoMetrics {
+        let rpcs = RPC_TYPES
+            .iter()
+            .zip(self.rpcs.iter())
+            .map(|(rpc_type, [ok, error, timeout])| {
+                let counts = RpcCounts {
+                    ok: ok.load(Ordering::Relaxed),
+                    error: error.load(Ordering::Relaxed),
+                    timeout: timeout.load(Ordering::Relaxed),
+                };
+                (*rpc_type, counts)
+            })
+            .filter(|(_, counts)| counts.total() > 0)
+            .collect();
+
+        IoMetrics {
+            log_entries_appended: self.log_entries_appended.load(Ordering::Relaxed),
+            log_bytes_appended: self.log_bytes_appended.load(Ordering::Relaxed),
+            log_entries_purged: self.log_entries_purged.load(Ordering::Relaxed),
+            snapshots_sent: self.snapshots_sent.load(Ordering::Relaxed),
+            snapshot_bytes_sent: self.snapshot_bytes_sent.load(Ordering::Relaxed),
+            snapshots_received: self.snapshots_received.load(Ordering::Relaxed),
+            snapshot_bytes_received: self.snapshot_bytes_received.load(Ordering::Relaxed),
+            rpcs,
+        }
+    }
+
+    fn rpc_index(rpc_type: RPCTypes) -> usize {
+        match rpc_type {
+            RPCTypes::Vote => 0,
+            RPCTypes::AppendEntries => 1,
+            RPCTypes::InstallSnapshot => 2,
+            RPCTypes::TransferLeader => 3,
+            RPCTypes::ReadIndex => 4,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use maplit::btreemap;
+
+    use crate::core::io_counters::IoCounters;
+    use crate::metrics::RpcCounts;
+    use crate::RPCTypes;
+
+    #[test]
+    fn test_io_counters() {
+        let c = IoCounters::default();
+        c.add_log_entries_appended(3);
+        c.add_log_bytes_appended(30);
+        c.add_log_entries_purged(2);
+        c.add_snapshot_bytes_sent(20);
+        c.incr_snapshots_received();
+        c.add_snapshot_bytes_received(10);
+
+        c.record_rpc::<(), (), Duration>(RPCTypes::AppendEntries, &Ok(Ok(())));
+        c.record_rpc::<(), (), Duration>(RPCTypes::AppendEntries, &Ok(Err(())));
+        c.record_rpc::<(), (), Duration>(RPCTypes::Vote, &Err(Duration::from_millis(1)));
+
+        let m = c.metrics();
+        assert_eq!(3, m.log_entries_appended);
+        assert_eq!(30, m.log_bytes_appended);
+        assert_eq!(2, m.log_entries_purged);
+        assert_eq!(0, m.snapshots_sent);
+        assert_eq!(20, m.snapshot_bytes_sent);
+        assert_eq!(1, m.snapshots_received);
+        assert_eq!(10, m.snapshot_bytes_received);
+        assert_eq!(
+            btreemap! {
+                RPCTypes::Vote => RpcCounts { ok: 0, error: 0, timeout: 1 },
+                RPCTypes::AppendEntries => RpcCounts { ok: 1, error: 1, timeout: 0 },
+            },
+            m.rpcs
+        );
+    }
+}
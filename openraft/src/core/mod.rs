@@ -4,8 +4,12 @@
 //! Also it receives and execute `Command` emitted by `Engine` to apply raft state to underlying
 //! storage or forward messages to other raft nodes.
 
+pub(crate) mod auto_membership;
 pub(crate) mod balancer;
+pub(crate) mod correlation_ids;
+pub(crate) mod event_broadcast;
 pub(crate) mod heartbeat;
+pub(crate) mod io_counters;
 pub(crate) mod notification;
 mod raft_core;
 pub(crate) mod raft_msg;
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::time::Duration;
 
 use crate::core::sm;
 use crate::display_ext::DisplayInstantExt;
@@ -7,9 +8,11 @@ use crate::raft_state::IOId;
 use crate::replication;
 use crate::replication::ReplicationSessionId;
 use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
 use crate::vote::committed::CommittedVote;
 use crate::vote::non_committed::NonCommittedVote;
+use crate::RPCTypes;
 use crate::RaftTypeConfig;
 use crate::StorageError;
 
@@ -49,6 +52,9 @@ where C: RaftTypeConfig
     /// Completion of an IO operation to local store.
     LocalIO { io_id: IOId<C> },
 
+    /// Logs upto `purged`, inclusive, are durably removed from the log store.
+    LogPurged { purged: LogIdOf<C> },
+
     /// Result of executing a command sent from network worker.
     ReplicationProgress { progress: replication::Progress<C> },
 
@@ -58,9 +64,20 @@ where C: RaftTypeConfig
         target: C::NodeId,
     },
 
+    /// The latency of an RPC that is sent by replication and receives a response.
+    RPCLatency {
+        target: C::NodeId,
+        rpc_type: RPCTypes,
+        latency: Duration,
+    },
+
     /// Result of executing a command sent from state machine worker.
     StateMachine { command_result: sm::CommandResult<C> },
 
+    /// The max delay of the pending apply batch is reached, it should be sent to the state machine
+    /// worker.
+    ApplyDelayExpired,
+
     /// A tick event to wake up RaftCore to check timeout etc.
     Tick {
         /// ith tick
@@ -105,6 +122,7 @@ where C: RaftTypeConfig
             }
             Self::StorageError { error } => write!(f, "StorageError: {}", error),
             Self::LocalIO { io_id } => write!(f, "IOFlushed: {}", io_id),
+            Self::LogPurged { purged } => write!(f, "LogPurged: upto {}", purged),
             Self::ReplicationProgress { progress } => {
                 write!(f, "{}", progress)
             }
@@ -121,9 +139,19 @@ where C: RaftTypeConfig
                     sending_time.display(),
                 )
             }
+            Self::RPCLatency {
+                target,
+                rpc_type,
+                latency,
+            } => {
+                write!(f, "RPCLatency: target={}, {}: {:?}", target, rpc_type, latency)
+            }
             Self::StateMachine { command_result } => {
                 write!(f, "{}", command_result)
             }
+            Self::ApplyDelayExpired => {
+                write!(f, "ApplyDelayExpired")
+            }
             Self::Tick { i } => {
                 write!(f, "Tick {}", i)
             }
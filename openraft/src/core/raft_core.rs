@@ -1,11 +1,16 @@
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
 use std::fmt;
 use std::fmt::Debug;
+use std::ops::Range;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyerror::AnyError;
+use futures::future::Either;
+use futures::future::Pending;
 use futures::stream::FuturesUnordered;
 use futures::FutureExt;
 use futures::StreamExt;
@@ -17,13 +22,22 @@ use tracing::Span;
 
 use crate::async_runtime::watch::WatchSender;
 use crate::async_runtime::MpscUnboundedSender;
+use crate::async_runtime::MpscUnboundedWeakSender;
 use crate::async_runtime::OneshotSender;
 use crate::async_runtime::TryRecvError;
 use crate::config::Config;
+use crate::config::ConfigPatch;
+use crate::config::EffectiveConfig;
 use crate::config::RuntimeConfig;
+use crate::core::auto_membership::AutoChange;
+use crate::core::auto_membership::AutoMembership;
 use crate::core::balancer::Balancer;
+use crate::core::correlation_ids::CorrelationIds;
+use crate::core::event_broadcast::EventBroadcast;
+use crate::core::event_broadcast::FatalOnPanic;
 use crate::core::heartbeat::event::HeartbeatEvent;
 use crate::core::heartbeat::handle::HeartbeatWorkersHandle;
+use crate::core::io_counters::IoCounters;
 use crate::core::notification::Notification;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::raft_msg::AppendEntriesTx;
@@ -44,21 +58,41 @@ use crate::engine::ReplicationProgress;
 use crate::engine::Respond;
 use crate::entry::RaftEntry;
 use crate::error::AllowNextRevertError;
+use crate::error::ApplyError;
+use crate::error::ChangeMembershipError;
 use crate::error::ClientWriteError;
 use crate::error::Fatal;
 use crate::error::ForwardToLeader;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::MembershipRejected;
+use crate::error::PreflightFailed;
 use crate::error::QuorumNotEnough;
 use crate::error::RPCError;
+use crate::error::ReadIndexError;
+use crate::error::ShuttingDown;
 use crate::error::Timeout;
 use crate::log_id::option_raft_log_id_ext::OptionRaftLogIdExt;
-use crate::metrics::HeartbeatMetrics;
+use crate::membership::MembershipChangeReport;
+use crate::membership::MembershipValidator;
+use crate::membership::RemovalPlan;
+use crate::metrics::ApplyRate;
+use crate::metrics::CoreLoopMetrics;
+use crate::metrics::CoreLoopStats;
+use crate::metrics::HeartbeatAck;
+use crate::metrics::LeaderChange;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftMetrics;
+use crate::metrics::RaftNetworkMetrics;
+use crate::metrics::RaftReplicationMetrics;
 use crate::metrics::RaftServerMetrics;
-use crate::metrics::ReplicationMetrics;
+use crate::metrics::RaftStatus;
+use crate::metrics::ReplicationInflight;
+use crate::metrics::ReplicationStatus;
 use crate::metrics::SerdeInstant;
+use crate::metrics::ServerStateChange;
+use crate::metrics::SlowOp;
+use crate::metrics::SlowOpMetrics;
 use crate::network::v2::RaftNetworkV2;
 use crate::network::RPCOption;
 use crate::network::RPCTypes;
@@ -66,21 +100,27 @@ use crate::network::RaftNetworkFactory;
 use crate::progress::entry::ProgressEntry;
 use crate::progress::Progress;
 use crate::quorum::QuorumSet;
+use crate::raft::message::ReadIndexRequest;
 use crate::raft::message::TransferLeaderRequest;
 use crate::raft::responder::Responder;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
 use crate::raft::ClientWriteResponse;
+use crate::raft::RaftEvent;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::raft_state::io_state::io_id::IOId;
 use crate::raft_state::LogStateReader;
+use crate::replication::request::Data;
 use crate::replication::request::Replicate;
+use crate::replication::request::WriteOrigin;
 use crate::replication::ReplicationCore;
 use crate::replication::ReplicationHandle;
 use crate::replication::ReplicationSessionId;
 use crate::runtime::RaftRuntime;
 use crate::storage::IOFlushed;
+use crate::storage::LogStats;
+use crate::storage::PurgeProgress;
 use crate::storage::RaftLogStorage;
 use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
@@ -88,6 +128,8 @@ use crate::type_config::alias::MpscUnboundedReceiverOf;
 use crate::type_config::alias::MpscUnboundedSenderOf;
 use crate::type_config::alias::OneshotReceiverOf;
 use crate::type_config::alias::ResponderOf;
+use crate::type_config::alias::SleepOf;
+use crate::type_config::alias::VoteOf;
 use crate::type_config::alias::WatchSenderOf;
 use crate::type_config::async_runtime::MpscUnboundedReceiver;
 use crate::type_config::TypeConfigExt;
@@ -98,10 +140,12 @@ use crate::vote::vote_status::VoteStatus;
 use crate::vote::RaftLeaderId;
 use crate::vote::RaftVote;
 use crate::ChangeMembers;
+use crate::ConfigError;
 use crate::Instant;
 use crate::Membership;
 use crate::OptionalSend;
 use crate::RaftTypeConfig;
+use crate::RemovedLeaderAction;
 use crate::StorageError;
 
 /// A temp struct to hold the data for a node that is being applied.
@@ -135,7 +179,10 @@ pub(crate) struct ApplyResult<C: RaftTypeConfig> {
     pub(crate) end: u64,
     pub(crate) last_applied: LogIdOf<C>,
     pub(crate) applying_entries: Vec<ApplyingEntry<C>>,
-    pub(crate) apply_results: Vec<C::R>,
+    pub(crate) apply_results: Vec<Result<C::R, ApplyError<C>>>,
+
+    /// The time spent applying the entries.
+    pub(crate) elapsed: Duration,
 }
 
 impl<C: RaftTypeConfig> Debug for ApplyResult<C> {
@@ -192,6 +239,37 @@ where
     /// Channels to send result back to client when logs are applied.
     pub(crate) client_resp_channels: BTreeMap<u64, ResponderOf<C>>,
 
+    /// The tracing spans of the callers of client writes, keyed by log index, in which the apply
+    /// of the entries is recorded.
+    pub(crate) client_write_spans: BTreeMap<u64, Span>,
+
+    /// The correlation ids of client writes, keyed by log index, which are passed to replication
+    /// streams along with the logs to replicate.
+    pub(crate) correlation_ids: CorrelationIds,
+
+    /// The storage and network IO counters, shared with replication streams and heartbeat workers.
+    pub(crate) io_counters: Arc<IoCounters>,
+
+    /// Whether to accept new client writes.
+    ///
+    /// It is set to `false` when a graceful shutdown begins, so that only the already accepted
+    /// writes are waited for.
+    pub(crate) accept_writes: bool,
+
+    /// The log ids of the client writes that are accepted before a graceful shutdown begins and
+    /// are applied since then.
+    ///
+    /// A write whose log is truncated is not in it, even if another log at the same index is
+    /// applied.
+    pub(crate) drained_writes: Vec<LogIdOf<C>>,
+
+    /// Validates the membership configs before this leader proposes them.
+    pub(crate) membership_validator: Option<Box<dyn MembershipValidator<C>>>,
+
+    /// Whether to shut down after this leader is removed from the membership, see
+    /// [`RemovedLeaderAction::Shutdown`].
+    pub(crate) shutdown_when_removed: bool,
+
     /// A mapping of node IDs the replication state of the target node.
     pub(crate) replications: BTreeMap<C::NodeId, ReplicationHandle<C>>,
 
@@ -211,6 +289,104 @@ where
     pub(crate) tx_metrics: WatchSenderOf<C, RaftMetrics<C>>,
     pub(crate) tx_data_metrics: WatchSenderOf<C, RaftDataMetrics<C>>,
     pub(crate) tx_server_metrics: WatchSenderOf<C, RaftServerMetrics<C>>,
+    pub(crate) tx_replication_metrics: WatchSenderOf<C, RaftReplicationMetrics<C>>,
+    pub(crate) tx_network_metrics: WatchSenderOf<C, RaftNetworkMetrics<C>>,
+
+    /// Sends the last applied log id as soon as it changes, for [`Raft::wait_applied`].
+    ///
+    /// [`Raft::wait_applied`]: crate::Raft::wait_applied
+    pub(crate) tx_applied: WatchSenderOf<C, Option<LogIdOf<C>>>,
+
+    /// Sends the server state along with the vote as soon as either changes, for
+    /// [`Raft::server_state_watch`].
+    ///
+    /// [`Raft::server_state_watch`]: crate::Raft::server_state_watch
+    pub(crate) tx_server_state: WatchSenderOf<C, ServerStateChange<C>>,
+
+    /// Broadcasts [`RaftEvent`]s to the subscribers of [`Raft::event_stream`].
+    ///
+    /// [`Raft::event_stream`]: crate::Raft::event_stream
+    pub(crate) events: Arc<EventBroadcast<C>>,
+
+    /// The vote of the leadership the state machine was last notified of by
+    /// [`RaftStateMachine::on_become_leader`], or `None` if it is not notified as a leader.
+    ///
+    /// [`RaftStateMachine::on_become_leader`]: crate::storage::RaftStateMachine::on_become_leader
+    pub(crate) sm_leader_vote: Option<VoteOf<C>>,
+
+    /// The number of apply batches sent to the state machine worker that are not yet applied.
+    pub(crate) apply_inflight: u64,
+
+    /// Committed entries `[first, last]` to apply, which are not sent because the apply queue is
+    /// full. They are sent when a queued batch is applied.
+    pub(crate) pending_apply: Option<(LogIdOf<C>, LogIdOf<C>)>,
+
+    /// When to send the pending apply batch even if it is not full.
+    pub(crate) apply_deadline: Option<InstantOf<C>>,
+
+    /// Statistics of this loop in the current window.
+    pub(crate) core_loop_stats: CoreLoopStats<C>,
+
+    /// The saturation metrics of this loop in the last complete window.
+    pub(crate) core_loop_metrics: CoreLoopMetrics,
+
+    /// Estimates the recent apply rate, to report the replication lag in time.
+    pub(crate) apply_rate: ApplyRate<C>,
+
+    /// The log id of the last committed membership config that is sent as a
+    /// [`RaftEvent::MembershipCommitted`], or `None` if it is not yet initialized.
+    pub(crate) committed_membership: Option<Option<LogIdOf<C>>>,
+
+    /// The last change of the leader seen by this node.
+    pub(crate) last_leader_change: Option<LeaderChange<C>>,
+
+    /// The log appends submitted to the log store and their start time, in submission order.
+    pub(crate) appending: VecDeque<(IOId<C>, InstantOf<C>)>,
+
+    /// The number of slow operations by kind.
+    pub(crate) slow_ops: SlowOpMetrics,
+
+    /// The last acknowledged heartbeat or replication to every target, when this node is leader.
+    pub(crate) heartbeat_acks: BTreeMap<C::NodeId, HeartbeatAck<C>>,
+
+    /// The targets that the replication fails to, for which a [`RaftEvent::ReplicationStalled`]
+    /// is sent.
+    pub(crate) stalled_targets: BTreeSet<C::NodeId>,
+
+    /// Whether automatic ticks are paused: a paused node neither elects nor sends heartbeats
+    /// when a tick is received.
+    pub(crate) ticks_paused: bool,
+
+    /// Decides when to promote a learner or demote a voter automatically, when this node is
+    /// leader.
+    pub(crate) auto_membership: AutoMembership<C>,
+
+    /// The latest statistics polled from the log store.
+    pub(crate) log_stats: Option<LogStats>,
+
+    /// When to poll the log store statistics next time.
+    pub(crate) next_log_stats_poll: Option<InstantOf<C>>,
+
+    /// The earliest time to flush metrics next time, if [`Config::metrics_flush_interval`] is set.
+    pub(crate) next_metrics_flush: Option<InstantOf<C>>,
+
+    /// Whether there is a metrics flush postponed by [`Config::metrics_flush_interval`].
+    pub(crate) metrics_flush_postponed: bool,
+
+    /// The replication and heartbeat metrics last flushed.
+    pub(crate) replication_metrics: RaftReplicationMetrics<C>,
+
+    /// The earliest time to flush the replication metrics next time, if
+    /// [`Config::replication_metrics_flush_interval`] is set.
+    pub(crate) next_replication_metrics_flush: Option<InstantOf<C>>,
+
+    /// Whether there is a replication metrics flush postponed by
+    /// [`Config::replication_metrics_flush_interval`].
+    pub(crate) replication_metrics_flush_postponed: bool,
+
+    /// The RPC latency histograms recorded since startup, sent along with the replication
+    /// metrics.
+    pub(crate) network_metrics: RaftNetworkMetrics<C>,
 
     pub(crate) span: Span,
 }
@@ -224,10 +400,15 @@ where
     /// The main loop of the Raft protocol.
     pub(crate) async fn main(mut self, rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
         let span = tracing::span!(parent: &self.span, Level::DEBUG, "main");
+
+        let fatal_on_panic = FatalOnPanic::new(self.events.clone());
         let res = self.do_main(rx_shutdown).instrument(span).await;
+        drop(fatal_on_panic);
 
         // Flush buffered metrics
-        self.report_metrics(None, None);
+        self.report_replication_metrics(RaftReplicationMetrics::default());
+        self.report_network_metrics();
+        self.report_metrics();
 
         // Safe unwrap: res is Result<Infallible, _>
         let err = res.unwrap_err();
@@ -235,6 +416,13 @@ where
             Fatal::Stopped => { /* Normal quit */ }
             _ => {
                 tracing::error!(error = display(&err), "quit RaftCore::main on error");
+
+                self.engine.end_decision();
+                for decision in self.engine.decision_trace.iter() {
+                    tracing::error!("decision trace: {}", decision);
+                }
+
+                self.events.send(RaftEvent::Fatal { error: err.clone() });
             }
         }
 
@@ -261,7 +449,8 @@ where
         self.run_engine_commands().await?;
 
         // Initialize metrics.
-        self.report_metrics(None, None);
+        self.report_replication_metrics(RaftReplicationMetrics::default());
+        self.report_metrics();
 
         self.runtime_loop(rx_shutdown).await
     }
@@ -300,6 +489,12 @@ where
             (read_log_id, applied)
         };
 
+        if self.is_leader_lease_valid() {
+            tracing::debug!("leader lease is valid, skip confirming leadership with a quorum");
+            let _ = tx.send(Ok(resp));
+            return;
+        }
+
         let my_id = self.id.clone();
         let my_vote = self.engine.state.vote_ref().clone();
         let ttl = Duration::from_millis(self.config.heartbeat_interval);
@@ -340,6 +535,7 @@ where
             let mut client = self.network_factory.new_client(target.clone(), &target_node).await;
 
             let option = RPCOption::new(ttl);
+            let io_counters = self.io_counters.clone();
 
             let fu = {
                 let my_id = my_id.clone();
@@ -347,6 +543,7 @@ where
 
                 async move {
                     let outer_res = C::timeout(ttl, client.append_entries(rpc, option)).await;
+                    io_counters.record_rpc(RPCTypes::AppendEntries, &outer_res);
                     match outer_res {
                         Ok(append_res) => match append_res {
                             Ok(x) => Ok((target, x)),
@@ -455,8 +652,37 @@ where
     //       Because allowing this requires the engine to be able to store more than 2
     //       membership logs. And it does not need to wait for the previous membership log to commit
     //       to propose the new membership log.
+    ///
+    /// If `preflight` is `true`, the change is rejected with [`PreflightFailed`] unless the
+    /// [`MembershipChangeReport`] of it is ok.
     #[tracing::instrument(level = "debug", skip(self, tx))]
-    pub(super) fn change_membership(&mut self, changes: ChangeMembers<C>, retain: bool, tx: ResponderOf<C>) {
+    pub(super) fn change_membership(
+        &mut self,
+        changes: ChangeMembers<C>,
+        retain: bool,
+        preflight: bool,
+        tx: ResponderOf<C>,
+    ) {
+        if preflight {
+            let report = match self.change_membership_report(changes.clone(), retain) {
+                Ok(x) => x,
+                Err(forward_err) => {
+                    tx.send(Err(ClientWriteError::ForwardToLeader(forward_err)));
+                    return;
+                }
+            };
+
+            if !report.is_ok() {
+                tracing::info!(
+                    report = display(&report),
+                    "change membership rejected by pre-flight check"
+                );
+                let err = ChangeMembershipError::PreflightFailed(PreflightFailed::new(report));
+                tx.send(Err(ClientWriteError::ChangeMembershipError(err)));
+                return;
+            }
+        }
+
         let res = self.engine.state.membership_state.change_handler().apply(changes, retain);
         let new_membership = match res {
             Ok(x) => x,
@@ -466,10 +692,204 @@ where
             }
         };
 
+        if let Err(e) = self.validate_membership(&new_membership) {
+            tracing::info!(error = display(&e), "change membership rejected by validator");
+            tx.send(Err(ClientWriteError::ChangeMembershipError(e.into())));
+            return;
+        }
+
         let ent = C::Entry::new_membership(LogIdOf::<C>::default(), new_membership);
         self.write_entry(ent, Some(tx));
     }
 
+    /// Run the [`MembershipValidator`], if there is one, on a membership config to propose.
+    pub(super) fn validate_membership(&self, proposed: &Membership<C>) -> Result<(), MembershipRejected> {
+        let Some(validator) = &self.membership_validator else {
+            return Ok(());
+        };
+
+        let current = self.engine.state.membership_state.effective().membership();
+        validator.validate(current, proposed)
+    }
+
+    /// Build a report of what a change-membership request would do, without proposing it.
+    ///
+    /// The replication progress of the leader is used to find out the lagging voters, thus it
+    /// returns [`ForwardToLeader`] if this node is not a leader. The time for a lagging voter to
+    /// catch up is estimated with the recent apply rate of the leader.
+    pub(super) fn change_membership_report(
+        &mut self,
+        changes: ChangeMembers<C>,
+        retain: bool,
+    ) -> Result<MembershipChangeReport<C>, ForwardToLeader<C>> {
+        let max_lag = self.config.replication_lag_threshold;
+
+        let lh = self.engine.leader_handler()?;
+        let last_log_index = lh.state.last_log_id().index();
+        let progress = &lh.leader.progress;
+        let apply_rate = &self.apply_rate;
+
+        let report = lh.state.membership_state.change_handler().report(
+            changes,
+            retain,
+            last_log_index,
+            max_lag,
+            |id| progress.try_get(id).and_then(|p| p.matching().index()),
+            |entries_behind| apply_rate.lag(entries_behind),
+        );
+
+        Ok(report)
+    }
+
+    /// Take the [`RemovedLeaderAction`] before stepping down, if this leader has committed a
+    /// membership config in which it is not a voter.
+    ///
+    /// For [`RemovedLeaderAction::TransferLeader`] and [`RemovedLeaderAction::Shutdown`], the
+    /// leadership is transferred to the voter with the greatest matching log id.
+    fn take_removed_leader_action(&mut self) {
+        let Some(leader) = self.engine.leader_ref() else {
+            return;
+        };
+
+        let effective = self.engine.state.membership_state.effective();
+        if effective.is_voter(&self.id) || effective.log_id().as_ref() > self.engine.state.committed() {
+            return;
+        }
+
+        let Some(log_id) = effective.log_id().clone() else {
+            return;
+        };
+
+        let target = effective
+            .voter_ids()
+            .filter(|id| id != &self.id)
+            .max_by_key(|id| leader.progress.try_get(id).and_then(|p| p.matching().cloned()));
+
+        let action = self.config.removed_leader_action;
+
+        tracing::info!(
+            log_id = display(&log_id),
+            action = display(action),
+            target = display(target.display()),
+            "this leader is removed from the membership"
+        );
+
+        if action != RemovedLeaderAction::StepDown {
+            if let Some(target) = target {
+                self.engine.trigger_transfer_leader(target);
+            }
+        }
+
+        if action == RemovedLeaderAction::Shutdown {
+            self.shutdown_when_removed = true;
+        }
+
+        self.events.send(RaftEvent::RemovedFromMembership { log_id, action });
+    }
+
+    /// Build a plan to remove the nodes that have not acknowledged this leader for at least
+    /// `min_downtime`.
+    ///
+    /// It returns [`ForwardToLeader`] if this node is not a leader, because only the leader knows
+    /// when a node last acknowledged it.
+    pub(super) fn unreachable_removal_plan(
+        &mut self,
+        min_downtime: Duration,
+    ) -> Result<RemovalPlan<C>, ForwardToLeader<C>> {
+        let now = C::now();
+
+        let lh = self.engine.leader_handler()?;
+        let established_at = lh.leader.established_at;
+        let clock_progress = &lh.leader.clock_progress;
+
+        let membership_state = &lh.state.membership_state;
+        let current = membership_state.effective().membership().clone();
+        let in_progress = membership_state.change_handler().ensure_committed().err();
+
+        let plan = RemovalPlan::new(
+            current,
+            &self.id,
+            min_downtime,
+            |id| {
+                let acked = clock_progress.try_get(id).copied().flatten();
+                now - acked.unwrap_or(established_at)
+            },
+            in_progress,
+        );
+
+        Ok(plan)
+    }
+
+    /// Apply a [`ConfigPatch`] to the config in use.
+    ///
+    /// The patched config is validated before replacing the config of `RaftCore`, `Engine` and the
+    /// heartbeat workers handle, thus an invalid patch changes nothing.
+    ///
+    /// Running replication streams and heartbeat workers keep the config they are spawned with.
+    /// They do not read any value a [`ConfigPatch`] can change.
+    pub(super) fn update_config(&mut self, patch: ConfigPatch) -> Result<Arc<Config>, ConfigError> {
+        let config = Arc::new(patch.apply(&self.config)?);
+
+        tracing::info!(patch = debug(&patch), "update config");
+
+        self.engine.config.update(&self.config, &config);
+        self.heartbeat_handle.config = config.clone();
+        self.config = config.clone();
+
+        Ok(config)
+    }
+
+    /// Build the [`EffectiveConfig`] from the config in use and the derived values in `Engine`.
+    ///
+    /// `enable_tick` is left `false` since the ticker is not controlled by `RaftCore`.
+    pub(super) fn effective_config(&self) -> EffectiveConfig {
+        let timer_config = &self.engine.config.timer_config;
+
+        EffectiveConfig {
+            config: self.config.clone(),
+            election_timeout: timer_config.election_timeout,
+            smaller_log_election_timeout: timer_config.election_timeout + timer_config.smaller_log_timeout,
+            leader_lease: timer_config.leader_lease,
+            snapshot_policy: self.engine.config.snapshot_policy.clone(),
+            enable_tick: false,
+            enable_heartbeat: self.runtime_config.enable_heartbeat.load(Ordering::Relaxed),
+            enable_elect: self.runtime_config.enable_elect.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Build a consistent view of the status of this node.
+    ///
+    /// The metrics are flushed first, so that they reflect the messages handled in the current
+    /// iteration.
+    pub(super) fn status(&mut self) -> RaftStatus<C> {
+        self.flush_metrics();
+
+        let replication = self.engine.leader.as_ref().map(|leader| {
+            leader
+                .progress
+                .iter()
+                .map(|(id, p)| {
+                    let st = ReplicationStatus {
+                        matching: p.matching().cloned(),
+                        searching_end: p.searching_end,
+                        inflight: ReplicationInflight::new(&p.inflight),
+                    };
+                    (id.clone(), st)
+                })
+                .collect()
+        });
+
+        RaftStatus {
+            server: self.tx_server_metrics.borrow_watched().clone(),
+            data: self.tx_data_metrics.borrow_watched().clone(),
+            committed_membership: Arc::new(
+                self.engine.state.membership_state.committed().stored_membership().as_ref().clone(),
+            ),
+            replication,
+            config: self.effective_config(),
+        }
+    }
+
     /// Write a log entry to the cluster through raft protocol.
     ///
     /// I.e.: append the log entry to local store, forward it to a quorum(including the leader),
@@ -477,13 +897,20 @@ where
     ///
     /// The result of applying it to state machine is sent to `resp_tx`, if it is not `None`.
     /// The calling side may not receive a result from `resp_tx`, if raft is shut down.
+    ///
+    /// It returns the log id assigned to the entry, or `None` if the entry is rejected.
     #[tracing::instrument(level = "debug", skip_all, fields(id = display(&self.id)))]
-    pub fn write_entry(&mut self, entry: C::Entry, resp_tx: Option<ResponderOf<C>>) {
+    pub fn write_entry(&mut self, entry: C::Entry, resp_tx: Option<ResponderOf<C>>) -> Option<LogIdOf<C>> {
         tracing::debug!(payload = display(&entry), "write_entry");
 
-        let Some((mut lh, tx)) = self.engine.get_leader_handler_or_reject(resp_tx) else {
-            return;
-        };
+        if !self.accept_writes {
+            if let Some(tx) = resp_tx {
+                tx.send(Err(ClientWriteError::ShuttingDown(ShuttingDown)));
+            }
+            return None;
+        }
+
+        let (mut lh, tx) = self.engine.get_leader_handler_or_reject(resp_tx)?;
 
         // If the leader is transferring leadership, forward writes to the new leader.
         if let Some(to) = lh.leader.get_transfer_to() {
@@ -491,19 +918,79 @@ where
                 let err = lh.state.new_forward_to_leader(to.clone());
                 tx.send(Err(ClientWriteError::ForwardToLeader(err)));
             }
-            return;
+            return None;
         }
 
         let entries = vec![entry];
         // TODO: it should returns membership config error etc. currently this is done by the
         //       caller.
         lh.leader_append_entries(entries);
-        let index = lh.state.last_log_id().unwrap().index();
+        let log_id = lh.state.last_log_id().unwrap().clone();
 
         // Install callback channels.
         if let Some(tx) = tx {
-            self.client_resp_channels.insert(index, tx);
+            self.client_resp_channels.insert(log_id.index(), tx);
+        }
+
+        Some(log_id)
+    }
+
+    /// Write a batch of log entries to the cluster in one pass.
+    ///
+    /// The entries are appended as consecutive logs with a single storage append, and the result
+    /// of applying the `i`-th entry is sent to `resp_txs[i]`.
+    ///
+    /// It returns the range of the log indexes assigned to the entries, or `None` if they are
+    /// rejected.
+    #[tracing::instrument(level = "debug", skip_all, fields(id = display(&self.id)))]
+    pub(crate) fn write_entries(
+        &mut self,
+        entries: Vec<C::Entry>,
+        resp_txs: Vec<ResponderOf<C>>,
+    ) -> Option<Range<u64>> {
+        tracing::debug!(n = entries.len(), "write_entries");
+        debug_assert_eq!(entries.len(), resp_txs.len());
+
+        if entries.is_empty() {
+            return None;
+        }
+
+        if !self.accept_writes {
+            for tx in resp_txs {
+                tx.send(Err(ClientWriteError::ShuttingDown(ShuttingDown)));
+            }
+            return None;
+        }
+
+        let mut lh = match self.engine.leader_handler() {
+            Ok(lh) => lh,
+            Err(forward_err) => {
+                for tx in resp_txs {
+                    tx.send(Err(forward_err.clone().into()));
+                }
+                return None;
+            }
+        };
+
+        // If the leader is transferring leadership, forward writes to the new leader.
+        if let Some(to) = lh.leader.get_transfer_to() {
+            let err = lh.state.new_forward_to_leader(to.clone());
+            for tx in resp_txs {
+                tx.send(Err(ClientWriteError::ForwardToLeader(err.clone())));
+            }
+            return None;
+        }
+
+        let n = entries.len() as u64;
+        lh.leader_append_entries(entries);
+        let first_index = lh.state.last_log_id().unwrap().index() + 1 - n;
+
+        // Install callback channels.
+        for (i, tx) in resp_txs.into_iter().enumerate() {
+            self.client_resp_channels.insert(first_index + i as u64, tx);
         }
+
+        Some(first_index..first_index + n)
     }
 
     /// Send a heartbeat message to every follower/learners.
@@ -537,29 +1024,90 @@ where
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub fn flush_metrics(&mut self) {
-        let (replication, heartbeat) = if let Some(leader) = self.engine.leader.as_ref() {
+        self.flush_replication_metrics();
+        self.report_metrics();
+    }
+
+    /// Build the replication and heartbeat metrics of the current state and send them.
+    fn flush_replication_metrics(&mut self) {
+        let applied_index = self.engine.state.io_applied().next_index();
+        self.apply_rate.update(C::now(), applied_index);
+
+        let replication_metrics = if let Some(leader) = self.engine.leader.as_ref() {
             let replication_prog = &leader.progress;
             let replication =
                 Some(replication_prog.iter().map(|(id, p)| (id.clone(), p.matching().cloned())).collect());
 
+            let last_log_next = self.engine.state.last_log_id().next_index();
+            let replication_lag = Some(
+                replication_prog
+                    .iter()
+                    .map(|(id, p)| {
+                        let entries_behind = last_log_next.saturating_sub(p.matching().next_index());
+                        (id.clone(), self.apply_rate.lag(entries_behind))
+                    })
+                    .collect(),
+            );
+
+            let replication_inflight = Some(
+                replication_prog.iter().map(|(id, p)| (id.clone(), ReplicationInflight::new(&p.inflight))).collect(),
+            );
+
             let clock_prog = &leader.clock_progress;
             let heartbeat =
                 Some(clock_prog.iter().map(|(id, opt_t)| (id.clone(), opt_t.map(SerdeInstant::new))).collect());
 
-            (replication, heartbeat)
+            let heartbeat_ack = Some(self.heartbeat_acks.clone());
+
+            RaftReplicationMetrics {
+                replication,
+                replication_lag,
+                replication_inflight,
+                heartbeat,
+                heartbeat_ack,
+            }
         } else {
-            (None, None)
+            RaftReplicationMetrics::default()
         };
-        self.report_metrics(replication, heartbeat);
+        self.report_replication_metrics(replication_metrics);
+        self.report_network_metrics();
+    }
+
+    /// Send the RPC latency histograms if they change.
+    fn report_network_metrics(&mut self) {
+        let network_metrics = &self.network_metrics;
+        self.tx_network_metrics.send_if_modified(|metrix| {
+            if network_metrics.ne(metrix) {
+                *metrix = network_metrics.clone();
+                return true;
+            }
+            false
+        });
+    }
+
+    /// Send the replication and heartbeat metrics if they change, and keep them for the other
+    /// metrics reported later.
+    fn report_replication_metrics(&mut self, replication_metrics: RaftReplicationMetrics<C>) {
+        self.tx_replication_metrics.send_if_modified(|metrix| {
+            if replication_metrics.ne(metrix) {
+                *metrix = replication_metrics.clone();
+                return true;
+            }
+            false
+        });
+
+        self.replication_metrics = replication_metrics;
     }
 
     /// Report a metrics payload on the current state of the Raft node.
+    ///
+    /// The replication and heartbeat fields are those last flushed by
+    /// [`Self::flush_replication_metrics()`].
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(crate) fn report_metrics(
-        &mut self,
-        replication: Option<ReplicationMetrics<C>>,
-        heartbeat: Option<HeartbeatMetrics<C>>,
-    ) {
+    pub(crate) fn report_metrics(&mut self) {
+        let replication = self.replication_metrics.replication.clone();
+        let heartbeat = self.replication_metrics.heartbeat.clone();
+
         let last_quorum_acked = self.last_quorum_acked_time();
         let millis_since_quorum_ack = last_quorum_acked.map(|t| t.elapsed().as_millis() as u64);
 
@@ -568,6 +1116,14 @@ where
         let membership_config = st.membership_state.effective().stored_membership().clone();
         let current_leader = self.current_leader();
 
+        let election = {
+            let mut m = self.engine.election_metrics.clone();
+            let in_progress = self.engine.candidate_ref().is_some() as u64;
+            m.elections_lost = m.elections_started.saturating_sub(m.elections_won + in_progress);
+            m.last_leader_change = self.last_leader_change.clone();
+            m
+        };
+
         #[allow(deprecated)]
         let m = RaftMetrics {
             running_state: Ok(()),
@@ -588,6 +1144,7 @@ where
             last_quorum_acked: last_quorum_acked.map(SerdeInstant::new),
             membership_config: membership_config.clone(),
             heartbeat: heartbeat.clone(),
+            config: self.config.clone(),
 
             // --- replication ---
             replication: replication.clone(),
@@ -599,10 +1156,17 @@ where
             last_applied: st.io_applied().cloned(),
             snapshot: st.io_snapshot_last_log_id().cloned(),
             purged: st.io_purged().cloned(),
+            snapshot_last_log_id: st.snapshot_last_log_id().cloned(),
+            purge_upto: st.purge_upto().cloned(),
+            purge_submitted: st.last_purged_log_id().cloned(),
             millis_since_quorum_ack,
             last_quorum_acked: last_quorum_acked.map(SerdeInstant::new),
             replication,
             heartbeat,
+            log_stats: self.log_stats.clone(),
+            core_loop: self.core_loop_metrics,
+            slow_ops: self.slow_ops,
+            io: self.io_counters.metrics(),
         };
 
         let server_metrics = RaftServerMetrics {
@@ -611,6 +1175,8 @@ where
             state: st.server_state,
             current_leader,
             membership_config,
+            ticks_paused: self.ticks_paused,
+            election,
         };
 
         // Start to send metrics
@@ -635,11 +1201,13 @@ where
         });
 
         tracing::debug!("report_metrics: {}", m);
-        let res = self.tx_metrics.send(m);
-
-        if let Err(err) = res {
-            tracing::error!(error=%err, id=display(&self.id), "error reporting metrics");
-        }
+        self.tx_metrics.send_if_modified(|metrix| {
+            if m.ne(metrix) {
+                *metrix = m;
+                return true;
+            }
+            false
+        });
     }
 
     /// Handle the admin command `initialize`.
@@ -652,11 +1220,19 @@ where
     pub(crate) fn handle_initialize(
         &mut self,
         member_nodes: BTreeMap<C::NodeId, C::Node>,
+        learner_nodes: BTreeMap<C::NodeId, C::Node>,
         tx: ResultSender<C, (), InitializeError<C>>,
     ) {
-        tracing::debug!(member_nodes = debug(&member_nodes), "{}", func_name!());
+        tracing::debug!(
+            member_nodes = debug(&member_nodes),
+            learner_nodes = debug(&learner_nodes),
+            "{}",
+            func_name!()
+        );
 
-        let membership = Membership::from(member_nodes);
+        let voter_ids = member_nodes.keys().cloned().collect();
+        let nodes = Membership::<C>::extend_nodes(member_nodes, &learner_nodes);
+        let membership = Membership::new_unchecked(vec![voter_ids], nodes);
 
         let entry = C::Entry::new_membership(LogIdOf::<C>::default(), membership);
         let res = self.engine.initialize(entry);
@@ -702,6 +1278,70 @@ where
         let _ = tx.send(Err(err.into()));
     }
 
+    /// Get a read log id for a linearizable read on this node.
+    ///
+    /// A leader confirms its leadership itself. A non-leader node sends a ReadIndex RPC to the
+    /// current leader.
+    #[tracing::instrument(level = "debug", skip(self, tx))]
+    pub(super) async fn handle_read_index_via_leader(
+        &mut self,
+        tx: ResultSender<C, Option<LogIdOf<C>>, ReadIndexError<C>>,
+    ) {
+        if self.engine.leader.is_some() {
+            let (tx_read, rx_read) = C::oneshot();
+            self.handle_check_is_leader_request(tx_read).await;
+
+            let _handle = C::spawn(async move {
+                // Error: RaftCore is shutting down.
+                if let Ok(res) = rx_read.await {
+                    let res = res.map(|(read_log_id, _applied)| read_log_id).map_err(ReadIndexError::from);
+                    let _ = tx.send(res);
+                }
+            });
+            return;
+        }
+
+        let leader_id = self.current_leader();
+        let leader_node = self.get_leader_node(leader_id.clone());
+
+        let (Some(leader_id), Some(leader_node)) = (leader_id, leader_node) else {
+            self.reject_with_forward_to_leader(tx);
+            return;
+        };
+
+        let mut client = self.network_factory.new_client(leader_id.clone(), &leader_node).await;
+
+        // The leader has to confirm its leadership with a quorum before responding.
+        let ttl = Duration::from_millis(self.config.election_timeout_min);
+        let req = ReadIndexRequest::new(self.id.clone());
+        let my_id = self.id.clone();
+        let target = leader_id.clone();
+        let io_counters = self.io_counters.clone();
+
+        let fu = async move {
+            let res = C::timeout(ttl, client.read_index(req, RPCOption::new(ttl))).await;
+            io_counters.record_rpc(RPCTypes::ReadIndex, &res);
+
+            let res = match res {
+                Ok(read_res) => read_res.map_err(ReadIndexError::from),
+                Err(_timeout) => {
+                    let timeout_err = Timeout::<C> {
+                        action: RPCTypes::ReadIndex,
+                        id: my_id,
+                        target,
+                        timeout: ttl,
+                    };
+                    Err(ReadIndexError::RPCError(RPCError::Timeout(timeout_err)))
+                }
+            };
+
+            let _ = tx.send(res);
+        };
+
+        let fu = fu.instrument(tracing::debug_span!("read_index", target = display(&leader_id)));
+        let _handle = C::spawn(fu);
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn current_leader(&self) -> Option<C::NodeId> {
         tracing::debug!(
@@ -729,6 +1369,30 @@ where
         }
     }
 
+    /// Returns `true` if lease reads are enabled and this leader's lease has not yet expired.
+    ///
+    /// See: [`Config::enable_lease_read`](crate::Config::enable_lease_read).
+    fn is_leader_lease_valid(&mut self) -> bool {
+        if !self.config.enable_lease_read {
+            return false;
+        }
+
+        let Some(leader) = self.engine.leader.as_mut() else {
+            return false;
+        };
+
+        // The target of a leadership transfer may be elected before the lease expires.
+        if leader.get_transfer_to().is_some() {
+            return false;
+        }
+
+        let Some(acked) = leader.last_quorum_acked_time() else {
+            return false;
+        };
+
+        C::now() < acked + self.config.lease_read_duration()
+    }
+
     /// Retrieves the most recent timestamp that is acknowledged by a quorum.
     ///
     /// This function returns the latest known time at which the leader received acknowledgment
@@ -761,12 +1425,213 @@ where
             last.index()
         );
 
+        let first = match self.pending_apply.take() {
+            Some((pending_first, _)) => pending_first,
+            None => first,
+        };
+        self.pending_apply = Some((first, last));
+
+        self.send_pending_apply(false)
+    }
+
+    /// Send the pending apply batch to the state machine worker.
+    ///
+    /// Unless `force` is `true`, the batch is kept pending if the apply queue is full, or if it is
+    /// smaller than [`Config::apply_batch_size`] and [`Config::apply_batch_max_delay`] is not
+    /// reached yet.
+    pub(crate) fn send_pending_apply(&mut self, force: bool) -> Result<(), StorageError<C>> {
+        let Some((first, last)) = self.pending_apply.clone() else {
+            return Ok(());
+        };
+
+        if !force {
+            if self.apply_inflight >= self.config.apply_queue_size {
+                tracing::debug!(
+                    "apply queue is full({}), pending apply: {}..={}",
+                    self.apply_inflight,
+                    first,
+                    last
+                );
+                return Ok(());
+            }
+
+            let n = last.index() - first.index() + 1;
+            let max_delay = self.config.apply_batch_max_delay;
+
+            if n < self.config.apply_batch_size && max_delay > 0 {
+                let now = C::now();
+                let deadline = match self.apply_deadline {
+                    Some(t) => t,
+                    None => {
+                        let t = now + Duration::from_millis(max_delay);
+                        self.apply_deadline = Some(t);
+                        self.wake_up_at_apply_deadline(max_delay);
+                        t
+                    }
+                };
+
+                if now < deadline {
+                    tracing::debug!("wait for a full apply batch: {}..={}, has {} entries", first, last, n);
+                    return Ok(());
+                }
+            }
+        }
+
+        self.pending_apply = None;
+        self.apply_deadline = None;
+        self.send_apply(first, last)
+    }
+
+    /// Notify RaftCore to send the pending apply batch when the max apply delay is reached.
+    fn wake_up_at_apply_deadline(&self, delay_ms: u64) {
+        let tx = self.tx_notification.downgrade();
+        let _handle = C::spawn(async move {
+            C::sleep(Duration::from_millis(delay_ms)).await;
+            if let Some(tx) = tx.upgrade() {
+                let _ = tx.send(Notification::ApplyDelayExpired);
+            }
+        });
+    }
+
+    fn send_apply(&mut self, first: LogIdOf<C>, last: LogIdOf<C>) -> Result<(), StorageError<C>> {
         let cmd = sm::Command::apply(first, last.clone());
         self.sm_handle.send(cmd).map_err(|e| StorageError::apply(last, AnyError::error(e)))?;
+        self.apply_inflight += 1;
+
+        Ok(())
+    }
+
+    /// Notify the state machine if this node became or is no longer the leader since the last
+    /// notification.
+    ///
+    /// Entries committed before the change are sent to apply first, so that the state machine
+    /// sees the change in order with the applied entries.
+    fn notify_leader_change(&mut self) -> Result<(), StorageError<C>> {
+        let leader_vote = self.engine.leader.as_ref().map(|l| l.committed_vote_ref().clone().into_vote());
+
+        if leader_vote == self.sm_leader_vote {
+            return Ok(());
+        }
+
+        self.send_pending_apply(true)?;
+
+        self.auto_membership.reset();
+
+        let send_err =
+            |_e| StorageError::write_state_machine(AnyError::error("can not send to sm::Worker".to_string()));
+
+        if let Some(vote) = self.sm_leader_vote.take() {
+            self.sm_handle.send(sm::Command::step_down()).map_err(send_err)?;
+            self.stalled_targets.clear();
+            self.heartbeat_acks.clear();
+            self.events.send(RaftEvent::SteppedDown { vote });
+        }
+
+        if let Some(vote) = leader_vote {
+            self.sm_handle.send(sm::Command::become_leader(vote.clone())).map_err(send_err)?;
+            self.sm_leader_vote = Some(vote.clone());
+            self.events.send(RaftEvent::LeaderElected { vote });
+        }
 
         Ok(())
     }
 
+    /// Log a warning and count it in the metrics if an operation on `item` takes longer than the
+    /// configured threshold.
+    fn check_slow_op(&mut self, op: SlowOp, elapsed: Duration, item: impl fmt::Display) {
+        let Some(threshold) = op.threshold(&self.config) else {
+            return;
+        };
+
+        if elapsed <= threshold {
+            return;
+        }
+
+        tracing::warn!(
+            op = display(op),
+            elapsed = debug(elapsed),
+            threshold = debug(threshold),
+            item = display(item),
+            "slow operation"
+        );
+
+        self.slow_ops.incr(op);
+    }
+
+    /// Record the round trip time of an acknowledged heartbeat or replication request sent at
+    /// `sending_time`.
+    ///
+    /// A delayed ack of an earlier request is ignored.
+    fn update_heartbeat_ack(&mut self, target: &C::NodeId, sending_time: InstantOf<C>) {
+        if let Some(prev) = self.heartbeat_acks.get(target) {
+            if *prev.sent_at > sending_time {
+                return;
+            }
+        }
+
+        let rtt = C::now().saturating_duration_since(sending_time);
+        self.heartbeat_acks.insert(target.clone(), HeartbeatAck::new(SerdeInstant::new(sending_time), rtt));
+    }
+
+    /// Send a [`RaftEvent::MembershipCommitted`] if the committed membership config changes.
+    ///
+    /// The membership config that is already committed when this node starts is not sent.
+    fn notify_membership_committed(&mut self) {
+        let committed = self.engine.state.membership_state.committed();
+        let log_id = committed.log_id().clone();
+
+        let Some(prev) = &self.committed_membership else {
+            self.committed_membership = Some(log_id);
+            return;
+        };
+
+        if prev >= &log_id {
+            return;
+        }
+
+        let membership = committed.stored_membership().as_ref().clone();
+        self.committed_membership = Some(log_id);
+        self.events.send(RaftEvent::MembershipCommitted { membership });
+    }
+
+    /// Send the last applied log id to the applied watch channel if it changed.
+    fn notify_applied(&self) {
+        let applied = self.engine.state.io_applied();
+
+        self.tx_applied.send_if_modified(|x| {
+            if x.as_ref() != applied {
+                *x = applied.cloned();
+                return true;
+            }
+            false
+        });
+    }
+
+    /// Send the server state and vote if they change, and record the time the leader changes.
+    fn notify_server_state(&mut self) {
+        // Record the leader change before waking up the subscribers of the server state, so that a
+        // subscriber never observes a change that happened before the recorded time.
+        let current_leader = self.current_leader();
+        let last_leader = self.last_leader_change.as_ref().and_then(|c| c.leader.clone());
+        if current_leader != last_leader {
+            self.last_leader_change = Some(LeaderChange {
+                leader: current_leader,
+                term: self.engine.state.vote_ref().term(),
+                at: SerdeInstant::new(C::now()),
+            });
+        }
+
+        let st = &self.engine.state;
+
+        self.tx_server_state.send_if_modified(|x| {
+            if x.state != st.server_state || &x.vote != st.vote_ref() {
+                *x = ServerStateChange::new(st.server_state, st.vote_ref().clone());
+                return true;
+            }
+            false
+        });
+    }
+
     /// When received results of applying log entries to the state machine, send back responses to
     /// the callers that proposed the entries.
     #[tracing::instrument(level = "debug", skip_all)]
@@ -781,13 +1646,33 @@ where
             let apply_res = results.next().unwrap();
             let tx = self.client_resp_channels.remove(&log_index);
 
+            if !self.accept_writes && tx.is_some() {
+                self.drained_writes.push(ent.log_id.clone());
+            }
+
+            if let Some(correlation_id) = self.correlation_ids.remove(log_index) {
+                tracing::debug!(
+                    correlation_id = display(correlation_id),
+                    log_id = display(&ent.log_id),
+                    "client write is applied"
+                );
+            }
+
+            if let Some(span) = self.client_write_spans.remove(&log_index) {
+                tracing::debug!(parent: &span, log_id = display(&ent.log_id), "client write is applied");
+            }
+
             Self::send_response(ent, apply_res, tx);
         }
     }
 
     /// Send result of applying a log entry to its client.
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(super) fn send_response(entry: ApplyingEntry<C>, resp: C::R, tx: Option<ResponderOf<C>>) {
+    pub(super) fn send_response(
+        entry: ApplyingEntry<C>,
+        resp: Result<C::R, ApplyError<C>>,
+        tx: Option<ResponderOf<C>>,
+    ) {
         tracing::debug!(entry = debug(&entry), "send_response");
 
         let tx = match tx {
@@ -797,11 +1682,14 @@ where
 
         let membership = entry.membership;
 
-        let res = Ok(ClientWriteResponse {
-            log_id: entry.log_id,
-            data: resp,
-            membership,
-        });
+        let res = match resp {
+            Ok(data) => Ok(ClientWriteResponse {
+                log_id: entry.log_id,
+                data,
+                membership,
+            }),
+            Err(apply_err) => Err(ClientWriteError::ApplyError(apply_err)),
+        };
 
         tx.send(res);
     }
@@ -836,6 +1724,7 @@ where
             self.log_store.get_log_reader().await,
             self.sm_handle.new_snapshot_reader(),
             self.tx_notification.clone(),
+            self.io_counters.clone(),
             tracing::span!(parent: &self.span, Level::DEBUG, "replication", id=display(&self.id), target=display(&target)),
         )
     }
@@ -870,8 +1759,17 @@ where
     ///
     /// If there is a command that waits for a callback, just return and wait for
     /// next RaftMsg.
+    ///
+    /// It returns [`Fatal::InvariantViolation`] without running any command if the engine has
+    /// found an invariant violated.
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(crate) async fn run_engine_commands(&mut self) -> Result<(), StorageError<C>> {
+    pub(crate) async fn run_engine_commands(&mut self) -> Result<(), Fatal<C>> {
+        self.engine.end_decision();
+
+        if let Some(violation) = self.engine.output.take_invariant_violation() {
+            return Err(Fatal::from(violation));
+        }
+
         if tracing::enabled!(Level::DEBUG) {
             tracing::debug!("queued commands: start...");
             for c in self.engine.output.iter_commands() {
@@ -881,6 +1779,7 @@ where
         }
 
         while let Some(cmd) = self.engine.output.pop_command() {
+            self.core_loop_stats.add_command();
             let res = self.run_command(cmd).await?;
 
             if let Some(cmd) = res {
@@ -914,7 +1813,16 @@ where
         let mut balancer = Balancer::new(10_000);
 
         loop {
-            self.flush_metrics();
+            self.core_loop_stats.end_iteration();
+            if let Some(m) = self.core_loop_stats.complete_window(C::now()) {
+                self.core_loop_metrics = m;
+            }
+
+            self.poll_log_stats().await;
+            self.notify_leader_change()?;
+            self.notify_server_state();
+            self.notify_membership_committed();
+            self.flush_metrics_if_due();
 
             tracing::debug!(
                 "RAFT_stats id={:<2} log_io: {}",
@@ -931,6 +1839,8 @@ where
             // `select!` without `biased` provides a random fairness.
             // We want to check shutdown prior to other channels.
             // See: https://docs.rs/tokio/latest/tokio/macro.select.html#fairness
+            let metrics_flush = self.postponed_metrics_flush();
+            let idle_start = C::now();
             futures::select_biased! {
                 _ = (&mut rx_shutdown).fuse() => {
                     tracing::info!("recv from rx_shutdown");
@@ -947,6 +1857,10 @@ where
                     };
                 }
 
+                _ = metrics_flush.fuse() => {
+                    // Postponed metrics are flushed at the beginning of the next iteration.
+                }
+
                 msg_res = self.rx_api.recv().fuse() => {
                     match msg_res {
                         Some(msg) => self.handle_api_msg(msg).await,
@@ -957,6 +1871,7 @@ where
                     };
                 }
             }
+            self.core_loop_stats.add_idle(idle_start.elapsed());
 
             self.run_engine_commands().await?;
 
@@ -983,6 +1898,79 @@ where
                 lh.replication_handler().initiate_replication();
             }
             self.run_engine_commands().await?;
+
+            if self.shutdown_when_removed {
+                tracing::info!("shut down RaftCore: this leader is removed from the membership");
+                return Err(Fatal::Stopped);
+            }
+        }
+    }
+
+    /// Flush metrics if [`Config::metrics_flush_interval`] has elapsed since the last flush,
+    /// otherwise postpone it until the interval elapses.
+    ///
+    /// The replication metrics are flushed on their own schedule, by
+    /// [`Config::replication_metrics_flush_interval`].
+    fn flush_metrics_if_due(&mut self) {
+        let now = C::now();
+
+        if self.next_replication_metrics_flush.is_some_and(|t| now < t) {
+            self.replication_metrics_flush_postponed = true;
+        } else {
+            self.flush_replication_metrics();
+
+            self.replication_metrics_flush_postponed = false;
+            if self.config.replication_metrics_flush_interval > 0 {
+                let interval = Duration::from_millis(self.config.replication_metrics_flush_interval);
+                self.next_replication_metrics_flush = Some(now + interval);
+            }
+        }
+
+        if self.next_metrics_flush.is_some_and(|t| now < t) {
+            self.metrics_flush_postponed = true;
+            return;
+        }
+
+        self.report_metrics();
+
+        self.metrics_flush_postponed = false;
+        if self.config.metrics_flush_interval > 0 {
+            self.next_metrics_flush = Some(now + Duration::from_millis(self.config.metrics_flush_interval));
+        }
+    }
+
+    /// Returns a future that resolves when a postponed metrics or replication metrics flush is
+    /// due, or never resolves if there is none.
+    fn postponed_metrics_flush(&self) -> Either<SleepOf<C>, Pending<()>> {
+        let metrics = self.next_metrics_flush.filter(|_| self.metrics_flush_postponed);
+        let replication = self.next_replication_metrics_flush.filter(|_| self.replication_metrics_flush_postponed);
+
+        match [metrics, replication].into_iter().flatten().min() {
+            Some(t) => Either::Left(C::sleep_until(t)),
+            None => Either::Right(futures::future::pending()),
+        }
+    }
+
+    /// Poll [`RaftLogStorage::stats()`] if [`Config::log_stats_interval`] has elapsed since the
+    /// last poll.
+    ///
+    /// A failure to get the statistics is not fatal, the last polled statistics are kept.
+    async fn poll_log_stats(&mut self) {
+        if self.config.log_stats_interval == 0 {
+            return;
+        }
+
+        let now = C::now();
+        if self.next_log_stats_poll.is_some_and(|t| now < t) {
+            return;
+        }
+        self.next_log_stats_poll = Some(now + Duration::from_millis(self.config.log_stats_interval));
+
+        match self.log_store.stats().await {
+            Ok(stats) => self.log_stats = stats,
+            Err(e) => {
+                tracing::warn!(error = display(&e), "failed to poll log store stats");
+            }
         }
     }
 
@@ -1082,6 +2070,7 @@ where
             let ttl = Duration::from_millis(self.config.election_timeout_min);
             let id = self.id.clone();
             let option = RPCOption::new(ttl);
+            let io_counters = self.io_counters.clone();
 
             let vote = vote.clone();
 
@@ -1092,6 +2081,7 @@ where
                     let target = target.clone();
                     async move {
                         let tm_res = C::timeout(ttl, client.vote(req, option)).await;
+                        io_counters.record_rpc(RPCTypes::Vote, &tm_res);
                         let res = match tm_res {
                             Ok(res) => res,
 
@@ -1128,7 +2118,14 @@ where
         }
     }
 
-    /// Spawn parallel vote requests to all cluster members.
+    /// Send the TransferLeader request to all other voters.
+    ///
+    /// Every voter is sent the request in its own task, so that a slow or unreachable voter does
+    /// not delay the assigned next leader from starting the election.
+    ///
+    /// The vote request of the next leader takes one more hop to reach another voter than this
+    /// request does. If it still arrives first, the voter rejects it by the leader lease, and the
+    /// election falls back to the election timeout.
     #[tracing::instrument(level = "trace", skip_all)]
     async fn broadcast_transfer_leader(&mut self, req: TransferLeaderRequest<C>) {
         let voter_ids = self.engine.state.membership_state.effective().voter_ids();
@@ -1146,11 +2143,13 @@ where
 
             let ttl = Duration::from_millis(self.config.election_timeout_min);
             let option = RPCOption::new(ttl);
+            let io_counters = self.io_counters.clone();
 
             let fut = {
                 let target = target.clone();
                 async move {
                     let tm_res = C::timeout(ttl, client.transfer_leader(r, option)).await;
+                    io_counters.record_rpc(RPCTypes::TransferLeader, &tm_res);
                     let res = match tm_res {
                         Ok(res) => res,
                         Err(timeout) => {
@@ -1208,6 +2207,8 @@ where
     #[tracing::instrument(level = "debug", skip(self, msg), fields(state = debug(self.engine.state.server_state), id=display(&self.id)))]
     pub(crate) async fn handle_api_msg(&mut self, msg: RaftMsg<C>) {
         tracing::debug!("RAFT_event id={:<2}  input: {}", self.id, msg);
+        self.core_loop_stats.add_message();
+        self.engine.begin_decision(&msg);
 
         match msg {
             RaftMsg::AppendEntries { rpc, tx } => {
@@ -1228,32 +2229,131 @@ where
                 self.engine.handle_begin_receiving_snapshot(tx);
             }
             RaftMsg::InstallFullSnapshot { vote, snapshot, tx } => {
+                self.io_counters.incr_snapshots_received();
                 self.engine.handle_install_full_snapshot(vote, snapshot, tx);
             }
             RaftMsg::CheckIsLeaderRequest { tx } => {
                 self.handle_check_is_leader_request(tx).await;
             }
-            RaftMsg::ClientWriteRequest { app_data, tx } => {
-                self.write_entry(C::Entry::new_normal(LogIdOf::<C>::default(), app_data), Some(tx));
+            RaftMsg::ReadIndexViaLeader { tx } => {
+                self.handle_read_index_via_leader(tx).await;
             }
-            RaftMsg::Initialize { members, tx } => {
+            RaftMsg::ClientWriteRequest {
+                app_data,
+                tx,
+                log_id_tx,
+                span,
+                correlation_id,
+            } => {
+                let log_id = self.write_entry(C::Entry::new_normal(LogIdOf::<C>::default(), app_data), Some(tx));
+
+                if let Some(log_id) = &log_id {
+                    tracing::debug!(
+                        parent: &span,
+                        correlation_id = display(correlation_id),
+                        log_id = display(log_id),
+                        "client write is appended"
+                    );
+                    if !span.is_none() {
+                        self.client_write_spans.insert(log_id.index(), span);
+                    }
+                    self.correlation_ids.insert(log_id.index(), correlation_id);
+                }
+
+                if let (Some(log_id_tx), Some(log_id)) = (log_id_tx, log_id) {
+                    let _ = log_id_tx.send(log_id);
+                }
+            }
+            RaftMsg::StopAcceptingWrites { tx } => {
+                tracing::info!("stop accepting client writes");
+                self.accept_writes = false;
+
+                let pending = self.client_resp_channels.keys().filter_map(|index| self.engine.state.get_log_id(*index));
+                let _ = tx.send(Ok(pending.collect()));
+            }
+            RaftMsg::GetDrainedWrites { tx } => {
+                let _ = tx.send(Ok(self.drained_writes.clone()));
+            }
+            RaftMsg::ClientWriteManyRequest {
+                app_data,
+                txs,
+                span,
+                correlation_ids,
+            } => {
+                let entries = app_data.into_iter().map(|d| C::Entry::new_normal(LogIdOf::<C>::default(), d)).collect();
+                let indexes = self.write_entries(entries, txs);
+
+                if let Some(indexes) = indexes {
+                    tracing::debug!(
+                        parent: &span,
+                        first_index = indexes.start,
+                        last_index = indexes.end - 1,
+                        "client write batch is appended"
+                    );
+                    for (index, correlation_id) in indexes.zip(correlation_ids) {
+                        if !span.is_none() {
+                            self.client_write_spans.insert(index, span.clone());
+                        }
+                        self.correlation_ids.insert(index, correlation_id);
+                    }
+                }
+            }
+            RaftMsg::Initialize { members, learners, tx } => {
                 tracing::info!(
                     members = debug(&members),
+                    learners = debug(&learners),
                     "received RaftMsg::Initialize: {}",
                     func_name!()
                 );
 
-                self.handle_initialize(members, tx);
+                self.handle_initialize(members, learners, tx);
             }
-            RaftMsg::ChangeMembership { changes, retain, tx } => {
+            RaftMsg::ChangeMembership {
+                changes,
+                retain,
+                preflight,
+                tx,
+            } => {
                 tracing::info!(
                     members = debug(&changes),
                     retain = debug(&retain),
+                    preflight = debug(&preflight),
                     "received RaftMsg::ChangeMembership: {}",
                     func_name!()
                 );
 
-                self.change_membership(changes, retain, tx);
+                self.change_membership(changes, retain, preflight, tx);
+            }
+            RaftMsg::ChangeMembershipDryRun { changes, retain, tx } => {
+                let res = self.change_membership_report(changes, retain);
+                let _ = tx.send(res.map_err(ClientWriteError::from));
+            }
+            RaftMsg::SetMembershipValidator { validator } => {
+                tracing::info!("set membership validator");
+                self.membership_validator = Some(validator);
+            }
+            RaftMsg::PlanUnreachableRemoval { min_downtime, tx } => {
+                let res = self.unreachable_removal_plan(min_downtime);
+                let _ = tx.send(res.map_err(ClientWriteError::from));
+            }
+            RaftMsg::UpdateConfig { patch, tx } => {
+                let res = self.update_config(patch);
+                let _ = tx.send(res);
+            }
+            RaftMsg::GetEffectiveConfig { tx } => {
+                let _ = tx.send(self.effective_config());
+            }
+            RaftMsg::GetStatus { tx } => {
+                let _ = tx.send(self.status());
+            }
+            RaftMsg::GetLastQuorumAckedTime { tx } => {
+                let _ = tx.send(self.last_quorum_acked_time());
+            }
+            RaftMsg::GetDecisionTrace { tx } => {
+                let _ = tx.send(self.engine.decision_trace.iter().cloned().collect());
+            }
+            RaftMsg::VerifyState { tx } => {
+                let _ = tx.send(self.engine.verify_state());
             }
             RaftMsg::ExternalCoreRequest { req } => {
                 req(&self.engine.state);
@@ -1295,8 +2395,9 @@ where
                             tracing::error!(error = display(e), "error sending GetSnapshot to sm worker");
                         }
                     }
-                    ExternalCommand::PurgeLog { upto } => {
-                        self.engine.trigger_purge_log(upto);
+                    ExternalCommand::PurgeLog { upto, tx } => {
+                        let scheduled = self.engine.trigger_purge_log(upto);
+                        let _ = tx.send(Ok(scheduled));
                     }
                     ExternalCommand::TriggerTransferLeader { to } => {
                         self.engine.trigger_transfer_leader(to);
@@ -1315,11 +2416,14 @@ where
                         };
                         let _ = tx.send(res);
                     }
+                    ExternalCommand::PauseTicks { paused } => {
+                        tracing::info!(paused, "ExternalCommand: set ticks paused");
+                        self.ticks_paused = paused;
+                    }
                     ExternalCommand::StateMachineCommand { sm_cmd } => {
-                        let res = self.sm_handle.send(sm_cmd);
-                        if let Err(e) = res {
-                            tracing::error!(error = display(e), "error sending sm::Command to sm::Worker");
-                        }
+                        // Queue it after the commands already output, so that it runs after all
+                        // the entries committed before it are applied.
+                        self.engine.output.push_command(Command::from(sm_cmd));
                     }
                 }
             }
@@ -1329,6 +2433,8 @@ where
     #[tracing::instrument(level = "debug", skip_all, fields(state = debug(self.engine.state.server_state), id=display(&self.id)))]
     pub(crate) fn handle_notification(&mut self, notify: Notification<C>) -> Result<(), Fatal<C>> {
         tracing::debug!("RAFT_event id={:<2} notify: {}", self.id, notify);
+        self.core_loop_stats.add_message();
+        self.engine.begin_decision(&notify);
 
         match notify {
             Notification::VoteResponse {
@@ -1372,13 +2478,20 @@ where
                 }
             }
 
+            Notification::ApplyDelayExpired => {
+                self.send_pending_apply(false)?;
+            }
+
             Notification::Tick { i } => {
                 // check every timer
 
                 let now = C::now();
                 tracing::debug!("received tick: {}, now: {}", i, now.display());
 
-                self.handle_tick_election();
+                if !self.ticks_paused {
+                    self.handle_tick_election();
+                    self.handle_tick_auto_membership();
+                }
 
                 // TODO: test: fixture: make isolated_nodes a single-way isolating.
 
@@ -1386,7 +2499,7 @@ where
                 let heartbeat_at = self.engine.leader_ref().map(|l| l.next_heartbeat);
                 if let Some(t) = heartbeat_at {
                     if now >= t {
-                        if self.runtime_config.enable_heartbeat.load(Ordering::Relaxed) {
+                        if !self.ticks_paused && self.runtime_config.enable_heartbeat.load(Ordering::Relaxed) {
                             self.send_heartbeat("tick");
                         }
 
@@ -1416,6 +2529,7 @@ where
                 //       ---
                 //       A better way is to make leader step down a command that waits for the log to be applied.
                 if self.engine.state.io_applied() >= self.engine.state.membership_state.effective().log_id().as_ref() {
+                    self.take_removed_leader_action();
                     self.engine.leader_step_down();
                 }
             }
@@ -1426,6 +2540,11 @@ where
             }
 
             Notification::LocalIO { io_id } => {
+                if let Some(pos) = self.appending.iter().position(|(id, _)| id == &io_id) {
+                    let (_, start) = self.appending.drain(..=pos).last().unwrap();
+                    self.check_slow_op(SlowOp::Append, start.elapsed(), &io_id);
+                }
+
                 self.engine.state.io_state.io_progress.flush(io_id.clone());
 
                 match io_id {
@@ -1446,12 +2565,37 @@ where
                 }
             }
 
+            Notification::LogPurged { purged } => {
+                // Progress of different purges may arrive out of order; keep the greatest.
+                let io_state = self.engine.state.io_state_mut();
+                if io_state.purged() < Some(&purged) {
+                    let n = purged.index() + 1 - io_state.purged().next_index();
+                    self.io_counters.add_log_entries_purged(n);
+                    io_state.update_purged(Some(purged.clone()));
+                    self.events.send(RaftEvent::PurgeCompleted { upto: purged });
+                }
+            }
+
             Notification::ReplicationProgress { progress } => {
                 // If vote or membership changes, ignore the message.
                 // There is chance delayed message reports a wrong state.
                 if self.does_replication_session_match(&progress.session_id, "ReplicationProgress") {
                     tracing::debug!(progress = display(&progress), "recv Notification::ReplicationProgress");
 
+                    match &progress.result {
+                        Ok(_) => {
+                            self.stalled_targets.remove(&progress.target);
+                        }
+                        Err(error) => {
+                            if self.stalled_targets.insert(progress.target.clone()) {
+                                self.events.send(RaftEvent::ReplicationStalled {
+                                    target: progress.target.clone(),
+                                    error: error.clone(),
+                                });
+                            }
+                        }
+                    }
+
                     // replication_handler() won't panic because:
                     // The leader is still valid because progress.session_id.leader_vote does not change.
                     self.engine.replication_handler().update_progress(progress.target, progress.result);
@@ -1470,29 +2614,46 @@ where
                         sending_time = display(sending_time.display()),
                         "HeartbeatProgress"
                     );
+                    self.update_heartbeat_ack(&target, sending_time);
+
                     // replication_handler() won't panic because:
                     // The leader is still valid because progress.session_id.leader_vote does not change.
                     self.engine.replication_handler().update_leader_clock(target, sending_time);
                 }
             }
 
+            Notification::RPCLatency {
+                target,
+                rpc_type,
+                latency,
+            } => {
+                self.check_slow_op(SlowOp::Rpc, latency, format_args!("{} to {}", rpc_type, target));
+
+                // Sent with the replication metrics, not to wake up the subscribers for every RPC.
+                self.network_metrics.record(target, rpc_type, latency, &self.config.rpc_latency_buckets);
+            }
+
             Notification::StateMachine { command_result } => {
                 tracing::debug!("sm::StateMachine command result: {:?}", command_result);
 
                 let res = command_result.result?;
 
                 match res {
-                    sm::Response::BuildSnapshot(meta) => {
+                    sm::Response::BuildSnapshot((meta, elapsed)) => {
                         tracing::info!(
-                            "sm::StateMachine command done: BuildSnapshot: {}: {}",
+                            "sm::StateMachine command done: BuildSnapshot: {}, elapsed: {:?}: {}",
                             meta,
+                            elapsed,
                             func_name!()
                         );
 
+                        self.check_slow_op(SlowOp::BuildSnapshot, elapsed, &meta.snapshot_id);
+
                         // Update in-memory state first, then the io state.
                         // In-memory state should always be ahead or equal to the io state.
 
                         let last_log_id = meta.last_log_id.clone();
+                        self.events.send(RaftEvent::SnapshotBuilt { meta: meta.clone() });
                         self.engine.finish_building_snapshot(meta);
 
                         let st = self.engine.state.io_state_mut();
@@ -1509,15 +2670,29 @@ where
                         self.engine.state.io_state_mut().io_progress.flush(io_id);
 
                         if let Some(meta) = meta {
+                            self.events.send(RaftEvent::SnapshotInstalled { meta: meta.clone() });
+
+                            // The writes included in the snapshot will never be applied one by one.
+                            let upto = meta.last_log_id.next_index();
+                            self.client_write_spans = self.client_write_spans.split_off(&upto);
+                            self.correlation_ids.purge(upto);
+
                             let st = self.engine.state.io_state_mut();
                             st.update_applied(meta.last_log_id.clone());
                             st.update_snapshot(meta.last_log_id);
+                            self.notify_applied();
                         }
                     }
                     sm::Response::Apply(res) => {
+                        self.check_slow_op(SlowOp::Apply, res.elapsed, format_args!("[{}, {})", res.since, res.end));
+
                         self.engine.state.io_state_mut().update_applied(Some(res.last_applied.clone()));
+                        self.notify_applied();
 
                         self.handle_apply_result(res);
+
+                        self.apply_inflight -= 1;
+                        self.send_pending_apply(false)?;
                     }
                 }
             }
@@ -1525,6 +2700,95 @@ where
         Ok(())
     }
 
+    /// Propose a membership change on the leader, if a learner is to be promoted or a voter is to
+    /// be demoted automatically.
+    ///
+    /// A change goes through a joint config: the uniform config is proposed on a later tick, when
+    /// the joint config is committed. No change is decided while another membership change is in
+    /// progress.
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn handle_tick_auto_membership(&mut self) {
+        let config = self.config.clone();
+        if !config.enable_auto_promote && config.auto_demote_unreachable_period.is_none() {
+            return;
+        }
+
+        let Some(leader) = self.engine.leader.as_ref() else {
+            return;
+        };
+
+        let membership_state = &self.engine.state.membership_state;
+        if membership_state.effective().log_id() != membership_state.committed().log_id() {
+            return;
+        }
+
+        let is_joint = membership_state.effective().membership().get_joint_config().len() > 1;
+
+        let (changes, retain, auto_change) = if let Some((changes, retain)) = self.auto_membership.pending.take() {
+            if !is_joint {
+                return;
+            }
+            (changes, retain, None)
+        } else {
+            if is_joint {
+                return;
+            }
+
+            let auto_change = self.auto_membership.decide(
+                C::now(),
+                &config,
+                &self.id,
+                membership_state.effective().membership(),
+                self.engine.state.last_log_id().index(),
+                |id| leader.progress.try_get(id).and_then(|p| p.matching().index()),
+                |id| leader.clock_progress.try_get(id).copied().flatten(),
+            );
+
+            let Some(auto_change) = auto_change else {
+                return;
+            };
+
+            let (changes, retain) = auto_change.to_changes();
+            (changes, retain, Some(auto_change))
+        };
+
+        let new_membership = match membership_state.change_handler().apply(changes.clone(), retain) {
+            Ok(x) => x,
+            Err(e) => {
+                tracing::warn!("auto membership change {:?} is not applicable: {}", changes, e);
+                return;
+            }
+        };
+
+        if let Err(e) = self.validate_membership(&new_membership) {
+            tracing::warn!("auto membership change {:?} is rejected: {}", changes, e);
+            return;
+        }
+
+        tracing::info!("auto membership change: {:?}, propose: {}", auto_change, new_membership);
+
+        let proposes_joint = new_membership.get_joint_config().len() > 1;
+
+        let ent = C::Entry::new_membership(LogIdOf::<C>::default(), new_membership);
+        let Some(log_id) = self.write_entry(ent, None) else {
+            return;
+        };
+
+        if proposes_joint {
+            self.auto_membership.pending = Some((changes, retain));
+        }
+
+        match auto_change {
+            Some(AutoChange::Promote(target)) => {
+                self.events.send(RaftEvent::LearnerAutoPromoted { target, log_id });
+            }
+            Some(AutoChange::Demote(target)) => {
+                self.events.send(RaftEvent::VoterAutoDemoted { target, log_id });
+            }
+            None => {}
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     fn handle_tick_election(&mut self) {
         let now = C::now();
@@ -1737,6 +3001,17 @@ where
                 let last_log_id = entries.last().unwrap().log_id();
                 tracing::debug!("AppendInputEntries: {}", DisplaySlice::<_>(&entries),);
 
+                let first_index = entries.first().unwrap().index();
+                self.io_counters.add_log_entries_appended(entries.len() as u64);
+                self.io_counters.add_log_bytes_appended(entries.iter().filter_map(|e| e.size_bytes()).sum());
+                for (index, correlation_id) in self.correlation_ids.range(first_index..last_log_id.index() + 1) {
+                    tracing::debug!(
+                        correlation_id = display(correlation_id),
+                        log_index = index,
+                        "client write is submitted to log store"
+                    );
+                }
+
                 let io_id = IOId::new_log_io(vote, Some(last_log_id));
                 let notify = Notification::LocalIO { io_id: io_id.clone() };
                 let callback = IOFlushed::new(notify, self.tx_notification.downgrade());
@@ -1749,7 +3024,8 @@ where
                 //
                 // The `submit` state must be updated before calling `append()`,
                 // because `append()` may call the callback before returning.
-                self.engine.state.io_state.io_progress.submit(io_id);
+                self.engine.state.io_state.io_progress.submit(io_id.clone());
+                self.appending.push_back((io_id, C::now()));
 
                 // Submit IO request, do not wait for the response.
                 self.log_store.append(entries, callback).await?;
@@ -1774,14 +3050,18 @@ where
                 }
             }
             Command::PurgeLog { upto } => {
-                self.log_store.purge(upto.clone()).await?;
-                self.engine.state.io_state_mut().update_purged(Some(upto));
+                // The purge may go on in the background; `io_state.purged` is updated when the log
+                // store reports progress.
+                let callback = PurgeProgress::new(upto.clone(), self.tx_notification.downgrade());
+                self.log_store.purge_with_progress(upto, callback).await?;
             }
             Command::TruncateLog { since } => {
                 self.log_store.truncate(since.clone()).await?;
 
                 // Inform clients waiting for logs to be applied.
                 let removed = self.client_resp_channels.split_off(&since.index());
+                self.client_write_spans.split_off(&since.index());
+                self.correlation_ids.truncate(since.index());
                 if !removed.is_empty() {
                     let leader_id = self.current_leader();
                     let leader_node = self.get_leader_node(leader_id.clone());
@@ -1821,7 +3101,16 @@ where
                 let first = self.engine.state.get_log_id(already_committed.next_index()).unwrap();
                 self.apply_to_state_machine(first, upto).await?;
             }
-            Command::Replicate { req, target } => {
+            Command::Replicate { mut req, target } => {
+                if let Replicate::Data(Data::Logs(log_ids, origin)) = &mut req {
+                    // An RPC is sent in the span of the last client write it carries.
+                    let last = log_ids.last.index();
+                    let span = last.and_then(|i| self.client_write_spans.get(&i).cloned());
+                    let correlation_ids =
+                        self.correlation_ids.range(log_ids.prev.next_index()..log_ids.last.next_index());
+                    *origin = WriteOrigin::new(span, correlation_ids);
+                }
+
                 let node = self.replications.get(&target).expect("replication to target node exists");
                 let _ = node.tx_repl.send(req);
             }
@@ -1851,6 +3140,9 @@ where
                     self.engine.state.io_state.io_progress.submit(io_id);
                 }
 
+                // Entries committed before this command must be applied before it.
+                self.send_pending_apply(true)?;
+
                 // Just forward a state machine command to the worker.
                 self.sm_handle.send(command).map_err(|_e| {
                     StorageError::write_state_machine(AnyError::error("can not send to sm::Worker".to_string()))
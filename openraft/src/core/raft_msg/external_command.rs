@@ -5,6 +5,7 @@ use std::fmt;
 use crate::core::raft_msg::ResultSender;
 use crate::core::sm;
 use crate::error::AllowNextRevertError;
+use crate::type_config::alias::LogIdOf;
 use crate::RaftTypeConfig;
 use crate::Snapshot;
 
@@ -31,8 +32,13 @@ pub(crate) enum ExternalCommand<C: RaftTypeConfig> {
     ///
     /// Openraft respects the [`max_in_snapshot_log_to_keep`] config when purging.
     ///
+    /// The scheduled purge point is sent back via `tx`.
+    ///
     /// [`max_in_snapshot_log_to_keep`]: `crate::Config::max_in_snapshot_log_to_keep`
-    PurgeLog { upto: u64 },
+    PurgeLog {
+        upto: u64,
+        tx: ResultSender<C, Option<LogIdOf<C>>>,
+    },
 
     /// Submit a command to inform RaftCore to transfer leadership to the specified node.
     TriggerTransferLeader { to: C::NodeId },
@@ -44,6 +50,9 @@ pub(crate) enum ExternalCommand<C: RaftTypeConfig> {
         tx: ResultSender<C, (), AllowNextRevertError<C>>,
     },
 
+    /// Pause or resume the election and heartbeat triggered by ticks.
+    PauseTicks { paused: bool },
+
     /// Send a [`sm::Command`] to [`sm::worker::Worker`].
     /// This command is run in the sm task.
     StateMachineCommand { sm_cmd: sm::Command<C> },
@@ -74,7 +83,7 @@ where C: RaftTypeConfig
             ExternalCommand::GetSnapshot { .. } => {
                 write!(f, "GetSnapshot")
             }
-            ExternalCommand::PurgeLog { upto } => {
+            ExternalCommand::PurgeLog { upto, .. } => {
                 write!(f, "PurgeLog[..={}]", upto)
             }
             ExternalCommand::TriggerTransferLeader { to } => {
@@ -88,6 +97,9 @@ where C: RaftTypeConfig
                     to
                 )
             }
+            ExternalCommand::PauseTicks { paused } => {
+                write!(f, "PauseTicks: {}", paused)
+            }
             ExternalCommand::StateMachineCommand { sm_cmd } => {
                 write!(f, "StateMachineCommand: {}", sm_cmd)
             }
@@ -1,23 +1,39 @@
 use std::collections::BTreeMap;
 use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::base::BoxOnce;
+use crate::config::ConfigPatch;
+use crate::config::EffectiveConfig;
 use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::engine::EngineDecision;
 use crate::error::CheckIsLeaderError;
+use crate::error::ClientWriteError;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::ReadIndexError;
+use crate::membership::MembershipChangeReport;
+use crate::membership::MembershipValidator;
+use crate::membership::RemovalPlan;
+use crate::metrics::RaftStatus;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::CorrelationId;
 use crate::raft::SnapshotResponse;
+use crate::raft::StateReport;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::storage::Snapshot;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::alias::ResponderOf;
 use crate::type_config::alias::SnapshotDataOf;
 use crate::type_config::alias::VoteOf;
 use crate::ChangeMembers;
+use crate::Config;
+use crate::ConfigError;
 use crate::RaftState;
 use crate::RaftTypeConfig;
 
@@ -70,14 +86,58 @@ where C: RaftTypeConfig
     ClientWriteRequest {
         app_data: C::D,
         tx: ResponderOf<C>,
+
+        /// Receives the log id assigned to the entry, once it is appended to the leader's log.
+        log_id_tx: Option<OneshotSenderOf<C, LogIdOf<C>>>,
+
+        /// The tracing span of the caller, in which the append and the apply of the entry are
+        /// recorded. It is `Span::none()` if feature `otel` is not enabled.
+        span: tracing::Span,
+
+        /// Identifies this write in the logs of every stage it goes through.
+        correlation_id: CorrelationId,
+    },
+
+    /// Reject client writes from now on, and get the log ids of the writes that are accepted but
+    /// not yet applied.
+    StopAcceptingWrites {
+        tx: ResultSender<C, Vec<LogIdOf<C>>>,
+    },
+
+    /// Get the log ids of the writes accepted before `StopAcceptingWrites` that are applied since
+    /// then.
+    GetDrainedWrites {
+        tx: ResultSender<C, Vec<LogIdOf<C>>>,
+    },
+
+    /// Write a batch of requests as consecutive log entries, with one responder for each.
+    ClientWriteManyRequest {
+        app_data: Vec<C::D>,
+        txs: Vec<ResponderOf<C>>,
+
+        /// The tracing span of the batch, in which the append and the apply of every entry are
+        /// recorded. It is `Span::none()` if feature `otel` is not enabled.
+        span: tracing::Span,
+
+        /// Identifies each write of the batch in the logs of every stage it goes through.
+        correlation_ids: Vec<CorrelationId>,
     },
 
     CheckIsLeaderRequest {
         tx: ClientReadTx<C>,
     },
 
+    /// Get a read log id on this node, from the leader if this node is not the leader.
+    ReadIndexViaLeader {
+        tx: ResultSender<C, Option<LogIdOf<C>>, ReadIndexError<C>>,
+    },
+
     Initialize {
         members: BTreeMap<C::NodeId, C::Node>,
+
+        /// Learners in the initial membership, in addition to the voters in `members`.
+        learners: BTreeMap<C::NodeId, C::Node>,
+
         tx: ResultSender<C, (), InitializeError<C>>,
     },
 
@@ -88,9 +148,67 @@ where C: RaftTypeConfig
         /// config will be converted into learners, otherwise they will be removed.
         retain: bool,
 
+        /// If `preflight` is `true`, the change is rejected with a `PreflightFailed` error unless
+        /// the [`MembershipChangeReport`] of it is ok.
+        preflight: bool,
+
         tx: ResponderOf<C>,
     },
 
+    /// Build a [`MembershipChangeReport`] of a change without proposing it.
+    ChangeMembershipDryRun {
+        changes: ChangeMembers<C>,
+        retain: bool,
+        tx: ResultSender<C, MembershipChangeReport<C>, ClientWriteError<C>>,
+    },
+
+    /// Replace the [`MembershipValidator`] run before proposing a membership config.
+    SetMembershipValidator {
+        validator: Box<dyn MembershipValidator<C>>,
+    },
+
+    /// Build a [`RemovalPlan`] of the nodes that have been unreachable for at least
+    /// `min_downtime`.
+    PlanUnreachableRemoval {
+        min_downtime: Duration,
+        tx: ResultSender<C, RemovalPlan<C>, ClientWriteError<C>>,
+    },
+
+    /// Apply a [`ConfigPatch`] to the config in use, and return the updated config.
+    UpdateConfig {
+        patch: ConfigPatch,
+        tx: ResultSender<C, Arc<Config>, ConfigError>,
+    },
+
+    /// Get the config in use and the values derived from it.
+    ///
+    /// `enable_tick` is not known to `RaftCore` and is filled by the caller.
+    GetEffectiveConfig {
+        tx: OneshotSenderOf<C, EffectiveConfig>,
+    },
+
+    /// Get a consistent view of the status of this node.
+    ///
+    /// `config.enable_tick` is not known to `RaftCore` and is filled by the caller.
+    GetStatus {
+        tx: OneshotSenderOf<C, RaftStatus<C>>,
+    },
+
+    /// Get the last time a quorum acknowledged this leader, or `None` if it is not a leader.
+    GetLastQuorumAckedTime {
+        tx: OneshotSenderOf<C, Option<InstantOf<C>>>,
+    },
+
+    /// Get the last decisions made by the `Engine`, from the oldest to the latest.
+    GetDecisionTrace {
+        tx: OneshotSenderOf<C, Vec<EngineDecision>>,
+    },
+
+    /// Run the internal consistency checks and report the result.
+    VerifyState {
+        tx: OneshotSenderOf<C, StateReport>,
+    },
+
     ExternalCoreRequest {
         req: BoxOnce<'static, RaftState<C>>,
     },
@@ -129,16 +247,47 @@ where C: RaftTypeConfig
             RaftMsg::InstallFullSnapshot { vote, snapshot, .. } => {
                 write!(f, "InstallFullSnapshot: vote: {}, snapshot: {}", vote, snapshot)
             }
-            RaftMsg::ClientWriteRequest { .. } => write!(f, "ClientWriteRequest"),
+            RaftMsg::ClientWriteRequest { correlation_id, .. } => write!(f, "ClientWriteRequest: {}", correlation_id),
+            RaftMsg::StopAcceptingWrites { .. } => write!(f, "StopAcceptingWrites"),
+            RaftMsg::GetDrainedWrites { .. } => write!(f, "GetDrainedWrites"),
+            RaftMsg::ClientWriteManyRequest { app_data, .. } => {
+                write!(f, "ClientWriteManyRequest: {} entries", app_data.len())
+            }
             RaftMsg::CheckIsLeaderRequest { .. } => write!(f, "CheckIsLeaderRequest"),
-            RaftMsg::Initialize { members, .. } => {
+            RaftMsg::ReadIndexViaLeader { .. } => write!(f, "ReadIndexViaLeader"),
+            RaftMsg::Initialize { members, learners, .. } => {
+                // TODO: avoid using Debug
+                write!(f, "Initialize: {:?}, learners: {:?}", members, learners)
+            }
+            RaftMsg::ChangeMembership {
+                changes,
+                retain,
+                preflight,
+                ..
+            } => {
                 // TODO: avoid using Debug
-                write!(f, "Initialize: {:?}", members)
+                write!(
+                    f,
+                    "ChangeMembership: {:?}, retain: {}, preflight: {}",
+                    changes, retain, preflight
+                )
+            }
+            RaftMsg::ChangeMembershipDryRun { changes, retain, .. } => {
+                write!(f, "ChangeMembershipDryRun: {:?}, retain: {}", changes, retain)
+            }
+            RaftMsg::SetMembershipValidator { .. } => write!(f, "SetMembershipValidator"),
+            RaftMsg::PlanUnreachableRemoval { min_downtime, .. } => {
+                write!(f, "PlanUnreachableRemoval: min_downtime: {:?}", min_downtime)
             }
-            RaftMsg::ChangeMembership { changes, retain, .. } => {
+            RaftMsg::UpdateConfig { patch, .. } => {
                 // TODO: avoid using Debug
-                write!(f, "ChangeMembership: {:?}, retain: {}", changes, retain,)
+                write!(f, "UpdateConfig: {:?}", patch)
             }
+            RaftMsg::GetEffectiveConfig { .. } => write!(f, "GetEffectiveConfig"),
+            RaftMsg::GetStatus { .. } => write!(f, "GetStatus"),
+            RaftMsg::GetLastQuorumAckedTime { .. } => write!(f, "GetLastQuorumAckedTime"),
+            RaftMsg::GetDecisionTrace { .. } => write!(f, "GetDecisionTrace"),
+            RaftMsg::VerifyState { .. } => write!(f, "VerifyState"),
             RaftMsg::ExternalCoreRequest { .. } => write!(f, "External Request"),
             RaftMsg::HandleTransferLeader { from, to } => {
                 write!(f, "TransferLeader: from_leader: vote={}, to: {}", from, to)
@@ -9,6 +9,7 @@ use crate::raft_state::IOId;
 use crate::storage::Snapshot;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::SnapshotDataOf;
+use crate::type_config::alias::VoteOf;
 use crate::RaftTypeConfig;
 
 /// The payload of a state machine command.
@@ -53,6 +54,12 @@ where C: RaftTypeConfig
         /// The SM type user specified, for debug purpose.
         input_sm_type: &'static str,
     },
+
+    /// Notify the state machine that this node becomes the leader with the given vote.
+    BecomeLeader { vote: VoteOf<C> },
+
+    /// Notify the state machine that this node is no longer the leader.
+    StepDown,
 }
 
 impl<C> Command<C>
@@ -79,6 +86,14 @@ where C: RaftTypeConfig
         Command::Apply { first, last }
     }
 
+    pub(crate) fn become_leader(vote: VoteOf<C>) -> Self {
+        Command::BecomeLeader { vote }
+    }
+
+    pub(crate) fn step_down() -> Self {
+        Command::StepDown
+    }
+
     /// Return the IOId if this command submit any IO.
     pub(crate) fn get_submit_io(&self) -> Option<IOId<C>> {
         match self {
@@ -88,6 +103,8 @@ where C: RaftTypeConfig
             Command::InstallFullSnapshot { io_id, .. } => Some(io_id.clone()),
             Command::Apply { .. } => None,
             Command::Func { .. } => None,
+            Command::BecomeLeader { .. } => None,
+            Command::StepDown => None,
         }
     }
 }
@@ -107,6 +124,8 @@ where C: RaftTypeConfig
             }
             Command::Apply { first, last } => write!(f, "Apply: [{},{}]", first, last),
             Command::Func { .. } => write!(f, "Func"),
+            Command::BecomeLeader { vote } => write!(f, "BecomeLeader: vote: {}", vote),
+            Command::StepDown => write!(f, "StepDown"),
         }
     }
 }
@@ -126,6 +145,8 @@ where C: RaftTypeConfig
             }
             Command::Apply { first, last } => write!(f, "Apply: [{},{}]", first, last),
             Command::Func { .. } => write!(f, "Func"),
+            Command::BecomeLeader { vote } => write!(f, "BecomeLeader: vote: {}", vote),
+            Command::StepDown => write!(f, "StepDown"),
         }
     }
 }
@@ -157,6 +178,8 @@ where C: RaftTypeConfig
                 },
             ) => first == first2 && last == last2,
             (Command::Func { .. }, Command::Func { .. }) => false,
+            (Command::BecomeLeader { vote }, Command::BecomeLeader { vote: vote2 }) => vote == vote2,
+            (Command::StepDown, Command::StepDown) => true,
             _ => false,
         }
     }
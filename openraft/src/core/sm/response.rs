@@ -1,5 +1,6 @@
 use std::fmt;
 use std::fmt::Formatter;
+use std::time::Duration;
 
 use crate::core::ApplyResult;
 use crate::display_ext::display_result::DisplayResultExt;
@@ -14,8 +15,9 @@ use crate::StorageError;
 pub(crate) enum Response<C>
 where C: RaftTypeConfig
 {
-    /// Build a snapshot, it returns result via the universal RaftCore response channel.
-    BuildSnapshot(SnapshotMeta<C>),
+    /// Build a snapshot, it returns result via the universal RaftCore response channel, along
+    /// with the time spent building it.
+    BuildSnapshot((SnapshotMeta<C>, Duration)),
 
     /// When finishing installing a snapshot.
     ///
@@ -31,8 +33,8 @@ where C: RaftTypeConfig
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Self::BuildSnapshot(meta) => {
-                write!(f, "BuildSnapshot({})", meta)
+            Self::BuildSnapshot((meta, elapsed)) => {
+                write!(f, "BuildSnapshot({}, elapsed:{:?})", meta, elapsed)
             }
             Self::InstallSnapshot((io_id, meta)) => {
                 write!(f, "InstallSnapshot(io_id:{}, meta:{})", io_id, meta.display())
@@ -24,10 +24,12 @@ use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::MpscUnboundedReceiverOf;
 use crate::type_config::alias::MpscUnboundedSenderOf;
 use crate::type_config::TypeConfigExt;
+use crate::Instant;
 use crate::RaftLogReader;
 use crate::RaftSnapshotBuilder;
 use crate::RaftTypeConfig;
 use crate::StorageError;
+use crate::StoredMembership;
 
 pub(crate) struct Worker<C, SM, LR>
 where
@@ -43,6 +45,9 @@ where
     /// [`RaftLogStorage`]: `crate::storage::RaftLogStorage`
     log_reader: LR,
 
+    /// The max number of entries to pass to one [`RaftStateMachine::apply()`] call.
+    apply_batch_size: u64,
+
     /// Read command from RaftCore to execute.
     cmd_rx: MpscUnboundedReceiverOf<C, Command<C>>,
 
@@ -60,6 +65,7 @@ where
     pub(crate) fn spawn(
         state_machine: SM,
         log_reader: LR,
+        apply_batch_size: u64,
         resp_tx: MpscUnboundedSenderOf<C, Notification<C>>,
         span: tracing::Span,
     ) -> Handle<C> {
@@ -68,6 +74,7 @@ where
         let worker = Worker {
             state_machine,
             log_reader,
+            apply_batch_size,
             cmd_rx,
             resp_tx,
         };
@@ -94,6 +101,12 @@ where
 
     #[tracing::instrument(level = "debug", skip_all)]
     async fn worker_loop(&mut self) -> Result<(), StorageError<C>> {
+        // Commit the entries that were applied but maybe not committed before the last shutdown.
+        let (applied, _) = self.state_machine.applied_state().await?;
+        if let Some(applied) = applied {
+            self.state_machine.commit_apply(applied).await?;
+        }
+
         loop {
             let cmd = self.cmd_rx.recv().await;
             let cmd = match cmd {
@@ -127,6 +140,8 @@ where
 
                     tracing::info!("Done install complete snapshot, meta: {}", meta);
 
+                    self.state_machine.on_snapshot_installed(&meta).await;
+
                     let res = CommandResult::new(Ok(Response::InstallSnapshot((io_id, Some(meta)))));
                     let _ = self.resp_tx.send(Notification::sm(res));
                 }
@@ -139,9 +154,11 @@ where
                     // No response to RaftCore
                 }
                 Command::Apply { first, last } => {
-                    let resp = self.apply(first, last).await?;
+                    let resp = self.apply(first, last.clone()).await?;
                     let res = CommandResult::new(Ok(Response::Apply(resp)));
                     let _ = self.resp_tx.send(Notification::sm(res));
+
+                    self.state_machine.commit_apply(last).await?;
                 }
                 Command::Func { func, input_sm_type } => {
                     tracing::debug!("{}: run user defined Func", func_name!());
@@ -157,6 +174,16 @@ where
                         );
                     };
                 }
+                Command::BecomeLeader { vote } => {
+                    tracing::info!("{}: become leader: vote: {}", func_name!(), vote);
+
+                    self.state_machine.on_become_leader(&vote).await;
+                }
+                Command::StepDown => {
+                    tracing::info!("{}: step down", func_name!());
+
+                    self.state_machine.on_step_down().await;
+                }
             };
         }
     }
@@ -166,45 +193,65 @@ where
         //       so that an Entry does not need to be Clone,
         //       and no references will be used by apply
 
+        let start = C::now();
         let since = first.index();
         let end = last.index() + 1;
 
-        let entries = self.log_reader.try_get_log_entries(since..end).await?;
-        if entries.len() != (end - since) as usize {
-            return Err(StorageError::read_logs(AnyError::error(format!(
-                "returned log entries count({}) does not match the input([{}, {}]))",
-                entries.len(),
-                since,
-                end
-            ))));
-        }
-        tracing::debug!(entries = display(entries.display()), "about to apply");
+        let mut applying_entries = Vec::with_capacity((end - since) as usize);
+        let mut apply_results = Vec::with_capacity((end - since) as usize);
+
+        // Entries are applied in batches of at most `apply_batch_size`.
+        let mut batch_start = since;
+        while batch_start < end {
+            let batch_end = std::cmp::min(batch_start + self.apply_batch_size, end);
+
+            let entries = self.log_reader.try_get_log_entries(batch_start..batch_end).await?;
+            if entries.len() != (batch_end - batch_start) as usize {
+                return Err(StorageError::read_logs(AnyError::error(format!(
+                    "returned log entries count({}) does not match the input([{}, {}]))",
+                    entries.len(),
+                    batch_start,
+                    batch_end
+                ))));
+            }
+            tracing::debug!(entries = display(entries.display()), "about to apply");
 
-        let last_applied = last;
+            let memberships = entries
+                .iter()
+                .filter_map(|e| e.get_membership().map(|m| StoredMembership::new(Some(e.log_id()), m)))
+                .collect::<Vec<_>>();
 
-        // Fake complain: avoid using `collect()` when not needed
-        #[allow(clippy::needless_collect)]
-        let applying_entries =
-            entries.iter().map(|e| ApplyingEntry::new(e.log_id(), e.get_membership())).collect::<Vec<_>>();
+            applying_entries.extend(entries.iter().map(|e| ApplyingEntry::new(e.log_id(), e.get_membership())));
 
-        let n_entries = end - since;
+            let n_entries = batch_end - batch_start;
 
-        let apply_results = self.state_machine.apply(entries).await?;
+            self.state_machine.prepare_apply(&entries).await?;
+            let results = self.state_machine.apply_fallible(entries).await?;
 
-        let n_replies = apply_results.len() as u64;
+            let n_replies = results.len() as u64;
 
-        debug_assert_eq!(
-            n_entries, n_replies,
-            "n_entries: {} should equal n_replies: {}",
-            n_entries, n_replies
-        );
+            debug_assert_eq!(
+                n_entries, n_replies,
+                "n_entries: {} should equal n_replies: {}",
+                n_entries, n_replies
+            );
+
+            apply_results.extend(results);
+
+            for membership in memberships {
+                self.state_machine.on_membership_applied(&membership).await;
+            }
+
+            batch_start = batch_end;
+        }
 
         let resp = ApplyResult {
             since,
             end,
-            last_applied,
+            last_applied: last,
             applying_entries,
             apply_results,
+            elapsed: start.elapsed(),
         };
 
         Ok(resp)
@@ -229,8 +276,9 @@ where
         let mut builder = self.state_machine.get_snapshot_builder().await;
 
         let _handle = C::spawn(async move {
+            let start = C::now();
             let res = builder.build_snapshot().await;
-            let res = res.map(|snap| Response::BuildSnapshot(snap.meta));
+            let res = res.map(|snap| Response::BuildSnapshot((snap.meta, start.elapsed())));
             let cmd_res = CommandResult::new(res);
             let _ = resp_tx.send(Notification::sm(cmd_res));
         });
@@ -130,6 +130,10 @@ where C: RaftTypeConfig
         self.enabled.store(enabled, Ordering::Relaxed);
     }
 
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
     /// Signal the tick loop to stop. And return a JoinHandle to wait for the loop to stop.
     ///
     /// If it is called twice, the second call will return None.
@@ -166,8 +170,12 @@ mod tests {
 
     use tokio::time::Duration;
 
+    use crate::core::notification::Notification;
     use crate::core::Tick;
+    use crate::declare_raft_types;
     use crate::impls::TokioRuntime;
+    use crate::testing::mock_clock::MockClock;
+    use crate::testing::mock_clock::MockClockRuntime;
     use crate::type_config::TypeConfigExt;
     use crate::RaftTypeConfig;
 
@@ -183,6 +191,7 @@ mod tests {
         type LeaderId = crate::impls::leader_id_adv::LeaderId<Self>;
         type Vote = crate::impls::Vote<Self>;
         type Entry = crate::Entry<Self>;
+        type EntryCodec = crate::impls::PassThroughCodec<Self>;
         type SnapshotData = Cursor<Vec<u8>>;
         type AsyncRuntime = TokioRuntime;
         type Responder = crate::impls::OneshotResponder<Self>;
@@ -210,4 +219,31 @@ mod tests {
 
         Ok(())
     }
+
+    declare_raft_types!(
+        MockClockUTConfig:
+            D = (),
+            R = (),
+            Node = (),
+            AsyncRuntime = MockClockRuntime<TokioRuntime>,
+    );
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_tick_with_mock_clock() -> anyhow::Result<()> {
+        let (tx, mut rx) = MockClockUTConfig::mpsc_unbounded();
+        let th = Tick::<MockClockUTConfig>::spawn(Duration::from_millis(100), tx, true);
+
+        for i in 1..=5 {
+            tokio::task::yield_now().await;
+            assert!(rx.try_recv().is_err(), "no tick before time advances");
+
+            MockClock::advance(Duration::from_millis(100));
+
+            let got = rx.recv().await.unwrap();
+            assert!(matches!(got, Notification::Tick { i: x } if x == i));
+        }
+
+        let _ = th.shutdown().unwrap().await;
+        Ok(())
+    }
 }
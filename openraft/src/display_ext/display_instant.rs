@@ -42,7 +42,7 @@ where T: Instant
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Convert Instant to SystemTime
         let sys_t = {
-            let sys_now = std::time::SystemTime::now();
+            let sys_now = T::system_now();
             let now = T::now();
 
             if &now >= self.0 {
@@ -0,0 +1,47 @@
+extern crate test;
+
+use test::black_box;
+use test::Bencher;
+
+use crate::engine::bench::eng;
+use crate::engine::testing::UTConfig;
+use crate::engine::Engine;
+use crate::testing::blank_ent;
+
+/// Propose `n` entries, let both followers accept them and commit.
+fn propose_and_commit(eng: &mut Engine<UTConfig>, n: usize) {
+    let entries = (0..n).map(|_| blank_ent(0, 0, 0)).collect::<Vec<_>>();
+    eng.leader_handler().unwrap().leader_append_entries(entries);
+
+    let last = eng.state.last_log_id().copied();
+    for target in [2, 3] {
+        eng.replication_handler().update_matching(target, last);
+    }
+
+    black_box(eng.output.take_commands());
+}
+
+/// Building the leader alone, to subtract from the other benches, which start every iteration
+/// with a new leader.
+#[bench]
+fn engine_new_leader(b: &mut Bencher) {
+    b.iter(eng);
+}
+
+#[bench]
+fn engine_propose_1(b: &mut Bencher) {
+    b.iter(|| {
+        let mut eng = eng();
+        propose_and_commit(&mut eng, 1);
+        eng
+    });
+}
+
+#[bench]
+fn engine_propose_64(b: &mut Bencher) {
+    b.iter(|| {
+        let mut eng = eng();
+        propose_and_commit(&mut eng, 64);
+        eng
+    });
+}
@@ -0,0 +1,31 @@
+extern crate test;
+
+use test::black_box;
+use test::Bencher;
+
+use crate::engine::bench::eng;
+use crate::engine::testing::log_id;
+use crate::replication::response::ReplicationResult;
+use crate::testing::blank_ent;
+
+/// Propose 64 entries at once, then let both followers acknowledge them one by one, each of which
+/// updates the replication progress and the committed log id.
+#[bench]
+fn engine_update_progress_64(b: &mut Bencher) {
+    b.iter(|| {
+        let mut eng = eng();
+
+        let entries = (0..64).map(|_| blank_ent(0, 0, 0)).collect::<Vec<_>>();
+        eng.leader_handler().unwrap().leader_append_entries(entries);
+
+        for index in 1..=64 {
+            for target in [2, 3] {
+                let res = ReplicationResult(Ok(Some(log_id(1, 1, index))));
+                eng.replication_handler().update_progress(black_box(target), Ok(res));
+            }
+        }
+
+        black_box(eng.output.take_commands());
+        eng
+    });
+}
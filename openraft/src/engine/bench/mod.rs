@@ -0,0 +1,42 @@
+mod leader_append_entries;
+mod leader_update_progress;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+
+use crate::engine::testing::log_id;
+use crate::engine::testing::UTConfig;
+use crate::engine::Engine;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+
+/// A leader of 3 voters, with storage and network replaced by dropping the output commands.
+pub(crate) fn eng() -> Engine<UTConfig> {
+    let m123 = Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2,3}], []);
+
+    let mut eng = Engine::testing_default(1);
+    eng.state.enable_validation(false);
+
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(1, 1),
+    );
+    eng.state.log_ids.append(log_id(0, 1, 0));
+    eng.state.committed = Some(log_id(0, 1, 0));
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(0, 1, 0)), m123.clone())),
+        Arc::new(EffectiveMembership::new(Some(log_id(0, 1, 0)), m123)),
+    );
+    eng.testing_new_leader();
+    eng.state.server_state = eng.calc_server_state();
+    eng.output.take_commands();
+
+    eng
+}
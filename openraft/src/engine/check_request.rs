@@ -0,0 +1,201 @@
+//! Reject requests that a correct peer never sends, before they change any local state.
+//!
+//! A malformed request, e.g., from a buggy third party implementation, may otherwise break the
+//! invariants the [`Engine`](super::Engine) relies on, such as truncating committed logs.
+
+use crate::entry::RaftEntry;
+use crate::error::MalformedRequest;
+use crate::log_id::option_raft_log_id_ext::OptionRaftLogIdExt;
+use crate::raft::VoteRequest;
+use crate::raft_state::LogStateReader;
+use crate::storage::SnapshotMeta;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::vote::raft_vote::RaftVoteExt;
+use crate::RaftState;
+use crate::RaftTypeConfig;
+
+/// Check the `prev_log_id` of an AppendEntries request before the sender's vote is accepted: it
+/// must not be greater than the sender's vote.
+///
+/// The check against the local committed log is done by [`check_prev_log_id_committed`] after the
+/// vote is accepted, so that a stale leader is told about the greater vote instead of being
+/// rejected as malformed.
+pub(crate) fn check_prev_log_id<C>(
+    vote: &VoteOf<C>,
+    prev_log_id: Option<&LogIdOf<C>>,
+) -> Result<(), MalformedRequest<C>>
+where
+    C: RaftTypeConfig,
+{
+    if let Some(prev) = prev_log_id {
+        check_log_id_not_after_vote(prev, vote)?;
+    }
+    Ok(())
+}
+
+/// Check the `prev_log_id` of an AppendEntries request after the sender's vote is accepted: it
+/// must not conflict with the local committed log.
+pub(crate) fn check_prev_log_id_committed<C>(
+    state: &RaftState<C>,
+    prev_log_id: Option<&LogIdOf<C>>,
+) -> Result<(), MalformedRequest<C>>
+where
+    C: RaftTypeConfig,
+{
+    if let Some(prev) = prev_log_id {
+        check_committed_log_id(state, prev)?;
+    }
+    Ok(())
+}
+
+/// Check the entries in an AppendEntries request: they must follow `prev_log_id` one by one, must
+/// not be greater than the sender's vote, and must not conflict with the local committed log.
+pub(crate) fn check_entries<C>(
+    state: &RaftState<C>,
+    vote: &VoteOf<C>,
+    prev_log_id: Option<&LogIdOf<C>>,
+    entries: &[C::Entry],
+) -> Result<(), MalformedRequest<C>>
+where
+    C: RaftTypeConfig,
+{
+    let mut prev = prev_log_id.cloned();
+
+    for entry in entries {
+        let log_id = entry.log_id();
+
+        let consecutive = log_id.index() == prev.next_index()
+            && prev.as_ref().map(|p| p.committed_leader_id()) <= Some(log_id.committed_leader_id());
+
+        if !consecutive {
+            return Err(MalformedRequest::NonConsecutiveLogIds { prev, next: log_id });
+        }
+
+        check_log_id_not_after_vote(&log_id, vote)?;
+        check_committed_log_id(state, &log_id)?;
+
+        prev = Some(log_id);
+    }
+
+    Ok(())
+}
+
+/// Check the committed log id sent by the leader, after the entries in the same request are
+/// accepted: it must not be greater than the leader's vote, and if it is not greater than the
+/// accepted logs, which are identical to the leader's, it must be one of them.
+pub(crate) fn check_leader_committed<C>(
+    state: &RaftState<C>,
+    leader_committed: Option<&LogIdOf<C>>,
+) -> Result<(), MalformedRequest<C>>
+where
+    C: RaftTypeConfig,
+{
+    let Some(leader_committed) = leader_committed else {
+        return Ok(());
+    };
+
+    check_log_id_not_after_vote(leader_committed, state.vote_ref())?;
+    check_committed_log_id(state, leader_committed)?;
+
+    let accepted_index = state.accepted_io().and_then(|io_id| io_id.last_log_id()).map(|x| x.index());
+
+    if Some(leader_committed.index()) <= accepted_index {
+        if let Some(local) = state.get_log_id(leader_committed.index()) {
+            if &local != leader_committed {
+                return Err(MalformedRequest::LeaderCommittedNotAccepted {
+                    leader_committed: leader_committed.clone(),
+                    local,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a vote request: the candidate's last log id must not be greater than its vote.
+pub(crate) fn check_vote_request<C>(req: &VoteRequest<C>) -> Result<(), MalformedRequest<C>>
+where C: RaftTypeConfig {
+    if let Some(last_log_id) = &req.last_log_id {
+        check_log_id_not_after_vote(last_log_id, &req.vote)?;
+    }
+    Ok(())
+}
+
+/// Check the meta of a snapshot to install before the sender's vote is accepted: the last log id
+/// must not be greater than the sender's vote, and the last membership must be included.
+///
+/// The check against the local committed log is done by [`check_snapshot_meta_committed`] after
+/// the vote is accepted.
+pub(crate) fn check_snapshot_meta<C>(vote: &VoteOf<C>, meta: &SnapshotMeta<C>) -> Result<(), MalformedRequest<C>>
+where C: RaftTypeConfig {
+    if let Some(last_log_id) = &meta.last_log_id {
+        check_log_id_not_after_vote(last_log_id, vote)?;
+    }
+
+    let membership = meta.last_membership.log_id();
+    if membership > &meta.last_log_id {
+        return Err(MalformedRequest::SnapshotMembershipAfterLastLogId {
+            membership: membership.clone(),
+            last_log_id: meta.last_log_id.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Check the meta of a snapshot to install after the sender's vote is accepted: the last log id
+/// must not conflict with the local committed log.
+pub(crate) fn check_snapshot_meta_committed<C>(
+    state: &RaftState<C>,
+    meta: &SnapshotMeta<C>,
+) -> Result<(), MalformedRequest<C>>
+where
+    C: RaftTypeConfig,
+{
+    if let Some(last_log_id) = &meta.last_log_id {
+        check_committed_log_id(state, last_log_id)?;
+    }
+    Ok(())
+}
+
+/// A log id must not be proposed by a leader greater than the vote of the sender.
+fn check_log_id_not_after_vote<C>(log_id: &LogIdOf<C>, vote: &VoteOf<C>) -> Result<(), MalformedRequest<C>>
+where C: RaftTypeConfig {
+    if log_id.committed_leader_id() > &vote.to_committed().committed_leader_id() {
+        return Err(MalformedRequest::LogIdAfterVote {
+            log_id: log_id.clone(),
+            vote: vote.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// A log id at or before the local committed index must be the one in the local log.
+///
+/// If the local log at the index is purged, it must not be greater than the committed log id.
+fn check_committed_log_id<C>(state: &RaftState<C>, log_id: &LogIdOf<C>) -> Result<(), MalformedRequest<C>>
+where C: RaftTypeConfig {
+    let Some(committed) = state.committed() else {
+        return Ok(());
+    };
+
+    if log_id.index() > committed.index() {
+        return Ok(());
+    }
+
+    let matches = match state.get_log_id(log_id.index()) {
+        Some(local) => &local == log_id,
+        None => log_id <= committed,
+    };
+
+    if matches {
+        Ok(())
+    } else {
+        Err(MalformedRequest::ConflictWithCommitted {
+            log_id: log_id.clone(),
+            committed: committed.clone(),
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+/// An input event handled by the `Engine` and the commands it emitted for it.
+///
+/// Both are recorded in their `Display` form, so that a trace can be dumped without knowing the
+/// application types.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+pub struct EngineDecision {
+    /// The input event, such as an API message or a notification.
+    pub input: String,
+
+    /// The commands emitted by the `Engine` when handling the input.
+    pub commands: Vec<String>,
+}
+
+impl fmt::Display for EngineDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} => [{}]", self.input, self.commands.join(", "))
+    }
+}
+
+/// A ring buffer of the last [`EngineDecision`]s.
+///
+/// It is disabled if the capacity is 0.
+#[derive(Clone, Debug, Default)]
+#[derive(PartialEq, Eq)]
+pub(crate) struct DecisionTrace {
+    capacity: usize,
+
+    decisions: VecDeque<EngineDecision>,
+
+    /// The input being handled and the number of commands queued before it.
+    pending: Option<(String, usize)>,
+}
+
+impl DecisionTrace {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            decisions: VecDeque::with_capacity(capacity),
+            pending: None,
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Start recording a decision for `input`, with `queued` commands already in the output.
+    ///
+    /// A pending decision must be ended before starting a new one.
+    pub(crate) fn begin(&mut self, input: String, queued: usize) {
+        debug_assert!(self.pending.is_none());
+        self.pending = Some((input, queued));
+    }
+
+    /// Returns the number of commands queued before the pending decision started.
+    pub(crate) fn pending_start(&self) -> Option<usize> {
+        self.pending.as_ref().map(|(_, queued)| *queued)
+    }
+
+    /// End the pending decision with the commands emitted for it, evicting the oldest one if full.
+    pub(crate) fn end(&mut self, commands: Vec<String>) {
+        let Some((input, _)) = self.pending.take() else {
+            return;
+        };
+
+        if self.decisions.len() == self.capacity {
+            self.decisions.pop_front();
+        }
+        self.decisions.push_back(EngineDecision { input, commands });
+    }
+
+    /// Iterate the recorded decisions, from the oldest to the latest.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &EngineDecision> {
+        self.decisions.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DecisionTrace;
+    use super::EngineDecision;
+
+    fn decision(input: &str, commands: &[&str]) -> EngineDecision {
+        EngineDecision {
+            input: input.to_string(),
+            commands: commands.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_decision_trace_ring_buffer() {
+        let mut t = DecisionTrace::new(2);
+        assert!(t.is_enabled());
+
+        t.begin("a".to_string(), 0);
+        assert_eq!(Some(0), t.pending_start());
+        t.end(vec!["x".to_string()]);
+        assert_eq!(None, t.pending_start());
+
+        t.begin("b".to_string(), 1);
+        t.end(vec![]);
+
+        t.begin("c".to_string(), 1);
+        t.end(vec!["y".to_string(), "z".to_string()]);
+
+        let got = t.iter().cloned().collect::<Vec<_>>();
+        assert_eq!(vec![decision("b", &[]), decision("c", &["y", "z"])], got);
+        assert_eq!("c => [y, z]", got[1].to_string());
+
+        // Ending without a pending decision records nothing.
+        t.end(vec!["w".to_string()]);
+        assert_eq!(2, t.iter().count());
+    }
+
+    #[test]
+    fn test_decision_trace_disabled() {
+        let t = DecisionTrace::default();
+        assert!(!t.is_enabled());
+        assert_eq!(0, t.iter().count());
+    }
+}
@@ -27,6 +27,12 @@ pub(crate) struct EngineConfig<C: RaftTypeConfig> {
 
     pub(crate) allow_log_reversion: bool,
 
+    /// The number of the last decisions to keep in the decision trace, 0 to disable it.
+    pub(crate) decision_trace_capacity: usize,
+
+    /// Panic at once on a violated invariant, or report it as a `Fatal` error.
+    pub(crate) panic_on_invariant_violation: bool,
+
     pub(crate) timer_config: time_state::Config,
 }
 
@@ -42,6 +48,8 @@ where C: RaftTypeConfig
             purge_batch_size: config.purge_batch_size,
             max_payload_entries: config.max_payload_entries,
             allow_log_reversion: config.get_allow_log_reversion(),
+            decision_trace_capacity: config.decision_trace_capacity as usize,
+            panic_on_invariant_violation: config.panic_on_invariant_violation,
 
             timer_config: time_state::Config {
                 election_timeout,
@@ -51,6 +59,25 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Replace the values that can be updated at runtime with the ones in `config`.
+    ///
+    /// The timer config is rebuilt only if the election timeout range differs from the one in
+    /// `prev`: then a new election timeout is chosen from the range in `config`, which takes
+    /// effect since the next time the timer is checked. Otherwise the election timeout in use is
+    /// kept.
+    pub(crate) fn update(&mut self, prev: &Config, config: &Config) {
+        self.snapshot_policy = config.snapshot_policy.clone();
+        self.max_in_snapshot_log_to_keep = config.max_in_snapshot_log_to_keep;
+        self.purge_batch_size = config.purge_batch_size;
+        self.max_payload_entries = config.max_payload_entries;
+
+        if prev.election_timeout_min != config.election_timeout_min
+            || prev.election_timeout_max != config.election_timeout_max
+        {
+            self.timer_config = Self::new(self.id.clone(), config).timer_config;
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn new_default(id: C::NodeId) -> Self {
         Self {
@@ -60,6 +87,8 @@ where C: RaftTypeConfig
             purge_batch_size: 256,
             max_payload_entries: 300,
             allow_log_reversion: false,
+            decision_trace_capacity: 0,
+            panic_on_invariant_violation: true,
             timer_config: time_state::Config::default(),
         }
     }
@@ -1,6 +1,8 @@
+use std::fmt;
 use std::time::Duration;
 
 use validit::Valid;
+use validit::Validate;
 
 use crate::core::raft_msg::AppendEntriesTx;
 use crate::core::raft_msg::ResultSender;
@@ -8,6 +10,7 @@ use crate::core::sm;
 use crate::core::ServerState;
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySliceExt;
+use crate::engine::check_request;
 use crate::engine::engine_config::EngineConfig;
 use crate::engine::handler::establish_handler::EstablishHandler;
 use crate::engine::handler::following_handler::FollowingHandler;
@@ -19,6 +22,7 @@ use crate::engine::handler::snapshot_handler::SnapshotHandler;
 use crate::engine::handler::vote_handler::VoteHandler;
 use crate::engine::Command;
 use crate::engine::Condition;
+use crate::engine::DecisionTrace;
 use crate::engine::EngineOutput;
 use crate::engine::Respond;
 use crate::entry::RaftEntry;
@@ -29,6 +33,8 @@ use crate::error::InitializeError;
 use crate::error::NotAllowed;
 use crate::error::NotInMembers;
 use crate::error::RejectAppendEntries;
+use crate::metrics::ElectionMetrics;
+use crate::progress::Progress;
 use crate::proposer::leader_state::CandidateState;
 use crate::proposer::Candidate;
 use crate::proposer::Leader;
@@ -37,6 +43,7 @@ use crate::proposer::LeaderState;
 use crate::raft::responder::Responder;
 use crate::raft::AppendEntriesResponse;
 use crate::raft::SnapshotResponse;
+use crate::raft::StateReport;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::raft_state::IOId;
@@ -93,21 +100,34 @@ where C: RaftTypeConfig
     /// without losing leadership status.
     pub(crate) candidate: CandidateState<C>,
 
+    /// Counts the elections started and won by this node, and the vote requests rejected by the
+    /// leader lease.
+    pub(crate) election_metrics: ElectionMetrics<C>,
+
     /// Output entry for the runtime.
     pub(crate) output: EngineOutput<C>,
+
+    /// The last decisions made by this `Engine`, if [`Config::decision_trace_capacity`] is not 0.
+    ///
+    /// [`Config::decision_trace_capacity`]: crate::Config::decision_trace_capacity
+    pub(crate) decision_trace: DecisionTrace,
 }
 
 impl<C> Engine<C>
 where C: RaftTypeConfig
 {
     pub(crate) fn new(init_state: RaftState<C>, config: EngineConfig<C>) -> Self {
+        let decision_trace = DecisionTrace::new(config.decision_trace_capacity);
+
         Self {
             config,
             state: Valid::new(init_state),
             seen_greater_log: false,
             leader: None,
             candidate: None,
+            election_metrics: ElectionMetrics::default(),
             output: EngineOutput::new(4096),
+            decision_trace,
         }
     }
 
@@ -132,6 +152,32 @@ where C: RaftTypeConfig
         self.candidate.as_mut().unwrap()
     }
 
+    /// Start recording the commands emitted for `input` in the decision trace.
+    ///
+    /// The decision being recorded, if any, is ended first. It does nothing if the decision trace
+    /// is disabled.
+    pub(crate) fn begin_decision(&mut self, input: impl fmt::Display) {
+        if !self.decision_trace.is_enabled() {
+            return;
+        }
+
+        self.end_decision();
+
+        let queued = self.output.len();
+        self.decision_trace.begin(input.to_string(), queued);
+    }
+
+    /// Record the commands emitted since the last [`Self::begin_decision()`] in the decision
+    /// trace.
+    pub(crate) fn end_decision(&mut self) {
+        let Some(start) = self.decision_trace.pending_start() else {
+            return;
+        };
+
+        let commands = self.output.iter_commands().skip(start).map(|c| c.to_string()).collect();
+        self.decision_trace.end(commands);
+    }
+
     /// Create a default Engine for testing.
     #[allow(dead_code)]
     pub(crate) fn testing_default(id: C::NodeId) -> Self {
@@ -200,7 +246,8 @@ where C: RaftTypeConfig
         self.state.vote.update(C::now(), Duration::default(), vote);
         self.following_handler().do_append_entries(vec![entry]);
 
-        // With the new config, start to elect to become leader
+        // With the new config, start to elect to become leader at once, without waiting for the
+        // election timeout.
         self.elect();
 
         Ok(())
@@ -213,6 +260,8 @@ where C: RaftTypeConfig
         let leader_id = LeaderIdOf::<C>::new(new_term, self.config.id.clone());
         let new_vote = VoteOf::<C>::from_leader_id(leader_id, false);
 
+        self.election_metrics.elections_started += 1;
+
         let candidate = self.new_candidate(new_vote.clone());
 
         tracing::info!("{}, new candidate: {}", func_name!(), candidate);
@@ -284,6 +333,11 @@ where C: RaftTypeConfig
             "Engine::handle_vote_req"
         );
 
+        if let Err(e) = check_request::check_vote_request(&req) {
+            tracing::warn!("reject vote-request: {}", e);
+            return VoteResponse::new(self.state.vote_ref(), self.state.last_log_id().cloned(), false);
+        }
+
         if local_leased_vote.is_committed() {
             // Current leader lease has not yet expired, reject voting request
             if !local_leased_vote.is_expired(now, Duration::from_millis(0)) {
@@ -292,6 +346,8 @@ where C: RaftTypeConfig
                     local_leased_vote.display_lease_info(now)
                 );
 
+                self.election_metrics.vote_requests_rejected_by_lease += 1;
+
                 return VoteResponse::new(self.state.vote_ref(), self.state.last_log_id().cloned(), false);
             }
         }
@@ -423,13 +479,25 @@ where C: RaftTypeConfig
         prev_log_id: Option<LogIdOf<C>>,
         entries: Vec<C::Entry>,
     ) -> Result<(), RejectAppendEntries<C>> {
+        check_request::check_prev_log_id(vote, prev_log_id.as_ref()).inspect_err(|e| {
+            tracing::warn!("reject AppendEntries: {}", e);
+        })?;
+
         self.vote_handler().update_vote(vote)?;
 
         // Vote is legal.
 
-        let mut fh = self.following_handler();
-        fh.ensure_log_consecutive(prev_log_id.as_ref())?;
-        fh.append_entries(prev_log_id, entries);
+        check_request::check_prev_log_id_committed(&self.state, prev_log_id.as_ref()).inspect_err(|e| {
+            tracing::warn!("reject AppendEntries: {}", e);
+        })?;
+
+        self.following_handler().ensure_log_consecutive(prev_log_id.as_ref())?;
+
+        check_request::check_entries(&self.state, vote, prev_log_id.as_ref(), &entries).inspect_err(|e| {
+            tracing::warn!("reject AppendEntries entries: {}", e);
+        })?;
+
+        self.following_handler().append_entries(prev_log_id, entries);
 
         Ok(())
     }
@@ -445,6 +513,11 @@ where C: RaftTypeConfig
             func_name!()
         );
 
+        if let Err(e) = check_request::check_leader_committed(&self.state, leader_committed.as_ref()) {
+            tracing::warn!("ignore leader committed: {}", e);
+            return;
+        }
+
         let mut fh = self.following_handler();
         fh.commit_entries(leader_committed);
     }
@@ -459,6 +532,18 @@ where C: RaftTypeConfig
     ) {
         tracing::info!(vote = display(&vote), snapshot = display(&snapshot), "{}", func_name!());
 
+        if let Err(e) = check_request::check_snapshot_meta(&vote, &snapshot.meta) {
+            tracing::warn!("reject snapshot: {}", e);
+            self.output.push_command(Command::Respond {
+                when: None,
+                resp: Respond::new(
+                    Ok(SnapshotResponse::new_malformed(self.state.vote_ref().clone(), e)),
+                    tx,
+                ),
+            });
+            return;
+        }
+
         let vote_res = self.vote_handler().accept_vote(&vote, tx, |state, _rejected| {
             Ok(SnapshotResponse::new(state.vote_ref().clone()))
         });
@@ -467,14 +552,26 @@ where C: RaftTypeConfig
             return;
         };
 
+        // Vote is legal.
+
+        if let Err(e) = check_request::check_snapshot_meta_committed(&self.state, &snapshot.meta) {
+            tracing::warn!("reject snapshot: {}", e);
+            self.output.push_command(Command::Respond {
+                when: None,
+                resp: Respond::new(
+                    Ok(SnapshotResponse::new_malformed(self.state.vote_ref().clone(), e)),
+                    tx,
+                ),
+            });
+            return;
+        }
+
         let mut fh = self.following_handler();
 
         // The condition to satisfy before running other command that depends on the snapshot.
         // In this case, the response can only be sent when the snapshot is installed.
         let cond = fh.install_full_snapshot(snapshot);
-        let res = Ok(SnapshotResponse {
-            vote: self.state.vote_ref().clone(),
-        });
+        let res = Ok(SnapshotResponse::new(self.state.vote_ref().clone()));
 
         self.output.push_command(Command::Respond {
             when: cond,
@@ -565,9 +662,12 @@ where C: RaftTypeConfig
         }
     }
 
-    /// This is a to user API that triggers log purging upto `index`, inclusive.
+    /// Schedule to purge logs upto `index`, inclusive, and return the scheduled purge point.
+    ///
+    /// The returned log id may be smaller than `index`, because only logs in a snapshot can be
+    /// purged.
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(crate) fn trigger_purge_log(&mut self, mut index: u64) {
+    pub(crate) fn trigger_purge_log(&mut self, mut index: u64) -> Option<LogIdOf<C>> {
         tracing::info!(index = display(index), "{}", func_name!());
 
         let snapshot_last_log_id = self.state.snapshot_last_log_id();
@@ -575,7 +675,7 @@ where C: RaftTypeConfig
             log_id.clone()
         } else {
             tracing::info!("no snapshot, can not purge");
-            return;
+            return self.state.purge_upto().cloned();
         };
 
         let scheduled = self.state.purge_upto();
@@ -586,7 +686,7 @@ where C: RaftTypeConfig
                 scheduled.display(),
                 index,
             );
-            return;
+            return scheduled.cloned();
         }
 
         if index > snapshot_last_log_id.index() {
@@ -603,8 +703,10 @@ where C: RaftTypeConfig
 
         tracing::info!(purge_upto = display(&log_id), "{}", func_name!());
 
-        self.log_handler().update_purge_upto(log_id);
+        self.log_handler().update_purge_upto(log_id.clone());
         self.try_purge_log();
+
+        Some(log_id)
     }
 
     pub(crate) fn trigger_transfer_leader(&mut self, to: C::NodeId) {
@@ -641,6 +743,8 @@ where C: RaftTypeConfig
         let vote = leader.committed_vote_ref().clone();
         let last_log_id = leader.last_log_id().cloned();
 
+        self.election_metrics.elections_won += 1;
+
         self.replication_handler().rebuild_replication_streams();
 
         // Before sending any log, update the vote.
@@ -710,6 +814,92 @@ where C: RaftTypeConfig
         self.seen_greater_log = false;
     }
 
+    /// Run the internal consistency checks of the state, and of the replication progress if this
+    /// node is a leader.
+    ///
+    /// Unlike validating the state, which panics on the first violation, it runs all of the
+    /// checks and reports the result of each.
+    pub(crate) fn verify_state(&self) -> StateReport {
+        fn less_equal<T>(a: Option<&T>, b: Option<&T>) -> Result<(), String>
+        where T: PartialOrd + fmt::Display {
+            if a <= b {
+                Ok(())
+            } else {
+                Err(format!("{} > {}", a.display(), b.display()))
+            }
+        }
+
+        let st = &self.state;
+        let mut report = StateReport::default();
+
+        let first = st.log_ids.first();
+        report.check(
+            "log ids start at purged",
+            if st.purged_next == first.next_index() || (st.purged_next == 0 && first.index() <= Some(0)) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "purged_next: {}, first log id: {}",
+                    st.purged_next,
+                    first.display()
+                ))
+            },
+        );
+
+        // An application may not persist snapshot, thus after restarting, there are purged logs
+        // but no snapshot.
+        if st.snapshot_last_log_id().is_some() {
+            report.check(
+                "purged <= snapshot",
+                less_equal(st.last_purged_log_id(), st.snapshot_last_log_id()),
+            );
+        } else {
+            report.check(
+                "purged <= committed",
+                less_equal(st.last_purged_log_id(), st.committed()),
+            );
+        }
+        report.check(
+            "snapshot <= applied",
+            less_equal(st.snapshot_last_log_id(), st.io_applied()),
+        );
+        report.check("applied <= committed", less_equal(st.io_applied(), st.committed()));
+        report.check("committed <= last_log", less_equal(st.committed(), st.last_log_id()));
+
+        let vote_leader = st.vote_ref().leader_id().map(|x| x.to_committed()).unwrap_or_default();
+        let last_log_leader = st.last_log_id().map(|x| x.committed_leader_id().clone()).unwrap_or_default();
+        report.check(
+            "last_log leader <= vote",
+            less_equal(Some(&last_log_leader), Some(&vote_leader)),
+        );
+
+        let committed_membership = st.membership_state.committed().log_id().as_ref();
+        let effective_membership = st.membership_state.effective().log_id().as_ref();
+        report.check(
+            "committed membership <= effective membership",
+            less_equal(committed_membership, effective_membership),
+        );
+        report.check(
+            "effective membership <= last_log",
+            less_equal(effective_membership, st.last_log_id()),
+        );
+
+        if let Some(leader) = self.leader.as_ref() {
+            for (id, entry) in leader.progress.iter() {
+                report.check(
+                    format!("progress of {} <= last_log", id),
+                    less_equal(entry.matching(), st.last_log_id()),
+                );
+                report.check(
+                    format!("progress of {} is valid", id),
+                    entry.validate().map_err(|e| format!("{}: {}", entry, e)),
+                );
+            }
+        }
+
+        report
+    }
+
     // Only used by tests
     #[allow(dead_code)]
     pub(crate) fn calc_server_state(&self) -> ServerState {
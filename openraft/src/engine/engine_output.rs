@@ -1,6 +1,8 @@
 use std::collections::VecDeque;
 
+use crate::engine::engine_config::EngineConfig;
 use crate::engine::Command;
+use crate::error::InvariantViolation;
 use crate::RaftTypeConfig;
 
 /// The entry of output from Engine to the runtime.
@@ -10,6 +12,9 @@ where C: RaftTypeConfig
 {
     /// Command queue that need to be executed by `RaftRuntime`.
     pub(crate) commands: VecDeque<Command<C>>,
+
+    /// The first violated invariant, if it does not panic, for `RaftCore` to quit with.
+    pub(crate) invariant_violation: Option<InvariantViolation>,
 }
 
 impl<C> EngineOutput<C>
@@ -18,6 +23,7 @@ where C: RaftTypeConfig
     pub(crate) fn new(command_buffer_size: usize) -> Self {
         Self {
             commands: VecDeque::with_capacity(command_buffer_size),
+            invariant_violation: None,
         }
     }
 
@@ -49,8 +55,30 @@ where C: RaftTypeConfig
         self.commands.iter()
     }
 
+    /// Report a violated invariant described by `context`.
+    ///
+    /// It panics if [`EngineConfig::panic_on_invariant_violation`] is enabled. Otherwise the first
+    /// violation is kept and `RaftCore` quits with it.
+    pub(crate) fn invariant_violated(&mut self, config: &EngineConfig<C>, context: impl ToString) {
+        let violation = InvariantViolation::new(context);
+
+        if config.panic_on_invariant_violation {
+            panic!("{}", violation);
+        }
+
+        tracing::error!("{}", violation);
+        if self.invariant_violation.is_none() {
+            self.invariant_violation = Some(violation);
+        }
+    }
+
+    /// Take the violated invariant reported since the last call, if any.
+    pub(crate) fn take_invariant_violation(&mut self) -> Option<InvariantViolation> {
+        self.invariant_violation.take()
+    }
+
     /// Take all queued commands and clear the queue.
-    #[cfg(test)]
+    #[cfg(any(test, feature = "fuzzing"))]
     pub(crate) fn take_commands(&mut self) -> Vec<Command<C>> {
         self.commands.drain(..).collect()
     }
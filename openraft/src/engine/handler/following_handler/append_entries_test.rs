@@ -10,7 +10,6 @@ use crate::engine::Command;
 use crate::engine::Condition;
 use crate::engine::Engine;
 use crate::raft_state::IOId;
-use crate::raft_state::LogStateReader;
 use crate::testing::blank_ent;
 use crate::type_config::alias::VoteOf;
 use crate::type_config::TypeConfigExt;
@@ -47,11 +47,11 @@ fn eng() -> Engine<UTConfig> {
         log_id(4, 1, 6),
         log_id(4, 1, 8),
     ]);
-    eng.state.snapshot_meta = SnapshotMeta {
-        last_log_id: Some(log_id(2, 1, 2)),
-        last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
-        snapshot_id: "1-2-3-4".to_string(),
-    };
+    eng.state.snapshot_meta = SnapshotMeta::new(
+        Some(log_id(2, 1, 2)),
+        StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+        "1-2-3-4".to_string(),
+    );
     eng.state.server_state = eng.calc_server_state();
 
     eng
@@ -64,22 +64,22 @@ fn test_install_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
     let mut eng = eng();
 
     let cond = eng.following_handler().install_full_snapshot(Snapshot {
-        meta: SnapshotMeta {
-            last_log_id: Some(log_id(2, 1, 2)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        meta: SnapshotMeta::new(
+            Some(log_id(2, 1, 2)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string(),
+        ),
         snapshot: Cursor::new(vec![0u8]),
     });
 
     assert_eq!(None, cond);
 
     assert_eq!(
-        SnapshotMeta {
-            last_log_id: Some(log_id(2, 1, 2)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        SnapshotMeta::new(
+            Some(log_id(2, 1, 2)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+            "1-2-3-4".to_string()
+        ),
         eng.state.snapshot_meta
     );
     assert!(eng.output.take_commands().is_empty());
@@ -96,22 +96,22 @@ fn test_install_snapshot_lt_committed() -> anyhow::Result<()> {
     let mut eng = eng();
 
     let cond = eng.following_handler().install_full_snapshot(Snapshot {
-        meta: SnapshotMeta {
-            last_log_id: Some(log_id(4, 1, 5)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        meta: SnapshotMeta::new(
+            Some(log_id(4, 1, 5)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string(),
+        ),
         snapshot: Cursor::new(vec![0u8]),
     });
 
     assert_eq!(None, cond);
 
     assert_eq!(
-        SnapshotMeta {
-            last_log_id: Some(log_id(2, 1, 2)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        SnapshotMeta::new(
+            Some(log_id(2, 1, 2)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+            "1-2-3-4".to_string()
+        ),
         eng.state.snapshot_meta
     );
     assert!(eng.output.take_commands().is_empty());
@@ -125,11 +125,11 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
     let mut eng = eng();
 
     let cond = eng.following_handler().install_full_snapshot(Snapshot {
-        meta: SnapshotMeta {
-            last_log_id: Some(log_id(4, 1, 6)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        meta: SnapshotMeta::new(
+            Some(log_id(4, 1, 6)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string(),
+        ),
         snapshot: Cursor::new(vec![0u8]),
     });
 
@@ -141,11 +141,11 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
     );
 
     assert_eq!(
-        SnapshotMeta {
-            last_log_id: Some(log_id(4, 1, 6)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        SnapshotMeta::new(
+            Some(log_id(4, 1, 6)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string()
+        ),
         eng.state.snapshot_meta
     );
     assert_eq!(&[log_id(4, 1, 6), log_id(4, 1, 8)], eng.state.log_ids.key_log_ids());
@@ -159,11 +159,11 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
             //
             Command::from(sm::Command::install_full_snapshot(
                 Snapshot {
-                    meta: SnapshotMeta {
-                        last_log_id: Some(log_id(4, 1, 6)),
-                        last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-                        snapshot_id: "1-2-3-4".to_string(),
-                    },
+                    meta: SnapshotMeta::new(
+                        Some(log_id(4, 1, 6)),
+                        StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                        "1-2-3-4".to_string()
+                    ),
                     snapshot: Cursor::new(vec![0u8]),
                 },
                 IOId::new_log_io(Vote::new(2, 1).into_committed(), Some(log_id(4, 1, 6))),
@@ -198,11 +198,11 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             log_id(4, 1, 8),
         ]);
 
-        eng.state.snapshot_meta = SnapshotMeta {
-            last_log_id: Some(log_id(2, 1, 2)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
-            snapshot_id: "1-2-3-4".to_string(),
-        };
+        eng.state.snapshot_meta = SnapshotMeta::new(
+            Some(log_id(2, 1, 2)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+            "1-2-3-4".to_string(),
+        );
 
         eng.state.server_state = eng.calc_server_state();
 
@@ -210,11 +210,11 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
     };
 
     let cond = eng.following_handler().install_full_snapshot(Snapshot {
-        meta: SnapshotMeta {
-            last_log_id: Some(log_id(5, 1, 6)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        meta: SnapshotMeta::new(
+            Some(log_id(5, 1, 6)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string(),
+        ),
         snapshot: Cursor::new(vec![0u8]),
     });
 
@@ -226,11 +226,11 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
     );
 
     assert_eq!(
-        SnapshotMeta {
-            last_log_id: Some(log_id(5, 1, 6)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        SnapshotMeta::new(
+            Some(log_id(5, 1, 6)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string()
+        ),
         eng.state.snapshot_meta
     );
     assert_eq!(&[log_id(5, 1, 6)], eng.state.log_ids.key_log_ids());
@@ -245,11 +245,11 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             Command::TruncateLog { since: log_id(2, 1, 4) },
             Command::from(sm::Command::install_full_snapshot(
                 Snapshot {
-                    meta: SnapshotMeta {
-                        last_log_id: Some(log_id(5, 1, 6)),
-                        last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-                        snapshot_id: "1-2-3-4".to_string(),
-                    },
+                    meta: SnapshotMeta::new(
+                        Some(log_id(5, 1, 6)),
+                        StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                        "1-2-3-4".to_string()
+                    ),
                     snapshot: Cursor::new(vec![0u8]),
                 },
                 IOId::new_log_io(Vote::new(2, 1).into_committed(), Some(log_id(5, 1, 6)))
@@ -262,17 +262,89 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_install_snapshot_conflict_shorter_log() -> anyhow::Result<()> {
+    // The local log is shorter than the snapshot but ends with a greater, non-committed log id.
+    // The non-committed logs conflict and will be deleted.
+    let mut eng = {
+        let mut eng = Engine::<UTConfig>::testing_default(0);
+        eng.state.enable_validation(false); // Disable validation for incomplete state
+
+        eng.state.vote.update(
+            UTConfig::<()>::now(),
+            Duration::from_millis(500),
+            Vote::new_committed(2, 1),
+        );
+        eng.state.committed = Some(log_id(2, 1, 3));
+        eng.state.log_ids = LogIdList::new(vec![
+            //
+            log_id(2, 1, 2),
+            log_id(5, 1, 5),
+        ]);
+
+        eng.state.snapshot_meta = SnapshotMeta::new(
+            Some(log_id(2, 1, 2)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+            "1-2-3-4".to_string(),
+        );
+
+        eng.state.server_state = eng.calc_server_state();
+
+        eng
+    };
+
+    let cond = eng.following_handler().install_full_snapshot(Snapshot {
+        meta: SnapshotMeta::new(
+            Some(log_id(4, 1, 6)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string(),
+        ),
+        snapshot: Cursor::new(vec![0u8]),
+    });
+
+    assert_eq!(
+        Some(Condition::Snapshot {
+            log_id: Some(log_id(4, 1, 6))
+        }),
+        cond
+    );
+
+    assert_eq!(&[log_id(4, 1, 6)], eng.state.log_ids.key_log_ids());
+    assert_eq!(Some(&log_id(4, 1, 6)), eng.state.committed());
+    assert_eq!(
+        vec![
+            //
+            Command::TruncateLog { since: log_id(2, 1, 4) },
+            Command::from(sm::Command::install_full_snapshot(
+                Snapshot {
+                    meta: SnapshotMeta::new(
+                        Some(log_id(4, 1, 6)),
+                        StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                        "1-2-3-4".to_string()
+                    ),
+                    snapshot: Cursor::new(vec![0u8]),
+                },
+                IOId::new_log_io(Vote::new(2, 1).into_committed(), Some(log_id(4, 1, 6)))
+            )),
+            Command::PurgeLog { upto: log_id(4, 1, 6) },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
     // Snapshot will be installed and there are no conflicting logs.
     let mut eng = eng();
 
     let cond = eng.following_handler().install_full_snapshot(Snapshot {
-        meta: SnapshotMeta {
-            last_log_id: Some(log_id(100, 1, 100)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        meta: SnapshotMeta::new(
+            Some(log_id(100, 1, 100)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string(),
+        ),
         snapshot: Cursor::new(vec![0u8]),
     });
 
@@ -284,11 +356,11 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
     );
 
     assert_eq!(
-        SnapshotMeta {
-            last_log_id: Some(log_id(100, 1, 100)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        SnapshotMeta::new(
+            Some(log_id(100, 1, 100)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string()
+        ),
         eng.state.snapshot_meta
     );
     assert_eq!(&[log_id(100, 1, 100)], eng.state.log_ids.key_log_ids());
@@ -305,11 +377,11 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
         vec![
             Command::from(sm::Command::install_full_snapshot(
                 Snapshot {
-                    meta: SnapshotMeta {
-                        last_log_id: Some(log_id(100, 1, 100)),
-                        last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-                        snapshot_id: "1-2-3-4".to_string(),
-                    },
+                    meta: SnapshotMeta::new(
+                        Some(log_id(100, 1, 100)),
+                        StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                        "1-2-3-4".to_string()
+                    ),
                     snapshot: Cursor::new(vec![0u8]),
                 },
                 IOId::new_log_io(Vote::new(2, 1).into_committed(), Some(log_id(100, 1, 100)))
@@ -330,11 +402,11 @@ fn test_install_snapshot_update_accepted() -> anyhow::Result<()> {
     let mut eng = eng();
 
     let cond = eng.following_handler().install_full_snapshot(Snapshot {
-        meta: SnapshotMeta {
-            last_log_id: Some(log_id(100, 1, 100)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        meta: SnapshotMeta::new(
+            Some(log_id(100, 1, 100)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string(),
+        ),
         snapshot: Cursor::new(vec![0u8]),
     });
 
@@ -305,12 +305,14 @@ where C: RaftTypeConfig
             return None;
         }
 
-        let local = self.state.get_log_id(snap_last_log_id.index());
-        if let Some(local) = local {
-            if local != snap_last_log_id {
-                // Conflict, delete all non-committed logs.
-                self.truncate_logs(self.state.committed().next_index());
-            }
+        // A shorter local log conflicts if its last log id is greater, which is not in the snapshot.
+        let conflict = match self.state.get_log_id(snap_last_log_id.index()) {
+            Some(local) => local != snap_last_log_id,
+            None => self.state.last_log_id() > Some(&snap_last_log_id),
+        };
+        if conflict {
+            // Conflict, delete all non-committed logs.
+            self.truncate_logs(self.state.committed().next_index());
         }
 
         let io_id = IOId::new_log_io(self.leader_vote.clone(), Some(snap_last_log_id.clone()));
@@ -3,7 +3,6 @@ use crate::engine::testing::UTConfig;
 use crate::engine::Command;
 use crate::engine::Engine;
 use crate::engine::LogIdList;
-use crate::raft_state::LogStateReader;
 
 fn eng() -> Engine<UTConfig> {
     let mut eng = Engine::testing_default(0);
@@ -31,6 +31,8 @@ use crate::ServerState;
 #[cfg(test)]
 mod append_membership_test;
 #[cfg(test)]
+mod update_local_progress_test;
+#[cfg(test)]
 mod update_matching_test;
 
 /// Handle replication operations.
@@ -160,6 +162,31 @@ where C: RaftTypeConfig
 
         debug_assert!(log_id.is_some(), "a valid update can never set matching to None");
 
+        let Some(prog_entry) = self.leader.progress.try_get(&node_id) else {
+            self.output.invariant_violated(
+                self.config,
+                format!(
+                    "update matching to {}: target {} has no progress",
+                    log_id.display(),
+                    node_id
+                ),
+            );
+            return;
+        };
+
+        if prog_entry.inflight.is_none() {
+            self.output.invariant_violated(
+                self.config,
+                format!(
+                    "update matching to {}: target {} has no inflight data to acknowledge: {}",
+                    log_id.display(),
+                    node_id,
+                    prog_entry
+                ),
+            );
+            return;
+        }
+
         // The value granted by a quorum may not yet be a committed.
         // A committed is **granted** and also is in current term.
         let quorum_accepted = self
@@ -217,7 +244,24 @@ where C: RaftTypeConfig
     pub(crate) fn update_conflicting(&mut self, target: C::NodeId, conflict: LogIdOf<C>) {
         // TODO(2): test it?
 
-        let prog_entry = self.leader.progress.get_mut(&target).unwrap();
+        let Some(prog_entry) = self.leader.progress.get_mut(&target) else {
+            self.output.invariant_violated(
+                self.config,
+                format!("update conflicting at {}: target {} has no progress", conflict, target),
+            );
+            return;
+        };
+
+        if !prog_entry.inflight.is_sending_log() {
+            self.output.invariant_violated(
+                self.config,
+                format!(
+                    "update conflicting at {}: target {} is not sending logs: {}",
+                    conflict, target, prog_entry
+                ),
+            );
+            return;
+        }
 
         let mut updater = progress::entry::update::Updater::new(self.config, prog_entry);
 
@@ -276,7 +320,13 @@ where C: RaftTypeConfig
                 tracing::warn!(result = display(&err_str), "update progress error");
 
                 // Reset inflight state and it will retry.
-                let p = self.leader.progress.get_mut(&target).unwrap();
+                let Some(p) = self.leader.progress.get_mut(&target) else {
+                    self.output.invariant_violated(
+                        self.config,
+                        format!("reset inflight on error: target {} has no progress", target),
+                    );
+                    return;
+                };
                 p.inflight = Inflight::None;
             }
         };
@@ -399,6 +449,7 @@ where C: RaftTypeConfig
         }
 
         let id = self.config.id.clone();
+        let single_voter = self.is_single_voter(&id);
 
         // The leader may not be in membership anymore
         if let Some(prog_entry) = self.leader.progress.get_mut(&id) {
@@ -410,6 +461,23 @@ where C: RaftTypeConfig
             if prog_entry.matching() >= upto.as_ref() {
                 return;
             }
+
+            // Fast path: if this leader is the only voter, the flushed log is accepted by a quorum
+            // at once. Skip the inflight bookkeeping, which is only meaningful to a remote target.
+            if single_voter {
+                let quorum_accepted = self
+                    .leader
+                    .progress
+                    .update_with(&id, |prog_entry| {
+                        prog_entry.new_updater(&*self.config).update_local_matching(upto)
+                    })
+                    .expect("it should always update existing progress")
+                    .clone();
+
+                self.try_commit_quorum_accepted(quorum_accepted);
+                return;
+            }
+
             // TODO: It should be self.state.last_log_id() but None is ok.
             prog_entry.inflight = Inflight::logs(None, upto.clone());
 
@@ -417,6 +485,15 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Returns `true` if `id` is the only voter of the effective membership.
+    ///
+    /// Once more voters are added, the effective membership is a joint config with more than one
+    /// voter, and the regular replication bookkeeping is used.
+    fn is_single_voter(&self, id: &C::NodeId) -> bool {
+        let mut voter_ids = self.state.membership_state.effective().voter_ids();
+        voter_ids.next().as_ref() == Some(id) && voter_ids.next().is_none()
+    }
+
     pub(crate) fn log_handler(&mut self) -> LogHandler<C> {
         LogHandler {
             config: self.config,
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::engine::testing::log_id;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::raft_state::LogStateReader;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+
+fn m1() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1}], [2])
+}
+
+fn m1_12() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1}, btreeset! {1,2}], [])
+}
+
+fn eng(m: Membership<UTConfig>) -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 1),
+    );
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m.clone())),
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m)),
+    );
+
+    eng.testing_new_leader();
+    eng.output.take_commands();
+    eng
+}
+
+#[test]
+fn test_update_local_progress_single_voter() -> anyhow::Result<()> {
+    let mut eng = eng(m1());
+
+    // The only voter commits a flushed log at once, without tracking inflight data.
+    let mut rh = eng.replication_handler();
+    rh.update_local_progress(Some(log_id(2, 1, 3)));
+
+    assert_eq!(Some(&log_id(2, 1, 3)), rh.state.committed());
+    let prog_entry = rh.leader.progress.get(&1);
+    assert_eq!(Some(&log_id(2, 1, 3)), prog_entry.matching());
+    assert_eq!(Inflight::None, prog_entry.inflight);
+    assert_eq!(4, prog_entry.searching_end);
+
+    assert_eq!(
+        vec![
+            Command::ReplicateCommitted {
+                committed: Some(log_id(2, 1, 3))
+            },
+            Command::SaveCommitted {
+                committed: log_id(2, 1, 3)
+            },
+            Command::Apply {
+                already_committed: None,
+                upto: log_id(2, 1, 3)
+            }
+        ],
+        rh.output.take_commands()
+    );
+
+    // A flushed log that is already matching is ignored.
+    rh.update_local_progress(Some(log_id(2, 1, 3)));
+    assert_eq!(0, rh.output.take_commands().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_update_local_progress_more_voters() -> anyhow::Result<()> {
+    let mut eng = eng(m1_12());
+
+    // With another voter, the flushed log has to be replicated to be committed.
+    let mut rh = eng.replication_handler();
+    rh.update_local_progress(Some(log_id(2, 1, 3)));
+
+    assert_eq!(None, rh.state.committed());
+    assert_eq!(Some(&log_id(2, 1, 3)), rh.leader.progress.get(&1).matching());
+    assert_eq!(0, rh.output.take_commands().len());
+
+    rh.leader.progress.get_mut(&2).unwrap().inflight = Inflight::logs(None, Some(log_id(2, 1, 3)));
+    rh.update_matching(2, Some(log_id(2, 1, 3)));
+    assert_eq!(Some(&log_id(2, 1, 3)), rh.state.committed());
+
+    Ok(())
+}
+
+#[test]
+fn test_update_local_progress_fall_back_when_voter_added() -> anyhow::Result<()> {
+    let mut eng = eng(m1());
+    eng.state.server_state = eng.calc_server_state();
+
+    let mut rh = eng.replication_handler();
+    rh.update_local_progress(Some(log_id(2, 1, 3)));
+    assert_eq!(Some(&log_id(2, 1, 3)), rh.state.committed());
+
+    // Node 2 becomes a voter, the flushed log is no longer committed at once.
+    rh.append_membership(&log_id(2, 1, 4), &m1_12());
+    rh.output.take_commands();
+
+    rh.update_local_progress(Some(log_id(2, 1, 5)));
+
+    assert_eq!(Some(&log_id(2, 1, 3)), rh.state.committed());
+    assert_eq!(Some(&log_id(2, 1, 5)), rh.leader.progress.get(&1).matching());
+    assert_eq!(0, rh.output.take_commands().len());
+
+    rh.leader.progress.get_mut(&2).unwrap().inflight = Inflight::logs(None, Some(log_id(2, 1, 5)));
+    rh.update_matching(2, Some(log_id(2, 1, 5)));
+    assert_eq!(Some(&log_id(2, 1, 5)), rh.state.committed());
+
+    Ok(())
+}
@@ -139,3 +139,37 @@ fn test_update_matching() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_update_matching_invariant_violation() -> anyhow::Result<()> {
+    // There is no inflight data to acknowledge, it panics by default.
+    let res = std::panic::catch_unwind(move || {
+        let mut eng = eng();
+        eng.testing_new_leader();
+        eng.replication_handler().update_matching(3, Some(log_id(1, 1, 2)));
+    });
+    assert!(res.is_err());
+
+    // With panic disabled, the violation is reported and the progress is not updated.
+    let mut eng = eng();
+    eng.config.panic_on_invariant_violation = false;
+    eng.testing_new_leader();
+    eng.output.take_commands();
+
+    let mut rh = eng.replication_handler();
+    rh.update_matching(3, Some(log_id(1, 1, 2)));
+    rh.update_matching(4, Some(log_id(1, 1, 2)));
+
+    assert_eq!(None, rh.leader.progress.get(&3).matching());
+    assert_eq!(0, rh.output.take_commands().len());
+
+    let violation = rh.output.take_invariant_violation().unwrap();
+    assert_eq!(
+        "update matching to T1-N1.2: target 3 has no inflight data to acknowledge: \
+         {[None, 0), inflight:None}",
+        violation.context()
+    );
+    assert_eq!(None, rh.output.take_invariant_violation());
+
+    Ok(())
+}
@@ -4,7 +4,6 @@ use crate::core::sm;
 use crate::display_ext::DisplayOptionExt;
 use crate::engine::Command;
 use crate::engine::EngineOutput;
-use crate::raft_state::LogStateReader;
 use crate::storage::SnapshotMeta;
 use crate::RaftState;
 use crate::RaftTypeConfig;
@@ -20,11 +20,11 @@ fn eng() -> Engine<UTConfig> {
     let mut eng = Engine::testing_default(0);
     eng.state.enable_validation(false); // Disable validation for incomplete state
 
-    eng.state.snapshot_meta = SnapshotMeta {
-        last_log_id: Some(log_id(2, 1, 2)),
-        last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
-        snapshot_id: "1-2-3-4".to_string(),
-    };
+    eng.state.snapshot_meta = SnapshotMeta::new(
+        Some(log_id(2, 1, 2)),
+        StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+        "1-2-3-4".to_string(),
+    );
     eng
 }
 
@@ -33,20 +33,20 @@ fn test_update_snapshot_no_update() -> anyhow::Result<()> {
     // snapshot will not be updated because of equal or less `last_log_id`.
     let mut eng = eng();
 
-    let got = eng.snapshot_handler().update_snapshot(SnapshotMeta {
-        last_log_id: Some(log_id(2, 1, 2)),
-        last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-        snapshot_id: "1-2-3-4".to_string(),
-    });
+    let got = eng.snapshot_handler().update_snapshot(SnapshotMeta::new(
+        Some(log_id(2, 1, 2)),
+        StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+        "1-2-3-4".to_string(),
+    ));
 
     assert_eq!(false, got);
 
     assert_eq!(
-        SnapshotMeta {
-            last_log_id: Some(log_id(2, 1, 2)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        SnapshotMeta::new(
+            Some(log_id(2, 1, 2)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+            "1-2-3-4".to_string()
+        ),
         eng.state.snapshot_meta
     );
 
@@ -60,20 +60,20 @@ fn test_update_snapshot_updated() -> anyhow::Result<()> {
     // snapshot will be updated to a new one with greater `last_log_id`.
     let mut eng = eng();
 
-    let got = eng.snapshot_handler().update_snapshot(SnapshotMeta {
-        last_log_id: Some(log_id(2, 1, 3)),
-        last_membership: StoredMembership::new(Some(log_id(2, 1, 2)), m1234()),
-        snapshot_id: "1-2-3-4".to_string(),
-    });
+    let got = eng.snapshot_handler().update_snapshot(SnapshotMeta::new(
+        Some(log_id(2, 1, 3)),
+        StoredMembership::new(Some(log_id(2, 1, 2)), m1234()),
+        "1-2-3-4".to_string(),
+    ));
 
     assert_eq!(true, got);
 
     assert_eq!(
-        SnapshotMeta {
-            last_log_id: Some(log_id(2, 1, 3)),
-            last_membership: StoredMembership::new(Some(log_id(2, 1, 2)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        SnapshotMeta::new(
+            Some(log_id(2, 1, 3)),
+            StoredMembership::new(Some(log_id(2, 1, 2)), m1234()),
+            "1-2-3-4".to_string()
+        ),
         eng.state.snapshot_meta
     );
 
@@ -88,6 +88,38 @@ fn test_handle_message_vote_committed_vote() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_handle_message_vote_leader_quits() -> anyhow::Result<()> {
+    // A leader that sees a greater vote stops its replication streams.
+
+    let mut eng = eng();
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 0),
+    );
+    eng.testing_new_leader();
+    eng.state.server_state = ServerState::Leader;
+
+    let resp = eng.vote_handler().update_vote(&Vote::new_committed(3, 1));
+
+    assert_eq!(Ok(()), resp);
+
+    assert!(eng.leader.is_none());
+    assert_eq!(ServerState::Follower, eng.state.server_state);
+    assert_eq!(
+        vec![
+            Command::SaveVote {
+                vote: Vote::new_committed(3, 1)
+            },
+            Command::RebuildReplicationStreams { targets: vec![] },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_handle_message_vote_granted_equal_vote() -> anyhow::Result<()> {
     // Equal vote should not emit a SaveVote command.
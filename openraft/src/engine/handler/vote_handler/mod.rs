@@ -17,7 +17,6 @@ use crate::error::RejectVoteRequest;
 use crate::proposer::CandidateState;
 use crate::proposer::LeaderState;
 use crate::raft_state::IOId;
-use crate::raft_state::LogStateReader;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
 use crate::type_config::TypeConfigExt;
@@ -229,7 +228,11 @@ where C: RaftTypeConfig
             "It must hold: vote is not mine, or I am not a voter(leader just left the cluster)"
         );
 
-        *self.leader = None;
+        if self.leader.take().is_some() {
+            // Stop the replication streams of the quitting leader: a stream does not stop by itself
+            // and would keep reading logs that this node may purge or truncate as a follower.
+            self.output.push_command(Command::RebuildReplicationStreams { targets: vec![] });
+        }
         *self.candidate = None;
 
         self.server_state_handler().update_server_state_if_changed();
@@ -27,7 +27,13 @@
 //!  <-------: command to run
 //! ```
 
+#[cfg(feature = "bench")]
+#[cfg(test)]
+mod bench;
+
+mod check_request;
 mod command_kind;
+mod decision_trace;
 mod engine_config;
 mod engine_impl;
 mod engine_output;
@@ -42,16 +48,23 @@ pub(crate) mod time_state;
 #[cfg(test)]
 mod tests {
     mod append_entries_test;
+    mod custom_term_test;
     mod elect_test;
     mod handle_vote_req_test;
     mod handle_vote_resp_test;
     mod initialize_test;
     mod install_full_snapshot_test;
     mod log_id_list_test;
+    mod malformed_request_test;
+    mod random_rpc_test;
     mod startup_test;
+    mod string_node_id_test;
     mod trigger_purge_log_test;
+    mod verify_state_test;
 }
-#[cfg(test)]
+#[cfg(any(test, feature = "fuzzing"))]
+pub(crate) mod rpc_input;
+#[cfg(any(test, feature = "fuzzing"))]
 pub(crate) mod testing;
 
 pub(crate) use command::Command;
@@ -59,6 +72,8 @@ pub(crate) use command::Condition;
 pub(crate) use command::Respond;
 pub(crate) use command::ValueSender;
 pub(crate) use command_kind::CommandKind;
+pub(crate) use decision_trace::DecisionTrace;
+pub use decision_trace::EngineDecision;
 pub(crate) use engine_config::EngineConfig;
 pub(crate) use engine_impl::Engine;
 pub(crate) use engine_output::EngineOutput;
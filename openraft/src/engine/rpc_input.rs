@@ -0,0 +1,322 @@
+//! Feed AppendEntries, Vote and InstallSnapshot requests to a follower [`Engine`] and check that it
+//! does not panic and keeps the log consistent with a model of the cluster history.
+//!
+//! The model is a set of leaders, one per term, each of which has a log that extends a prefix of
+//! the log of a previous leader. Thus a log id identifies the whole log up to it, and the log of
+//! the engine must be a prefix of the log of the leader that proposed its last entry.
+//!
+//! Every choice, of the model and of the inputs, is made by a [`Source`]: a seeded random number
+//! generator in unit tests, or the bytes from a fuzzer, see [`crate::testing::fuzz`].
+
+use std::fmt;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use maplit::btreeset;
+
+use crate::engine::testing::log_id;
+use crate::engine::testing::UTConfig;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::raft::VoteRequest;
+use crate::raft_state::LogStateReader;
+use crate::storage::Snapshot;
+use crate::storage::SnapshotMeta;
+use crate::testing::blank_ent;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::TypeConfigExt;
+use crate::EffectiveMembership;
+use crate::Entry;
+use crate::Membership;
+use crate::StoredMembership;
+use crate::Vote;
+
+type LogId = LogIdOf<UTConfig>;
+
+/// Makes the choices to build a model and the inputs.
+pub(crate) trait Source {
+    /// Choose a value in `[0, n)`, `n` must be greater than 0.
+    fn below(&mut self, n: usize) -> usize;
+
+    /// Whether no more input should be generated.
+    fn is_exhausted(&self) -> bool {
+        false
+    }
+
+    /// Choose a value in `[lo, hi]`.
+    fn between(&mut self, lo: usize, hi: usize) -> usize {
+        lo + self.below(hi - lo + 1)
+    }
+}
+
+/// A [`Source`] that reads a choice from every byte of a byte string, such as the input of a
+/// fuzzer.
+///
+/// A choice is `0` once the bytes are used up, and no more input is generated.
+pub(crate) struct ByteSource<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ByteSource<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+}
+
+impl Source for ByteSource<'_> {
+    fn below(&mut self, n: usize) -> usize {
+        let Some((b, rest)) = self.data.split_first() else {
+            return 0;
+        };
+        self.data = rest;
+        *b as usize % n
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+fn m012() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {0,1,2}], [])
+}
+
+/// The log of every leader. The leader of term `t` is node `t % 2 + 1` and its log is `logs[t]`.
+///
+/// `logs[0]` is the initial log that contains only the membership entry.
+///
+/// The leader of term `t` commits its log up to `committed[t]`. To satisfy leader completeness, a
+/// leader log extends a previous leader log that contains the greatest committed log id so far.
+struct Model {
+    logs: Vec<Vec<LogId>>,
+    committed: Vec<usize>,
+}
+
+impl Model {
+    fn new(src: &mut impl Source, n_terms: u64) -> Self {
+        let mut logs = vec![vec![log_id(0, 0, 0)]];
+        let mut committed = vec![0];
+
+        for term in 1..=n_terms {
+            let prev_committed = *committed.last().unwrap();
+            let commit_log_id = logs.last().unwrap()[prev_committed];
+
+            let bases = logs.iter().filter(|l| l.get(prev_committed) == Some(&commit_log_id)).collect::<Vec<_>>();
+            let base = bases[src.below(bases.len())];
+            let mut log = base[..src.between(prev_committed + 1, base.len())].to_vec();
+
+            for _ in 0..src.between(0, 5) {
+                log.push(log_id(term, Self::leader(term), log.len() as u64));
+            }
+
+            committed.push(src.between(prev_committed, log.len() - 1));
+            logs.push(log);
+        }
+
+        Self { logs, committed }
+    }
+
+    fn leader(term: u64) -> u64 {
+        term % 2 + 1
+    }
+
+    fn vote(term: u64) -> Vote<UTConfig> {
+        Vote::new_committed(term, Self::leader(term))
+    }
+
+    /// Pick a term and an index of a log id in its log.
+    fn pick(&self, src: &mut impl Source) -> (u64, usize) {
+        let term = src.below(self.logs.len());
+        (term as u64, src.below(self.logs[term].len()))
+    }
+}
+
+#[derive(Debug)]
+enum Input {
+    AppendEntries {
+        vote: Vote<UTConfig>,
+        prev_log_id: Option<LogId>,
+        entries: Vec<LogId>,
+    },
+    Vote {
+        vote: Vote<UTConfig>,
+        last_log_id: Option<LogId>,
+    },
+    InstallSnapshot {
+        vote: Vote<UTConfig>,
+        last_log_id: LogId,
+    },
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::AppendEntries {
+                vote,
+                prev_log_id,
+                entries,
+            } => write!(
+                f,
+                "append_entries(vote: {}, prev: {:?}, entries: {:?})",
+                vote, prev_log_id, entries
+            ),
+            Input::Vote { vote, last_log_id } => write!(f, "vote(vote: {}, last: {:?})", vote, last_log_id),
+            Input::InstallSnapshot { vote, last_log_id } => {
+                write!(f, "install_snapshot(vote: {}, last: {})", vote, last_log_id)
+            }
+        }
+    }
+}
+
+fn gen_input(src: &mut impl Source, model: &Model) -> Input {
+    match src.below(10) {
+        0..=5 => {
+            let (term, start) = model.pick(src);
+            let log = &model.logs[term as usize];
+            let end = src.between(start, log.len());
+
+            // `start == 0` sends the log from the beginning, without a prev log id.
+            let prev_log_id = if start == 0 { None } else { Some(log[start - 1]) };
+
+            Input::AppendEntries {
+                vote: Model::vote(term.max(1)),
+                prev_log_id,
+                entries: log[start..end].to_vec(),
+            }
+        }
+        6..=8 => {
+            let term = src.between(1, model.logs.len() + 1) as u64;
+            let last_log_id = if src.below(10) == 0 {
+                None
+            } else {
+                let (t, i) = model.pick(src);
+                Some(model.logs[t as usize][i])
+            };
+
+            Input::Vote {
+                vote: Vote::new(term, Model::leader(term)),
+                last_log_id,
+            }
+        }
+        _ => {
+            // A snapshot contains only committed logs.
+            let term = src.below(model.logs.len());
+            let i = src.between(0, model.committed[term]);
+
+            Input::InstallSnapshot {
+                vote: Model::vote((term as u64).max(1)),
+                last_log_id: model.logs[term][i],
+            }
+        }
+    }
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+
+    eng.state.log_ids = LogIdList::new(vec![log_id(0, 0, 0)]);
+    eng.state.committed = Some(log_id(0, 0, 0));
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(0, 0, 0)), m012())));
+    eng.state.server_state = eng.calc_server_state();
+    eng.output.take_commands();
+
+    eng
+}
+
+fn feed(eng: &mut Engine<UTConfig>, input: &Input) {
+    match input {
+        Input::AppendEntries {
+            vote,
+            prev_log_id,
+            entries,
+        } => {
+            let entries = entries.iter().map(|x| blank_ent(x.leader_id.term, x.leader_id.node_id, x.index));
+            eng.handle_append_entries(vote, *prev_log_id, entries.collect::<Vec<Entry<UTConfig>>>(), None);
+        }
+        Input::Vote { vote, last_log_id } => {
+            eng.handle_vote_req(VoteRequest::new(*vote, *last_log_id));
+        }
+        Input::InstallSnapshot { vote, last_log_id } => {
+            let (tx, _rx) = UTConfig::<()>::oneshot();
+            eng.handle_install_full_snapshot(
+                *vote,
+                Snapshot {
+                    meta: SnapshotMeta::new(
+                        Some(*last_log_id),
+                        StoredMembership::new(Some(log_id(0, 0, 0)), m012()),
+                        format!("{}", last_log_id),
+                    ),
+                    snapshot: Cursor::new(vec![]),
+                },
+                tx,
+            );
+        }
+    }
+
+    eng.output.take_commands();
+}
+
+/// Check the invariants, `prev` is the (vote, committed) before the last input.
+fn check(eng: &Engine<UTConfig>, model: &Model, prev: &(Vote<UTConfig>, Option<LogId>)) -> Result<(), String> {
+    let vote = *eng.state.vote_ref();
+    if vote < prev.0 {
+        return Err(format!("vote reverted from {} to {}", prev.0, vote));
+    }
+
+    let committed = eng.state.committed().copied();
+    if committed < prev.1 {
+        return Err(format!("committed reverted from {:?} to {:?}", prev.1, committed));
+    }
+
+    let Some(last) = eng.state.last_log_id().copied() else {
+        return Err("log is empty".to_string());
+    };
+
+    // Log matching: every log id in the log is the one proposed by the leader of the last entry.
+    let leader_log = &model.logs[last.leader_id.term as usize];
+    let first = eng.state.last_purged_log_id().map_or(0, |x| x.index + 1);
+
+    for index in first..=last.index {
+        let got = eng.state.get_log_id(index);
+        let want = leader_log.get(index as usize).copied();
+        if got != want {
+            return Err(format!(
+                "log id at {}: {:?}, expected {:?} in the log of {}",
+                index, got, want, last
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Build a model and feed at most `n` inputs chosen by `src` to a follower engine.
+///
+/// It stops early if `src` is exhausted, and returns the error message with the inputs on failure.
+pub(crate) fn run(src: &mut impl Source, n: usize) -> Result<(), String> {
+    let n_terms = src.between(1, 6) as u64;
+    let model = Model::new(src, n_terms);
+
+    let mut eng = eng();
+    let mut inputs = vec![];
+
+    for _ in 0..n {
+        if src.is_exhausted() {
+            break;
+        }
+
+        let input = gen_input(src, &model);
+        inputs.push(input.to_string());
+
+        let prev = (*eng.state.vote_ref(), eng.state.committed().copied());
+        feed(&mut eng, &input);
+
+        if let Err(e) = check(&eng, &model, &prev) {
+            return Err(format!("{}, inputs:\n  {}", e, inputs.join("\n  ")));
+        }
+    }
+
+    Ok(())
+}
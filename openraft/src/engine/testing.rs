@@ -43,6 +43,7 @@ where N: Node + Ord
     type LeaderId = crate::impls::leader_id_adv::LeaderId<Self>;
     type Vote = crate::impls::Vote<Self>;
     type Entry = crate::impls::Entry<Self>;
+    type EntryCodec = crate::impls::PassThroughCodec<Self>;
     type SnapshotData = Cursor<Vec<u8>>;
     type AsyncRuntime = TokioRuntime;
     type Responder = crate::impls::OneshotResponder<Self>;
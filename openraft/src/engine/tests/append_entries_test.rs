@@ -13,7 +13,6 @@ use crate::engine::Engine;
 use crate::entry::RaftEntry;
 use crate::error::RejectAppendEntries;
 use crate::raft_state::IOId;
-use crate::raft_state::LogStateReader;
 use crate::testing::blank_ent;
 use crate::type_config::TypeConfigExt;
 use crate::utime::Leased;
@@ -275,7 +274,7 @@ fn test_append_entries_conflict() -> anyhow::Result<()> {
     // It is no longer a member, change to learner
     let mut eng = eng();
 
-    let resp = eng.append_entries(&Vote::new_committed(2, 1), Some(log_id(1, 1, 1)), vec![
+    let resp = eng.append_entries(&Vote::new_committed(3, 1), Some(log_id(1, 1, 1)), vec![
         blank_ent(1, 1, 2),
         Entry::new_membership(log_id(3, 1, 3), m34()),
     ]);
@@ -288,7 +287,7 @@ fn test_append_entries_conflict() -> anyhow::Result<()> {
         ],
         eng.state.log_ids.key_log_ids()
     );
-    assert_eq!(Vote::new_committed(2, 1), *eng.state.vote_ref());
+    assert_eq!(Vote::new_committed(3, 1), *eng.state.vote_ref());
     assert_eq!(Some(&log_id(3, 1, 3)), eng.state.last_log_id());
     assert_eq!(
         MembershipState::new(
@@ -301,11 +300,11 @@ fn test_append_entries_conflict() -> anyhow::Result<()> {
     assert_eq!(
         vec![
             Command::SaveVote {
-                vote: Vote::new_committed(2, 1)
+                vote: Vote::new_committed(3, 1)
             },
             Command::TruncateLog { since: log_id(2, 1, 3) },
             Command::AppendInputEntries {
-                committed_vote: Vote::new(2, 1).into_committed(),
+                committed_vote: Vote::new(3, 1).into_committed(),
                 entries: vec![Entry::new_membership(log_id(3, 1, 3), m34())]
             },
         ],
@@ -0,0 +1,135 @@
+//! Test the Engine with a `Term` other than `u64`.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::core::ServerState;
+use crate::declare_raft_types;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::raft::VoteRequest;
+use crate::type_config::alias::LeaderIdOf;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::vote::RaftLeaderIdExt;
+use crate::vote::RaftTerm;
+use crate::BasicNode;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// A term that embeds the epoch of the cluster, a term in a newer epoch is always greater.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+struct EpochTerm {
+    epoch: u32,
+    term: u32,
+}
+
+impl fmt::Display for EpochTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.epoch, self.term)
+    }
+}
+
+impl RaftTerm for EpochTerm {
+    fn next(&self) -> Self {
+        Self {
+            epoch: self.epoch,
+            term: self.term + 1,
+        }
+    }
+}
+
+fn et(epoch: u32, term: u32) -> EpochTerm {
+    EpochTerm { epoch, term }
+}
+
+declare_raft_types!(
+    EpochConfig:
+        D = (),
+        R = (),
+        Term = EpochTerm,
+        LeaderId = crate::impls::leader_id_std::LeaderId<Self>,
+);
+
+declare_raft_types!(
+    U32Config:
+        D = (),
+        R = (),
+        Term = u32,
+);
+
+fn log_id<C: RaftTypeConfig>(term: C::Term, node_id: C::NodeId, index: u64) -> LogIdOf<C> {
+    LogIdOf::<C>::new(LeaderIdOf::<C>::new_committed(term, node_id), index)
+}
+
+fn eng<C>(term: C::Term) -> Engine<C>
+where C: RaftTypeConfig<NodeId = u64, Node = BasicNode> {
+    let mut eng = Engine::testing_default(1);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.state.log_ids = LogIdList::new([log_id::<C>(term, 1, 1)]);
+    eng.state.membership_state.set_effective(Arc::new(EffectiveMembership::new(
+        Some(log_id::<C>(term, 1, 1)),
+        Membership::new_with_defaults(vec![btreeset! {1}], []),
+    )));
+    eng
+}
+
+#[test]
+fn test_epoch_term_elect() -> anyhow::Result<()> {
+    let mut eng = eng::<EpochConfig>(et(3, 5));
+    eng.state.vote = Leased::new(
+        EpochConfig::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(et(3, 5), 1),
+    );
+
+    eng.elect();
+
+    assert_eq!(Vote::new(et(3, 6), 1), *eng.state.vote_ref());
+    assert_eq!(ServerState::Candidate, eng.state.server_state);
+    assert_eq!(
+        vec![
+            //
+            Command::SaveVote {
+                vote: Vote::new(et(3, 6), 1)
+            },
+            Command::SendVote {
+                vote_req: VoteRequest {
+                    vote: Vote::new(et(3, 6), 1),
+                    last_log_id: Some(log_id::<EpochConfig>(et(3, 5), 1, 1)),
+                },
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    assert!(
+        Vote::<EpochConfig>::new(et(4, 0), 2) > Vote::new(et(3, 6), 1),
+        "a vote in a newer epoch is greater"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_u32_term_elect() -> anyhow::Result<()> {
+    let mut eng = eng::<U32Config>(5);
+    eng.state.vote = Leased::new(U32Config::now(), Duration::from_millis(500), Vote::new_committed(5, 1));
+
+    eng.elect();
+
+    assert_eq!(Vote::new(6_u32, 1), *eng.state.vote_ref());
+    assert_eq!(ServerState::Candidate, eng.state.server_state);
+
+    Ok(())
+}
@@ -94,6 +94,8 @@ fn test_elect_single_node_elect_again() -> anyhow::Result<()> {
         assert_eq!(Vote::new(2, 1), *eng.candidate_ref().unwrap().vote_ref());
 
         assert!(eng.candidate_mut().is_some(), "candidate state is pending");
+        assert_eq!(1, eng.election_metrics.elections_started);
+        assert_eq!(0, eng.election_metrics.elections_won);
 
         assert_eq!(ServerState::Candidate, eng.state.server_state);
 
@@ -61,6 +61,7 @@ fn test_handle_vote_req_rejected_by_leader_lease() -> anyhow::Result<()> {
 
     assert_eq!(ServerState::Candidate, eng.state.server_state);
     assert_eq!(0, eng.output.take_commands().len());
+    assert_eq!(1, eng.election_metrics.vote_requests_rejected_by_lease);
 
     Ok(())
 }
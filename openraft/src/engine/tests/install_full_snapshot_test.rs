@@ -37,7 +37,7 @@ fn eng() -> Engine<UTConfig> {
     eng.state.vote.update(
         UTConfig::<()>::now(),
         Duration::from_millis(500),
-        Vote::new_committed(2, 1),
+        Vote::new_committed(4, 1),
     );
     eng.state.committed = Some(log_id(4, 1, 5));
     eng.state.log_ids = LogIdList::new(vec![
@@ -47,11 +47,11 @@ fn eng() -> Engine<UTConfig> {
         log_id(4, 1, 6),
         log_id(4, 1, 8),
     ]);
-    eng.state.snapshot_meta = SnapshotMeta {
-        last_log_id: Some(log_id(2, 1, 2)),
-        last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
-        snapshot_id: "1-2-3-4".to_string(),
-    };
+    eng.state.snapshot_meta = SnapshotMeta::new(
+        Some(log_id(2, 1, 2)),
+        StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+        "1-2-3-4".to_string(),
+    );
     eng.state.server_state = eng.calc_server_state();
 
     eng
@@ -59,7 +59,7 @@ fn eng() -> Engine<UTConfig> {
 
 #[test]
 fn test_handle_install_full_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
-    // Snapshot will not be installed because new `last_log_id` is less or equal current
+    // Snapshot will not be installed because new `last_log_id` is less than current
     // `snapshot_meta.last_log_id`.
     //
     // It should respond at once.
@@ -73,22 +73,72 @@ fn test_handle_install_full_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
     eng.handle_install_full_snapshot(
         curr_vote,
         Snapshot {
-            meta: SnapshotMeta {
-                last_log_id: Some(log_id(1, 1, 2)),
-                last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-                snapshot_id: "1-2-3-4".to_string(),
-            },
+            meta: SnapshotMeta::new(
+                Some(log_id(1, 1, 1)),
+                StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                "1-2-3-4".to_string(),
+            ),
             snapshot: Cursor::new(vec![0u8]),
         },
         tx,
     );
 
     assert_eq!(
-        SnapshotMeta {
-            last_log_id: Some(log_id(2, 1, 2)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
-            snapshot_id: "1-2-3-4".to_string(),
+        SnapshotMeta::new(
+            Some(log_id(2, 1, 2)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+            "1-2-3-4".to_string()
+        ),
+        eng.state.snapshot_meta
+    );
+
+    let (dummy_tx, _rx) = UTConfig::<()>::oneshot();
+    assert_eq!(
+        vec![
+            //
+            Command::Respond {
+                when: None,
+                resp: Respond::new(Ok(SnapshotResponse::new(curr_vote)), dummy_tx),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_handle_install_full_snapshot_eq_last_snapshot() -> anyhow::Result<()> {
+    // Snapshot will not be installed because new `last_log_id` is equal to current
+    // `snapshot_meta.last_log_id`.
+    //
+    // It should respond at once.
+
+    let mut eng = eng();
+
+    let curr_vote = *eng.state.vote_ref();
+
+    let (tx, _rx) = UTConfig::<()>::oneshot();
+
+    eng.handle_install_full_snapshot(
+        curr_vote,
+        Snapshot {
+            meta: SnapshotMeta::new(
+                Some(log_id(2, 1, 2)),
+                StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                "1-2-3-4".to_string(),
+            ),
+            snapshot: Cursor::new(vec![0u8]),
         },
+        tx,
+    );
+
+    assert_eq!(
+        SnapshotMeta::new(
+            Some(log_id(2, 1, 2)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
+            "1-2-3-4".to_string()
+        ),
         eng.state.snapshot_meta
     );
 
@@ -121,22 +171,22 @@ fn test_handle_install_full_snapshot_no_conflict() -> anyhow::Result<()> {
     eng.handle_install_full_snapshot(
         curr_vote,
         Snapshot {
-            meta: SnapshotMeta {
-                last_log_id: Some(log_id(4, 1, 6)),
-                last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-                snapshot_id: "1-2-3-4".to_string(),
-            },
+            meta: SnapshotMeta::new(
+                Some(log_id(4, 1, 6)),
+                StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                "1-2-3-4".to_string(),
+            ),
             snapshot: Cursor::new(vec![0u8]),
         },
         tx,
     );
 
     assert_eq!(
-        SnapshotMeta {
-            last_log_id: Some(log_id(4, 1, 6)),
-            last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-            snapshot_id: "1-2-3-4".to_string(),
-        },
+        SnapshotMeta::new(
+            Some(log_id(4, 1, 6)),
+            StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+            "1-2-3-4".to_string()
+        ),
         eng.state.snapshot_meta
     );
 
@@ -146,14 +196,14 @@ fn test_handle_install_full_snapshot_no_conflict() -> anyhow::Result<()> {
             //
             Command::from(sm::Command::install_full_snapshot(
                 Snapshot {
-                    meta: SnapshotMeta {
-                        last_log_id: Some(log_id(4, 1, 6)),
-                        last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
-                        snapshot_id: "1-2-3-4".to_string(),
-                    },
+                    meta: SnapshotMeta::new(
+                        Some(log_id(4, 1, 6)),
+                        StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
+                        "1-2-3-4".to_string()
+                    ),
                     snapshot: Cursor::new(vec![0u8]),
                 },
-                IOId::new_log_io(Vote::new(2, 1).into_committed(), Some(log_id(4, 1, 6)))
+                IOId::new_log_io(Vote::new(4, 1).into_committed(), Some(log_id(4, 1, 6)))
             )),
             Command::PurgeLog { upto: log_id(4, 1, 6) },
             Command::Respond {
@@ -0,0 +1,406 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::core::ServerState;
+use crate::engine::check_request;
+use crate::engine::testing::log_id;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Condition;
+use crate::engine::Engine;
+use crate::engine::Respond;
+use crate::error::MalformedRequest;
+use crate::error::RejectAppendEntries;
+use crate::raft::AppendEntriesResponse;
+use crate::raft::SnapshotResponse;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::raft_state::IOId;
+use crate::raft_state::LogStateReader;
+use crate::storage::Snapshot;
+use crate::storage::SnapshotMeta;
+use crate::testing::blank_ent;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::EffectiveMembership;
+use crate::Entry;
+use crate::Membership;
+use crate::MembershipState;
+use crate::StoredMembership;
+use crate::Vote;
+
+fn m012() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {0,1,2}], [])
+}
+
+/// A follower with logs `1-1, 1-2, 2-3`, in which `1-2` is committed.
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 2;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(0),
+        Vote::new_committed(2, 1),
+    );
+    eng.state.log_ids.append(log_id(1, 1, 1));
+    eng.state.log_ids.append(log_id(1, 1, 2));
+    eng.state.log_ids.append(log_id(2, 1, 3));
+    eng.state.committed = Some(log_id(1, 1, 2));
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m012())),
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m012())),
+    );
+    eng.state.server_state = eng.calc_server_state();
+    eng
+}
+
+/// Assert that the state is unchanged and no command is emitted.
+fn assert_unchanged(eng: &mut Engine<UTConfig>) {
+    assert_eq!(Vote::new_committed(2, 1), *eng.state.vote_ref());
+    assert_eq!(
+        &[log_id(1, 1, 1), log_id(2, 1, 3)],
+        eng.state.log_ids.key_log_ids(),
+        "logs are unchanged"
+    );
+    assert_eq!(Some(&log_id(1, 1, 2)), eng.state.committed());
+    assert_eq!(ServerState::Follower, eng.state.server_state);
+    assert_eq!(0, eng.output.take_commands().len());
+}
+
+#[test]
+fn test_append_entries_log_id_after_vote() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    // An entry proposed by a leader of term 3, sent with a vote of term 2.
+    let res = eng.append_entries(&Vote::new_committed(2, 1), Some(log_id(2, 1, 3)), vec![blank_ent(
+        3, 1, 4,
+    )]);
+    assert_eq!(
+        Err(RejectAppendEntries::Malformed(MalformedRequest::LogIdAfterVote {
+            log_id: log_id(3, 1, 4),
+            vote: Vote::new_committed(2, 1),
+        })),
+        res
+    );
+    assert_unchanged(&mut eng);
+
+    assert_eq!(
+        AppendEntriesResponse::<UTConfig>::Malformed(MalformedRequest::LogIdAfterVote {
+            log_id: log_id(3, 1, 4),
+            vote: Vote::new_committed(2, 1),
+        }),
+        res.into()
+    );
+
+    // A malformed prev_log_id is rejected before the vote is updated.
+    let res = eng.append_entries(&Vote::new_committed(3, 1), Some(log_id(4, 1, 3)), vec![]);
+    assert_eq!(
+        Err(RejectAppendEntries::Malformed(MalformedRequest::LogIdAfterVote {
+            log_id: log_id(4, 1, 3),
+            vote: Vote::new_committed(3, 1),
+        })),
+        res
+    );
+    assert_unchanged(&mut eng);
+
+    assert_eq!(
+        AppendEntriesResponse::<UTConfig>::Malformed(MalformedRequest::LogIdAfterVote {
+            log_id: log_id(4, 1, 3),
+            vote: Vote::new_committed(3, 1),
+        }),
+        res.into()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_append_entries_non_consecutive_log_ids() -> anyhow::Result<()> {
+    let mut eng = eng();
+    let vote = Vote::new_committed(2, 1);
+
+    let cases: Vec<(Option<_>, Vec<Entry<UTConfig>>, Option<_>, _)> = vec![
+        // The first entry does not follow prev_log_id.
+        (
+            Some(log_id(2, 1, 3)),
+            vec![blank_ent(2, 1, 5)],
+            Some(log_id(2, 1, 3)),
+            log_id(2, 1, 5),
+        ),
+        // A gap.
+        (
+            Some(log_id(2, 1, 3)),
+            vec![blank_ent(2, 1, 4), blank_ent(2, 1, 6)],
+            Some(log_id(2, 1, 4)),
+            log_id(2, 1, 6),
+        ),
+        // Overlapping entries.
+        (
+            Some(log_id(2, 1, 3)),
+            vec![blank_ent(2, 1, 4), blank_ent(2, 1, 5), blank_ent(2, 1, 5)],
+            Some(log_id(2, 1, 5)),
+            log_id(2, 1, 5),
+        ),
+        // The term decreases.
+        (
+            Some(log_id(1, 1, 2)),
+            vec![blank_ent(2, 1, 3), blank_ent(1, 1, 4)],
+            Some(log_id(2, 1, 3)),
+            log_id(1, 1, 4),
+        ),
+        (None, vec![blank_ent(1, 1, 1)], None, log_id(1, 1, 1)),
+    ];
+
+    for (prev_log_id, entries, want_prev, want_next) in cases {
+        let res = eng.append_entries(&vote, prev_log_id, entries);
+        assert_eq!(
+            Err(RejectAppendEntries::Malformed(MalformedRequest::NonConsecutiveLogIds {
+                prev: want_prev,
+                next: want_next,
+            })),
+            res
+        );
+        assert_unchanged(&mut eng);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_append_entries_conflict_with_committed() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    // Replacing the committed `1-2` would truncate a committed log.
+    let res = eng.append_entries(&Vote::new_committed(2, 1), Some(log_id(1, 1, 1)), vec![
+        blank_ent(2, 1, 2),
+        blank_ent(2, 1, 3),
+    ]);
+    assert_eq!(
+        Err(RejectAppendEntries::Malformed(
+            MalformedRequest::ConflictWithCommitted {
+                log_id: log_id(2, 1, 2),
+                committed: log_id(1, 1, 2),
+            }
+        )),
+        res
+    );
+    assert_unchanged(&mut eng);
+
+    // A prev_log_id that conflicts with the committed one.
+    let res = eng.append_entries(&Vote::new_committed(2, 1), Some(log_id(2, 1, 2)), vec![]);
+    assert_eq!(
+        Err(RejectAppendEntries::Malformed(
+            MalformedRequest::ConflictWithCommitted {
+                log_id: log_id(2, 1, 2),
+                committed: log_id(1, 1, 2),
+            }
+        )),
+        res
+    );
+    assert_unchanged(&mut eng);
+
+    Ok(())
+}
+
+#[test]
+fn test_append_entries_stale_vote_conflict_with_committed() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    // A stale leader whose prev_log_id conflicts with the committed `1-2` is told about the
+    // greater vote, not rejected as malformed.
+    let res = eng.append_entries(&Vote::new_committed(1, 2), Some(log_id(1, 2, 2)), vec![blank_ent(
+        1, 2, 3,
+    )]);
+    assert_eq!(Err(RejectAppendEntries::ByVote(Vote::new_committed(2, 1))), res);
+    assert_unchanged(&mut eng);
+
+    assert_eq!(
+        AppendEntriesResponse::<UTConfig>::HigherVote(Vote::new_committed(2, 1)),
+        res.into()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_leader_committed_not_accepted() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let res = eng.append_entries(&Vote::new_committed(2, 1), Some(log_id(2, 1, 3)), vec![]);
+    assert_eq!(Ok(()), res);
+    eng.output.take_commands();
+
+    // The leader claims `1-3` is committed, while the accepted log at index 3 is `2-3`.
+    assert_eq!(
+        Err(MalformedRequest::LeaderCommittedNotAccepted {
+            leader_committed: log_id(1, 1, 3),
+            local: log_id(2, 1, 3),
+        }),
+        check_request::check_leader_committed(&eng.state, Some(&log_id(1, 1, 3)))
+    );
+
+    eng.handle_commit_entries(Some(log_id(1, 1, 3)));
+    assert_unchanged(&mut eng);
+
+    // A committed log id from a greater leader than the vote.
+    assert_eq!(
+        Err(MalformedRequest::LogIdAfterVote {
+            log_id: log_id(3, 1, 3),
+            vote: Vote::new_committed(2, 1),
+        }),
+        check_request::check_leader_committed(&eng.state, Some(&log_id(3, 1, 3)))
+    );
+
+    eng.handle_commit_entries(Some(log_id(3, 1, 3)));
+    assert_unchanged(&mut eng);
+
+    Ok(())
+}
+
+#[test]
+fn test_vote_request_last_log_id_after_vote() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let req = VoteRequest::new(Vote::new(3, 2), Some(log_id(4, 2, 5)));
+    assert_eq!(
+        Err(MalformedRequest::LogIdAfterVote {
+            log_id: log_id(4, 2, 5),
+            vote: Vote::new(3, 2),
+        }),
+        check_request::check_vote_request(&req)
+    );
+
+    let resp = eng.handle_vote_req(req);
+    assert_eq!(
+        VoteResponse::new(Vote::new_committed(2, 1), Some(log_id(2, 1, 3)), false),
+        resp
+    );
+    assert_unchanged(&mut eng);
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_membership_after_last_log_id() -> anyhow::Result<()> {
+    let mut eng = eng();
+    let vote = Vote::new_committed(3, 1);
+
+    let meta = SnapshotMeta::new(
+        Some(log_id(3, 1, 5)),
+        StoredMembership::new(Some(log_id(3, 1, 6)), m012()),
+        "1-2-3-4".to_string(),
+    );
+    assert_eq!(
+        Err(MalformedRequest::SnapshotMembershipAfterLastLogId {
+            membership: Some(log_id(3, 1, 6)),
+            last_log_id: Some(log_id(3, 1, 5)),
+        }),
+        check_request::check_snapshot_meta(&vote, &meta)
+    );
+
+    let (tx, _rx) = UTConfig::<()>::oneshot();
+    eng.handle_install_full_snapshot(
+        vote,
+        Snapshot {
+            meta,
+            snapshot: Cursor::new(vec![0u8]),
+        },
+        tx,
+    );
+
+    let (dummy_tx, _rx) = UTConfig::<()>::oneshot();
+    assert_eq!(
+        vec![Command::Respond {
+            when: None,
+            resp: Respond::new(
+                Ok(SnapshotResponse::new_malformed(
+                    Vote::new_committed(2, 1),
+                    MalformedRequest::SnapshotMembershipAfterLastLogId {
+                        membership: Some(log_id(3, 1, 6)),
+                        last_log_id: Some(log_id(3, 1, 5)),
+                    }
+                )),
+                dummy_tx
+            ),
+        }],
+        eng.output.take_commands()
+    );
+    assert_unchanged(&mut eng);
+    assert_eq!(SnapshotMeta::default(), eng.state.snapshot_meta);
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_conflict_with_committed() -> anyhow::Result<()> {
+    let eng = eng();
+
+    // The snapshot claims index 2 is proposed by leader 3, while the committed one is `1-2`.
+    let meta = SnapshotMeta::new(
+        Some(log_id(3, 1, 2)),
+        StoredMembership::new(Some(log_id(1, 1, 1)), m012()),
+        "1-2-3-4".to_string(),
+    );
+    assert_eq!(
+        Err(MalformedRequest::ConflictWithCommitted {
+            log_id: log_id(3, 1, 2),
+            committed: log_id(1, 1, 2),
+        }),
+        check_request::check_snapshot_meta_committed(&eng.state, &meta)
+    );
+
+    // A snapshot sent with a smaller vote than its last log id.
+    let meta = SnapshotMeta::new(Some(log_id(3, 1, 5)), meta.last_membership, meta.snapshot_id);
+    assert_eq!(
+        Err(MalformedRequest::LogIdAfterVote {
+            log_id: log_id(3, 1, 5),
+            vote: Vote::new_committed(2, 1),
+        }),
+        check_request::check_snapshot_meta(&Vote::new_committed(2, 1), &meta)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_stale_vote_conflict_with_committed() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    // A snapshot from a stale leader, conflicting with the committed `1-2`.
+    let meta = SnapshotMeta::new(
+        Some(log_id(1, 2, 2)),
+        StoredMembership::new(Some(log_id(1, 1, 1)), m012()),
+        "1-2-3-4".to_string(),
+    );
+
+    let (tx, _rx) = UTConfig::<()>::oneshot();
+    eng.handle_install_full_snapshot(
+        Vote::new_committed(1, 2),
+        Snapshot {
+            meta,
+            snapshot: Cursor::new(vec![0u8]),
+        },
+        tx,
+    );
+
+    let (dummy_tx, _rx) = UTConfig::<()>::oneshot();
+    assert_eq!(
+        vec![Command::Respond {
+            when: Some(Condition::IOFlushed {
+                io_id: IOId::new(&Vote::new_committed(2, 1)),
+            }),
+            resp: Respond::new(Ok(SnapshotResponse::new(Vote::new_committed(2, 1))), dummy_tx),
+        }],
+        eng.output.take_commands()
+    );
+    assert_unchanged(&mut eng);
+    assert_eq!(SnapshotMeta::default(), eng.state.snapshot_meta);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Feed random AppendEntries, Vote and InstallSnapshot requests to a follower [`Engine`], see
+//! [`rpc_input`](crate::engine::rpc_input).
+//!
+//! The inputs are generated from a seed. A failure reports the seed and the inputs, and can be
+//! replayed with `OPENRAFT_RPC_SEED=<seed>`.
+//!
+//! [`Engine`]: crate::engine::Engine
+
+use std::env;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+use crate::engine::rpc_input;
+use crate::engine::rpc_input::ByteSource;
+use crate::engine::rpc_input::Source;
+
+struct RngSource(StdRng);
+
+impl Source for RngSource {
+    fn below(&mut self, n: usize) -> usize {
+        self.0.gen_range(0..n)
+    }
+}
+
+#[test]
+fn test_random_rpc_input() -> anyhow::Result<()> {
+    let seeds = match env::var("OPENRAFT_RPC_SEED") {
+        Ok(s) => vec![s.parse::<u64>()?],
+        Err(_) => (0..1_000).collect(),
+    };
+
+    for seed in seeds {
+        let mut src = RngSource(StdRng::seed_from_u64(seed));
+        if let Err(e) = rpc_input::run(&mut src, 30) {
+            panic!("seed: {}: {}", seed, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// The bytes of a fuzzer input are used up choice by choice, and an input of any length is valid.
+#[test]
+fn test_rpc_input_from_bytes() -> anyhow::Result<()> {
+    let data = (0..=255u8).rev().collect::<Vec<_>>();
+
+    for len in 0..data.len() {
+        if let Err(e) = rpc_input::run(&mut ByteSource::new(&data[..len]), usize::MAX) {
+            panic!("len: {}: {}", len, e);
+        }
+    }
+
+    Ok(())
+}
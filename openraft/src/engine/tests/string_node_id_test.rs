@@ -0,0 +1,109 @@
+//! Test the Engine with a non-`Copy` node id.
+
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::core::ServerState;
+use crate::declare_raft_types;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::engine::ReplicationProgress;
+use crate::entry::RaftEntry;
+use crate::impls::TokioRuntime;
+use crate::log_id_range::LogIdRange;
+use crate::progress::entry::ProgressEntry;
+use crate::raft::VoteResponse;
+use crate::replication::request::Replicate;
+use crate::type_config::alias::LeaderIdOf;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::vote::raft_vote::RaftVoteExt;
+use crate::vote::RaftLeaderIdExt;
+use crate::EffectiveMembership;
+use crate::Entry;
+use crate::Membership;
+use crate::Vote;
+
+declare_raft_types!(
+    StrConfig:
+        D = (),
+        R = (),
+        NodeId = String,
+        Node = (),
+        LeaderId = crate::impls::leader_id_adv::LeaderId<Self>,
+        Entry = Entry<Self>,
+        SnapshotData = Cursor<Vec<u8>>,
+        AsyncRuntime = TokioRuntime,
+);
+
+fn id(s: &str) -> String {
+    s.to_string()
+}
+
+fn log_id(term: u64, node_id: &str, index: u64) -> LogIdOf<StrConfig> {
+    LogIdOf::<StrConfig>::new(LeaderIdOf::<StrConfig>::new_committed(term, id(node_id)), index)
+}
+
+fn m_ab() -> Membership<StrConfig> {
+    Membership::<StrConfig>::new_with_defaults(vec![btreeset! {id("a"), id("b")}], [])
+}
+
+fn eng() -> Engine<StrConfig> {
+    let mut eng = Engine::testing_default(id("a"));
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.state.log_ids = LogIdList::new([log_id(0, "a", 0)]);
+    eng
+}
+
+#[test]
+fn test_string_node_id_become_leader() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.vote = Leased::new(StrConfig::now(), Duration::from_millis(500), Vote::new(2, id("a")));
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, "a", 1)), m_ab())));
+
+    let voting = eng.new_candidate(eng.state.vote_ref().clone());
+    voting.grant_by(&id("a"));
+
+    eng.state.server_state = ServerState::Candidate;
+
+    eng.handle_vote_resp(
+        id("b"),
+        VoteResponse::new(Vote::new(2, id("a")), Some(log_id(1, "a", 1)), true),
+    );
+
+    assert_eq!(Vote::new_committed(2, id("a")), *eng.state.vote_ref());
+    assert_eq!(ServerState::Leader, eng.state.server_state);
+
+    assert_eq!(Some(log_id(2, "a", 1)), eng.leader.as_ref().unwrap().noop_log_id);
+
+    assert_eq!(
+        vec![
+            Command::RebuildReplicationStreams {
+                targets: vec![ReplicationProgress(id("b"), ProgressEntry::empty(1))]
+            },
+            Command::SaveVote {
+                vote: Vote::new_committed(2, id("a"))
+            },
+            Command::AppendInputEntries {
+                committed_vote: Vote::new(2, id("a")).into_committed(),
+                entries: vec![Entry::<StrConfig>::new_blank(log_id(2, "a", 1))],
+            },
+            Command::Replicate {
+                target: id("b"),
+                req: Replicate::logs(LogIdRange::new(None, Some(log_id(2, "a", 1))))
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
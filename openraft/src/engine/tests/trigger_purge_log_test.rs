@@ -39,7 +39,9 @@ fn eng() -> Engine<UTConfig> {
 fn test_trigger_purge_log_no_snapshot() -> anyhow::Result<()> {
     let mut eng = eng();
 
-    eng.trigger_purge_log(1);
+    let scheduled = eng.trigger_purge_log(1);
+
+    assert_eq!(None, scheduled);
 
     assert_eq!(None, eng.state.purge_upto, "no snapshot, can not purge");
 
@@ -51,15 +53,17 @@ fn test_trigger_purge_log_no_snapshot() -> anyhow::Result<()> {
 #[test]
 fn test_trigger_purge_log_already_scheduled() -> anyhow::Result<()> {
     let mut eng = eng();
-    eng.state.snapshot_meta = SnapshotMeta {
-        last_log_id: Some(log_id(1, 0, 3)),
-        last_membership: StoredMembership::new(Some(log_id(1, 0, 1)), m12()),
-        snapshot_id: "1".to_string(),
-    };
+    eng.state.snapshot_meta = SnapshotMeta::new(
+        Some(log_id(1, 0, 3)),
+        StoredMembership::new(Some(log_id(1, 0, 1)), m12()),
+        "1".to_string(),
+    );
     eng.state.purge_upto = Some(log_id(1, 0, 2));
     eng.state.io_state.purged = Some(log_id(1, 0, 2));
 
-    eng.trigger_purge_log(2);
+    let scheduled = eng.trigger_purge_log(2);
+
+    assert_eq!(Some(log_id(1, 0, 2)), scheduled);
 
     assert_eq!(Some(log_id(1, 0, 2)), eng.state.purge_upto, "already purged, no update");
 
@@ -71,16 +75,18 @@ fn test_trigger_purge_log_already_scheduled() -> anyhow::Result<()> {
 #[test]
 fn test_trigger_purge_log_delete_only_in_snapshot_logs() -> anyhow::Result<()> {
     let mut eng = eng();
-    eng.state.snapshot_meta = SnapshotMeta {
-        last_log_id: Some(log_id(1, 0, 3)),
-        last_membership: StoredMembership::new(Some(log_id(1, 0, 1)), m12()),
-        snapshot_id: "1".to_string(),
-    };
+    eng.state.snapshot_meta = SnapshotMeta::new(
+        Some(log_id(1, 0, 3)),
+        StoredMembership::new(Some(log_id(1, 0, 1)), m12()),
+        "1".to_string(),
+    );
     eng.state.purge_upto = Some(log_id(1, 0, 2));
     eng.state.io_state.purged = Some(log_id(1, 0, 2));
     eng.state.log_ids = LogIdList::new([log_id(1, 0, 2), log_id(1, 0, 10)]);
 
-    eng.trigger_purge_log(5);
+    let scheduled = eng.trigger_purge_log(5);
+
+    assert_eq!(Some(log_id(1, 0, 3)), scheduled, "scheduled upto the snapshot");
 
     assert_eq!(
         Some(log_id(1, 0, 3)),
@@ -99,11 +105,11 @@ fn test_trigger_purge_log_delete_only_in_snapshot_logs() -> anyhow::Result<()> {
 #[test]
 fn test_trigger_purge_log_in_used_wont_be_delete() -> anyhow::Result<()> {
     let mut eng = eng();
-    eng.state.snapshot_meta = SnapshotMeta {
-        last_log_id: Some(log_id(1, 0, 3)),
-        last_membership: StoredMembership::new(Some(log_id(1, 0, 1)), m12()),
-        snapshot_id: "1".to_string(),
-    };
+    eng.state.snapshot_meta = SnapshotMeta::new(
+        Some(log_id(1, 0, 3)),
+        StoredMembership::new(Some(log_id(1, 0, 1)), m12()),
+        "1".to_string(),
+    );
     eng.state.purge_upto = Some(log_id(1, 0, 2));
     eng.state.io_state.purged = Some(log_id(1, 0, 2));
     eng.state.log_ids = LogIdList::new([log_id(1, 0, 2), log_id(1, 0, 10)]);
@@ -118,7 +124,9 @@ fn test_trigger_purge_log_in_used_wont_be_delete() -> anyhow::Result<()> {
     let l = eng.leader.as_mut().unwrap();
     let _ = l.progress.get_mut(&2).unwrap().next_send(eng.state.deref(), 10).unwrap();
 
-    eng.trigger_purge_log(5);
+    let scheduled = eng.trigger_purge_log(5);
+
+    assert_eq!(Some(log_id(1, 0, 3)), scheduled, "scheduled upto the snapshot");
 
     assert_eq!(
         Some(log_id(1, 0, 3)),
@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::engine::testing::log_id;
+use crate::engine::testing::UTConfig;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::progress::Progress;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+
+fn m01() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {0,1}], [])
+}
+
+/// A consistent state of a leader: logs `[1, 4]` with membership at 1 and 4 committed upto 3.
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 0),
+    );
+    eng.state.log_ids = LogIdList::new([log_id(0, 0, 0), log_id(1, 0, 1), log_id(2, 0, 3), log_id(2, 0, 4)]);
+    eng.state.membership_state = MembershipState::new(
+        EffectiveMembership::new_arc(Some(log_id(1, 0, 1)), m01()),
+        EffectiveMembership::new_arc(Some(log_id(2, 0, 4)), m01()),
+    );
+    eng.state.committed = Some(log_id(2, 0, 3));
+    eng.state.io_state_mut().update_applied(Some(log_id(2, 0, 3)));
+    eng.state.io_state_mut().update_snapshot(Some(log_id(1, 0, 2)));
+    eng
+}
+
+#[test]
+fn test_verify_state_ok() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.testing_new_leader();
+
+    let report = eng.verify_state();
+    assert!(report.is_ok(), "{}", report);
+
+    let names = report.checks.iter().map(|c| c.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "log ids start at purged",
+            "purged <= committed",
+            "snapshot <= applied",
+            "applied <= committed",
+            "committed <= last_log",
+            "last_log leader <= vote",
+            "committed membership <= effective membership",
+            "effective membership <= last_log",
+            "progress of 0 <= last_log",
+            "progress of 0 is valid",
+            "progress of 1 <= last_log",
+            "progress of 1 is valid",
+        ],
+        names
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_state_violations() -> anyhow::Result<()> {
+    let mut eng = eng();
+    let leader = eng.testing_new_leader();
+    leader.progress.get_mut(&1).unwrap().matching = Some(log_id(2, 0, 5));
+
+    eng.state.committed = Some(log_id(2, 0, 5));
+    eng.state.vote = Leased::new(UTConfig::<()>::now(), Duration::from_millis(500), Vote::new(1, 0));
+
+    let report = eng.verify_state();
+    assert!(!report.is_ok());
+
+    let violations = report.violations().map(|c| c.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "committed <= last_log: T2-N0.5 > T2-N0.4",
+            "last_log leader <= vote: T2-N0 > T1-N0",
+            "progress of 1 <= last_log: T2-N0.5 > T2-N0.4",
+            "progress of 1 is valid: {[T2-N0.5, 5), inflight:None}: expect: self.matching().next_index()(6) <= self.searching_end(5)",
+        ],
+        violations
+    );
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+//! Convert log entries to and from the form that is sent over the network.
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use openraft_macros::since;
+
+use crate::base::OptionalFeatures;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+
+/// Converts a log entry [`RaftTypeConfig::Entry`] to the form sent by replication, and back.
+///
+/// By default entries are sent as they are, with [`PassThroughCodec`], and a network
+/// implementation serializes the [`AppendEntriesRequest`] as a whole.
+/// An application whose in-memory entry already holds a serialized payload can send the payload
+/// bytes verbatim instead, with an [`EntryCodec::Encoded`] that does not serialize it again.
+///
+/// A network implementation converts a request with [`AppendEntriesRequest::encode()`] before
+/// sending it, and converts it back with [`EncodedAppendEntriesRequest::decode()`] on the remote
+/// end before passing it to [`Raft::append_entries()`].
+///
+/// [`AppendEntriesRequest`]: crate::raft::AppendEntriesRequest
+/// [`AppendEntriesRequest::encode()`]: crate::raft::AppendEntriesRequest::encode
+/// [`EncodedAppendEntriesRequest::decode()`]: crate::raft::EncodedAppendEntriesRequest::decode
+/// [`Raft::append_entries()`]: crate::Raft::append_entries
+#[since(version = "0.10.0")]
+pub trait EntryCodec<C>: OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+    /// The entry in the form sent over the network.
+    type Encoded: OptionalFeatures + Debug + 'static;
+
+    /// The error returned if an entry can not be encoded or decoded.
+    type Error: Error + OptionalSend + OptionalSync + 'static;
+
+    /// Convert an entry to the form sent over the network.
+    fn encode(entry: C::Entry) -> Result<Self::Encoded, Self::Error>;
+
+    /// Convert an entry received from the network back to the in-memory form.
+    fn decode(encoded: Self::Encoded) -> Result<C::Entry, Self::Error>;
+}
+
+/// The default [`EntryCodec`] that sends an entry as it is.
+#[since(version = "0.10.0")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PassThroughCodec<C> {
+    _p: PhantomData<C>,
+}
+
+impl<C> EntryCodec<C> for PassThroughCodec<C>
+where C: RaftTypeConfig
+{
+    type Encoded = C::Entry;
+    type Error = Infallible;
+
+    fn encode(entry: C::Entry) -> Result<Self::Encoded, Self::Error> {
+        Ok(entry)
+    }
+
+    fn decode(encoded: Self::Encoded) -> Result<C::Entry, Self::Error> {
+        Ok(encoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyerror::AnyError;
+
+    use crate::declare_raft_types;
+    use crate::entry::EntryCodec;
+    use crate::entry::RaftEntry;
+    use crate::raft::AppendEntriesRequest;
+    use crate::type_config::alias::LeaderIdOf;
+    use crate::type_config::alias::LogIdOf;
+    use crate::vote::RaftLeaderIdExt;
+    use crate::Entry;
+    use crate::EntryPayload;
+    use crate::Vote;
+
+    declare_raft_types!(
+        CodecConfig:
+            D = String,
+            EntryCodec = PayloadCodec,
+    );
+
+    /// Sends the application payload of a normal entry as it is.
+    struct PayloadCodec {}
+
+    impl EntryCodec<CodecConfig> for PayloadCodec {
+        type Encoded = (LogIdOf<CodecConfig>, String);
+        type Error = AnyError;
+
+        fn encode(entry: Entry<CodecConfig>) -> Result<Self::Encoded, Self::Error> {
+            match entry.payload {
+                EntryPayload::Normal(data) => Ok((entry.log_id, data)),
+                _ => Err(AnyError::error(format!("unsupported entry: {}", entry.log_id))),
+            }
+        }
+
+        fn decode(encoded: Self::Encoded) -> Result<Entry<CodecConfig>, Self::Error> {
+            let (log_id, data) = encoded;
+            Ok(Entry::new_normal(log_id, data))
+        }
+    }
+
+    fn lid(term: u64, index: u64) -> LogIdOf<CodecConfig> {
+        LogIdOf::<CodecConfig>::new(LeaderIdOf::<CodecConfig>::new_committed(term, 1), index)
+    }
+
+    #[test]
+    fn test_encode_decode_append_entries_request() -> anyhow::Result<()> {
+        let req = AppendEntriesRequest::<CodecConfig> {
+            vote: Vote::new_committed(2, 1),
+            prev_log_id: Some(lid(1, 1)),
+            entries: vec![Entry::new_normal(lid(2, 2), "a".to_string())],
+            leader_commit: Some(lid(1, 1)),
+        };
+
+        let encoded = req.encode()?;
+        assert_eq!(vec![(lid(2, 2), "a".to_string())], encoded.entries);
+
+        let decoded = encoded.decode()?;
+        assert_eq!(Vote::new_committed(2, 1), decoded.vote);
+        assert_eq!(Some(lid(1, 1)), decoded.prev_log_id);
+        assert_eq!(Some(lid(1, 1)), decoded.leader_commit);
+        assert_eq!(vec![Entry::new_normal(lid(2, 2), "a".to_string())], decoded.entries);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_error() -> anyhow::Result<()> {
+        let req = AppendEntriesRequest::<CodecConfig> {
+            vote: Vote::new_committed(2, 1),
+            prev_log_id: None,
+            entries: vec![Entry::new_blank(lid(2, 1))],
+            leader_commit: None,
+        };
+
+        let res = req.encode();
+        assert_eq!("unsupported entry: T2-N1.1", res.unwrap_err().to_string());
+
+        Ok(())
+    }
+}
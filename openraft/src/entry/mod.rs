@@ -6,10 +6,12 @@ use std::fmt::Debug;
 use crate::Membership;
 use crate::RaftTypeConfig;
 
+pub mod codec;
 pub mod payload;
 pub(crate) mod raft_entry_ext;
 mod traits;
 
+pub use codec::EntryCodec;
 pub use payload::EntryPayload;
 pub use traits::RaftEntry;
 pub use traits::RaftPayload;
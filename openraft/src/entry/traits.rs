@@ -84,4 +84,16 @@ where
     where Self: Final {
         self.log_id_parts().1
     }
+
+    /// Returns the size in bytes of this entry, if it is known to the application.
+    ///
+    /// Openraft does not serialize entries, thus it can not tell their size by itself. The sizes
+    /// are summed up in [`IoMetrics::log_bytes_appended`]. An entry that returns `None`, as the
+    /// default implementation does, is not counted.
+    ///
+    /// [`IoMetrics::log_bytes_appended`]: crate::metrics::IoMetrics::log_bytes_appended
+    #[since(version = "0.10.0")]
+    fn size_bytes(&self) -> Option<u64> {
+        None
+    }
 }
@@ -1,13 +1,20 @@
 //! Error types exposed by this crate.
 
 mod allow_next_revert_error;
+mod apply_error;
+mod client_write_timeout;
 pub mod decompose;
 pub mod into_ok;
 mod invalid_sm;
+mod invariant_violation;
+mod malformed_request;
 mod membership_error;
+mod membership_rejected;
 mod node_not_found;
 mod operation;
+mod preflight_failed;
 mod replication_closed;
+mod shutting_down;
 mod streaming_error;
 
 use std::collections::BTreeSet;
@@ -19,11 +26,18 @@ use std::time::Duration;
 use anyerror::AnyError;
 
 pub use self::allow_next_revert_error::AllowNextRevertError;
+pub use self::apply_error::ApplyError;
+pub use self::client_write_timeout::ClientWriteTimeout;
 pub use self::invalid_sm::InvalidStateMachineType;
+pub use self::invariant_violation::InvariantViolation;
+pub use self::malformed_request::MalformedRequest;
 pub use self::membership_error::MembershipError;
+pub use self::membership_rejected::MembershipRejected;
 pub use self::node_not_found::NodeNotFound;
 pub use self::operation::Operation;
+pub use self::preflight_failed::PreflightFailed;
 pub use self::replication_closed::ReplicationClosed;
+pub use self::shutting_down::ShuttingDown;
 pub use self::streaming_error::StreamingError;
 use crate::network::RPCTypes;
 use crate::raft::AppendEntriesResponse;
@@ -130,6 +144,7 @@ where C: RaftTypeConfig
 /// Fatal is unrecoverable and shuts down raft at once.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[non_exhaustive]
 pub enum Fatal<C>
 where C: RaftTypeConfig
 {
@@ -139,6 +154,12 @@ where C: RaftTypeConfig
     #[error("panicked")]
     Panicked,
 
+    /// An internal invariant is violated, with [`Config::panic_on_invariant_violation`] disabled.
+    ///
+    /// [`Config::panic_on_invariant_violation`]: crate::Config::panic_on_invariant_violation
+    #[error(transparent)]
+    InvariantViolation(#[from] InvariantViolation),
+
     /// Raft stopped normally.
     #[error("raft stopped")]
     Stopped,
@@ -148,9 +169,13 @@ where C: RaftTypeConfig
 #[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
 #[derive(PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[non_exhaustive]
 pub enum InstallSnapshotError {
     #[error(transparent)]
     SnapshotMismatch(#[from] SnapshotMismatch),
+
+    #[error(transparent)]
+    SnapshotVersionUnsupported(#[from] SnapshotVersionUnsupported),
 }
 
 /// An error related to a is_leader request.
@@ -177,10 +202,72 @@ where C: RaftTypeConfig
     }
 }
 
+/// An error when getting a read log id from the leader for a linearizable read on a non-leader
+/// node.
+#[derive(Debug, Clone, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum ReadIndexError<C>
+where C: RaftTypeConfig
+{
+    /// The leader is unknown, or the node that is believed to be the leader is not.
+    #[error(transparent)]
+    ForwardToLeader(#[from] ForwardToLeader<C>),
+
+    /// The leader can not confirm its leadership with a quorum.
+    #[error(transparent)]
+    QuorumNotEnough(#[from] QuorumNotEnough<C>),
+
+    /// Failed to communicate with the leader.
+    #[error(transparent)]
+    RPCError(#[from] RPCError<C>),
+}
+
+impl<C> From<CheckIsLeaderError<C>> for ReadIndexError<C>
+where C: RaftTypeConfig
+{
+    fn from(e: CheckIsLeaderError<C>) -> Self {
+        match e {
+            CheckIsLeaderError::ForwardToLeader(e) => e.into(),
+            CheckIsLeaderError::QuorumNotEnough(e) => e.into(),
+        }
+    }
+}
+
+impl<C> From<RPCError<C, RaftError<C, CheckIsLeaderError<C>>>> for ReadIndexError<C>
+where C: RaftTypeConfig
+{
+    fn from(e: RPCError<C, RaftError<C, CheckIsLeaderError<C>>>) -> Self {
+        let rpc_err = match e {
+            RPCError::Timeout(e) => RPCError::Timeout(e),
+            RPCError::Unreachable(e) => RPCError::Unreachable(e),
+            RPCError::PayloadTooLarge(e) => RPCError::PayloadTooLarge(e),
+            RPCError::Network(e) => RPCError::Network(e),
+            RPCError::RemoteError(remote_err) => match remote_err.source {
+                RaftError::APIError(e) => return e.into(),
+                // The leader is stopped, retry after backoff.
+                RaftError::Fatal(_) => RPCError::Unreachable(Unreachable::new(&remote_err)),
+            },
+        };
+        Self::RPCError(rpc_err)
+    }
+}
+
+impl<C> TryAsRef<ForwardToLeader<C>> for ReadIndexError<C>
+where C: RaftTypeConfig
+{
+    fn try_as_ref(&self) -> Option<&ForwardToLeader<C>> {
+        match self {
+            Self::ForwardToLeader(f) => Some(f),
+            _ => None,
+        }
+    }
+}
+
 /// An error related to a client write request.
 #[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
 #[derive(PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[non_exhaustive]
 pub enum ClientWriteError<C>
 where C: RaftTypeConfig
 {
@@ -190,6 +277,20 @@ where C: RaftTypeConfig
     /// When writing a change-membership entry.
     #[error(transparent)]
     ChangeMembershipError(#[from] ChangeMembershipError<C>),
+
+    /// The entry is committed, but the state machine rejected it with an application error.
+    #[error(transparent)]
+    ApplyError(#[from] ApplyError<C>),
+
+    /// The entry is not applied before the deadline given to
+    /// [`Raft::client_write_with_timeout()`](crate::Raft::client_write_with_timeout).
+    #[error(transparent)]
+    Timeout(#[from] ClientWriteTimeout<C>),
+
+    /// This node is draining the accepted writes before shutting down, see
+    /// [`Raft::shutdown_gracefully()`](crate::Raft::shutdown_gracefully).
+    #[error(transparent)]
+    ShuttingDown(#[from] ShuttingDown),
 }
 
 impl<C> TryAsRef<ForwardToLeader<C>> for ClientWriteError<C>
@@ -215,6 +316,18 @@ pub enum ChangeMembershipError<C: RaftTypeConfig> {
 
     #[error(transparent)]
     LearnerNotFound(#[from] LearnerNotFound<C>),
+
+    #[error(transparent)]
+    NodeNotFound(#[from] NodeNotFound<C>),
+
+    #[error(transparent)]
+    PreflightFailed(#[from] PreflightFailed<C>),
+
+    #[error(transparent)]
+    QueueFull(#[from] QueueFull),
+
+    #[error(transparent)]
+    Rejected(#[from] MembershipRejected),
 }
 
 /// The set of errors which may take place when initializing a pristine Raft node.
@@ -469,6 +582,9 @@ impl fmt::Display for PayloadTooLarge {
             RPCTypes::TransferLeader => {
                 unreachable!("TransferLeader rpc should not have payload")
             }
+            RPCTypes::ReadIndex => {
+                unreachable!("ReadIndex rpc should not have payload")
+            }
         }
         write!(f, ")")?;
 
@@ -575,6 +691,17 @@ pub struct SnapshotMismatch {
     pub got: SnapshotSegmentId,
 }
 
+/// The snapshot is in a state machine data format newer than this node can read.
+///
+/// See: [`Config::max_snapshot_version`](crate::Config::max_snapshot_version).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("snapshot version {version} is not supported, max supported version: {max_version}")]
+pub struct SnapshotVersionUnsupported {
+    pub version: u64,
+    pub max_version: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("not enough for a quorum, cluster: {cluster}, got: {got:?}")]
@@ -591,6 +718,17 @@ pub struct InProgress<C: RaftTypeConfig> {
     pub membership_log_id: Option<LogIdOf<C>>,
 }
 
+/// Too many membership changes are waiting for a previous one to finish.
+///
+/// See: [`Config::membership_change_queue_depth`](crate::Config::membership_change_queue_depth).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("membership change queue is full: {waiting} changes are waiting, max queue depth: {depth}")]
+pub struct QueueFull {
+    pub waiting: u64,
+    pub depth: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("Learner {node_id} not found: add it as learner before adding it as a voter")]
@@ -666,6 +804,9 @@ pub(crate) enum RejectAppendEntries<C: RaftTypeConfig> {
         expect: LogIdOf<C>,
         local: Option<LogIdOf<C>>,
     },
+
+    #[error("reject malformed AppendEntries: {0}")]
+    Malformed(#[from] MalformedRequest<C>),
 }
 
 impl<C> From<RejectVoteRequest<C>> for RejectAppendEntries<C>
@@ -690,6 +831,7 @@ where C: RaftTypeConfig
             Err(e) => match e {
                 RejectAppendEntries::ByVote(v) => AppendEntriesResponse::HigherVote(v),
                 RejectAppendEntries::ByConflictingLogId { expect: _, local: _ } => AppendEntriesResponse::Conflict,
+                RejectAppendEntries::Malformed(reason) => AppendEntriesResponse::Malformed(reason),
             },
         }
     }
@@ -0,0 +1,62 @@
+use std::error::Error;
+
+use anyerror::AnyError;
+
+use crate::type_config::alias::LogIdOf;
+use crate::RaftTypeConfig;
+
+/// An application error returned by the state machine when applying a log entry, such as a
+/// violated business rule.
+///
+/// Unlike a [`StorageError`](crate::StorageError), it does not stop the Raft node. The entry is
+/// still applied, i.e., the state machine records its log id as the last applied log id, but it
+/// makes no other change to the state machine. The error is returned to the caller of
+/// [`Raft::client_write()`](crate::Raft::client_write).
+///
+/// See: [`RaftStateMachine::apply_fallible()`](crate::storage::RaftStateMachine::apply_fallible).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("failed to apply log entry {log_id}: {source}")]
+pub struct ApplyError<C>
+where C: RaftTypeConfig
+{
+    /// The id of the log entry that failed to apply.
+    pub log_id: LogIdOf<C>,
+
+    pub source: AnyError,
+}
+
+impl<C> ApplyError<C>
+where C: RaftTypeConfig
+{
+    pub fn new(log_id: LogIdOf<C>, source: &(impl Error + 'static)) -> Self {
+        Self {
+            log_id,
+            source: AnyError::new(source),
+        }
+    }
+
+    /// Create an error with a message instead of a concrete error type.
+    pub fn with_message(log_id: LogIdOf<C>, msg: impl ToString) -> Self {
+        Self {
+            log_id,
+            source: AnyError::error(msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::testing::UTConfig;
+    use crate::error::ApplyError;
+    use crate::testing::log_id;
+
+    #[test]
+    fn test_apply_error_to_string() {
+        let err = ApplyError::<UTConfig>::with_message(log_id(1, 2, 3), "insufficient balance");
+        assert_eq!(
+            err.to_string(),
+            "failed to apply log entry T1-N2.3: insufficient balance"
+        );
+    }
+}
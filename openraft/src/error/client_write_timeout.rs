@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::type_config::alias::LogIdOf;
+use crate::RaftTypeConfig;
+
+/// A client write is not applied before the deadline.
+///
+/// Returned by [`Raft::client_write_with_timeout()`](crate::Raft::client_write_with_timeout).
+/// Timing out does not cancel the write: if the entry is already appended, i.e., `log_id` is
+/// `Some`, it may still be committed and applied later. If `log_id` is `None`, the request did not
+/// reach the leader's log before the deadline, but it may still be appended later, since it is
+/// already enqueued.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("client write timeout after {timeout:?}, log id: {}", .log_id.display())]
+pub struct ClientWriteTimeout<C>
+where C: RaftTypeConfig
+{
+    /// The log id assigned to the entry, if it is appended to the leader's log before the
+    /// deadline.
+    pub log_id: Option<LogIdOf<C>>,
+
+    pub timeout: Duration,
+}
+
+impl<C> ClientWriteTimeout<C>
+where C: RaftTypeConfig
+{
+    pub fn new(log_id: Option<LogIdOf<C>>, timeout: Duration) -> Self {
+        Self { log_id, timeout }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::testing::UTConfig;
+    use crate::error::ClientWriteTimeout;
+    use crate::testing::log_id;
+
+    #[test]
+    fn test_client_write_timeout_to_string() {
+        let err = ClientWriteTimeout::<UTConfig>::new(Some(log_id(1, 2, 3)), Duration::from_millis(100));
+        assert_eq!(err.to_string(), "client write timeout after 100ms, log id: T1-N2.3");
+
+        let err = ClientWriteTimeout::<UTConfig>::new(None, Duration::from_millis(100));
+        assert_eq!(err.to_string(), "client write timeout after 100ms, log id: None");
+    }
+}
@@ -0,0 +1,26 @@
+/// An internal invariant of Raft is found violated, which indicates a bug.
+///
+/// It is returned as [`Fatal::InvariantViolation`] instead of panicking, if
+/// [`Config::panic_on_invariant_violation`] is disabled.
+///
+/// [`Fatal::InvariantViolation`]: crate::error::Fatal::InvariantViolation
+/// [`Config::panic_on_invariant_violation`]: crate::Config::panic_on_invariant_violation
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[error("invariant violated: {context}")]
+pub struct InvariantViolation {
+    context: String,
+}
+
+impl InvariantViolation {
+    pub fn new(context: impl ToString) -> Self {
+        Self {
+            context: context.to_string(),
+        }
+    }
+
+    /// What is violated and where.
+    pub fn context(&self) -> &str {
+        &self.context
+    }
+}
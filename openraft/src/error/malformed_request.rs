@@ -0,0 +1,43 @@
+use crate::display_ext::DisplayOptionExt;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::RaftTypeConfig;
+
+/// A request that a correct peer never sends, e.g., one from a buggy third party implementation.
+///
+/// It is rejected before it changes any local log, and is returned to the sender in
+/// [`AppendEntriesResponse::Malformed`] or [`SnapshotResponse::malformed`].
+///
+/// [`AppendEntriesResponse::Malformed`]: crate::raft::AppendEntriesResponse::Malformed
+/// [`SnapshotResponse::malformed`]: crate::raft::SnapshotResponse::malformed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum MalformedRequest<C: RaftTypeConfig> {
+    /// A log id is proposed by a leader greater than the sender's vote, which can not be in the
+    /// sender's log.
+    #[error("log id {log_id} is greater than the sender vote {vote}")]
+    LogIdAfterVote { log_id: LogIdOf<C>, vote: VoteOf<C> },
+
+    /// The log entries do not follow the previous log id one by one, e.g., they have a gap or
+    /// overlap with each other, or the term decreases.
+    #[error("log id {next} does not follow {}", prev.display())]
+    NonConsecutiveLogIds { prev: Option<LogIdOf<C>>, next: LogIdOf<C> },
+
+    /// A log id conflicts with the local committed log.
+    #[error("log id {log_id} conflicts with the committed log, committed: {committed}")]
+    ConflictWithCommitted { log_id: LogIdOf<C>, committed: LogIdOf<C> },
+
+    /// The committed log id sent by the leader is not the one accepted from the same leader.
+    #[error("leader committed log id {leader_committed} is not the accepted log id {local} at the same index")]
+    LeaderCommittedNotAccepted {
+        leader_committed: LogIdOf<C>,
+        local: LogIdOf<C>,
+    },
+
+    /// The last membership of a snapshot is not included in the snapshot.
+    #[error("snapshot last membership log id {} is greater than the last log id {}", membership.display(), last_log_id.display())]
+    SnapshotMembershipAfterLastLogId {
+        membership: Option<LogIdOf<C>>,
+        last_log_id: Option<LogIdOf<C>>,
+    },
+}
@@ -0,0 +1,19 @@
+/// A membership config is rejected by the [`MembershipValidator`] of the leader.
+///
+/// Nothing is appended to the log.
+///
+/// [`MembershipValidator`]: crate::membership::MembershipValidator
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("membership config is rejected by validator: {reason}")]
+pub struct MembershipRejected {
+    pub reason: String,
+}
+
+impl MembershipRejected {
+    pub fn new(reason: impl ToString) -> Self {
+        Self {
+            reason: reason.to_string(),
+        }
+    }
+}
@@ -34,6 +34,9 @@ pub enum Operation {
 
     /// Start an election.
     Elect,
+
+    /// Update the node of an existing member.
+    UpdateNode,
 }
 
 impl fmt::Display for Operation {
@@ -48,6 +51,7 @@ impl fmt::Display for Operation {
             Operation::ClientWrite => write!(f, "write application data"),
             Operation::Initialize => write!(f, "initialize"),
             Operation::Elect => write!(f, "elect"),
+            Operation::UpdateNode => write!(f, "update node"),
         }
     }
 }
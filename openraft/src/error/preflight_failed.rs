@@ -0,0 +1,26 @@
+use crate::membership::MembershipChangeReport;
+use crate::RaftTypeConfig;
+
+/// A change-membership request is rejected by the pre-flight check.
+///
+/// Returned by [`Raft::change_membership_checked()`](crate::Raft::change_membership_checked) when
+/// [`MembershipChangeReport::is_ok()`] is `false`. Nothing is appended to the log.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("change membership rejected by pre-flight check: {report}")]
+pub struct PreflightFailed<C>
+where C: RaftTypeConfig
+{
+    /// What the change would have done.
+    pub report: Box<MembershipChangeReport<C>>,
+}
+
+impl<C> PreflightFailed<C>
+where C: RaftTypeConfig
+{
+    pub fn new(report: MembershipChangeReport<C>) -> Self {
+        Self {
+            report: Box::new(report),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+/// This node is shutting down gracefully and does not accept new client writes.
+///
+/// Returned after [`Raft::shutdown_gracefully()`] is called. Unlike a [`ForwardToLeader`], it does
+/// not mean an election is in progress: the client should not retry on this node, but send the
+/// write to another node.
+///
+/// [`Raft::shutdown_gracefully()`]: crate::Raft::shutdown_gracefully
+/// [`ForwardToLeader`]: crate::error::ForwardToLeader
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[error("this node is shutting down and does not accept writes")]
+pub struct ShuttingDown;
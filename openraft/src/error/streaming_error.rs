@@ -2,6 +2,7 @@ use crate::error::NetworkError;
 use crate::error::RPCError;
 use crate::error::ReplicationClosed;
 use crate::error::ReplicationError;
+use crate::error::SnapshotVersionUnsupported;
 use crate::error::Timeout;
 use crate::error::Unreachable;
 use crate::RaftTypeConfig;
@@ -32,6 +33,12 @@ pub enum StreamingError<C: RaftTypeConfig> {
     /// Failed to send the RPC request and should retry immediately.
     #[error(transparent)]
     Network(#[from] NetworkError),
+
+    /// The remote node refused the snapshot because it can not read its data format version.
+    ///
+    /// The leader does not send this snapshot to the node again, until a new snapshot is built.
+    #[error(transparent)]
+    SnapshotVersionUnsupported(#[from] SnapshotVersionUnsupported),
 }
 
 impl<C: RaftTypeConfig> From<StreamingError<C>> for ReplicationError<C> {
@@ -42,6 +49,9 @@ impl<C: RaftTypeConfig> From<StreamingError<C>> for ReplicationError<C> {
             StreamingError::Timeout(e) => ReplicationError::RPCError(RPCError::Timeout(e)),
             StreamingError::Unreachable(e) => ReplicationError::RPCError(RPCError::Unreachable(e)),
             StreamingError::Network(e) => ReplicationError::RPCError(RPCError::Network(e)),
+            StreamingError::SnapshotVersionUnsupported(e) => {
+                ReplicationError::RPCError(RPCError::Unreachable(Unreachable::new(&e)))
+            }
         }
     }
 }
@@ -1,5 +1,6 @@
 //! Collection of implementations of usually used traits defined by Openraft
 
+pub use crate::entry::codec::PassThroughCodec;
 pub use crate::entry::Entry;
 pub use crate::node::BasicNode;
 pub use crate::node::EmptyNode;
@@ -8,6 +8,9 @@ use std::ops::SubAssign;
 use std::panic::RefUnwindSafe;
 use std::panic::UnwindSafe;
 use std::time::Duration;
+use std::time::SystemTime;
+
+use openraft_macros::since;
 
 use crate::OptionalSend;
 use crate::OptionalSync;
@@ -36,6 +39,16 @@ pub trait Instant:
     /// Return the current instant.
     fn now() -> Self;
 
+    /// Return the current wall clock time, used to display or serialize an instant.
+    ///
+    /// The default implementation calls [`SystemTime::now()`], which panics on targets without a
+    /// system clock, such as `wasm32-unknown-unknown`. A runtime for such a target should
+    /// override it, e.g., with `Date.now()` of JavaScript.
+    #[since(version = "0.10.0")]
+    fn system_now() -> SystemTime {
+        SystemTime::now()
+    }
+
     /// Return the amount of time since the instant.
     ///
     /// The returned duration is guaranteed to be non-negative.
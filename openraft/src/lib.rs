@@ -54,6 +54,7 @@ pub(crate) mod raft_state;
 pub(crate) mod utime;
 
 pub mod base;
+pub mod client;
 #[cfg(feature = "compat")]
 pub mod compat;
 pub mod docs;
@@ -64,8 +65,10 @@ pub mod instant;
 pub mod log_id;
 pub mod membership;
 pub mod metrics;
+pub mod multi_raft;
 pub mod network;
 pub mod raft;
+pub mod session;
 pub mod storage;
 pub mod testing;
 pub mod type_config;
@@ -95,8 +98,12 @@ pub use crate::base::OptionalSync;
 pub use crate::change_members::ChangeMembers;
 pub use crate::config::Config;
 pub use crate::config::ConfigError;
+pub use crate::config::ConfigPatch;
+pub use crate::config::EffectiveConfig;
+pub use crate::config::RemovedLeaderAction;
 pub use crate::config::SnapshotPolicy;
 pub use crate::core::ServerState;
+pub use crate::engine::EngineDecision;
 pub use crate::entry::Entry;
 pub use crate::entry::EntryPayload;
 pub use crate::instant::Instant;
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::display_ext::DisplayOption;
+use crate::display_ext::DisplayOptionExt;
+use crate::display_ext::DisplaySliceExt;
+use crate::error::InProgress;
+use crate::metrics::ReplicationLag;
+use crate::Membership;
+use crate::RaftTypeConfig;
+
+/// The replication progress of a lagging voter in a [`MembershipChangeReport`].
+///
+/// A caller can use [`ReplicationLag::millis_behind`] to decide when to retry the change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LaggingVoter {
+    /// The last log index replicated to the voter, or `None` if nothing is replicated.
+    pub matched: Option<u64>,
+
+    /// How many entries the voter is behind the leader and the estimated time to catch up.
+    pub lag: ReplicationLag,
+}
+
+impl fmt::Display for LaggingVoter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{matched: {}, lag: {}}}", DisplayOption(&self.matched), self.lag)
+    }
+}
+
+/// What a change-membership request would do to the cluster, without proposing it.
+///
+/// It is returned by [`Raft::change_membership_dry_run()`], and is included in the
+/// [`PreflightFailed`] error when [`Raft::change_membership_checked()`] rejects a change.
+///
+/// [`Raft::change_membership_dry_run()`]: crate::Raft::change_membership_dry_run
+/// [`Raft::change_membership_checked()`]: crate::Raft::change_membership_checked
+/// [`PreflightFailed`]: crate::error::PreflightFailed
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct MembershipChangeReport<C>
+where C: RaftTypeConfig
+{
+    /// The effective membership the change is applied to.
+    pub current: Membership<C>,
+
+    /// The joint config proposed in the first step, or `None` if the change takes one step.
+    pub joint: Option<Membership<C>>,
+
+    /// The uniform config the cluster ends up with.
+    pub target: Membership<C>,
+
+    /// Voters in the new configs that are not a node in the cluster, i.e., not added as learners.
+    pub missing: BTreeSet<C::NodeId>,
+
+    /// Voters in the new configs whose replication lags behind the leader by more than
+    /// [`Config::replication_lag_threshold`], with the log index they have replicated and the
+    /// estimated time for them to catch up.
+    ///
+    /// [`Config::replication_lag_threshold`]: crate::Config::replication_lag_threshold
+    pub lagging: BTreeMap<C::NodeId, LaggingVoter>,
+
+    /// Whether the up-to-date voters form a quorum in every new config, i.e., whether the new
+    /// configs can be committed without waiting for a lagging node.
+    pub quorum_preserved: bool,
+
+    /// Set if the last membership change is not committed yet.
+    pub in_progress: Option<InProgress<C>>,
+}
+
+impl<C> MembershipChangeReport<C>
+where C: RaftTypeConfig
+{
+    /// Returns `true` if the change can be proposed and is expected to be committed.
+    pub fn is_ok(&self) -> bool {
+        self.in_progress.is_none()
+            && self.missing.is_empty()
+            && self.target.voter_ids().next().is_some()
+            && self.quorum_preserved
+    }
+}
+
+impl<C> fmt::Display for MembershipChangeReport<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{current: {}, joint: {}, target: {}, missing: {}, lagging: {{{}}}, quorum_preserved: {}, in_progress: {}}}",
+            self.current,
+            self.joint.display(),
+            self.target,
+            self.missing.iter().collect::<Vec<_>>().display(),
+            self.lagging.iter().map(|(id, l)| format!("{}: {}", id, l)).collect::<Vec<_>>().join(", "),
+            self.quorum_preserved,
+            self.in_progress.display(),
+        )
+    }
+}
@@ -291,7 +291,40 @@ where C: RaftTypeConfig
     /// ```
     pub(crate) fn next_coherent(&self, goal: BTreeSet<C::NodeId>, retain: bool) -> Self {
         let config = Joint::from(self.configs.clone()).find_coherent(goal).children().clone();
+        self.with_configs(config, retain)
+    }
+
+    /// Returns the next coherent membership to change to, like [`Self::next_coherent()`], but
+    /// going through every config in `via` on the way to `goal`.
+    ///
+    /// E.g.:
+    /// - `c1.next_coherent_via([c2], c3)` returns `c1c2c3`
+    /// - `c1c2c3.next_coherent_via([c2], c3)` returns `c3`
+    /// - `c1.next_coherent_via([c1, c3], c3)` returns `c1c3`
+    pub(crate) fn next_coherent_via(
+        &self,
+        via: Vec<BTreeSet<C::NodeId>>,
+        goal: BTreeSet<C::NodeId>,
+        retain: bool,
+    ) -> Self {
+        if self.configs.contains(&goal) {
+            return self.next_coherent(goal, retain);
+        }
+
+        let mut config = self.configs.last().cloned().into_iter().collect::<Vec<_>>();
+        for c in via.into_iter().chain([goal]) {
+            if !config.contains(&c) {
+                config.push(c);
+            }
+        }
+
+        self.with_configs(config, retain)
+    }
 
+    /// Build a membership with the voter `config` and the nodes of this membership.
+    ///
+    /// If `retain` is `false`, the voters that are not in `config` are removed from the nodes.
+    fn with_configs(&self, config: Vec<BTreeSet<C::NodeId>>, retain: bool) -> Self {
         let mut nodes = self.nodes.clone();
 
         if !retain {
@@ -312,7 +345,25 @@ where C: RaftTypeConfig
     ///
     /// `retain` specifies whether to retain the removed voters as a learners, i.e., nodes that
     /// continue to receive log replication from the leader.
-    pub(crate) fn change(mut self, change: ChangeMembers<C>, retain: bool) -> Result<Self, ChangeMembershipError<C>> {
+    pub(crate) fn change(self, change: ChangeMembers<C>, retain: bool) -> Result<Self, ChangeMembershipError<C>> {
+        if let ChangeMembers::UpdateNodes(update_nodes) = &change {
+            if let Some(node_id) = update_nodes.keys().find(|id| !self.contains(id)) {
+                return Err(NodeNotFound::new(node_id.clone(), Operation::UpdateNode).into());
+            }
+        }
+
+        let new_membership = self.change_unchecked(change, retain);
+
+        new_membership.ensure_valid()?;
+
+        Ok(new_membership)
+    }
+
+    /// Apply a change-membership request without validating the result.
+    ///
+    /// The returned instance may have no voter, or have voters without a node, and an absent node
+    /// in `UpdateNodes` is ignored. It is used to find out what a rejected change would have been.
+    pub(crate) fn change_unchecked(mut self, change: ChangeMembers<C>, retain: bool) -> Self {
         tracing::debug!(change = debug(&change), "{}", func_name!());
 
         let last = self.get_joint_config().last().cloned().unwrap_or_default();
@@ -335,6 +386,10 @@ where C: RaftTypeConfig
                 self.next_coherent(new_voter_ids, retain)
             }
             ChangeMembers::ReplaceAllVoters(all_voter_ids) => self.next_coherent(all_voter_ids, retain),
+            ChangeMembers::ReplaceAllVotersVia(mut configs) => {
+                let goal = configs.pop().unwrap_or_default();
+                self.next_coherent_via(configs, goal, retain)
+            }
             ChangeMembers::AddNodes(add_nodes) => {
                 // When adding nodes, do not override existing node
                 for (node_id, node) in add_nodes.into_iter() {
@@ -348,6 +403,14 @@ where C: RaftTypeConfig
                 }
                 self
             }
+            ChangeMembers::UpdateNodes(update_nodes) => {
+                for (node_id, node) in update_nodes.into_iter() {
+                    if let Some(n) = self.nodes.get_mut(&node_id) {
+                        *n = node;
+                    }
+                }
+                self
+            }
             ChangeMembers::RemoveNodes(remove_node_ids) => {
                 for node_id in remove_node_ids.iter() {
                     self.nodes.remove(node_id);
@@ -362,9 +425,7 @@ where C: RaftTypeConfig
 
         tracing::debug!(new_membership = display(&new_membership), "new membership");
 
-        new_membership.ensure_valid()?;
-
-        Ok(new_membership)
+        new_membership
     }
 
     /// Build a QuorumSet from current joint config
@@ -386,6 +447,8 @@ mod tests {
     use crate::error::ChangeMembershipError;
     use crate::error::EmptyMembership;
     use crate::error::LearnerNotFound;
+    use crate::error::NodeNotFound;
+    use crate::error::Operation;
     use crate::ChangeMembers;
     use crate::Membership;
 
@@ -509,6 +572,39 @@ mod tests {
             );
         }
 
+        // ReplaceVia: every voter has to be a node
+        {
+            let res = m().change(
+                ChangeMembers::ReplaceAllVotersVia(vec![btreeset! {2,4}, btreeset! {3}]),
+                false,
+            );
+            assert_eq!(
+                Err(ChangeMembershipError::LearnerNotFound(LearnerNotFound { node_id: 4 })),
+                res
+            );
+        }
+
+        // ReplaceVia:
+        {
+            let res = m().change(
+                ChangeMembers::ReplaceAllVotersVia(vec![btreeset! {2,3}, btreeset! {3}]),
+                false,
+            );
+            assert_eq!(
+                Ok(Membership::<UTConfig> {
+                    configs: vec![btreeset! {1,2}, btreeset! {2,3}, btreeset! {3}],
+                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                }),
+                res
+            );
+        }
+
+        // ReplaceVia: empty
+        {
+            let res = m().change(ChangeMembers::ReplaceAllVotersVia(vec![]), false);
+            assert_eq!(Err(ChangeMembershipError::EmptyMembership(EmptyMembership {})), res);
+        }
+
         // AddNodes: existent voter
         {
             let res = m().change(ChangeMembers::AddNodes(btreemap! {2=>()}), false);
@@ -562,6 +658,35 @@ mod tests {
             );
         }
 
+        // UpdateNodes: can not update an absent node
+        {
+            let res = m().change(ChangeMembers::UpdateNodes(btreemap! {4=>()}), false);
+            assert_eq!(
+                Err(ChangeMembershipError::NodeNotFound(NodeNotFound::new(
+                    4,
+                    Operation::UpdateNode
+                ))),
+                res
+            );
+        }
+
+        // UpdateNodes: Ok
+        {
+            let m = || Membership::<UTConfig<u64>> {
+                configs: vec![btreeset! {1,2}],
+                nodes: btreemap! {1=>1,2=>2,3=>3},
+            };
+
+            let res = m().change(ChangeMembers::UpdateNodes(btreemap! {2=>20, 3=>30}), false);
+            assert_eq!(
+                Ok(Membership::<UTConfig<u64>> {
+                    configs: vec![btreeset! {1,2}],
+                    nodes: btreemap! {1=>1,2=>20,3=>30}
+                }),
+                res
+            );
+        }
+
         // RemoveNodes: can not remove node for voter
         {
             let res = m().change(ChangeMembers::RemoveNodes(btreeset! {2}), false);
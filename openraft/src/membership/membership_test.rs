@@ -297,6 +297,46 @@ fn test_membership_next_coherent() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_membership_next_coherent_via() -> anyhow::Result<()> {
+    let nodes = || vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let c1 = || btreeset! {1,2,3};
+    let c2 = || btreeset! {3,4,5};
+    let c3 = || btreeset! {7,8,9};
+
+    #[allow(clippy::redundant_closure)]
+    let new_mem = |voter_ids, ns| Membership::<UTConfig>::new_with_defaults(voter_ids, ns);
+
+    let m1 = new_mem(vec![c1()], nodes());
+    let m123 = new_mem(vec![c1(), c2(), c3()], nodes());
+
+    assert_eq!(m123, m1.next_coherent_via(vec![c2()], c3(), false));
+    assert_eq!(m123, m1.next_coherent_via(vec![c1(), c2(), c2()], c3(), false));
+    assert_eq!(
+        new_mem(vec![c1(), c3()], nodes()),
+        m1.next_coherent_via(vec![], c3(), false)
+    );
+
+    // The goal is in the joint config: change to the uniform config.
+    assert_eq!(
+        new_mem(vec![c3()], vec![6, 7, 8, 9]),
+        m123.next_coherent_via(vec![c2()], c3(), false)
+    );
+    assert_eq!(
+        m123.next_coherent(c3(), true),
+        m123.next_coherent_via(vec![c2()], c3(), true)
+    );
+
+    // A joint config starts from the last config of the current joint config.
+    let m12 = new_mem(vec![c1(), c2()], nodes());
+    assert_eq!(
+        new_mem(vec![c2(), c1(), c3()], nodes()),
+        m12.next_coherent_via(vec![c1()], c3(), false)
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_membership_next_coherent_with_nodes() -> anyhow::Result<()> {
     let node = |s: &str| TestNode {
@@ -1,8 +1,11 @@
+mod change_report;
 mod effective_membership;
 mod into_nodes;
 #[allow(clippy::module_inception)]
 mod membership;
+mod removal_plan;
 mod stored_membership;
+mod validator;
 
 #[cfg(feature = "bench")]
 #[cfg(test)]
@@ -12,8 +15,17 @@ mod bench;
 mod effective_membership_test;
 #[cfg(test)]
 mod membership_test;
+#[cfg(test)]
+mod removal_plan_test;
+#[cfg(test)]
+mod validator_test;
 
+pub use change_report::LaggingVoter;
+pub use change_report::MembershipChangeReport;
 pub use effective_membership::EffectiveMembership;
 pub use into_nodes::IntoNodes;
 pub use membership::Membership;
+pub use removal_plan::RemovalPlan;
 pub use stored_membership::StoredMembership;
+pub use validator::MembershipValidator;
+pub use validator::ZoneSpreadValidator;
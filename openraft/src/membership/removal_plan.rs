@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::time::Duration;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::display_ext::DisplaySliceExt;
+use crate::error::InProgress;
+use crate::quorum::QuorumSet;
+use crate::Membership;
+use crate::RaftTypeConfig;
+
+/// Which unreachable nodes the leader removes from the cluster, built by
+/// [`Raft::remove_unreachable_nodes()`].
+///
+/// A node is unreachable if it has not acknowledged the leader for at least the given downtime.
+/// A node that has never acknowledged the current leader is counted as down since the leader is
+/// established. The leader itself is never unreachable.
+///
+/// [`Raft::remove_unreachable_nodes()`]: crate::Raft::remove_unreachable_nodes
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct RemovalPlan<C>
+where C: RaftTypeConfig
+{
+    /// The effective membership the removal is applied to.
+    pub current: Membership<C>,
+
+    /// The unreachable voters and learners, with the time since they last acknowledged the
+    /// leader.
+    pub unreachable: BTreeMap<C::NodeId, Duration>,
+
+    /// The unreachable voters to remove.
+    pub remove_voters: BTreeSet<C::NodeId>,
+
+    /// The unreachable learners to remove.
+    pub remove_learners: BTreeSet<C::NodeId>,
+
+    /// Whether the reachable voters form a quorum of the current config.
+    ///
+    /// If not, a membership change can not be committed, and no voter is removed.
+    pub quorum_preserved: bool,
+
+    /// Set if the last membership change is not committed yet, in which case nothing is removed.
+    pub in_progress: Option<InProgress<C>>,
+}
+
+impl<C> RemovalPlan<C>
+where C: RaftTypeConfig
+{
+    /// Build a plan to remove the nodes in `current` that have been down for at least
+    /// `min_downtime`.
+    ///
+    /// `down_for` returns the time since a node last acknowledged the leader.
+    pub(crate) fn new(
+        current: Membership<C>,
+        leader_id: &C::NodeId,
+        min_downtime: Duration,
+        down_for: impl Fn(&C::NodeId) -> Duration,
+        in_progress: Option<InProgress<C>>,
+    ) -> Self {
+        let mut unreachable = BTreeMap::new();
+
+        for (id, _) in current.nodes() {
+            if id == leader_id {
+                continue;
+            }
+
+            let d = down_for(id);
+            if d >= min_downtime {
+                unreachable.insert(id.clone(), d);
+            }
+        }
+
+        let reachable = current.voter_ids().filter(|id| !unreachable.contains_key(id)).collect::<Vec<_>>();
+        let quorum_preserved = current.to_quorum_set().is_quorum(reachable.iter());
+
+        let mut plan = Self {
+            current,
+            unreachable,
+            remove_voters: BTreeSet::new(),
+            remove_learners: BTreeSet::new(),
+            quorum_preserved,
+            in_progress,
+        };
+
+        if plan.in_progress.is_some() {
+            return plan;
+        }
+
+        for id in plan.unreachable.keys() {
+            if plan.current.is_voter(id) {
+                if plan.quorum_preserved {
+                    plan.remove_voters.insert(id.clone());
+                }
+            } else {
+                plan.remove_learners.insert(id.clone());
+            }
+        }
+
+        plan
+    }
+
+    /// Returns `true` if there is nothing to remove.
+    pub fn is_empty(&self) -> bool {
+        self.remove_voters.is_empty() && self.remove_learners.is_empty()
+    }
+}
+
+impl<C> fmt::Display for RemovalPlan<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{current: {}, unreachable: {:?}, remove_voters: {}, remove_learners: {}, quorum_preserved: {}, in_progress: {}}}",
+            self.current,
+            self.unreachable,
+            self.remove_voters.iter().collect::<Vec<_>>().display(),
+            self.remove_learners.iter().collect::<Vec<_>>().display(),
+            self.quorum_preserved,
+            self.in_progress.display(),
+        )
+    }
+}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use maplit::btreemap;
+use maplit::btreeset;
+
+use crate::engine::testing::log_id;
+use crate::engine::testing::UTConfig;
+use crate::error::InProgress;
+use crate::membership::RemovalPlan;
+use crate::Membership;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+/// Node `id` has been down for `id * 100` ms.
+fn down_for(id: &u64) -> Duration {
+    ms(id * 100)
+}
+
+#[test]
+fn test_removal_plan_remove_unreachable() -> anyhow::Result<()> {
+    let m = Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2,3,4,5}], [6]);
+
+    let plan = RemovalPlan::new(m.clone(), &5, ms(400), down_for, None);
+
+    assert_eq!(m, plan.current);
+    assert_eq!(
+        btreemap! {4=>ms(400), 6=>ms(600)},
+        plan.unreachable,
+        "leader 5 is never unreachable"
+    );
+    assert_eq!(btreeset! {4}, plan.remove_voters);
+    assert_eq!(btreeset! {6}, plan.remove_learners);
+    assert!(plan.quorum_preserved);
+    assert!(!plan.is_empty());
+
+    let plan = RemovalPlan::new(m, &1, ms(400), down_for, None);
+    assert_eq!(btreeset! {4,5}, plan.remove_voters);
+    assert_eq!(btreeset! {6}, plan.remove_learners);
+    assert!(plan.quorum_preserved);
+
+    Ok(())
+}
+
+#[test]
+fn test_removal_plan_quorum_not_preserved() -> anyhow::Result<()> {
+    let m = Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2,3}], [4]);
+
+    let plan = RemovalPlan::new(m, &1, ms(200), down_for, None);
+
+    assert_eq!(btreemap! {2=>ms(200), 3=>ms(300), 4=>ms(400)}, plan.unreachable);
+    assert!(plan.remove_voters.is_empty(), "{{1}} is not a quorum of {{1,2,3}}");
+    assert_eq!(btreeset! {4}, plan.remove_learners);
+    assert!(!plan.quorum_preserved);
+
+    Ok(())
+}
+
+#[test]
+fn test_removal_plan_in_progress() -> anyhow::Result<()> {
+    let m = Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2,3}, btreeset! {1,2,3,4}], [5]);
+    let in_progress = InProgress {
+        committed: Some(log_id(1, 1, 2)),
+        membership_log_id: Some(log_id(1, 1, 3)),
+    };
+
+    let plan = RemovalPlan::new(m, &1, ms(300), down_for, Some(in_progress.clone()));
+
+    assert_eq!(btreemap! {3=>ms(300), 4=>ms(400), 5=>ms(500)}, plan.unreachable);
+    assert!(
+        !plan.quorum_preserved,
+        "{{1,2}} is a quorum of {{1,2,3}} but not of {{1,2,3,4}}"
+    );
+    assert!(plan.is_empty());
+    assert_eq!(Some(in_progress), plan.in_progress);
+
+    Ok(())
+}
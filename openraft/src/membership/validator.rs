@@ -0,0 +1,93 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::error::MembershipRejected;
+use crate::Membership;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+
+/// Validates a membership config on the leader before it is appended to the log.
+///
+/// It is registered with [`Raft::set_membership_validator()`], and is run for every membership
+/// config proposed by the leader, including the **joint** and the **uniform** config of a
+/// [`Raft::change_membership()`], adding learners, and the changes proposed automatically.
+/// Returning an error rejects the config, and the change fails with
+/// [`ChangeMembershipError::Rejected`].
+///
+/// Application specific node metadata, such as the zone, the rack or tags, is in the
+/// [`RaftTypeConfig::Node`] of every node in the membership, and can be used to enforce placement
+/// constraints, see [`ZoneSpreadValidator`].
+///
+/// Since only the leader runs the validator, it has to be registered on every node that may
+/// become a leader.
+///
+/// [`Raft::set_membership_validator()`]: crate::Raft::set_membership_validator
+/// [`Raft::change_membership()`]: crate::Raft::change_membership
+/// [`ChangeMembershipError::Rejected`]: crate::error::ChangeMembershipError::Rejected
+pub trait MembershipValidator<C>: OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+    /// Check the `proposed` membership config that is going to replace `current`.
+    ///
+    /// A **joint** config contains both the old and the new voter sets, the last of which is what
+    /// the cluster ends up with.
+    fn validate(&self, current: &Membership<C>, proposed: &Membership<C>) -> Result<(), MembershipRejected>;
+}
+
+/// Rejects a membership config whose voters are not spread across enough zones.
+///
+/// The zone of a node is returned by `zone_of`, from the node id or the node metadata. A node
+/// without a zone does not count for any zone.
+///
+/// The voters the cluster ends up with, i.e., the last voter set of a proposed config, have to
+/// span at least `min_zones` zones, or have to be in distinct zones if there are fewer voters than
+/// `min_zones`. The old voter set in a **joint** config is not checked, and a config that does not
+/// change the voters, e.g., one that adds a learner, is always accepted, so that a cluster that
+/// does not yet satisfy the constraint can still be fixed.
+pub struct ZoneSpreadValidator<F> {
+    min_zones: usize,
+    zone_of: F,
+}
+
+impl<F> ZoneSpreadValidator<F> {
+    pub fn new(min_zones: usize, zone_of: F) -> Self {
+        Self { min_zones, zone_of }
+    }
+}
+
+impl<C, F, Z> MembershipValidator<C> for ZoneSpreadValidator<F>
+where
+    C: RaftTypeConfig,
+    F: Fn(&C::NodeId, &C::Node) -> Option<Z> + OptionalSend + OptionalSync + 'static,
+    Z: Ord + fmt::Display,
+{
+    fn validate(&self, current: &Membership<C>, proposed: &Membership<C>) -> Result<(), MembershipRejected> {
+        if current.get_joint_config() == proposed.get_joint_config() {
+            return Ok(());
+        }
+
+        let Some(voter_ids) = proposed.get_joint_config().last() else {
+            return Ok(());
+        };
+
+        let zones = voter_ids
+            .iter()
+            .filter_map(|id| proposed.get_node(id).and_then(|node| (self.zone_of)(id, node)))
+            .collect::<BTreeSet<_>>();
+
+        let want = self.min_zones.min(voter_ids.len());
+        if zones.len() < want {
+            let zones = zones.iter().map(|z| z.to_string()).collect::<Vec<_>>().join(",");
+            return Err(MembershipRejected::new(format!(
+                "voters {:?} span {} zones [{}], expect at least {}",
+                voter_ids,
+                zones.len(),
+                zones,
+                want
+            )));
+        }
+
+        Ok(())
+    }
+}
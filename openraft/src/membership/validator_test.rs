@@ -0,0 +1,57 @@
+use maplit::btreeset;
+
+use crate::engine::testing::UTConfig;
+use crate::membership::MembershipValidator;
+use crate::membership::ZoneSpreadValidator;
+use crate::Membership;
+
+/// Node 1,2,3 are in zone `a`, node 4 is in zone `b`, node 5 has no zone.
+fn zone_of(id: &u64, _node: &()) -> Option<&'static str> {
+    match id {
+        1..=3 => Some("a"),
+        4 => Some("b"),
+        _ => None,
+    }
+}
+
+fn m(configs: Vec<std::collections::BTreeSet<u64>>, learners: impl IntoIterator<Item = u64>) -> Membership<UTConfig> {
+    Membership::new_with_defaults(configs, learners)
+}
+
+#[test]
+fn test_zone_spread_validator() -> anyhow::Result<()> {
+    let v = ZoneSpreadValidator::new(2, zone_of);
+    let validate = |current: &Membership<UTConfig>, proposed: &Membership<UTConfig>| {
+        MembershipValidator::<UTConfig>::validate(&v, current, proposed)
+    };
+
+    let m1 = m(vec![btreeset! {1}], []);
+
+    // A single voter spans as many zones as it can.
+    assert!(validate(&m1, &m1).is_ok());
+
+    // Voters unchanged: adding a learner is accepted.
+    let m12 = m(vec![btreeset! {1,2}], []);
+    assert!(validate(&m12, &m(vec![btreeset! {1,2}], [4])).is_ok());
+
+    // All voters in zone `a`.
+    let res = validate(&m1, &m(vec![btreeset! {1}, btreeset! {1,2}], [2]));
+    assert_eq!(
+        "voters {1, 2} span 1 zones [a], expect at least 2",
+        res.unwrap_err().reason
+    );
+
+    // A node without a zone does not count.
+    let res = validate(&m1, &m(vec![btreeset! {1,5}], []));
+    assert!(res.is_err());
+
+    // Spread across zone `a` and `b`.
+    assert!(validate(&m1, &m(vec![btreeset! {1,2,4}], [])).is_ok());
+
+    // Only the last config of a joint config is checked.
+    assert!(validate(&m12, &m(vec![btreeset! {1,2}, btreeset! {1,2,4}], [])).is_ok());
+    let res = validate(&m12, &m(vec![btreeset! {1,2,4}, btreeset! {1,2,3}], [4]));
+    assert!(res.is_err());
+
+    Ok(())
+}
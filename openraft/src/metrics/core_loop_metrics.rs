@@ -0,0 +1,178 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::type_config::alias::InstantOf;
+use crate::Instant;
+use crate::RaftTypeConfig;
+
+/// Saturation metrics of the `RaftCore` event loop, measured in the last complete window of about
+/// one second.
+///
+/// In every iteration, the loop waits for a message, processes it along with the other queued
+/// messages, runs the engine commands they produce, and flushes the metrics. A high busy ratio
+/// along with a deep queue suggests that `RaftCore` itself is the bottleneck, rather than the
+/// storage or the network.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CoreLoopMetrics {
+    /// The max number of messages, i.e., API calls and internal notifications, processed in one
+    /// iteration: the messages that are queued while the loop is busy.
+    pub max_queue_depth: u64,
+
+    /// The average number of messages processed per iteration.
+    pub messages_per_flush: u64,
+
+    /// The average number of engine commands run per iteration.
+    pub commands_per_flush: u64,
+
+    /// The fraction of time the loop is not waiting for messages, in permille.
+    pub busy_permille: u64,
+}
+
+impl fmt::Display for CoreLoopMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{max_queue_depth:{}, messages_per_flush:{}, commands_per_flush:{}, busy:{}‰}}",
+            self.max_queue_depth, self.messages_per_flush, self.commands_per_flush, self.busy_permille
+        )
+    }
+}
+
+/// Accumulates the statistics of the `RaftCore` loop in the current window.
+pub(crate) struct CoreLoopStats<C>
+where C: RaftTypeConfig
+{
+    window_start: Option<InstantOf<C>>,
+    idle: Duration,
+    iterations: u64,
+    iteration_messages: u64,
+    messages: u64,
+    max_messages: u64,
+    commands: u64,
+}
+
+impl<C> Default for CoreLoopStats<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            window_start: None,
+            idle: Duration::default(),
+            iterations: 0,
+            iteration_messages: 0,
+            messages: 0,
+            max_messages: 0,
+            commands: 0,
+        }
+    }
+}
+
+impl<C> CoreLoopStats<C>
+where C: RaftTypeConfig
+{
+    const WINDOW: Duration = Duration::from_secs(1);
+
+    /// Record the time the loop waited for a message.
+    pub(crate) fn add_idle(&mut self, idle: Duration) {
+        self.idle += idle;
+    }
+
+    pub(crate) fn add_command(&mut self) {
+        self.commands += 1;
+    }
+
+    pub(crate) fn add_message(&mut self) {
+        self.iteration_messages += 1;
+    }
+
+    /// Record the end of an iteration, with the messages added since the last one.
+    pub(crate) fn end_iteration(&mut self) {
+        let messages = std::mem::take(&mut self.iteration_messages);
+
+        self.iterations += 1;
+        self.messages += messages;
+        self.max_messages = std::cmp::max(self.max_messages, messages);
+    }
+
+    /// Return the metrics of the current window and start a new one, if the window is complete.
+    pub(crate) fn complete_window(&mut self, now: InstantOf<C>) -> Option<CoreLoopMetrics> {
+        let Some(start) = self.window_start else {
+            self.window_start = Some(now);
+            return None;
+        };
+
+        let elapsed = now.saturating_duration_since(start);
+        if elapsed < Self::WINDOW {
+            return None;
+        }
+
+        let iterations = std::cmp::max(self.iterations, 1);
+        let busy = elapsed.saturating_sub(self.idle);
+
+        let metrics = CoreLoopMetrics {
+            max_queue_depth: self.max_messages,
+            messages_per_flush: self.messages / iterations,
+            commands_per_flush: self.commands / iterations,
+            busy_permille: (busy.as_micros() * 1000 / elapsed.as_micros()) as u64,
+        };
+
+        *self = Self {
+            window_start: Some(now),
+            ..Default::default()
+        };
+
+        Some(metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::testing::UTConfig;
+    use crate::metrics::core_loop_metrics::CoreLoopStats;
+    use crate::metrics::CoreLoopMetrics;
+    use crate::type_config::TypeConfigExt;
+
+    #[test]
+    fn test_core_loop_stats() {
+        let mut stats = CoreLoopStats::<UTConfig>::default();
+
+        let now = UTConfig::<()>::now();
+        assert_eq!(None, stats.complete_window(now));
+
+        stats.add_message();
+        stats.end_iteration();
+
+        for _ in 0..5 {
+            stats.add_message();
+        }
+        stats.end_iteration();
+        for _ in 0..6 {
+            stats.add_command();
+        }
+        stats.add_idle(Duration::from_millis(1500));
+
+        assert_eq!(None, stats.complete_window(now + Duration::from_millis(500)));
+
+        assert_eq!(
+            Some(CoreLoopMetrics {
+                max_queue_depth: 5,
+                messages_per_flush: 3,
+                commands_per_flush: 3,
+                busy_permille: 250,
+            }),
+            stats.complete_window(now + Duration::from_secs(2))
+        );
+
+        assert_eq!(
+            Some(CoreLoopMetrics {
+                busy_permille: 1000,
+                ..Default::default()
+            }),
+            stats.complete_window(now + Duration::from_secs(3)),
+            "a new window starts"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOption;
+use crate::type_config::alias::SerdeInstantOf;
+use crate::RaftTypeConfig;
+
+/// Statistics of the elections of this node, since it is started.
+///
+/// A leadership that flaps shows up as a fast growing `elections_started` on the voters, or a
+/// recent [`Self::last_leader_change`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ElectionMetrics<C: RaftTypeConfig> {
+    /// The number of elections this node started as a candidate.
+    pub elections_started: u64,
+
+    /// The number of elections in which this node is granted by a quorum and becomes the leader.
+    pub elections_won: u64,
+
+    /// The number of elections that ended without this node becoming the leader, e.g., a vote is
+    /// rejected, or another candidate wins first.
+    ///
+    /// An election that is still in progress is neither won nor lost.
+    pub elections_lost: u64,
+
+    /// The number of vote requests this node rejected because the lease of the current leader has
+    /// not yet expired.
+    ///
+    /// A growing count indicates that some node keeps trying to elect itself while the leader is
+    /// alive.
+    pub vote_requests_rejected_by_lease: u64,
+
+    /// The last time the leader seen by this node changed, or `None` if it never changed since
+    /// this node is started.
+    pub last_leader_change: Option<LeaderChange<C>>,
+}
+
+impl<C> fmt::Display for ElectionMetrics<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{started:{}, won:{}, lost:{}, rejected_by_lease:{}, last_leader_change:{}}}",
+            self.elections_started,
+            self.elections_won,
+            self.elections_lost,
+            self.vote_requests_rejected_by_lease,
+            DisplayOption(&self.last_leader_change),
+        )
+    }
+}
+
+/// A change of the leader seen by a node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct LeaderChange<C: RaftTypeConfig> {
+    /// The new leader, or `None` if there is no known leader.
+    pub leader: Option<C::NodeId>,
+
+    /// The term of the vote when the change is seen.
+    pub term: C::Term,
+
+    /// When the change is seen.
+    pub at: SerdeInstantOf<C>,
+}
+
+impl<C> fmt::Display for LeaderChange<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{leader:{}, term:{}, at:{}}}",
+            DisplayOption(&self.leader),
+            self.term,
+            self.at
+        )
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::type_config::alias::SerdeInstantOf;
+use crate::RaftTypeConfig;
+
+/// The last acknowledged heartbeat or replication to a target, as seen by the leader.
+///
+/// Openraft does not exchange wall clock time between nodes, so the offset of the target's clock
+/// can not be measured. What matters for a lease based read is when the target accepted the
+/// leadership, which happened at some point within the round trip. The leader conservatively uses
+/// [`Self::sent_at`], the sending time, and [`Self::max_clock_skew`] bounds how far the target's
+/// view of that instant can be off, assuming symmetric network delay.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct HeartbeatAck<C: RaftTypeConfig> {
+    /// The sending time of the last acknowledged request.
+    ///
+    /// It is the same as the value in [`RaftMetrics::heartbeat`].
+    ///
+    /// [`RaftMetrics::heartbeat`]: crate::RaftMetrics::heartbeat
+    pub sent_at: SerdeInstantOf<C>,
+
+    /// The round trip time of the last acknowledged request.
+    pub rtt: Duration,
+
+    /// The estimated bound of the clock skew between the leader and the target when the request
+    /// is accepted, i.e., half of [`Self::rtt`].
+    pub max_clock_skew: Duration,
+}
+
+impl<C> HeartbeatAck<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(sent_at: SerdeInstantOf<C>, rtt: Duration) -> Self {
+        Self {
+            sent_at,
+            rtt,
+            max_clock_skew: rtt / 2,
+        }
+    }
+}
+
+impl<C> fmt::Display for HeartbeatAck<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{sent_at:{}, rtt:{:?}, max_clock_skew:{:?}}}",
+            self.sent_at, self.rtt, self.max_clock_skew
+        )
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::RPCTypes;
+
+/// Counters of the storage and network IO issued by Openraft since this node is started.
+///
+/// They are counted by Openraft itself, thus they do not depend on what the storage or network
+/// implementation reports. Every counter is monotonic.
+///
+/// Openraft does not serialize log entries and snapshots, thus the byte counters rely on the sizes
+/// the application tells: [`RaftEntry::size_bytes()`] for log entries, and for snapshots sent or
+/// received by an application defined transport, [`RPCOption::add_snapshot_bytes_sent()`] and
+/// [`Raft::add_snapshot_bytes_received()`]. The chunks of the snapshot transport of Openraft are
+/// counted by itself.
+///
+/// [`RaftEntry::size_bytes()`]: crate::entry::RaftEntry::size_bytes
+/// [`RPCOption::add_snapshot_bytes_sent()`]: crate::network::RPCOption::add_snapshot_bytes_sent
+/// [`Raft::add_snapshot_bytes_received()`]: crate::Raft::add_snapshot_bytes_received
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct IoMetrics {
+    /// The number of log entries submitted to the log store to append.
+    pub log_entries_appended: u64,
+
+    /// The number of bytes of log entries submitted to the log store to append, by
+    /// [`RaftEntry::size_bytes()`](crate::entry::RaftEntry::size_bytes).
+    pub log_bytes_appended: u64,
+
+    /// The number of log entries the log store has purged.
+    pub log_entries_purged: u64,
+
+    /// The number of snapshots sent to other nodes successfully.
+    pub snapshots_sent: u64,
+
+    /// The number of bytes of snapshot data sent to other nodes.
+    pub snapshot_bytes_sent: u64,
+
+    /// The number of snapshots received from the leader.
+    pub snapshots_received: u64,
+
+    /// The number of bytes of snapshot data received from the leader.
+    pub snapshot_bytes_received: u64,
+
+    /// The number of RPCs sent, by RPC type and result.
+    pub rpcs: BTreeMap<RPCTypes, RpcCounts>,
+}
+
+impl fmt::Display for IoMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{log_entries_appended:{}, log_bytes_appended:{}, log_entries_purged:{}, snapshots_sent:{}, snapshot_bytes_sent:{}, snapshots_received:{}, snapshot_bytes_received:{}, rpcs:{{",
+            self.log_entries_appended,
+            self.log_bytes_appended,
+            self.log_entries_purged,
+            self.snapshots_sent,
+            self.snapshot_bytes_sent,
+            self.snapshots_received,
+            self.snapshot_bytes_received,
+        )?;
+
+        for (i, (rpc_type, counts)) in self.rpcs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}:{}", rpc_type, counts)?;
+        }
+
+        write!(f, "}}}}")
+    }
+}
+
+/// The number of RPCs of a type, by result.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RpcCounts {
+    /// The RPCs that receive a response.
+    pub ok: u64,
+
+    /// The RPCs that return an error, e.g., the target is unreachable.
+    pub error: u64,
+
+    /// The RPCs that do not finish in time.
+    pub timeout: u64,
+}
+
+impl RpcCounts {
+    /// The total number of RPCs sent.
+    pub fn total(&self) -> u64 {
+        self.ok + self.error + self.timeout
+    }
+}
+
+impl fmt::Display for RpcCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{ok:{}, error:{}, timeout:{}}}", self.ok, self.error, self.timeout)
+    }
+}
@@ -23,12 +23,29 @@
 //! Metrics can be used as a trigger of application events, as a monitoring data
 //! source, etc.
 //!
+//! Subsets of the metrics are also sent on their own channels, so that a subscriber is only
+//! notified by the changes it is interested in: [`RaftServerMetrics`], [`RaftDataMetrics`],
+//! [`RaftReplicationMetrics`] and [`RaftNetworkMetrics`].
+//!
 //! Metrics is not a stream thus it only guarantees to provide the latest state but
 //! not every change of the state.
 //! Because internally, `watch::channel()` only stores one last state.
 
+mod core_loop_metrics;
+mod election_metrics;
+mod heartbeat_ack;
+mod io_metrics;
 mod metric;
+mod network_metrics;
+#[cfg(feature = "otel")]
+mod otel_metrics;
 mod raft_metrics;
+mod raft_replication_metrics;
+mod raft_status;
+mod replication_inflight;
+mod replication_lag;
+mod server_state_change;
+mod slow_op_metrics;
 mod wait;
 
 mod metric_display;
@@ -39,11 +56,31 @@ mod wait_test;
 
 use std::collections::BTreeMap;
 
+pub use core_loop_metrics::CoreLoopMetrics;
+pub(crate) use core_loop_metrics::CoreLoopStats;
+pub use election_metrics::ElectionMetrics;
+pub use election_metrics::LeaderChange;
+pub use heartbeat_ack::HeartbeatAck;
+pub use io_metrics::IoMetrics;
+pub use io_metrics::RpcCounts;
 pub use metric::Metric;
+pub use network_metrics::LatencyHistogram;
+pub use network_metrics::RaftNetworkMetrics;
+#[cfg(feature = "otel")]
+pub use otel_metrics::export_otel_metrics;
 pub use raft_metrics::RaftDataMetrics;
 pub use raft_metrics::RaftMetrics;
 pub use raft_metrics::RaftServerMetrics;
+pub use raft_replication_metrics::RaftReplicationMetrics;
+pub use raft_status::RaftStatus;
+pub use raft_status::ReplicationStatus;
+pub use replication_inflight::ReplicationInflight;
+pub(crate) use replication_lag::ApplyRate;
+pub use replication_lag::ReplicationLag;
 pub use serde_instant::SerdeInstant;
+pub use server_state_change::ServerStateChange;
+pub(crate) use slow_op_metrics::SlowOp;
+pub use slow_op_metrics::SlowOpMetrics;
 pub use wait::Wait;
 pub use wait::WaitError;
 pub(crate) use wait_condition::Condition;
@@ -53,6 +90,15 @@ use crate::type_config::alias::NodeIdOf;
 use crate::type_config::alias::SerdeInstantOf;
 
 pub(crate) type ReplicationMetrics<C> = BTreeMap<NodeIdOf<C>, Option<LogIdOf<C>>>;
+/// Replication lag metrics, a mapping between a node's ID and how far the replication to it lags
+/// behind the leader.
+pub(crate) type ReplicationLagMetrics<C> = BTreeMap<NodeIdOf<C>, ReplicationLag>;
+/// Replication inflight metrics, a mapping between a node's ID and the data being sent to it, or
+/// `None` if nothing is in flight.
+pub(crate) type ReplicationInflightMetrics<C> = BTreeMap<NodeIdOf<C>, Option<ReplicationInflight<C>>>;
 /// Heartbeat metrics, a mapping between a node's ID and the time of the last
 /// acknowledged heartbeat or replication to this node.
 pub(crate) type HeartbeatMetrics<C> = BTreeMap<NodeIdOf<C>, Option<SerdeInstantOf<C>>>;
+/// Heartbeat ack metrics, a mapping between a node's ID and the time and round trip time of the
+/// last acknowledged heartbeat or replication to this node.
+pub(crate) type HeartbeatAckMetrics<C> = BTreeMap<NodeIdOf<C>, HeartbeatAck<C>>;
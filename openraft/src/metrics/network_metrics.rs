@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::RPCTypes;
+use crate::RaftTypeConfig;
+
+/// Metrics of the RPCs sent by this node.
+///
+/// It is sent through [`Raft::network_metrics()`](crate::Raft::network_metrics).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct RaftNetworkMetrics<C: RaftTypeConfig> {
+    /// The latency histograms of the RPCs sent by replication, by target node and RPC type.
+    ///
+    /// Only the RPCs that receive a response are recorded; a timed out or failed RPC is not.
+    /// The histograms are cumulative since this node is started.
+    pub rpc_latency: BTreeMap<C::NodeId, BTreeMap<RPCTypes, LatencyHistogram>>,
+}
+
+impl<C> RaftNetworkMetrics<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn record(&mut self, target: C::NodeId, rpc_type: RPCTypes, latency: Duration, bounds: &[u64]) {
+        self.rpc_latency
+            .entry(target)
+            .or_default()
+            .entry(rpc_type)
+            .or_insert_with(|| LatencyHistogram::new(bounds))
+            .record(latency);
+    }
+}
+
+impl<C> fmt::Display for RaftNetworkMetrics<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NetworkMetrics{{")?;
+
+        for (i, (target, histograms)) in self.rpc_latency.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}:{{", target)?;
+            for (j, (rpc_type, h)) in histograms.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}:{}", rpc_type, h)?;
+            }
+            write!(f, "}}")?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+/// A histogram of RPC latencies, with the bucket bounds set by
+/// [`Config::rpc_latency_buckets`](crate::Config::rpc_latency_buckets).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LatencyHistogram {
+    /// The upper bounds of the buckets in milliseconds, inclusive, in ascending order.
+    pub bounds: Vec<u64>,
+
+    /// The number of RPCs in every bucket.
+    ///
+    /// It has one more element than `bounds`: the last one counts the latencies greater than the
+    /// last bound.
+    pub counts: Vec<u64>,
+
+    /// The total number of recorded RPCs.
+    pub total: u64,
+
+    /// The sum of the recorded latencies in microseconds.
+    pub sum_micros: u64,
+}
+
+impl LatencyHistogram {
+    pub(crate) fn new(bounds: &[u64]) -> Self {
+        Self {
+            bounds: bounds.to_vec(),
+            counts: vec![0; bounds.len() + 1],
+            total: 0,
+            sum_micros: 0,
+        }
+    }
+
+    pub(crate) fn record(&mut self, latency: Duration) {
+        let millis = latency.as_millis() as u64;
+        let i = self.bounds.partition_point(|b| *b < millis);
+
+        self.counts[i] += 1;
+        self.total += 1;
+        self.sum_micros += latency.as_micros() as u64;
+    }
+
+    /// The mean latency, or `None` if nothing is recorded.
+    pub fn mean(&self) -> Option<Duration> {
+        if self.total == 0 {
+            return None;
+        }
+        Some(Duration::from_micros(self.sum_micros / self.total))
+    }
+
+    /// The upper bound in milliseconds of the bucket that contains the `q`-quantile, e.g., `0.99`
+    /// for p99.
+    ///
+    /// It returns `None` if nothing is recorded, or the quantile is in the overflow bucket.
+    pub fn quantile_bound(&self, q: f64) -> Option<u64> {
+        if self.total == 0 {
+            return None;
+        }
+
+        let rank = ((self.total as f64 * q).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (i, c) in self.counts.iter().enumerate() {
+            seen += c;
+            if seen >= rank {
+                return self.bounds.get(i).copied();
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for LatencyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{total:{}, mean:{:?}, buckets:[", self.total, self.mean())?;
+
+        for (i, c) in self.counts.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match self.bounds.get(i) {
+                Some(b) => write!(f, "<={}ms:{}", b, c)?,
+                None => write!(f, "+Inf:{}", c)?,
+            }
+        }
+
+        write!(f, "]}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::metrics::LatencyHistogram;
+
+    #[test]
+    fn test_latency_histogram() {
+        let mut h = LatencyHistogram::new(&[1, 10, 100]);
+
+        assert_eq!(None, h.mean());
+        assert_eq!(None, h.quantile_bound(0.5));
+
+        h.record(Duration::from_micros(500));
+        h.record(Duration::from_millis(1));
+        h.record(Duration::from_millis(5));
+        h.record(Duration::from_millis(50));
+        h.record(Duration::from_millis(500));
+
+        assert_eq!(vec![2, 1, 1, 1], h.counts);
+        assert_eq!(5, h.total);
+        assert_eq!(Some(Duration::from_micros(111_300)), h.mean());
+
+        assert_eq!(Some(1), h.quantile_bound(0.0));
+        assert_eq!(Some(1), h.quantile_bound(0.4));
+        assert_eq!(Some(10), h.quantile_bound(0.5));
+        assert_eq!(Some(100), h.quantile_bound(0.8));
+        assert_eq!(None, h.quantile_bound(0.99));
+
+        assert_eq!(
+            "{total:5, mean:Some(111.3ms), buckets:[<=1ms:2, <=10ms:1, <=100ms:1, +Inf:1]}",
+            h.to_string()
+        );
+    }
+}
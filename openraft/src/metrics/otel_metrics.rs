@@ -0,0 +1,177 @@
+use openraft_macros::since;
+use opentelemetry::metrics::Counter;
+use opentelemetry::metrics::Gauge;
+use opentelemetry::metrics::Meter;
+use opentelemetry::KeyValue;
+
+use crate::async_runtime::watch::WatchReceiver;
+use crate::log_id::LogIdOptionExt;
+use crate::metrics::IoMetrics;
+use crate::metrics::RaftDataMetrics;
+use crate::metrics::RaftMetrics;
+use crate::Raft;
+use crate::RaftTypeConfig;
+use crate::ServerState;
+
+/// Export the metrics of a Raft node with the OpenTelemetry metrics API, until the node shuts
+/// down.
+///
+/// Every time [`Raft::data_metrics()`] changes, the log indexes and whether this node is the
+/// leader are recorded to gauges, and the increments of [`IoMetrics`] are added to counters. Every
+/// instrument is named with an `openraft.` prefix and is recorded with a `node_id` attribute.
+///
+/// It runs until the metrics channel is closed, an application spawns it, e.g.:
+/// ```ignore
+/// tokio::spawn(export_otel_metrics(raft.clone(), opentelemetry::global::meter("openraft")));
+/// ```
+#[since(version = "0.10.0")]
+pub async fn export_otel_metrics<C>(raft: Raft<C>, meter: Meter)
+where C: RaftTypeConfig {
+    let instruments = Instruments::new(&meter);
+
+    let rx = raft.metrics();
+    let mut data_rx = raft.data_metrics();
+
+    let node_id = rx.borrow_watched().id.to_string();
+    let attrs = [KeyValue::new("node_id", node_id)];
+
+    let mut last_io = IoMetrics::default();
+
+    loop {
+        {
+            let metrics = rx.borrow_watched();
+            let data = data_rx.borrow_watched();
+
+            instruments.record(&metrics, &data, &attrs);
+            instruments.add_io(&last_io, &data.io, &attrs);
+            last_io = data.io.clone();
+        }
+
+        if data_rx.changed().await.is_err() {
+            tracing::info!("metrics channel is closed, stop exporting OpenTelemetry metrics");
+            return;
+        }
+    }
+}
+
+/// The OpenTelemetry instruments the metrics of a Raft node are exported to.
+struct Instruments {
+    last_log_index: Gauge<u64>,
+    last_applied_index: Gauge<u64>,
+    snapshot_index: Gauge<u64>,
+    purged_index: Gauge<u64>,
+    is_leader: Gauge<u64>,
+
+    log_entries_appended: Counter<u64>,
+    log_bytes_appended: Counter<u64>,
+    log_entries_purged: Counter<u64>,
+    snapshots_sent: Counter<u64>,
+    snapshot_bytes_sent: Counter<u64>,
+    snapshots_received: Counter<u64>,
+    snapshot_bytes_received: Counter<u64>,
+    rpcs: Counter<u64>,
+}
+
+impl Instruments {
+    fn new(meter: &Meter) -> Self {
+        let gauge =
+            |name: &'static str, description: &'static str| meter.u64_gauge(name).with_description(description).build();
+        let counter = |name: &'static str, description: &'static str| {
+            meter.u64_counter(name).with_description(description).build()
+        };
+
+        Self {
+            last_log_index: gauge("openraft.last_log_index", "The index of the last log entry"),
+            last_applied_index: gauge("openraft.last_applied_index", "The index of the last applied log entry"),
+            snapshot_index: gauge("openraft.snapshot_index", "The last log index included in the snapshot"),
+            purged_index: gauge("openraft.purged_index", "The index of the last purged log entry"),
+            is_leader: gauge("openraft.is_leader", "1 if this node is the leader, otherwise 0"),
+
+            log_entries_appended: counter("openraft.log_entries_appended", "Log entries submitted to append"),
+            log_bytes_appended: counter("openraft.log_bytes_appended", "Log bytes submitted to append"),
+            log_entries_purged: counter("openraft.log_entries_purged", "Log entries purged"),
+            snapshots_sent: counter("openraft.snapshots_sent", "Snapshots sent to other nodes"),
+            snapshot_bytes_sent: counter("openraft.snapshot_bytes_sent", "Snapshot bytes sent"),
+            snapshots_received: counter("openraft.snapshots_received", "Snapshots received from the leader"),
+            snapshot_bytes_received: counter("openraft.snapshot_bytes_received", "Snapshot bytes received"),
+            rpcs: counter("openraft.rpcs", "RPCs sent, by rpc_type and result"),
+        }
+    }
+
+    fn record<C>(&self, metrics: &RaftMetrics<C>, data: &RaftDataMetrics<C>, attrs: &[KeyValue])
+    where C: RaftTypeConfig {
+        if let Some(i) = data.last_log.index() {
+            self.last_log_index.record(i, attrs);
+        }
+        if let Some(i) = data.last_applied.index() {
+            self.last_applied_index.record(i, attrs);
+        }
+        if let Some(i) = data.snapshot.index() {
+            self.snapshot_index.record(i, attrs);
+        }
+        if let Some(i) = data.purged.index() {
+            self.purged_index.record(i, attrs);
+        }
+
+        let is_leader = metrics.state == ServerState::Leader;
+        self.is_leader.record(is_leader as u64, attrs);
+    }
+
+    /// Add the increments of the IO counters since `prev`.
+    fn add_io(&self, prev: &IoMetrics, curr: &IoMetrics, attrs: &[KeyValue]) {
+        let add = |counter: &Counter<u64>, prev: u64, curr: u64| {
+            if curr > prev {
+                counter.add(curr - prev, attrs);
+            }
+        };
+
+        add(
+            &self.log_entries_appended,
+            prev.log_entries_appended,
+            curr.log_entries_appended,
+        );
+        add(
+            &self.log_bytes_appended,
+            prev.log_bytes_appended,
+            curr.log_bytes_appended,
+        );
+        add(
+            &self.log_entries_purged,
+            prev.log_entries_purged,
+            curr.log_entries_purged,
+        );
+        add(&self.snapshots_sent, prev.snapshots_sent, curr.snapshots_sent);
+        add(
+            &self.snapshot_bytes_sent,
+            prev.snapshot_bytes_sent,
+            curr.snapshot_bytes_sent,
+        );
+        add(
+            &self.snapshots_received,
+            prev.snapshots_received,
+            curr.snapshots_received,
+        );
+        add(
+            &self.snapshot_bytes_received,
+            prev.snapshot_bytes_received,
+            curr.snapshot_bytes_received,
+        );
+
+        for (rpc_type, counts) in curr.rpcs.iter() {
+            let prev_counts = prev.rpcs.get(rpc_type).copied().unwrap_or_default();
+
+            for (result, prev, curr) in [
+                ("ok", prev_counts.ok, counts.ok),
+                ("error", prev_counts.error, counts.error),
+                ("timeout", prev_counts.timeout, counts.timeout),
+            ] {
+                if curr > prev {
+                    let mut rpc_attrs = attrs.to_vec();
+                    rpc_attrs.push(KeyValue::new("rpc_type", rpc_type.to_string()));
+                    rpc_attrs.push(KeyValue::new("result", result));
+                    self.rpcs.add(curr - prev, &rpc_attrs);
+                }
+            }
+        }
+    }
+}
@@ -5,13 +5,19 @@ use crate::core::ServerState;
 use crate::display_ext::DisplayBTreeMapOptValue;
 use crate::display_ext::DisplayOption;
 use crate::error::Fatal;
+use crate::metrics::CoreLoopMetrics;
+use crate::metrics::ElectionMetrics;
 use crate::metrics::HeartbeatMetrics;
+use crate::metrics::IoMetrics;
 use crate::metrics::ReplicationMetrics;
 use crate::metrics::SerdeInstant;
+use crate::metrics::SlowOpMetrics;
+use crate::storage::LogStats;
 use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::SerdeInstantOf;
 use crate::type_config::alias::VoteOf;
+use crate::Config;
 use crate::Instant;
 use crate::RaftTypeConfig;
 use crate::StoredMembership;
@@ -106,10 +112,17 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// higher possibility of that.
     pub heartbeat: Option<HeartbeatMetrics<C>>,
 
+    /// The config in use, including the updates by
+    /// [`Raft::update_config()`](crate::Raft::update_config).
+    pub config: Arc<Config>,
+
     // ---
     // --- replication ---
     // ---
     /// The replication states. It is Some() only when this node is leader.
+    ///
+    /// The lag, the inflight data and the heartbeat acknowledgements of every target are only
+    /// reported by [`Raft::replication_metrics()`](crate::Raft::replication_metrics).
     pub replication: Option<ReplicationMetrics<C>>,
 }
 
@@ -181,6 +194,7 @@ where C: RaftTypeConfig
             membership_config: Arc::new(StoredMembership::default()),
             replication: None,
             heartbeat: None,
+            config: Arc::new(Config::default()),
         }
     }
 }
@@ -192,8 +206,32 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     pub last_log: Option<LogIdOf<C>>,
     pub last_applied: Option<LogIdOf<C>>,
     pub snapshot: Option<LogIdOf<C>>,
+
+    /// The last log id that the log store has finished purging, inclusive.
     pub purged: Option<LogIdOf<C>>,
 
+    /// The last log id included in the current snapshot, built or installed.
+    ///
+    /// It is updated as soon as the snapshot is accepted by `RaftCore`, and may be ahead of
+    /// [`Self::snapshot`], which is updated after the snapshot is persisted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub snapshot_last_log_id: Option<LogIdOf<C>>,
+
+    /// The log id that logs are scheduled to be purged up to, inclusive.
+    ///
+    /// Logs may not be purged at once because they are still in use by replication tasks. The
+    /// purge is submitted when [`Self::purge_submitted`] catches up with it, and is complete when
+    /// [`Self::purged`] does.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub purge_upto: Option<LogIdOf<C>>,
+
+    /// The last log id whose purge has been submitted to the log store, inclusive.
+    ///
+    /// It may be ahead of [`Self::purged`], which is updated after the log store finishes the
+    /// purge.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub purge_submitted: Option<LogIdOf<C>>,
+
     /// For a leader, it is the elapsed time in milliseconds since the most recently acknowledged
     /// timestamp by a quorum.
     ///
@@ -224,6 +262,7 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     /// cluster.
     pub last_quorum_acked: Option<SerdeInstant<InstantOf<C>>>,
 
+    #[deprecated(since = "0.10.0", note = "use `RaftReplicationMetrics::replication` instead.")]
     pub replication: Option<ReplicationMetrics<C>>,
 
     /// Heartbeat metrics. It is Some() only when this node is leader.
@@ -234,7 +273,27 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     /// This duration since the recorded time can be used by applications to
     /// guess if a follwer/learner node is offline, longer duration suggests
     /// higher possibility of that.
+    #[deprecated(since = "0.10.0", note = "use `RaftReplicationMetrics::heartbeat` instead.")]
     pub heartbeat: Option<HeartbeatMetrics<C>>,
+
+    /// Statistics of the log store, polled from [`RaftLogStorage::stats()`].
+    ///
+    /// It is `None` if the log store does not provide statistics, or they are not polled yet.
+    ///
+    /// [`RaftLogStorage::stats()`]: crate::storage::RaftLogStorage::stats
+    pub log_stats: Option<LogStats>,
+
+    /// Saturation metrics of the `RaftCore` loop, updated about once per second.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub core_loop: CoreLoopMetrics,
+
+    /// The number of slow operations, by kind, since this node is started.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub slow_ops: SlowOpMetrics,
+
+    /// The storage and network IO counters since this node is started.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub io: IoMetrics,
 }
 
 impl<C> fmt::Display for RaftDataMetrics<C>
@@ -245,11 +304,14 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            "last_log:{}, last_applied:{}, snapshot:{}, purged:{}",
+            "last_log:{}, last_applied:{}, snapshot:{}, purged:{}, snapshot_last_log_id:{}, purge_upto:{}, purge_submitted:{}",
             DisplayOption(&self.last_log),
             DisplayOption(&self.last_applied),
             DisplayOption(&self.snapshot),
             DisplayOption(&self.purged),
+            DisplayOption(&self.snapshot_last_log_id),
+            DisplayOption(&self.purge_upto),
+            DisplayOption(&self.purge_submitted),
         )?;
 
         if let Some(quorum_acked) = &self.last_quorum_acked {
@@ -265,9 +327,11 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            ", replication:{{{}}}, heartbeat:{{{}}}",
-            DisplayOption(&self.replication.as_ref().map(DisplayBTreeMapOptValue)),
-            DisplayOption(&self.heartbeat.as_ref().map(DisplayBTreeMapOptValue)),
+            ", log_stats:{}, core_loop:{}, slow_ops:{}, io:{}",
+            DisplayOption(&self.log_stats),
+            self.core_loop,
+            self.slow_ops,
+            self.io,
         )?;
 
         write!(f, "}}")?;
@@ -285,6 +349,17 @@ pub struct RaftServerMetrics<C: RaftTypeConfig> {
     pub current_leader: Option<C::NodeId>,
 
     pub membership_config: Arc<StoredMembership<C>>,
+
+    /// Whether automatic ticks are paused by [`RuntimeConfigHandle::pause_ticks()`]: a paused
+    /// node neither starts an election nor sends heartbeats on its own.
+    ///
+    /// [`RuntimeConfigHandle::pause_ticks()`]: crate::raft::RuntimeConfigHandle::pause_ticks
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ticks_paused: bool,
+
+    /// Statistics of the elections of this node, to measure how often the leadership changes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub election: ElectionMetrics<C>,
 }
 
 impl<C> fmt::Display for RaftServerMetrics<C>
@@ -295,12 +370,14 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            "id:{}, {:?}, vote:{}, leader:{}, membership:{}",
+            "id:{}, {:?}, vote:{}, leader:{}, membership:{}, ticks_paused:{}, election:{}",
             self.id,
             self.state,
             self.vote,
             DisplayOption(&self.current_leader),
             self.membership_config,
+            self.ticks_paused,
+            self.election,
         )?;
 
         write!(f, "}}")?;
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use crate::display_ext::DisplayBTreeMapOptValue;
+use crate::display_ext::DisplayOption;
+use crate::metrics::HeartbeatAckMetrics;
+use crate::metrics::HeartbeatMetrics;
+use crate::metrics::ReplicationInflightMetrics;
+use crate::metrics::ReplicationLagMetrics;
+use crate::metrics::ReplicationMetrics;
+use crate::RaftTypeConfig;
+
+/// The replication and heartbeat metrics of a leader.
+///
+/// These fields change with every replication and heartbeat response. They are sent on a
+/// separate channel, [`Raft::replication_metrics()`], which is only notified when any of them
+/// changes, so that a subscriber of them does not have to watch the other metrics, and vice versa
+/// a subscriber of [`Raft::server_metrics()`] or [`Raft::data_metrics()`] is not woken up by
+/// replication progress. They are flushed at most once per
+/// [`Config::replication_metrics_flush_interval`].
+///
+/// Every field is `None` if this node is not a leader.
+///
+/// [`Raft::replication_metrics()`]: crate::Raft::replication_metrics
+/// [`Raft::server_metrics()`]: crate::Raft::server_metrics
+/// [`Raft::data_metrics()`]: crate::Raft::data_metrics
+/// [`Config::replication_metrics_flush_interval`]: crate::Config::replication_metrics_flush_interval
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct RaftReplicationMetrics<C: RaftTypeConfig> {
+    /// The last matching log id of every target.
+    pub replication: Option<ReplicationMetrics<C>>,
+
+    /// How far the replication to every target lags behind the leader, in entries and in
+    /// estimated milliseconds.
+    ///
+    /// The time is estimated with the apply rate of the leader in the recent second, thus it is
+    /// `None` for a target until the rate is known.
+    pub replication_lag: Option<ReplicationLagMetrics<C>>,
+
+    /// The logs or snapshot being sent to every target.
+    pub replication_inflight: Option<ReplicationInflightMetrics<C>>,
+
+    /// The time of the last acknowledged heartbeat or replication to every target.
+    pub heartbeat: Option<HeartbeatMetrics<C>>,
+
+    /// The last acknowledged heartbeat or replication to every target, along with its round trip
+    /// time and the estimated clock skew bound.
+    ///
+    /// A target that has not yet acknowledged anything is absent.
+    pub heartbeat_ack: Option<HeartbeatAckMetrics<C>>,
+}
+
+impl<C> fmt::Display for RaftReplicationMetrics<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ReplicationMetrics{{replication:{{{}}}, inflight:{{{}}}, heartbeat:{{{}}}}}",
+            DisplayOption(&self.replication.as_ref().map(DisplayBTreeMapOptValue)),
+            DisplayOption(&self.replication_inflight.as_ref().map(DisplayBTreeMapOptValue)),
+            DisplayOption(&self.heartbeat.as_ref().map(DisplayBTreeMapOptValue)),
+        )
+    }
+}
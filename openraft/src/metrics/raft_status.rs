@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::display_ext::DisplayOption;
+use crate::metrics::RaftDataMetrics;
+use crate::metrics::RaftServerMetrics;
+use crate::metrics::ReplicationInflight;
+use crate::type_config::alias::LogIdOf;
+use crate::EffectiveConfig;
+use crate::RaftTypeConfig;
+use crate::StoredMembership;
+
+/// A consistent view of the status of a Raft node, returned by
+/// [`Raft::status()`](crate::Raft::status).
+///
+/// All of the fields are taken at the same time in `RaftCore`, unlike reading the metrics
+/// channels one by one. With the `serde` feature enabled it can be serialized, e.g., to serve an
+/// HTTP admin endpoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct RaftStatus<C: RaftTypeConfig> {
+    /// The server state, vote, leader and the effective membership.
+    pub server: RaftServerMetrics<C>,
+
+    /// The log, state machine and snapshot progress.
+    pub data: RaftDataMetrics<C>,
+
+    /// The last committed membership config.
+    ///
+    /// The effective one in [`RaftServerMetrics::membership_config`] may be not yet committed.
+    pub committed_membership: Arc<StoredMembership<C>>,
+
+    /// The replication progress to every target. It is Some() only when this node is leader.
+    pub replication: Option<BTreeMap<C::NodeId, ReplicationStatus<C>>>,
+
+    /// The configuration in use.
+    pub config: EffectiveConfig,
+}
+
+impl<C> fmt::Display for RaftStatus<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Status{{server:{}, data:{}, committed_membership:{}, replication:{{",
+            self.server, self.data, self.committed_membership
+        )?;
+
+        for (i, (target, r)) in self.replication.iter().flatten().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}:{}", target, r)?;
+        }
+
+        write!(f, "}}}}")
+    }
+}
+
+/// The replication progress from the leader to a target.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ReplicationStatus<C: RaftTypeConfig> {
+    /// The last log id known to match on the target.
+    pub matching: Option<LogIdOf<C>>,
+
+    /// One plus the max log index on the target that might match the leader log.
+    ///
+    /// It is greater than the next index of `matching` while the leader is still searching for
+    /// the last matching log on the target.
+    pub searching_end: u64,
+
+    /// The data being sent to the target, or `None` if nothing is in flight.
+    pub inflight: Option<ReplicationInflight<C>>,
+}
+
+impl<C> fmt::Display for ReplicationStatus<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{matching:{}, searching_end:{}, inflight:{}}}",
+            DisplayOption(&self.matching),
+            self.searching_end,
+            DisplayOption(&self.inflight)
+        )
+    }
+}
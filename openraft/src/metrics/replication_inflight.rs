@@ -0,0 +1,90 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::progress::Inflight;
+use crate::type_config::alias::LogIdOf;
+use crate::RaftTypeConfig;
+
+/// The data the leader is sending to a target and waiting for the response of.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum ReplicationInflight<C: RaftTypeConfig> {
+    /// Sending the logs in the range `(prev, last]`.
+    Logs {
+        /// The log id before the first log being sent, exclusive.
+        prev: Option<LogIdOf<C>>,
+
+        /// The last log id being sent, inclusive.
+        last: Option<LogIdOf<C>>,
+    },
+
+    /// Sending a snapshot that includes the logs upto `last_log_id`, inclusive.
+    Snapshot {
+        /// The last log id the snapshot includes, or `None` if the snapshot is empty.
+        last_log_id: Option<LogIdOf<C>>,
+    },
+}
+
+impl<C> ReplicationInflight<C>
+where C: RaftTypeConfig
+{
+    /// Return the metrics of an [`Inflight`], or `None` if nothing is in flight.
+    pub(crate) fn new(inflight: &Inflight<C>) -> Option<Self> {
+        match inflight {
+            Inflight::None => None,
+            Inflight::Logs { log_id_range } => Some(Self::Logs {
+                prev: log_id_range.prev.clone(),
+                last: log_id_range.last.clone(),
+            }),
+            Inflight::Snapshot { last_log_id } => Some(Self::Snapshot {
+                last_log_id: last_log_id.clone(),
+            }),
+        }
+    }
+}
+
+impl<C> fmt::Display for ReplicationInflight<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Logs { prev, last } => write!(f, "Logs:({}, {}]", prev.display(), last.display()),
+            Self::Snapshot { last_log_id } => write!(f, "Snapshot:{}", last_log_id.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::testing::log_id;
+    use crate::engine::testing::UTConfig;
+    use crate::metrics::ReplicationInflight;
+    use crate::progress::Inflight;
+
+    #[test]
+    fn test_replication_inflight_new() {
+        assert_eq!(None, ReplicationInflight::new(&Inflight::<UTConfig>::None));
+
+        let got = ReplicationInflight::new(&Inflight::<UTConfig>::logs(
+            Some(log_id(1, 1, 2)),
+            Some(log_id(1, 1, 5)),
+        ));
+        assert_eq!(
+            Some(ReplicationInflight::Logs {
+                prev: Some(log_id(1, 1, 2)),
+                last: Some(log_id(1, 1, 5)),
+            }),
+            got
+        );
+        assert_eq!("Logs:(T1-N1.2, T1-N1.5]", got.unwrap().to_string());
+
+        let got = ReplicationInflight::new(&Inflight::<UTConfig>::snapshot(Some(log_id(1, 1, 5))));
+        assert_eq!(
+            Some(ReplicationInflight::Snapshot {
+                last_log_id: Some(log_id(1, 1, 5)),
+            }),
+            got
+        );
+        assert_eq!("Snapshot:T1-N1.5", got.unwrap().to_string());
+    }
+}
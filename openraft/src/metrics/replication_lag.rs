@@ -0,0 +1,132 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::display_ext::DisplayOption;
+use crate::type_config::alias::InstantOf;
+use crate::Instant;
+use crate::RaftTypeConfig;
+
+/// How far the replication to a target node lags behind the leader.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ReplicationLag {
+    /// The number of log entries on the leader that are not yet replicated to the target.
+    pub entries_behind: u64,
+
+    /// The estimated time in milliseconds for the target to catch up, based on the recent apply
+    /// rate of the leader.
+    ///
+    /// It is `None` if the apply rate is not yet known, or nothing is applied recently while the
+    /// target is behind.
+    pub millis_behind: Option<u64>,
+}
+
+impl fmt::Display for ReplicationLag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} entries, {}ms",
+            self.entries_behind,
+            DisplayOption(&self.millis_behind)
+        )
+    }
+}
+
+/// Estimates the recent apply rate, in entries per second, from samples of the last applied
+/// index.
+///
+/// The rate is updated once every [`Self::WINDOW`].
+pub(crate) struct ApplyRate<C>
+where C: RaftTypeConfig
+{
+    /// The time and the last applied index when the current window started.
+    base: Option<(InstantOf<C>, u64)>,
+
+    /// The apply rate of the last complete window.
+    per_sec: Option<f64>,
+}
+
+impl<C> Default for ApplyRate<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            base: None,
+            per_sec: None,
+        }
+    }
+}
+
+impl<C> ApplyRate<C>
+where C: RaftTypeConfig
+{
+    const WINDOW: Duration = Duration::from_secs(1);
+
+    pub(crate) fn update(&mut self, now: InstantOf<C>, applied_index: u64) {
+        let Some((base_time, base_index)) = self.base else {
+            self.base = Some((now, applied_index));
+            return;
+        };
+
+        let elapsed = now.saturating_duration_since(base_time);
+        if elapsed < Self::WINDOW {
+            return;
+        }
+
+        let applied = applied_index.saturating_sub(base_index);
+        self.per_sec = Some(applied as f64 / elapsed.as_secs_f64());
+        self.base = Some((now, applied_index));
+    }
+
+    pub(crate) fn lag(&self, entries_behind: u64) -> ReplicationLag {
+        let millis_behind = if entries_behind == 0 {
+            Some(0)
+        } else {
+            match self.per_sec {
+                Some(r) if r > 0.0 => Some((entries_behind as f64 / r * 1000.0) as u64),
+                _ => None,
+            }
+        };
+
+        ReplicationLag {
+            entries_behind,
+            millis_behind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::testing::UTConfig;
+    use crate::metrics::replication_lag::ApplyRate;
+    use crate::metrics::ReplicationLag;
+    use crate::type_config::TypeConfigExt;
+
+    #[test]
+    fn test_apply_rate() {
+        let mut rate = ApplyRate::<UTConfig>::default();
+
+        assert_eq!(
+            ReplicationLag {
+                entries_behind: 0,
+                millis_behind: Some(0)
+            },
+            rate.lag(0)
+        );
+
+        let now = UTConfig::<()>::now();
+        rate.update(now, 10);
+        assert_eq!(None, rate.lag(5).millis_behind, "rate is unknown");
+
+        rate.update(now + Duration::from_millis(500), 100);
+        assert_eq!(None, rate.lag(5).millis_behind, "window is not complete");
+
+        rate.update(now + Duration::from_secs(2), 210);
+        assert_eq!(Some(50), rate.lag(5).millis_behind, "100 entries per second");
+
+        rate.update(now + Duration::from_secs(4), 210);
+        assert_eq!(None, rate.lag(5).millis_behind, "nothing applied");
+    }
+}
@@ -85,7 +85,7 @@ mod serde_impl {
         where S: Serializer {
             // Convert Instant to SystemTime
             let system_time = {
-                let sys_now = SystemTime::now();
+                let sys_now = I::system_now();
                 let now = I::now();
 
                 if now >= self.inner {
@@ -126,7 +126,7 @@ mod serde_impl {
                     let system_time: SystemTime = datetime.with_timezone(&Utc).into();
 
                     // Calculate the `Instant` from the current time
-                    let sys_now = SystemTime::now();
+                    let sys_now = II::system_now();
                     let now = II::now();
                     let instant = if system_time > sys_now {
                         now + (system_time.duration_since(sys_now).unwrap())
@@ -0,0 +1,43 @@
+use std::fmt;
+
+use crate::core::ServerState;
+use crate::type_config::alias::VoteOf;
+use crate::RaftTypeConfig;
+
+/// The server state of a Raft node along with the vote it is in.
+///
+/// It is sent through [`Raft::server_state_watch()`](crate::Raft::server_state_watch) when the
+/// server state or the vote changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ServerStateChange<C: RaftTypeConfig> {
+    /// The server state, such as `Leader` or `Follower`.
+    pub state: ServerState,
+
+    /// The vote of this node when it enters `state`.
+    ///
+    /// For a leader, it identifies the term in which it is the leader: the leader-only work
+    /// started for one vote has to be stopped when the vote changes.
+    pub vote: VoteOf<C>,
+}
+
+impl<C> ServerStateChange<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(state: ServerState, vote: VoteOf<C>) -> Self {
+        Self { state, vote }
+    }
+
+    /// Return `true` if this node is the leader.
+    pub fn is_leader(&self) -> bool {
+        self.state == ServerState::Leader
+    }
+}
+
+impl<C> fmt::Display for ServerStateChange<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} at vote:{}", self.state, self.vote)
+    }
+}
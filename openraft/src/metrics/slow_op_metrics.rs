@@ -0,0 +1,112 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::Config;
+
+/// The number of slow operations since this node is started.
+///
+/// An operation is slow if it takes longer than the threshold for its kind in [`Config`], e.g.,
+/// [`Config::slow_append_threshold`]. Every slow operation is also logged as a warning along with
+/// the elapsed time and the item involved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SlowOpMetrics {
+    /// Slow appends of log entries to the log store.
+    pub appends: u64,
+
+    /// Slow applies of log entries to the state machine.
+    pub applies: u64,
+
+    /// Slow snapshot builds.
+    pub snapshot_builds: u64,
+
+    /// Slow replication RPCs.
+    pub rpcs: u64,
+}
+
+impl SlowOpMetrics {
+    pub(crate) fn incr(&mut self, op: SlowOp) {
+        let cnt = match op {
+            SlowOp::Append => &mut self.appends,
+            SlowOp::Apply => &mut self.applies,
+            SlowOp::BuildSnapshot => &mut self.snapshot_builds,
+            SlowOp::Rpc => &mut self.rpcs,
+        };
+        *cnt += 1;
+    }
+}
+
+impl fmt::Display for SlowOpMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{appends:{}, applies:{}, snapshot_builds:{}, rpcs:{}}}",
+            self.appends, self.applies, self.snapshot_builds, self.rpcs
+        )
+    }
+}
+
+/// The kinds of operations that are checked against a slow threshold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SlowOp {
+    Append,
+    Apply,
+    BuildSnapshot,
+    Rpc,
+}
+
+impl SlowOp {
+    /// The threshold for this kind of operation, or `None` if it is disabled.
+    pub(crate) fn threshold(&self, config: &Config) -> Option<Duration> {
+        let millis = match self {
+            SlowOp::Append => config.slow_append_threshold,
+            SlowOp::Apply => config.slow_apply_threshold,
+            SlowOp::BuildSnapshot => config.slow_snapshot_build_threshold,
+            SlowOp::Rpc => config.slow_rpc_threshold,
+        };
+
+        if millis == 0 {
+            None
+        } else {
+            Some(Duration::from_millis(millis))
+        }
+    }
+}
+
+impl fmt::Display for SlowOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlowOp::Append => write!(f, "append"),
+            SlowOp::Apply => write!(f, "apply"),
+            SlowOp::BuildSnapshot => write!(f, "build-snapshot"),
+            SlowOp::Rpc => write!(f, "rpc"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::metrics::slow_op_metrics::SlowOp;
+    use crate::metrics::SlowOpMetrics;
+    use crate::Config;
+
+    #[test]
+    fn test_slow_op_threshold() {
+        let config = Config {
+            slow_append_threshold: 0,
+            slow_rpc_threshold: 20,
+            ..Default::default()
+        };
+
+        assert_eq!(None, SlowOp::Append.threshold(&config));
+        assert_eq!(Some(Duration::from_millis(20)), SlowOp::Rpc.threshold(&config));
+
+        let mut m = SlowOpMetrics::default();
+        m.incr(SlowOp::Apply);
+        m.incr(SlowOp::Rpc);
+        m.incr(SlowOp::Rpc);
+        assert_eq!("{appends:0, applies:1, snapshot_builds:0, rpcs:2}", m.to_string());
+    }
+}
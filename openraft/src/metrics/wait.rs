@@ -2,12 +2,15 @@ use core::time::Duration;
 use std::collections::BTreeSet;
 
 use futures::FutureExt;
+use openraft_macros::since;
 
 use crate::async_runtime::watch::WatchReceiver;
 use crate::core::ServerState;
+use crate::log_id::LogIdOptionExt;
 use crate::metrics::Condition;
 use crate::metrics::Metric;
 use crate::metrics::RaftMetrics;
+use crate::quorum::QuorumSet;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
 use crate::type_config::alias::WatchReceiverOf;
@@ -159,6 +162,30 @@ where C: RaftTypeConfig
         self.ge(Metric::AppliedIndex(index), msg).await
     }
 
+    /// Block until the leader knows a quorum has accepted the log up to `index`(inclusive), or
+    /// timeout.
+    ///
+    /// Unlike [`applied_index_at_least()`](Self::applied_index_at_least), which only tells the
+    /// local state machine, it resolves when the entries are durable on a quorum of the voters in
+    /// the membership config, according to the matching log ids in `replication` reported by the
+    /// leader. Thus it only resolves on a leader.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "trace", skip(self), fields(msg=msg.to_string().as_str()))]
+    pub async fn applied_on_quorum(&self, index: Option<u64>, msg: impl ToString) -> Result<RaftMetrics<C>, WaitError> {
+        self.metrics(
+            |m| {
+                let Some(replication) = &m.replication else {
+                    return false;
+                };
+
+                let acked = replication.iter().filter(|(_, matching)| matching.index() >= index).map(|(id, _)| id);
+                m.membership_config.membership().to_quorum_set().is_quorum(acked)
+            },
+            &format!("{} .applied_on_quorum >= {:?}", msg.to_string(), index),
+        )
+        .await
+    }
+
     /// Wait for `state` to become `want_state` or timeout.
     #[tracing::instrument(level = "trace", skip(self), fields(msg=msg.to_string().as_str()))]
     pub async fn state(&self, want_state: ServerState, msg: impl ToString) -> Result<RaftMetrics<C>, WaitError> {
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use maplit::btreemap;
 use maplit::btreeset;
 use tokio::time::sleep;
 
@@ -13,6 +14,7 @@ use crate::metrics::WaitError;
 use crate::type_config::alias::NodeIdOf;
 use crate::type_config::alias::WatchSenderOf;
 use crate::type_config::TypeConfigExt;
+use crate::Config;
 use crate::Membership;
 use crate::RaftMetrics;
 use crate::RaftTypeConfig;
@@ -240,6 +242,42 @@ async fn test_wait_purged() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_wait_applied_on_quorum() -> anyhow::Result<()> {
+    let (init, w, tx) = init_wait_test::<UTConfig>();
+
+    let mut update = init.clone();
+    update.membership_config = Arc::new(StoredMembership::new(
+        None,
+        Membership::new_with_defaults(vec![btreeset! {1,2,3}], []),
+    ));
+    update.replication = Some(btreemap! {
+        1 => Some(log_id(1, 1, 5)),
+        2 => Some(log_id(1, 1, 3)),
+        3 => None,
+        4 => Some(log_id(1, 1, 5)),
+    });
+    tx.send(update.clone())?;
+
+    let got = w.applied_on_quorum(Some(3), "quorum acked 3").await?;
+    assert_eq!(update.replication, got.replication);
+
+    // Learner 4 does not count
+    let res = w.applied_on_quorum(Some(4), "quorum acked 4").await;
+    assert!(res.is_err());
+
+    let h = tokio::spawn(async move {
+        sleep(Duration::from_millis(10)).await;
+        update.replication.as_mut().unwrap().insert(3, Some(log_id(1, 1, 4)));
+        let rst = tx.send(update);
+        assert!(rst.is_ok());
+    });
+    w.applied_on_quorum(Some(4), "quorum acked 4").await?;
+    h.await?;
+
+    Ok(())
+}
+
 pub(crate) type InitResult<C> = (RaftMetrics<C>, Wait<C>, WatchSenderOf<C, RaftMetrics<C>>);
 
 /// Build a initial state for testing of Wait:
@@ -262,6 +300,7 @@ where C: RaftTypeConfig {
         last_quorum_acked: None,
         membership_config: Arc::new(StoredMembership::new(None, Membership::default())),
         heartbeat: None,
+        config: Arc::new(Config::default()),
 
         snapshot: None,
         replication: None,
@@ -0,0 +1,264 @@
+use std::fmt;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyerror::AnyError;
+use openraft_macros::add_async_trait;
+
+use crate::async_runtime::OneshotSender;
+use crate::error::RPCError;
+use crate::error::Unreachable;
+use crate::network::RPCOption;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+use crate::type_config::alias::OneshotSenderOf;
+use crate::type_config::TypeConfigExt;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::Raft;
+use crate::RaftTypeConfig;
+
+/// The heartbeats of multiple groups, sent from one process to another in a single RPC.
+///
+/// Every heartbeat is an AppendEntries request without entries, to the node of group `G` in the
+/// target process.
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(serialize = "G: serde::Serialize")),
+    serde(bound(deserialize = "G: for <'d> serde::Deserialize<'d>"))
+)]
+pub struct HeartbeatBatch<C, G>
+where C: RaftTypeConfig
+{
+    pub heartbeats: Vec<(G, AppendEntriesRequest<C>)>,
+}
+
+impl<C, G> HeartbeatBatch<C, G>
+where
+    C: RaftTypeConfig,
+    G: fmt::Display,
+{
+    /// Deliver every heartbeat to the local [`Raft`] of its group, returned by `get_raft`, and
+    /// collect the responses in the same order.
+    ///
+    /// A heartbeat to a group that is not found, or that is rejected by a fatal error, gets an
+    /// error response, which the sender takes as the node being unreachable.
+    pub async fn handle<F>(self, get_raft: F) -> HeartbeatBatchResponse<C>
+    where F: Fn(&G) -> Option<Raft<C>> {
+        let responses = self.heartbeats.into_iter().map(|(group, rpc)| {
+            let raft = get_raft(&group);
+            async move {
+                let Some(raft) = raft else {
+                    return Err(AnyError::error(format!("group {} is not found", group)));
+                };
+                raft.append_entries(rpc)
+                    .await
+                    .map_err(|e| AnyError::new(&e).add_context(|| format!("group {}", group)))
+            }
+        });
+
+        HeartbeatBatchResponse {
+            responses: futures::future::join_all(responses).await,
+        }
+    }
+}
+
+/// The responses to a [`HeartbeatBatch`], in the same order as the heartbeats.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound = ""))]
+pub struct HeartbeatBatchResponse<C>
+where C: RaftTypeConfig
+{
+    pub responses: Vec<Result<AppendEntriesResponse<C>, AnyError>>,
+}
+
+/// Sends a [`HeartbeatBatch`] to a peer process.
+///
+/// The peer process handles the batch with [`HeartbeatBatch::handle()`].
+#[add_async_trait]
+pub trait HeartbeatTransport<C, G>: OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+    async fn send_heartbeats(
+        &self,
+        batch: HeartbeatBatch<C, G>,
+        option: RPCOption,
+    ) -> Result<HeartbeatBatchResponse<C>, RPCError<C>>;
+}
+
+struct PendingHeartbeat<C, G>
+where C: RaftTypeConfig
+{
+    group: G,
+    rpc: AppendEntriesRequest<C>,
+    option: RPCOption,
+    tx: OneshotSenderOf<C, Result<AppendEntriesResponse<C>, RPCError<C>>>,
+}
+
+struct CoalescerInner<C, G, T>
+where C: RaftTypeConfig
+{
+    transport: T,
+    window: Duration,
+    pending: Mutex<Vec<PendingHeartbeat<C, G>>>,
+    sent_batches: AtomicU64,
+    sent_heartbeats: AtomicU64,
+}
+
+/// Coalesces the heartbeats that the groups in this process send to the same peer process.
+///
+/// There is one coalescer for every peer process, shared by all the groups. The first heartbeat
+/// queued starts a window of `window`, and every heartbeat queued in the window is sent in the
+/// same [`HeartbeatBatch`], then the responses are sent back to every sender. Thus an idle
+/// process that leads `N` groups sends one RPC to a peer every heartbeat interval, instead of
+/// `N`.
+///
+/// A heartbeat is delayed by at most `window`, which should be much smaller than
+/// [`Config::heartbeat_interval`]. The leader lease is not affected: it is counted from the time
+/// the heartbeat is queued.
+///
+/// A [`RaftNetworkV2`] implementation of a group sends every AppendEntries request without
+/// entries, i.e., a heartbeat or a committed log id update, with [`Self::send()`], see the
+/// [module docs](crate::multi_raft).
+///
+/// [`RaftNetworkV2`]: crate::network::v2::RaftNetworkV2
+/// [`Config::heartbeat_interval`]: crate::Config::heartbeat_interval
+pub struct HeartbeatCoalescer<C, G, T>
+where C: RaftTypeConfig
+{
+    inner: Arc<CoalescerInner<C, G, T>>,
+}
+
+impl<C, G, T> Clone for HeartbeatCoalescer<C, G, T>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<C, G, T> HeartbeatCoalescer<C, G, T>
+where
+    C: RaftTypeConfig,
+    G: OptionalSend + 'static,
+    T: HeartbeatTransport<C, G>,
+{
+    pub fn new(transport: T, window: Duration) -> Self {
+        Self {
+            inner: Arc::new(CoalescerInner {
+                transport,
+                window,
+                pending: Mutex::new(Vec::new()),
+                sent_batches: AtomicU64::new(0),
+                sent_heartbeats: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Queue a heartbeat of `group` and wait for its response.
+    pub async fn send(
+        &self,
+        group: G,
+        rpc: AppendEntriesRequest<C>,
+        option: RPCOption,
+    ) -> Result<AppendEntriesResponse<C>, RPCError<C>> {
+        debug_assert!(rpc.entries.is_empty(), "only a heartbeat can be coalesced");
+
+        let (tx, rx) = C::oneshot();
+
+        let is_first = {
+            let mut pending = self.inner.pending.lock().unwrap();
+            pending.push(PendingHeartbeat { group, rpc, option, tx });
+            pending.len() == 1
+        };
+
+        if is_first {
+            let inner = self.inner.clone();
+            drop(C::spawn(async move {
+                C::sleep(inner.window).await;
+                inner.flush().await;
+            }));
+        }
+
+        match rx.await {
+            Ok(res) => res,
+            Err(_) => Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+                "heartbeat batch is dropped",
+            )))),
+        }
+    }
+
+    /// The number of batch RPCs sent.
+    pub fn sent_batches(&self) -> u64 {
+        self.inner.sent_batches.load(Ordering::Relaxed)
+    }
+
+    /// The number of heartbeats sent in all batches.
+    pub fn sent_heartbeats(&self) -> u64 {
+        self.inner.sent_heartbeats.load(Ordering::Relaxed)
+    }
+}
+
+impl<C, G, T> CoalescerInner<C, G, T>
+where
+    C: RaftTypeConfig,
+    G: OptionalSend + 'static,
+    T: HeartbeatTransport<C, G>,
+{
+    /// Send all the queued heartbeats in one batch and fan the responses out.
+    async fn flush(&self) {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+
+        let Some(first) = pending.first() else {
+            return;
+        };
+
+        // The heartbeats share the same timeout config, use the first one.
+        let option = first.option.clone();
+
+        let mut heartbeats = Vec::with_capacity(pending.len());
+        let mut txs = Vec::with_capacity(pending.len());
+
+        for p in pending {
+            heartbeats.push((p.group, p.rpc));
+            txs.push(p.tx);
+        }
+
+        self.sent_batches.fetch_add(1, Ordering::Relaxed);
+        self.sent_heartbeats.fetch_add(txs.len() as u64, Ordering::Relaxed);
+
+        let res = self.transport.send_heartbeats(HeartbeatBatch { heartbeats }, option).await;
+
+        let res = res.and_then(|resp| {
+            if resp.responses.len() == txs.len() {
+                Ok(resp)
+            } else {
+                Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
+                    "expect {} heartbeat responses, got {}",
+                    txs.len(),
+                    resp.responses.len()
+                )))))
+            }
+        });
+
+        match res {
+            Ok(resp) => {
+                for (tx, r) in txs.into_iter().zip(resp.responses) {
+                    let _ = tx.send(r.map_err(|e| RPCError::Unreachable(Unreachable::from(e))));
+                }
+            }
+            Err(e) => {
+                for tx in txs {
+                    let _ = tx.send(Err(e.clone()));
+                }
+            }
+        }
+    }
+}
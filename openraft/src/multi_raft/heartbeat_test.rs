@@ -0,0 +1,150 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyerror::AnyError;
+use futures::future::join_all;
+use pretty_assertions::assert_eq;
+
+use crate::engine::testing::log_id;
+use crate::engine::testing::UTConfig;
+use crate::error::RPCError;
+use crate::error::Unreachable;
+use crate::multi_raft::HeartbeatBatch;
+use crate::multi_raft::HeartbeatBatchResponse;
+use crate::multi_raft::HeartbeatCoalescer;
+use crate::multi_raft::HeartbeatTransport;
+use crate::network::RPCOption;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+use crate::Vote;
+
+type C = UTConfig;
+
+/// A transport that records the groups in every batch, and responds with what `respond` returns.
+#[derive(Clone)]
+struct MockTransport {
+    batches: Arc<Mutex<Vec<Vec<u64>>>>,
+
+    #[allow(clippy::type_complexity)]
+    respond:
+        Arc<dyn Fn(&[(u64, AppendEntriesRequest<C>)]) -> Result<HeartbeatBatchResponse<C>, RPCError<C>> + Send + Sync>,
+}
+
+impl MockTransport {
+    fn new(
+        respond: impl Fn(&[(u64, AppendEntriesRequest<C>)]) -> Result<HeartbeatBatchResponse<C>, RPCError<C>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self {
+            batches: Default::default(),
+            respond: Arc::new(respond),
+        }
+    }
+}
+
+impl HeartbeatTransport<C, u64> for MockTransport {
+    async fn send_heartbeats(
+        &self,
+        batch: HeartbeatBatch<C, u64>,
+        _option: RPCOption,
+    ) -> Result<HeartbeatBatchResponse<C>, RPCError<C>> {
+        self.batches.lock().unwrap().push(batch.heartbeats.iter().map(|(g, _)| *g).collect());
+        (self.respond)(&batch.heartbeats)
+    }
+}
+
+fn heartbeat(group: u64) -> AppendEntriesRequest<C> {
+    AppendEntriesRequest {
+        vote: Vote::new_committed(1, 0),
+        prev_log_id: Some(log_id(1, 0, group)),
+        leader_commit: None,
+        entries: vec![],
+    }
+}
+
+fn option() -> RPCOption {
+    RPCOption::new(Duration::from_millis(1_000))
+}
+
+fn unreachable(msg: &str) -> RPCError<C> {
+    RPCError::Unreachable(Unreachable::from(AnyError::error(msg)))
+}
+
+/// Responds `Success` to an even group, `PartialSuccess(prev_log_id)` to an odd group, and an
+/// error to group 5.
+fn respond_by_group(heartbeats: &[(u64, AppendEntriesRequest<C>)]) -> Result<HeartbeatBatchResponse<C>, RPCError<C>> {
+    let responses = heartbeats
+        .iter()
+        .map(|(g, rpc)| match g {
+            5 => Err(AnyError::error("group 5 is not found")),
+            g if g % 2 == 0 => Ok(AppendEntriesResponse::Success),
+            _ => Ok(AppendEntriesResponse::PartialSuccess(rpc.prev_log_id)),
+        })
+        .collect();
+    Ok(HeartbeatBatchResponse { responses })
+}
+
+#[tokio::test]
+async fn test_coalesce_heartbeats_into_one_batch() -> anyhow::Result<()> {
+    let transport = MockTransport::new(respond_by_group);
+    let coalescer = HeartbeatCoalescer::new(transport.clone(), Duration::from_millis(50));
+
+    let results = join_all((0..10).map(|g| coalescer.send(g, heartbeat(g), option()))).await;
+
+    assert_eq!(vec![(0..10).collect::<Vec<_>>()], *transport.batches.lock().unwrap());
+    assert_eq!(1, coalescer.sent_batches());
+    assert_eq!(10, coalescer.sent_heartbeats());
+
+    for (g, res) in (0..10).zip(results) {
+        let want = match g {
+            5 => Err(unreachable("group 5 is not found")),
+            g if g % 2 == 0 => Ok(AppendEntriesResponse::Success),
+            g => Ok(AppendEntriesResponse::PartialSuccess(Some(log_id(1, 0, g)))),
+        };
+        assert_eq!(want, res, "response to group {}", g);
+    }
+
+    // Heartbeats queued after a batch is sent start a new window.
+    let res = coalescer.send(2, heartbeat(2), option()).await;
+    assert_eq!(Ok(AppendEntriesResponse::Success), res);
+    assert_eq!(2, coalescer.sent_batches());
+    assert_eq!(11, coalescer.sent_heartbeats());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_coalesce_heartbeats_transport_error() -> anyhow::Result<()> {
+    let transport = MockTransport::new(|_| Err(unreachable("peer is down")));
+    let coalescer = HeartbeatCoalescer::new(transport.clone(), Duration::from_millis(10));
+
+    let results = join_all((0..3).map(|g| coalescer.send(g, heartbeat(g), option()))).await;
+
+    assert_eq!(1, coalescer.sent_batches());
+    for res in results {
+        assert_eq!(Err(unreachable("peer is down")), res);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_coalesce_heartbeats_response_count_mismatch() -> anyhow::Result<()> {
+    let transport = MockTransport::new(|_| {
+        Ok(HeartbeatBatchResponse {
+            responses: vec![Ok(AppendEntriesResponse::Success)],
+        })
+    });
+    let coalescer = HeartbeatCoalescer::new(transport.clone(), Duration::from_millis(10));
+
+    let results = join_all((0..3).map(|g| coalescer.send(g, heartbeat(g), option()))).await;
+
+    for res in results {
+        assert_eq!(Err(unreachable("expect 3 heartbeat responses, got 1")), res);
+    }
+
+    Ok(())
+}
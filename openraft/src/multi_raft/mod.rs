@@ -0,0 +1,73 @@
+//! Utilities for running multiple Raft groups in one process.
+//!
+//! Every group is an independent [`Raft`](crate::Raft) instance, identified by an application
+//! defined group id `G`. A process usually hosts a node of many groups, and the nodes of a group
+//! are spread over several processes, thus two processes exchange the messages of many groups.
+//!
+//! - [`HeartbeatCoalescer`] sends the heartbeats of all the groups to the same peer process in one
+//!   [`HeartbeatBatch`], which the peer dispatches to its groups with [`HeartbeatBatch::handle()`].
+//!
+//! ```ignore
+//! impl RaftNetworkV2<TypeConfig> for GroupNetwork {
+//!     async fn append_entries(
+//!         &mut self,
+//!         rpc: AppendEntriesRequest<TypeConfig>,
+//!         option: RPCOption,
+//!     ) -> Result<AppendEntriesResponse<TypeConfig>, RPCError<TypeConfig>> {
+//!         if rpc.entries.is_empty() {
+//!             // Shared by all the groups that send to the target process.
+//!             return self.coalescer.send(self.group_id, rpc, option).await;
+//!         }
+//!         self.client.append_entries(self.group_id, rpc, option).await
+//!     }
+//!     // ...
+//! }
+//!
+//! // On the target process:
+//! async fn handle_heartbeats(&self, batch: HeartbeatBatch<TypeConfig, GroupId>) -> HeartbeatBatchResponse<TypeConfig> {
+//!     batch.handle(|group_id| self.groups.get(group_id).cloned()).await
+//! }
+//! ```
+//!
+//! - [`SnapshotScheduler`] limits the snapshots being built and sent at the same time by all the
+//!   groups, so that groups recovering at the same time do not saturate the disk and network.
+//!
+//! - [`GroupFence`], [`split_snapshot_meta()`] and [`snapshot_at_cut()`] are the building blocks to
+//!   split a group into two or merge two groups in a range-sharded system: the state machine
+//!   applies a [`CutPoint`] at the same log entry on every replica, fences the requests routed with
+//!   the old configuration, and the new group starts from the data cut at that entry.
+//!
+//! - [`ShardRouter`] routes a shard key to the group serving it and to the leader of that group,
+//!   following [`ForwardToLeader`](crate::error::ForwardToLeader) errors to a new leader.
+
+mod heartbeat;
+mod shard_router;
+mod snapshot_scheduler;
+mod split_merge;
+
+#[cfg(test)]
+mod heartbeat_test;
+#[cfg(test)]
+mod shard_router_test;
+#[cfg(test)]
+mod snapshot_scheduler_test;
+#[cfg(test)]
+mod split_merge_test;
+
+pub use heartbeat::HeartbeatBatch;
+pub use heartbeat::HeartbeatBatchResponse;
+pub use heartbeat::HeartbeatCoalescer;
+pub use heartbeat::HeartbeatTransport;
+pub use shard_router::RouteError;
+pub use shard_router::ShardRouter;
+pub use snapshot_scheduler::SnapshotPermit;
+pub use snapshot_scheduler::SnapshotScheduler;
+pub use snapshot_scheduler::SnapshotSchedulerConfig;
+pub use snapshot_scheduler::SnapshotSchedulerStat;
+pub use split_merge::snapshot_at_cut;
+pub use split_merge::split_snapshot_meta;
+pub use split_merge::CutPoint;
+pub use split_merge::FenceError;
+pub use split_merge::GroupChange;
+pub use split_merge::GroupFence;
+pub use split_merge::SnapshotAtCutError;
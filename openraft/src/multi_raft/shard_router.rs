@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use crate::async_runtime::watch::WatchReceiver;
+use crate::error::ForwardToLeader;
+use crate::metrics::RaftMetrics;
+use crate::type_config::alias::JoinHandleOf;
+use crate::type_config::alias::WatchReceiverOf;
+use crate::type_config::TypeConfigExt;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+use crate::TryAsRef;
+
+/// The default max number of times a request is forwarded to a new leader by
+/// [`ShardRouter::send_to_leader()`].
+const DEFAULT_MAX_FORWARDS: usize = 3;
+
+/// An error returned by [`ShardRouter::send_to_shard()`] and [`ShardRouter::send_to_leader()`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RouteError<E>
+where E: Error
+{
+    /// No group serves the key.
+    #[error("no group serves the key")]
+    ShardNotFound,
+
+    /// The leader of the group is not known, e.g., it is electing a new leader.
+    #[error("the leader of the group is unknown")]
+    LeaderUnknown,
+
+    /// The error returned by the last request.
+    #[error(transparent)]
+    Request(E),
+}
+
+struct RouterState<C, K, G>
+where C: RaftTypeConfig
+{
+    /// The group serving the keys from the start key, until the next start key.
+    shards: BTreeMap<K, G>,
+
+    /// The last known leader of every group.
+    leaders: BTreeMap<G, C::NodeId>,
+}
+
+/// Routes shard keys to groups and their leaders, in a process that runs many Raft groups.
+///
+/// Every group serves a range of keys, starting from a key set by [`ShardRouter::set_shard()`]
+/// until the start of the next range. The leader of a group is a hint: it is updated with
+/// [`ShardRouter::watch_leader()`] from the metrics of a local member of the group, or from the
+/// [`ForwardToLeader`] errors returned by requests sent with [`ShardRouter::send_to_leader()`].
+///
+/// ```ignore
+/// let router = ShardRouter::new();
+/// router.set_shard(b"a".to_vec(), 1);
+/// router.watch_leader(1, raft_of_group_1.metrics());
+///
+/// let resp = router.send_to_shard(&b"apple".to_vec(), |group, leader| {
+///     client.write(group, leader, req.clone())
+/// }).await?;
+/// ```
+pub struct ShardRouter<C, K, G>
+where C: RaftTypeConfig
+{
+    state: Arc<RwLock<RouterState<C, K, G>>>,
+    max_forwards: usize,
+}
+
+impl<C, K, G> Clone for ShardRouter<C, K, G>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            max_forwards: self.max_forwards,
+        }
+    }
+}
+
+impl<C, K, G> Default for ShardRouter<C, K, G>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            state: Arc::new(RwLock::new(RouterState {
+                shards: BTreeMap::new(),
+                leaders: BTreeMap::new(),
+            })),
+            max_forwards: DEFAULT_MAX_FORWARDS,
+        }
+    }
+}
+
+impl<C, K, G> ShardRouter<C, K, G>
+where
+    C: RaftTypeConfig,
+    K: Ord + Clone,
+    G: Ord + Clone + fmt::Display,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the max number of times a request is forwarded to a new leader.
+    pub fn with_max_forwards(mut self, max_forwards: usize) -> Self {
+        self.max_forwards = max_forwards;
+        self
+    }
+
+    /// Set the group serving the keys from `start`, until the start of the next range.
+    pub fn set_shard(&self, start: K, group: G) {
+        self.state.write().unwrap().shards.insert(start, group);
+    }
+
+    /// Remove the range starting from `start`, whose keys are then served by the range before it.
+    pub fn remove_shard(&self, start: &K) -> Option<G> {
+        self.state.write().unwrap().shards.remove(start)
+    }
+
+    /// Return the group serving `key`.
+    pub fn route(&self, key: &K) -> Option<G> {
+        let state = self.state.read().unwrap();
+        state.shards.range(..=key).next_back().map(|(_, g)| g.clone())
+    }
+
+    /// Return the last known leader of `group`.
+    pub fn leader(&self, group: &G) -> Option<C::NodeId> {
+        self.state.read().unwrap().leaders.get(group).cloned()
+    }
+
+    /// Set or clear the leader hint of `group`.
+    pub fn set_leader(&self, group: G, leader: Option<C::NodeId>) {
+        let mut state = self.state.write().unwrap();
+        match leader {
+            Some(leader) => {
+                state.leaders.insert(group, leader);
+            }
+            None => {
+                state.leaders.remove(&group);
+            }
+        }
+    }
+
+    /// Update the leader hint of `group` from the metrics of one of its members.
+    pub fn update_from_metrics(&self, group: G, metrics: &RaftMetrics<C>) {
+        self.set_leader(group, metrics.current_leader.clone());
+    }
+
+    /// Keep the leader hint of `group` fresh with the metrics of a local member, e.g.,
+    /// [`Raft::metrics()`], until the member shuts down.
+    ///
+    /// [`Raft::metrics()`]: crate::Raft::metrics
+    pub fn watch_leader(&self, group: G, mut metrics: WatchReceiverOf<C, RaftMetrics<C>>) -> JoinHandleOf<C, ()>
+    where
+        K: OptionalSend + OptionalSync + 'static,
+        G: OptionalSend + OptionalSync + 'static,
+    {
+        let router = self.clone();
+
+        C::spawn(async move {
+            loop {
+                let leader = metrics.borrow_watched().current_leader.clone();
+                router.set_leader(group.clone(), leader);
+
+                if metrics.changed().await.is_err() {
+                    tracing::info!("stop watching the leader of group {}: metrics channel is closed", group);
+                    return;
+                }
+            }
+        })
+    }
+
+    /// Send a request to the leader of the group serving `key`, see
+    /// [`ShardRouter::send_to_leader()`].
+    pub async fn send_to_shard<T, E, F, Fut>(&self, key: &K, send: F) -> Result<T, RouteError<E>>
+    where
+        E: Error + TryAsRef<ForwardToLeader<C>>,
+        F: FnMut(&G, C::NodeId) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let group = self.route(key).ok_or(RouteError::ShardNotFound)?;
+        self.send_to_leader(&group, send).await
+    }
+
+    /// Send a request with `send` to the leader of `group`, and follow the [`ForwardToLeader`]
+    /// errors to a new leader, at most [`ShardRouter::with_max_forwards()`] times.
+    ///
+    /// A [`ForwardToLeader`] error updates the leader hint of the group. If it does not tell the
+    /// new leader, the hint is cleared and the error is returned.
+    pub async fn send_to_leader<T, E, F, Fut>(&self, group: &G, mut send: F) -> Result<T, RouteError<E>>
+    where
+        E: Error + TryAsRef<ForwardToLeader<C>>,
+        F: FnMut(&G, C::NodeId) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut leader = self.leader(group).ok_or(RouteError::LeaderUnknown)?;
+        let mut forwards = 0;
+
+        loop {
+            let err = match send(group, leader.clone()).await {
+                Ok(x) => return Ok(x),
+                Err(e) => e,
+            };
+
+            let Some(forward) = err.try_as_ref() else {
+                return Err(RouteError::Request(err));
+            };
+
+            let new_leader = forward.leader_id.clone();
+            tracing::debug!(
+                "group {}: request to {} is forwarded to {:?}, forwards: {}",
+                group,
+                leader,
+                new_leader,
+                forwards
+            );
+
+            self.set_leader(group.clone(), new_leader.clone());
+
+            let Some(new_leader) = new_leader else {
+                return Err(RouteError::Request(err));
+            };
+
+            if forwards >= self.max_forwards {
+                return Err(RouteError::Request(err));
+            }
+
+            forwards += 1;
+            leader = new_leader;
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use pretty_assertions::assert_eq;
+
+use crate::engine::testing::UTConfig;
+use crate::error::ClientWriteError;
+use crate::error::ForwardToLeader;
+use crate::error::RaftError;
+use crate::metrics::RaftMetrics;
+use crate::multi_raft::RouteError;
+use crate::multi_raft::ShardRouter;
+use crate::type_config::TypeConfigExt;
+
+type C = UTConfig;
+type WriteError = RaftError<C, ClientWriteError<C>>;
+
+fn forward(leader_id: Option<u64>) -> WriteError {
+    RaftError::APIError(ClientWriteError::ForwardToLeader(ForwardToLeader {
+        leader_id,
+        leader_node: leader_id.map(|_| ()),
+    }))
+}
+
+/// A group whose leader is `leader`: other nodes respond with `ForwardToLeader` to `hint`.
+struct MockGroup {
+    leader: u64,
+    hint: Option<u64>,
+    sent_to: Arc<Mutex<Vec<u64>>>,
+}
+
+impl MockGroup {
+    fn new(leader: u64, hint: Option<u64>) -> Self {
+        Self {
+            leader,
+            hint,
+            sent_to: Default::default(),
+        }
+    }
+
+    async fn send(&self, target: u64) -> Result<u64, WriteError> {
+        self.sent_to.lock().unwrap().push(target);
+        if target == self.leader {
+            Ok(target)
+        } else {
+            Err(forward(self.hint))
+        }
+    }
+
+    fn sent_to(&self) -> Vec<u64> {
+        self.sent_to.lock().unwrap().clone()
+    }
+}
+
+#[test]
+fn test_shard_router_route() -> anyhow::Result<()> {
+    let r = ShardRouter::<C, &str, u64>::new();
+
+    assert_eq!(None, r.route(&"a"));
+
+    r.set_shard("b", 1);
+    r.set_shard("m", 2);
+
+    assert_eq!(None, r.route(&"a"));
+    assert_eq!(Some(1), r.route(&"b"));
+    assert_eq!(Some(1), r.route(&"lz"));
+    assert_eq!(Some(2), r.route(&"m"));
+    assert_eq!(Some(2), r.route(&"z"));
+
+    // Group 3 is split off from group 1.
+    r.set_shard("f", 3);
+    assert_eq!(Some(1), r.route(&"e"));
+    assert_eq!(Some(3), r.route(&"f"));
+
+    // Group 3 is merged back.
+    assert_eq!(Some(3), r.remove_shard(&"f"));
+    assert_eq!(Some(1), r.route(&"f"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_router_follow_forward_to_leader() -> anyhow::Result<()> {
+    let r = ShardRouter::<C, &str, u64>::new();
+    r.set_shard("a", 1);
+
+    tracing::info!("--- no leader is known");
+    {
+        let g = MockGroup::new(3, Some(3));
+        let res = r.send_to_shard(&"a", |_, target| g.send(target)).await;
+        assert_eq!(Err(RouteError::LeaderUnknown), res);
+        assert!(g.sent_to().is_empty());
+    }
+
+    tracing::info!("--- key is not served");
+    {
+        let g = MockGroup::new(3, Some(3));
+        let res = r.send_to_shard(&"0", |_, target| g.send(target)).await;
+        assert_eq!(Err(RouteError::ShardNotFound), res);
+    }
+
+    tracing::info!("--- forwarded to the new leader, which becomes the hint");
+    {
+        r.set_leader(1, Some(2));
+
+        let g = MockGroup::new(3, Some(3));
+        let res = r.send_to_shard(&"a", |_, target| g.send(target)).await;
+        assert_eq!(Ok(3), res);
+        assert_eq!(vec![2, 3], g.sent_to());
+        assert_eq!(Some(3), r.leader(&1));
+    }
+
+    tracing::info!("--- the new leader is unknown, the hint is cleared");
+    {
+        let g = MockGroup::new(5, None);
+        let res = r.send_to_shard(&"a", |_, target| g.send(target)).await;
+        assert_eq!(Err(RouteError::Request(forward(None))), res);
+        assert_eq!(None, r.leader(&1));
+    }
+
+    tracing::info!("--- stop following after max forwards");
+    {
+        let r = r.with_max_forwards(2);
+        r.set_leader(1, Some(2));
+
+        let g = MockGroup::new(5, Some(2));
+        let res = r.send_to_shard(&"a", |_, target| g.send(target)).await;
+        assert_eq!(Err(RouteError::Request(forward(Some(2)))), res);
+        assert_eq!(vec![2, 2, 2], g.sent_to());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shard_router_watch_leader() -> anyhow::Result<()> {
+    let r = ShardRouter::<C, &'static str, u64>::new();
+
+    let mut metrics = RaftMetrics::<C>::new_initial(1);
+    metrics.current_leader = Some(2);
+    let (tx, rx) = C::watch_channel(metrics.clone());
+
+    let handle = r.watch_leader(7, rx);
+
+    let wait_leader = |want: Option<u64>| {
+        let r = r.clone();
+        async move {
+            for _ in 0..100 {
+                if r.leader(&7) == want {
+                    return;
+                }
+                C::sleep(Duration::from_millis(10)).await;
+            }
+            panic!("leader of group 7 is not {:?}", want);
+        }
+    };
+
+    wait_leader(Some(2)).await;
+
+    metrics.current_leader = Some(3);
+    tx.send(metrics.clone())?;
+    wait_leader(Some(3)).await;
+
+    metrics.current_leader = None;
+    tx.send(metrics)?;
+    wait_leader(None).await;
+
+    drop(tx);
+    handle.await?;
+
+    Ok(())
+}
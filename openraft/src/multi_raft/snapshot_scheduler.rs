@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::async_runtime::OneshotSender;
+use crate::type_config::alias::OneshotSenderOf;
+use crate::type_config::TypeConfigExt;
+use crate::RaftTypeConfig;
+
+/// The limits of a [`SnapshotScheduler`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotSchedulerConfig {
+    /// The max number of snapshots being built at the same time in the process.
+    pub max_builds: usize,
+
+    /// The max number of snapshots being sent at the same time from the process.
+    pub max_sends: usize,
+
+    /// The max number of snapshots being sent at the same time to the same target.
+    pub max_sends_per_target: usize,
+}
+
+impl Default for SnapshotSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            max_builds: 2,
+            max_sends: 4,
+            max_sends_per_target: 1,
+        }
+    }
+}
+
+/// A waiter for a permit, woken up by the task that releases one.
+struct Waiter<C, T>
+where C: RaftTypeConfig
+{
+    id: u64,
+
+    /// The target to send a snapshot to, or `None` to build a snapshot.
+    target: Option<T>,
+    tx: OneshotSenderOf<C, ()>,
+}
+
+struct SchedulerState<C, T>
+where C: RaftTypeConfig
+{
+    config: SnapshotSchedulerConfig,
+
+    building: usize,
+    sending: usize,
+    sending_to: BTreeMap<T, usize>,
+
+    /// The waiters in arrival order.
+    waiters: VecDeque<Waiter<C, T>>,
+
+    next_waiter_id: u64,
+}
+
+impl<C, T> SchedulerState<C, T>
+where
+    C: RaftTypeConfig,
+    T: Ord + Clone,
+{
+    fn can_acquire(&self, target: Option<&T>) -> bool {
+        match target {
+            None => self.building < self.config.max_builds,
+            Some(t) => {
+                self.sending < self.config.max_sends
+                    && self.sending_to.get(t).copied().unwrap_or_default() < self.config.max_sends_per_target
+            }
+        }
+    }
+
+    fn acquire(&mut self, target: Option<&T>) {
+        match target {
+            None => self.building += 1,
+            Some(t) => {
+                self.sending += 1;
+                *self.sending_to.entry(t.clone()).or_default() += 1;
+            }
+        }
+    }
+
+    fn release(&mut self, target: Option<&T>) {
+        match target {
+            None => self.building -= 1,
+            Some(t) => {
+                self.sending -= 1;
+                let n = self.sending_to.get_mut(t).unwrap();
+                *n -= 1;
+                if *n == 0 {
+                    self.sending_to.remove(t);
+                }
+            }
+        }
+    }
+
+    /// Hand the released permits to the earliest waiters that can acquire one.
+    ///
+    /// A waiter that can not acquire a permit, e.g., because its target is busy, does not block
+    /// the waiters after it.
+    fn wake_waiters(&mut self) {
+        let mut i = 0;
+        while i < self.waiters.len() {
+            if !self.can_acquire(self.waiters[i].target.as_ref()) {
+                i += 1;
+                continue;
+            }
+
+            let waiter = self.waiters.remove(i).unwrap();
+            self.acquire(waiter.target.as_ref());
+
+            // A cancelled waiter removes itself from the queue, the receiver is alive.
+            let _ = waiter.tx.send(());
+        }
+    }
+}
+
+/// Limits the snapshots being built and sent at the same time, shared by all the groups in a
+/// process.
+///
+/// When many groups recover at the same time, e.g., after a process restarts, building and
+/// sending all their snapshots at once saturates the disk and network. A group acquires a
+/// [`SnapshotPermit`] before building a snapshot in
+/// [`RaftSnapshotBuilder::build_snapshot()`], or sending one in
+/// [`RaftNetworkV2::full_snapshot()`], and holds it until done:
+///
+/// ```ignore
+/// async fn build_snapshot(&mut self) -> Result<Snapshot<TypeConfig>, StorageError<TypeConfig>> {
+///     let _permit = self.scheduler.acquire_build().await;
+///     // ...
+/// }
+/// ```
+///
+/// Permits are granted in the order they are requested, except that a send to a target that has
+/// reached [`SnapshotSchedulerConfig::max_sends_per_target`] does not block sends to other
+/// targets.
+///
+/// [`RaftSnapshotBuilder::build_snapshot()`]: crate::storage::RaftSnapshotBuilder::build_snapshot
+/// [`RaftNetworkV2::full_snapshot()`]: crate::network::v2::RaftNetworkV2::full_snapshot
+pub struct SnapshotScheduler<C, T>
+where C: RaftTypeConfig
+{
+    state: Arc<Mutex<SchedulerState<C, T>>>,
+}
+
+impl<C, T> Clone for SnapshotScheduler<C, T>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<C, T> SnapshotScheduler<C, T>
+where
+    C: RaftTypeConfig,
+    T: Ord + Clone,
+{
+    pub fn new(config: SnapshotSchedulerConfig) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(SchedulerState {
+                config,
+                building: 0,
+                sending: 0,
+                sending_to: BTreeMap::new(),
+                waiters: VecDeque::new(),
+                next_waiter_id: 0,
+            })),
+        }
+    }
+
+    /// Wait for a permit to build a snapshot.
+    pub async fn acquire_build(&self) -> SnapshotPermit<C, T> {
+        self.acquire(None).await
+    }
+
+    /// Wait for a permit to send a snapshot to `target`.
+    pub async fn acquire_send(&self, target: T) -> SnapshotPermit<C, T> {
+        self.acquire(Some(target)).await
+    }
+
+    async fn acquire(&self, target: Option<T>) -> SnapshotPermit<C, T> {
+        let (id, rx) = {
+            let mut state = self.state.lock().unwrap();
+
+            // A waiter is woken up as soon as it can acquire a permit. Thus if this one can
+            // acquire a permit, none of the waiters can use it.
+            if state.can_acquire(target.as_ref()) {
+                state.acquire(target.as_ref());
+                return SnapshotPermit {
+                    state: self.state.clone(),
+                    target,
+                    waiting: None,
+                };
+            }
+
+            let id = state.next_waiter_id;
+            state.next_waiter_id += 1;
+
+            let (tx, rx) = C::oneshot();
+            state.waiters.push_back(Waiter {
+                id,
+                target: target.clone(),
+                tx,
+            });
+            (id, rx)
+        };
+
+        // If this future is dropped before the permit is granted, the permit leaves the queue.
+        let mut permit = SnapshotPermit {
+            state: self.state.clone(),
+            target,
+            waiting: Some(id),
+        };
+
+        rx.await.expect("the waiter is removed from the queue only when the permit is dropped");
+
+        permit.waiting = None;
+        permit
+    }
+
+    /// Return the number of snapshots being built, being sent and waiting for a permit.
+    pub fn stat(&self) -> SnapshotSchedulerStat {
+        let state = self.state.lock().unwrap();
+        SnapshotSchedulerStat {
+            building: state.building,
+            sending: state.sending,
+            waiting: state.waiters.len(),
+        }
+    }
+}
+
+/// The number of snapshots in a [`SnapshotScheduler`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnapshotSchedulerStat {
+    pub building: usize,
+    pub sending: usize,
+    pub waiting: usize,
+}
+
+impl fmt::Display for SnapshotSchedulerStat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{building: {}, sending: {}, waiting: {}}}",
+            self.building, self.sending, self.waiting
+        )
+    }
+}
+
+/// A permit to build or send a snapshot, released when dropped.
+pub struct SnapshotPermit<C, T>
+where
+    C: RaftTypeConfig,
+    T: Ord + Clone,
+{
+    state: Arc<Mutex<SchedulerState<C, T>>>,
+    target: Option<T>,
+
+    /// The id of the waiter if the permit is not yet known to be granted.
+    waiting: Option<u64>,
+}
+
+impl<C, T> Drop for SnapshotPermit<C, T>
+where
+    C: RaftTypeConfig,
+    T: Ord + Clone,
+{
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(id) = self.waiting {
+            if let Some(pos) = state.waiters.iter().position(|w| w.id == id) {
+                // Not granted yet.
+                state.waiters.remove(pos);
+                return;
+            }
+        }
+
+        state.release(self.target.as_ref());
+        state.wake_waiters();
+    }
+}
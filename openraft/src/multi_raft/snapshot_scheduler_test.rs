@@ -0,0 +1,140 @@
+use std::pin::pin;
+use std::task::Poll;
+
+use futures::poll;
+use pretty_assertions::assert_eq;
+
+use crate::engine::testing::UTConfig;
+use crate::multi_raft::SnapshotScheduler;
+use crate::multi_raft::SnapshotSchedulerConfig;
+use crate::multi_raft::SnapshotSchedulerStat;
+
+fn scheduler(max_builds: usize, max_sends: usize, max_sends_per_target: usize) -> SnapshotScheduler<UTConfig, u64> {
+    SnapshotScheduler::new(SnapshotSchedulerConfig {
+        max_builds,
+        max_sends,
+        max_sends_per_target,
+    })
+}
+
+fn stat(building: usize, sending: usize, waiting: usize) -> SnapshotSchedulerStat {
+    SnapshotSchedulerStat {
+        building,
+        sending,
+        waiting,
+    }
+}
+
+#[tokio::test]
+async fn test_snapshot_scheduler_max_builds() -> anyhow::Result<()> {
+    let s = scheduler(2, 4, 1);
+
+    let p1 = s.acquire_build().await;
+    let _p2 = s.acquire_build().await;
+
+    let mut p3 = pin!(s.acquire_build());
+    assert!(poll!(p3.as_mut()).is_pending());
+    assert_eq!(stat(2, 0, 1), s.stat());
+
+    // Builds do not block sends.
+    let _p4 = s.acquire_send(1).await;
+    assert_eq!(stat(2, 1, 1), s.stat());
+
+    drop(p1);
+    assert_eq!(stat(2, 1, 0), s.stat());
+    assert!(matches!(poll!(p3.as_mut()), Poll::Ready(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_scheduler_max_sends_per_target() -> anyhow::Result<()> {
+    let s = scheduler(2, 2, 1);
+
+    let p1 = s.acquire_send(1).await;
+
+    // A busy target does not block the sends to other targets.
+    let mut p2 = pin!(s.acquire_send(1));
+    assert!(poll!(p2.as_mut()).is_pending());
+
+    let p3 = s.acquire_send(2).await;
+    assert_eq!(stat(0, 2, 1), s.stat());
+
+    // Reaches `max_sends`.
+    let mut p4 = pin!(s.acquire_send(3));
+    assert!(poll!(p4.as_mut()).is_pending());
+    assert_eq!(stat(0, 2, 2), s.stat());
+
+    // The slot of target 2 goes to target 3, target 1 is still busy.
+    drop(p3);
+    assert!(poll!(p2.as_mut()).is_pending());
+    let Poll::Ready(_p4) = poll!(p4.as_mut()) else {
+        panic!("target 3 acquires the released permit");
+    };
+    assert_eq!(stat(0, 2, 1), s.stat());
+
+    drop(p1);
+    let Poll::Ready(_p2) = poll!(p2.as_mut()) else {
+        panic!("target 1 acquires the released permit");
+    };
+    assert_eq!(stat(0, 2, 0), s.stat());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_scheduler_fifo() -> anyhow::Result<()> {
+    let s = scheduler(1, 4, 1);
+
+    let p1 = s.acquire_build().await;
+
+    let mut p2 = pin!(s.acquire_build());
+    let mut p3 = pin!(s.acquire_build());
+    assert!(poll!(p2.as_mut()).is_pending());
+    assert!(poll!(p3.as_mut()).is_pending());
+
+    drop(p1);
+    assert!(poll!(p3.as_mut()).is_pending());
+    let Poll::Ready(p2) = poll!(p2.as_mut()) else {
+        panic!("the earliest waiter acquires the permit");
+    };
+
+    drop(p2);
+    assert!(matches!(poll!(p3.as_mut()), Poll::Ready(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_scheduler_cancel_waiter() -> anyhow::Result<()> {
+    let s = scheduler(1, 4, 1);
+
+    let p1 = s.acquire_build().await;
+
+    tracing::info!("--- a waiter cancelled before it is granted leaves the queue");
+    {
+        let mut p2 = Box::pin(s.acquire_build());
+        assert!(poll!(p2.as_mut()).is_pending());
+        assert_eq!(stat(1, 0, 1), s.stat());
+
+        drop(p2);
+        assert_eq!(stat(1, 0, 0), s.stat());
+    }
+
+    tracing::info!("--- a waiter cancelled after it is granted releases the permit");
+    {
+        let mut p2 = Box::pin(s.acquire_build());
+        assert!(poll!(p2.as_mut()).is_pending());
+
+        drop(p1);
+        assert_eq!(stat(1, 0, 0), s.stat());
+
+        drop(p2);
+        assert_eq!(stat(0, 0, 0), s.stat());
+    }
+
+    let _p3 = s.acquire_build().await;
+    assert_eq!(stat(1, 0, 0), s.stat());
+
+    Ok(())
+}
@@ -0,0 +1,268 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::error::Fatal;
+use crate::metrics::Metric;
+use crate::metrics::WaitError;
+use crate::type_config::alias::CommittedLeaderIdOf;
+use crate::type_config::alias::LogIdOf;
+use crate::Membership;
+use crate::Raft;
+use crate::RaftTypeConfig;
+use crate::Snapshot;
+use crate::SnapshotMeta;
+use crate::StoredMembership;
+
+/// How a group in a range-sharded system is reconfigured by a log entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(serialize = "G: serde::Serialize")),
+    serde(bound(deserialize = "G: for <'d> serde::Deserialize<'d>"))
+)]
+pub enum GroupChange<G> {
+    /// Part of the data of this group is split off to the new group `new_group`.
+    Split { new_group: G },
+
+    /// The data of the group `source` is merged into this group.
+    MergeFrom { source: G },
+
+    /// This group is merged into the group `target` and serves no more requests.
+    MergeInto { target: G },
+}
+
+impl<G> fmt::Display for GroupChange<G>
+where G: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupChange::Split { new_group } => write!(f, "Split{{new_group: {}}}", new_group),
+            GroupChange::MergeFrom { source } => write!(f, "MergeFrom{{source: {}}}", source),
+            GroupChange::MergeInto { target } => write!(f, "MergeInto{{target: {}}}", target),
+        }
+    }
+}
+
+/// A [`GroupChange`] that takes effect at the log entry `log_id`.
+///
+/// The state machine of every replica applies the change when it applies the entry, thus all the
+/// replicas cut the data at the same point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(serialize = "G: serde::Serialize")),
+    serde(bound(deserialize = "G: for <'d> serde::Deserialize<'d>"))
+)]
+pub struct CutPoint<C, G>
+where C: RaftTypeConfig
+{
+    pub log_id: LogIdOf<C>,
+    pub change: GroupChange<G>,
+}
+
+impl<C, G> fmt::Display for CutPoint<C, G>
+where
+    C: RaftTypeConfig,
+    G: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.change, self.log_id)
+    }
+}
+
+/// A request is rejected by a [`GroupFence`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(serialize = "G: serde::Serialize")),
+    serde(bound(deserialize = "G: for <'d> serde::Deserialize<'d>"))
+)]
+pub enum FenceError<C, G>
+where
+    C: RaftTypeConfig,
+    G: fmt::Debug + fmt::Display,
+{
+    /// The request is routed with a configuration of another epoch.
+    #[error("epoch mismatch: expect: {expect}, got: {got}")]
+    EpochMismatch { expect: u64, got: u64 },
+
+    /// The group is merged into `target` and serves no more requests.
+    #[error("group is merged into {target} at {log_id}")]
+    Retired { target: G, log_id: LogIdOf<C> },
+}
+
+/// Fences the requests routed with an old configuration of a group in a range-sharded system.
+///
+/// It is part of the state machine of the group: every [`CutPoint`] applied to it bumps the
+/// epoch, and a request carrying another epoch, which is routed with the configuration before or
+/// after a split or merge, is rejected by [`GroupFence::check()`]. Include it in the snapshot so
+/// that a replica restored from a snapshot fences the same requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(serialize = "G: serde::Serialize")),
+    serde(bound(deserialize = "G: for <'d> serde::Deserialize<'d>"))
+)]
+pub struct GroupFence<C, G>
+where C: RaftTypeConfig
+{
+    epoch: u64,
+    last_cut: Option<CutPoint<C, G>>,
+}
+
+impl<C, G> Default for GroupFence<C, G>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            epoch: 0,
+            last_cut: None,
+        }
+    }
+}
+
+impl<C, G> GroupFence<C, G>
+where
+    C: RaftTypeConfig,
+    G: fmt::Debug + fmt::Display + Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The epoch of the current configuration, starting from 0.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The last applied [`CutPoint`].
+    pub fn last_cut(&self) -> Option<&CutPoint<C, G>> {
+        self.last_cut.as_ref()
+    }
+
+    /// Return the group this group is merged into, if it is retired.
+    pub fn retired_into(&self) -> Option<&G> {
+        match &self.last_cut {
+            Some(CutPoint {
+                change: GroupChange::MergeInto { target },
+                ..
+            }) => Some(target),
+            _ => None,
+        }
+    }
+
+    /// Apply a [`CutPoint`] when the state machine applies its log entry, and bump the epoch.
+    ///
+    /// Returns `false` if it is ignored: a cut point not after the last one is already applied,
+    /// e.g., when logs are re-applied after a restart, and a retired group accepts no more
+    /// changes.
+    pub fn apply(&mut self, cut: CutPoint<C, G>) -> bool {
+        if let Some(last) = &self.last_cut {
+            if cut.log_id <= last.log_id {
+                return false;
+            }
+        }
+
+        if self.retired_into().is_some() {
+            tracing::warn!(
+                "ignore {} to a retired group, last: {}",
+                cut,
+                self.last_cut.as_ref().unwrap()
+            );
+            return false;
+        }
+
+        self.epoch += 1;
+        self.last_cut = Some(cut);
+        true
+    }
+
+    /// Check a request routed with the configuration of `epoch`.
+    pub fn check(&self, epoch: u64) -> Result<(), FenceError<C, G>> {
+        if let Some(last) = &self.last_cut {
+            if let GroupChange::MergeInto { target } = &last.change {
+                return Err(FenceError::Retired {
+                    target: target.clone(),
+                    log_id: last.log_id.clone(),
+                });
+            }
+        }
+
+        if epoch != self.epoch {
+            return Err(FenceError::EpochMismatch {
+                expect: self.epoch,
+                got: epoch,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Build the meta of the initial snapshot of a group split off at the log entry `cut` of its
+/// parent, with the voters and learners in `membership`.
+///
+/// Every replica of the new group installs the data split off at `cut` with
+/// [`RaftStateMachine::install_snapshot()`] and this meta, before starting its [`Raft`]. The new
+/// group does not inherit the leaders of the parent: its log starts at the index of `cut` with
+/// the smallest leader id, as an initialized group does, and the first election of the new group
+/// proposes logs after it.
+///
+/// [`RaftStateMachine::install_snapshot()`]: crate::storage::RaftStateMachine::install_snapshot
+pub fn split_snapshot_meta<C>(cut: &LogIdOf<C>, membership: Membership<C>) -> SnapshotMeta<C>
+where C: RaftTypeConfig {
+    let last_log_id = LogIdOf::<C>::new(CommittedLeaderIdOf::<C>::default(), cut.index());
+
+    SnapshotMeta::new(
+        Some(last_log_id.clone()),
+        StoredMembership::new(Some(last_log_id), membership),
+        format!("split-at-{}", cut),
+    )
+}
+
+/// An error returned by [`snapshot_at_cut()`].
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotAtCutError<C>
+where C: RaftTypeConfig
+{
+    #[error(transparent)]
+    Fatal(#[from] Fatal<C>),
+
+    #[error(transparent)]
+    Wait(#[from] WaitError),
+}
+
+/// Return a snapshot of a group that includes the log entry `cut`, building one if needed.
+///
+/// To merge a group into another, the source group applies a [`GroupChange::MergeInto`] at `cut`
+/// first and stops changing its data, thus a snapshot including `cut` contains the same data as
+/// the state machine at `cut`. The target group then merges this data when it applies a
+/// [`GroupChange::MergeFrom`].
+pub async fn snapshot_at_cut<C>(
+    raft: &Raft<C>,
+    cut: &LogIdOf<C>,
+    timeout: Option<Duration>,
+) -> Result<Snapshot<C>, SnapshotAtCutError<C>>
+where
+    C: RaftTypeConfig,
+{
+    loop {
+        let snapshot = raft.get_snapshot().await.map_err(|e| e.into_fatal().unwrap())?;
+
+        if let Some(snapshot) = snapshot {
+            if snapshot.meta.last_log_id.as_ref() >= Some(cut) {
+                return Ok(snapshot);
+            }
+        }
+
+        raft.wait(timeout).applied_index_at_least(Some(cut.index()), "applied the cut point").await?;
+        raft.trigger().snapshot().await?;
+        raft.wait(timeout)
+            .ge(Metric::Snapshot(Some(cut.clone())), "snapshot including the cut point")
+            .await?;
+    }
+}
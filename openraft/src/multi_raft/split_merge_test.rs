@@ -0,0 +1,90 @@
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::engine::testing::log_id;
+use crate::engine::testing::UTConfig;
+use crate::multi_raft::split_snapshot_meta;
+use crate::multi_raft::CutPoint;
+use crate::multi_raft::FenceError;
+use crate::multi_raft::GroupChange;
+use crate::multi_raft::GroupFence;
+use crate::Membership;
+use crate::StoredMembership;
+
+fn split(index: u64, new_group: u64) -> CutPoint<UTConfig, u64> {
+    CutPoint {
+        log_id: log_id(2, 1, index),
+        change: GroupChange::Split { new_group },
+    }
+}
+
+fn merge_into(index: u64, target: u64) -> CutPoint<UTConfig, u64> {
+    CutPoint {
+        log_id: log_id(2, 1, index),
+        change: GroupChange::MergeInto { target },
+    }
+}
+
+#[test]
+fn test_group_fence_epoch() -> anyhow::Result<()> {
+    let mut f = GroupFence::<UTConfig, u64>::new();
+    assert_eq!(0, f.epoch());
+    assert_eq!(Ok(()), f.check(0));
+
+    assert!(f.apply(split(5, 10)));
+    assert_eq!(1, f.epoch());
+    assert_eq!(Some(&split(5, 10)), f.last_cut());
+
+    assert_eq!(Err(FenceError::EpochMismatch { expect: 1, got: 0 }), f.check(0));
+    assert_eq!(Ok(()), f.check(1));
+    assert_eq!(Err(FenceError::EpochMismatch { expect: 1, got: 2 }), f.check(2));
+
+    // Re-applying a cut point, e.g., after a restart, is ignored.
+    assert!(!f.apply(split(5, 10)));
+    assert!(!f.apply(split(3, 11)));
+    assert_eq!(1, f.epoch());
+
+    assert!(f.apply(split(7, 11)));
+    assert_eq!(2, f.epoch());
+    assert_eq!(None, f.retired_into());
+
+    Ok(())
+}
+
+#[test]
+fn test_group_fence_retired() -> anyhow::Result<()> {
+    let mut f = GroupFence::<UTConfig, u64>::new();
+
+    assert!(f.apply(merge_into(5, 3)));
+    assert_eq!(Some(&3), f.retired_into());
+
+    let want = Err(FenceError::Retired {
+        target: 3,
+        log_id: log_id(2, 1, 5),
+    });
+    assert_eq!(want, f.check(1));
+    assert_eq!(want, f.check(0));
+
+    // A retired group accepts no more changes.
+    assert!(!f.apply(split(7, 11)));
+    assert_eq!(1, f.epoch());
+
+    Ok(())
+}
+
+#[test]
+fn test_split_snapshot_meta() -> anyhow::Result<()> {
+    let membership = Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2,3}], []);
+
+    let meta = split_snapshot_meta(&log_id(3, 1, 10), membership.clone());
+
+    assert_eq!(Some(log_id(0, 0, 10)), meta.last_log_id);
+    assert_eq!(
+        StoredMembership::new(Some(log_id(0, 0, 10)), membership),
+        meta.last_membership
+    );
+    assert_eq!("split-at-T3-N1.10", meta.snapshot_id);
+    assert_eq!(0, meta.version());
+
+    Ok(())
+}
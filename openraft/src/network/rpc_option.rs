@@ -1,5 +1,12 @@
+use std::sync::Arc;
 use std::time::Duration;
 
+use openraft_macros::since;
+use tracing::Span;
+
+use crate::core::io_counters::IoCounters;
+use crate::raft::CorrelationId;
+
 /// An additional argument to the [`RaftNetwork`] methods to allow applications to customize
 /// networking behaviors.
 ///
@@ -13,6 +20,15 @@ pub struct RPCOption {
 
     /// The size of the snapshot chunk.
     pub(crate) snapshot_chunk_size: Option<usize>,
+
+    /// The tracing span of the client write the RPC originates from.
+    pub(crate) span: Span,
+
+    /// The correlation ids of the client writes the RPC carries.
+    pub(crate) correlation_ids: Vec<CorrelationId>,
+
+    /// The IO counters of the node sending the RPC, to count the snapshot bytes sent.
+    pub(crate) io_counters: Option<Arc<IoCounters>>,
 }
 
 impl RPCOption {
@@ -20,6 +36,9 @@ impl RPCOption {
         Self {
             hard_ttl,
             snapshot_chunk_size: None,
+            span: Span::none(),
+            correlation_ids: vec![],
+            io_counters: None,
         }
     }
 
@@ -51,4 +70,48 @@ impl RPCOption {
     pub fn snapshot_chunk_size(&self) -> Option<usize> {
         self.snapshot_chunk_size
     }
+
+    /// The tracing span of the client write this RPC originates from.
+    ///
+    /// For an `AppendEntries` it is the span of the caller of [`Raft::client_write()`] that
+    /// proposed the last entry in the RPC. It is [`Span::none()`] if the RPC carries no client
+    /// write, or feature `otel` is not enabled.
+    ///
+    /// An application can propagate it to the remote node to build a distributed trace, e.g.,
+    /// with `tracing-opentelemetry`, inject `option.span().context()` into the headers of the RPC
+    /// request, and on the receiving side, set the extracted context as the parent of the span
+    /// that calls the [`Raft`] API.
+    ///
+    /// [`Raft::client_write()`]: `crate::Raft::client_write`
+    /// [`Raft`]: `crate::Raft`
+    #[since(version = "0.10.0")]
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// The correlation ids of the client writes whose log entries are carried by this RPC.
+    ///
+    /// It is empty if the RPC carries no client write, e.g., a heartbeat, or the writes are
+    /// already applied, or the RPC is not an `AppendEntries`. An application can send them along
+    /// with the RPC and log them on the remote node, to trace a write across nodes.
+    #[since(version = "0.10.0")]
+    pub fn correlation_ids(&self) -> &[CorrelationId] {
+        &self.correlation_ids
+    }
+
+    /// Count `n` bytes of snapshot data sent by this RPC, in
+    /// [`IoMetrics::snapshot_bytes_sent`].
+    ///
+    /// The snapshot transport of Openraft counts the chunks it sends by itself. An application
+    /// defined transport should call it in [`RaftNetworkV2::full_snapshot()`] for the data it
+    /// sends. It does nothing if the RPC does not send a snapshot.
+    ///
+    /// [`IoMetrics::snapshot_bytes_sent`]: crate::metrics::IoMetrics::snapshot_bytes_sent
+    /// [`RaftNetworkV2::full_snapshot()`]: crate::network::v2::RaftNetworkV2::full_snapshot
+    #[since(version = "0.10.0")]
+    pub fn add_snapshot_bytes_sent(&self, n: u64) {
+        if let Some(io_counters) = &self.io_counters {
+            io_counters.add_snapshot_bytes_sent(n);
+        }
+    }
 }
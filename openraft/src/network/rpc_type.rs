@@ -2,6 +2,7 @@ use std::fmt;
 
 #[derive(Debug, Clone, Copy)]
 #[derive(PartialEq, Eq)]
+#[derive(PartialOrd, Ord)]
 #[derive(Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum RPCTypes {
@@ -9,6 +10,7 @@ pub enum RPCTypes {
     AppendEntries,
     InstallSnapshot,
     TransferLeader,
+    ReadIndex,
 }
 
 impl fmt::Display for RPCTypes {
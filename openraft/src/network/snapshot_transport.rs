@@ -1,5 +1,5 @@
 //! Provide a default chunked snapshot transport implementation for SnapshotData that implements
-//! AsyncWrite + AsyncRead + AsyncSeek + Unpin.
+//! AsyncWrite + AsyncRead + Unpin.
 
 mod tokio_rt {
     #![cfg(feature = "tokio-rt")]
@@ -7,21 +7,22 @@ mod tokio_rt {
     //! feature.
 
     use std::future::Future;
-    use std::io::SeekFrom;
+    use std::io;
     use std::time::Duration;
 
     use futures::FutureExt;
     use tokio::io::AsyncReadExt;
-    use tokio::io::AsyncSeekExt;
     use tokio::io::AsyncWriteExt;
 
     use super::Chunked;
     use super::SnapshotTransport;
     use super::Streaming;
     use crate::error::InstallSnapshotError;
+    use crate::error::NetworkError;
     use crate::error::RPCError;
     use crate::error::RaftError;
     use crate::error::ReplicationClosed;
+    use crate::error::SnapshotMismatch;
     use crate::error::StreamingError;
     use crate::network::RPCOption;
     use crate::raft::InstallSnapshotRequest;
@@ -36,12 +37,18 @@ mod tokio_rt {
     use crate::Raft;
     use crate::RaftNetwork;
     use crate::RaftTypeConfig;
+    use crate::SnapshotId;
+    use crate::SnapshotSegmentId;
     use crate::StorageError;
     use crate::ToStorageResult;
 
-    /// This chunk based implementation requires `SnapshotData` to be `AsyncRead + AsyncSeek`.
+    /// This chunk based implementation requires `SnapshotData` to be `AsyncRead + AsyncWrite`.
+    ///
+    /// It does not seek: the data is read and written sequentially, and the offset is maintained
+    /// internally. Thus a snapshot backed by a stream, such as a pipe or an object store download,
+    /// can be transferred without buffering it in a temporary file.
     impl<C: RaftTypeConfig> SnapshotTransport<C> for Chunked
-    where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io::AsyncSeek + Unpin
+    where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin
     {
         async fn send_snapshot<Net>(
             net: &mut Net,
@@ -55,96 +62,88 @@ mod tokio_rt {
         {
             let subject_verb = || (ErrorSubject::Snapshot(Some(snapshot.meta.signature())), ErrorVerb::Read);
 
+            // Safe unwrap(): this function is called only by default implementation of
+            // `RaftNetwork::full_snapshot()` and it is always set.
+            let chunk_size = option.snapshot_chunk_size().unwrap();
+
             let mut offset = 0;
-            let end = snapshot.snapshot.seek(SeekFrom::End(0)).await.sto_res(subject_verb)?;
+            let mut data = read_chunk(&mut snapshot.snapshot, chunk_size).await.sto_res(subject_verb)?;
 
             let mut c = std::pin::pin!(cancel);
             loop {
-                // If canceled, return at once
-                if let Some(err) = c.as_mut().now_or_never() {
-                    return Err(err.into());
-                }
-
-                // Sleep a short time otherwise in test environment it is a dead-loop that never
-                // yields.
-                // Because network implementation does not yield.
-                C::sleep(Duration::from_millis(1)).await;
-
-                snapshot.snapshot.seek(SeekFrom::Start(offset)).await.sto_res(subject_verb)?;
-
-                // Safe unwrap(): this function is called only by default implementation of
-                // `RaftNetwork::full_snapshot()` and it is always set.
-                let chunk_size = option.snapshot_chunk_size().unwrap();
-                let mut buf = Vec::with_capacity(chunk_size);
-                while buf.capacity() > buf.len() {
-                    let n = snapshot.snapshot.read_buf(&mut buf).await.sto_res(subject_verb)?;
-                    if n == 0 {
-                        break;
-                    }
-                }
-
-                let n_read = buf.len();
-
-                let done = (offset + n_read as u64) == end;
-                let req = InstallSnapshotRequest {
-                    vote: vote.clone(),
-                    meta: snapshot.meta.clone(),
-                    offset,
-                    data: buf,
-                    done,
+                // Read the next chunk ahead to tell if the current chunk is the last one.
+                let next = if data.len() < chunk_size {
+                    vec![]
+                } else {
+                    read_chunk(&mut snapshot.snapshot, chunk_size).await.sto_res(subject_verb)?
                 };
+                let done = next.is_empty();
+
+                // Send the current chunk until it is accepted. It is kept in memory for retrying,
+                // because the data can not be read again.
+                let resp = loop {
+                    // If canceled, return at once
+                    if let Some(err) = c.as_mut().now_or_never() {
+                        return Err(err.into());
+                    }
 
-                // Send the RPC over to the target.
-                tracing::debug!(
-                    snapshot_size = req.data.len(),
-                    req.offset,
-                    end,
-                    req.done,
-                    "sending snapshot chunk"
-                );
-
-                #[allow(deprecated)]
-                let res = C::timeout(option.hard_ttl(), net.install_snapshot(req, option.clone())).await;
-
-                let resp = match res {
-                    Ok(outer_res) => match outer_res {
-                        Ok(res) => res,
+                    // Sleep a short time otherwise in test environment it is a dead-loop that never
+                    // yields.
+                    // Because network implementation does not yield.
+                    C::sleep(Duration::from_millis(1)).await;
+
+                    let req = InstallSnapshotRequest {
+                        vote: vote.clone(),
+                        meta: snapshot.meta.clone(),
+                        offset,
+                        data: data.clone(),
+                        done,
+                    };
+
+                    // Send the RPC over to the target.
+                    tracing::debug!(
+                        snapshot_size = req.data.len(),
+                        req.offset,
+                        req.done,
+                        "sending snapshot chunk"
+                    );
+
+                    #[allow(deprecated)]
+                    let res = C::timeout(option.hard_ttl(), net.install_snapshot(req, option.clone())).await;
+
+                    let err = match res {
+                        Ok(Ok(resp)) => {
+                            option.add_snapshot_bytes_sent(data.len() as u64);
+                            break resp;
+                        }
+                        Ok(Err(err)) => err,
                         Err(err) => {
-                            let err: RPCError<C, RaftError<C, InstallSnapshotError>> = err;
-
-                            tracing::warn!(error=%err, "error sending InstallSnapshot RPC to target");
-
-                            match err {
-                                RPCError::Timeout(_) => {}
-                                RPCError::Unreachable(_) => {}
-                                RPCError::PayloadTooLarge(_) => {}
-                                RPCError::Network(_) => {}
-                                RPCError::RemoteError(remote_err) => {
-                                    //
-                                    match remote_err.source {
-                                        RaftError::Fatal(_) => {}
-                                        RaftError::APIError(snapshot_err) => {
-                                            //
-                                            match snapshot_err {
-                                                InstallSnapshotError::SnapshotMismatch(mismatch) => {
-                                                    //
-                                                    tracing::warn!(
-                                                        mismatch = display(&mismatch),
-                                                        "snapshot mismatch, reset offset and retry"
-                                                    );
-                                                    offset = 0;
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
+                            tracing::warn!(error=%err, "timeout while sending InstallSnapshot RPC to target");
                             continue;
                         }
-                    },
-                    Err(err) => {
-                        tracing::warn!(error=%err, "timeout while sending InstallSnapshot RPC to target");
-                        continue;
+                    };
+
+                    let err: RPCError<C, RaftError<C, InstallSnapshotError>> = err;
+                    tracing::warn!(error=%err, "error sending InstallSnapshot RPC to target");
+
+                    if let RPCError::RemoteError(remote_err) = &err {
+                        match &remote_err.source {
+                            RaftError::APIError(InstallSnapshotError::SnapshotMismatch(mismatch)) => {
+                                // The target lost the received chunks. The data can not be read
+                                // from the start again, let the replication re-send the snapshot.
+                                tracing::warn!(
+                                    mismatch = display(mismatch),
+                                    "snapshot mismatch, re-send the snapshot from the start"
+                                );
+                                return Err(StreamingError::Network(NetworkError::new(mismatch)));
+                            }
+                            RaftError::APIError(InstallSnapshotError::SnapshotVersionUnsupported(unsupported)) => {
+                                // The target can not read this snapshot until it is
+                                // upgraded. Re-sending it is useless.
+                                return Err(StreamingError::SnapshotVersionUnsupported(unsupported.clone()));
+                            }
+                            RaftError::Fatal(_) => {}
+                        }
                     }
                 };
 
@@ -155,10 +154,11 @@ mod tokio_rt {
                 }
 
                 if done {
-                    return Ok(SnapshotResponse::new(resp.vote));
+                    return Ok(resp.into());
                 }
 
-                offset += n_read as u64;
+                offset += data.len() as u64;
+                data = next;
             }
         }
 
@@ -175,19 +175,11 @@ mod tokio_rt {
 
             let curr_id = streaming.as_ref().map(|s| s.snapshot_id());
 
-            if curr_id != Some(snapshot_id) {
+            // A chunk at offset 0 of the current snapshot means the leader re-sends it from the
+            // start. The received data can not be rewound, thus start receiving again.
+            if curr_id != Some(snapshot_id) || req.offset == 0 {
                 if req.offset != 0 {
-                    let mismatch = InstallSnapshotError::SnapshotMismatch(crate::error::SnapshotMismatch {
-                        expect: crate::SnapshotSegmentId {
-                            id: snapshot_id.clone(),
-                            offset: 0,
-                        },
-                        got: crate::SnapshotSegmentId {
-                            id: snapshot_id.clone(),
-                            offset: req.offset,
-                        },
-                    });
-                    return Err(RaftError::APIError(mismatch));
+                    return Err(RaftError::APIError(snapshot_mismatch(snapshot_id, 0, req.offset)));
                 }
 
                 // Changed to another stream. re-init snapshot state.
@@ -201,6 +193,16 @@ mod tokio_rt {
 
             {
                 let s = streaming.as_mut().unwrap();
+
+                // A chunk after a lost one can not be written without seeking.
+                if req.offset > s.offset {
+                    return Err(RaftError::APIError(snapshot_mismatch(
+                        snapshot_id,
+                        s.offset,
+                        req.offset,
+                    )));
+                }
+
                 s.receive(req).await?;
             }
 
@@ -225,37 +227,70 @@ mod tokio_rt {
     impl<C> Streaming<C>
     where
         C: RaftTypeConfig,
-        C::SnapshotData: tokio::io::AsyncWrite + tokio::io::AsyncSeek + Unpin,
+        C::SnapshotData: tokio::io::AsyncWrite + Unpin,
     {
         /// Receive a chunk of snapshot data.
+        ///
+        /// The data is written sequentially. The part of the chunk that has already been written,
+        /// e.g., a chunk re-sent because the response is lost, is skipped. A chunk after the
+        /// written data is rejected, because the data in between is missing.
         pub async fn receive(&mut self, req: InstallSnapshotRequest<C>) -> Result<bool, StorageError<C>> {
             // TODO: check id?
 
-            // Always seek to the target offset if not an exact match.
-            if req.offset != self.offset {
-                if let Err(err) = self.snapshot_data.seek(SeekFrom::Start(req.offset)).await {
-                    return Err(StorageError::from_io_error(
-                        ErrorSubject::Snapshot(Some(req.meta.signature())),
-                        ErrorVerb::Seek,
-                        err,
-                    ));
-                }
-                self.offset = req.offset;
+            let subject = || ErrorSubject::Snapshot(Some(req.meta.signature()));
+
+            if req.offset > self.offset {
+                let err = io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("expect snapshot chunk at offset {}, got {}", self.offset, req.offset),
+                );
+                return Err(StorageError::from_io_error(subject(), ErrorVerb::Write, err));
+            }
+
+            let end = req.offset + req.data.len() as u64;
+            if end <= self.offset {
+                return Ok(req.done);
             }
 
-            // Write the next segment & update offset.
-            let res = self.snapshot_data.write_all(&req.data).await;
+            // Write the part not yet written & update offset.
+            let written = (self.offset - req.offset) as usize;
+            let res = self.snapshot_data.write_all(&req.data[written..]).await;
             if let Err(err) = res {
-                return Err(StorageError::from_io_error(
-                    ErrorSubject::Snapshot(Some(req.meta.signature())),
-                    ErrorVerb::Write,
-                    err,
-                ));
+                return Err(StorageError::from_io_error(subject(), ErrorVerb::Write, err));
             }
-            self.offset += req.data.len() as u64;
+            self.offset = end;
             Ok(req.done)
         }
     }
+
+    /// Read up to `chunk_size` bytes, fewer only if the end of the data is reached.
+    async fn read_chunk<R>(r: &mut R, chunk_size: usize) -> io::Result<Vec<u8>>
+    where R: tokio::io::AsyncRead + Unpin {
+        let mut buf = vec![0; chunk_size];
+        let mut n_read = 0;
+        while n_read < chunk_size {
+            let n = r.read(&mut buf[n_read..]).await?;
+            if n == 0 {
+                break;
+            }
+            n_read += n;
+        }
+        buf.truncate(n_read);
+        Ok(buf)
+    }
+
+    fn snapshot_mismatch(snapshot_id: &SnapshotId, expect: u64, got: u64) -> InstallSnapshotError {
+        InstallSnapshotError::SnapshotMismatch(SnapshotMismatch {
+            expect: SnapshotSegmentId {
+                id: snapshot_id.clone(),
+                offset: expect,
+            },
+            got: SnapshotSegmentId {
+                id: snapshot_id.clone(),
+                offset: got,
+            },
+        })
+    }
 }
 
 use std::future::Future;
@@ -382,13 +417,18 @@ mod tests {
     use std::io::Cursor;
     use std::time::Duration;
 
+    use tokio::io::AsyncWriteExt;
+
+    use crate::declare_raft_types;
     use crate::engine::testing::UTConfig;
     use crate::error::InstallSnapshotError;
     use crate::error::RPCError;
     use crate::error::RaftError;
     use crate::error::SnapshotMismatch;
+    use crate::error::StreamingError;
     use crate::network::snapshot_transport::Chunked;
     use crate::network::snapshot_transport::SnapshotTransport;
+    use crate::network::snapshot_transport::Streaming;
     use crate::network::RPCOption;
     use crate::raft::AppendEntriesRequest;
     use crate::raft::AppendEntriesResponse;
@@ -403,11 +443,34 @@ mod tests {
     use crate::StoredMembership;
     use crate::Vote;
 
+    // A config whose snapshot data can only be read or written sequentially.
+    declare_raft_types!(
+        NoSeekConfig:
+            D = (),
+            R = (),
+            Node = (),
+            SnapshotData = tokio::io::DuplexStream,
+    );
+
     struct Network {
-        received_offset: Vec<u64>,
+        /// The `(offset, data, done)` of every received chunk.
+        received: Vec<(u64, Vec<u8>, bool)>,
         match_cnt: u64,
     }
 
+    impl Network {
+        fn new(match_cnt: u64) -> Self {
+            Self {
+                received: vec![],
+                match_cnt,
+            }
+        }
+
+        fn received_offsets(&self) -> Vec<u64> {
+            self.received.iter().map(|(offset, _, _)| *offset).collect()
+        }
+    }
+
     impl<C> RaftNetwork<C> for Network
     where C: RaftTypeConfig<NodeId = u64>
     {
@@ -434,7 +497,7 @@ mod tests {
         ) -> Result<InstallSnapshotResponse<C>, RPCError<C, RaftError<C, InstallSnapshotError>>> {
             // A fake implementation to test the Chunked::send_snapshot.
 
-            self.received_offset.push(rpc.offset);
+            self.received.push((rpc.offset, rpc.data.clone(), rpc.done));
 
             // For the second last time, return a mismatch error.
             // Then return Ok for the reset of the time.
@@ -453,43 +516,106 @@ mod tests {
                 let err = RaftError::APIError(InstallSnapshotError::SnapshotMismatch(mismatch));
                 Err(RPCError::RemoteError(crate::error::RemoteError::new(0, err)))
             } else {
-                Ok(InstallSnapshotResponse { vote: rpc.vote })
+                Ok(InstallSnapshotResponse {
+                    vote: rpc.vote,
+                    malformed: None,
+                })
             }
         }
     }
 
-    /// Test that `Chunked` should reset the offset to 0 to re-send all data,
-    /// if a [`SnapshotMismatch`] error is received.
-    #[tokio::test]
-    async fn test_chunked_reset_offset_if_snapshot_id_mismatch() {
-        let mut net = Network {
-            received_offset: vec![],
-            // When match_cnt == 1, return a mismatch error.
-            // For other times, return Ok.
-            match_cnt: 4,
-        };
+    fn meta<C: RaftTypeConfig>() -> SnapshotMeta<C> {
+        SnapshotMeta::new(None, StoredMembership::default(), "1-1-1-1".to_string())
+    }
 
+    fn option(chunk_size: usize) -> RPCOption {
         let mut opt = RPCOption::new(Duration::from_millis(100));
-        opt.snapshot_chunk_size = Some(1);
+        opt.snapshot_chunk_size = Some(chunk_size);
+        opt
+    }
+
+    /// Test that `Chunked` returns a retryable error to let the replication re-send the snapshot
+    /// from the start, if a [`SnapshotMismatch`] error is received, because the snapshot data can
+    /// not be read again.
+    #[tokio::test]
+    async fn test_chunked_return_error_if_snapshot_id_mismatch() {
+        let mut net = Network::new(4);
+
         let cancel = futures::future::pending();
 
-        Chunked::send_snapshot(
+        let res = Chunked::send_snapshot(
             &mut net,
             Vote::new(1, 0),
-            Snapshot::<UTConfig>::new(
-                SnapshotMeta {
-                    last_log_id: None,
-                    last_membership: StoredMembership::default(),
-                    snapshot_id: "1-1-1-1".to_string(),
-                },
-                Cursor::new(vec![1, 2, 3]),
-            ),
+            Snapshot::<UTConfig>::new(meta(), Cursor::new(vec![1, 2, 3])),
             cancel,
-            opt,
+            option(1),
         )
-        .await
-        .unwrap();
+        .await;
+
+        assert!(matches!(res, Err(StreamingError::Network(_))), "got: {:?}", res);
+        assert_eq!(net.received_offsets(), vec![0, 1, 2]);
+    }
+
+    /// Test that `Chunked` sends snapshot data that can not seek, and marks the last chunk as done.
+    #[tokio::test]
+    async fn test_chunked_send_without_seek() -> anyhow::Result<()> {
+        for (data, want) in [
+            (vec![1, 2, 3, 4, 5], vec![
+                (0, vec![1, 2], false),
+                (2, vec![3, 4], false),
+                (4, vec![5], true),
+            ]),
+            (vec![1, 2, 3, 4], vec![(0, vec![1, 2], false), (2, vec![3, 4], true)]),
+            (vec![], vec![(0, vec![], true)]),
+        ] {
+            let (mut w, r) = tokio::io::duplex(64);
+            w.write_all(&data).await?;
+            drop(w);
+
+            let mut net = Network::new(0);
+            let cancel = futures::future::pending();
+
+            Chunked::send_snapshot(
+                &mut net,
+                Vote::new(1, 0),
+                Snapshot::<NoSeekConfig>::new(meta(), r),
+                cancel,
+                option(2),
+            )
+            .await?;
+
+            assert_eq!(want, net.received, "data: {:?}", data);
+        }
+
+        Ok(())
+    }
+
+    /// Test that `Streaming` writes chunks sequentially, skips the data already written, and
+    /// rejects a chunk after missing data.
+    #[tokio::test]
+    async fn test_streaming_receive_without_seek() -> anyhow::Result<()> {
+        let req = |offset: u64, data: Vec<u8>, done: bool| InstallSnapshotRequest::<UTConfig> {
+            vote: Vote::new(1, 0),
+            meta: meta(),
+            offset,
+            data,
+            done,
+        };
+
+        let mut s = Streaming::<UTConfig>::new("1-1-1-1".to_string(), Cursor::new(vec![]));
+
+        assert!(!s.receive(req(0, vec![1, 2], false)).await?);
+        // Re-sent chunk is skipped.
+        assert!(!s.receive(req(0, vec![1, 2], false)).await?);
+        // Overlapping chunk writes only the new part.
+        assert!(!s.receive(req(1, vec![2, 3], false)).await?);
+
+        let res = s.receive(req(5, vec![6], false)).await;
+        assert!(res.is_err(), "chunk after missing data is rejected");
+
+        assert!(s.receive(req(3, vec![4, 5], true)).await?);
 
-        assert_eq!(net.received_offset, vec![0, 1, 2, 0, 1, 2]);
+        assert_eq!(vec![1, 2, 3, 4, 5], s.into_snapshot_data().into_inner());
+        Ok(())
     }
 }
@@ -22,7 +22,7 @@ impl<C, V1> RaftNetworkV2<C> for V1
 where
     C: RaftTypeConfig,
     V1: RaftNetwork<C>,
-    C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io::AsyncSeek + Unpin,
+    C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
 {
     async fn append_entries(
         &mut self,
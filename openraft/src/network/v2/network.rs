@@ -5,12 +5,15 @@ use anyerror::AnyError;
 use openraft_macros::add_async_trait;
 use openraft_macros::since;
 
+use crate::error::CheckIsLeaderError;
 use crate::error::RPCError;
+use crate::error::RaftError;
 use crate::error::ReplicationClosed;
 use crate::error::StreamingError;
 use crate::error::Unreachable;
 use crate::network::Backoff;
 use crate::network::RPCOption;
+use crate::raft::message::ReadIndexRequest;
 use crate::raft::message::TransferLeaderRequest;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
@@ -18,6 +21,7 @@ use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::storage::Snapshot;
+use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
 use crate::OptionalSend;
 use crate::OptionalSync;
@@ -97,6 +101,28 @@ where C: RaftTypeConfig
         ))))
     }
 
+    /// Send a ReadIndex request to the leader, to get a read log id for a linearizable read on
+    /// this node.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_read_index()`] and respond
+    /// with its result.
+    ///
+    /// This method provide a default implementation that just return [`Unreachable`] error, in
+    /// which case [`Raft::ensure_linearizable_via_leader()`] fails on a non-leader node.
+    ///
+    /// [`Raft::handle_read_index()`]: crate::raft::Raft::handle_read_index
+    /// [`Raft::ensure_linearizable_via_leader()`]: crate::raft::Raft::ensure_linearizable_via_leader
+    #[since(version = "0.10.0")]
+    async fn read_index(
+        &mut self,
+        _req: ReadIndexRequest<C>,
+        _option: RPCOption,
+    ) -> Result<Option<LogIdOf<C>>, RPCError<C, RaftError<C, CheckIsLeaderError<C>>>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "read_index not implemented",
+        ))))
+    }
+
     /// Build a backoff instance if the target node is temporarily(or permanently) unreachable.
     ///
     /// When a [`Unreachable`](`crate::error::Unreachable`) error is returned from the `Network`
@@ -8,6 +8,9 @@ use crate::base::OptionalFeatures;
 /// A Raft node's ID.
 ///
 /// A `NodeId` uniquely identifies a node in the Raft cluster.
+///
+/// It does not have to be `Copy`: a `String` such as a hostname, or a UUID, can be used as a node
+/// id. Openraft clones it where an owned id is required.
 pub trait NodeId
 where Self: Sized
         + OptionalFeatures
@@ -33,7 +36,6 @@ impl<T> NodeId for T where T: Sized
         + Debug
         + Display
         + Hash
-        + Copy
         + Clone
         + Default
         + 'static
@@ -76,6 +76,16 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Update the matching log id of the leader itself, to which no log is replicated, thus there
+    /// is no inflight data to acknowledge.
+    pub(crate) fn update_local_matching(&mut self, matching: Option<LogIdOf<C>>) {
+        debug_assert!(matching.as_ref() >= self.entry.matching());
+        self.entry.matching = matching;
+
+        let matching_next = self.entry.matching().next_index();
+        self.entry.searching_end = std::cmp::max(self.entry.searching_end, matching_next);
+    }
+
     pub(crate) fn update_matching(&mut self, matching: Option<LogIdOf<C>>) {
         tracing::debug!(
             "update_matching: current progress_entry: {}; matching: {}",
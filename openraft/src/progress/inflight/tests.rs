@@ -103,13 +103,19 @@ fn test_inflight_ack() -> anyhow::Result<()> {
             assert_eq!(Inflight::<UTConfig>::None, f, "valid ack");
         }
 
+        {
+            let mut f = Inflight::<UTConfig>::snapshot(Some(log_id(5)));
+            f.ack(Some(log_id(6)));
+            assert_eq!(Inflight::<UTConfig>::None, f, "a newer snapshot is sent");
+        }
+
         {
             let res = std::panic::catch_unwind(|| {
                 let mut f = Inflight::<UTConfig>::snapshot(Some(log_id(5)));
                 f.ack(Some(log_id(4)));
             });
             tracing::info!("res: {:?}", res);
-            assert!(res.is_err(), "non-matching ack != snapshot.last_log_id");
+            assert!(res.is_err(), "ack < snapshot.last_log_id");
         }
     }
 
@@ -11,6 +11,9 @@ mod bench;
 pub(crate) mod entry;
 pub(crate) mod inflight;
 
+#[cfg(test)]
+mod progress_prop_test;
+
 use std::borrow::Borrow;
 use std::fmt::Debug;
 use std::fmt::Display;
@@ -354,11 +357,18 @@ where
             return Ok(&self.granted);
         }
 
-        // Sort and find the greatest value granted by a quorum set.
+        if !new_gt_granted {
+            return Ok(&self.granted);
+        }
 
-        if prev_le_granted && new_gt_granted {
-            let new_index = self.move_up(index);
+        // Values greater than granted must be kept sorted, even if the granted does not change.
+        let new_index = self.move_up(index);
 
+        // If the previous value is greater than granted, the nodes with a value greater than the
+        // previous one do not constitute a quorum, thus the granted does not change.
+        //
+        // Otherwise, find the greatest value granted by a quorum set.
+        if prev_le_granted {
             // From high to low, find the max value that has constituted a quorum.
             for i in new_index..self.voter_count {
                 let prog = self.vector[i].1.borrow();
@@ -574,6 +584,26 @@ mod t {
         Ok(())
     }
 
+    #[test]
+    fn vec_progress_update_value_greater_than_granted() -> anyhow::Result<()> {
+        let quorum_set: Vec<u64> = vec![0, 1, 2, 3, 4];
+        let mut progress = VecProgress::<u64, u64, u64, _>::new(quorum_set, [], || 0);
+
+        // initial: 0,0,0,0,0
+        let cases = [
+            ((0, 8), Ok(&0)), // 8,0,0,0,0
+            ((1, 7), Ok(&0)), // 8,7,0,0,0
+            ((1, 9), Ok(&0)), // 8,9,0,0,0 // 1 has to be moved before 0 though granted does not change
+            ((2, 9), Ok(&8)), // 8,9,9,0,0 // only 1,2 have 9
+        ];
+
+        for (ith, ((id, v), want_committed)) in cases.iter().enumerate() {
+            let got = progress.increase_to(id, *v);
+            assert_eq!(want_committed.clone(), got, "{}-th case: id:{}, v:{}", ith, id, v);
+        }
+        Ok(())
+    }
+
     /// Progress entry for testing
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     struct ProgressEntry {
@@ -0,0 +1,129 @@
+//! Property tests of [`VecProgress`].
+//!
+//! Random memberships, including joint configs and learners, and random sequences of
+//! [`Progress::increase_to()`] and [`Progress::upgrade_quorum_set()`] are generated by proptest.
+//! After every step the granted value is compared with the one calculated by brute force.
+//!
+//! A failure is shrunk to a minimal sequence of steps and is saved to `proptest-regressions/` to be
+//! replayed by later runs.
+
+use std::collections::BTreeSet;
+
+use maplit::btreeset;
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+use crate::engine::testing::UTConfig;
+use crate::progress::Progress;
+use crate::progress::VecProgress;
+use crate::quorum::Joint;
+use crate::quorum::QuorumSet;
+use crate::Membership;
+
+type QS = Joint<u64, Vec<u64>, Vec<Vec<u64>>>;
+
+/// The progress value, as the one used by the leader clock: `None` is not granted by anyone.
+type Value = Option<u64>;
+
+const NODES: u64 = 7;
+
+/// One step applied to a [`VecProgress`].
+#[derive(Debug, Clone)]
+enum Step {
+    /// Increase the value of the node at `node` in the current membership.
+    IncreaseTo {
+        node: Index,
+        value: Value,
+    },
+    Upgrade {
+        membership: Membership<UTConfig>,
+    },
+}
+
+/// Generate a membership with 1 or 2 configs, i.e., a uniform or joint config, and learners.
+fn membership() -> impl Strategy<Value = Membership<UTConfig>> {
+    let configs = prop::collection::vec(prop::collection::btree_set(0..NODES, 1..=5), 1..=2);
+    let learners = prop::collection::btree_set(0..NODES, 0..=NODES as usize);
+
+    (configs, learners).prop_map(|(configs, learners)| {
+        let voters = configs.iter().flatten().copied().collect::<BTreeSet<_>>();
+        let learners = learners.into_iter().filter(|id| !voters.contains(id)).collect::<Vec<_>>();
+        Membership::new_with_defaults(configs, learners)
+    })
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        9 => (any::<Index>(), prop::option::weighted(0.9, 0..=20u64))
+            .prop_map(|(node, value)| Step::IncreaseTo { node, value }),
+        1 => membership().prop_map(|membership| Step::Upgrade { membership }),
+    ]
+}
+
+/// The greatest value that the voters having a greater or equal value constitute a quorum.
+fn expected_granted(progress: &VecProgress<u64, Value, Value, QS>) -> Value {
+    let qs = progress.quorum_set();
+    let voters = progress.iter().filter(|(id, _)| progress.is_voter(id) == Some(true)).collect::<Vec<_>>();
+
+    let mut candidates = voters.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+    candidates.sort();
+
+    for v in candidates.into_iter().rev() {
+        let granting = voters.iter().filter(|(_, x)| *x >= v).map(|(id, _)| id);
+        if qs.is_quorum(granting) {
+            return v;
+        }
+    }
+
+    None
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1_000))]
+
+    #[test]
+    fn test_vec_progress_granted_by_quorum(
+        membership in membership(),
+        steps in prop::collection::vec(step(), 0..50),
+    ) {
+        let mut membership = membership;
+        let mut progress =
+            VecProgress::<u64, Value, Value, QS>::new(membership.to_quorum_set(), membership.learner_ids(), || None);
+
+        for step in steps {
+            let granted = match &step {
+                Step::IncreaseTo { node, value } => {
+                    let ids = membership.nodes().map(|(id, _)| *id).collect::<Vec<_>>();
+                    let id = ids[node.index(ids.len())];
+                    *progress.increase_to(&id, *value).unwrap()
+                }
+                Step::Upgrade { membership: m } => {
+                    membership = m.clone();
+                    progress =
+                        progress.upgrade_quorum_set(membership.to_quorum_set(), membership.learner_ids(), || None);
+                    *progress.granted()
+                }
+            };
+
+            prop_assert_eq!(expected_granted(&progress), granted, "after {:?}", step);
+        }
+    }
+}
+
+#[test]
+fn test_vec_progress_joint_learner_does_not_grant() -> anyhow::Result<()> {
+    // A joint config {0,1,2},{2,3,4} with learner 5: a learner with greater value grants nothing.
+    let m = Membership::<UTConfig>::new_with_defaults(vec![btreeset! {0,1,2}, btreeset! {2,3,4}], [5]);
+    let mut progress = VecProgress::<u64, Value, Value, QS>::new(m.to_quorum_set(), m.learner_ids(), || None);
+
+    for (id, v) in [(5, 9), (0, 3), (1, 3), (2, 2)] {
+        let _ = progress.increase_to(&id, Some(v));
+    }
+    assert_eq!(&None, progress.granted(), "only config {{0,1,2}} grants");
+
+    let _ = progress.increase_to(&3, Some(4));
+    assert_eq!(&Some(2), progress.granted());
+    assert_eq!(expected_granted(&progress), *progress.granted());
+
+    Ok(())
+}
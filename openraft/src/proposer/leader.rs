@@ -52,6 +52,9 @@ where C: RaftTypeConfig
     /// The time to send next heartbeat.
     pub(crate) next_heartbeat: InstantOf<C>,
 
+    /// The time this leader is established, i.e., when its vote is committed.
+    pub(crate) established_at: InstantOf<C>,
+
     last_log_id: Option<LogIdOf<C>>,
 
     /// The log id of the first log entry proposed by this leader,
@@ -125,6 +128,7 @@ where
             transfer_to: None,
             committed_vote: vote,
             next_heartbeat: C::now(),
+            established_at: C::now(),
             last_log_id: last_log_id.clone(),
             noop_log_id,
             progress: VecProgress::new(quorum_set.clone(), learner_ids.iter().cloned(), || {
@@ -0,0 +1,34 @@
+use std::fmt;
+
+/// Identifies a client write in the logs of every stage it goes through.
+///
+/// The id is logged when the write is appended to the leader's log, when it is submitted to the
+/// log store, when it is sent to a follower, and when it is applied, so that a single slow write
+/// can be traced. It is also passed to the network via [`RPCOption::correlation_ids()`], with
+/// which an application can propagate it to the remote node.
+///
+/// An application can provide its own id with [`Raft::client_write_with_correlation_id()`], e.g.,
+/// a request id that is unique in the cluster. Otherwise one is generated, which is only unique on
+/// this node.
+///
+/// [`RPCOption::correlation_ids()`]: crate::network::RPCOption::correlation_ids
+/// [`Raft::client_write_with_correlation_id()`]: crate::Raft::client_write_with_correlation_id
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CorrelationId(u64);
+
+impl CorrelationId {
+    pub fn new(id: u64) -> Self {
+        Self(id)
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cid-{}", self.0)
+    }
+}
@@ -20,6 +20,7 @@ declare_raft_types!(
         Term = u64,
         LeaderId = crate::impls::leader_id_std::LeaderId<Self>,
         Entry = crate::Entry<Self>,
+        EntryCodec = crate::impls::PassThroughCodec<Self>,
         Vote = crate::impls::Vote<Self>,
         SnapshotData = Cursor<Vec<u8>>,
         AsyncRuntime = TokioRuntime,
@@ -49,3 +50,32 @@ declare_raft_types!(
 declare_raft_types!(EmptyWithColon:);
 
 declare_raft_types!(Empty);
+
+#[test]
+fn test_default_types() {
+    use std::any::TypeId;
+
+    use crate::RaftTypeConfig;
+
+    fn assert_type<T: 'static, U: 'static>() {
+        assert_eq!(TypeId::of::<T>(), TypeId::of::<U>(), "{}", std::any::type_name::<T>());
+    }
+
+    assert_type::<<Empty as RaftTypeConfig>::D, String>();
+    assert_type::<<Empty as RaftTypeConfig>::R, String>();
+    assert_type::<<Empty as RaftTypeConfig>::NodeId, u64>();
+    assert_type::<<Empty as RaftTypeConfig>::Node, crate::impls::BasicNode>();
+    assert_type::<<Empty as RaftTypeConfig>::Term, u64>();
+    assert_type::<<Empty as RaftTypeConfig>::LeaderId, crate::impls::leader_id_adv::LeaderId<Empty>>();
+    assert_type::<<Empty as RaftTypeConfig>::Vote, crate::impls::Vote<Empty>>();
+    assert_type::<<Empty as RaftTypeConfig>::Entry, crate::impls::Entry<Empty>>();
+    assert_type::<<Empty as RaftTypeConfig>::EntryCodec, crate::impls::PassThroughCodec<Empty>>();
+    assert_type::<<Empty as RaftTypeConfig>::SnapshotData, Cursor<Vec<u8>>>();
+    assert_type::<<Empty as RaftTypeConfig>::Responder, crate::impls::OneshotResponder<Empty>>();
+    assert_type::<<Empty as RaftTypeConfig>::AsyncRuntime, TokioRuntime>();
+
+    // User defined types override the defaults, the others are filled with defaults.
+    assert_type::<<WithoutD as RaftTypeConfig>::D, String>();
+    assert_type::<<WithoutD as RaftTypeConfig>::R, ()>();
+    assert_type::<<WithoutD as RaftTypeConfig>::Node, ()>();
+}
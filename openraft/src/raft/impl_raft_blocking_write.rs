@@ -2,18 +2,33 @@
 //! Blocking mode write API blocks until the write operation is completed,
 //! where [`RaftTypeConfig::Responder`] is a [`OneshotResponder`].
 
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
 use maplit::btreemap;
+use maplit::btreeset;
+use openraft_macros::since;
 
+use crate::async_runtime::mutex::Mutex;
+use crate::async_runtime::watch::WatchReceiver;
 use crate::core::raft_msg::RaftMsg;
+use crate::core::replication_lag;
 use crate::display_ext::DisplayResult;
+use crate::error::ChangeMembershipError;
 use crate::error::ClientWriteError;
+use crate::error::InProgress;
+use crate::error::QueueFull;
 use crate::error::RaftError;
 use crate::raft::message::ClientWriteResult;
 use crate::raft::responder::OneshotResponder;
 use crate::raft::ClientWriteResponse;
+use crate::raft::LearnerCatchUp;
+use crate::raft::UnreachableRemoval;
 use crate::type_config::alias::OneshotReceiverOf;
 use crate::type_config::TypeConfigExt;
 use crate::ChangeMembers;
+use crate::LogIdOptionExt;
 use crate::Raft;
 use crate::RaftTypeConfig;
 
@@ -48,17 +63,200 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
     ///
     /// If it loses leadership or crashed before committing the second **uniform** config log, the
     /// cluster is left in the **joint** config.
+    ///
+    /// If a previous change is still in progress, it fails with [`InProgress`], unless
+    /// [`Config::membership_change_queue_depth`] is greater than 0, in which case it waits for the
+    /// previous changes to finish.
+    ///
+    /// To find out what a change would do without proposing it, use
+    /// [`Raft::change_membership_dry_run()`]; to reject a change that is not expected to commit,
+    /// use [`Raft::change_membership_checked()`].
+    ///
+    /// [`Config::membership_change_queue_depth`]: crate::Config::membership_change_queue_depth
     #[tracing::instrument(level = "info", skip_all)]
     pub async fn change_membership(
         &self,
         members: impl Into<ChangeMembers<C>>,
         retain: bool,
     ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
-        let changes: ChangeMembers<C> = members.into();
+        self.do_change_membership(members.into(), retain, false).await
+    }
+
+    /// Propose a cluster configuration change, if it passes the pre-flight check.
+    ///
+    /// It works the same as [`Raft::change_membership()`], except that before proposing the
+    /// **joint** config, the leader builds a [`MembershipChangeReport`] the same way
+    /// [`Raft::change_membership_dry_run()`] does. If the report is not ok, i.e., a previous change
+    /// is still in progress, a new voter is not a learner, or the up-to-date voters do not form a
+    /// quorum of the new configs, it returns a [`PreflightFailed`] error that includes the report,
+    /// and nothing is appended.
+    ///
+    /// The second step, from the **joint** config to the **uniform** config, is not checked,
+    /// because the **joint** config is already committed by the voters of the final config.
+    ///
+    /// [`MembershipChangeReport`]: crate::membership::MembershipChangeReport
+    /// [`PreflightFailed`]: crate::error::PreflightFailed
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "info", skip_all)]
+    pub async fn change_membership_checked(
+        &self,
+        members: impl Into<ChangeMembers<C>>,
+        retain: bool,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.do_change_membership(members.into(), retain, true).await
+    }
+
+    /// Promote a learner to a voter, leaving all other voters and learners unchanged.
+    ///
+    /// It is a shortcut of [`Raft::change_membership()`] with
+    /// [`ChangeMembers::AddVoterIds`], thus the change takes two steps through a **joint**
+    /// config, and it returns after the **uniform** config is committed.
+    ///
+    /// If `id` is not a learner, it returns a [`LearnerNotFound`] error.
+    ///
+    /// [`LearnerNotFound`]: crate::error::LearnerNotFound
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "info", skip_all, fields(id = display(&id)))]
+    pub async fn promote_learner(
+        &self,
+        id: C::NodeId,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.do_change_membership(ChangeMembers::AddVoterIds(btreeset! {id}), false, false).await
+    }
+
+    /// Demote a voter to a learner, leaving all other voters and learners unchanged.
+    ///
+    /// It is a shortcut of [`Raft::change_membership()`] with [`ChangeMembers::RemoveVoters`]
+    /// and `retain=true`, thus the demoted node keeps receiving logs as a learner. Use
+    /// [`ChangeMembers::RemoveNodes`] afterward to remove it from the cluster.
+    ///
+    /// If `id` is not a voter, the voter set is not changed. Demoting the last voter fails with
+    /// an [`EmptyMembership`] error.
+    ///
+    /// [`EmptyMembership`]: crate::error::EmptyMembership
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "info", skip_all, fields(id = display(&id)))]
+    pub async fn demote_voter(
+        &self,
+        id: C::NodeId,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.do_change_membership(ChangeMembers::RemoveVoters(btreeset! {id}), true, false).await
+    }
+
+    /// Remove the voters and learners that have not acknowledged the leader for at least
+    /// `min_downtime`, as long as the remaining voters can commit the change.
+    ///
+    /// The leader first builds a [`RemovalPlan`] from the last time every node acknowledged it. A
+    /// node that has never acknowledged the current leader is counted as down since the leader is
+    /// established, thus a newly elected leader does not remove anything before `min_downtime`
+    /// elapses. The leader itself is never removed.
+    ///
+    /// The unreachable voters are removed only if the reachable voters form a quorum of the
+    /// current config, i.e., only if the joint config can be committed without them. Nothing is
+    /// removed if a previous membership change is not committed yet.
+    ///
+    /// The voters are removed with [`ChangeMembers::RemoveVoters`] and `retain=false`, then the
+    /// learners with [`ChangeMembers::RemoveNodes`]. If the plan is empty, nothing is proposed and
+    /// the plan is returned with no responses.
+    ///
+    /// It returns a [`ForwardToLeader`] error if this node is not a leader.
+    ///
+    /// [`RemovalPlan`]: crate::membership::RemovalPlan
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn remove_unreachable_nodes(
+        &self,
+        min_downtime: Duration,
+    ) -> Result<UnreachableRemoval<C>, RaftError<C, ClientWriteError<C>>> {
+        let (tx, rx) = C::oneshot();
+        let plan = self.inner.call_core(RaftMsg::PlanUnreachableRemoval { min_downtime, tx }, rx).await?;
+
+        tracing::info!(plan = display(&plan), "remove_unreachable_nodes: plan built");
+
+        let mut responses = vec![];
+
+        if !plan.remove_voters.is_empty() {
+            let changes = ChangeMembers::RemoveVoters(plan.remove_voters.clone());
+            responses.push(self.do_change_membership(changes, false, false).await?);
+        }
+
+        if !plan.remove_learners.is_empty() {
+            let changes = ChangeMembers::RemoveNodes(plan.remove_learners.clone());
+            responses.push(self.do_change_membership(changes, false, false).await?);
+        }
+
+        Ok(UnreachableRemoval { plan, responses })
+    }
+
+    /// Change the membership in two steps, waiting in the queue for the previous changes if
+    /// [`Config::membership_change_queue_depth`] is greater than 0.
+    ///
+    /// [`Config::membership_change_queue_depth`]: crate::Config::membership_change_queue_depth
+    async fn do_change_membership(
+        &self,
+        changes: ChangeMembers<C>,
+        retain: bool,
+        preflight: bool,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        let depth = self.inner.config.membership_change_queue_depth;
+        if depth == 0 {
+            return self.change_membership_in_two_steps(changes, retain, preflight).await;
+        }
+
+        let waiting = WaitingGuard::new(&self.inner.membership_change_waiting);
+        if waiting.ahead >= depth {
+            tracing::info!(
+                changes = debug(&changes),
+                waiting = display(waiting.ahead),
+                depth = display(depth),
+                "change_membership: queue is full"
+            );
+            let err = ChangeMembershipError::QueueFull(QueueFull {
+                waiting: waiting.ahead,
+                depth,
+            });
+            return Err(RaftError::APIError(ClientWriteError::ChangeMembershipError(err)));
+        }
+
+        let _lock = self.inner.membership_change_lock.lock().await;
+        drop(waiting);
+
+        loop {
+            let res = self.change_membership_in_two_steps(changes.clone(), retain, preflight).await;
+
+            // A change not submitted through this queue, e.g., by `add_learner()`, is in progress:
+            // wait for it to be applied and try again.
+            if let Err(RaftError::APIError(ClientWriteError::ChangeMembershipError(
+                ChangeMembershipError::InProgress(InProgress {
+                    membership_log_id: Some(log_id),
+                    ..
+                }),
+            ))) = &res
+            {
+                tracing::info!(
+                    "change_membership: wait for the membership at {} to be applied before proposing {:?}",
+                    log_id,
+                    changes
+                );
+                self.wait_applied(log_id).await?;
+                continue;
+            }
+
+            return res;
+        }
+    }
 
+    async fn change_membership_in_two_steps(
+        &self,
+        changes: ChangeMembers<C>,
+        retain: bool,
+        preflight: bool,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
         tracing::info!(
             changes = debug(&changes),
             retain = display(retain),
+            preflight = display(preflight),
             "change_membership: start to commit joint config"
         );
 
@@ -72,6 +270,7 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
                 RaftMsg::ChangeMembership {
                     changes: changes.clone(),
                     retain,
+                    preflight,
                     tx,
                 },
                 rx,
@@ -96,7 +295,13 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
 
         let (tx, rx) = oneshot_channel::<C>();
 
-        let res = self.inner.call_core(RaftMsg::ChangeMembership { changes, retain, tx }, rx).await;
+        let msg = RaftMsg::ChangeMembership {
+            changes,
+            retain,
+            preflight: false,
+            tx,
+        };
+        let res = self.inner.call_core(msg, rx).await;
 
         if let Err(e) = &res {
             tracing::error!("the second step error: {}", e);
@@ -136,6 +341,7 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
         let msg = RaftMsg::ChangeMembership {
             changes: ChangeMembers::AddNodes(btreemap! {id.clone()=>node}),
             retain: true,
+            preflight: false,
             tx,
         };
 
@@ -157,7 +363,12 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
         let wait_res = self
             .wait(None)
             .metrics(
-                |metrics| match self.check_replication_upto_date(metrics, &id, Some(membership_log_id)) {
+                |metrics| match self.check_replication_upto_date(
+                    metrics,
+                    &id,
+                    Some(membership_log_id),
+                    self.inner.config.replication_lag_threshold,
+                ) {
                     Ok(_matching) => true,
                     // keep waiting
                     Err(_) => false,
@@ -173,6 +384,122 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
 
         Ok(resp)
     }
+
+    /// Add a new learner raft node and wait until its replication lag is at most `max_lag`
+    /// entries, or `timeout` elapses.
+    ///
+    /// It is similar to [`Raft::add_learner()`] with `blocking` set to `true`, but the allowed lag
+    /// and the time to wait are given by the caller, and it returns the replication progress of
+    /// the learner when it stops waiting. Check [`LearnerCatchUp::caught_up`] to tell whether the
+    /// learner is ready to be promoted to a voter.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, id, node), fields(target=display(&id)))]
+    pub async fn add_learner_and_wait(
+        &self,
+        id: C::NodeId,
+        node: C::Node,
+        max_lag: u64,
+        timeout: Duration,
+    ) -> Result<LearnerCatchUp<C>, RaftError<C, ClientWriteError<C>>> {
+        let resp = self.add_learner(id.clone(), node, false).await?;
+
+        if self.inner.id != id {
+            let wait_res = self
+                .wait(Some(timeout))
+                .metrics(
+                    |metrics| self.check_replication_upto_date(metrics, &id, Some(&resp.log_id), max_lag).is_ok(),
+                    "wait new learner to catch up",
+                )
+                .await;
+
+            tracing::info!(
+                wait_res = display(DisplayResult(&wait_res)),
+                "waiting for replication to new learner"
+            );
+        }
+
+        let metrics = self.metrics().borrow_watched().clone();
+
+        let matched = if self.inner.id == id {
+            metrics.last_applied.clone()
+        } else {
+            metrics.replication.as_ref().and_then(|r| r.get(&id)).cloned().flatten()
+        };
+
+        let caught_up = if self.inner.id == id {
+            true
+        } else {
+            metrics
+                .replication
+                .as_ref()
+                .and_then(|r| r.get(&id))
+                .map(|m| replication_lag(&m.index(), &metrics.last_log_index) <= max_lag)
+                .unwrap_or(false)
+        };
+
+        Ok(LearnerCatchUp {
+            response: resp,
+            matched,
+            leader_last_log_index: metrics.last_log_index,
+            caught_up,
+        })
+    }
+
+    /// Replace the node, e.g., the network address, of an existing voter or learner.
+    ///
+    /// It proposes a membership config in which only the node of `node_id` is changed, and blocks
+    /// until it is committed. The voter and learner sets are not changed, thus it takes only one
+    /// step. Since the node is stored in the membership config, every member, and the
+    /// [`RaftNetworkFactory`] of every leader elected afterwards, sees the new node.
+    ///
+    /// If `node_id` is not in the membership config, it returns a [`NodeNotFound`] error. It
+    /// also fails with [`InProgress`] if a previous membership change is not yet committed.
+    ///
+    /// Updating a node to the address of another node may lead to a brain split, see:
+    /// [Update-Node](crate::docs::cluster_control::dynamic_membership#update-node).
+    ///
+    /// [`RaftNetworkFactory`]: crate::network::RaftNetworkFactory
+    /// [`NodeNotFound`]: crate::error::NodeNotFound
+    /// [`InProgress`]: crate::error::InProgress
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, node_id, node), fields(target=display(&node_id)))]
+    pub async fn update_node(
+        &self,
+        node_id: C::NodeId,
+        node: C::Node,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        let (tx, rx) = oneshot_channel::<C>();
+
+        let msg = RaftMsg::ChangeMembership {
+            changes: ChangeMembers::UpdateNodes(btreemap! {node_id=>node}),
+            retain: true,
+            preflight: false,
+            tx,
+        };
+
+        self.inner.call_core(msg, rx).await
+    }
+}
+
+/// Counts a membership change waiting in the queue, until it is dropped.
+struct WaitingGuard<'a> {
+    waiting: &'a AtomicU64,
+
+    /// The number of changes that were already waiting when this one joined.
+    ahead: u64,
+}
+
+impl<'a> WaitingGuard<'a> {
+    fn new(waiting: &'a AtomicU64) -> Self {
+        let ahead = waiting.fetch_add(1, Ordering::Relaxed);
+        Self { waiting, ahead }
+    }
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.waiting.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 fn oneshot_channel<C>() -> (OneshotResponder<C>, OneshotReceiverOf<C, ClientWriteResult<C>>)
@@ -0,0 +1,60 @@
+//! The replication progress of a newly added learner.
+
+use std::fmt;
+use std::fmt::Debug;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::raft::ClientWriteResponse;
+use crate::type_config::alias::LogIdOf;
+use crate::RaftTypeConfig;
+
+/// The result of [`Raft::add_learner_and_wait()`](crate::Raft::add_learner_and_wait): the
+/// replication progress of the learner when the leader stops waiting for it to catch up.
+pub struct LearnerCatchUp<C>
+where C: RaftTypeConfig
+{
+    /// The response of the membership change that adds the learner.
+    pub response: ClientWriteResponse<C>,
+
+    /// The last log id replicated to the learner, as known by the leader.
+    pub matched: Option<LogIdOf<C>>,
+
+    /// The last log index on the leader.
+    pub leader_last_log_index: Option<u64>,
+
+    /// Whether the learner is within the allowed lag.
+    ///
+    /// It is `false` if the timeout elapses first, or the waiting is aborted because this node is
+    /// no longer the leader or the learner is removed.
+    pub caught_up: bool,
+}
+
+impl<C> Debug for LearnerCatchUp<C>
+where
+    C: RaftTypeConfig,
+    C::R: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LearnerCatchUp")
+            .field("response", &self.response)
+            .field("matched", &self.matched)
+            .field("leader_last_log_index", &self.leader_last_log_index)
+            .field("caught_up", &self.caught_up)
+            .finish()
+    }
+}
+
+impl<C> fmt::Display for LearnerCatchUp<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LearnerCatchUp{{matched: {}, leader_last_log_index: {}, caught_up: {}, response: {}}}",
+            self.matched.display(),
+            self.leader_last_log_index.display(),
+            self.caught_up,
+            self.response
+        )
+    }
+}
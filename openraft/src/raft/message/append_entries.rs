@@ -1,7 +1,14 @@
 use std::fmt;
 
+use openraft_macros::since;
+
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySlice;
+use crate::entry::EntryCodec;
+use crate::error::MalformedRequest;
+use crate::type_config::alias::EncodedEntryOf;
+use crate::type_config::alias::EntryCodecErrorOf;
+use crate::type_config::alias::EntryCodecOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
 use crate::RaftTypeConfig;
@@ -56,6 +63,70 @@ where C: RaftTypeConfig
     }
 }
 
+impl<C> AppendEntriesRequest<C>
+where C: RaftTypeConfig
+{
+    /// Convert the entries with [`RaftTypeConfig::EntryCodec`] to the form sent over the network.
+    #[since(version = "0.10.0")]
+    pub fn encode(self) -> Result<EncodedAppendEntriesRequest<C>, EntryCodecErrorOf<C>> {
+        let entries = self.entries.into_iter().map(EntryCodecOf::<C>::encode).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(EncodedAppendEntriesRequest {
+            vote: self.vote,
+            prev_log_id: self.prev_log_id,
+            entries,
+            leader_commit: self.leader_commit,
+        })
+    }
+}
+
+/// An [`AppendEntriesRequest`] whose entries are encoded by [`RaftTypeConfig::EntryCodec`].
+///
+/// It is the form of an [`AppendEntriesRequest`] sent over the network: a network implementation
+/// sends it instead of [`AppendEntriesRequest`] to avoid serializing the entries twice.
+#[since(version = "0.10.0")]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct EncodedAppendEntriesRequest<C: RaftTypeConfig> {
+    pub vote: VoteOf<C>,
+
+    pub prev_log_id: Option<LogIdOf<C>>,
+
+    /// The encoded log entries to store.
+    pub entries: Vec<EncodedEntryOf<C>>,
+
+    /// The leader's committed log id.
+    pub leader_commit: Option<LogIdOf<C>>,
+}
+
+impl<C: RaftTypeConfig> fmt::Debug for EncodedAppendEntriesRequest<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncodedAppendEntriesRequest")
+            .field("vote", &self.vote)
+            .field("prev_log_id", &self.prev_log_id)
+            .field("entries", &self.entries)
+            .field("leader_commit", &self.leader_commit)
+            .finish()
+    }
+}
+
+impl<C> EncodedAppendEntriesRequest<C>
+where C: RaftTypeConfig
+{
+    /// Convert the entries back with [`RaftTypeConfig::EntryCodec`] to an
+    /// [`AppendEntriesRequest`].
+    #[since(version = "0.10.0")]
+    pub fn decode(self) -> Result<AppendEntriesRequest<C>, EntryCodecErrorOf<C>> {
+        let entries = self.entries.into_iter().map(EntryCodecOf::<C>::decode).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(AppendEntriesRequest {
+            vote: self.vote,
+            prev_log_id: self.prev_log_id,
+            entries,
+            leader_commit: self.leader_commit,
+        })
+    }
+}
+
 /// The response to an `AppendEntriesRequest`.
 ///
 /// [`RaftNetwork::append_entries`] returns this type only when received an RPC reply.
@@ -96,6 +167,12 @@ pub enum AppendEntriesResponse<C: RaftTypeConfig> {
     /// And a leader's vote(committed vote) must be total order with other vote.
     /// Therefore it has to be a higher vote: `mine_vote < v`
     HigherVote(VoteOf<C>),
+
+    /// The request is malformed and nothing in it is appended, e.g., the entries are not
+    /// consecutive or conflict with the committed logs on the remote target node.
+    ///
+    /// A correct leader never receives it. The leader retries the replication after a backoff.
+    Malformed(MalformedRequest<C>),
 }
 
 impl<C> AppendEntriesResponse<C>
@@ -121,6 +198,7 @@ where C: RaftTypeConfig
             }
             AppendEntriesResponse::HigherVote(vote) => write!(f, "Higher vote, {}", vote),
             AppendEntriesResponse::Conflict => write!(f, "Conflict"),
+            AppendEntriesResponse::Malformed(reason) => write!(f, "Malformed: {}", reason),
         }
     }
 }
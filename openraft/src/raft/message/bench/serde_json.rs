@@ -0,0 +1,51 @@
+extern crate test;
+
+use test::black_box;
+use test::Bencher;
+
+use crate::engine::testing::log_id;
+use crate::engine::testing::UTConfig;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+use crate::raft::VoteRequest;
+use crate::testing::blank_ent;
+use crate::Vote;
+
+fn append_entries_request(n: u64) -> AppendEntriesRequest<UTConfig> {
+    AppendEntriesRequest {
+        vote: Vote::new_committed(1, 1),
+        prev_log_id: Some(log_id(1, 1, 0)),
+        entries: (1..=n).map(|i| blank_ent(1, 1, i)).collect(),
+        leader_commit: Some(log_id(1, 1, 0)),
+    }
+}
+
+#[bench]
+fn serialize_append_entries_request_0(b: &mut Bencher) {
+    let req = append_entries_request(0);
+    b.iter(|| serde_json::to_vec(black_box(&req)).unwrap())
+}
+
+#[bench]
+fn serialize_append_entries_request_64(b: &mut Bencher) {
+    let req = append_entries_request(64);
+    b.iter(|| serde_json::to_vec(black_box(&req)).unwrap())
+}
+
+#[bench]
+fn deserialize_append_entries_request_64(b: &mut Bencher) {
+    let buf = serde_json::to_vec(&append_entries_request(64)).unwrap();
+    b.iter(|| serde_json::from_slice::<AppendEntriesRequest<UTConfig>>(black_box(&buf)).unwrap())
+}
+
+#[bench]
+fn serialize_append_entries_response(b: &mut Bencher) {
+    let resp = AppendEntriesResponse::<UTConfig>::Success;
+    b.iter(|| serde_json::to_vec(black_box(&resp)).unwrap())
+}
+
+#[bench]
+fn serialize_vote_request(b: &mut Bencher) {
+    let req = VoteRequest::<UTConfig>::new(Vote::new(2, 1), Some(log_id(1, 1, 64)));
+    b.iter(|| serde_json::to_vec(black_box(&req)).unwrap())
+}
@@ -1,5 +1,9 @@
 use std::fmt;
 
+use openraft_macros::since;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::error::MalformedRequest;
 use crate::storage::SnapshotMeta;
 use crate::type_config::alias::VoteOf;
 use crate::RaftTypeConfig;
@@ -41,25 +45,48 @@ impl<C: RaftTypeConfig> fmt::Display for InstallSnapshotRequest<C> {
 #[derive(Debug)]
 #[derive(PartialEq, Eq)]
 #[derive(derive_more::Display)]
-#[display("{{vote:{}}}", vote)]
+#[display("{{vote:{}, malformed:{}}}", vote, malformed.display())]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 pub struct InstallSnapshotResponse<C: RaftTypeConfig> {
     pub vote: VoteOf<C>,
+
+    /// The snapshot is rejected and not installed, because the request is malformed.
+    ///
+    /// Since: 0.10.0
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub malformed: Option<MalformedRequest<C>>,
 }
 
 /// The response to `Raft::install_full_snapshot` API.
 #[derive(Debug)]
 #[derive(PartialEq, Eq)]
 #[derive(derive_more::Display)]
-#[display("SnapshotResponse{{vote:{}}}", vote)]
+#[display("SnapshotResponse{{vote:{}, malformed:{}}}", vote, malformed.display())]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 pub struct SnapshotResponse<C: RaftTypeConfig> {
     pub vote: VoteOf<C>,
+
+    /// The snapshot is rejected and not installed, because the request is malformed.
+    ///
+    /// A correct leader never receives it. The leader retries the replication after a backoff.
+    ///
+    /// Since: 0.10.0
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub malformed: Option<MalformedRequest<C>>,
 }
 
 impl<C: RaftTypeConfig> SnapshotResponse<C> {
     pub fn new(vote: VoteOf<C>) -> Self {
-        Self { vote }
+        Self { vote, malformed: None }
+    }
+
+    /// Create a response that rejects a malformed snapshot request.
+    #[since(version = "0.10.0")]
+    pub fn new_malformed(vote: VoteOf<C>, reason: MalformedRequest<C>) -> Self {
+        Self {
+            vote,
+            malformed: Some(reason),
+        }
     }
 }
 
@@ -67,6 +94,20 @@ impl<C> From<SnapshotResponse<C>> for InstallSnapshotResponse<C>
 where C: RaftTypeConfig
 {
     fn from(snap_resp: SnapshotResponse<C>) -> Self {
-        Self { vote: snap_resp.vote }
+        Self {
+            vote: snap_resp.vote,
+            malformed: snap_resp.malformed,
+        }
+    }
+}
+
+impl<C> From<InstallSnapshotResponse<C>> for SnapshotResponse<C>
+where C: RaftTypeConfig
+{
+    fn from(resp: InstallSnapshotResponse<C>) -> Self {
+        Self {
+            vote: resp.vote,
+            malformed: resp.malformed,
+        }
     }
 }
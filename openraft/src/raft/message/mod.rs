@@ -3,8 +3,14 @@
 //! Request and response types for an application to talk to the Raft,
 //! and are also used by network layer to talk to other Raft nodes.
 
+#[cfg(feature = "bench")]
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod bench;
+
 mod append_entries;
 mod install_snapshot;
+mod read_index;
 mod transfer_leader;
 mod vote;
 
@@ -12,11 +18,13 @@ mod client_write;
 
 pub use append_entries::AppendEntriesRequest;
 pub use append_entries::AppendEntriesResponse;
+pub use append_entries::EncodedAppendEntriesRequest;
 pub use client_write::ClientWriteResponse;
 pub use client_write::ClientWriteResult;
 pub use install_snapshot::InstallSnapshotRequest;
 pub use install_snapshot::InstallSnapshotResponse;
 pub use install_snapshot::SnapshotResponse;
+pub use read_index::ReadIndexRequest;
 pub use transfer_leader::TransferLeaderRequest;
 pub use vote::VoteRequest;
 pub use vote::VoteResponse;
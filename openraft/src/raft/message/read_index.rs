@@ -0,0 +1,39 @@
+use std::fmt;
+
+use crate::RaftTypeConfig;
+
+/// A request from a follower or learner to ask the leader for a read log id.
+///
+/// The leader confirms its leadership and responds with the log id up to which the requesting node
+/// should apply before serving a linearizable read locally.
+/// See: [`Raft::ensure_linearizable_via_leader()`](crate::Raft::ensure_linearizable_via_leader).
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ReadIndexRequest<C>
+where C: RaftTypeConfig
+{
+    /// The node that sends this request.
+    pub(crate) from: C::NodeId,
+}
+
+impl<C> ReadIndexRequest<C>
+where C: RaftTypeConfig
+{
+    pub fn new(from: C::NodeId) -> Self {
+        Self { from }
+    }
+
+    /// The node that sends this request.
+    pub fn from(&self) -> &C::NodeId {
+        &self.from
+    }
+}
+
+impl<C> fmt::Display for ReadIndexRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(from={})", self.from)
+    }
+}
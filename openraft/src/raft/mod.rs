@@ -8,22 +8,35 @@
 //! This allows multiple components within the application that require interaction with `RaftCore`
 //! to efficiently share access.
 
+mod correlation_id;
 #[cfg(test)]
 mod declare_raft_types_test;
 mod impl_raft_blocking_write;
+mod learner_catch_up;
 pub(crate) mod message;
+mod raft_event;
+mod raft_event_listener;
+mod raft_event_receiver;
 mod raft_inner;
+mod read_ticket;
 pub mod responder;
+mod response_handle;
 mod runtime_config_handle;
+mod shutdown_report;
+mod state_report;
 pub mod trigger;
+mod unreachable_removal;
 
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
 use std::error::Error;
 
 pub(in crate::raft) mod core_state;
 
 use std::fmt::Debug;
 use std::future::Future;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -32,8 +45,10 @@ pub use message::AppendEntriesRequest;
 pub use message::AppendEntriesResponse;
 pub use message::ClientWriteResponse;
 pub use message::ClientWriteResult;
+pub use message::EncodedAppendEntriesRequest;
 pub use message::InstallSnapshotRequest;
 pub use message::InstallSnapshotResponse;
+pub use message::ReadIndexRequest;
 pub use message::SnapshotResponse;
 pub use message::TransferLeaderRequest;
 pub use message::VoteRequest;
@@ -50,8 +65,13 @@ use crate::base::BoxAsyncOnceMut;
 use crate::base::BoxFuture;
 use crate::base::BoxOnce;
 use crate::config::Config;
+use crate::config::ConfigPatch;
+use crate::config::EffectiveConfig;
 use crate::config::RuntimeConfig;
+use crate::core::correlation_ids::CorrelationIds;
+use crate::core::event_broadcast::EventBroadcast;
 use crate::core::heartbeat::handle::HeartbeatWorkersHandle;
+use crate::core::io_counters::IoCounters;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::replication_lag;
@@ -62,26 +82,53 @@ use crate::core::Tick;
 use crate::display_ext::DisplayOptionExt;
 use crate::engine::Engine;
 use crate::engine::EngineConfig;
+use crate::engine::EngineDecision;
 use crate::error::CheckIsLeaderError;
 use crate::error::ClientWriteError;
+use crate::error::ClientWriteTimeout;
 use crate::error::Fatal;
+use crate::error::ForwardToLeader;
 use crate::error::Infallible;
 use crate::error::InitializeError;
 use crate::error::InvalidStateMachineType;
 use crate::error::RaftError;
+use crate::error::ReadIndexError;
+use crate::error::SnapshotVersionUnsupported;
 use crate::membership::IntoNodes;
+use crate::membership::MembershipChangeReport;
+use crate::membership::MembershipValidator;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftMetrics;
+use crate::metrics::RaftNetworkMetrics;
+use crate::metrics::RaftReplicationMetrics;
 use crate::metrics::RaftServerMetrics;
+use crate::metrics::RaftStatus;
+use crate::metrics::ServerStateChange;
 use crate::metrics::Wait;
 use crate::metrics::WaitError;
+pub use crate::raft::correlation_id::CorrelationId;
+pub use crate::raft::learner_catch_up::LearnerCatchUp;
+pub use crate::raft::raft_event::RaftEvent;
+pub use crate::raft::raft_event_listener::RaftEventListener;
+pub use crate::raft::raft_event_receiver::RaftEventReceiver;
+pub use crate::raft::raft_event_receiver::RecvEventError;
+pub use crate::raft::raft_event_receiver::EVENT_QUEUE_SIZE;
 use crate::raft::raft_inner::RaftInner;
+pub use crate::raft::read_ticket::ReadTicket;
 use crate::raft::responder::Responder;
+pub use crate::raft::response_handle::ResponseHandle;
 pub use crate::raft::runtime_config_handle::RuntimeConfigHandle;
+pub use crate::raft::shutdown_report::ShutdownReport;
+pub use crate::raft::state_report::StateCheck;
+pub use crate::raft::state_report::StateReport;
 use crate::raft::trigger::Trigger;
+pub use crate::raft::unreachable_removal::UnreachableRemoval;
+use crate::raft_state::LogStateReader;
 use crate::storage::RaftLogStorage;
 use crate::storage::RaftStateMachine;
 use crate::storage::Snapshot;
+use crate::storage::SnapshotMeta;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::JoinErrorOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::ResponderOf;
@@ -91,13 +138,18 @@ use crate::type_config::alias::VoteOf;
 use crate::type_config::alias::WatchReceiverOf;
 use crate::type_config::TypeConfigExt;
 use crate::vote::raft_vote::RaftVoteExt;
+use crate::ChangeMembers;
+use crate::ConfigError;
+use crate::Instant;
 use crate::LogIdOptionExt;
 use crate::LogIndexOptionExt;
 use crate::OptionalSend;
 use crate::RaftNetworkFactory;
 use crate::RaftState;
 pub use crate::RaftTypeConfig;
+use crate::ServerState;
 use crate::StorageHelper;
+use crate::StoredMembership;
 
 /// Define types for a Raft type configuration.
 ///
@@ -119,6 +171,7 @@ use crate::StorageHelper;
 ///        LeaderId     = openraft::impls::leader_id_adv::LeaderId<Self>,
 ///        Vote         = openraft::impls::Vote<Self>,
 ///        Entry        = openraft::Entry<Self>,
+///        EntryCodec   = openraft::impls::PassThroughCodec<Self>,
 ///        SnapshotData = Cursor<Vec<u8>>,
 ///        Responder    = openraft::impls::OneshotResponder<Self>,
 ///        AsyncRuntime = openraft::TokioRuntime,
@@ -134,6 +187,7 @@ use crate::StorageHelper;
 /// - `LeaderId`:     `::openraft::impls::leader_id_adv::LeaderId<Self>`
 /// - `Vote`:         `::openraft::impls::Vote<Self>`
 /// - `Entry`:        `::openraft::impls::Entry<Self>`
+/// - `EntryCodec`:   `::openraft::impls::PassThroughCodec<Self>`
 /// - `SnapshotData`: `Cursor<Vec<u8>>`
 /// - `Responder`:    `::openraft::impls::OneshotResponder<Self>`
 /// - `AsyncRuntime`: `::openraft::impls::TokioRuntime`
@@ -183,6 +237,7 @@ macro_rules! declare_raft_types {
                 (LeaderId     , , $crate::impls::leader_id_adv::LeaderId<Self> ),
                 (Vote         , , $crate::impls::Vote<Self>                    ),
                 (Entry        , , $crate::impls::Entry<Self>                   ),
+                (EntryCodec   , , $crate::impls::PassThroughCodec<Self>        ),
                 (SnapshotData , , std::io::Cursor<Vec<u8>>                     ),
                 (Responder    , , $crate::impls::OneshotResponder<Self>        ),
                 (AsyncRuntime , , $crate::impls::TokioRuntime                  ),
@@ -257,6 +312,10 @@ where C: RaftTypeConfig
         let (tx_metrics, rx_metrics) = C::watch_channel(RaftMetrics::new_initial(id.clone()));
         let (tx_data_metrics, rx_data_metrics) = C::watch_channel(RaftDataMetrics::default());
         let (tx_server_metrics, rx_server_metrics) = C::watch_channel(RaftServerMetrics::default());
+        let (tx_network_metrics, rx_network_metrics) = C::watch_channel(RaftNetworkMetrics::default());
+        let (tx_replication_metrics, rx_replication_metrics) = C::watch_channel(RaftReplicationMetrics::default());
+        let events = Arc::new(EventBroadcast::new());
+        let io_counters = Arc::new(IoCounters::default());
         let (tx_shutdown, rx_shutdown) = C::oneshot();
 
         let tick_handle = Tick::spawn(
@@ -284,11 +343,18 @@ where C: RaftTypeConfig
 
         let engine = Engine::new(state, eng_config);
 
+        let (tx_applied, rx_applied) = C::watch_channel(engine.state.io_applied().cloned());
+        let (tx_server_state, rx_server_state) = C::watch_channel(ServerStateChange::new(
+            engine.state.server_state,
+            engine.state.vote_ref().clone(),
+        ));
+
         let sm_span = tracing::span!(parent: &core_span, Level::DEBUG, "sm_worker");
 
         let sm_handle = worker::Worker::spawn(
             state_machine,
             log_store.get_log_reader().await,
+            config.apply_batch_size,
             tx_notify.clone(),
             sm_span,
         );
@@ -304,10 +370,17 @@ where C: RaftTypeConfig
             engine,
 
             client_resp_channels: BTreeMap::new(),
+            client_write_spans: BTreeMap::new(),
+            correlation_ids: CorrelationIds::new(),
+            io_counters: io_counters.clone(),
+            accept_writes: true,
+            drained_writes: vec![],
+            membership_validator: None,
+            shutdown_when_removed: false,
 
             replications: Default::default(),
 
-            heartbeat_handle: HeartbeatWorkersHandle::new(id.clone(), config.clone()),
+            heartbeat_handle: HeartbeatWorkersHandle::new(id.clone(), config.clone(), io_counters.clone()),
             tx_api: tx_api.clone(),
             rx_api,
 
@@ -317,6 +390,36 @@ where C: RaftTypeConfig
             tx_metrics,
             tx_data_metrics,
             tx_server_metrics,
+            tx_replication_metrics,
+            tx_network_metrics,
+            tx_applied,
+            tx_server_state,
+            events: events.clone(),
+
+            sm_leader_vote: None,
+            apply_inflight: 0,
+            pending_apply: None,
+            apply_deadline: None,
+            core_loop_stats: Default::default(),
+            core_loop_metrics: Default::default(),
+            apply_rate: Default::default(),
+            committed_membership: None,
+            last_leader_change: None,
+            heartbeat_acks: BTreeMap::new(),
+            appending: VecDeque::new(),
+            slow_ops: Default::default(),
+            stalled_targets: BTreeSet::new(),
+            ticks_paused: false,
+            auto_membership: Default::default(),
+
+            log_stats: None,
+            next_log_stats_poll: None,
+            next_metrics_flush: None,
+            metrics_flush_postponed: false,
+            replication_metrics: RaftReplicationMetrics::default(),
+            next_replication_metrics_flush: None,
+            replication_metrics_flush_postponed: false,
+            network_metrics: RaftNetworkMetrics::default(),
 
             span: core_span,
         };
@@ -332,10 +435,20 @@ where C: RaftTypeConfig
             rx_metrics,
             rx_data_metrics,
             rx_server_metrics,
+            rx_replication_metrics,
+            rx_network_metrics,
+            rx_applied,
+            rx_server_state,
+            events,
+            next_correlation_id: AtomicU64::new(1),
+            io_counters,
             tx_shutdown: std::sync::Mutex::new(Some(tx_shutdown)),
             core_state: std::sync::Mutex::new(CoreState::Running(core_handle)),
 
             snapshot: C::mutex(None),
+
+            membership_change_lock: C::mutex(()),
+            membership_change_waiting: AtomicU64::new(0),
         };
 
         Ok(Self { inner: Arc::new(inner) })
@@ -351,16 +464,176 @@ where C: RaftTypeConfig
     /// raft.runtime_config().heartbeat(true);
     /// raft.runtime_config().tick(true);
     /// raft.runtime_config().elect(true);
+    /// raft.runtime_config().pause_ticks().await?;
     /// ```
     pub fn runtime_config(&self) -> RuntimeConfigHandle<C> {
         RuntimeConfigHandle::new(self.inner.as_ref())
     }
 
-    /// Return the config of this Raft node.
+    /// Return the config this Raft node is created with.
+    ///
+    /// It does not include the updates by [`Raft::update_config()`], the config in use is
+    /// reported in [`RaftMetrics::config`].
     pub fn config(&self) -> &Arc<Config> {
         &self.inner.config
     }
 
+    /// Update some of the config values of this running Raft node.
+    ///
+    /// The fields set in `patch` replace the current values, then the config is validated, or an
+    /// invalid patch is rejected with a [`ConfigError`] and nothing is changed. The returned config
+    /// is the one in use after the update, and is also reported in [`RaftMetrics::config`].
+    ///
+    /// When the updated values take effect:
+    /// - `max_payload_entries` applies to the next replication request this node sends as a leader;
+    ///   a request already being sent is not changed.
+    /// - `snapshot_policy` applies the next time the committed log id advances.
+    /// - `max_in_snapshot_log_to_keep` and `purge_batch_size` apply the next time a snapshot is
+    ///   built.
+    /// - If `election_timeout_min` or `election_timeout_max` is changed, a new election timeout is
+    ///   chosen from the new range and applies since the next timer tick; the leader lease becomes
+    ///   `election_timeout_max`. Otherwise the election timeout in use is kept. The wait in
+    ///   [`Raft::handle_transfer_leader()`] still uses the election timeout of [`Raft::config()`].
+    ///
+    /// The update is local to this node, it is not replicated to other nodes.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let patch = ConfigPatch {
+    ///     max_payload_entries: Some(1000),
+    ///     ..Default::default()
+    /// };
+    /// raft.update_config(patch).await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn update_config(&self, patch: ConfigPatch) -> Result<Arc<Config>, RaftError<C, ConfigError>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::UpdateConfig { patch, tx }, rx).await
+    }
+
+    /// Get the configuration this running Raft node actually uses.
+    ///
+    /// Unlike [`Raft::config()`], it reflects the updates by [`Raft::update_config()`] and
+    /// [`Raft::runtime_config()`], and includes the values derived from the config, such as the
+    /// election timeout chosen from the configured range.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let c = raft.effective_config().await?;
+    /// println!("election timeout: {:?}, snapshot policy: {:?}", c.election_timeout, c.snapshot_policy);
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn effective_config(&self) -> Result<EffectiveConfig, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.send_msg(RaftMsg::GetEffectiveConfig { tx }).await?;
+        let mut effective = self.inner.recv_msg(rx).await?;
+
+        effective.enable_tick = self.inner.tick_handle.is_enabled();
+        Ok(effective)
+    }
+
+    /// Get a consistent view of the status of this node, including the server metrics, data
+    /// metrics, membership, replication progress and the configuration in use.
+    ///
+    /// Unlike reading [`Raft::server_metrics()`] and [`Raft::data_metrics()`] separately, all of
+    /// the fields are taken at the same time. With the `serde` feature enabled, the returned
+    /// [`RaftStatus`] can be serialized, e.g., as the response of an HTTP admin endpoint.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let status = raft.status().await?;
+    /// let body = serde_json::to_string(&status)?;
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn status(&self) -> Result<RaftStatus<C>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.send_msg(RaftMsg::GetStatus { tx }).await?;
+        let mut status = self.inner.recv_msg(rx).await?;
+
+        status.config.enable_tick = self.inner.tick_handle.is_enabled();
+        Ok(status)
+    }
+
+    /// Get the last time this leader proved its authority to a quorum, i.e., the latest time `t`
+    /// such that a quorum of voters has acknowledged this leader since `t`.
+    ///
+    /// The acknowledgements are collected from the responses to the replication and heartbeat
+    /// RPCs. No new leader can be elected until the election timeout elapses since this time,
+    /// thus an application implementing its own lease or fencing token can rely on it, with clock
+    /// drift taken into account. Unlike [`RaftMetrics::last_quorum_acked`], it is computed when
+    /// queried and is not affected by [`Config::metrics_flush_interval`].
+    ///
+    /// It returns `None` if this node is not a leader, or no quorum has acknowledged it yet.
+    ///
+    /// Example:
+    /// ```ignore
+    /// if let Some(acked) = raft.last_quorum_acked_time().await? {
+    ///     let lease_end = acked + lease;
+    ///     // serve requests guarded by the lease until lease_end
+    /// }
+    /// ```
+    ///
+    /// [`Config::metrics_flush_interval`]: crate::Config::metrics_flush_interval
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn last_quorum_acked_time(&self) -> Result<Option<InstantOf<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.send_msg(RaftMsg::GetLastQuorumAckedTime { tx }).await?;
+        self.inner.recv_msg(rx).await
+    }
+
+    /// Get the last decisions made by the Raft core, from the oldest to the latest.
+    ///
+    /// A decision is an input event, such as an API message or a notification, and the commands
+    /// emitted for it. It is empty unless [`Config::decision_trace_capacity`] is not 0. The input
+    /// being handled when this call is served is not included.
+    ///
+    /// Example:
+    /// ```ignore
+    /// for d in raft.decision_trace().await? {
+    ///     tracing::info!("decision: {}", d);
+    /// }
+    /// ```
+    ///
+    /// [`Config::decision_trace_capacity`]: crate::Config::decision_trace_capacity
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn decision_trace(&self) -> Result<Vec<EngineDecision>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.send_msg(RaftMsg::GetDecisionTrace { tx }).await?;
+        self.inner.recv_msg(rx).await
+    }
+
+    /// Run the internal consistency checks of this node and return a [`StateReport`] of them.
+    ///
+    /// It checks the order of the log ids this node tracks, i.e., `purged <= snapshot <= applied
+    /// <= committed <= last_log`, that the vote is not smaller than the leader of the last log,
+    /// that the membership configs are in order and, on a leader, that the replication progress
+    /// of every target is consistent with the local log.
+    ///
+    /// A failed check indicates a bug or a corrupted storage. It is meant to be used by a health
+    /// endpoint or before upgrading a node.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let report = raft.verify_state().await?;
+    /// if !report.is_ok() {
+    ///     for check in report.violations() {
+    ///         tracing::error!("state check failed: {}", check);
+    ///     }
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn verify_state(&self) -> Result<StateReport, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.send_msg(RaftMsg::VerifyState { tx }).await?;
+        self.inner.recv_msg(rx).await
+    }
+
     /// Return a [`Trigger`] handle to manually trigger raft actions, such as elect or build
     /// snapshot.
     ///
@@ -421,11 +694,43 @@ where C: RaftTypeConfig
         Ok(resp)
     }
 
+    /// Check if this node is able to install a snapshot of the version in `meta`.
+    ///
+    /// An application defined snapshot transmission should call it when receiving the snapshot
+    /// meta, and reply the error to the leader instead of calling [`Self::install_full_snapshot`].
+    /// See: [`Config::max_snapshot_version`].
+    #[since(version = "0.10.0")]
+    pub fn check_snapshot_version(&self, meta: &SnapshotMeta<C>) -> Result<(), SnapshotVersionUnsupported> {
+        match self.inner.config.max_snapshot_version {
+            Some(max_version) if meta.version() > max_version => Err(SnapshotVersionUnsupported {
+                version: meta.version(),
+                max_version,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Count `n` bytes of snapshot data received from the leader, in
+    /// [`IoMetrics::snapshot_bytes_received`].
+    ///
+    /// The chunks received by [`Self::install_snapshot`] are counted by Openraft. An application
+    /// defined snapshot transmission should call it for the data it receives, before calling
+    /// [`Self::install_full_snapshot`].
+    ///
+    /// [`IoMetrics::snapshot_bytes_received`]: crate::metrics::IoMetrics::snapshot_bytes_received
+    #[since(version = "0.10.0")]
+    pub fn add_snapshot_bytes_received(&self, n: u64) {
+        self.inner.io_counters.add_snapshot_bytes_received(n);
+    }
+
     /// Install a completely received snapshot to the state machine.
     ///
     /// This method is used to implement an application defined snapshot transmission.
     /// The application receives a snapshot from the leader, in chunks or a stream, and
     /// then rebuild a snapshot, then pass the snapshot to Raft to install.
+    ///
+    /// The snapshot version is not checked, call [`Self::check_snapshot_version`] before
+    /// receiving the snapshot data.
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn install_full_snapshot(
         &self,
@@ -459,7 +764,7 @@ where C: RaftTypeConfig
         req: InstallSnapshotRequest<C>,
     ) -> Result<InstallSnapshotResponse<C>, RaftError<C, crate::error::InstallSnapshotError>>
     where
-        C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io::AsyncSeek + Unpin,
+        C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
     {
         use crate::async_runtime::mutex::Mutex;
 
@@ -467,7 +772,10 @@ where C: RaftTypeConfig
 
         let req_vote = req.vote.clone();
         let my_vote = self.with_raft_state(|state| state.vote_ref().clone()).await?;
-        let resp = InstallSnapshotResponse { vote: my_vote.clone() };
+        let resp = InstallSnapshotResponse {
+            vote: my_vote.clone(),
+            malformed: None,
+        };
 
         // Check vote.
         // It is not mandatory because it is just a read operation
@@ -481,6 +789,9 @@ where C: RaftTypeConfig
             }
         }
 
+        self.check_snapshot_version(&req.meta).map_err(|e| RaftError::APIError(e.into()))?;
+        self.add_snapshot_bytes_received(req.data.len() as u64);
+
         let finished_snapshot = {
             use crate::network::snapshot_transport::Chunked;
             use crate::network::snapshot_transport::SnapshotTransport;
@@ -506,11 +817,92 @@ where C: RaftTypeConfig
         self.metrics().borrow_watched().current_leader.clone()
     }
 
+    /// Get the id and the node of the current leader known to this Raft node.
+    ///
+    /// Like [`Raft::current_leader`], it reads the latest server metrics without cloning the whole
+    /// [`RaftMetrics`]. The node data can be used to forward a request to the leader.
+    ///
+    /// It returns `None` if the leader is unknown, or the leader is not in the membership config
+    /// known to this node.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn current_leader_node(&self) -> Option<(C::NodeId, C::Node)> {
+        let metrics = self.server_metrics();
+        let m = metrics.borrow_watched();
+
+        let leader_id = m.current_leader.as_ref()?;
+        let node = m.membership_config.membership().get_node(leader_id)?;
+        Some((leader_id.clone(), node.clone()))
+    }
+
+    /// Get the latest membership config known to this Raft node, without cloning the whole
+    /// [`RaftMetrics`].
+    ///
+    /// It is the effective membership, which may not be committed yet.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn current_membership(&self) -> Arc<StoredMembership<C>> {
+        self.server_metrics().borrow_watched().membership_config.clone()
+    }
+
+    /// Find out what a change-membership request would do, without appending anything.
+    ///
+    /// It builds the **joint** config and the final **uniform** config the same way
+    /// [`Raft::change_membership()`] proposes them, and reports which new voters are not in the
+    /// cluster, which new voters lag behind the leader by more than
+    /// [`Config::replication_lag_threshold`], and whether the up-to-date voters form a quorum of
+    /// the new configs. See [`MembershipChangeReport`].
+    ///
+    /// A change that can not be proposed is not an error here: the reason is in the report. It
+    /// returns a [`ForwardToLeader`] error if this node is not a leader, because only the leader
+    /// knows the replication progress.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn change_membership_dry_run(
+        &self,
+        members: impl Into<ChangeMembers<C>>,
+        retain: bool,
+    ) -> Result<MembershipChangeReport<C>, RaftError<C, ClientWriteError<C>>> {
+        let (tx, rx) = C::oneshot();
+
+        let msg = RaftMsg::ChangeMembershipDryRun {
+            changes: members.into(),
+            retain,
+            tx,
+        };
+
+        self.inner.call_core(msg, rx).await
+    }
+
+    /// Register a [`MembershipValidator`] that checks every membership config before this node,
+    /// as a leader, proposes it. It replaces the validator registered before.
+    ///
+    /// A config rejected by the validator fails the change with
+    /// [`ChangeMembershipError::Rejected`], and nothing is appended. The validator has to be
+    /// registered on every node that may become a leader, e.g., right after [`Raft::new()`].
+    ///
+    /// ```ignore
+    /// // Reject a config that places all voters in a single zone:
+    /// raft.set_membership_validator(ZoneSpreadValidator::new(2, |_id, node: &MyNode| {
+    ///     Some(node.zone.clone())
+    /// }))
+    /// .await?;
+    /// ```
+    ///
+    /// [`ChangeMembershipError::Rejected`]: crate::error::ChangeMembershipError::Rejected
+    #[since(version = "0.10.0")]
+    pub async fn set_membership_validator(&self, validator: impl MembershipValidator<C>) -> Result<(), Fatal<C>> {
+        let validator = Box::new(validator);
+        self.inner.send_msg(RaftMsg::SetMembershipValidator { validator }).await
+    }
+
     /// Check to ensure this node is still the cluster leader, in order to guard against stale reads
     /// (§8).
     ///
     /// The actual read operation itself is up to the application, this method just ensures that
     /// the read will not be stale.
+    ///
+    /// For a cheap check of the local state only, use [`Raft::is_leader_local()`].
     #[deprecated(since = "0.9.0", note = "use `Raft::ensure_linearizable()` instead")]
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn is_leader(&self) -> Result<(), RaftError<C, CheckIsLeaderError<C>>> {
@@ -519,6 +911,39 @@ where C: RaftTypeConfig
         Ok(())
     }
 
+    /// Check if this node is the leader, according to its local state.
+    ///
+    /// It is a cheap check that does not talk to other nodes, e.g., to decide whether to serve a
+    /// request or redirect it. Unlike [`Raft::is_leader()`], it does not guard against stale
+    /// reads: this node may believe it is the leader after a new leader has been elected. Use
+    /// [`Raft::ensure_linearizable()`] before a linearizable read.
+    ///
+    /// If this node is not the leader, it returns a [`ForwardToLeader`] error with the id and the
+    /// node of the leader known to this node, if any.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn is_leader_local(&self) -> Result<(), ForwardToLeader<C>> {
+        let metrics = self.server_metrics();
+        let m = metrics.borrow_watched();
+
+        if m.state == ServerState::Leader && m.current_leader.as_ref() == Some(&m.id) {
+            return Ok(());
+        }
+
+        let Some(leader_id) = m.current_leader.clone() else {
+            return Err(ForwardToLeader::empty());
+        };
+
+        let fwd = match m.membership_config.membership().get_node(&leader_id) {
+            Some(node) => ForwardToLeader::new(leader_id, node.clone()),
+            None => ForwardToLeader {
+                leader_id: Some(leader_id),
+                leader_node: None,
+            },
+        };
+        Err(fwd)
+    }
+
     /// Ensures a read operation performed following this method are linearizable across the
     /// cluster.
     ///
@@ -534,7 +959,8 @@ where C: RaftTypeConfig
     ///   represents the log id up to which the state machine has applied to ensure a linearizable
     ///   read.
     /// - `Err(RaftError<CheckIsLeaderError>)` if it detects a higher term, or if it fails to
-    ///   communicate with a quorum of followers.
+    ///   communicate with a quorum of followers. If this node is not the leader, it is a
+    ///   [`CheckIsLeaderError::ForwardToLeader`] with the id and node of the known leader.
     ///
     /// # Examples
     /// ```ignore
@@ -560,6 +986,95 @@ where C: RaftTypeConfig
         Ok(read_log_id)
     }
 
+    /// Performs a linearizable read by running `func` against the state machine.
+    ///
+    /// It confirms the leadership with [`ensure_linearizable()`](Self::ensure_linearizable), which
+    /// waits for the state machine to apply upto the read log id, then runs `func` in the state
+    /// machine worker, as [`with_state_machine()`](Self::with_state_machine) does. `func` thus
+    /// observes every write that is committed before this call.
+    ///
+    /// Returns:
+    /// - `Ok(Ok(v))` with the value returned by `func`.
+    /// - `Ok(Err(InvalidStateMachineType))` if `SM` is not the state machine type of this Raft.
+    /// - `Err(RaftError<CheckIsLeaderError>)` if this node is not the leader, or fails to
+    ///   communicate with a quorum.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let value = my_raft
+    ///     .linearizable_read(|sm: &mut MyStateMachine| Box::pin(async move { sm.get("foo") }))
+    ///     .await??;
+    /// ```
+    ///
+    /// See: [Read Operation](crate::docs::protocol::read)
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn linearizable_read<F, SM, V>(
+        &self,
+        func: F,
+    ) -> Result<Result<V, InvalidStateMachineType>, RaftError<C, CheckIsLeaderError<C>>>
+    where
+        SM: RaftStateMachine<C>,
+        F: FnOnce(&mut SM) -> BoxFuture<V> + OptionalSend + 'static,
+        V: OptionalSend + 'static,
+    {
+        self.ensure_linearizable().await?;
+
+        let res = self.with_state_machine(func).await?;
+        Ok(res)
+    }
+
+    /// Ensures a linearizable read on this node, which can be a follower or a learner.
+    ///
+    /// A non-leader node sends a ReadIndex RPC, [`RaftNetworkV2::read_index()`], to the current
+    /// leader to get a read log id, then waits for the local state machine to apply upto it. The
+    /// read can then be served from the local state machine, spreading the read load across the
+    /// cluster. On the leader it is the same as
+    /// [`ensure_linearizable()`](Self::ensure_linearizable).
+    ///
+    /// Returns:
+    /// - `Ok(read_log_id)` when the local state machine has applied upto `read_log_id`.
+    /// - `Err(RaftError<ReadIndexError>)` if the leader is unknown, can not be reached, or fails to
+    ///   confirm its leadership with a quorum.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// my_raft.ensure_linearizable_via_leader().await?;
+    /// // Proceed with the local state machine read
+    /// ```
+    ///
+    /// [`RaftNetworkV2::read_index()`]: crate::network::v2::RaftNetworkV2::read_index
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn ensure_linearizable_via_leader(&self) -> Result<Option<LogIdOf<C>>, RaftError<C, ReadIndexError<C>>> {
+        let (tx, rx) = C::oneshot();
+        let read_log_id = self.inner.call_core(RaftMsg::ReadIndexViaLeader { tx }, rx).await?;
+
+        if let Some(read_log_id) = &read_log_id {
+            self.wait_applied(read_log_id).await?;
+        }
+        Ok(read_log_id)
+    }
+
+    /// Handle a ReadIndex request from a follower or learner, received by
+    /// [`RaftNetworkV2::read_index()`].
+    ///
+    /// It confirms this node is the leader and returns the read log id, as
+    /// [`get_read_log_id()`](Self::get_read_log_id) does.
+    ///
+    /// [`RaftNetworkV2::read_index()`]: crate::network::v2::RaftNetworkV2::read_index
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn handle_read_index(
+        &self,
+        req: ReadIndexRequest<C>,
+    ) -> Result<Option<LogIdOf<C>>, RaftError<C, CheckIsLeaderError<C>>> {
+        tracing::debug!(req = display(&req), "Raft::handle_read_index");
+
+        let (read_log_id, _applied) = self.get_read_log_id().await?;
+        Ok(read_log_id)
+    }
+
     /// Ensures this node is leader and returns the log id up to which the state machine should
     /// apply to ensure a read can be linearizable across the cluster.
     ///
@@ -598,6 +1113,40 @@ where C: RaftTypeConfig
         Ok((read_log_id, applied))
     }
 
+    /// Ensures this node is leader and returns a [`ReadTicket`] for composing a custom read
+    /// consistency scheme.
+    ///
+    /// It confirms the leadership the same way as [`get_read_log_id()`](Self::get_read_log_id),
+    /// and the returned ticket carries the read log id, the time the confirmation is requested
+    /// and a watch of the local applied log id. Unlike
+    /// [`ensure_linearizable()`](Self::ensure_linearizable), it does not wait for the state
+    /// machine: the caller decides when and how to wait, e.g., with
+    /// [`ReadTicket::wait_ready()`], or by reusing a recent ticket for a bounded staleness read.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let ticket = my_raft.get_read_ticket().await?;
+    ///
+    /// // Reuse the ticket for reads within 100ms.
+    /// if ticket.requested_at().elapsed() < Duration::from_millis(100) {
+    ///     ticket.wait_ready().await?;
+    ///     // Proceed with the state machine read
+    /// }
+    /// ```
+    ///
+    /// See: [Read Operation](crate::docs::protocol::read)
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_read_ticket(&self) -> Result<ReadTicket<C>, RaftError<C, CheckIsLeaderError<C>>> {
+        let requested_at = C::now();
+        let (read_log_id, _applied) = self.get_read_log_id().await?;
+        Ok(ReadTicket::new(
+            read_log_id,
+            requested_at,
+            self.inner.rx_applied.clone(),
+        ))
+    }
+
     /// Submit a mutating client request to Raft to update the state of the system (§5.1).
     ///
     /// It will be appended to the log, committed to the cluster, and then applied to the
@@ -625,12 +1174,91 @@ where C: RaftTypeConfig
         ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
         E: Error + OptionalSend,
     {
-        let rx = self.client_write_ff(app_data).await?;
+        let handle = self.client_write_handle(app_data).await?;
+        handle.response().await
+    }
+
+    /// Submit a mutating client request to Raft, same as [`Raft::client_write`], but identify it
+    /// with the application provided `correlation_id` in the logs, instead of a generated one.
+    ///
+    /// See [`CorrelationId`] for the stages at which it is logged.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data), fields(correlation_id = display(correlation_id)))]
+    pub async fn client_write_with_correlation_id<E>(
+        &self,
+        app_data: C::D,
+        correlation_id: CorrelationId,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        let (app_data, tx, rx) = ResponderOf::<C>::from_app_data(app_data);
+
+        self.inner
+            .send_msg(RaftMsg::ClientWriteRequest {
+                app_data,
+                tx,
+                log_id_tx: None,
+                span: Self::client_write_span(),
+                correlation_id,
+            })
+            .await?;
+
+        ResponseHandle::new(self.inner.clone(), rx).response().await
+    }
+
+    /// Submit a mutating client request to Raft, same as [`Raft::client_write`], but give up
+    /// waiting if it is not applied within `timeout`.
+    ///
+    /// On timeout it returns a [`ClientWriteError::Timeout`]. Its `log_id` is the log id assigned
+    /// to the entry, if the entry has been appended to the leader's log: such an entry may still be
+    /// committed and applied later. The write is not cancelled, and the caller should treat its
+    /// outcome as unknown, e.g., retry it with a serial number that the state machine deduplicates.
+    ///
+    /// Dropping the returned future, or timing out, only drops the receiving end. `RaftCore`
+    /// releases the responder once the entry is applied or truncated.
+    ///
+    /// [`ClientWriteError::Timeout`]: crate::error::ClientWriteError::Timeout
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_with_timeout<E>(
+        &self,
+        app_data: C::D,
+        timeout: Duration,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>> + OptionalSend,
+        E: Error + OptionalSend,
+    {
+        let deadline = C::now() + timeout;
+
+        let (app_data, tx, rx) = ResponderOf::<C>::from_app_data(app_data);
+        let (log_id_tx, log_id_rx) = C::oneshot();
+
+        self.inner
+            .send_msg(RaftMsg::ClientWriteRequest {
+                app_data,
+                tx,
+                log_id_tx: Some(log_id_tx),
+                span: Self::client_write_span(),
+                correlation_id: self.inner.new_correlation_id(),
+            })
+            .await?;
+
+        // `log_id_tx` is dropped without sending if the write is rejected,
+        // in which case the response tells the reason.
+        let log_id = match C::timeout_at(deadline, log_id_rx).await {
+            Ok(res) => res.ok(),
+            Err(_) => return Err(RaftError::APIError(ClientWriteTimeout::new(None, timeout).into())),
+        };
 
-        let res: ClientWriteResult<C> = self.inner.recv_msg(rx).await?;
+        let res: ClientWriteResult<C> = match C::timeout_at(deadline, self.inner.recv_msg(rx)).await {
+            Ok(res) => res?,
+            Err(_) => return Err(RaftError::APIError(ClientWriteTimeout::new(log_id, timeout).into())),
+        };
 
-        let client_write_response = res.map_err(|e| RaftError::APIError(e))?;
-        Ok(client_write_response)
+        res.map_err(RaftError::APIError)
     }
 
     /// Submit a mutating client request to Raft to update the state machine, returns an application
@@ -639,15 +1267,128 @@ where C: RaftTypeConfig
     /// `_ff` means fire and forget.
     ///
     /// It is same as [`Raft::client_write`] but does not wait for the response.
+    /// To receive the response in the same form as [`Raft::client_write`], use
+    /// [`Raft::client_write_handle`].
     #[tracing::instrument(level = "debug", skip(self, app_data))]
     pub async fn client_write_ff(&self, app_data: C::D) -> Result<ResponderReceiverOf<C>, Fatal<C>> {
         let (app_data, tx, rx) = ResponderOf::<C>::from_app_data(app_data);
 
-        self.inner.send_msg(RaftMsg::ClientWriteRequest { app_data, tx }).await?;
+        self.inner
+            .send_msg(RaftMsg::ClientWriteRequest {
+                app_data,
+                tx,
+                log_id_tx: None,
+                span: Self::client_write_span(),
+                correlation_id: self.inner.new_correlation_id(),
+            })
+            .await?;
 
         Ok(rx)
     }
 
+    /// Submit a mutating client request to Raft with an application built [`Responder`], through
+    /// which the result is delivered once the request is applied.
+    ///
+    /// Unlike [`Raft::client_write_ff`], which builds the responder with
+    /// [`Responder::from_app_data`], the application creates the responder itself, e.g., with the
+    /// address of the remote caller, so that a node proxying writes for other nodes can deliver the
+    /// result over its own transport instead of a local channel.
+    ///
+    /// It returns once the request is enqueued to `RaftCore`. If `RaftCore` has stopped, the
+    /// responder is dropped without being called.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data, responder))]
+    pub async fn client_write_with_responder(&self, app_data: C::D, responder: ResponderOf<C>) -> Result<(), Fatal<C>> {
+        self.inner
+            .send_msg(RaftMsg::ClientWriteRequest {
+                app_data,
+                tx: responder,
+                log_id_tx: None,
+                span: Self::client_write_span(),
+                correlation_id: self.inner.new_correlation_id(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Submit a batch of mutating client requests to Raft and wait for all of them to be applied.
+    ///
+    /// The requests are appended as consecutive log entries in one pass of `RaftCore` and with one
+    /// storage append, which is much cheaper than calling [`Raft::client_write`] for each of them.
+    ///
+    /// It returns one result for every request, in the order of `app_data`, or a [`Fatal`] error
+    /// if `RaftCore` stopped. If this node is not a leader, every request fails with a
+    /// [`ClientWriteError::ForwardToLeader`].
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_many<E>(&self, app_data: Vec<C::D>) -> Result<Vec<ClientWriteResult<C>>, Fatal<C>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        let mut data = Vec::with_capacity(app_data.len());
+        let mut txs = Vec::with_capacity(app_data.len());
+        let mut rxs = Vec::with_capacity(app_data.len());
+        let mut correlation_ids = Vec::with_capacity(app_data.len());
+
+        for d in app_data {
+            let (d, tx, rx) = ResponderOf::<C>::from_app_data(d);
+            data.push(d);
+            txs.push(tx);
+            rxs.push(rx);
+            correlation_ids.push(self.inner.new_correlation_id());
+        }
+
+        let span = if cfg!(feature = "otel") {
+            tracing::info_span!("client_write_many", entries = data.len())
+        } else {
+            tracing::Span::none()
+        };
+
+        self.inner
+            .send_msg(RaftMsg::ClientWriteManyRequest {
+                app_data: data,
+                txs,
+                span,
+                correlation_ids,
+            })
+            .await?;
+
+        let mut results = Vec::with_capacity(rxs.len());
+        for rx in rxs {
+            results.push(self.inner.recv_msg(rx).await?);
+        }
+        Ok(results)
+    }
+
+    /// The span in which the progress of a client write is recorded and that the RPCs replicating
+    /// it are sent in: the span of the caller if feature `otel` is enabled.
+    fn client_write_span() -> tracing::Span {
+        if cfg!(feature = "otel") {
+            tracing::Span::current()
+        } else {
+            tracing::Span::none()
+        }
+    }
+
+    /// Submit a mutating client request to Raft and return a [`ResponseHandle`] at once, without
+    /// waiting for the request to be applied.
+    ///
+    /// The proposal is enqueued to `RaftCore` when this method returns. The applied result can be
+    /// retrieved later with [`ResponseHandle::response`]. Unlike [`Raft::client_write`], the
+    /// caller does not have to keep a task running for every pending write, which suits a proxy
+    /// that forwards many writes concurrently.
+    ///
+    /// Unlike [`Raft::client_write_ff`], a closed response channel is reported as the [`Fatal`]
+    /// error that stopped `RaftCore`, as [`Raft::client_write`] does.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, app_data))]
+    pub async fn client_write_handle(&self, app_data: C::D) -> Result<ResponseHandle<C>, Fatal<C>> {
+        let rx = self.client_write_ff(app_data).await?;
+        Ok(ResponseHandle::new(self.inner.clone(), rx))
+    }
+
     /// Handle the LeaderTransfer request from a Leader node.
     ///
     /// If this node is the `to` node, it resets the Leader lease and triggers an election when the
@@ -756,18 +1497,48 @@ where C: RaftTypeConfig
     /// Once a node successfully initialized it will commit a new membership config
     /// log entry to store.
     /// Then it starts to work, i.e., entering Candidate state and try electing itself as the
-    /// leader.
+    /// leader at once, without waiting for an election timeout. Thus a new cluster becomes
+    /// writable as soon as the other members grant the vote. Other nodes that are not initialized
+    /// by this call keep their election timeout unchanged.
     ///
     /// More than one node performing `initialize()` with the same config is safe,
     /// with different config will result in split brain condition.
+    ///
+    /// To include learners in the initial membership, use [`Raft::initialize_with_learners()`].
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn initialize<T>(&self, members: T) -> Result<(), RaftError<C, InitializeError<C>>>
     where T: IntoNodes<C::NodeId, C::Node> + Debug {
+        self.initialize_with_learners(members, ()).await
+    }
+
+    /// Initialize a pristine Raft node with voters and learners in the initial membership config.
+    ///
+    /// It works the same as [`Raft::initialize()`], except that the nodes in `learners` are added
+    /// to the initial membership config as learners. Thus they start to receive logs as soon as a
+    /// leader is elected, without a follow-up [`Raft::add_learner()`].
+    ///
+    /// A node that is in both `members` and `learners` is a voter, and its node in `members` is
+    /// used. This node itself has to be one of the voters, otherwise it fails with a
+    /// [`NotInMembers`](crate::error::NotInMembers) error.
+    ///
+    /// All nodes performing initialization have to use the same voters and learners.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn initialize_with_learners<T, L>(
+        &self,
+        members: T,
+        learners: L,
+    ) -> Result<(), RaftError<C, InitializeError<C>>>
+    where
+        T: IntoNodes<C::NodeId, C::Node> + Debug,
+        L: IntoNodes<C::NodeId, C::Node> + Debug,
+    {
         let (tx, rx) = C::oneshot();
         self.inner
             .call_core(
                 RaftMsg::Initialize {
                     members: members.into_nodes(),
+                    learners: learners.into_nodes(),
                     tx,
                 },
                 rx,
@@ -779,11 +1550,14 @@ where C: RaftTypeConfig
     /// node removed, or replication becomes upto date.
     ///
     /// Returns Err() if it should keep waiting.
+    ///
+    /// The replication is regarded as upto date if the lag is at most `max_lag`.
     fn check_replication_upto_date(
         &self,
         metrics: &RaftMetrics<C>,
         node_id: &C::NodeId,
         membership_log_id: Option<&LogIdOf<C>>,
+        max_lag: u64,
     ) -> Result<Option<LogIdOf<C>>, ()> {
         if metrics.membership_config.log_id().as_ref() < membership_log_id {
             // Waiting for the latest metrics to report.
@@ -816,7 +1590,7 @@ where C: RaftTypeConfig
 
         let distance = replication_lag(&matched.index(), &metrics.last_log_index);
 
-        if distance <= self.inner.config.replication_lag_threshold {
+        if distance <= max_lag {
             // replication became up to date.
             return Ok(matched);
         }
@@ -840,6 +1614,20 @@ where C: RaftTypeConfig
     /// ```ignore
     /// let committed = my_raft.with_raft_state(|st| st.committed).await?;
     /// ```
+    ///
+    /// `func` runs inside `RaftCore`, thus several fields read in one call are consistent with each
+    /// other, which is not guaranteed by reading them from [`Raft::metrics`] one by one:
+    /// ```ignore
+    /// let (vote, membership, last_log_id) = my_raft
+    ///     .with_raft_state(|st| {
+    ///         (
+    ///             st.vote_ref().clone(),
+    ///             st.membership_state.effective().clone(),
+    ///             st.last_log_id().cloned(),
+    ///         )
+    ///     })
+    ///     .await?;
+    /// ```
     pub async fn with_raft_state<F, V>(&self, func: F) -> Result<V, Fatal<C>>
     where
         F: FnOnce(&RaftState<C>) -> V + OptionalSend + 'static,
@@ -946,6 +1734,12 @@ where C: RaftTypeConfig
     /// The request functor will be called with a mutable reference to the state machine.
     /// The functor returns a [`Future`] because state machine methods are `async`.
     ///
+    /// The request is serialized with applying log entries: it runs after all the entries
+    /// committed before `RaftCore` receives the request are applied, even if they are held back
+    /// for a larger apply batch by [`Config::apply_batch_max_delay`], and before any entry
+    /// committed later. Thus the functor sees a consistent applied state, for example, for an
+    /// admin inspection.
+    ///
     /// If the API channel is already closed (Raft is in shutdown), then the request functor is
     /// destroyed right away and not called at all.
     ///
@@ -986,6 +1780,165 @@ where C: RaftTypeConfig
         self.inner.rx_server_metrics.clone()
     }
 
+    /// Get a handle to the replication metrics channel.
+    ///
+    /// It reports the replication and heartbeat progress to every target if this node is a
+    /// leader, and is notified only when they change. See [`RaftReplicationMetrics`].
+    #[since(version = "0.10.0")]
+    pub fn replication_metrics(&self) -> WatchReceiverOf<C, RaftReplicationMetrics<C>> {
+        self.inner.rx_replication_metrics.clone()
+    }
+
+    /// Get a handle to the network metrics channel.
+    ///
+    /// It reports the latency histograms of the RPCs sent by replication, by target and RPC type,
+    /// so that the tail latency to a particular peer can be watched. It is flushed along with the
+    /// replication metrics, see [`Config::replication_metrics_flush_interval`].
+    ///
+    /// [`Config::replication_metrics_flush_interval`]: crate::Config::replication_metrics_flush_interval
+    #[since(version = "0.10.0")]
+    pub fn network_metrics(&self) -> WatchReceiverOf<C, RaftNetworkMetrics<C>> {
+        self.inner.rx_network_metrics.clone()
+    }
+
+    /// Get a handle to the channel of the last log id applied to the local state machine.
+    ///
+    /// It is updated as soon as a batch of entries is applied or a snapshot is installed, without
+    /// waiting for the metrics to be reported. Use it to wait for a specific write to be applied
+    /// on this node, e.g., a follower serving reads that must observe a write of the same client.
+    ///
+    /// See: [`Raft::wait_applied`].
+    #[since(version = "0.10.0")]
+    pub fn applied_index_watch(&self) -> WatchReceiverOf<C, Option<LogIdOf<C>>> {
+        self.inner.rx_applied.clone()
+    }
+
+    /// Get a handle to the channel of the server state of this Raft node, along with the vote.
+    ///
+    /// A new value is sent as soon as the server state or the vote changes, such as when this
+    /// node becomes a leader or steps down. Applications can use it to start and stop leader-only
+    /// background work, without comparing every [`RaftMetrics`] update.
+    ///
+    /// Like other watch channels, a receiver only sees the latest value: two changes in a short
+    /// time may be observed as one.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let mut rx = raft.server_state_watch();
+    /// loop {
+    ///     let change = rx.borrow_watched().clone();
+    ///     if change.is_leader() {
+    ///         // start leader-only work for change.vote
+    ///     }
+    ///     rx.changed().await?;
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn server_state_watch(&self) -> WatchReceiverOf<C, ServerStateChange<C>> {
+        self.inner.rx_server_state.clone()
+    }
+
+    /// Subscribe to the [`RaftEvent`]s of this node, such as becoming a leader, committing a
+    /// membership config or building a snapshot.
+    ///
+    /// Every subscriber receives the events that happen after it subscribes, in order. At most
+    /// [`EVENT_QUEUE_SIZE`] events are queued for a subscriber: if it falls further behind, the
+    /// following events are dropped for it, and it receives a [`RecvEventError::Lagged`] in
+    /// their place. [`RaftEvent::Fatal`] is never dropped. A subscriber should drop the receiver
+    /// once it is no longer interested.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let mut rx = raft.event_stream();
+    /// loop {
+    ///     match rx.recv().await {
+    ///         Ok(RaftEvent::LeaderElected { vote }) => {
+    ///             // start leader-only work for vote
+    ///         }
+    ///         Ok(_) => {}
+    ///         Err(RecvEventError::Lagged(_)) => {
+    ///             // re-sync with `raft.metrics()`
+    ///         }
+    ///         Err(RecvEventError::Closed) => break,
+    ///     }
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn event_stream(&self) -> RaftEventReceiver<C> {
+        self.inner.events.subscribe()
+    }
+
+    /// Register a [`RaftEventListener`], whose callbacks are called for the [`RaftEvent`]s that
+    /// happen after it is registered.
+    ///
+    /// It is an alternative to consuming [`Self::event_stream()`]: the callbacks are called in
+    /// a task spawned for the listener, thus `RaftCore` never waits for a listener. At most
+    /// [`EVENT_QUEUE_SIZE`] events are queued for a listener: if a listener falls further behind,
+    /// the following events are dropped for it, with a warning logged, until it catches up.
+    ///
+    /// The listener task quits when this Raft node is shut down and all of its handles are
+    /// dropped.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// struct LeaderWatcher;
+    ///
+    /// impl RaftEventListener<TypeConfig> for LeaderWatcher {
+    ///     fn on_leader_elected(&mut self, vote: &Vote<TypeConfig>) {
+    ///         // start leader-only work for vote
+    ///     }
+    /// }
+    ///
+    /// raft.add_listener(LeaderWatcher);
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn add_listener(&self, mut listener: impl RaftEventListener<C>) {
+        let mut rx = self.inner.events.subscribe();
+
+        let fu = async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => event.dispatch_to(&mut listener),
+                    Err(RecvEventError::Lagged(dropped)) => {
+                        tracing::warn!(dropped, "RaftEventListener lagged behind, events are dropped");
+                    }
+                    Err(RecvEventError::Closed) => break,
+                }
+            }
+            tracing::debug!("RaftEventListener quit: events closed");
+        };
+        // The listener task is detached, it quits when the events are closed.
+        drop(C::spawn(fu.instrument(trace_span!("RaftEventListener").or_current())));
+    }
+
+    /// Wait until the local state machine has applied upto `log_id`, inclusive.
+    ///
+    /// Returns the last applied log id, which may be greater than `log_id`.
+    /// It returns an error only if this Raft node is shut down. To wait with a timeout, wrap the
+    /// returned future with a timer of the async runtime.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let resp = leader.client_write(req).await?;
+    ///
+    /// // Before reading from a follower, wait for the write to be applied on it:
+    /// follower.wait_applied(&resp.log_id).await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn wait_applied(&self, log_id: &LogIdOf<C>) -> Result<Option<LogIdOf<C>>, Fatal<C>> {
+        let mut rx = self.inner.rx_applied.clone();
+
+        loop {
+            let applied = rx.borrow_watched().clone();
+            if applied.next_index() > log_id.index() {
+                return Ok(applied);
+            }
+
+            rx.changed().await.map_err(|_| Fatal::Stopped)?;
+        }
+    }
+
     /// Get a handle to wait for the metrics to satisfy some condition.
     ///
     /// If `timeout` is `None`, then it will wait forever(10 years).
@@ -1017,6 +1970,34 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Wait until a leader is known to this Raft node, and return the id and the node of it.
+    ///
+    /// It resolves as soon as the metrics report a leader that is in the membership config known
+    /// to this node, including this node itself. Applications can use it instead of a retry loop
+    /// on [`Raft::current_leader`] before sending the first request to the cluster.
+    ///
+    /// If `timeout` is `None`, then it will wait forever(10 years).
+    /// It returns [`WaitError::Timeout`] if no leader is known before `timeout`, or
+    /// [`WaitError::ShuttingDown`] if this Raft node is shut down.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let (leader_id, leader_node) = raft.wait_for_leader(Some(Duration::from_secs(3))).await?;
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn wait_for_leader(&self, timeout: Option<Duration>) -> Result<(C::NodeId, C::Node), WaitError> {
+        let leader_node = |m: &RaftMetrics<C>| {
+            let leader_id = m.current_leader.as_ref()?;
+            let node = m.membership_config.membership().get_node(leader_id)?;
+            Some((leader_id.clone(), node.clone()))
+        };
+
+        let metrics = self.wait(timeout).metrics(|m| leader_node(m).is_some(), "wait for leader").await?;
+
+        Ok(leader_node(&metrics).expect("the leader is known when the wait condition is satisfied"))
+    }
+
     /// Shutdown this Raft node.
     ///
     /// It sends a shutdown signal and waits until `RaftCore` returns.
@@ -1036,4 +2017,65 @@ where C: RaftTypeConfig
         //           to let the caller know the return value of RaftCore task.
         Ok(())
     }
+
+    /// Shutdown this Raft node after draining the client writes it has accepted.
+    ///
+    /// It stops accepting new client writes: they are rejected with a
+    /// [`ClientWriteError::ShuttingDown`] error, so that a client can tell a shutdown from an
+    /// election and sends them to another node instead of retrying on this one. Then it waits for
+    /// the already accepted writes to be applied, and if `transfer_leader_to` is given and this
+    /// node is the leader, transfers the leadership to that node. At last it shuts down as
+    /// [`Raft::shutdown()`] does.
+    ///
+    /// The draining and the leader transfer stop waiting at `timeout`, and the returned
+    /// [`ShutdownReport`] tells which writes were applied and which were abandoned.
+    ///
+    /// [`ClientWriteError::ShuttingDown`]: crate::error::ClientWriteError::ShuttingDown
+    #[since(version = "0.10.0")]
+    pub async fn shutdown_gracefully(
+        &self,
+        timeout: Duration,
+        transfer_leader_to: Option<C::NodeId>,
+    ) -> Result<ShutdownReport<C>, JoinErrorOf<C>> {
+        let start = C::now();
+        let remaining = || timeout.saturating_sub(start.elapsed());
+
+        let mut report = ShutdownReport::default();
+
+        // If RaftCore is already stopped, there is nothing to drain.
+        let (tx, rx) = C::oneshot();
+        let pending = self.inner.call_core(RaftMsg::StopAcceptingWrites { tx }, rx).await.unwrap_or_default();
+
+        if let Some(last) = pending.last() {
+            let res =
+                self.wait(Some(remaining())).applied_index_at_least(Some(last.index()), "drain client writes").await;
+            tracing::info!("graceful shutdown: drain client writes: {:?}", res.as_ref().map(|_| ()));
+
+            // A log at the same index may be applied after the write is truncated by a new leader.
+            // Thus only the writes whose responder is consumed by applying it are drained.
+            let (tx, rx) = C::oneshot();
+            let applied = self.inner.call_core(RaftMsg::GetDrainedWrites { tx }, rx).await.unwrap_or_default();
+
+            let (drained, abandoned) = pending.into_iter().partition(|log_id| applied.contains(log_id));
+            report.drained = drained;
+            report.abandoned = abandoned;
+        }
+
+        if let Some(to) = transfer_leader_to {
+            if to != self.inner.id
+                && self.is_leader_local().await.is_ok()
+                && self.trigger().transfer_leader(to.clone()).await.is_ok()
+            {
+                let res = self.wait(Some(remaining())).current_leader(to.clone(), "transfer leader").await;
+                if res.is_ok() {
+                    report.leader_transferred_to = Some(to);
+                }
+            }
+        }
+
+        tracing::info!("graceful shutdown: {}", report);
+
+        self.shutdown().await?;
+        Ok(report)
+    }
 }
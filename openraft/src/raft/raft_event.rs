@@ -0,0 +1,109 @@
+//! Events of a Raft node, broadcast to the subscribers of [`Raft::event_stream()`].
+//!
+//! [`Raft::event_stream()`]: crate::Raft::event_stream
+
+use std::fmt;
+
+use crate::error::Fatal;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::RaftTypeConfig;
+use crate::RemovedLeaderAction;
+use crate::SnapshotMeta;
+use crate::StoredMembership;
+
+/// A structured event of a Raft node, received from [`Raft::event_stream()`].
+///
+/// [`Raft::event_stream()`]: crate::Raft::event_stream
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum RaftEvent<C>
+where C: RaftTypeConfig
+{
+    /// This node becomes the leader with `vote`.
+    LeaderElected { vote: VoteOf<C> },
+
+    /// This node is no longer the leader of `vote`.
+    SteppedDown { vote: VoteOf<C> },
+
+    /// A membership config is committed.
+    MembershipCommitted { membership: StoredMembership<C> },
+
+    /// A snapshot is built on this node.
+    SnapshotBuilt { meta: SnapshotMeta<C> },
+
+    /// A snapshot received from the leader is installed on this node.
+    SnapshotInstalled { meta: SnapshotMeta<C> },
+
+    /// Logs upto `upto`, inclusive, are purged from the log store.
+    PurgeCompleted { upto: LogIdOf<C> },
+
+    /// The leader fails to replicate to `target`.
+    ///
+    /// It is sent once when the replication starts to fail, and not again until the replication
+    /// to the target succeeds.
+    ReplicationStalled { target: C::NodeId, error: String },
+
+    /// The leader proposes to promote the learner `target` to a voter, because its replication
+    /// lag stays within [`Config::auto_promote_max_lag`] for
+    /// [`Config::auto_promote_stable_period`].
+    ///
+    /// `log_id` is the id of the proposed joint membership config.
+    ///
+    /// [`Config::auto_promote_max_lag`]: crate::Config::auto_promote_max_lag
+    /// [`Config::auto_promote_stable_period`]: crate::Config::auto_promote_stable_period
+    LearnerAutoPromoted { target: C::NodeId, log_id: LogIdOf<C> },
+
+    /// The leader proposes to demote the voter `target` to a learner, because it has not
+    /// acknowledged the leader for [`Config::auto_demote_unreachable_period`].
+    ///
+    /// `log_id` is the id of the proposed joint membership config.
+    ///
+    /// [`Config::auto_demote_unreachable_period`]: crate::Config::auto_demote_unreachable_period
+    VoterAutoDemoted { target: C::NodeId, log_id: LogIdOf<C> },
+
+    /// This leader has applied the membership config at `log_id`, in which it is not a voter,
+    /// and takes `action` as configured by [`Config::removed_leader_action`].
+    ///
+    /// [`Config::removed_leader_action`]: crate::Config::removed_leader_action
+    RemovedFromMembership {
+        log_id: LogIdOf<C>,
+        action: RemovedLeaderAction,
+    },
+
+    /// `RaftCore` quits on a fatal error, such as a storage error or a panic, and this node no
+    /// longer serves any request.
+    ///
+    /// It is the last event of a node. It is not sent if the node is shut down normally, i.e.,
+    /// `error` is never [`Fatal::Stopped`]. A supervisor may restart or fence the node when
+    /// receiving it, instead of discovering the failure by a closed metrics channel.
+    Fatal { error: Fatal<C> },
+}
+
+impl<C> fmt::Display for RaftEvent<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RaftEvent::LeaderElected { vote } => write!(f, "LeaderElected: {}", vote),
+            RaftEvent::SteppedDown { vote } => write!(f, "SteppedDown: {}", vote),
+            RaftEvent::MembershipCommitted { membership } => write!(f, "MembershipCommitted: {}", membership),
+            RaftEvent::SnapshotBuilt { meta } => write!(f, "SnapshotBuilt: {}", meta),
+            RaftEvent::SnapshotInstalled { meta } => write!(f, "SnapshotInstalled: {}", meta),
+            RaftEvent::PurgeCompleted { upto } => write!(f, "PurgeCompleted: upto {}", upto),
+            RaftEvent::ReplicationStalled { target, error } => {
+                write!(f, "ReplicationStalled: target {}: {}", target, error)
+            }
+            RaftEvent::LearnerAutoPromoted { target, log_id } => {
+                write!(f, "LearnerAutoPromoted: target {} at {}", target, log_id)
+            }
+            RaftEvent::VoterAutoDemoted { target, log_id } => {
+                write!(f, "VoterAutoDemoted: target {} at {}", target, log_id)
+            }
+            RaftEvent::RemovedFromMembership { log_id, action } => {
+                write!(f, "RemovedFromMembership: at {}, action: {}", log_id, action)
+            }
+            RaftEvent::Fatal { error } => write!(f, "Fatal: {}", error),
+        }
+    }
+}
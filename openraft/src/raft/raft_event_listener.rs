@@ -0,0 +1,104 @@
+use crate::error::Fatal;
+use crate::raft::RaftEvent;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+use crate::RemovedLeaderAction;
+use crate::SnapshotMeta;
+use crate::StoredMembership;
+
+/// Typed callbacks of the [`RaftEvent`]s of a Raft node, registered with
+/// [`Raft::add_listener()`].
+///
+/// Every method has a default implementation that does nothing, thus a listener only implements
+/// the events it is interested in.
+///
+/// The callbacks are called in a task of the listener, in the order the events happen, not in
+/// `RaftCore`. A slow callback delays only the following events of the same listener.
+///
+/// [`Raft::add_listener()`]: crate::Raft::add_listener
+pub trait RaftEventListener<C>: OptionalSend + 'static
+where C: RaftTypeConfig
+{
+    /// This node becomes the leader with `vote`.
+    fn on_leader_elected(&mut self, vote: &VoteOf<C>) {
+        let _ = vote;
+    }
+
+    /// This node is no longer the leader of `vote`.
+    fn on_stepped_down(&mut self, vote: &VoteOf<C>) {
+        let _ = vote;
+    }
+
+    /// A membership config is committed.
+    fn on_membership_committed(&mut self, membership: &StoredMembership<C>) {
+        let _ = membership;
+    }
+
+    /// A snapshot is built on this node.
+    fn on_snapshot_built(&mut self, meta: &SnapshotMeta<C>) {
+        let _ = meta;
+    }
+
+    /// A snapshot received from the leader is installed on this node.
+    fn on_snapshot_installed(&mut self, meta: &SnapshotMeta<C>) {
+        let _ = meta;
+    }
+
+    /// Logs upto `upto`, inclusive, are purged from the log store.
+    fn on_purge_completed(&mut self, upto: &LogIdOf<C>) {
+        let _ = upto;
+    }
+
+    /// The leader fails to replicate to `target`.
+    fn on_replication_stalled(&mut self, target: &C::NodeId, error: &str) {
+        let _ = (target, error);
+    }
+
+    /// The leader proposes to promote the learner `target` to a voter automatically.
+    fn on_learner_auto_promoted(&mut self, target: &C::NodeId, log_id: &LogIdOf<C>) {
+        let _ = (target, log_id);
+    }
+
+    /// The leader proposes to demote the voter `target` to a learner automatically.
+    fn on_voter_auto_demoted(&mut self, target: &C::NodeId, log_id: &LogIdOf<C>) {
+        let _ = (target, log_id);
+    }
+
+    /// This leader is removed from the voters by the membership config at `log_id`, and takes
+    /// `action`.
+    fn on_removed_from_membership(&mut self, log_id: &LogIdOf<C>, action: RemovedLeaderAction) {
+        let _ = (log_id, action);
+    }
+
+    /// `RaftCore` quits on a fatal `error`, such as a storage error or a panic.
+    ///
+    /// The node no longer serves any request. It is the place for a supervisor to restart or
+    /// fence the node, e.g., by notifying a task that rebuilds the `Raft` from its storage.
+    fn on_fatal(&mut self, error: &Fatal<C>) {
+        let _ = error;
+    }
+}
+
+impl<C> RaftEvent<C>
+where C: RaftTypeConfig
+{
+    /// Call the callback of `listener` for this event.
+    pub(crate) fn dispatch_to<L>(&self, listener: &mut L)
+    where L: RaftEventListener<C> + ?Sized {
+        match self {
+            RaftEvent::LeaderElected { vote } => listener.on_leader_elected(vote),
+            RaftEvent::SteppedDown { vote } => listener.on_stepped_down(vote),
+            RaftEvent::MembershipCommitted { membership } => listener.on_membership_committed(membership),
+            RaftEvent::SnapshotBuilt { meta } => listener.on_snapshot_built(meta),
+            RaftEvent::SnapshotInstalled { meta } => listener.on_snapshot_installed(meta),
+            RaftEvent::PurgeCompleted { upto } => listener.on_purge_completed(upto),
+            RaftEvent::ReplicationStalled { target, error } => listener.on_replication_stalled(target, error),
+            RaftEvent::LearnerAutoPromoted { target, log_id } => listener.on_learner_auto_promoted(target, log_id),
+            RaftEvent::VoterAutoDemoted { target, log_id } => listener.on_voter_auto_demoted(target, log_id),
+            RaftEvent::RemovedFromMembership { log_id, action } => listener.on_removed_from_membership(log_id, *action),
+            RaftEvent::Fatal { error } => listener.on_fatal(error),
+        }
+    }
+}
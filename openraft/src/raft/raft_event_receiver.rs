@@ -0,0 +1,66 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::async_runtime::MpscUnboundedReceiver;
+use crate::core::event_broadcast::EventItem;
+use crate::raft::RaftEvent;
+use crate::type_config::alias::MpscUnboundedReceiverOf;
+use crate::RaftTypeConfig;
+
+/// The max number of events queued for a subscriber of [`Raft::event_stream()`] or a
+/// [`RaftEventListener`].
+///
+/// [`Raft::event_stream()`]: crate::Raft::event_stream
+/// [`RaftEventListener`]: crate::raft::RaftEventListener
+pub const EVENT_QUEUE_SIZE: usize = 1024;
+
+/// Receives the [`RaftEvent`]s of a node, returned by [`Raft::event_stream()`].
+///
+/// At most [`EVENT_QUEUE_SIZE`] events are queued for a receiver. When a receiver falls further
+/// behind, the following events are dropped for it, and the next [`recv()`] after the queued
+/// events returns [`RecvEventError::Lagged`] with the number of dropped events.
+///
+/// [`Raft::event_stream()`]: crate::Raft::event_stream
+/// [`recv()`]: Self::recv
+pub struct RaftEventReceiver<C>
+where C: RaftTypeConfig
+{
+    pub(crate) rx: MpscUnboundedReceiverOf<C, EventItem<C>>,
+
+    /// The number of events sent to this receiver but not yet received.
+    pub(crate) queued: Arc<AtomicUsize>,
+}
+
+impl<C> RaftEventReceiver<C>
+where C: RaftTypeConfig
+{
+    /// Receive the next event.
+    ///
+    /// It returns [`RecvEventError::Lagged`] once in place of the events dropped for this
+    /// receiver, and [`RecvEventError::Closed`] after the node is shut down and every queued
+    /// event is received.
+    pub async fn recv(&mut self) -> Result<RaftEvent<C>, RecvEventError> {
+        let item = self.rx.recv().await.ok_or(RecvEventError::Closed)?;
+        self.queued.fetch_sub(1, Ordering::Relaxed);
+
+        match item {
+            EventItem::Event(event) => Ok(event),
+            EventItem::Lagged(dropped) => Err(RecvEventError::Lagged(dropped)),
+        }
+    }
+}
+
+/// Error returned by [`RaftEventReceiver::recv()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum RecvEventError {
+    /// The receiver fell behind and the contained number of events are dropped for it.
+    ///
+    /// The events after them are received as usual.
+    #[error("event receiver lagged behind, {0} events are dropped")]
+    Lagged(u64),
+
+    /// The node is shut down and every event is received.
+    #[error("event stream is closed")]
+    Closed,
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 use std::fmt::Debug;
 use std::future::Future;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use tracing::Level;
@@ -9,15 +11,22 @@ use crate::async_runtime::watch::WatchReceiver;
 use crate::async_runtime::watch::WatchSender;
 use crate::async_runtime::MpscUnboundedSender;
 use crate::config::RuntimeConfig;
+use crate::core::event_broadcast::EventBroadcast;
+use crate::core::io_counters::IoCounters;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::TickHandle;
 use crate::error::Fatal;
 use crate::error::RaftError;
 use crate::metrics::RaftDataMetrics;
+use crate::metrics::RaftNetworkMetrics;
+use crate::metrics::RaftReplicationMetrics;
 use crate::metrics::RaftServerMetrics;
+use crate::metrics::ServerStateChange;
 use crate::raft::core_state::CoreState;
+use crate::raft::CorrelationId;
 use crate::type_config::alias::AsyncRuntimeOf;
+use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::MpscUnboundedSenderOf;
 use crate::type_config::alias::MutexOf;
 use crate::type_config::alias::OneshotReceiverOf;
@@ -43,6 +52,17 @@ where C: RaftTypeConfig
     pub(in crate::raft) rx_metrics: WatchReceiverOf<C, RaftMetrics<C>>,
     pub(in crate::raft) rx_data_metrics: WatchReceiverOf<C, RaftDataMetrics<C>>,
     pub(in crate::raft) rx_server_metrics: WatchReceiverOf<C, RaftServerMetrics<C>>,
+    pub(in crate::raft) rx_replication_metrics: WatchReceiverOf<C, RaftReplicationMetrics<C>>,
+    pub(in crate::raft) rx_network_metrics: WatchReceiverOf<C, RaftNetworkMetrics<C>>,
+    pub(in crate::raft) rx_applied: WatchReceiverOf<C, Option<LogIdOf<C>>>,
+    pub(in crate::raft) rx_server_state: WatchReceiverOf<C, ServerStateChange<C>>,
+    pub(in crate::raft) events: Arc<EventBroadcast<C>>,
+
+    /// The next [`CorrelationId`] to generate for a client write.
+    pub(in crate::raft) next_correlation_id: AtomicU64,
+
+    /// The IO counters, shared with `RaftCore`, to count the snapshot bytes received.
+    pub(in crate::raft) io_counters: Arc<IoCounters>,
 
     pub(in crate::raft) tx_shutdown: std::sync::Mutex<Option<OneshotSenderOf<C, ()>>>,
     pub(in crate::raft) core_state: std::sync::Mutex<CoreState<C>>,
@@ -51,11 +71,22 @@ where C: RaftTypeConfig
     #[cfg_attr(not(feature = "tokio-rt"), allow(dead_code))]
     // This field will only be read when feature tokio-rt is on
     pub(in crate::raft) snapshot: MutexOf<C, Option<crate::network::snapshot_transport::Streaming<C>>>,
+
+    /// Serializes the queued membership changes, see [`Config::membership_change_queue_depth`].
+    pub(in crate::raft) membership_change_lock: MutexOf<C, ()>,
+
+    /// The number of membership changes waiting for [`Self::membership_change_lock`].
+    pub(in crate::raft) membership_change_waiting: AtomicU64,
 }
 
 impl<C> RaftInner<C>
 where C: RaftTypeConfig
 {
+    /// Generate a [`CorrelationId`] for a client write that does not provide one.
+    pub(crate) fn new_correlation_id(&self) -> CorrelationId {
+        CorrelationId::new(self.next_correlation_id.fetch_add(1, Ordering::Relaxed))
+    }
+
     /// Send a RaftMsg to RaftCore
     pub(crate) async fn send_msg(&self, mes: RaftMsg<C>) -> Result<(), Fatal<C>> {
         let send_res = self.tx_api.send(mes);
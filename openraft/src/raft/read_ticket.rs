@@ -0,0 +1,113 @@
+//! A ticket for building custom read consistency schemes.
+
+use std::fmt;
+
+use crate::async_runtime::watch::WatchReceiver;
+use crate::display_ext::DisplayOptionExt;
+use crate::error::Fatal;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::WatchReceiverOf;
+use crate::LogIdOptionExt;
+use crate::RaftTypeConfig;
+
+/// The result of [`Raft::get_read_ticket()`](crate::Raft::get_read_ticket): the log id a read has
+/// to wait for, along with a watch of the local applied log id.
+///
+/// The leadership is confirmed by a quorum **after** [`Self::requested_at`], thus a read that is
+/// served once the state machine reaches [`Self::read_log_id`] observes every write committed
+/// before that instant. Applications can build their own consistency schemes upon it, e.g., a
+/// bounded staleness read that reuses a ticket until it is older than a given duration, instead of
+/// confirming the leadership for every read.
+pub struct ReadTicket<C>
+where C: RaftTypeConfig
+{
+    read_log_id: Option<LogIdOf<C>>,
+    requested_at: InstantOf<C>,
+    rx_applied: WatchReceiverOf<C, Option<LogIdOf<C>>>,
+}
+
+impl<C> ReadTicket<C>
+where C: RaftTypeConfig
+{
+    pub(in crate::raft) fn new(
+        read_log_id: Option<LogIdOf<C>>,
+        requested_at: InstantOf<C>,
+        rx_applied: WatchReceiverOf<C, Option<LogIdOf<C>>>,
+    ) -> Self {
+        Self {
+            read_log_id,
+            requested_at,
+            rx_applied,
+        }
+    }
+
+    /// The log id up to which the state machine has to apply before serving a read.
+    pub fn read_log_id(&self) -> Option<&LogIdOf<C>> {
+        self.read_log_id.as_ref()
+    }
+
+    /// The time before the leadership confirmation is started.
+    ///
+    /// The ticket reflects every write committed before this time.
+    pub fn requested_at(&self) -> InstantOf<C> {
+        self.requested_at
+    }
+
+    /// The last log id applied to the local state machine.
+    pub fn applied(&self) -> Option<LogIdOf<C>> {
+        self.rx_applied.borrow_watched().clone()
+    }
+
+    /// Return `true` if the local state machine has applied upto [`Self::read_log_id`].
+    pub fn is_ready(&self) -> bool {
+        self.applied().index() >= self.read_log_id.index()
+    }
+
+    /// Wait until the local state machine applies upto [`Self::read_log_id`].
+    ///
+    /// Returns the last applied log id, which may be greater than the read log id.
+    /// It returns an error only if this Raft node is shut down.
+    pub async fn wait_ready(&self) -> Result<Option<LogIdOf<C>>, Fatal<C>> {
+        let mut rx = self.rx_applied.clone();
+
+        loop {
+            let applied = rx.borrow_watched().clone();
+            if applied.index() >= self.read_log_id.index() {
+                return Ok(applied);
+            }
+
+            rx.changed().await.map_err(|_| Fatal::Stopped)?;
+        }
+    }
+
+    /// Get a handle to the channel of the local applied log id.
+    pub fn applied_watch(&self) -> WatchReceiverOf<C, Option<LogIdOf<C>>> {
+        self.rx_applied.clone()
+    }
+}
+
+impl<C> fmt::Debug for ReadTicket<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadTicket")
+            .field("read_log_id", &self.read_log_id)
+            .field("requested_at", &self.requested_at)
+            .field("applied", &self.applied())
+            .finish()
+    }
+}
+
+impl<C> fmt::Display for ReadTicket<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ReadTicket{{read_log_id: {}, applied: {}}}",
+            self.read_log_id.display(),
+            self.applied().display()
+        )
+    }
+}
@@ -19,7 +19,12 @@ use crate::RaftTypeConfig;
 /// Usually an implementation of [`Responder`] is a oneshot channel Sender,
 /// and [`Responder::Receiver`] is a oneshot channel Receiver.
 ///
+/// An application can also build a responder by itself and submit it with
+/// [`Raft::client_write_with_responder`], e.g., one that sends the result to a remote caller over
+/// the application's own transport.
+///
 /// [`AppData`]: `crate::AppData`
+/// [`Raft::client_write_with_responder`]: `crate::Raft::client_write_with_responder`
 pub trait Responder<C>: OptionalSend + 'static
 where C: RaftTypeConfig
 {
@@ -0,0 +1,62 @@
+//! A handle to the response of a submitted client write.
+
+use std::error::Error;
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::error::ClientWriteError;
+use crate::error::RaftError;
+use crate::raft::ClientWriteResponse;
+use crate::raft::ClientWriteResult;
+use crate::raft::RaftInner;
+use crate::type_config::alias::ResponderReceiverOf;
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+
+/// A handle to the result of a client write that has been submitted but not yet awaited.
+///
+/// It is returned by [`Raft::client_write_handle`] once the proposal is enqueued to `RaftCore`.
+/// The handle does not need a task to be polled: it can be stored and awaited later with
+/// [`ResponseHandle::response`], or dropped if the caller is not interested in the result.
+/// Dropping it does not cancel the write.
+///
+/// [`Raft::client_write_handle`]: crate::Raft::client_write_handle
+pub struct ResponseHandle<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<RaftInner<C>>,
+    rx: ResponderReceiverOf<C>,
+}
+
+impl<C> ResponseHandle<C>
+where C: RaftTypeConfig
+{
+    pub(in crate::raft) fn new(inner: Arc<RaftInner<C>>, rx: ResponderReceiverOf<C>) -> Self {
+        Self { inner, rx }
+    }
+
+    /// Wait for the write to be applied and return the response from the state machine.
+    ///
+    /// It returns the same result as [`Raft::client_write`]: a [`Fatal`] error if `RaftCore`
+    /// stopped before responding, or a [`ClientWriteError`] if the write is rejected, e.g., this
+    /// node is not a leader.
+    ///
+    /// [`Raft::client_write`]: crate::Raft::client_write
+    /// [`Fatal`]: crate::error::Fatal
+    pub async fn response<E>(self) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        let res: ClientWriteResult<C> = self.inner.recv_msg(self.rx).await?;
+        let resp = res.map_err(RaftError::APIError)?;
+        Ok(resp)
+    }
+
+    /// Return the underlying [`Responder::Receiver`], for an application defined responder.
+    ///
+    /// [`Responder::Receiver`]: crate::raft::responder::Responder::Receiver
+    pub fn into_receiver(self) -> ResponderReceiverOf<C> {
+        self.rx
+    }
+}
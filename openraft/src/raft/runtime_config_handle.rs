@@ -2,6 +2,10 @@
 
 use std::sync::atomic::Ordering;
 
+use openraft_macros::since;
+
+use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::error::Fatal;
 use crate::raft::RaftInner;
 use crate::RaftTypeConfig;
 
@@ -42,4 +46,29 @@ where C: RaftTypeConfig
     pub fn elect(&self, enabled: bool) {
         self.raft_inner.runtime_config.enable_elect.store(enabled, Ordering::Relaxed);
     }
+
+    /// Pause the election and heartbeat triggered by ticks, e.g., for a maintenance window or a
+    /// deterministic test.
+    ///
+    /// Unlike [`Self::tick()`], the ticker keeps running, and a paused node neither starts an
+    /// election nor sends heartbeats until [`Self::resume_ticks()`] is called, regardless of
+    /// [`Self::elect()`] and [`Self::heartbeat()`]. Actions triggered explicitly with
+    /// [`Raft::trigger()`](crate::Raft::trigger) are not affected. The paused state is reported
+    /// in [`RaftServerMetrics::ticks_paused`](crate::metrics::RaftServerMetrics::ticks_paused).
+    ///
+    /// It returns once the command is sent to `RaftCore`.
+    #[since(version = "0.10.0")]
+    pub async fn pause_ticks(&self) -> Result<(), Fatal<C>> {
+        self.raft_inner
+            .send_external_command(ExternalCommand::PauseTicks { paused: true }, "pause_ticks")
+            .await
+    }
+
+    /// Resume the election and heartbeat paused by [`Self::pause_ticks()`].
+    #[since(version = "0.10.0")]
+    pub async fn resume_ticks(&self) -> Result<(), Fatal<C>> {
+        self.raft_inner
+            .send_external_command(ExternalCommand::PauseTicks { paused: false }, "resume_ticks")
+            .await
+    }
 }
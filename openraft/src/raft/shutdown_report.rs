@@ -0,0 +1,57 @@
+//! What a graceful shutdown has done.
+
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::display_ext::DisplaySliceExt;
+use crate::type_config::alias::LogIdOf;
+use crate::RaftTypeConfig;
+
+/// The result of [`Raft::shutdown_gracefully()`](crate::Raft::shutdown_gracefully).
+///
+/// It tells which of the client writes accepted before the shutdown were applied, and which were
+/// abandoned when the deadline is reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShutdownReport<C>
+where C: RaftTypeConfig
+{
+    /// Log ids of the accepted client writes that are applied before shutting down.
+    pub drained: Vec<LogIdOf<C>>,
+
+    /// Log ids of the accepted client writes that are not applied before the deadline, or are
+    /// truncated by a new leader.
+    ///
+    /// Such a write is not necessarily lost if it is not truncated: it may still be committed by
+    /// the next leader.
+    pub abandoned: Vec<LogIdOf<C>>,
+
+    /// The node that takes over the leadership before this node shuts down, if a leader transfer
+    /// is requested and completes before the deadline.
+    pub leader_transferred_to: Option<C::NodeId>,
+}
+
+impl<C> Default for ShutdownReport<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            drained: vec![],
+            abandoned: vec![],
+            leader_transferred_to: None,
+        }
+    }
+}
+
+impl<C> fmt::Display for ShutdownReport<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ShutdownReport{{drained: {}, abandoned: {}, leader_transferred_to: {}}}",
+            self.drained.display(),
+            self.abandoned.display(),
+            self.leader_transferred_to.display()
+        )
+    }
+}
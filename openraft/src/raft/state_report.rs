@@ -0,0 +1,74 @@
+//! The result of the internal consistency checks of a Raft node.
+
+use std::fmt;
+
+/// One consistency check run by [`Raft::verify_state()`](crate::Raft::verify_state).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct StateCheck {
+    /// What is checked, such as `"committed <= last_log"`.
+    pub name: String,
+
+    /// The violation found, or `None` if the check passes.
+    pub violation: Option<String>,
+}
+
+impl StateCheck {
+    /// Returns `true` if the check passes.
+    pub fn is_ok(&self) -> bool {
+        self.violation.is_none()
+    }
+}
+
+impl fmt::Display for StateCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.violation {
+            None => write!(f, "{}: ok", self.name),
+            Some(v) => write!(f, "{}: {}", self.name, v),
+        }
+    }
+}
+
+/// The result of [`Raft::verify_state()`](crate::Raft::verify_state).
+///
+/// It lists every check that is run, whether it passes or not, so that it can be returned as is
+/// by a health endpoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct StateReport {
+    /// The checks in the order they are run.
+    pub checks: Vec<StateCheck>,
+}
+
+impl StateReport {
+    /// Returns `true` if all of the checks pass.
+    pub fn is_ok(&self) -> bool {
+        self.checks.iter().all(|c| c.is_ok())
+    }
+
+    /// Iterate the checks that do not pass.
+    pub fn violations(&self) -> impl Iterator<Item = &StateCheck> {
+        self.checks.iter().filter(|c| !c.is_ok())
+    }
+
+    /// Record the result of a check.
+    pub(crate) fn check(&mut self, name: impl ToString, res: Result<(), String>) {
+        self.checks.push(StateCheck {
+            name: name.to_string(),
+            violation: res.err(),
+        });
+    }
+}
+
+impl fmt::Display for StateReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StateReport{{")?;
+        for (i, c) in self.checks.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        write!(f, "}}")
+    }
+}
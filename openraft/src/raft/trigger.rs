@@ -1,9 +1,13 @@
 //! Trigger an action to RaftCore by external caller.
 
+use openraft_macros::since;
+
 use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::error::into_ok::into_ok;
 use crate::error::AllowNextRevertError;
 use crate::error::Fatal;
 use crate::raft::RaftInner;
+use crate::type_config::alias::LogIdOf;
 use crate::type_config::TypeConfigExt;
 use crate::RaftTypeConfig;
 
@@ -68,6 +72,8 @@ where C: RaftTypeConfig
     /// The [`max_in_snapshot_log_to_keep`] config is not taken into account
     /// when purging logs.
     ///
+    /// It returns the log id upto which the purge is scheduled, which may be smaller than `upto`,
+    /// or `None` if no log can be purged, e.g., there is no snapshot yet.
     /// It returns error only when RaftCore has [`Fatal`] error, e.g. shut down or having storage
     /// error.
     ///
@@ -76,8 +82,13 @@ where C: RaftTypeConfig
     /// can't be purged until the replication task is finished.
     ///
     /// [`max_in_snapshot_log_to_keep`]: `crate::Config::max_in_snapshot_log_to_keep`
-    pub async fn purge_log(&self, upto: u64) -> Result<(), Fatal<C>> {
-        self.raft_inner.send_external_command(ExternalCommand::PurgeLog { upto }, "purge_log").await
+    #[since(version = "0.10.0", change = "return the scheduled purge point")]
+    pub async fn purge_log(&self, upto: u64) -> Result<Option<LogIdOf<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.raft_inner.send_external_command(ExternalCommand::PurgeLog { upto, tx }, "purge_log").await?;
+
+        let scheduled = into_ok(self.raft_inner.recv_msg(rx).await?);
+        Ok(scheduled)
     }
 
     /// Submit a command to inform RaftCore to transfer leadership to the specified node.
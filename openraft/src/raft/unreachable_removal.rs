@@ -0,0 +1,48 @@
+//! The result of removing the unreachable nodes from the cluster.
+
+use std::fmt;
+use std::fmt::Debug;
+
+use crate::display_ext::DisplaySliceExt;
+use crate::membership::RemovalPlan;
+use crate::raft::ClientWriteResponse;
+use crate::RaftTypeConfig;
+
+/// The result of [`Raft::remove_unreachable_nodes()`](crate::Raft::remove_unreachable_nodes): the
+/// plan built by the leader and the responses of the membership changes proposed for it.
+pub struct UnreachableRemoval<C>
+where C: RaftTypeConfig
+{
+    /// Which nodes are unreachable and which of them are removed.
+    pub plan: RemovalPlan<C>,
+
+    /// The responses of the membership changes, which remove the voters first and then the
+    /// learners. It is empty if there is nothing to remove.
+    pub responses: Vec<ClientWriteResponse<C>>,
+}
+
+impl<C> Debug for UnreachableRemoval<C>
+where
+    C: RaftTypeConfig,
+    C::R: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnreachableRemoval")
+            .field("plan", &self.plan)
+            .field("responses", &self.responses)
+            .finish()
+    }
+}
+
+impl<C> fmt::Display for UnreachableRemoval<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "UnreachableRemoval{{plan: {}, responses: {}}}",
+            self.plan,
+            self.responses.display()
+        )
+    }
+}
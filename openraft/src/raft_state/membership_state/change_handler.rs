@@ -1,5 +1,13 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::core::replication_lag;
 use crate::error::ChangeMembershipError;
 use crate::error::InProgress;
+use crate::membership::LaggingVoter;
+use crate::membership::MembershipChangeReport;
+use crate::metrics::ReplicationLag;
+use crate::quorum::QuorumSet;
 use crate::ChangeMembers;
 use crate::Membership;
 use crate::MembershipState;
@@ -39,6 +47,69 @@ where C: RaftTypeConfig
         Ok(new_membership)
     }
 
+    /// Builds a report of what applying `change` would do, without validating it.
+    ///
+    /// `matching` returns the last log index replicated to a node, and a voter is lagging if it is
+    /// behind `last_log_index` by more than `max_lag`. `estimate` returns how long it takes for a
+    /// node to catch up the given number of entries.
+    ///
+    /// The joint config and the final uniform config are built the same way
+    /// `Raft::change_membership()` proposes them, i.e., by applying `change` twice.
+    pub(crate) fn report(
+        &self,
+        change: ChangeMembers<C>,
+        retain: bool,
+        last_log_index: Option<u64>,
+        max_lag: u64,
+        matching: impl Fn(&C::NodeId) -> Option<u64>,
+        estimate: impl Fn(u64) -> ReplicationLag,
+    ) -> MembershipChangeReport<C> {
+        let current = self.state.effective().membership().clone();
+
+        let first = current.clone().change_unchecked(change.clone(), retain);
+        let (joint, target) = if first.get_joint_config().len() > 1 {
+            let target = first.clone().change_unchecked(change, retain);
+            (Some(first), target)
+        } else {
+            (None, first)
+        };
+
+        let new_config = joint.as_ref().unwrap_or(&target);
+
+        let mut missing = BTreeSet::new();
+        let mut lagging = BTreeMap::new();
+        let mut up_to_date = BTreeSet::new();
+
+        for id in new_config.voter_ids() {
+            if new_config.get_node(&id).is_none() {
+                missing.insert(id);
+                continue;
+            }
+
+            let matched = matching(&id);
+            let entries_behind = replication_lag(&matched, &last_log_index);
+            if entries_behind > max_lag {
+                let lag = estimate(entries_behind);
+                lagging.insert(id, LaggingVoter { matched, lag });
+            } else {
+                up_to_date.insert(id);
+            }
+        }
+
+        let quorum_preserved = new_config.to_quorum_set().is_quorum(up_to_date.iter())
+            && target.to_quorum_set().is_quorum(up_to_date.iter());
+
+        MembershipChangeReport {
+            current,
+            joint,
+            target,
+            missing,
+            lagging,
+            quorum_preserved,
+            in_progress: self.ensure_committed().err(),
+        }
+    }
+
     /// Ensures that the latest membership has been committed.
     ///
     /// Returns Ok if the last membership is committed, or an InProgress error
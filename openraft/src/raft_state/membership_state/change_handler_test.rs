@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use maplit::btreemap;
 use maplit::btreeset;
 
 use crate::engine::testing::log_id;
@@ -8,6 +9,8 @@ use crate::error::ChangeMembershipError;
 use crate::error::EmptyMembership;
 use crate::error::InProgress;
 use crate::error::LearnerNotFound;
+use crate::membership::LaggingVoter;
+use crate::metrics::ReplicationLag;
 use crate::ChangeMembers;
 use crate::EffectiveMembership;
 use crate::Membership;
@@ -31,6 +34,21 @@ fn m123_345() -> Membership<UTConfig> {
     Membership::new_with_defaults(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], [])
 }
 
+/// Estimate the catch-up time at 100 entries per second.
+fn estimate(entries_behind: u64) -> ReplicationLag {
+    ReplicationLag {
+        entries_behind,
+        millis_behind: Some(entries_behind * 10),
+    }
+}
+
+fn lagging(matched: Option<u64>, entries_behind: u64) -> LaggingVoter {
+    LaggingVoter {
+        matched,
+        lag: estimate(entries_behind),
+    }
+}
+
 #[test]
 fn test_apply_not_committed() -> anyhow::Result<()> {
     let new = || MembershipState::<UTConfig>::new(effmem(2, 2, m1()), effmem(3, 4, m123_345()));
@@ -90,3 +108,103 @@ fn test_apply_retain_learner() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_report_joint_and_target() -> anyhow::Result<()> {
+    let st = MembershipState::<UTConfig>::new(effmem(3, 4, m12()), effmem(3, 4, m12()));
+
+    // Node 3 is not in nodes, node 2 replicated nothing.
+    let report = st.change_handler().report(
+        ChangeMembers::ReplaceAllVoters(btreeset! {1,3}),
+        true,
+        Some(10),
+        5,
+        |id| if *id == 1 { Some(10) } else { None },
+        estimate,
+    );
+
+    assert_eq!(m12(), report.current);
+    assert_eq!(
+        Some(Membership::new_unchecked(
+            vec![btreeset! {1,2}, btreeset! {1,3}],
+            btreemap! {1=>(),2=>()}
+        )),
+        report.joint
+    );
+    assert_eq!(
+        Membership::new_unchecked(vec![btreeset! {1,3}], btreemap! {1=>(),2=>()}),
+        report.target
+    );
+    assert_eq!(btreeset! {3}, report.missing);
+    assert_eq!(btreemap! {2=>lagging(None, 11)}, report.lagging);
+    assert!(!report.quorum_preserved);
+    assert_eq!(None, report.in_progress);
+    assert!(!report.is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_report_quorum_preserved() -> anyhow::Result<()> {
+    let m = Membership::new_with_defaults(vec![btreeset! {1,2,3}], [4]);
+    let st = MembershipState::<UTConfig>::new(effmem(3, 4, m.clone()), effmem(3, 4, m));
+
+    // Node 3 lags behind, but {1,2,4} is still a quorum of both configs.
+    let report = st.change_handler().report(
+        ChangeMembers::AddVoterIds(btreeset! {4}),
+        true,
+        Some(10),
+        5,
+        |id| if *id == 3 { Some(2) } else { Some(8) },
+        estimate,
+    );
+
+    assert_eq!(
+        Some(Membership::new_with_defaults(
+            vec![btreeset! {1,2,3}, btreeset! {1,2,3,4}],
+            []
+        )),
+        report.joint
+    );
+    assert_eq!(
+        Membership::new_with_defaults(vec![btreeset! {1,2,3,4}], []),
+        report.target
+    );
+    assert!(report.missing.is_empty());
+    assert_eq!(btreemap! {3=>lagging(Some(2), 8)}, report.lagging);
+    assert!(report.quorum_preserved);
+    assert!(report.is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_report_in_progress() -> anyhow::Result<()> {
+    let st = MembershipState::<UTConfig>::new(effmem(2, 2, m1()), effmem(3, 4, m123_345()));
+
+    let report = st.change_handler().report(
+        ChangeMembers::RemoveVoters(btreeset! {1,2}),
+        false,
+        Some(10),
+        5,
+        |_id| Some(10),
+        estimate,
+    );
+
+    assert_eq!(None, report.joint);
+    assert_eq!(
+        Membership::new_with_defaults(vec![btreeset! {3,4,5}], []),
+        report.target
+    );
+    assert!(report.quorum_preserved);
+    assert_eq!(
+        Some(InProgress {
+            committed: Some(log_id(2, 1, 2)),
+            membership_log_id: Some(log_id(3, 1, 4))
+        }),
+        report.in_progress
+    );
+    assert!(!report.is_ok());
+
+    Ok(())
+}
@@ -188,8 +188,8 @@ where C: RaftTypeConfig
         None
     }
 
-    // This method is only used by tests
-    #[cfg(test)]
+    // This method is only used by tests and fuzzing
+    #[cfg(any(test, feature = "fuzzing"))]
     pub(crate) fn set_effective(&mut self, e: Arc<EffectiveMembership<C>>) {
         self.effective = e
     }
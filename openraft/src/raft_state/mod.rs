@@ -1,6 +1,7 @@
 use std::error::Error;
 use std::ops::Deref;
 
+use openraft_macros::since;
 use validit::Valid;
 use validit::Validate;
 
@@ -201,6 +202,30 @@ where C: RaftTypeConfig
         self.vote.last_update()
     }
 
+    /// Get the id of the last known log entry, which may not be flushed to storage yet.
+    #[since(version = "0.10.0")]
+    pub fn last_log_id(&self) -> Option<&LogIdOf<C>> {
+        LogStateReader::last_log_id(self)
+    }
+
+    /// Get the id of the last log entry applied to the state machine.
+    #[since(version = "0.10.0")]
+    pub fn applied(&self) -> Option<&LogIdOf<C>> {
+        self.io_applied()
+    }
+
+    /// Get the last log id included in the last built or installed snapshot.
+    #[since(version = "0.10.0")]
+    pub fn snapshot_last_log_id(&self) -> Option<&LogIdOf<C>> {
+        LogStateReader::snapshot_last_log_id(self)
+    }
+
+    /// Get the id of the last purged log entry.
+    #[since(version = "0.10.0")]
+    pub fn last_purged_log_id(&self) -> Option<&LogIdOf<C>> {
+        LogStateReader::last_purged_log_id(self)
+    }
+
     pub(crate) fn is_initialized(&self) -> bool {
         // initialize() writes a membership config log entry.
         // If there are logs, it is already initialized.
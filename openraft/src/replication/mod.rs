@@ -14,6 +14,7 @@ use futures::future::FutureExt;
 pub(crate) use replication_session_id::ReplicationSessionId;
 use request::Data;
 use request::Replicate;
+use request::WriteOrigin;
 pub(crate) use response::Progress;
 use response::ReplicationResult;
 use tracing_futures::Instrument;
@@ -22,6 +23,7 @@ use crate::async_runtime::MpscUnboundedReceiver;
 use crate::async_runtime::MpscUnboundedSender;
 use crate::async_runtime::MpscUnboundedWeakSender;
 use crate::config::Config;
+use crate::core::io_counters::IoCounters;
 use crate::core::notification::Notification;
 use crate::core::sm::handle::SnapshotReader;
 use crate::display_ext::DisplayInstantExt;
@@ -33,7 +35,9 @@ use crate::error::PayloadTooLarge;
 use crate::error::RPCError;
 use crate::error::ReplicationClosed;
 use crate::error::ReplicationError;
+use crate::error::StreamingError;
 use crate::error::Timeout;
+use crate::error::Unreachable;
 use crate::log_id::LogIdOptionExt;
 use crate::log_id_range::LogIdRange;
 use crate::network::v2::RaftNetworkV2;
@@ -60,8 +64,10 @@ use crate::type_config::alias::VoteOf;
 use crate::type_config::async_runtime::mutex::Mutex;
 use crate::type_config::TypeConfigExt;
 use crate::vote::raft_vote::RaftVoteExt;
+use crate::Instant;
 use crate::RaftNetworkFactory;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::StorageError;
 
 /// The handle to a spawned replication stream.
@@ -121,6 +127,11 @@ where
     /// to quit.
     snapshot_state: Option<(OneshotSenderOf<C, ()>, JoinHandleOf<C, ()>)>,
 
+    /// The id of the last snapshot the target refused because of its data format version.
+    ///
+    /// Such a snapshot is not sent again: the target refuses it until it is upgraded.
+    rejected_snapshot_id: Option<SnapshotId>,
+
     /// The backoff policy if an [`Unreachable`](`crate::error::Unreachable`) error is returned.
     /// It will be reset to `None` when an successful response is received.
     backoff: Option<Backoff>,
@@ -146,6 +157,9 @@ where
     /// Appropriate number of entries to send.
     /// This is only used by AppendEntries RPC.
     entries_hint: ReplicationHint,
+
+    /// The storage and network IO counters.
+    io_counters: Arc<IoCounters>,
 }
 
 impl<C, N, LS> ReplicationCore<C, N, LS>
@@ -169,6 +183,7 @@ where
         log_reader: LS::LogReader,
         snapshot_reader: SnapshotReader<C>,
         tx_raft_core: MpscUnboundedSenderOf<C, Notification<C>>,
+        io_counters: Arc<IoCounters>,
         span: tracing::Span,
     ) -> ReplicationHandle<C> {
         tracing::debug!(
@@ -188,6 +203,7 @@ where
             network,
             snapshot_network: Arc::new(C::mutex(snapshot_network)),
             snapshot_state: None,
+            rejected_snapshot_id: None,
             backoff: None,
             log_reader,
             snapshot_reader,
@@ -199,6 +215,7 @@ where
             weak_tx_event: tx_event.downgrade(),
             next_action: None,
             entries_hint: Default::default(),
+            io_counters,
         };
 
         let join_handle = C::spawn(this.main().instrument(span));
@@ -232,12 +249,12 @@ where
                     let m = &self.matching;
                     let d = LogIdRange::new(m.clone(), m.clone());
 
-                    log_data = Some(d.clone());
-                    self.send_log_entries(d, false).await
+                    log_data = Some(Data::new_logs(d.clone(), WriteOrigin::default()));
+                    self.send_log_entries(d, WriteOrigin::default(), false).await
                 }
-                Data::Logs(log) => {
-                    log_data = Some(log.clone());
-                    self.send_log_entries(log, true).await
+                Data::Logs(log, origin) => {
+                    log_data = Some(Data::new_logs(log.clone(), origin.clone()));
+                    self.send_log_entries(log, origin, true).await
                 }
                 Data::Snapshot(snap) => self.stream_snapshot(snap).await,
                 Data::SnapshotCallback(resp) => self.handle_snapshot_callback(resp),
@@ -294,7 +311,7 @@ where
                                     self.update_hint(too_large);
 
                                     // PayloadTooLarge is a retryable error: retry at once.
-                                    self.next_action = Some(Data::Logs(log_data.unwrap()));
+                                    self.next_action = Some(log_data.unwrap());
                                     true
                                 }
                                 RPCError::Network(_) => false,
@@ -353,6 +370,9 @@ where
             RPCTypes::TransferLeader => {
                 unreachable!("TransferLeader RPC should not be too large")
             }
+            RPCTypes::ReadIndex => {
+                unreachable!("ReadIndex RPC should not be too large")
+            }
         }
     }
 
@@ -369,6 +389,7 @@ where
     async fn send_log_entries(
         &mut self,
         log_ids: LogIdRange<C>,
+        origin: WriteOrigin,
         has_payload: bool,
     ) -> Result<Option<Data<C>>, ReplicationError<C>> {
         tracing::debug!(log_id_range = display(&log_ids), "send_log_entries",);
@@ -435,8 +456,22 @@ where
         );
 
         let the_timeout = Duration::from_millis(self.config.heartbeat_interval);
-        let option = RPCOption::new(the_timeout);
+        let mut option = RPCOption::new(the_timeout);
+        option.span = origin.span();
+
+        let correlation_ids = origin.correlation_ids(sending_range.prev.next_index()..sending_range.last.next_index());
+        for (index, correlation_id) in correlation_ids.iter() {
+            tracing::debug!(
+                correlation_id = display(correlation_id),
+                log_index = index,
+                target = display(&self.target),
+                "client write is sent to target"
+            );
+        }
+        option.correlation_ids = correlation_ids.into_iter().map(|(_, id)| id).collect();
+
         let res = C::timeout(the_timeout, self.network.append_entries(payload, option)).await;
+        self.io_counters.record_rpc(RPCTypes::AppendEntries, &res);
 
         tracing::debug!("append_entries res: {:?}", res);
 
@@ -452,6 +487,8 @@ where
 
         let append_resp = append_res?;
 
+        self.notify_rpc_latency(RPCTypes::AppendEntries, leader_time);
+
         tracing::debug!(
             req = display(&sending_range),
             resp = display(&append_resp),
@@ -465,7 +502,7 @@ where
                 let matching = &sending_range.last;
                 if has_payload {
                     self.notify_progress(ReplicationResult(Ok(matching.clone())));
-                    Ok(self.next_action_to_send(matching.clone(), log_ids))
+                    Ok(self.next_action_to_send(matching.clone(), log_ids, origin))
                 } else {
                     Ok(None)
                 }
@@ -477,7 +514,7 @@ where
 
                 if has_payload {
                     self.notify_progress(ReplicationResult(Ok(matching.clone())));
-                    Ok(self.next_action_to_send(matching.clone(), log_ids))
+                    Ok(self.next_action_to_send(matching.clone(), log_ids, origin))
                 } else {
                     Ok(None)
                 }
@@ -510,6 +547,19 @@ where
 
                 Ok(None)
             }
+            AppendEntriesResponse::Malformed(reason) => {
+                tracing::error!(
+                    req = display(&sending_range),
+                    reason = display(&reason),
+                    "target rejected AppendEntries as malformed"
+                );
+
+                let unreachable = Unreachable::new(&AnyError::error(format_args!(
+                    "target rejected AppendEntries {} as malformed: {}",
+                    sending_range, reason
+                )));
+                Err(ReplicationError::RPCError(RPCError::Unreachable(unreachable)))
+            }
         }
     }
 
@@ -539,6 +589,17 @@ where
         });
     }
 
+    /// Notify [`RaftCore`] with the latency of an RPC that receives a response.
+    ///
+    /// [`RaftCore`]: crate::core::RaftCore
+    fn notify_rpc_latency(&mut self, rpc_type: RPCTypes, sending_time: InstantOf<C>) {
+        let _ = self.tx_raft_core.send(Notification::RPCLatency {
+            target: self.target.clone(),
+            rpc_type,
+            latency: sending_time.elapsed(),
+        });
+    }
+
     /// Notify RaftCore with the success replication result(log matching or conflict).
     fn notify_progress(&mut self, replication_result: ReplicationResult<C>) {
         tracing::debug!(
@@ -724,8 +785,17 @@ where
             Some(x) => x,
         };
 
+        if self.rejected_snapshot_id.as_ref() == Some(&snapshot.meta.snapshot_id) {
+            let unreachable = Unreachable::new(&AnyError::error(format_args!(
+                "target refused the version of snapshot {}, wait for a new snapshot",
+                snapshot.meta.snapshot_id
+            )));
+            return Err(ReplicationError::RPCError(RPCError::Unreachable(unreachable)));
+        }
+
         let mut option = RPCOption::new(self.config.install_snapshot_timeout());
         option.snapshot_chunk_size = Some(self.config.snapshot_max_chunk_size as usize);
+        option.io_counters = Some(self.io_counters.clone());
 
         let (tx_cancel, rx_cancel) = C::oneshot();
 
@@ -736,6 +806,7 @@ where
             option,
             rx_cancel,
             self.weak_tx_event.clone(),
+            self.io_counters.clone(),
         ));
 
         // When self.rx_event is dropped:
@@ -753,6 +824,7 @@ where
         option: RPCOption,
         cancel: OneshotReceiverOf<C, ()>,
         weak_tx: MpscUnboundedWeakSenderOf<C, Replicate<C>>,
+        io_counters: Arc<IoCounters>,
     ) {
         let meta = snapshot.meta.clone();
 
@@ -766,6 +838,7 @@ where
         };
 
         let res = net.full_snapshot(vote, snapshot, cancel, option).await;
+        io_counters.record_snapshot_sent(&res);
         if let Err(e) = &res {
             tracing::warn!(error = display(e), "failed to send snapshot");
         }
@@ -799,8 +872,14 @@ where
             snapshot_meta,
         } = callback;
 
+        if let Err(StreamingError::SnapshotVersionUnsupported(_)) = &result {
+            self.rejected_snapshot_id = Some(snapshot_meta.snapshot_id.clone());
+        }
+
         let resp = result?;
 
+        self.notify_rpc_latency(RPCTypes::InstallSnapshot, start_time);
+
         // Handle response conditions.
         let sender_vote = self.session_id.vote();
         if resp.vote.as_ref_vote() > sender_vote.as_ref_vote() {
@@ -810,6 +889,20 @@ where
             }));
         }
 
+        if let Some(reason) = resp.malformed {
+            tracing::error!(
+                snapshot_meta = display(&snapshot_meta),
+                reason = display(&reason),
+                "target rejected snapshot as malformed"
+            );
+
+            let unreachable = Unreachable::new(&AnyError::error(format_args!(
+                "target rejected snapshot {} as malformed: {}",
+                snapshot_meta.snapshot_id, reason
+            )));
+            return Err(ReplicationError::RPCError(RPCError::Unreachable(unreachable)));
+        }
+
         self.notify_heartbeat_progress(start_time);
         self.notify_progress(ReplicationResult(Ok(snapshot_meta.last_log_id)));
 
@@ -817,9 +910,14 @@ where
     }
 
     /// If there are more logs to send, it returns a new `Some(Data::Logs)` to send.
-    fn next_action_to_send(&mut self, matching: Option<LogIdOf<C>>, log_ids: LogIdRange<C>) -> Option<Data<C>> {
+    fn next_action_to_send(
+        &mut self,
+        matching: Option<LogIdOf<C>>,
+        log_ids: LogIdRange<C>,
+        origin: WriteOrigin,
+    ) -> Option<Data<C>> {
         if matching < log_ids.last {
-            Some(Data::new_logs(LogIdRange::new(matching, log_ids.last)))
+            Some(Data::new_logs(LogIdRange::new(matching, log_ids.last), origin))
         } else {
             None
         }
@@ -1,4 +1,7 @@
 use std::fmt;
+use std::ops::Range;
+
+use tracing::Span;
 
 use crate::type_config::alias::LogIdOf;
 
@@ -19,7 +22,7 @@ impl<C> Replicate<C>
 where C: RaftTypeConfig
 {
     pub(crate) fn logs(log_id_range: LogIdRange<C>) -> Self {
-        Self::Data(Data::new_logs(log_id_range))
+        Self::Data(Data::new_logs(log_id_range, WriteOrigin::default()))
     }
 
     pub(crate) fn snapshot(last_log_id: Option<LogIdOf<C>>) -> Self {
@@ -43,6 +46,7 @@ impl<C: RaftTypeConfig> fmt::Display for Replicate<C> {
 use crate::display_ext::DisplayOptionExt;
 use crate::error::StreamingError;
 use crate::log_id_range::LogIdRange;
+use crate::raft::CorrelationId;
 use crate::raft::SnapshotResponse;
 use crate::replication::callbacks::SnapshotCallback;
 use crate::storage::SnapshotMeta;
@@ -59,7 +63,7 @@ pub(crate) enum Data<C>
 where C: RaftTypeConfig
 {
     Committed,
-    Logs(LogIdRange<C>),
+    Logs(LogIdRange<C>, WriteOrigin),
     Snapshot(Option<LogIdOf<C>>),
     SnapshotCallback(SnapshotCallback<C>),
 }
@@ -72,7 +76,7 @@ where C: RaftTypeConfig
             Data::Committed => {
                 write!(f, "Data::Committed")
             }
-            Self::Logs(l) => f.debug_struct("Data::Logs").field("log_id_range", l).finish(),
+            Self::Logs(l, _) => f.debug_struct("Data::Logs").field("log_id_range", l).finish(),
             Self::Snapshot(s) => f.debug_struct("Data::Snapshot").field("last_log_id", s).finish(),
             Self::SnapshotCallback(resp) => f.debug_struct("Data::SnapshotCallback").field("callback", resp).finish(),
         }
@@ -85,7 +89,7 @@ impl<C: RaftTypeConfig> fmt::Display for Data<C> {
             Data::Committed => {
                 write!(f, "Committed")
             }
-            Self::Logs(l) => {
+            Self::Logs(l, _) => {
                 write!(f, "Logs{{log_id_range: {}}}", l)
             }
             Self::Snapshot(s) => {
@@ -105,8 +109,8 @@ where C: RaftTypeConfig
         Self::Committed
     }
 
-    pub(crate) fn new_logs(log_id_range: LogIdRange<C>) -> Self {
-        Self::Logs(log_id_range)
+    pub(crate) fn new_logs(log_id_range: LogIdRange<C>, origin: WriteOrigin) -> Self {
+        Self::Logs(log_id_range, origin)
     }
 
     pub(crate) fn new_snapshot(last_log_id: Option<LogIdOf<C>>) -> Self {
@@ -125,9 +129,49 @@ where C: RaftTypeConfig
     pub(crate) fn has_payload(&self) -> bool {
         match self {
             Self::Committed => false,
-            Self::Logs(_) => true,
+            Self::Logs(_, _) => true,
             Self::Snapshot(_) => true,
             Self::SnapshotCallback(_) => true,
         }
     }
 }
+
+/// The client writes that the logs to replicate originate from.
+///
+/// The RPCs sending the logs are sent in the span of the last write, see [`RPCOption::span()`],
+/// and carry the correlation ids of the writes in them, see [`RPCOption::correlation_ids()`]. It
+/// is not part of a replication request and is ignored when comparing requests.
+///
+/// [`RPCOption::span()`]: crate::network::RPCOption::span
+/// [`RPCOption::correlation_ids()`]: crate::network::RPCOption::correlation_ids
+#[derive(Debug, Clone, Default)]
+pub(crate) struct WriteOrigin {
+    span: Option<Span>,
+
+    /// The `(log_index, correlation_id)` of the client writes in the logs to replicate.
+    correlation_ids: Vec<(u64, CorrelationId)>,
+}
+
+impl WriteOrigin {
+    pub(crate) fn new(span: Option<Span>, correlation_ids: Vec<(u64, CorrelationId)>) -> Self {
+        Self { span, correlation_ids }
+    }
+
+    /// Returns the span, or `Span::none()` if the logs do not originate from a traced write.
+    pub(crate) fn span(&self) -> Span {
+        self.span.clone().unwrap_or_else(Span::none)
+    }
+
+    /// Returns the `(log_index, correlation_id)` of the client writes in the log index range.
+    pub(crate) fn correlation_ids(&self, range: Range<u64>) -> Vec<(u64, CorrelationId)> {
+        self.correlation_ids.iter().filter(|(index, _)| range.contains(index)).copied().collect()
+    }
+}
+
+impl PartialEq for WriteOrigin {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for WriteOrigin {}
@@ -0,0 +1,29 @@
+/// The outcome of feeding a client write to a [`SessionTable`].
+///
+/// [`SessionTable`]: crate::session::SessionTable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Dedup<R> {
+    /// The write is new and is applied, with the response returned by applying it.
+    Applied(R),
+
+    /// The write is the last applied write of the client, which is not applied again.
+    /// It contains the cached response of the first apply.
+    Duplicate(R),
+
+    /// The write is older than the last applied write of the client. It is not applied, and its
+    /// response is no longer cached.
+    Stale {
+        /// The serial of the last applied write of the client.
+        last_serial: u64,
+    },
+}
+
+impl<R> Dedup<R> {
+    /// Returns the response if the write is applied now or before, or `None` if it is stale.
+    pub fn into_response(self) -> Option<R> {
+        match self {
+            Dedup::Applied(r) | Dedup::Duplicate(r) => Some(r),
+            Dedup::Stale { .. } => None,
+        }
+    }
+}
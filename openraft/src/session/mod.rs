@@ -0,0 +1,37 @@
+//! Client sessions for exactly-once apply of client writes.
+//!
+//! A client may retry a write when it does not receive the response, e.g., because the leader
+//! crashed after committing the write. The retried write is a new log entry, and without
+//! deduplication it would be applied twice.
+//!
+//! With sessions, a client tags every write with its id and a serial number that increases by
+//! each new request, by using a [`SessionWrite`] as the application data. The state machine feeds
+//! every write to a [`SessionTable`], which applies a write only if its serial is greater than the
+//! last applied one of the client, and otherwise returns the cached response.
+//!
+//! The [`SessionTable`] is part of the state machine: it must be included in snapshots, so that a
+//! node that restores a snapshot also deduplicates the writes applied before the snapshot.
+//!
+//! Sessions are opt-in, openraft itself does not inspect the application data.
+//!
+//! ```ignore
+//! fn apply_write(&mut self, w: SessionWrite<String, Data>) -> Response {
+//!     let state = &mut self.state;
+//!     match self.sessions.apply(w.client_id, w.serial, || state.apply(w.data)) {
+//!         Dedup::Applied(resp) | Dedup::Duplicate(resp) => resp,
+//!         Dedup::Stale { .. } => Response::stale(),
+//!     }
+//! }
+//! ```
+
+mod dedup;
+mod session_table;
+mod session_write;
+
+#[cfg(test)]
+mod session_table_test;
+
+pub use dedup::Dedup;
+pub use session_table::ClientSession;
+pub use session_table::SessionTable;
+pub use session_write::SessionWrite;
@@ -0,0 +1,105 @@
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+
+use crate::session::Dedup;
+
+/// The last applied write of a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ClientSession<R> {
+    /// The serial of the last applied write.
+    pub last_serial: u64,
+
+    /// The response of applying the last write, returned to a retry of it.
+    pub response: R,
+}
+
+/// Tracks the last applied write of every client to deduplicate retried writes.
+///
+/// It is part of the state machine and must be saved in snapshots.
+/// See: [session module](crate::session).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "K: serde::Serialize, R: serde::Serialize"))
+)]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "K: Ord + serde::Deserialize<'de>, R: serde::Deserialize<'de>"))
+)]
+pub struct SessionTable<K, R> {
+    sessions: BTreeMap<K, ClientSession<R>>,
+}
+
+impl<K, R> Default for SessionTable<K, R> {
+    fn default() -> Self {
+        Self {
+            sessions: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K, R> SessionTable<K, R>
+where
+    K: Ord,
+    R: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply the write `serial` of client `client_id` with `f`, unless it is already applied.
+    ///
+    /// `f` is called only if `serial` is greater than the last applied serial of the client, and
+    /// its response is cached for retries of this write.
+    pub fn apply<F>(&mut self, client_id: K, serial: u64, f: F) -> Dedup<R>
+    where F: FnOnce() -> R {
+        if let Some(session) = self.sessions.get(&client_id) {
+            if serial == session.last_serial {
+                return Dedup::Duplicate(session.response.clone());
+            }
+            if serial < session.last_serial {
+                return Dedup::Stale {
+                    last_serial: session.last_serial,
+                };
+            }
+        }
+
+        let response = f();
+        self.sessions.insert(client_id, ClientSession {
+            last_serial: serial,
+            response: response.clone(),
+        });
+        Dedup::Applied(response)
+    }
+
+    /// Get the last applied write of a client.
+    pub fn get<Q>(&self, client_id: &Q) -> Option<&ClientSession<R>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.sessions.get(client_id)
+    }
+
+    /// Remove a client session, e.g., when the client is known to be gone.
+    ///
+    /// Writes of a removed client are applied again, as if they were new.
+    pub fn remove<Q>(&mut self, client_id: &Q) -> Option<ClientSession<R>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.sessions.remove(client_id)
+    }
+
+    /// Returns the number of client sessions.
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+}
@@ -0,0 +1,56 @@
+use crate::session::Dedup;
+use crate::session::SessionTable;
+
+#[test]
+fn test_session_table_apply() -> anyhow::Result<()> {
+    let mut t = SessionTable::<String, u64>::new();
+    let mut applied = 0;
+
+    let mut apply = |t: &mut SessionTable<String, u64>, client: &str, serial: u64| {
+        t.apply(client.to_string(), serial, || {
+            applied += 1;
+            serial * 10
+        })
+    };
+
+    assert_eq!(Dedup::Applied(10), apply(&mut t, "a", 1));
+    assert_eq!(Dedup::Duplicate(10), apply(&mut t, "a", 1));
+    assert_eq!(Dedup::Applied(30), apply(&mut t, "a", 3));
+    assert_eq!(Dedup::Stale { last_serial: 3 }, apply(&mut t, "a", 2));
+    assert_eq!(Dedup::Duplicate(30), apply(&mut t, "a", 3));
+
+    // Sessions of different clients are independent
+    assert_eq!(Dedup::Applied(10), apply(&mut t, "b", 1));
+
+    assert_eq!(3, applied);
+    assert_eq!(2, t.len());
+    assert_eq!(3, t.get("a").unwrap().last_serial);
+
+    Ok(())
+}
+
+#[test]
+fn test_session_table_remove() -> anyhow::Result<()> {
+    let mut t = SessionTable::<String, u64>::new();
+
+    assert_eq!(Dedup::Applied(1), t.apply("a".to_string(), 1, || 1));
+    assert!(t.remove("a").is_some());
+    assert!(t.is_empty());
+
+    assert_eq!(Dedup::Applied(2), t.apply("a".to_string(), 1, || 2));
+
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_session_table_serde() -> anyhow::Result<()> {
+    let mut t = SessionTable::<String, u64>::new();
+    t.apply("a".to_string(), 1, || 1);
+
+    let s = serde_json::to_string(&t)?;
+    let t2: SessionTable<String, u64> = serde_json::from_str(&s)?;
+    assert_eq!(t, t2);
+
+    Ok(())
+}
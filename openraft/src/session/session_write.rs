@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// A client write tagged with the client session it belongs to.
+///
+/// It is used as the application data, i.e., [`RaftTypeConfig::D`], to let the state machine
+/// deduplicate retried writes with a [`SessionTable`].
+///
+/// [`RaftTypeConfig::D`]: crate::RaftTypeConfig::D
+/// [`SessionTable`]: crate::session::SessionTable
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SessionWrite<K, D> {
+    /// The id of the client that sends this write.
+    pub client_id: K,
+
+    /// The serial number of this write in the client session.
+    ///
+    /// A client must use a greater serial for every new write, and the same serial for a retry.
+    pub serial: u64,
+
+    /// The application data to apply.
+    pub data: D,
+}
+
+impl<K, D> SessionWrite<K, D> {
+    pub fn new(client_id: K, serial: u64, data: D) -> Self {
+        Self {
+            client_id,
+            serial,
+            data,
+        }
+    }
+}
+
+impl<K, D> fmt::Display for SessionWrite<K, D>
+where
+    K: fmt::Display,
+    D: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}: {}", self.client_id, self.serial, self.data)
+    }
+}
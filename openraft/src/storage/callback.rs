@@ -86,10 +86,13 @@ where C: RaftTypeConfig
                 StorageError::from_io_error(subject, verb, e)
             }
             Notification::HigherVote { .. }
+            | Notification::LogPurged { .. }
             | Notification::StorageError { .. }
             | Notification::ReplicationProgress { .. }
             | Notification::HeartbeatProgress { .. }
+            | Notification::RPCLatency { .. }
             | Notification::StateMachine { .. }
+            | Notification::ApplyDelayExpired
             | Notification::Tick { .. } => {
                 unreachable!("Unexpected notification: {}", self.notification)
             }
@@ -97,6 +100,78 @@ where C: RaftTypeConfig
     }
 }
 
+/// A callback for reporting the progress of purging logs from [`RaftLogStorage`].
+///
+/// See [`RaftLogStorage::purge_with_progress()`].
+///
+/// [`RaftLogStorage`]: `crate::storage::RaftLogStorage`
+/// [`RaftLogStorage::purge_with_progress()`]: `crate::storage::RaftLogStorage::purge_with_progress`
+pub struct PurgeProgress<C>
+where C: RaftTypeConfig
+{
+    /// The log id to purge upto, inclusive.
+    upto: LogIdOf<C>,
+
+    tx: MpscUnboundedWeakSenderOf<C, Notification<C>>,
+}
+
+impl<C> PurgeProgress<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(upto: LogIdOf<C>, tx: MpscUnboundedWeakSenderOf<C, Notification<C>>) -> Self {
+        Self { upto, tx }
+    }
+
+    /// The log id this purge removes logs upto, inclusive.
+    pub fn upto(&self) -> &LogIdOf<C> {
+        &self.upto
+    }
+
+    /// Report that logs upto `purged`, inclusive, are durably removed.
+    ///
+    /// It can be called several times with ascending log ids not greater than [`Self::upto()`].
+    pub fn purged(&self, purged: LogIdOf<C>) {
+        debug_assert!(
+            purged <= self.upto,
+            "purged {} must not exceed upto {}",
+            purged,
+            self.upto
+        );
+
+        tracing::debug!("{}: purged upto {}, requested upto {}", func_name!(), purged, self.upto);
+        self.send(Notification::LogPurged { purged });
+    }
+
+    /// Report completion of the purge.
+    ///
+    /// On success, all logs upto [`Self::upto()`] are considered durably removed.
+    pub fn completed(self, result: Result<(), io::Error>) {
+        let notification = match result {
+            Ok(()) => Notification::LogPurged {
+                purged: self.upto.clone(),
+            },
+            Err(e) => {
+                tracing::error!("{}: error: {}, while purging upto {}", func_name!(), e, self.upto);
+                let error = StorageError::from_io_error(ErrorSubject::Logs, ErrorVerb::Delete, e);
+                Notification::StorageError { error }
+            }
+        };
+
+        self.send(notification);
+    }
+
+    fn send(&self, notification: Notification<C>) {
+        let Some(tx) = self.tx.upgrade() else {
+            tracing::warn!("failed to upgrade tx, RaftCore may have closed the receiver");
+            return;
+        };
+
+        if let Err(e) = tx.send(notification) {
+            tracing::warn!("failed to send purge progress event: {}", e.0);
+        }
+    }
+}
+
 /// A oneshot callback for completion of applying logs to state machine.
 pub struct LogApplied<C>
 where C: RaftTypeConfig
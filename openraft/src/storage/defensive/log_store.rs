@@ -0,0 +1,256 @@
+use crate::entry::RaftEntry;
+use crate::storage::defensive::Shared;
+use crate::storage::defensive::Violation;
+use crate::storage::IOFlushed;
+use crate::storage::LogState;
+use crate::storage::LogStats;
+use crate::storage::PurgeProgress;
+use crate::storage::RaftLogStorage;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::vote::raft_vote::RaftVoteExt;
+use crate::AnyError;
+use crate::LogIdOptionExt;
+use crate::OptionalSend;
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+
+/// A [`RaftLogStorage`] wrapper that checks the invariants of every mutating call.
+///
+/// It is created by [`wrap()`](super::wrap) together with a [`DefensiveStateMachine`].
+///
+/// [`DefensiveStateMachine`]: super::DefensiveStateMachine
+pub struct DefensiveLogStore<C, LS>
+where
+    C: RaftTypeConfig,
+    LS: RaftLogStorage<C>,
+{
+    inner: LS,
+    observed: Shared<C>,
+}
+
+impl<C, LS> DefensiveLogStore<C, LS>
+where
+    C: RaftTypeConfig,
+    LS: RaftLogStorage<C>,
+{
+    pub(crate) fn new(inner: LS, observed: Shared<C>) -> Self {
+        Self { inner, observed }
+    }
+
+    /// Get a reference to the wrapped log store.
+    pub fn inner(&self) -> &LS {
+        &self.inner
+    }
+
+    /// Read the state to check against from the log store, if it has not been read yet.
+    async fn load(&mut self) -> Result<(), StorageError<C>> {
+        if self.observed.lock().log_loaded {
+            return Ok(());
+        }
+
+        let vote = self.inner.get_log_reader().await.read_vote().await?;
+        let committed = self.inner.read_committed().await?;
+        let log_state = self.inner.get_log_state().await?;
+
+        let mut o = self.observed.lock();
+        o.log_loaded = true;
+        o.vote = vote;
+        o.committed = committed;
+        o.last_purged = log_state.last_purged_log_id;
+        o.last_log_id = log_state.last_log_id;
+        Ok(())
+    }
+
+    fn check_purge(&self, log_id: &LogIdOf<C>) -> Result<(), StorageError<C>> {
+        let o = self.observed.lock();
+        let upto = Some(log_id);
+
+        if upto < o.last_purged.as_ref() {
+            let v = Violation::<C>::PurgeDecrease {
+                last_purged: o.last_purged.clone(),
+                upto: log_id.clone(),
+            };
+            return Err(StorageError::write_logs(AnyError::new(&v)));
+        }
+
+        if let Some(snapshot) = &o.snapshot {
+            if upto > snapshot.as_ref() {
+                let v = Violation::<C>::PurgeBeyondSnapshot {
+                    snapshot: snapshot.clone(),
+                    upto: log_id.clone(),
+                };
+                return Err(StorageError::write_logs(AnyError::new(&v)));
+            }
+        }
+
+        if o.sm_loaded && upto > o.applied.as_ref() {
+            let v = Violation::<C>::PurgeBeyondApplied {
+                applied: o.applied.clone(),
+                upto: log_id.clone(),
+            };
+            return Err(StorageError::write_logs(AnyError::new(&v)));
+        }
+
+        Ok(())
+    }
+
+    fn update_purged(&self, log_id: LogIdOf<C>) {
+        let mut o = self.observed.lock();
+        if o.last_log_id.as_ref() < Some(&log_id) {
+            o.last_log_id = Some(log_id.clone());
+        }
+        o.last_purged = Some(log_id);
+    }
+}
+
+impl<C, LS> RaftLogStorage<C> for DefensiveLogStore<C, LS>
+where
+    C: RaftTypeConfig,
+    LS: RaftLogStorage<C>,
+{
+    type LogReader = LS::LogReader;
+
+    async fn get_log_state(&mut self) -> Result<LogState<C>, StorageError<C>> {
+        self.load().await?;
+        self.inner.get_log_state().await
+    }
+
+    async fn get_log_reader(&mut self) -> Self::LogReader {
+        self.inner.get_log_reader().await
+    }
+
+    async fn save_vote(&mut self, vote: &VoteOf<C>) -> Result<(), StorageError<C>> {
+        self.load().await?;
+
+        {
+            let o = self.observed.lock();
+            if let Some(saved) = &o.vote {
+                // Votes are partially ordered: an incomparable vote is a violation too.
+                let ascending = vote.as_ref_vote().partial_cmp(&saved.as_ref_vote()).is_some_and(|o| o.is_ge());
+                if !ascending {
+                    let v = Violation::<C>::VoteDecrease {
+                        saved: saved.clone(),
+                        new: vote.clone(),
+                    };
+                    return Err(StorageError::write_vote(AnyError::new(&v)));
+                }
+            }
+        }
+
+        self.inner.save_vote(vote).await?;
+
+        self.observed.lock().vote = Some(vote.clone());
+        Ok(())
+    }
+
+    async fn save_committed(&mut self, committed: Option<LogIdOf<C>>) -> Result<(), StorageError<C>> {
+        self.load().await?;
+
+        {
+            let o = self.observed.lock();
+            if committed < o.committed {
+                let v = Violation::<C>::CommittedDecrease {
+                    saved: o.committed.clone(),
+                    new: committed,
+                };
+                return Err(StorageError::write_logs(AnyError::new(&v)));
+            }
+        }
+
+        self.inner.save_committed(committed.clone()).await?;
+
+        self.observed.lock().committed = committed;
+        Ok(())
+    }
+
+    async fn read_committed(&mut self) -> Result<Option<LogIdOf<C>>, StorageError<C>> {
+        self.inner.read_committed().await
+    }
+
+    async fn append<I>(&mut self, entries: I, callback: IOFlushed<C>) -> Result<(), StorageError<C>>
+    where
+        I: IntoIterator<Item = C::Entry> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        self.load().await?;
+
+        let entries = entries.into_iter().collect::<Vec<_>>();
+
+        let mut last = self.observed.lock().last_log_id.clone();
+        for ent in entries.iter() {
+            let next = ent.log_id();
+            if next.index() != last.next_index() || Some(&next) < last.as_ref() {
+                let v = Violation::<C>::LogNotConsecutive { last, next };
+                return Err(StorageError::write_log_entry(ent.log_id(), AnyError::new(&v)));
+            }
+            last = Some(next);
+        }
+
+        self.inner.append(entries, callback).await?;
+
+        self.observed.lock().last_log_id = last;
+        Ok(())
+    }
+
+    async fn truncate(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        self.load().await?;
+
+        {
+            let o = self.observed.lock();
+
+            if log_id.index() < o.last_purged.next_index() {
+                let v = Violation::<C>::TruncatePurged {
+                    last_purged: o.last_purged.clone(),
+                    since: log_id,
+                };
+                return Err(StorageError::write_logs(AnyError::new(&v)));
+            }
+
+            if log_id.index() < o.committed.next_index() {
+                let v = Violation::<C>::TruncateCommitted {
+                    committed: o.committed.clone(),
+                    since: log_id,
+                };
+                return Err(StorageError::write_logs(AnyError::new(&v)));
+            }
+        }
+
+        self.inner.truncate(log_id).await?;
+
+        // The log id before the truncated one is unknown, read it back.
+        let log_state = self.inner.get_log_state().await?;
+        self.observed.lock().last_log_id = log_state.last_log_id;
+        Ok(())
+    }
+
+    async fn purge(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        self.load().await?;
+        self.check_purge(&log_id)?;
+
+        self.inner.purge(log_id.clone()).await?;
+
+        self.update_purged(log_id);
+        Ok(())
+    }
+
+    async fn purge_with_progress(
+        &mut self,
+        log_id: LogIdOf<C>,
+        callback: PurgeProgress<C>,
+    ) -> Result<(), StorageError<C>> {
+        self.load().await?;
+        self.check_purge(&log_id)?;
+
+        self.inner.purge_with_progress(log_id.clone(), callback).await?;
+
+        // A purge in progress already hides the logs from openraft, check the next call against it.
+        self.update_purged(log_id);
+        Ok(())
+    }
+
+    async fn stats(&mut self) -> Result<Option<LogStats>, StorageError<C>> {
+        self.inner.stats().await
+    }
+}
@@ -0,0 +1,133 @@
+//! Runtime invariant checks for storage implementations.
+//!
+//! [`DefensiveLogStore`] and [`DefensiveStateMachine`] wrap a [`RaftLogStorage`] and a
+//! [`RaftStateMachine`] and validate every mutating call before forwarding it:
+//!
+//! - The saved vote and committed log id never decrease.
+//! - Appended and applied log ids are consecutive and ascending.
+//! - Truncation does not remove purged or committed logs.
+//! - `last_purged <= snapshot <= applied`.
+//!
+//! A broken invariant is returned as a [`StorageError`] whose source is a [`Violation`], so that a
+//! storage bug surfaces at the call that causes it, instead of as data loss much later.
+//!
+//! The checks cost a few storage reads when the wrappers are created and a lock on every call.
+//! They are meant for tests and for debugging a storage implementation, not for production.
+//!
+//! ```ignore
+//! let (log_store, state_machine) = openraft::storage::defensive::wrap(log_store, state_machine);
+//! let raft = Raft::new(id, config, network, log_store, state_machine).await?;
+//! ```
+//!
+//! [`RaftLogStorage`]: crate::storage::RaftLogStorage
+//! [`RaftStateMachine`]: crate::storage::RaftStateMachine
+//! [`StorageError`]: crate::StorageError
+
+mod log_store;
+mod state_machine;
+mod violation;
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+pub use log_store::DefensiveLogStore;
+pub use state_machine::DefensiveSnapshotBuilder;
+pub use state_machine::DefensiveStateMachine;
+pub use violation::Violation;
+
+use crate::storage::RaftLogStorage;
+use crate::storage::RaftStateMachine;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::RaftTypeConfig;
+
+/// Wrap a log store and a state machine with runtime invariant checks.
+///
+/// Both are wrapped together because some invariants, such as `purged <= snapshot <= applied`,
+/// involve the state of both of them.
+pub fn wrap<C, LS, SM>(log_store: LS, state_machine: SM) -> (DefensiveLogStore<C, LS>, DefensiveStateMachine<C, SM>)
+where
+    C: RaftTypeConfig,
+    LS: RaftLogStorage<C>,
+    SM: RaftStateMachine<C>,
+{
+    let observed = Shared::default();
+    (
+        DefensiveLogStore::new(log_store, observed.clone()),
+        DefensiveStateMachine::new(state_machine, observed),
+    )
+}
+
+/// The storage state seen by the wrappers, shared by the log store and the state machine.
+pub(crate) struct Observed<C>
+where C: RaftTypeConfig
+{
+    /// Whether the log store fields below have been loaded from the log store.
+    pub(crate) log_loaded: bool,
+    pub(crate) vote: Option<VoteOf<C>>,
+    pub(crate) committed: Option<LogIdOf<C>>,
+    pub(crate) last_purged: Option<LogIdOf<C>>,
+    pub(crate) last_log_id: Option<LogIdOf<C>>,
+
+    /// Whether `applied` has been loaded from the state machine.
+    pub(crate) sm_loaded: bool,
+    pub(crate) applied: Option<LogIdOf<C>>,
+
+    /// The last log id of the latest snapshot; the outer `None` means it is not seen yet.
+    ///
+    /// The snapshot is never read only for checking, because it may be expensive.
+    pub(crate) snapshot: Option<Option<LogIdOf<C>>>,
+}
+
+impl<C> Default for Observed<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            log_loaded: false,
+            vote: None,
+            committed: None,
+            last_purged: None,
+            last_log_id: None,
+            sm_loaded: false,
+            applied: None,
+            snapshot: None,
+        }
+    }
+}
+
+pub(crate) struct Shared<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Mutex<Observed<C>>>,
+}
+
+impl<C> Clone for Shared<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<C> Default for Shared<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Observed::default())),
+        }
+    }
+}
+
+impl<C> Shared<C>
+where C: RaftTypeConfig
+{
+    /// Lock the observed state. The lock must not be held across an `await`.
+    pub(crate) fn lock(&self) -> MutexGuard<'_, Observed<C>> {
+        self.inner.lock().unwrap()
+    }
+}
@@ -0,0 +1,238 @@
+use crate::entry::RaftEntry;
+use crate::error::ApplyError;
+use crate::storage::defensive::Shared;
+use crate::storage::defensive::Violation;
+use crate::storage::RaftStateMachine;
+use crate::storage::Snapshot;
+use crate::storage::SnapshotMeta;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::AnyError;
+use crate::LogIdOptionExt;
+use crate::OptionalSend;
+use crate::RaftSnapshotBuilder;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::StoredMembership;
+
+/// A [`RaftStateMachine`] wrapper that checks the invariants of every mutating call.
+///
+/// It is created by [`wrap()`](super::wrap) together with a [`DefensiveLogStore`].
+///
+/// [`DefensiveLogStore`]: super::DefensiveLogStore
+pub struct DefensiveStateMachine<C, SM>
+where
+    C: RaftTypeConfig,
+    SM: RaftStateMachine<C>,
+{
+    inner: SM,
+    observed: Shared<C>,
+}
+
+impl<C, SM> DefensiveStateMachine<C, SM>
+where
+    C: RaftTypeConfig,
+    SM: RaftStateMachine<C>,
+{
+    pub(crate) fn new(inner: SM, observed: Shared<C>) -> Self {
+        Self { inner, observed }
+    }
+
+    /// Get a reference to the wrapped state machine.
+    pub fn inner(&self) -> &SM {
+        &self.inner
+    }
+
+    /// Read the last applied log id from the state machine, if it has not been read yet.
+    async fn load(&mut self) -> Result<(), StorageError<C>> {
+        if self.observed.lock().sm_loaded {
+            return Ok(());
+        }
+
+        let (applied, _) = self.inner.applied_state().await?;
+
+        let mut o = self.observed.lock();
+        o.sm_loaded = true;
+        o.applied = applied;
+        Ok(())
+    }
+
+    /// Check that `entries` to apply follow the last applied log id and return the last log id
+    /// that will be applied.
+    async fn check_apply(&mut self, entries: &[C::Entry]) -> Result<Option<LogIdOf<C>>, StorageError<C>> {
+        self.load().await?;
+
+        let mut last = self.observed.lock().applied.clone();
+        for ent in entries.iter() {
+            let next = ent.log_id();
+            if next.index() != last.next_index() || Some(&next) < last.as_ref() {
+                let v = Violation::<C>::ApplyNotConsecutive { applied: last, next };
+                return Err(StorageError::apply(ent.log_id(), AnyError::new(&v)));
+            }
+            last = Some(next);
+        }
+
+        Ok(last)
+    }
+}
+
+impl<C, SM> RaftStateMachine<C> for DefensiveStateMachine<C, SM>
+where
+    C: RaftTypeConfig,
+    SM: RaftStateMachine<C>,
+{
+    type SnapshotBuilder = DefensiveSnapshotBuilder<C, SM::SnapshotBuilder>;
+
+    async fn applied_state(&mut self) -> Result<(Option<LogIdOf<C>>, StoredMembership<C>), StorageError<C>> {
+        let (applied, membership) = self.inner.applied_state().await?;
+
+        let mut o = self.observed.lock();
+        o.sm_loaded = true;
+        o.applied = applied.clone();
+
+        Ok((applied, membership))
+    }
+
+    async fn apply<I>(&mut self, entries: I) -> Result<Vec<C::R>, StorageError<C>>
+    where
+        I: IntoIterator<Item = C::Entry> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let entries = entries.into_iter().collect::<Vec<_>>();
+        let last = self.check_apply(&entries).await?;
+
+        let res = self.inner.apply(entries).await?;
+
+        self.observed.lock().applied = last;
+        Ok(res)
+    }
+
+    async fn apply_fallible<I>(&mut self, entries: I) -> Result<Vec<Result<C::R, ApplyError<C>>>, StorageError<C>>
+    where
+        I: IntoIterator<Item = C::Entry> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let entries = entries.into_iter().collect::<Vec<_>>();
+        let last = self.check_apply(&entries).await?;
+
+        let res = self.inner.apply_fallible(entries).await?;
+
+        self.observed.lock().applied = last;
+        Ok(res)
+    }
+
+    async fn prepare_apply(&mut self, entries: &[C::Entry]) -> Result<(), StorageError<C>> {
+        self.inner.prepare_apply(entries).await
+    }
+
+    async fn commit_apply(&mut self, applied: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        self.inner.commit_apply(applied).await
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        DefensiveSnapshotBuilder {
+            inner: self.inner.get_snapshot_builder().await,
+            observed: self.observed.clone(),
+        }
+    }
+
+    async fn begin_receiving_snapshot(&mut self) -> Result<C::SnapshotData, StorageError<C>> {
+        self.inner.begin_receiving_snapshot().await
+    }
+
+    async fn install_snapshot(
+        &mut self,
+        meta: &SnapshotMeta<C>,
+        snapshot: C::SnapshotData,
+    ) -> Result<(), StorageError<C>> {
+        self.load().await?;
+
+        {
+            let o = self.observed.lock();
+            if meta.last_log_id < o.applied {
+                let v = Violation::<C>::InstallStaleSnapshot {
+                    applied: o.applied.clone(),
+                    snapshot: meta.last_log_id.clone(),
+                };
+                return Err(StorageError::write_snapshot(Some(meta.signature()), AnyError::new(&v)));
+            }
+        }
+
+        self.inner.install_snapshot(meta, snapshot).await?;
+
+        let mut o = self.observed.lock();
+        o.applied = meta.last_log_id.clone();
+        o.snapshot = Some(meta.last_log_id.clone());
+        Ok(())
+    }
+
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<C>>, StorageError<C>> {
+        let snapshot = self.inner.get_current_snapshot().await?;
+
+        let last_log_id = snapshot.as_ref().and_then(|s| s.meta.last_log_id.clone());
+        self.observed.lock().snapshot = Some(last_log_id);
+
+        Ok(snapshot)
+    }
+
+    async fn on_become_leader(&mut self, vote: &VoteOf<C>) {
+        self.inner.on_become_leader(vote).await
+    }
+
+    async fn on_step_down(&mut self) {
+        self.inner.on_step_down().await
+    }
+
+    async fn on_snapshot_installed(&mut self, meta: &SnapshotMeta<C>) {
+        self.inner.on_snapshot_installed(meta).await
+    }
+
+    async fn on_membership_applied(&mut self, membership: &StoredMembership<C>) {
+        self.inner.on_membership_applied(membership).await
+    }
+}
+
+/// A [`RaftSnapshotBuilder`] wrapper that checks a built snapshot does not include logs that are
+/// not applied.
+pub struct DefensiveSnapshotBuilder<C, B>
+where
+    C: RaftTypeConfig,
+    B: RaftSnapshotBuilder<C>,
+{
+    inner: B,
+    observed: Shared<C>,
+}
+
+impl<C, B> RaftSnapshotBuilder<C> for DefensiveSnapshotBuilder<C, B>
+where
+    C: RaftTypeConfig,
+    B: RaftSnapshotBuilder<C>,
+{
+    async fn build_snapshot(&mut self) -> Result<Snapshot<C>, StorageError<C>> {
+        let snapshot = self.inner.build_snapshot().await?;
+        let last_log_id = snapshot.meta.last_log_id.clone();
+
+        let mut o = self.observed.lock();
+
+        if o.sm_loaded && last_log_id > o.applied {
+            let v = Violation::<C>::SnapshotBeyondApplied {
+                applied: o.applied.clone(),
+                snapshot: last_log_id,
+            };
+            return Err(StorageError::write_snapshot(
+                Some(snapshot.meta.signature()),
+                AnyError::new(&v),
+            ));
+        }
+
+        let newer = match &o.snapshot {
+            None => true,
+            Some(prev) => &last_log_id > prev,
+        };
+        if newer {
+            o.snapshot = Some(last_log_id);
+        }
+
+        Ok(snapshot)
+    }
+}
@@ -0,0 +1,73 @@
+use crate::display_ext::DisplayOptionExt;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::RaftTypeConfig;
+
+/// An invariant of the storage API that is broken by a call.
+///
+/// It is returned as the source of a [`StorageError`](crate::StorageError) by the defensive
+/// wrappers.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Violation<C>
+where C: RaftTypeConfig
+{
+    #[error("vote must not decrease: saved: {saved}, new: {new}")]
+    VoteDecrease { saved: VoteOf<C>, new: VoteOf<C> },
+
+    #[error("committed log id must not decrease: saved: {}, new: {}", saved.display(), new.display())]
+    CommittedDecrease {
+        saved: Option<LogIdOf<C>>,
+        new: Option<LogIdOf<C>>,
+    },
+
+    #[error("log ids must be consecutive and ascending: last: {}, next: {next}", last.display())]
+    LogNotConsecutive { last: Option<LogIdOf<C>>, next: LogIdOf<C> },
+
+    #[error("can not truncate purged logs: last purged: {}, truncate since: {since}", last_purged.display())]
+    TruncatePurged {
+        last_purged: Option<LogIdOf<C>>,
+        since: LogIdOf<C>,
+    },
+
+    #[error("can not truncate committed logs: committed: {}, truncate since: {since}", committed.display())]
+    TruncateCommitted {
+        committed: Option<LogIdOf<C>>,
+        since: LogIdOf<C>,
+    },
+
+    #[error("last purged log id must not decrease: purged: {}, new: {upto}", last_purged.display())]
+    PurgeDecrease {
+        last_purged: Option<LogIdOf<C>>,
+        upto: LogIdOf<C>,
+    },
+
+    #[error("can not purge logs not in snapshot: snapshot: {}, purge upto: {upto}", snapshot.display())]
+    PurgeBeyondSnapshot {
+        snapshot: Option<LogIdOf<C>>,
+        upto: LogIdOf<C>,
+    },
+
+    #[error("can not purge logs not applied: applied: {}, purge upto: {upto}", applied.display())]
+    PurgeBeyondApplied {
+        applied: Option<LogIdOf<C>>,
+        upto: LogIdOf<C>,
+    },
+
+    #[error("applied log ids must be consecutive and ascending: applied: {}, next: {next}", applied.display())]
+    ApplyNotConsecutive {
+        applied: Option<LogIdOf<C>>,
+        next: LogIdOf<C>,
+    },
+
+    #[error("snapshot must not include logs not applied: applied: {}, snapshot: {}", applied.display(), snapshot.display())]
+    SnapshotBeyondApplied {
+        applied: Option<LogIdOf<C>>,
+        snapshot: Option<LogIdOf<C>>,
+    },
+
+    #[error("can not install a snapshot older than the state machine: applied: {}, snapshot: {}", applied.display(), snapshot.display())]
+    InstallStaleSnapshot {
+        applied: Option<LogIdOf<C>>,
+        snapshot: Option<LogIdOf<C>>,
+    },
+}
@@ -0,0 +1,178 @@
+//! Archive log entries before they are purged.
+
+use openraft_macros::add_async_trait;
+
+use crate::storage::IOFlushed;
+use crate::storage::LogState;
+use crate::storage::LogStats;
+use crate::storage::PurgeProgress;
+use crate::storage::RaftLogStorage;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::LogIdOptionExt;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+
+/// Max number of entries passed to one [`LogArchiver::archive`] call.
+const ARCHIVE_CHUNK_SIZE: u64 = 1024;
+
+/// A hook that receives log entries right before they are purged, e.g., to copy them to an object
+/// storage for audit or change data capture.
+///
+/// Install it by wrapping a log store with [`ArchivingLogStore`].
+#[add_async_trait]
+pub trait LogArchiver<C>: OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+    /// Archive a chunk of consecutive log entries that are about to be purged.
+    ///
+    /// Chunks are passed in log index order. The entries are purged only after every chunk of a
+    /// purge is archived. An error stops the purge and is returned to openraft as a storage error,
+    /// so that no entry is ever purged without being archived.
+    async fn archive(&mut self, entries: Vec<C::Entry>) -> Result<(), StorageError<C>>;
+}
+
+/// A [`RaftLogStorage`] wrapper that passes log entries to a [`LogArchiver`] before purging them.
+///
+/// All other calls are forwarded to the wrapped log store unchanged.
+pub struct ArchivingLogStore<C, LS, A>
+where
+    C: RaftTypeConfig,
+    LS: RaftLogStorage<C>,
+    A: LogArchiver<C>,
+{
+    inner: LS,
+    archiver: A,
+
+    /// The last archived log id.
+    ///
+    /// A purge may still be running in the background after it is archived. This prevents the
+    /// next purge from archiving the same entries again.
+    last_archived: Option<LogIdOf<C>>,
+
+    _p: std::marker::PhantomData<C>,
+}
+
+impl<C, LS, A> ArchivingLogStore<C, LS, A>
+where
+    C: RaftTypeConfig,
+    LS: RaftLogStorage<C>,
+    A: LogArchiver<C>,
+{
+    /// Wrap `inner` so that `archiver` receives every log entry before it is purged.
+    pub fn new(inner: LS, archiver: A) -> Self {
+        Self {
+            inner,
+            archiver,
+            last_archived: None,
+            _p: Default::default(),
+        }
+    }
+
+    /// Get a reference to the wrapped log store.
+    pub fn inner(&self) -> &LS {
+        &self.inner
+    }
+
+    /// Get a reference to the archiver.
+    pub fn archiver(&self) -> &A {
+        &self.archiver
+    }
+
+    /// Archive the not yet purged entries upto `log_id`, inclusive.
+    async fn archive_upto(&mut self, log_id: &LogIdOf<C>) -> Result<(), StorageError<C>> {
+        let log_state = self.inner.get_log_state().await?;
+
+        let mut start = std::cmp::max(
+            log_state.last_purged_log_id.next_index(),
+            self.last_archived.next_index(),
+        );
+        let end = log_id.index() + 1;
+
+        if start >= end {
+            return Ok(());
+        }
+
+        tracing::debug!("archive logs before purge: [{}, {})", start, end);
+
+        let mut reader = self.inner.get_log_reader().await;
+
+        while start < end {
+            let chunk_end = std::cmp::min(start + ARCHIVE_CHUNK_SIZE, end);
+
+            let entries = reader.try_get_log_entries(start..chunk_end).await?;
+            if entries.is_empty() {
+                // Entries are already purged by a previous purge that did not complete.
+                return Ok(());
+            }
+
+            start += entries.len() as u64;
+            self.archiver.archive(entries).await?;
+        }
+
+        self.last_archived = Some(log_id.clone());
+        Ok(())
+    }
+}
+
+impl<C, LS, A> RaftLogStorage<C> for ArchivingLogStore<C, LS, A>
+where
+    C: RaftTypeConfig,
+    LS: RaftLogStorage<C>,
+    A: LogArchiver<C>,
+{
+    type LogReader = LS::LogReader;
+
+    async fn get_log_state(&mut self) -> Result<LogState<C>, StorageError<C>> {
+        self.inner.get_log_state().await
+    }
+
+    async fn get_log_reader(&mut self) -> Self::LogReader {
+        self.inner.get_log_reader().await
+    }
+
+    async fn save_vote(&mut self, vote: &VoteOf<C>) -> Result<(), StorageError<C>> {
+        self.inner.save_vote(vote).await
+    }
+
+    async fn save_committed(&mut self, committed: Option<LogIdOf<C>>) -> Result<(), StorageError<C>> {
+        self.inner.save_committed(committed).await
+    }
+
+    async fn read_committed(&mut self) -> Result<Option<LogIdOf<C>>, StorageError<C>> {
+        self.inner.read_committed().await
+    }
+
+    async fn append<I>(&mut self, entries: I, callback: IOFlushed<C>) -> Result<(), StorageError<C>>
+    where
+        I: IntoIterator<Item = C::Entry> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        self.inner.append(entries, callback).await
+    }
+
+    async fn truncate(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        self.inner.truncate(log_id).await
+    }
+
+    async fn purge(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        self.archive_upto(&log_id).await?;
+        self.inner.purge(log_id).await
+    }
+
+    async fn purge_with_progress(
+        &mut self,
+        log_id: LogIdOf<C>,
+        callback: PurgeProgress<C>,
+    ) -> Result<(), StorageError<C>> {
+        self.archive_upto(&log_id).await?;
+        self.inner.purge_with_progress(log_id, callback).await
+    }
+
+    async fn stats(&mut self) -> Result<Option<LogStats>, StorageError<C>> {
+        self.inner.stats().await
+    }
+}
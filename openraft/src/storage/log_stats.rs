@@ -0,0 +1,37 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOption;
+
+/// Statistics about the data held by a log store, returned by [`RaftLogStorage::stats()`].
+///
+/// It is polled by openraft every [`Config::log_stats_interval`] milliseconds and reported in
+/// [`RaftDataMetrics::log_stats`]. A log that keeps growing usually means purging is stalled,
+/// e.g., because no snapshot is built.
+///
+/// [`RaftLogStorage::stats()`]: crate::storage::RaftLogStorage::stats
+/// [`Config::log_stats_interval`]: crate::Config::log_stats_interval
+/// [`RaftDataMetrics::log_stats`]: crate::metrics::RaftDataMetrics::log_stats
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LogStats {
+    /// Number of log entries that are not purged.
+    pub entries: u64,
+
+    /// Total size in bytes of the log entries, as stored by the log store.
+    pub bytes: u64,
+
+    /// Number of segment files the log is stored in, or `None` if the store is not file based.
+    pub segments: Option<u64>,
+}
+
+impl fmt::Display for LogStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LogStats{{entries:{}, bytes:{}, segments:{}}}",
+            self.entries,
+            self.bytes,
+            DisplayOption(&self.segments)
+        )
+    }
+}
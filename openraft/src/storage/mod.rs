@@ -1,9 +1,13 @@
 //! The Raft storage interface and data types.
 
 mod callback;
+#[cfg(feature = "defensive")]
+pub mod defensive;
 mod helper;
+mod log_archiver;
 mod log_reader_ext;
 mod log_state;
+mod log_stats;
 mod snapshot;
 mod snapshot_meta;
 mod snapshot_signature;
@@ -13,9 +17,13 @@ pub use self::callback::IOFlushed;
 pub use self::callback::LogApplied;
 #[allow(deprecated)]
 pub use self::callback::LogFlushed;
+pub use self::callback::PurgeProgress;
 pub use self::helper::StorageHelper;
+pub use self::log_archiver::ArchivingLogStore;
+pub use self::log_archiver::LogArchiver;
 pub use self::log_reader_ext::RaftLogReaderExt;
 pub use self::log_state::LogState;
+pub use self::log_stats::LogStats;
 pub use self::snapshot::Snapshot;
 pub use self::snapshot_meta::SnapshotMeta;
 pub use self::snapshot_signature::SnapshotSignature;
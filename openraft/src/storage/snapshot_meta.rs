@@ -11,7 +11,8 @@ use crate::StoredMembership;
 ///
 /// Including the last log id that included in this snapshot,
 /// the last membership included,
-/// and a snapshot id.
+/// a snapshot id,
+/// and the version of the state machine data format.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 pub struct SnapshotMeta<C>
@@ -27,6 +28,19 @@ where C: RaftTypeConfig
     /// Caveat: even when two snapshot is built with the same `last_log_id`, they still could be
     /// different in bytes.
     pub snapshot_id: SnapshotId,
+
+    /// The application defined version of the state machine data format in this snapshot.
+    ///
+    /// Openraft does not interpret it, except that a follower refuses a snapshot with a version
+    /// greater than [`Config::max_snapshot_version`]. Bump it when the format changes, so that a
+    /// new format snapshot is not installed on a node that can not read it during a rolling
+    /// upgrade. A snapshot written without a version has version `0`.
+    ///
+    /// Set it with [`SnapshotMeta::with_version()`] and read it with [`SnapshotMeta::version()`].
+    ///
+    /// [`Config::max_snapshot_version`]: crate::Config::max_snapshot_version
+    #[cfg_attr(feature = "serde", serde(default))]
+    version: u64,
 }
 
 impl<C> fmt::Display for SnapshotMeta<C>
@@ -35,10 +49,11 @@ where C: RaftTypeConfig
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{snapshot_id: {}, last_log:{}, last_membership: {}}}",
+            "{{snapshot_id: {}, last_log:{}, last_membership: {}, version: {}}}",
             self.snapshot_id,
             DisplayOption(&self.last_log_id),
-            self.last_membership
+            self.last_membership,
+            self.version()
         )
     }
 }
@@ -46,6 +61,27 @@ where C: RaftTypeConfig
 impl<C> SnapshotMeta<C>
 where C: RaftTypeConfig
 {
+    /// Create the metadata of a snapshot with data format version `0`.
+    pub fn new(last_log_id: Option<LogIdOf<C>>, last_membership: StoredMembership<C>, snapshot_id: SnapshotId) -> Self {
+        Self {
+            last_log_id,
+            last_membership,
+            snapshot_id,
+            version: 0,
+        }
+    }
+
+    /// Set the application defined version of the state machine data format in this snapshot.
+    pub fn with_version(mut self, version: u64) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Returns the application defined version of the state machine data format in this snapshot.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
     pub fn signature(&self) -> SnapshotSignature<C> {
         SnapshotSignature {
             last_log_id: self.last_log_id.clone(),
@@ -1,7 +1,10 @@
 use openraft_macros::add_async_trait;
+use openraft_macros::since;
 
 use crate::storage::IOFlushed;
 use crate::storage::LogState;
+use crate::storage::LogStats;
+use crate::storage::PurgeProgress;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
 use crate::OptionalSend;
@@ -114,4 +117,44 @@ where C: RaftTypeConfig
     ///
     /// - It must not leave a **hole** in logs.
     async fn purge(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>>;
+
+    /// Purge logs upto `log_id`, inclusive, and report the progress with `callback`.
+    ///
+    /// Openraft calls this method instead of [`purge()`](Self::purge) when executing a purge.
+    /// An implementation may return before the logs are removed and delete them in the background,
+    /// so that a large purge does not stall the following `append()` calls.
+    /// It then calls [`PurgeProgress::purged()`] each time a prefix of the logs is durably
+    /// removed, and [`PurgeProgress::completed()`] when it finishes or fails.
+    ///
+    /// Until a purge completes, logs upto `log_id` may still be read, and another purge with a
+    /// greater `log_id` may be requested; the implementation must keep purges in order.
+    ///
+    /// The default implementation calls [`purge()`](Self::purge) and reports completion when it
+    /// returns.
+    #[since(version = "0.10.0")]
+    async fn purge_with_progress(
+        &mut self,
+        log_id: LogIdOf<C>,
+        callback: PurgeProgress<C>,
+    ) -> Result<(), StorageError<C>> {
+        self.purge(log_id).await?;
+        callback.completed(Ok(()));
+        Ok(())
+    }
+
+    /// Return statistics about the stored logs, such as the number of entries and their size.
+    ///
+    /// Openraft polls it every [`Config::log_stats_interval`] milliseconds and reports the result
+    /// in [`RaftDataMetrics::log_stats`]. It is awaited in the `RaftCore` event loop, thus it must
+    /// be cheap: return counters maintained by `append()`, `truncate()` and `purge()`, instead of
+    /// scanning the logs or doing any IO.
+    ///
+    /// The default implementation returns `None`: no statistics are provided.
+    ///
+    /// [`Config::log_stats_interval`]: crate::Config::log_stats_interval
+    /// [`RaftDataMetrics::log_stats`]: crate::metrics::RaftDataMetrics::log_stats
+    #[since(version = "0.10.0")]
+    async fn stats(&mut self) -> Result<Option<LogStats>, StorageError<C>> {
+        Ok(None)
+    }
 }
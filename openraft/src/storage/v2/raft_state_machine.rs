@@ -1,9 +1,11 @@
 use openraft_macros::add_async_trait;
 use openraft_macros::since;
 
+use crate::error::ApplyError;
 use crate::storage::Snapshot;
 use crate::storage::SnapshotMeta;
 use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
 use crate::OptionalSend;
 use crate::OptionalSync;
 use crate::RaftSnapshotBuilder;
@@ -69,6 +71,74 @@ where C: RaftTypeConfig
         I: IntoIterator<Item = C::Entry> + OptionalSend,
         I::IntoIter: OptionalSend;
 
+    /// Apply the given entries to the state machine, allowing every entry to fail with an
+    /// application error.
+    ///
+    /// It is the same as [`apply()`](Self::apply), except that the response of an entry can be an
+    /// [`ApplyError`], which is returned to the [`Raft::client_write()`] caller as
+    /// [`ClientWriteError::ApplyError`], instead of a response. The failed entry is still regarded
+    /// as applied: the implementation should record its log id as the last applied log id, and
+    /// just skip its business logic. A [`StorageError`] still stops the Raft node.
+    ///
+    /// The default implementation calls `apply()` and never returns an [`ApplyError`]. An
+    /// implementation that overrides it receives all entries to apply by this method, and
+    /// `apply()` is no longer called by Openraft.
+    ///
+    /// [`Raft::client_write()`]: crate::Raft::client_write
+    /// [`ClientWriteError::ApplyError`]: crate::error::ClientWriteError::ApplyError
+    #[since(version = "0.10.0")]
+    async fn apply_fallible<I>(&mut self, entries: I) -> Result<Vec<Result<C::R, ApplyError<C>>>, StorageError<C>>
+    where
+        I: IntoIterator<Item = C::Entry> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let responses = self.apply(entries).await?;
+        Ok(responses.into_iter().map(Ok).collect())
+    }
+
+    /// Prepare external side effects of the given entries before they are applied.
+    ///
+    /// It is the first phase of a two-phase apply, for an application that coordinates an
+    /// external system, e.g., writes objects to an object storage, for every entry:
+    ///
+    /// 1. `prepare_apply()` is called with a batch of entries right before they are passed to
+    ///    [`apply_fallible()`](Self::apply_fallible). It prepares the side effects, e.g., uploads
+    ///    objects to a staging location. An error stops the Raft node, and the entries are not
+    ///    applied.
+    /// 2. [`apply_fallible()`](Self::apply_fallible), which calls [`apply()`](Self::apply) by
+    ///    default, updates the state machine and thus makes the entries applied.
+    /// 3. [`commit_apply()`](Self::commit_apply) is called to publish the prepared side effects,
+    ///    after the apply result is sent to `RaftCore`. Thus a client may receive the response of
+    ///    an entry before its side effects are published.
+    ///
+    /// If the node crashes before the entries are applied, they are prepared again after restart.
+    /// Thus this method must be idempotent. Entries included in an installed snapshot are neither
+    /// prepared nor committed.
+    ///
+    /// The default implementation does nothing.
+    #[since(version = "0.10.0")]
+    async fn prepare_apply(&mut self, entries: &[C::Entry]) -> Result<(), StorageError<C>> {
+        let _ = entries;
+        Ok(())
+    }
+
+    /// Publish the side effects prepared by [`prepare_apply()`](Self::prepare_apply) for all
+    /// applied entries upto `applied`, inclusive.
+    ///
+    /// It is called after every call of applying entries is done and its result is sent to
+    /// `RaftCore`, which responds to the clients without waiting for this method. It is also
+    /// called once when the Raft node starts, with the last applied log id returned by
+    /// [`applied_state()`](Self::applied_state), so that entries applied but not committed before
+    /// a crash are eventually committed. Thus this method must be idempotent. An error stops the
+    /// Raft node.
+    ///
+    /// The default implementation does nothing.
+    #[since(version = "0.10.0")]
+    async fn commit_apply(&mut self, applied: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        let _ = applied;
+        Ok(())
+    }
+
     /// Get the snapshot builder for the state machine.
     ///
     /// Usually it returns a snapshot view of the state machine(i.e., subsequent changes to the
@@ -122,4 +192,49 @@ where C: RaftTypeConfig
     /// last-applied-membership config as part of the snapshot, which should be decoded for
     /// creating this method's response data.
     async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<C>>, StorageError<C>>;
+
+    /// Called when this node becomes the leader with `vote`.
+    ///
+    /// Lifecycle hooks are called by the same task that applies entries: every entry submitted to
+    /// apply before the leadership change has been applied before this hook is called, and no
+    /// entry submitted after it is applied until it returns. The state machine may not yet have
+    /// applied all entries of previous leaders, wait for the blank log of this term to be applied
+    /// if it needs to.
+    ///
+    /// It is called again with a new vote if this node is re-elected in a higher term, after
+    /// [`on_step_down()`](Self::on_step_down). The default implementation does nothing.
+    #[since(version = "0.10.0")]
+    async fn on_become_leader(&mut self, vote: &VoteOf<C>) {
+        let _ = vote;
+    }
+
+    /// Called when this node is no longer the leader that
+    /// [`on_become_leader()`](Self::on_become_leader) was called for.
+    ///
+    /// It is in the same order with applying entries as `on_become_leader()`. Leader-only
+    /// background jobs and leases should be stopped here. The default implementation does
+    /// nothing.
+    #[since(version = "0.10.0")]
+    async fn on_step_down(&mut self) {}
+
+    /// Called after a snapshot received from the leader is installed by
+    /// [`install_snapshot()`](Self::install_snapshot).
+    ///
+    /// Caches built from the previous state machine data should be rebuilt here. It is not
+    /// called for a snapshot built by this node. The default implementation does nothing.
+    #[since(version = "0.10.0")]
+    async fn on_snapshot_installed(&mut self, meta: &SnapshotMeta<C>) {
+        let _ = meta;
+    }
+
+    /// Called after a membership config entry is applied.
+    ///
+    /// It is called once for every membership entry, right after the batch containing the entry is
+    /// applied, and before the next batch is applied. A membership included in an installed
+    /// snapshot is reported by [`on_snapshot_installed()`](Self::on_snapshot_installed) instead.
+    /// The default implementation does nothing.
+    #[since(version = "0.10.0")]
+    async fn on_membership_applied(&mut self, membership: &StoredMembership<C>) {
+        let _ = membership;
+    }
 }
@@ -0,0 +1,16 @@
+//! Entry points of the fuzz targets in `fuzz/`, enabled by feature `fuzzing`.
+//!
+//! They are for fuzzing only and are not part of the public API.
+
+use crate::engine::rpc_input;
+use crate::engine::rpc_input::ByteSource;
+
+/// Feed the AppendEntries, Vote and InstallSnapshot requests chosen by `data` to a follower
+/// `Engine`, and return an error message if it violates the log-matching invariants, or reverts
+/// its vote or committed log id.
+///
+/// Every byte of `data` makes one choice, of the cluster history the requests are built from, or
+/// of the requests. Any `data` is a valid input.
+pub fn engine_rpc_input(data: &[u8]) -> Result<(), String> {
+    rpc_input::run(&mut ByteSource::new(data), usize::MAX)
+}
@@ -134,6 +134,9 @@ where
         run_test(builder, Self::get_membership_from_log_le_sm_last_applied).await?;
         run_test(builder, Self::get_membership_from_log_gt_sm_last_applied_1).await?;
         run_test(builder, Self::get_membership_from_log_gt_sm_last_applied_2).await?;
+        run_test(builder, Self::get_membership_joint_config).await?;
+        run_test(builder, Self::get_membership_learner_only_change).await?;
+        run_test(builder, Self::get_initial_state_membership_interleaved_with_snapshot).await?;
         run_test(builder, Self::get_initial_state_without_init).await?;
         run_test(builder, Self::get_initial_state_membership_from_empty_log_and_sm).await?;
         run_test(builder, Self::get_initial_state_membership_from_sm_inlog_is_smaller).await?;
@@ -164,6 +167,7 @@ where
         run_test(builder, Self::apply_multiple).await?;
 
         Self::transfer_snapshot(builder).await?;
+        Self::transfer_snapshot_joint_membership(builder).await?;
 
         // TODO(xp): test: do_log_compaction
 
@@ -459,6 +463,152 @@ where
         Ok(())
     }
 
+    pub async fn get_membership_joint_config(mut store: LS, mut sm: SM) -> Result<(), StorageError<C>> {
+        let joint = || Membership::new_with_defaults(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], []);
+
+        tracing::info!("--- joint config applied, uniform config in log");
+        {
+            apply(&mut sm, [
+                blank_ent_0::<C>(1, 1),
+                joint_membership_ent_0::<C>(1, 2, vec![btreeset! {1,2,3}, btreeset! {3,4,5}], btreeset! {}),
+            ])
+            .await?;
+
+            append(&mut store, [
+                blank_ent_0::<C>(1, 1),
+                joint_membership_ent_0::<C>(1, 2, vec![btreeset! {1,2,3}, btreeset! {3,4,5}], btreeset! {}),
+                membership_ent_0::<C>(1, 3, btreeset! {3,4,5}),
+            ])
+            .await?;
+
+            let (_, mem) = sm.applied_state().await?;
+            assert_eq!(&Some(log_id_0(1, 2)), mem.log_id());
+            assert_eq!(&joint(), mem.membership(), "joint config is persisted in state machine");
+
+            let mem_state = StorageHelper::new(&mut store, &mut sm).get_membership().await?;
+            assert_eq!(&joint(), mem_state.committed().membership());
+            assert_eq!(
+                &Membership::new_with_defaults(vec![btreeset! {3,4,5}], []),
+                mem_state.effective().membership(),
+            );
+        }
+
+        tracing::info!("--- joint config in log is returned as is");
+        {
+            let mems = StorageHelper::new(&mut store, &mut sm).last_membership_in_log(0).await?;
+            assert_eq!(2, mems.len());
+            assert_eq!(&joint(), mems[0].membership());
+            assert_eq!(
+                &Membership::new_with_defaults(vec![btreeset! {3,4,5}], []),
+                mems[1].membership(),
+            );
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_membership_learner_only_change(mut store: LS, mut sm: SM) -> Result<(), StorageError<C>> {
+        Self::default_vote(&mut store).await?;
+
+        tracing::info!("--- memberships that differ only in learners");
+        {
+            apply(&mut sm, [
+                blank_ent_0::<C>(0, 0),
+                membership_ent_0::<C>(1, 1, btreeset! {1,2,3}),
+                joint_membership_ent_0::<C>(1, 2, vec![btreeset! {1,2,3}], btreeset! {4}),
+            ])
+            .await?;
+
+            append(&mut store, [
+                blank_ent_0::<C>(0, 0),
+                membership_ent_0::<C>(1, 1, btreeset! {1,2,3}),
+                joint_membership_ent_0::<C>(1, 2, vec![btreeset! {1,2,3}], btreeset! {4}),
+                joint_membership_ent_0::<C>(1, 3, vec![btreeset! {1,2,3}], btreeset! {4,5}),
+            ])
+            .await?;
+
+            let (_, mem) = sm.applied_state().await?;
+            assert_eq!(&Some(log_id_0(1, 2)), mem.log_id());
+            assert_eq!(
+                vec![C::NodeId::from(4)],
+                mem.membership().learner_ids().collect::<Vec<_>>()
+            );
+
+            let initial = StorageHelper::new(&mut store, &mut sm).get_initial_state().await?;
+            let mem_state = &initial.membership_state;
+
+            assert_eq!(&Some(log_id_0(1, 2)), mem_state.committed().log_id());
+            assert_eq!(
+                &Membership::new_with_defaults(vec![btreeset! {1,2,3}], btreeset! {4}),
+                mem_state.committed().membership(),
+            );
+            assert_eq!(&Some(log_id_0(1, 3)), mem_state.effective().log_id());
+            assert_eq!(
+                &Membership::new_with_defaults(vec![btreeset! {1,2,3}], btreeset! {4,5}),
+                mem_state.effective().membership(),
+            );
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_initial_state_membership_interleaved_with_snapshot(
+        mut store: LS,
+        mut sm: SM,
+    ) -> Result<(), StorageError<C>> {
+        let joint = || Membership::new_with_defaults(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], btreeset! {6});
+
+        Self::default_vote(&mut store).await?;
+
+        let entries = || {
+            [
+                membership_ent_0::<C>(1, 1, btreeset! {1,2,3}),
+                blank_ent_0::<C>(1, 2),
+                joint_membership_ent_0::<C>(1, 3, vec![btreeset! {1,2,3}, btreeset! {3,4,5}], btreeset! {6}),
+                blank_ent_0::<C>(1, 4),
+            ]
+        };
+
+        tracing::info!("--- build a snapshot after a joint config, purge the logs in it");
+        {
+            apply(&mut sm, entries()).await?;
+            append(&mut store, entries()).await?;
+
+            let snapshot = sm.get_snapshot_builder().await.build_snapshot().await?;
+            assert_eq!(Some(log_id_0(1, 4)), snapshot.meta.last_log_id);
+            assert_eq!(&Some(log_id_0(1, 3)), snapshot.meta.last_membership.log_id());
+            assert_eq!(&joint(), snapshot.meta.last_membership.membership());
+
+            store.purge(log_id_0(1, 4)).await?;
+        }
+
+        tracing::info!("--- a membership log after the snapshot");
+        {
+            append(&mut store, [
+                joint_membership_ent_0::<C>(2, 5, vec![btreeset! {3,4,5}], btreeset! {6}),
+                blank_ent_0::<C>(2, 6),
+            ])
+            .await?;
+
+            let initial = StorageHelper::new(&mut store, &mut sm).get_initial_state().await?;
+
+            assert_eq!(Some(&log_id_0(1, 4)), initial.snapshot_last_log_id());
+            assert_eq!(Some(&log_id_0(1, 4)), initial.last_purged_log_id());
+            assert_eq!(Some(&log_id_0(2, 6)), initial.last_log_id());
+
+            let mem_state = &initial.membership_state;
+            assert_eq!(&Some(log_id_0(1, 3)), mem_state.committed().log_id());
+            assert_eq!(&joint(), mem_state.committed().membership());
+            assert_eq!(&Some(log_id_0(2, 5)), mem_state.effective().log_id());
+            assert_eq!(
+                &Membership::new_with_defaults(vec![btreeset! {3,4,5}], btreeset! {6}),
+                mem_state.effective().membership(),
+            );
+        }
+
+        Ok(())
+    }
+
     pub async fn get_initial_state_without_init(mut store: LS, mut sm: SM) -> Result<(), StorageError<C>> {
         let initial = StorageHelper::new(&mut store, &mut sm).get_initial_state().await?;
         let mut want = RaftState::<C>::default();
@@ -1347,6 +1497,45 @@ where
         Ok(())
     }
 
+    pub async fn transfer_snapshot_joint_membership(builder: &B) -> Result<(), StorageError<C>> {
+        // A snapshot with a joint config and learners installed on sm_f is loaded on restart.
+        let (_g_l, _store_l, mut sm_l) = builder.build().await?;
+        let (_g_f, mut store_f, mut sm_f) = builder.build().await?;
+
+        let joint = || Membership::new_with_defaults(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], btreeset! {6});
+
+        sm_l.apply([
+            membership_ent_0::<C>(1, 1, btreeset! {1,2,3}),
+            joint_membership_ent_0::<C>(1, 2, vec![btreeset! {1,2,3}, btreeset! {3,4,5}], btreeset! {6}),
+            blank_ent_0::<C>(1, 3),
+        ])
+        .await?;
+
+        tracing::info!("--- install snapshot on follower state machine");
+        {
+            let snapshot = sm_l.get_snapshot_builder().await.build_snapshot().await?;
+            sm_f.install_snapshot(&snapshot.meta, snapshot.snapshot).await?;
+
+            let (last_applied, mem) = sm_f.applied_state().await?;
+            assert_eq!(Some(log_id_0(1, 3)), last_applied);
+            assert_eq!(StoredMembership::new(Some(log_id_0(1, 2)), joint()), mem);
+        }
+
+        tracing::info!("--- restart with the logs purged, membership is loaded from the snapshot");
+        {
+            // Raft purges the logs included in an installed snapshot.
+            Self::default_vote(&mut store_f).await?;
+            store_f.purge(log_id_0(1, 3)).await?;
+
+            let initial = StorageHelper::new(&mut store_f, &mut sm_f).get_initial_state().await?;
+            assert_eq!(&Some(log_id_0(1, 2)), initial.membership_state.committed().log_id());
+            assert_eq!(&joint(), initial.membership_state.committed().membership());
+            assert_eq!(&joint(), initial.membership_state.effective().membership());
+        }
+
+        Ok(())
+    }
+
     pub async fn feed_10_logs_vote_self(sto: &mut LS) -> Result<(), StorageError<C>> {
         append(sto, [blank_ent_0::<C>(0, 0)]).await?;
 
@@ -1394,6 +1583,21 @@ where
     C::Entry::new_membership(log_id_0(term, index), Membership::new_with_defaults(vec![bs], []))
 }
 
+/// Create a membership entry with joint configs and learners, with node_id 0 for test.
+fn joint_membership_ent_0<C>(
+    term: impl Into<C::Term>,
+    index: u64,
+    configs: Vec<BTreeSet<C::NodeId>>,
+    learners: BTreeSet<C::NodeId>,
+) -> C::Entry
+where
+    C: RaftTypeConfig,
+    C::NodeId: From<u64>,
+    C::Node: Default,
+{
+    C::Entry::new_membership(log_id_0(term, index), Membership::new_with_defaults(configs, learners))
+}
+
 /// Build a `RaftLogStorage` and `RaftStateMachine` implementation and run a test on it.
 async fn run_test<C, LS, SM, G, B, TestFn, Ret, Fu>(builder: &B, test_fn: TestFn) -> Result<Ret, StorageError<C>>
 where
@@ -0,0 +1,373 @@
+//! A mock clock that only advances when a test tells it to.
+//!
+//! [`MockClockRuntime`] is an [`AsyncRuntime`] that replaces the time of another runtime `Rt`,
+//! i.e., [`Instant`], `sleep()` and `timeout()`, with a virtual clock driven by
+//! [`MockClock::advance()`], and delegates everything else to `Rt`.
+//! Every timer in Openraft, such as election timeout, heartbeat and leader lease, is based on
+//! [`AsyncRuntime`], thus a test can make them fire deterministically, without waiting for real
+//! time:
+//!
+//! ```ignore
+//! openraft::declare_raft_types!(
+//!     pub TypeConfig:
+//!         AsyncRuntime = MockClockRuntime<TokioRuntime>,
+//! );
+//!
+//! #[tokio::test(flavor = "current_thread")]
+//! async fn test_election_timeout() {
+//!     // Start Raft, then fire the election timeout at once:
+//!     MockClock::advance(Duration::from_secs(1));
+//! }
+//! ```
+//!
+//! The clock is per thread, so that tests running in parallel do not affect each other.
+//! Thus all the tasks of a test have to run on the same thread, e.g., in a `current_thread` Tokio
+//! runtime.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+use std::time::Duration;
+
+use crate::AsyncRuntime;
+use crate::Instant;
+use crate::OptionalSend;
+
+#[derive(Default)]
+struct ClockState {
+    /// The virtual time since the clock is created.
+    now: Duration,
+
+    /// The id to assign to the next registered sleep.
+    next_id: u64,
+
+    /// The pending sleeps, keyed by `(deadline, id)`.
+    sleeps: BTreeMap<(Duration, u64), Waker>,
+}
+
+thread_local! {
+    static CLOCK: RefCell<ClockState> = RefCell::new(ClockState::default());
+}
+
+/// The virtual clock of the current thread, used by [`MockClockRuntime`].
+pub struct MockClock {}
+
+impl MockClock {
+    /// Return the current virtual time.
+    pub fn now() -> MockInstant {
+        CLOCK.with(|c| MockInstant(c.borrow().now))
+    }
+
+    /// Advance the virtual time by `duration` and wake up every sleep that reaches its deadline.
+    ///
+    /// The woken tasks run when the caller yields to the runtime, e.g., by awaiting.
+    pub fn advance(duration: Duration) {
+        let wakers = CLOCK.with(|c| {
+            let mut c = c.borrow_mut();
+            c.now += duration;
+
+            let now = c.now;
+            let pending = c.sleeps.split_off(&(now, u64::MAX));
+            std::mem::replace(&mut c.sleeps, pending)
+        });
+
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+
+    /// Return the number of sleeps that are waiting for the clock to advance.
+    pub fn pending_sleeps() -> usize {
+        CLOCK.with(|c| c.borrow().sleeps.len())
+    }
+
+    /// Return the earliest deadline of the sleeps that are waiting for the clock to advance.
+    pub fn next_deadline() -> Option<MockInstant> {
+        CLOCK.with(|c| c.borrow().sleeps.keys().next().map(|(deadline, _id)| MockInstant(*deadline)))
+    }
+}
+
+/// An instant of the virtual time of [`MockClock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MockInstant(Duration);
+
+impl MockInstant {
+    /// Return the instant at `duration` since the clock is created.
+    #[cfg_attr(not(feature = "tokio-rt"), allow(dead_code))]
+    pub(crate) fn from_duration(duration: Duration) -> Self {
+        Self(duration)
+    }
+
+    /// Return the virtual time since the clock is created.
+    #[cfg_attr(not(feature = "tokio-rt"), allow(dead_code))]
+    pub(crate) fn as_duration(&self) -> Duration {
+        self.0
+    }
+}
+
+impl Add<Duration> for MockInstant {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        Self(self.0 + rhs)
+    }
+}
+
+impl AddAssign<Duration> for MockInstant {
+    fn add_assign(&mut self, rhs: Duration) {
+        self.0 += rhs;
+    }
+}
+
+/// The virtual time starts at zero, an instant before it is saturated to zero.
+impl Sub<Duration> for MockInstant {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Self(self.0.saturating_sub(rhs))
+    }
+}
+
+impl Sub<Self> for MockInstant {
+    type Output = Duration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.0.saturating_sub(rhs.0)
+    }
+}
+
+impl SubAssign<Duration> for MockInstant {
+    fn sub_assign(&mut self, rhs: Duration) {
+        self.0 = self.0.saturating_sub(rhs);
+    }
+}
+
+impl Instant for MockInstant {
+    fn now() -> Self {
+        MockClock::now()
+    }
+}
+
+/// A future that resolves when the virtual time reaches the deadline.
+pub struct MockSleep {
+    deadline: Duration,
+
+    /// The id of the registered waker, if this sleep is pending.
+    id: Option<u64>,
+}
+
+impl MockSleep {
+    fn until(deadline: MockInstant) -> Self {
+        Self {
+            deadline: deadline.0,
+            id: None,
+        }
+    }
+}
+
+impl Future for MockSleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let deadline = self.deadline;
+        let id = self.id;
+
+        let next_id = CLOCK.with(|c| {
+            let mut c = c.borrow_mut();
+            if let Some(id) = id {
+                c.sleeps.remove(&(deadline, id));
+            }
+
+            if c.now >= deadline {
+                return None;
+            }
+
+            let id = id.unwrap_or_else(|| {
+                c.next_id += 1;
+                c.next_id
+            });
+            c.sleeps.insert((deadline, id), cx.waker().clone());
+            Some(id)
+        });
+
+        self.id = next_id;
+        match next_id {
+            None => Poll::Ready(()),
+            Some(_) => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for MockSleep {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            // The thread local may have been destroyed if it is dropped when the thread exits.
+            let _ = CLOCK.try_with(|c| c.borrow_mut().sleeps.remove(&(self.deadline, id)));
+        }
+    }
+}
+
+/// A future that resolves to [`Elapsed`] if the inner future does not finish before the virtual
+/// time reaches the deadline.
+pub struct MockTimeout<F> {
+    future: Pin<Box<F>>,
+    sleep: MockSleep,
+}
+
+impl<F> Future for MockTimeout<F>
+where F: Future
+{
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(output) = self.future.as_mut().poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+
+        match Pin::new(&mut self.sleep).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(Elapsed {})),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// The error returned by [`MockTimeout`] when the deadline has elapsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed {}
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "deadline has elapsed")
+    }
+}
+
+impl Error for Elapsed {}
+
+/// An [`AsyncRuntime`] that uses [`MockClock`] for time, and `Rt` for everything else.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MockClockRuntime<Rt> {
+    _p: PhantomData<Rt>,
+}
+
+impl<Rt> AsyncRuntime for MockClockRuntime<Rt>
+where Rt: AsyncRuntime
+{
+    type JoinError = Rt::JoinError;
+    type JoinHandle<T: OptionalSend + 'static> = Rt::JoinHandle<T>;
+    type Sleep = MockSleep;
+    type Instant = MockInstant;
+    type TimeoutError = Elapsed;
+    type Timeout<R, T: Future<Output = R> + OptionalSend> = MockTimeout<T>;
+    type ThreadLocalRng = Rt::ThreadLocalRng;
+
+    fn spawn<T>(future: T) -> Self::JoinHandle<T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        Rt::spawn(future)
+    }
+
+    fn sleep(duration: Duration) -> Self::Sleep {
+        MockSleep::until(MockClock::now() + duration)
+    }
+
+    fn sleep_until(deadline: Self::Instant) -> Self::Sleep {
+        MockSleep::until(deadline)
+    }
+
+    fn timeout<R, F: Future<Output = R> + OptionalSend>(duration: Duration, future: F) -> Self::Timeout<R, F> {
+        Self::timeout_at(MockClock::now() + duration, future)
+    }
+
+    fn timeout_at<R, F: Future<Output = R> + OptionalSend>(deadline: Self::Instant, future: F) -> Self::Timeout<R, F> {
+        MockTimeout {
+            future: Box::pin(future),
+            sleep: MockSleep::until(deadline),
+        }
+    }
+
+    fn is_panic(join_error: &Self::JoinError) -> bool {
+        Rt::is_panic(join_error)
+    }
+
+    fn thread_rng() -> Self::ThreadLocalRng {
+        Rt::thread_rng()
+    }
+
+    type Mpsc = Rt::Mpsc;
+    type MpscUnbounded = Rt::MpscUnbounded;
+    type Watch = Rt::Watch;
+    type Oneshot = Rt::Oneshot;
+    type Mutex<T: OptionalSend + 'static> = Rt::Mutex<T>;
+}
+
+// `TokioRuntime::spawn()` is `spawn_local()` with singlethreaded enabled.
+#[cfg(not(feature = "singlethreaded"))]
+#[cfg(feature = "tokio-rt")]
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::impls::TokioRuntime;
+    use crate::testing::mock_clock::MockClock;
+    use crate::testing::mock_clock::MockClockRuntime;
+    use crate::AsyncRuntime;
+    use crate::Instant;
+
+    type Rt = MockClockRuntime<TokioRuntime>;
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_mock_clock_sleep() {
+        let start = <Rt as AsyncRuntime>::Instant::now();
+
+        let sleep = Rt::spawn(Rt::sleep(Duration::from_secs(10)));
+        tokio::task::yield_now().await;
+        assert_eq!(1, MockClock::pending_sleeps());
+
+        MockClock::advance(Duration::from_secs(9));
+        tokio::task::yield_now().await;
+        assert!(!sleep.is_finished());
+
+        MockClock::advance(Duration::from_secs(1));
+        sleep.await.unwrap();
+
+        assert_eq!(Duration::from_secs(10), start.elapsed());
+        assert_eq!(0, MockClock::pending_sleeps());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_mock_clock_timeout() {
+        let timeout = Rt::spawn(Rt::timeout(Duration::from_secs(10), std::future::pending::<()>()));
+        tokio::task::yield_now().await;
+
+        MockClock::advance(Duration::from_secs(10));
+        let res = timeout.await.unwrap();
+        assert_eq!("deadline has elapsed", res.unwrap_err().to_string());
+
+        let res = Rt::timeout(Duration::from_secs(10), async { 3 }).await;
+        assert_eq!(Ok(3), res);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_mock_clock_drop_sleep() {
+        let sleep = Rt::spawn(Rt::sleep(Duration::from_secs(10)));
+        tokio::task::yield_now().await;
+        assert_eq!(1, MockClock::pending_sleeps());
+
+        sleep.abort();
+        let _ = sleep.await;
+        assert_eq!(0, MockClock::pending_sleeps());
+    }
+}
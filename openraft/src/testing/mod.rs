@@ -1,7 +1,13 @@
 //! Testing utilities for OpenRaft.
 
 pub mod common;
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub mod fuzz;
 pub mod log;
+pub mod mock_clock;
 pub mod runtime;
+#[cfg(feature = "tokio-rt")]
+pub mod sim;
 
 pub use common::*;
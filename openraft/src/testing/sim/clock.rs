@@ -0,0 +1,128 @@
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::time::Duration;
+
+use crate::testing::mock_clock::MockClock;
+use crate::testing::mock_clock::MockInstant;
+use crate::testing::sim::runtime::current_node_clock;
+use crate::Instant;
+
+/// The local clock of a simulated node, derived from the virtual time of [`MockClock`].
+///
+/// The local time is `local + (now - global) * (1 + drift_ppm / 1_000_000)`, in which
+/// `(global, local)` is the time when the clock was last adjusted, i.e., a clock with a positive
+/// drift runs faster than the virtual time, and a clock with a negative drift runs slower.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct NodeClock {
+    /// The virtual time when the clock was last adjusted.
+    global: Duration,
+
+    /// The local time when the clock was last adjusted.
+    local: Duration,
+
+    /// The drift rate in parts per million.
+    drift_ppm: i64,
+}
+
+impl NodeClock {
+    const PPM: u128 = 1_000_000;
+
+    /// Return a clock that jumps forward by `offset` at virtual time `now` and then runs with
+    /// `drift_ppm`, so that the local time never goes backward.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the drift makes the clock stop or run backward, i.e., `drift_ppm <= -1_000_000`.
+    pub(crate) fn adjust(&self, now: Duration, offset: Duration, drift_ppm: i64) -> Self {
+        assert!(
+            drift_ppm > -(Self::PPM as i64),
+            "drift must be greater than -1_000_000 ppm, got: {}",
+            drift_ppm
+        );
+
+        Self {
+            global: now,
+            local: self.local_time(now) + offset,
+            drift_ppm,
+        }
+    }
+
+    fn rate(&self) -> u128 {
+        (Self::PPM as i64 + self.drift_ppm) as u128
+    }
+
+    /// Convert a virtual time to the local time of this clock.
+    pub(crate) fn local_time(&self, global: Duration) -> Duration {
+        let elapsed = global.saturating_sub(self.global).as_nanos() * self.rate() / Self::PPM;
+        self.local + nanos(elapsed)
+    }
+
+    /// Convert a local time of this clock to the earliest virtual time when it is reached.
+    pub(crate) fn global_time(&self, local: Duration) -> Duration {
+        let elapsed = local.saturating_sub(self.local).as_nanos() * Self::PPM;
+        self.global + nanos(elapsed.div_ceil(self.rate()))
+    }
+}
+
+fn nanos(n: u128) -> Duration {
+    Duration::from_nanos(n as u64)
+}
+
+/// An instant of the local clock of the simulated node that the current task belongs to.
+///
+/// A task that does not belong to a node, such as the future passed to
+/// [`Sim::run()`](super::Sim::run), uses the virtual time of [`MockClock`] as it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SimInstant(Duration);
+
+impl SimInstant {
+    /// Return the virtual time when the local clock of the current node reaches this instant.
+    pub(crate) fn to_mock_instant(self) -> MockInstant {
+        MockInstant::from_duration(current_node_clock().global_time(self.0))
+    }
+}
+
+impl Add<Duration> for SimInstant {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        Self(self.0 + rhs)
+    }
+}
+
+impl AddAssign<Duration> for SimInstant {
+    fn add_assign(&mut self, rhs: Duration) {
+        self.0 += rhs;
+    }
+}
+
+/// The local time starts at zero, an instant before it is saturated to zero.
+impl Sub<Duration> for SimInstant {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Self(self.0.saturating_sub(rhs))
+    }
+}
+
+impl Sub<Self> for SimInstant {
+    type Output = Duration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.0.saturating_sub(rhs.0)
+    }
+}
+
+impl SubAssign<Duration> for SimInstant {
+    fn sub_assign(&mut self, rhs: Duration) {
+        self.0 = self.0.saturating_sub(rhs);
+    }
+}
+
+impl Instant for SimInstant {
+    fn now() -> Self {
+        Self(current_node_clock().local_time(MockClock::now().as_duration()))
+    }
+}
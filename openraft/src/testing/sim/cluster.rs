@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::Fatal;
+use crate::testing::sim::network::Router;
+use crate::testing::sim::network::SimNetworkFactory;
+use crate::testing::sim::runtime::adjust_node_clock;
+use crate::testing::sim::runtime::in_node;
+use crate::testing::sim::SimConfig;
+use crate::testing::sim::SimLogStore;
+use crate::testing::sim::SimStateMachine;
+use crate::Config;
+use crate::Raft;
+
+type C = SimConfig;
+
+/// A cluster of [`SimConfig`] nodes that run in one thread, inside [`Sim::run()`].
+///
+/// Every node has an in-memory log store and state machine. RPCs are delivered after a random
+/// latency and are recorded in [`Self::history()`], which is identical for the same seed.
+///
+/// ```ignore
+/// let history = Sim::run(seed, async {
+///     let mut cluster = SimCluster::new(Arc::new(Config::default()));
+///     for id in 0..3 {
+///         cluster.add_node(id).await?;
+///     }
+///     cluster.raft(0).initialize(btreeset! {0, 1, 2}).await?;
+///     // Write, isolate nodes, wait for the cluster to converge...
+///     Ok(cluster.history())
+/// })?;
+/// ```
+///
+/// [`Sim::run()`]: super::Sim::run
+pub struct SimCluster {
+    config: Arc<Config>,
+    router: Router,
+    state_machines: BTreeMap<u64, SimStateMachine>,
+}
+
+impl SimCluster {
+    /// Create an empty cluster, nodes are added with [`Self::add_node()`].
+    pub fn new(config: Arc<Config>) -> Self {
+        Self {
+            config,
+            router: Router::new(),
+            state_machines: BTreeMap::new(),
+        }
+    }
+
+    /// Start a node with empty storage and add it to the network.
+    ///
+    /// The node is not a member of the cluster until it is added by initialization or by a
+    /// membership change.
+    pub async fn add_node(&mut self, id: u64) -> Result<Raft<C>, Fatal<C>> {
+        let network = SimNetworkFactory {
+            router: self.router.clone(),
+            source: id,
+        };
+        let state_machine = SimStateMachine::default();
+
+        let raft = in_node(
+            id,
+            Raft::new(
+                id,
+                self.config.clone(),
+                network,
+                SimLogStore::default(),
+                state_machine.clone(),
+            ),
+        )
+        .await?;
+
+        self.state_machines.insert(id, state_machine);
+        self.router.state.lock().unwrap().nodes.insert(id, raft.clone());
+        self.router.record(format!("add node {}", id));
+
+        Ok(raft)
+    }
+
+    /// Return the `Raft` of a node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not added.
+    pub fn raft(&self, id: u64) -> Raft<C> {
+        self.router
+            .state
+            .lock()
+            .unwrap()
+            .nodes
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| panic!("node {} not found", id))
+    }
+
+    /// Return the state machine of a node, to inspect the applied values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not added.
+    pub fn state_machine(&self, id: u64) -> SimStateMachine {
+        self.state_machines.get(&id).cloned().unwrap_or_else(|| panic!("node {} not found", id))
+    }
+
+    /// Drop every RPC sent from or to a node, until it is restored.
+    pub fn isolate(&self, id: u64) {
+        self.router.state.lock().unwrap().isolated.insert(id);
+        self.router.record(format!("isolate node {}", id));
+    }
+
+    /// Restore the network of an isolated node.
+    pub fn restore(&self, id: u64) {
+        self.router.state.lock().unwrap().isolated.remove(&id);
+        self.router.record(format!("restore node {}", id));
+    }
+
+    /// Set the max latency of an RPC, the actual latency of every RPC is chosen randomly.
+    ///
+    /// The default is 10 ms.
+    pub fn set_max_latency(&self, latency: Duration) {
+        self.router.state.lock().unwrap().max_latency = latency;
+    }
+
+    /// Make the local clock of a node jump forward by `offset`, then run with a drift of
+    /// `drift_ppm` parts per million, e.g., `100_000` runs 10% faster than the virtual time and
+    /// `-100_000` runs 10% slower.
+    ///
+    /// Every timer of the node, such as election timeout, heartbeat and leader lease, is measured
+    /// with its local clock. It can be called before or after the node is added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `drift_ppm <= -1_000_000`, with which the clock stops or runs backward.
+    pub fn set_clock_skew(&self, id: u64, offset: Duration, drift_ppm: i64) {
+        adjust_node_clock(id, offset, drift_ppm);
+        self.router.record(format!(
+            "set clock skew of node {}: offset {:?}, drift {} ppm",
+            id, offset, drift_ppm
+        ));
+    }
+
+    /// Append an application defined event to the history.
+    pub fn record(&self, event: impl ToString) {
+        self.router.record(event);
+    }
+
+    /// Return the recorded events: nodes added, RPCs sent, network changes and application
+    /// events, each with the virtual time since the cluster is created.
+    pub fn history(&self) -> Vec<String> {
+        self.router.state.lock().unwrap().history.clone()
+    }
+
+    /// Return the ids of all the nodes.
+    pub fn node_ids(&self) -> Vec<u64> {
+        self.state_machines.keys().copied().collect()
+    }
+}
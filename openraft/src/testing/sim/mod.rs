@@ -0,0 +1,49 @@
+//! Deterministic simulation of a whole cluster in one thread.
+//!
+//! [`SimRuntime`] is an [`AsyncRuntime`] whose time, task scheduling and random numbers are all
+//! controlled by the seed passed to [`Sim::run()`], and [`SimCluster`] runs several Raft nodes with
+//! in-memory storage and network on it. Running a test with the same seed replays the same
+//! history, so that a failure found with a seed can be reproduced and debugged.
+//! Every node can run with its own clock offset and drift, see [`SimCluster::set_clock_skew()`],
+//! to test timing based logic such as leader lease under clock skew:
+//!
+//! ```ignore
+//! for seed in 0..100 {
+//!     let history = Sim::run(seed, async {
+//!         let mut cluster = SimCluster::new(Arc::new(Config::default()));
+//!         // ...
+//!         cluster.history()
+//!     });
+//! }
+//! ```
+//!
+//! [`AsyncRuntime`]: crate::AsyncRuntime
+
+mod clock;
+mod cluster;
+mod network;
+mod runtime;
+mod store;
+
+#[cfg(test)]
+mod sim_test;
+
+pub use clock::SimInstant;
+pub use cluster::SimCluster;
+pub use runtime::Sim;
+pub use runtime::SimJoinError;
+pub use runtime::SimJoinHandle;
+pub use runtime::SimRng;
+pub use runtime::SimRuntime;
+pub use store::SimLogStore;
+pub use store::SimStateMachine;
+
+crate::declare_raft_types!(
+    /// The type config of a [`SimCluster`]: the application data is a `u64` value, and the
+    /// response is the number of values applied.
+    pub SimConfig:
+        D = u64,
+        R = u64,
+        Node = (),
+        AsyncRuntime = SimRuntime,
+);
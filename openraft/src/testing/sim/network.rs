@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::error::ReplicationClosed;
+use crate::error::StreamingError;
+use crate::error::Unreachable;
+use crate::network::v2::RaftNetworkV2;
+use crate::network::RPCOption;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+use crate::raft::SnapshotResponse;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::storage::Snapshot;
+use crate::testing::mock_clock::MockClock;
+use crate::testing::mock_clock::MockInstant;
+use crate::testing::sim::SimConfig;
+use crate::testing::sim::SimRuntime;
+use crate::type_config::alias::VoteOf;
+use crate::type_config::TypeConfigExt;
+use crate::AnyError;
+use crate::AsyncRuntime;
+use crate::OptionalSend;
+use crate::Raft;
+use crate::RaftNetworkFactory;
+
+type C = SimConfig;
+
+pub(crate) struct RouterState {
+    pub(crate) nodes: BTreeMap<u64, Raft<C>>,
+
+    /// Nodes that can not send or receive any RPC.
+    pub(crate) isolated: BTreeSet<u64>,
+
+    /// The max latency of an RPC, the actual latency is chosen randomly.
+    pub(crate) max_latency: Duration,
+
+    pub(crate) start: MockInstant,
+    pub(crate) history: Vec<String>,
+}
+
+/// Routes RPCs between the nodes of a [`SimCluster`](super::SimCluster) and records them.
+#[derive(Clone)]
+pub(crate) struct Router {
+    pub(crate) state: Arc<Mutex<RouterState>>,
+}
+
+impl Router {
+    // `Raft` is not `Send` with feature `singlethreaded`.
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(RouterState {
+                nodes: BTreeMap::new(),
+                isolated: BTreeSet::new(),
+                max_latency: Duration::from_millis(10),
+                start: MockClock::now(),
+                history: Vec::new(),
+            })),
+        }
+    }
+
+    /// Append an event to the history, with the virtual time since the router is created.
+    pub(crate) fn record(&self, event: impl ToString) {
+        let mut state = self.state.lock().unwrap();
+        let elapsed = MockClock::now() - state.start;
+        state.history.push(format!("{:?}: {}", elapsed, event.to_string()));
+    }
+
+    /// Wait for a random latency, then return the target `Raft` if neither end is isolated.
+    async fn connect(&self, source: u64, target: u64, rpc: &str) -> Result<Raft<C>, Unreachable> {
+        let max_latency = self.state.lock().unwrap().max_latency;
+        let latency = SimRuntime::thread_rng().gen_range(Duration::ZERO..=max_latency);
+        C::sleep(latency).await;
+
+        let raft = {
+            let state = self.state.lock().unwrap();
+            if state.isolated.contains(&source) || state.isolated.contains(&target) {
+                None
+            } else {
+                state.nodes.get(&target).cloned()
+            }
+        };
+
+        let delivered = if raft.is_some() { "" } else { ", dropped" };
+        self.record(format!("{}->{} {}{}", source, target, rpc, delivered));
+
+        raft.ok_or_else(|| Unreachable::new(&AnyError::error(format!("{} is unreachable from {}", target, source))))
+    }
+}
+
+/// Creates [`SimNetwork`] connections from a node.
+pub(crate) struct SimNetworkFactory {
+    pub(crate) router: Router,
+    pub(crate) source: u64,
+}
+
+impl RaftNetworkFactory<C> for SimNetworkFactory {
+    type Network = SimNetwork;
+
+    async fn new_client(&mut self, target: u64, _node: &()) -> Self::Network {
+        SimNetwork {
+            router: self.router.clone(),
+            source: self.source,
+            target,
+        }
+    }
+}
+
+/// A connection that delivers RPCs to the `Raft` of the target node via the [`Router`].
+pub(crate) struct SimNetwork {
+    router: Router,
+    source: u64,
+    target: u64,
+}
+
+impl RaftNetworkV2<C> for SimNetwork {
+    async fn append_entries(
+        &mut self,
+        rpc: AppendEntriesRequest<C>,
+        _option: RPCOption,
+    ) -> Result<AppendEntriesResponse<C>, crate::error::RPCError<C>> {
+        let raft = self.router.connect(self.source, self.target, "append_entries").await?;
+        let resp = raft.append_entries(rpc).await.map_err(|e| Unreachable::new(&e))?;
+        Ok(resp)
+    }
+
+    async fn vote(
+        &mut self,
+        rpc: VoteRequest<C>,
+        _option: RPCOption,
+    ) -> Result<VoteResponse<C>, crate::error::RPCError<C>> {
+        let raft = self.router.connect(self.source, self.target, "vote").await?;
+        let resp = raft.vote(rpc).await.map_err(|e| Unreachable::new(&e))?;
+        Ok(resp)
+    }
+
+    async fn full_snapshot(
+        &mut self,
+        vote: VoteOf<C>,
+        snapshot: Snapshot<C>,
+        _cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse<C>, StreamingError<C>> {
+        let raft = self.router.connect(self.source, self.target, "full_snapshot").await?;
+        let resp = raft.install_full_snapshot(vote, snapshot).await.map_err(|e| Unreachable::new(&e))?;
+        Ok(resp)
+    }
+}
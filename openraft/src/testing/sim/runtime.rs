@@ -0,0 +1,370 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::pin;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Wake;
+use std::task::Waker;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::RngCore;
+use rand::SeedableRng;
+
+use crate::impls::TokioRuntime;
+use crate::testing::mock_clock::Elapsed;
+use crate::testing::mock_clock::MockClock;
+use crate::testing::mock_clock::MockClockRuntime;
+use crate::testing::mock_clock::MockSleep;
+use crate::testing::mock_clock::MockTimeout;
+use crate::testing::sim::clock::NodeClock;
+use crate::testing::sim::SimInstant;
+use crate::AsyncRuntime;
+use crate::Instant;
+use crate::OptionalSend;
+
+/// The task id of the future passed to [`Sim::run()`].
+const MAIN_TASK: u64 = 0;
+
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// The ids of the tasks that are woken up and wait to be polled.
+///
+/// It is shared with the [`Waker`]s, which are required to be `Send + Sync`.
+///
+/// It is a set ordered by task id rather than a queue, because the order in which tasks are woken
+/// up is not always deterministic, e.g., a Tokio `watch` channel distributes its receivers among
+/// several internal notifiers with a random number generator that can not be seeded.
+type ReadyQueue = BTreeSet<u64>;
+
+struct TaskWaker {
+    id: u64,
+    ready: Arc<Mutex<ReadyQueue>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.ready.lock().unwrap().insert(self.id);
+    }
+}
+
+struct Executor {
+    tasks: BTreeMap<u64, Task>,
+    next_id: u64,
+    ready: Arc<Mutex<ReadyQueue>>,
+    rng: StdRng,
+
+    /// The node that each task belongs to, inherited from the task that spawns it.
+    task_nodes: BTreeMap<u64, u64>,
+
+    /// The node that the task being polled belongs to.
+    current_node: Option<u64>,
+
+    /// The local clocks of the nodes, a node without one uses the virtual time as it is.
+    clocks: BTreeMap<u64, NodeClock>,
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self {
+            tasks: BTreeMap::new(),
+            next_id: MAIN_TASK,
+            ready: Arc::new(Mutex::new(ReadyQueue::default())),
+            rng: StdRng::seed_from_u64(0),
+            task_nodes: BTreeMap::new(),
+            current_node: None,
+            clocks: BTreeMap::new(),
+        }
+    }
+}
+
+impl Executor {
+    fn waker(&self, id: u64) -> Waker {
+        Waker::from(Arc::new(TaskWaker {
+            id,
+            ready: self.ready.clone(),
+        }))
+    }
+
+    fn spawn(&mut self, task: Task) {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.tasks.insert(id, task);
+        if let Some(node) = self.current_node {
+            self.task_nodes.insert(id, node);
+        }
+        self.waker(id).wake();
+    }
+
+    /// Remove a randomly chosen task from the ready queue and return its id and waker.
+    fn pick_ready(&mut self) -> Option<(u64, Waker)> {
+        let id = {
+            let mut ready = self.ready.lock().unwrap();
+            if ready.is_empty() {
+                return None;
+            }
+            let i = self.rng.gen_range(0..ready.len());
+            let id = *ready.iter().nth(i).unwrap();
+            ready.remove(&id);
+            id
+        };
+        Some((id, self.waker(id)))
+    }
+}
+
+thread_local! {
+    static EXECUTOR: RefCell<Executor> = RefCell::new(Executor::default());
+}
+
+/// Return the local clock of the node that the current task belongs to.
+pub(crate) fn current_node_clock() -> NodeClock {
+    EXECUTOR.with(|e| {
+        let e = e.borrow();
+        e.current_node.and_then(|node| e.clocks.get(&node).copied()).unwrap_or_default()
+    })
+}
+
+/// Make the local clock of a node jump forward by `offset` and then run with `drift_ppm`.
+pub(crate) fn adjust_node_clock(node: u64, offset: Duration, drift_ppm: i64) {
+    let now = MockClock::now().as_duration();
+    EXECUTOR.with(|e| {
+        let mut e = e.borrow_mut();
+        let clock = e.clocks.get(&node).copied().unwrap_or_default();
+        e.clocks.insert(node, clock.adjust(now, offset, drift_ppm));
+    });
+}
+
+/// Set the node that the current task belongs to, and return the previous one.
+fn set_current_node(node: Option<u64>) -> Option<u64> {
+    EXECUTOR.with(|e| std::mem::replace(&mut e.borrow_mut().current_node, node))
+}
+
+/// Run a future as a part of a node: it and the tasks it spawns use the local clock of the node.
+pub(crate) fn in_node<F>(node: u64, future: F) -> InNode<F>
+where F: Future {
+    InNode {
+        node,
+        future: Box::pin(future),
+    }
+}
+
+/// A future returned by [`in_node()`].
+pub(crate) struct InNode<F> {
+    node: u64,
+    future: Pin<Box<F>>,
+}
+
+impl<F> Future for InNode<F>
+where F: Future
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let prev = set_current_node(Some(self.node));
+        let res = self.future.as_mut().poll(cx);
+        set_current_node(prev);
+        res
+    }
+}
+
+/// A deterministic executor that runs all the tasks of a simulation in the current thread.
+///
+/// Every source of nondeterminism of [`SimRuntime`] is controlled by a seed:
+/// - Time is the virtual time of [`MockClock`]. It only advances when no task is ready to run, to
+///   the earliest deadline of the pending sleeps, thus a simulation does not wait for real time. A
+///   task that belongs to a node reads the local clock of the node, which may have an offset and a
+///   drift, see [`SimCluster::set_clock_skew()`](super::SimCluster::set_clock_skew).
+/// - When more than one task is ready, the next task to poll is chosen by a random number generator
+///   built from the seed.
+/// - [`SimRuntime::thread_rng()`] returns the same generator, e.g., for election timeouts.
+///
+/// Thus running the same future with the same seed replays the same history.
+pub struct Sim {}
+
+impl Sim {
+    /// Run `future` until it completes, with all the tasks spawned by [`SimRuntime`] in the current
+    /// thread, and return its output.
+    ///
+    /// Tasks that are still running when `future` completes are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no task can make progress: no task is ready and no sleep is pending.
+    pub fn run<F>(seed: u64, future: F) -> F::Output
+    where F: Future {
+        let main_waker = EXECUTOR.with(|e| {
+            let mut e = e.borrow_mut();
+            *e = Executor::default();
+            e.rng = StdRng::seed_from_u64(seed);
+            e.waker(MAIN_TASK)
+        });
+        main_waker.wake_by_ref();
+
+        let mut future = pin!(future);
+
+        let output = loop {
+            let Some((id, waker)) = EXECUTOR.with(|e| e.borrow_mut().pick_ready()) else {
+                let deadline =
+                    MockClock::next_deadline().expect("simulation is stuck: no task is ready and no sleep is pending");
+                MockClock::advance(deadline - MockClock::now());
+                continue;
+            };
+
+            let mut cx = Context::from_waker(&waker);
+
+            let node = EXECUTOR.with(|e| e.borrow().task_nodes.get(&id).copied());
+            set_current_node(node);
+
+            if id == MAIN_TASK {
+                if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                    break output;
+                }
+                continue;
+            }
+
+            // The task is taken out while it is polled, because it may spawn new tasks.
+            let Some(mut task) = EXECUTOR.with(|e| e.borrow_mut().tasks.remove(&id)) else {
+                continue;
+            };
+
+            if task.as_mut().poll(&mut cx).is_pending() {
+                EXECUTOR.with(|e| e.borrow_mut().tasks.insert(id, task));
+            } else {
+                EXECUTOR.with(|e| e.borrow_mut().task_nodes.remove(&id));
+            }
+        };
+
+        set_current_node(None);
+
+        // Drop the remaining tasks outside the borrow, dropping a task may wake up other tasks.
+        let tasks = EXECUTOR.with(|e| std::mem::take(&mut e.borrow_mut().tasks));
+        drop(tasks);
+
+        output
+    }
+}
+
+/// A random number generator of the current [`Sim`], seeded by [`Sim::run()`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimRng {}
+
+impl RngCore for SimRng {
+    fn next_u32(&mut self) -> u32 {
+        EXECUTOR.with(|e| e.borrow_mut().rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        EXECUTOR.with(|e| e.borrow_mut().rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        EXECUTOR.with(|e| e.borrow_mut().rng.fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        EXECUTOR.with(|e| e.borrow_mut().rng.try_fill_bytes(dest))
+    }
+}
+
+/// The handle of a task spawned by [`SimRuntime`].
+pub struct SimJoinHandle<T> {
+    rx: tokio::sync::oneshot::Receiver<T>,
+}
+
+impl<T> Future for SimJoinHandle<T> {
+    type Output = Result<T, SimJoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.rx).poll(cx).map_err(|_| SimJoinError {})
+    }
+}
+
+/// The error returned by [`SimJoinHandle`] if the task is dropped before it completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimJoinError {}
+
+impl fmt::Display for SimJoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "task is dropped before it completes")
+    }
+}
+
+impl Error for SimJoinError {}
+
+/// An [`AsyncRuntime`] that runs tasks in a deterministic [`Sim`].
+///
+/// Time is provided by [`MockClock`], the same as [`MockClockRuntime`], except that an instant, a
+/// sleep or a timeout in a task of a node is measured with the local clock of the node.
+/// Channels are provided by Tokio, which do not need a Tokio runtime to run.
+/// A task panic is not caught, it is propagated to the caller of [`Sim::run()`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SimRuntime {}
+
+impl AsyncRuntime for SimRuntime {
+    type JoinError = SimJoinError;
+    type JoinHandle<T: OptionalSend + 'static> = SimJoinHandle<T>;
+    type Sleep = MockSleep;
+    type Instant = SimInstant;
+    type TimeoutError = Elapsed;
+    type Timeout<R, T: Future<Output = R> + OptionalSend> = MockTimeout<T>;
+    type ThreadLocalRng = SimRng;
+
+    fn spawn<T>(future: T) -> Self::JoinHandle<T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        let task = async move {
+            let output = future.await;
+            let _ = tx.send(output);
+        };
+
+        EXECUTOR.with(|e| e.borrow_mut().spawn(Box::pin(task)));
+
+        SimJoinHandle { rx }
+    }
+
+    fn sleep(duration: Duration) -> Self::Sleep {
+        Self::sleep_until(SimInstant::now() + duration)
+    }
+
+    fn sleep_until(deadline: Self::Instant) -> Self::Sleep {
+        MockClockRuntime::<TokioRuntime>::sleep_until(deadline.to_mock_instant())
+    }
+
+    fn timeout<R, F: Future<Output = R> + OptionalSend>(duration: Duration, future: F) -> Self::Timeout<R, F> {
+        Self::timeout_at(SimInstant::now() + duration, future)
+    }
+
+    fn timeout_at<R, F: Future<Output = R> + OptionalSend>(deadline: Self::Instant, future: F) -> Self::Timeout<R, F> {
+        MockClockRuntime::<TokioRuntime>::timeout_at(deadline.to_mock_instant(), future)
+    }
+
+    fn is_panic(_join_error: &Self::JoinError) -> bool {
+        false
+    }
+
+    fn thread_rng() -> Self::ThreadLocalRng {
+        SimRng {}
+    }
+
+    type Mpsc = <TokioRuntime as AsyncRuntime>::Mpsc;
+    type MpscUnbounded = <TokioRuntime as AsyncRuntime>::MpscUnbounded;
+    type Watch = <TokioRuntime as AsyncRuntime>::Watch;
+    type Oneshot = <TokioRuntime as AsyncRuntime>::Oneshot;
+    type Mutex<T: OptionalSend + 'static> = <TokioRuntime as AsyncRuntime>::Mutex<T>;
+}
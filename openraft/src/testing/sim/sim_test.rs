@@ -0,0 +1,265 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+
+use crate::async_runtime::watch::WatchReceiver;
+use crate::testing::mock_clock::MockClock;
+use crate::testing::runtime::Suite;
+use crate::testing::sim::runtime::adjust_node_clock;
+use crate::testing::sim::runtime::in_node;
+use crate::testing::sim::Sim;
+use crate::testing::sim::SimCluster;
+use crate::testing::sim::SimInstant;
+use crate::testing::sim::SimRuntime;
+use crate::AsyncRuntime;
+use crate::Config;
+use crate::Instant;
+use crate::ServerState;
+
+/// `Suite::test_sleep()` is skipped because it measures the real time.
+#[test]
+fn test_sim_runtime_suite() {
+    type S = Suite<SimRuntime>;
+
+    Sim::run(0, async {
+        S::test_spawn_join_handle().await;
+        S::test_instant().await;
+        S::test_sleep_until().await;
+        S::test_timeout().await;
+        S::test_timeout_at().await;
+
+        S::test_mpsc_recv_empty().await;
+        S::test_mpsc_recv_channel_closed().await;
+        S::test_mpsc_weak_sender_wont_prevent_channel_close().await;
+        S::test_mpsc_weak_sender_upgrade().await;
+        S::test_mpsc_send().await;
+
+        S::test_unbounded_mpsc_recv_empty().await;
+        S::test_unbounded_mpsc_recv_channel_closed().await;
+        S::test_unbounded_mpsc_weak_sender_wont_prevent_channel_close().await;
+        S::test_unbounded_mpsc_weak_sender_upgrade().await;
+        S::test_unbounded_mpsc_send().await;
+
+        S::test_watch_init_value().await;
+        S::test_watch_overwrite_init_value().await;
+        S::test_watch_send_error_no_receiver().await;
+        S::test_watch_send_if_modified().await;
+        S::test_oneshot_drop_tx().await;
+        S::test_oneshot().await;
+        S::test_mutex().await;
+        S::test_mutex_contention().await;
+    });
+}
+
+/// Elect a leader, write, isolate the leader, write to the new leader, then restore the old leader.
+///
+/// Returns the history and the values applied by every node.
+fn run_cluster(seed: u64) -> anyhow::Result<(Vec<String>, Vec<Vec<u64>>)> {
+    Sim::run(seed, async {
+        let timeout = Some(Duration::from_secs(10));
+        let config = Arc::new(Config::default().validate()?);
+
+        let mut cluster = SimCluster::new(config);
+        for id in 0..3 {
+            cluster.add_node(id).await?;
+        }
+        cluster.raft(0).initialize(btreeset! {0, 1, 2}).await?;
+
+        let m = cluster.raft(0).wait(timeout).metrics(|m| m.current_leader.is_some(), "elect").await?;
+        let leader = m.current_leader.unwrap();
+
+        for v in 1..=5 {
+            cluster.raft(leader).client_write(v).await?;
+        }
+
+        cluster.isolate(leader);
+        let others = cluster.node_ids().into_iter().filter(|id| *id != leader).collect::<Vec<_>>();
+        let m = cluster
+            .raft(others[0])
+            .wait(timeout)
+            .metrics(|m| m.current_leader.is_some_and(|l| l != leader), "re-elect")
+            .await?;
+        let new_leader = m.current_leader.unwrap();
+        cluster.record(format!("new leader {}", new_leader));
+
+        for v in 6..=10 {
+            cluster.raft(new_leader).client_write(v).await?;
+        }
+
+        cluster.restore(leader);
+        let last_applied = cluster.raft(new_leader).metrics().borrow_watched().last_applied;
+        for id in cluster.node_ids() {
+            cluster.raft(id).wait(timeout).applied_index_at_least(last_applied.map(|x| x.index), "sync").await?;
+        }
+
+        let values = cluster.node_ids().into_iter().map(|id| cluster.state_machine(id).values()).collect();
+        Ok((cluster.history(), values))
+    })
+}
+
+#[test]
+fn test_sim_cluster() -> anyhow::Result<()> {
+    let (_history, values) = run_cluster(1)?;
+
+    let want = (1..=10).collect::<Vec<_>>();
+    assert_eq!(vec![want.clone(), want.clone(), want], values);
+
+    Ok(())
+}
+
+#[test]
+fn test_sim_replay_with_seed() -> anyhow::Result<()> {
+    let (history, _) = run_cluster(3)?;
+    let (replayed, _) = run_cluster(3)?;
+    assert_eq!(history, replayed, "the same seed replays the same history");
+
+    let (other, _) = run_cluster(4)?;
+    assert_ne!(history, other, "a different seed explores a different history");
+
+    Ok(())
+}
+
+#[test]
+fn test_sim_clock_skew() {
+    Sim::run(0, async {
+        let start = MockClock::now();
+
+        // Node 1 starts 1 second ahead and runs twice as fast as the virtual time.
+        adjust_node_clock(1, Duration::from_secs(1), 1_000_000);
+
+        let t = in_node(1, async { SimInstant::now() }).await;
+        assert_eq!(
+            SimInstant::now() + Duration::from_secs(1),
+            t,
+            "main task uses the virtual time"
+        );
+
+        // A task spawned by node 1 belongs to node 1.
+        in_node(1, async {
+            SimRuntime::spawn(SimRuntime::sleep(Duration::from_millis(100))).await.unwrap()
+        })
+        .await;
+        assert_eq!(Duration::from_millis(50), MockClock::now() - start);
+
+        let t2 = in_node(1, async { SimInstant::now() }).await;
+        assert_eq!(Duration::from_millis(100), t2 - t);
+
+        // Slowing down the clock does not make it go backward.
+        adjust_node_clock(1, Duration::ZERO, -500_000);
+        let t3 = in_node(1, async { SimInstant::now() }).await;
+        assert_eq!(t2, t3);
+
+        let res = in_node(1, async {
+            SimRuntime::timeout(Duration::from_millis(100), std::future::pending::<()>()).await
+        })
+        .await;
+        assert!(res.is_err());
+        assert_eq!(Duration::from_millis(250), MockClock::now() - start);
+    });
+}
+
+/// Isolate the leader, whose clock runs with `leader_drift_ppm`, and the followers, whose clocks
+/// run with `follower_drift_ppm`, elect a new leader.
+///
+/// Returns whether the old leader served a lease read after the new leader is elected.
+fn lease_read_under_clock_skew(seed: u64, leader_drift_ppm: i64, follower_drift_ppm: i64) -> anyhow::Result<bool> {
+    Sim::run(seed, async {
+        let timeout = Some(Duration::from_secs(10));
+        let config = Arc::new(
+            Config {
+                enable_lease_read: true,
+                ..Default::default()
+            }
+            .validate()?,
+        );
+
+        let mut cluster = SimCluster::new(config);
+        for id in 0..3 {
+            // The offset does not affect the lease, which is measured by durations.
+            cluster.set_clock_skew(id, Duration::from_secs(id * 3600), 0);
+            cluster.add_node(id).await?;
+        }
+        cluster.raft(0).initialize(btreeset! {0, 1, 2}).await?;
+
+        let m = cluster.raft(0).wait(timeout).metrics(|m| m.current_leader.is_some(), "elect").await?;
+        let leader = m.current_leader.unwrap();
+        cluster.raft(leader).client_write(1).await?;
+
+        let followers = cluster.node_ids().into_iter().filter(|id| *id != leader).collect::<Vec<_>>();
+        cluster.set_clock_skew(leader, Duration::ZERO, leader_drift_ppm);
+        for id in followers.iter() {
+            cluster.set_clock_skew(*id, Duration::ZERO, follower_drift_ppm);
+        }
+
+        // Let the followers see a few heartbeats with the skewed clocks.
+        SimRuntime::sleep(Duration::from_millis(200)).await;
+        cluster.isolate(leader);
+
+        let mut stale_read = false;
+        let mut lease_expired = false;
+        let mut elected = false;
+
+        for _ in 0..1000 {
+            elected = elected
+                || followers.iter().any(|id| cluster.raft(*id).metrics().borrow_watched().state == ServerState::Leader);
+
+            let read =
+                SimRuntime::timeout(Duration::from_millis(100), cluster.raft(leader).ensure_linearizable()).await;
+            if matches!(read, Ok(Ok(_))) {
+                cluster.record(format!(
+                    "node {} serves a lease read, new leader elected: {}",
+                    leader, elected
+                ));
+                stale_read = stale_read || elected;
+            } else {
+                lease_expired = true;
+            }
+
+            if elected && lease_expired {
+                break;
+            }
+            SimRuntime::sleep(Duration::from_millis(5)).await;
+        }
+        assert!(
+            elected && lease_expired,
+            "seed: {}: a new leader is elected and the lease expires",
+            seed
+        );
+
+        // The old leader steps down once it sees the new leader.
+        cluster.restore(leader);
+        cluster.raft(leader).wait(timeout).state(ServerState::Follower, "old leader steps down").await?;
+
+        Ok(stale_read)
+    })
+}
+
+#[test]
+fn test_sim_lease_read_under_clock_skew() -> anyhow::Result<()> {
+    // The leader runs 5% slower and the followers 5% faster: the followers see the lease expire
+    // about 10% earlier, which is less than `lease_read_max_clock_drift`, 50 ms of 300 ms.
+    for seed in 0..20 {
+        let stale_read = lease_read_under_clock_skew(seed, -50_000, 50_000)?;
+        assert!(
+            !stale_read,
+            "seed: {}: no lease read after a new leader is elected",
+            seed
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_sim_lease_read_beyond_max_clock_drift() -> anyhow::Result<()> {
+    // The followers run 4 times as fast as the leader, far beyond `lease_read_max_clock_drift`: a
+    // follower elects after `election_timeout_max` plus an election timeout, i.e., at most 600 ms
+    // of its clock, 150 ms of the leader's, before the 250 ms lease of the old leader expires.
+    for seed in 0..20 {
+        let stale_read = lease_read_under_clock_skew(seed, 0, 3_000_000)?;
+        assert!(stale_read, "seed: {}: a stale lease read is served", seed);
+    }
+
+    Ok(())
+}
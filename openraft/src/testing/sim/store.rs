@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::io::Cursor;
+use std::ops::RangeBounds;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::entry::RaftEntry;
+use crate::storage::IOFlushed;
+use crate::storage::LogState;
+use crate::storage::RaftLogStorage;
+use crate::storage::RaftStateMachine;
+use crate::storage::Snapshot;
+use crate::storage::SnapshotMeta;
+use crate::testing::sim::SimConfig;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::AnyError;
+use crate::Entry;
+use crate::EntryPayload;
+use crate::LogIdOptionExt;
+use crate::OptionalSend;
+use crate::RaftLogReader;
+use crate::RaftSnapshotBuilder;
+use crate::StorageError;
+use crate::StoredMembership;
+
+type C = SimConfig;
+
+#[derive(Debug, Default)]
+struct LogData {
+    vote: Option<VoteOf<C>>,
+    committed: Option<LogIdOf<C>>,
+    purged: Option<LogIdOf<C>>,
+    logs: BTreeMap<u64, Entry<C>>,
+}
+
+/// An in-memory [`RaftLogStorage`] of a [`SimCluster`](super::SimCluster) node.
+#[derive(Debug, Clone, Default)]
+pub struct SimLogStore {
+    data: Arc<Mutex<LogData>>,
+}
+
+impl RaftLogReader<C> for SimLogStore {
+    async fn try_get_log_entries<RB: RangeBounds<u64> + Clone + Debug + OptionalSend>(
+        &mut self,
+        range: RB,
+    ) -> Result<Vec<Entry<C>>, StorageError<C>> {
+        let data = self.data.lock().unwrap();
+        Ok(data.logs.range(range).map(|(_, ent)| ent.clone()).collect())
+    }
+
+    async fn read_vote(&mut self) -> Result<Option<VoteOf<C>>, StorageError<C>> {
+        Ok(self.data.lock().unwrap().vote)
+    }
+}
+
+impl RaftLogStorage<C> for SimLogStore {
+    type LogReader = Self;
+
+    async fn get_log_state(&mut self) -> Result<LogState<C>, StorageError<C>> {
+        let data = self.data.lock().unwrap();
+        let last = data.logs.values().next_back().map(|ent| ent.log_id);
+
+        Ok(LogState {
+            last_purged_log_id: data.purged,
+            last_log_id: last.or(data.purged),
+        })
+    }
+
+    async fn get_log_reader(&mut self) -> Self::LogReader {
+        self.clone()
+    }
+
+    async fn save_vote(&mut self, vote: &VoteOf<C>) -> Result<(), StorageError<C>> {
+        self.data.lock().unwrap().vote = Some(*vote);
+        Ok(())
+    }
+
+    async fn save_committed(&mut self, committed: Option<LogIdOf<C>>) -> Result<(), StorageError<C>> {
+        self.data.lock().unwrap().committed = committed;
+        Ok(())
+    }
+
+    async fn read_committed(&mut self) -> Result<Option<LogIdOf<C>>, StorageError<C>> {
+        Ok(self.data.lock().unwrap().committed)
+    }
+
+    async fn append<I>(&mut self, entries: I, callback: IOFlushed<C>) -> Result<(), StorageError<C>>
+    where
+        I: IntoIterator<Item = Entry<C>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        {
+            let mut data = self.data.lock().unwrap();
+            for entry in entries {
+                data.logs.insert(entry.index(), entry);
+            }
+        }
+        callback.io_completed(Ok(()));
+        Ok(())
+    }
+
+    async fn truncate(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        self.data.lock().unwrap().logs.split_off(&log_id.index);
+        Ok(())
+    }
+
+    async fn purge(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>> {
+        let mut data = self.data.lock().unwrap();
+        data.logs = data.logs.split_off(&(log_id.index + 1));
+        data.purged = Some(log_id);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct StateMachineData {
+    last_applied: Option<LogIdOf<C>>,
+    last_membership: StoredMembership<C>,
+
+    /// The application data of every applied normal entry, in log order.
+    values: Vec<u64>,
+
+    snapshot: Option<(SnapshotMeta<C>, Vec<u8>)>,
+    snapshot_idx: u64,
+}
+
+/// An in-memory [`RaftStateMachine`] of a [`SimCluster`](super::SimCluster) node.
+///
+/// The state is the list of the values of the applied normal entries.
+/// A value is applied with the number of values applied so far as the response.
+#[derive(Debug, Clone, Default)]
+pub struct SimStateMachine {
+    data: Arc<Mutex<StateMachineData>>,
+}
+
+impl SimStateMachine {
+    /// Return the values that have been applied.
+    pub fn values(&self) -> Vec<u64> {
+        self.data.lock().unwrap().values.clone()
+    }
+
+    fn encode(values: &[u64]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Vec<u64>, AnyError> {
+        if bytes.len() % 8 != 0 {
+            return Err(AnyError::error(format!("invalid snapshot size: {}", bytes.len())));
+        }
+        Ok(bytes.chunks_exact(8).map(|b| u64::from_le_bytes(b.try_into().unwrap())).collect())
+    }
+}
+
+impl RaftSnapshotBuilder<C> for SimStateMachine {
+    async fn build_snapshot(&mut self) -> Result<Snapshot<C>, StorageError<C>> {
+        let mut data = self.data.lock().unwrap();
+        data.snapshot_idx += 1;
+
+        let meta = SnapshotMeta::new(
+            data.last_applied,
+            data.last_membership.clone(),
+            format!(
+                "{}-{}",
+                data.last_applied.index().unwrap_or_default(),
+                data.snapshot_idx
+            ),
+        );
+        let bytes = Self::encode(&data.values);
+        data.snapshot = Some((meta.clone(), bytes.clone()));
+
+        Ok(Snapshot {
+            meta,
+            snapshot: Cursor::new(bytes),
+        })
+    }
+}
+
+impl RaftStateMachine<C> for SimStateMachine {
+    type SnapshotBuilder = Self;
+
+    async fn applied_state(&mut self) -> Result<(Option<LogIdOf<C>>, StoredMembership<C>), StorageError<C>> {
+        let data = self.data.lock().unwrap();
+        Ok((data.last_applied, data.last_membership.clone()))
+    }
+
+    async fn apply<I>(&mut self, entries: I) -> Result<Vec<u64>, StorageError<C>>
+    where
+        I: IntoIterator<Item = Entry<C>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let mut data = self.data.lock().unwrap();
+        let mut res = Vec::new();
+
+        for entry in entries {
+            data.last_applied = Some(entry.log_id);
+
+            match entry.payload {
+                EntryPayload::Blank => {}
+                EntryPayload::Normal(v) => data.values.push(v),
+                EntryPayload::Membership(m) => data.last_membership = StoredMembership::new(Some(entry.log_id), m),
+            }
+            res.push(data.values.len() as u64);
+        }
+        Ok(res)
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        self.clone()
+    }
+
+    async fn begin_receiving_snapshot(&mut self) -> Result<Cursor<Vec<u8>>, StorageError<C>> {
+        Ok(Cursor::new(Vec::new()))
+    }
+
+    async fn install_snapshot(
+        &mut self,
+        meta: &SnapshotMeta<C>,
+        snapshot: Cursor<Vec<u8>>,
+    ) -> Result<(), StorageError<C>> {
+        let bytes = snapshot.into_inner();
+        let values = Self::decode(&bytes).map_err(|e| StorageError::read_snapshot(Some(meta.signature()), e))?;
+
+        let mut data = self.data.lock().unwrap();
+        data.last_applied = meta.last_log_id;
+        data.last_membership = meta.last_membership.clone();
+        data.values = values;
+        data.snapshot = Some((meta.clone(), bytes));
+        Ok(())
+    }
+
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<C>>, StorageError<C>> {
+        let data = self.data.lock().unwrap();
+        Ok(data.snapshot.as_ref().map(|(meta, bytes)| Snapshot {
+            meta: meta.clone(),
+            snapshot: Cursor::new(bytes.clone()),
+        }))
+    }
+}
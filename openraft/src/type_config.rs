@@ -13,6 +13,7 @@ pub use async_runtime::MpscUnbounded;
 pub use async_runtime::OneshotSender;
 pub use util::TypeConfigExt;
 
+use crate::entry::EntryCodec;
 use crate::entry::RaftEntry;
 use crate::raft::responder::Responder;
 use crate::vote::raft_vote::RaftVote;
@@ -80,6 +81,17 @@ pub trait RaftTypeConfig:
     type Term: RaftTerm;
 
     /// A Leader identifier in a cluster.
+    ///
+    /// It decides how leaders are ordered, i.e., whether a term can have more than one leader:
+    /// - [`leader_id_adv::LeaderId`] (the default): ordered by `(term, node_id)`, more than one
+    ///   leader can be elected in a term, which reduces election conflicts.
+    /// - [`leader_id_std::LeaderId`]: the standard Raft, only one leader can be elected in a term.
+    ///   Use it to interoperate with other standard Raft implementations.
+    ///
+    /// See: [Leader-id in Advanced mode and Standard mode](crate::docs::data::leader_id).
+    ///
+    /// [`leader_id_adv::LeaderId`]: crate::impls::leader_id_adv::LeaderId
+    /// [`leader_id_std::LeaderId`]: crate::impls::leader_id_std::LeaderId
     type LeaderId: RaftLeaderId<Self>;
 
     /// Raft vote type.
@@ -90,6 +102,15 @@ pub trait RaftTypeConfig:
     /// Raft log entry, which can be built from an AppData.
     type Entry: RaftEntry<Self>;
 
+    /// Converts log entries to and from the form sent by replication.
+    ///
+    /// The default [`PassThroughCodec`] sends an entry as it is.
+    /// Use another [`EntryCodec`] if the bytes sent over the network differ from the in-memory
+    /// entry, e.g., to send a pre-serialized application payload verbatim.
+    ///
+    /// [`PassThroughCodec`]: crate::impls::PassThroughCodec
+    type EntryCodec: EntryCodec<Self>;
+
     /// Snapshot data for exposing a snapshot for reading & writing.
     ///
     /// See the [storage chapter of the guide][sto] for details on log compaction / snapshotting.
@@ -121,6 +142,7 @@ pub mod alias {
     use crate::async_runtime::Mpsc;
     use crate::async_runtime::MpscUnbounded;
     use crate::async_runtime::Oneshot;
+    use crate::entry::EntryCodec;
     use crate::raft::responder::Responder;
     use crate::type_config::AsyncRuntime;
     use crate::vote::RaftLeaderId;
@@ -138,6 +160,9 @@ pub mod alias {
     pub type LeaderIdOf<C> = <C as RaftTypeConfig>::LeaderId;
     pub type VoteOf<C> = <C as RaftTypeConfig>::Vote;
     pub type EntryOf<C> = <C as RaftTypeConfig>::Entry;
+    pub type EntryCodecOf<C> = <C as RaftTypeConfig>::EntryCodec;
+    pub type EncodedEntryOf<C> = <EntryCodecOf<C> as EntryCodec<C>>::Encoded;
+    pub type EntryCodecErrorOf<C> = <EntryCodecOf<C> as EntryCodec<C>>::Error;
     pub type SnapshotDataOf<C> = <C as RaftTypeConfig>::SnapshotData;
     pub type AsyncRuntimeOf<C> = <C as RaftTypeConfig>::AsyncRuntime;
     pub type ResponderOf<C> = <C as RaftTypeConfig>::Responder;
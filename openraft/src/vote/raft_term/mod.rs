@@ -13,6 +13,14 @@ use crate::base::OptionalFeatures;
 /// such as old leaders. It must be totally ordered and monotonically increasing.
 ///
 /// Common implementations are provided for standard integer types like `u64`, `i64` etc.
+///
+/// A term does not have to be `u64`: to share an on-disk format with another Raft
+/// implementation, it can be a narrower integer such as `u32`, or a struct that embeds more
+/// information, such as a cluster epoch, ordered by `(epoch, term)`.
+/// How a term is combined with a node id to form a leader id is decided by
+/// [`RaftTypeConfig::LeaderId`].
+///
+/// [`RaftTypeConfig::LeaderId`]: crate::RaftTypeConfig::LeaderId
 #[since(version = "0.10.0")]
 pub trait RaftTerm
 where Self: OptionalFeatures + Ord + Debug + Display + Copy + Default + 'static
@@ -0,0 +1,586 @@
+//! This crate provides an [`AsyncStdRuntime`] type, which has [`AsyncRuntime`]
+//! implemented so that you can use Openraft with [async-std](async_std).
+//!
+//! ```ignore
+//! pub struct TypeConfig {}
+//!
+//! impl openraft::RaftTypeConfig for TypeConfig {
+//!     // Other type are omitted
+//!
+//!     type AsyncRuntime = openraft_rt_async_std::AsyncStdRuntime;
+//! }
+//! ```
+//!
+//! # NOTE
+//!
+//! 1. For the Openraft dependency used with this crate, you can disable the `default` feature as
+//!    you don't need the built-in Tokio runtime.
+//! 2. Timers are built upon [`async-io`](async_io), which async-std itself uses.
+//! 3. Even though this crate allows you to use async-std, it still uses the synchronization
+//!    primitives from Tokio, i.e., `Mpsc`, `MpscUnbounded`, `Watch`, `Oneshot` and `Mutex`. They do
+//!    not depend on the Tokio runtime: no Tokio runtime is started.
+
+use std::future::Future;
+use std::time::Duration;
+
+use async_io::Timer;
+use openraft::AsyncRuntime;
+use openraft::OptionalSend;
+
+/// [`AsyncRuntime`] implementation for async-std.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AsyncStdRuntime;
+
+impl AsyncRuntime for AsyncStdRuntime {
+    // A panic in an async-std task is propagated to the joiner, joining never returns an error.
+    type JoinError = openraft::error::Infallible;
+    type JoinHandle<T: OptionalSend + 'static> = async_std::task::JoinHandle<Result<T, Self::JoinError>>;
+    type Sleep = timer_mod::AsyncIoSleep;
+    type Instant = instant_mod::StdInstant;
+    type TimeoutError = timer_mod::Elapsed;
+    type Timeout<R, T: Future<Output = R> + OptionalSend> = timer_mod::AsyncIoTimeout<T>;
+    type ThreadLocalRng = rand::rngs::ThreadRng;
+
+    #[inline]
+    fn spawn<T>(future: T) -> Self::JoinHandle<T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        async_std::task::spawn(async move { Ok(future.await) })
+    }
+
+    #[inline]
+    fn sleep(duration: Duration) -> Self::Sleep {
+        timer_mod::AsyncIoSleep(Timer::after(duration))
+    }
+
+    #[inline]
+    fn sleep_until(deadline: Self::Instant) -> Self::Sleep {
+        timer_mod::AsyncIoSleep(Timer::at(deadline.0))
+    }
+
+    #[inline]
+    fn timeout<R, F: Future<Output = R> + OptionalSend>(duration: Duration, future: F) -> Self::Timeout<R, F> {
+        timer_mod::AsyncIoTimeout {
+            future,
+            timer: Timer::after(duration),
+        }
+    }
+
+    #[inline]
+    fn timeout_at<R, F: Future<Output = R> + OptionalSend>(deadline: Self::Instant, future: F) -> Self::Timeout<R, F> {
+        timer_mod::AsyncIoTimeout {
+            future,
+            timer: Timer::at(deadline.0),
+        }
+    }
+
+    #[inline]
+    fn is_panic(_join_error: &Self::JoinError) -> bool {
+        // Given that joining a task will never fail, i.e., `Self::JoinError`
+        // will never be constructed, and it is impossible to construct an
+        // enum like `Infallible`, this function could never be invoked.
+        unreachable!("unreachable since argument `join_error` could never be constructed")
+    }
+
+    #[inline]
+    fn thread_rng() -> Self::ThreadLocalRng {
+        rand::thread_rng()
+    }
+
+    type Mpsc = mpsc_mod::TokioMpsc;
+    type MpscUnbounded = mpsc_unbounded_mod::TokioMpscUnbounded;
+    type Watch = watch_mod::TokioWatch;
+    type Oneshot = oneshot_mod::TokioOneshot;
+    type Mutex<T: OptionalSend + 'static> = mutex_mod::TokioMutex<T>;
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod instant_mod {
+    //! Instant channel wrapper type and its trait impl.
+
+    use std::ops::Add;
+    use std::ops::AddAssign;
+    use std::ops::Sub;
+    use std::ops::SubAssign;
+    use std::time::Duration;
+
+    use openraft::instant;
+
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct StdInstant(pub(crate) std::time::Instant);
+
+    impl Add<Duration> for StdInstant {
+        type Output = Self;
+
+        #[inline]
+        fn add(self, rhs: Duration) -> Self::Output {
+            Self(self.0.add(rhs))
+        }
+    }
+
+    impl AddAssign<Duration> for StdInstant {
+        #[inline]
+        fn add_assign(&mut self, rhs: Duration) {
+            self.0.add_assign(rhs)
+        }
+    }
+
+    impl Sub<Duration> for StdInstant {
+        type Output = Self;
+
+        #[inline]
+        fn sub(self, rhs: Duration) -> Self::Output {
+            Self(self.0.sub(rhs))
+        }
+    }
+
+    impl Sub<Self> for StdInstant {
+        type Output = Duration;
+
+        #[inline]
+        fn sub(self, rhs: Self) -> Self::Output {
+            self.0.sub(rhs.0)
+        }
+    }
+
+    impl SubAssign<Duration> for StdInstant {
+        #[inline]
+        fn sub_assign(&mut self, rhs: Duration) {
+            self.0.sub_assign(rhs)
+        }
+    }
+
+    impl instant::Instant for StdInstant {
+        #[inline]
+        fn now() -> Self {
+            let inner = std::time::Instant::now();
+            Self(inner)
+        }
+
+        #[inline]
+        fn elapsed(&self) -> Duration {
+            self.0.elapsed()
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod timer_mod {
+    //! Sleep and timeout types built upon `async-io` timers, and their trait impl.
+
+    use std::error::Error;
+    use std::fmt;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::Context;
+    use std::task::Poll;
+
+    use async_io::Timer;
+
+    /// A future that resolves when the timer fires.
+    pub struct AsyncIoSleep(pub(crate) Timer);
+
+    impl Future for AsyncIoSleep {
+        type Output = ();
+
+        #[inline]
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut self.0).poll(cx).map(|_| ())
+        }
+    }
+
+    pin_project_lite::pin_project! {
+        /// A future that resolves to [`Elapsed`] if the inner future does not finish before the
+        /// timer fires.
+        pub struct AsyncIoTimeout<F> {
+            #[pin]
+            pub(crate) future: F,
+            pub(crate) timer: Timer,
+        }
+    }
+
+    impl<F> Future for AsyncIoTimeout<F>
+    where F: Future
+    {
+        type Output = Result<F::Output, Elapsed>;
+
+        #[inline]
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+
+            if let Poll::Ready(output) = this.future.poll(cx) {
+                return Poll::Ready(Ok(output));
+            }
+
+            match Pin::new(this.timer).poll(cx) {
+                Poll::Ready(_) => Poll::Ready(Err(Elapsed(()))),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    /// The error returned by [`AsyncIoTimeout`] when the deadline has elapsed.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Elapsed(());
+
+    impl fmt::Display for Elapsed {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "deadline has elapsed")
+        }
+    }
+
+    impl Error for Elapsed {}
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod oneshot_mod {
+    //! Oneshot channel wrapper types and their trait impl.
+
+    use openraft::type_config::async_runtime::oneshot;
+    use openraft::OptionalSend;
+    use tokio::sync::oneshot as tokio_oneshot;
+
+    pub struct TokioOneshot;
+
+    pub struct TokioOneshotSender<T>(tokio_oneshot::Sender<T>);
+
+    impl oneshot::Oneshot for TokioOneshot {
+        type Sender<T: OptionalSend> = TokioOneshotSender<T>;
+        type Receiver<T: OptionalSend> = tokio_oneshot::Receiver<T>;
+        type ReceiverError = tokio_oneshot::error::RecvError;
+
+        #[inline]
+        fn channel<T>() -> (Self::Sender<T>, Self::Receiver<T>)
+        where T: OptionalSend {
+            let (tx, rx) = tokio_oneshot::channel();
+            let tx_wrapper = TokioOneshotSender(tx);
+
+            (tx_wrapper, rx)
+        }
+    }
+
+    impl<T> oneshot::OneshotSender<T> for TokioOneshotSender<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn send(self, t: T) -> Result<(), T> {
+            self.0.send(t)
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod mpsc_mod {
+    //! MPSC channel wrapper types and their trait impl.
+
+    use std::future::Future;
+
+    use futures::TryFutureExt;
+    use openraft::async_runtime::Mpsc;
+    use openraft::async_runtime::MpscReceiver;
+    use openraft::async_runtime::MpscSender;
+    use openraft::async_runtime::MpscWeakSender;
+    use openraft::async_runtime::SendError;
+    use openraft::async_runtime::TryRecvError;
+    use openraft::OptionalSend;
+    use tokio::sync::mpsc as tokio_mpsc;
+
+    pub struct TokioMpsc;
+
+    pub struct TokioMpscSender<T>(tokio_mpsc::Sender<T>);
+
+    impl<T> Clone for TokioMpscSender<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    pub struct TokioMpscReceiver<T>(tokio_mpsc::Receiver<T>);
+
+    pub struct TokioMpscWeakSender<T>(tokio_mpsc::WeakSender<T>);
+
+    impl<T> Clone for TokioMpscWeakSender<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    impl Mpsc for TokioMpsc {
+        type Sender<T: OptionalSend> = TokioMpscSender<T>;
+        type Receiver<T: OptionalSend> = TokioMpscReceiver<T>;
+        type WeakSender<T: OptionalSend> = TokioMpscWeakSender<T>;
+
+        #[inline]
+        fn channel<T: OptionalSend>(buffer: usize) -> (Self::Sender<T>, Self::Receiver<T>) {
+            let (tx, rx) = tokio_mpsc::channel(buffer);
+            let tx_wrapper = TokioMpscSender(tx);
+            let rx_wrapper = TokioMpscReceiver(rx);
+
+            (tx_wrapper, rx_wrapper)
+        }
+    }
+
+    impl<T> MpscSender<TokioMpsc, T> for TokioMpscSender<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn send(&self, msg: T) -> impl Future<Output = Result<(), SendError<T>>> {
+            self.0.send(msg).map_err(|e| SendError(e.0))
+        }
+
+        #[inline]
+        fn downgrade(&self) -> <TokioMpsc as Mpsc>::WeakSender<T> {
+            let inner = self.0.downgrade();
+            TokioMpscWeakSender(inner)
+        }
+    }
+
+    impl<T> MpscReceiver<T> for TokioMpscReceiver<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn recv(&mut self) -> impl Future<Output = Option<T>> {
+            self.0.recv()
+        }
+
+        #[inline]
+        fn try_recv(&mut self) -> Result<T, TryRecvError> {
+            self.0.try_recv().map_err(|e| match e {
+                tokio_mpsc::error::TryRecvError::Empty => TryRecvError::Empty,
+                tokio_mpsc::error::TryRecvError::Disconnected => TryRecvError::Disconnected,
+            })
+        }
+    }
+
+    impl<T> MpscWeakSender<TokioMpsc, T> for TokioMpscWeakSender<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn upgrade(&self) -> Option<<TokioMpsc as Mpsc>::Sender<T>> {
+            self.0.upgrade().map(TokioMpscSender)
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod mpsc_unbounded_mod {
+    //! Unbounded MPSC channel wrapper types and their trait impl.
+
+    use openraft::type_config::async_runtime::mpsc_unbounded;
+    use openraft::OptionalSend;
+    use tokio::sync::mpsc as tokio_mpsc;
+
+    pub struct TokioMpscUnbounded;
+
+    pub struct TokioMpscUnboundedSender<T>(tokio_mpsc::UnboundedSender<T>);
+
+    impl<T> Clone for TokioMpscUnboundedSender<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    pub struct TokioMpscUnboundedReceiver<T>(tokio_mpsc::UnboundedReceiver<T>);
+
+    pub struct TokioMpscUnboundedWeakSender<T>(tokio_mpsc::WeakUnboundedSender<T>);
+
+    impl<T> Clone for TokioMpscUnboundedWeakSender<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    impl mpsc_unbounded::MpscUnbounded for TokioMpscUnbounded {
+        type Sender<T: OptionalSend> = TokioMpscUnboundedSender<T>;
+        type Receiver<T: OptionalSend> = TokioMpscUnboundedReceiver<T>;
+        type WeakSender<T: OptionalSend> = TokioMpscUnboundedWeakSender<T>;
+
+        #[inline]
+        fn channel<T: OptionalSend>() -> (Self::Sender<T>, Self::Receiver<T>) {
+            let (tx, rx) = tokio_mpsc::unbounded_channel();
+            let tx_wrapper = TokioMpscUnboundedSender(tx);
+            let rx_wrapper = TokioMpscUnboundedReceiver(rx);
+
+            (tx_wrapper, rx_wrapper)
+        }
+    }
+
+    impl<T> mpsc_unbounded::MpscUnboundedSender<TokioMpscUnbounded, T> for TokioMpscUnboundedSender<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn send(&self, msg: T) -> Result<(), mpsc_unbounded::SendError<T>> {
+            self.0.send(msg).map_err(|e| mpsc_unbounded::SendError(e.0))
+        }
+
+        #[inline]
+        fn downgrade(&self) -> <TokioMpscUnbounded as mpsc_unbounded::MpscUnbounded>::WeakSender<T> {
+            let inner = self.0.downgrade();
+            TokioMpscUnboundedWeakSender(inner)
+        }
+    }
+
+    impl<T> mpsc_unbounded::MpscUnboundedReceiver<T> for TokioMpscUnboundedReceiver<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        async fn recv(&mut self) -> Option<T> {
+            self.0.recv().await
+        }
+
+        #[inline]
+        fn try_recv(&mut self) -> Result<T, mpsc_unbounded::TryRecvError> {
+            self.0.try_recv().map_err(|e| match e {
+                tokio_mpsc::error::TryRecvError::Empty => mpsc_unbounded::TryRecvError::Empty,
+                tokio_mpsc::error::TryRecvError::Disconnected => mpsc_unbounded::TryRecvError::Disconnected,
+            })
+        }
+    }
+
+    impl<T> mpsc_unbounded::MpscUnboundedWeakSender<TokioMpscUnbounded, T> for TokioMpscUnboundedWeakSender<T>
+    where T: OptionalSend
+    {
+        #[inline]
+        fn upgrade(&self) -> Option<<TokioMpscUnbounded as mpsc_unbounded::MpscUnbounded>::Sender<T>> {
+            self.0.upgrade().map(TokioMpscUnboundedSender)
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod watch_mod {
+    //! Watch channel wrapper types and their trait impl.
+
+    use std::ops::Deref;
+
+    use openraft::async_runtime::watch::RecvError;
+    use openraft::async_runtime::watch::SendError;
+    use openraft::type_config::async_runtime::watch;
+    use openraft::OptionalSend;
+    use openraft::OptionalSync;
+    use tokio::sync::watch as tokio_watch;
+
+    pub struct TokioWatch;
+    pub struct TokioWatchSender<T>(tokio_watch::Sender<T>);
+    pub struct TokioWatchReceiver<T>(tokio_watch::Receiver<T>);
+    pub struct TokioWatchRef<'a, T>(tokio_watch::Ref<'a, T>);
+
+    impl watch::Watch for TokioWatch {
+        type Sender<T: OptionalSend + OptionalSync> = TokioWatchSender<T>;
+        type Receiver<T: OptionalSend + OptionalSync> = TokioWatchReceiver<T>;
+        type Ref<'a, T: OptionalSend + 'a> = TokioWatchRef<'a, T>;
+
+        #[inline]
+        fn channel<T: OptionalSend + OptionalSync>(init: T) -> (Self::Sender<T>, Self::Receiver<T>) {
+            let (tx, rx) = tokio_watch::channel(init);
+            let tx_wrapper = TokioWatchSender(tx);
+            let rx_wrapper = TokioWatchReceiver(rx);
+
+            (tx_wrapper, rx_wrapper)
+        }
+    }
+
+    impl<T> watch::WatchSender<TokioWatch, T> for TokioWatchSender<T>
+    where T: OptionalSend + OptionalSync
+    {
+        #[inline]
+        fn send(&self, value: T) -> Result<(), SendError<T>> {
+            self.0.send(value).map_err(|e| watch::SendError(e.0))
+        }
+
+        #[inline]
+        fn send_if_modified<F>(&self, modify: F) -> bool
+        where F: FnOnce(&mut T) -> bool {
+            self.0.send_if_modified(modify)
+        }
+
+        #[inline]
+        fn borrow_watched(&self) -> <TokioWatch as watch::Watch>::Ref<'_, T> {
+            let inner = self.0.borrow();
+            TokioWatchRef(inner)
+        }
+    }
+
+    impl<T> Clone for TokioWatchReceiver<T> {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    impl<T> watch::WatchReceiver<TokioWatch, T> for TokioWatchReceiver<T>
+    where T: OptionalSend + OptionalSync
+    {
+        #[inline]
+        async fn changed(&mut self) -> Result<(), RecvError> {
+            self.0.changed().await.map_err(|_| watch::RecvError(()))
+        }
+
+        #[inline]
+        fn borrow_watched(&self) -> <TokioWatch as watch::Watch>::Ref<'_, T> {
+            TokioWatchRef(self.0.borrow())
+        }
+    }
+
+    impl<'a, T> Deref for TokioWatchRef<'a, T> {
+        type Target = T;
+
+        #[inline]
+        fn deref(&self) -> &Self::Target {
+            self.0.deref()
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod mutex_mod {
+    //! Mutex wrapper type and its trait impl.
+
+    use std::future::Future;
+
+    use openraft::type_config::async_runtime::mutex;
+    use openraft::OptionalSend;
+
+    pub struct TokioMutex<T>(tokio::sync::Mutex<T>);
+
+    impl<T> mutex::Mutex<T> for TokioMutex<T>
+    where T: OptionalSend + 'static
+    {
+        type Guard<'a> = tokio::sync::MutexGuard<'a, T>;
+
+        #[inline]
+        fn new(value: T) -> Self {
+            TokioMutex(tokio::sync::Mutex::new(value))
+        }
+
+        #[inline]
+        fn lock(&self) -> impl Future<Output = Self::Guard<'_>> + OptionalSend {
+            self.0.lock()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openraft::testing::runtime::Suite;
+
+    use super::*;
+
+    #[test]
+    fn test_async_std_rt() {
+        async_std::task::block_on(Suite::<AsyncStdRuntime>::test_all());
+    }
+}
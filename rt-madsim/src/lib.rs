@@ -0,0 +1,232 @@
+//! This crate provides a [`MadsimRuntime`] type, which has [`AsyncRuntime`]
+//! implemented so that you can run Openraft on [madsim](madsim), a deterministic simulator.
+//!
+//! ```ignore
+//! pub struct TypeConfig {}
+//!
+//! impl openraft::RaftTypeConfig for TypeConfig {
+//!     // Other type are omitted
+//!
+//!     type AsyncRuntime = openraft_rt_madsim::MadsimRuntime;
+//! }
+//! ```
+//!
+//! # NOTE
+//!
+//! 1. This crate only builds in simulation mode, i.e., with `RUSTFLAGS="--cfg madsim"`, and a raft
+//!    node must run inside a madsim runtime, e.g., `madsim::runtime::Runtime::block_on()`. Tasks,
+//!    timers and random numbers are then driven by the seed of the runtime, thus a run is
+//!    reproduced by running with the same seed.
+//! 2. The clock is virtual: a timer fires as soon as no task is runnable, without actually
+//!    sleeping.
+//! 3. It uses the synchronization primitives of [`TokioRuntime`], i.e., `Mpsc`, `MpscUnbounded`,
+//!    `Watch`, `Oneshot` and `Mutex`. They do not depend on the Tokio runtime: no Tokio runtime is
+//!    started.
+
+#[cfg(not(madsim))]
+compile_error!("openraft-rt-madsim requires simulation mode: build with `RUSTFLAGS=\"--cfg madsim\"`");
+
+use std::future::Future;
+use std::time::Duration;
+
+use openraft::AsyncRuntime;
+use openraft::OptionalSend;
+use openraft::TokioRuntime;
+
+/// [`AsyncRuntime`] implementation for madsim.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MadsimRuntime;
+
+impl AsyncRuntime for MadsimRuntime {
+    type JoinError = madsim::task::JoinError;
+    type JoinHandle<T: OptionalSend + 'static> = madsim::task::JoinHandle<T>;
+    type Sleep = madsim::time::Sleep;
+    type Instant = instant_mod::StdInstant;
+    type TimeoutError = madsim::time::error::Elapsed;
+    type Timeout<R, T: Future<Output = R> + OptionalSend> = timer_mod::MadsimTimeout<T>;
+    type ThreadLocalRng = madsim::rand::GlobalRng;
+
+    #[inline]
+    fn spawn<T>(future: T) -> Self::JoinHandle<T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        madsim::task::spawn(future)
+    }
+
+    #[inline]
+    fn sleep(duration: Duration) -> Self::Sleep {
+        madsim::time::sleep(duration)
+    }
+
+    #[inline]
+    fn sleep_until(deadline: Self::Instant) -> Self::Sleep {
+        madsim::time::sleep_until(deadline.0)
+    }
+
+    #[inline]
+    fn timeout<R, F: Future<Output = R> + OptionalSend>(duration: Duration, future: F) -> Self::Timeout<R, F> {
+        timer_mod::MadsimTimeout {
+            future,
+            sleep: madsim::time::sleep(duration),
+        }
+    }
+
+    #[inline]
+    fn timeout_at<R, F: Future<Output = R> + OptionalSend>(deadline: Self::Instant, future: F) -> Self::Timeout<R, F> {
+        timer_mod::MadsimTimeout {
+            future,
+            sleep: madsim::time::sleep_until(deadline.0),
+        }
+    }
+
+    #[inline]
+    fn is_panic(join_error: &Self::JoinError) -> bool {
+        join_error.is_panic()
+    }
+
+    #[inline]
+    fn thread_rng() -> Self::ThreadLocalRng {
+        madsim::rand::thread_rng()
+    }
+
+    type Mpsc = <TokioRuntime as AsyncRuntime>::Mpsc;
+    type MpscUnbounded = <TokioRuntime as AsyncRuntime>::MpscUnbounded;
+    type Watch = <TokioRuntime as AsyncRuntime>::Watch;
+    type Oneshot = <TokioRuntime as AsyncRuntime>::Oneshot;
+    type Mutex<T: OptionalSend + 'static> = <TokioRuntime as AsyncRuntime>::Mutex<T>;
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod instant_mod {
+    //! Instant wrapper type and its trait impl.
+    //!
+    //! In simulation mode, `std::time::Instant::now()` returns the virtual time of madsim.
+
+    use std::ops::Add;
+    use std::ops::AddAssign;
+    use std::ops::Sub;
+    use std::ops::SubAssign;
+    use std::time::Duration;
+
+    use openraft::instant;
+
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+    pub struct StdInstant(pub(crate) std::time::Instant);
+
+    impl Add<Duration> for StdInstant {
+        type Output = Self;
+
+        #[inline]
+        fn add(self, rhs: Duration) -> Self::Output {
+            Self(self.0.add(rhs))
+        }
+    }
+
+    impl AddAssign<Duration> for StdInstant {
+        #[inline]
+        fn add_assign(&mut self, rhs: Duration) {
+            self.0.add_assign(rhs)
+        }
+    }
+
+    impl Sub<Duration> for StdInstant {
+        type Output = Self;
+
+        #[inline]
+        fn sub(self, rhs: Duration) -> Self::Output {
+            Self(self.0.sub(rhs))
+        }
+    }
+
+    impl Sub<Self> for StdInstant {
+        type Output = Duration;
+
+        #[inline]
+        fn sub(self, rhs: Self) -> Self::Output {
+            self.0.sub(rhs.0)
+        }
+    }
+
+    impl SubAssign<Duration> for StdInstant {
+        #[inline]
+        fn sub_assign(&mut self, rhs: Duration) {
+            self.0.sub_assign(rhs)
+        }
+    }
+
+    impl instant::Instant for StdInstant {
+        #[inline]
+        fn now() -> Self {
+            let inner = std::time::Instant::now();
+            Self(inner)
+        }
+
+        #[inline]
+        fn elapsed(&self) -> Duration {
+            self.0.elapsed()
+        }
+    }
+}
+
+// Put the wrapper types in a private module to make them `pub` but not
+// exposed to the user.
+mod timer_mod {
+    //! Timeout type built upon the madsim timer.
+    //!
+    //! `madsim::time::timeout()` returns an unnameable future that is not `Send`.
+
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::Context;
+    use std::task::Poll;
+
+    use madsim::time::error::Elapsed;
+    use madsim::time::Sleep;
+
+    pin_project_lite::pin_project! {
+        /// A future that resolves to [`Elapsed`] if the inner future does not finish before the
+        /// timer fires.
+        pub struct MadsimTimeout<F> {
+            #[pin]
+            pub(crate) future: F,
+            #[pin]
+            pub(crate) sleep: Sleep,
+        }
+    }
+
+    impl<F> Future for MadsimTimeout<F>
+    where F: Future
+    {
+        type Output = Result<F::Output, Elapsed>;
+
+        #[inline]
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+
+            if let Poll::Ready(output) = this.future.poll(cx) {
+                return Poll::Ready(Ok(output));
+            }
+
+            match this.sleep.poll(cx) {
+                Poll::Ready(_) => Poll::Ready(Err(Elapsed)),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openraft::testing::runtime::Suite;
+
+    use super::*;
+
+    #[test]
+    fn test_madsim_rt() {
+        let rt = madsim::runtime::Runtime::new();
+        rt.block_on(Suite::<MadsimRuntime>::test_all());
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use openraft::alias::LogIdOf;
+use openraft::error::ApplyError;
+use openraft::session::Dedup;
+use openraft::session::SessionTable;
+use openraft::Entry;
+use openraft::EntryPayload;
+use openraft::RaftTypeConfig;
+use openraft::StoredMembership;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::KvOp;
+use crate::KvRequest;
+use crate::KvResponse;
+
+/// A value and the time in milliseconds when it expires.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct Value {
+    value: String,
+    expire_at_ms: Option<u64>,
+}
+
+/// All data of the key-value state machine, which is also the content of a snapshot.
+///
+/// It is deterministic: replicas applying the same entries have the same data.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(bound = "")]
+pub struct KvData<C>
+where C: RaftTypeConfig
+{
+    pub last_applied: Option<LogIdOf<C>>,
+
+    pub last_membership: StoredMembership<C>,
+
+    /// The time of the state machine in milliseconds: the greatest `time_ms` of applied requests.
+    pub now_ms: u64,
+
+    kv: BTreeMap<String, Value>,
+
+    /// Keys with an expiration, ordered by the expiration time.
+    expirations: BTreeSet<(u64, String)>,
+
+    sessions: SessionTable<String, KvResponse>,
+}
+
+impl<C> Default for KvData<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            last_applied: None,
+            last_membership: StoredMembership::default(),
+            now_ms: 0,
+            kv: BTreeMap::new(),
+            expirations: BTreeSet::new(),
+            sessions: SessionTable::new(),
+        }
+    }
+}
+
+impl<C> KvData<C>
+where C: RaftTypeConfig
+{
+    /// Get the value of `key` if it exists and is not expired at the state machine time.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        let v = self.kv.get(key)?;
+        if v.expire_at_ms.is_some_and(|t| t <= self.now_ms) {
+            return None;
+        }
+        Some(&v.value)
+    }
+
+    /// The number of keys, including expired keys that are not yet removed.
+    pub fn len(&self) -> usize {
+        self.kv.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kv.is_empty()
+    }
+
+    /// The client sessions and the response of the last write of every client.
+    pub fn sessions(&self) -> &SessionTable<String, KvResponse> {
+        &self.sessions
+    }
+}
+
+impl<C> KvData<C>
+where C: RaftTypeConfig<D = KvRequest>
+{
+    /// Apply an entry and return the response for it.
+    ///
+    /// A write with a session that is older than the last write of the client is rejected with an
+    /// [`ApplyError`]. The entry is still applied and `last_applied` is updated.
+    pub fn apply(&mut self, entry: &Entry<C>) -> Result<KvResponse, ApplyError<C>> {
+        self.last_applied = Some(entry.log_id.clone());
+
+        match &entry.payload {
+            EntryPayload::Blank => Ok(KvResponse::default()),
+            EntryPayload::Normal(req) => self.apply_request(&entry.log_id, req),
+            EntryPayload::Membership(mem) => {
+                self.last_membership = StoredMembership::new(Some(entry.log_id.clone()), mem.clone());
+                Ok(KvResponse::default())
+            }
+        }
+    }
+
+    fn apply_request(&mut self, log_id: &LogIdOf<C>, req: &KvRequest) -> Result<KvResponse, ApplyError<C>> {
+        self.now_ms = std::cmp::max(self.now_ms, req.time_ms);
+        self.remove_expired();
+
+        let Some((client_id, serial)) = &req.session else {
+            return Ok(self.apply_op(&req.op));
+        };
+
+        let (sessions, mut state) = self.split_sessions();
+        let res = sessions.apply(client_id.clone(), *serial, || state.apply_op(&req.op));
+
+        match res {
+            Dedup::Applied(resp) => Ok(resp),
+            Dedup::Duplicate(resp) => {
+                tracing::debug!("duplicated write: {}, respond with the cached response", req);
+                Ok(resp)
+            }
+            Dedup::Stale { last_serial } => Err(ApplyError::with_message(
+                log_id.clone(),
+                format!(
+                    "stale write of client {}: serial {} is older than the last serial {}",
+                    client_id, serial, last_serial
+                ),
+            )),
+        }
+    }
+
+    fn apply_op(&mut self, op: &KvOp) -> KvResponse {
+        self.split_sessions().1.apply_op(op)
+    }
+
+    /// Remove the keys that are expired at the state machine time.
+    fn remove_expired(&mut self) {
+        while let Some((t, key)) = self.expirations.first() {
+            if *t > self.now_ms {
+                break;
+            }
+            self.kv.remove(key);
+            self.expirations.pop_first();
+        }
+    }
+
+    /// Borrow the session table and the key-value data separately.
+    fn split_sessions(&mut self) -> (&mut SessionTable<String, KvResponse>, KvState<'_>) {
+        let state = KvState {
+            now_ms: self.now_ms,
+            kv: &mut self.kv,
+            expirations: &mut self.expirations,
+        };
+        (&mut self.sessions, state)
+    }
+}
+
+/// Mutable access to the key-value data without the sessions.
+struct KvState<'a> {
+    now_ms: u64,
+    kv: &'a mut BTreeMap<String, Value>,
+    expirations: &'a mut BTreeSet<(u64, String)>,
+}
+
+impl KvState<'_> {
+    fn apply_op(&mut self, op: &KvOp) -> KvResponse {
+        let (key, new_value) = match op {
+            KvOp::Set { key, value, ttl_ms } => {
+                let v = Value {
+                    value: value.clone(),
+                    expire_at_ms: ttl_ms.map(|ttl| self.now_ms + ttl),
+                };
+                (key, Some(v))
+            }
+            KvOp::Delete { key } => (key, None),
+        };
+
+        let prev = self.kv.remove(key);
+        if let Some(Value {
+            expire_at_ms: Some(t), ..
+        }) = &prev
+        {
+            self.expirations.remove(&(*t, key.clone()));
+        }
+
+        if let Some(v) = new_value {
+            if let Some(t) = v.expire_at_ms {
+                self.expirations.insert((t, key.clone()));
+            }
+            self.kv.insert(key.clone(), v);
+        }
+
+        let prev = prev.filter(|p| p.expire_at_ms.is_none_or(|t| t > self.now_ms)).map(|p| p.value);
+
+        KvResponse { prev }
+    }
+}
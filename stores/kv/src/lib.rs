@@ -0,0 +1,36 @@
+//! A reference key-value state machine for openraft.
+//!
+//! [`KvStateMachine`] implements [`RaftStateMachine`] for any type config that uses
+//! [`KvRequest`] as application data and [`KvResponse`] as response:
+//!
+//! ```ignore
+//! openraft::declare_raft_types!(
+//!     pub TypeConfig:
+//!         D = openraft_kv::KvRequest,
+//!         R = openraft_kv::KvResponse,
+//! );
+//!
+//! let sm = openraft_kv::KvStateMachine::<TypeConfig>::new();
+//! let raft = openraft::Raft::new(1, config, network, log_store, sm.clone()).await?;
+//!
+//! raft.client_write(KvRequest::set("foo", "bar").with_session("client-1", 1)).await?;
+//! assert_eq!(Some("bar".to_string()), sm.get("foo").await);
+//! ```
+//!
+//! [`RaftStateMachine`]: openraft::storage::RaftStateMachine
+
+#![deny(unused_crate_dependencies)]
+#![deny(unused_qualifications)]
+
+#[cfg(test)]
+mod test;
+
+mod data;
+mod request;
+mod state_machine;
+
+pub use data::KvData;
+pub use request::KvOp;
+pub use request::KvRequest;
+pub use request::KvResponse;
+pub use state_machine::KvStateMachine;
@@ -0,0 +1,110 @@
+use std::fmt;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An operation on the key-value store.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum KvOp {
+    /// Set `key` to `value`, which expires after `ttl_ms` milliseconds if it is not `None`.
+    Set {
+        key: String,
+        value: String,
+        ttl_ms: Option<u64>,
+    },
+
+    /// Delete `key`.
+    Delete { key: String },
+}
+
+/// A write request to the key-value store.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct KvRequest {
+    /// Unix timestamp in milliseconds when the request is proposed.
+    ///
+    /// The state machine does not read the local clock. Its time is the greatest timestamp of the
+    /// applied requests, which decides when a key expires.
+    pub time_ms: u64,
+
+    /// The client id and the serial of this write in the client session, if the write should be
+    /// applied at most once.
+    pub session: Option<(String, u64)>,
+
+    pub op: KvOp,
+}
+
+impl KvRequest {
+    /// Create a request of `op` stamped with the current time.
+    pub fn new(op: KvOp) -> Self {
+        let time_ms = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or_default();
+
+        Self {
+            time_ms,
+            session: None,
+            op,
+        }
+    }
+
+    pub fn set(key: impl ToString, value: impl ToString) -> Self {
+        Self::new(KvOp::Set {
+            key: key.to_string(),
+            value: value.to_string(),
+            ttl_ms: None,
+        })
+    }
+
+    /// Set a key that expires after `ttl_ms` milliseconds since the proposing time.
+    pub fn set_with_ttl(key: impl ToString, value: impl ToString, ttl_ms: u64) -> Self {
+        Self::new(KvOp::Set {
+            key: key.to_string(),
+            value: value.to_string(),
+            ttl_ms: Some(ttl_ms),
+        })
+    }
+
+    pub fn delete(key: impl ToString) -> Self {
+        Self::new(KvOp::Delete { key: key.to_string() })
+    }
+
+    /// Apply this request at most once, as the `serial`-th write of `client_id`.
+    ///
+    /// A client must use increasing serials for its writes, and may only retry its last write.
+    pub fn with_session(mut self, client_id: impl ToString, serial: u64) -> Self {
+        self.session = Some((client_id.to_string(), serial));
+        self
+    }
+
+    /// Override the proposing time.
+    pub fn with_time_ms(mut self, time_ms: u64) -> Self {
+        self.time_ms = time_ms;
+        self
+    }
+}
+
+impl fmt::Display for KvRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.op {
+            KvOp::Set { key, value, ttl_ms } => {
+                write!(f, "set {}={}", key, value)?;
+                if let Some(ttl) = ttl_ms {
+                    write!(f, " ttl={}ms", ttl)?;
+                }
+            }
+            KvOp::Delete { key } => write!(f, "delete {}", key)?,
+        }
+
+        if let Some((client_id, serial)) = &self.session {
+            write!(f, " session={}:{}", client_id, serial)?;
+        }
+        write!(f, " at {}ms", self.time_ms)
+    }
+}
+
+/// The response to a [`KvRequest`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct KvResponse {
+    /// The value of the key before the write, `None` if it does not exist or is expired.
+    pub prev: Option<String>,
+}
@@ -0,0 +1,217 @@
+use std::io::Cursor;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use openraft::alias::LogIdOf;
+use openraft::error::ApplyError;
+use openraft::storage::RaftStateMachine;
+use openraft::storage::Snapshot;
+use openraft::Entry;
+use openraft::OptionalSend;
+use openraft::RaftSnapshotBuilder;
+use openraft::RaftTypeConfig;
+use openraft::SnapshotMeta;
+use openraft::StorageError;
+use openraft::StoredMembership;
+use tokio::sync::RwLock;
+
+use crate::KvData;
+use crate::KvRequest;
+use crate::KvResponse;
+
+/// A snapshot kept by the state machine.
+#[derive(Debug)]
+struct StoredSnapshot<C>
+where C: RaftTypeConfig
+{
+    meta: SnapshotMeta<C>,
+
+    /// The serialized [`KvData`].
+    data: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct Inner<C>
+where C: RaftTypeConfig
+{
+    data: RwLock<KvData<C>>,
+
+    /// Used in identifier for snapshot.
+    snapshot_idx: AtomicU64,
+
+    /// The last built or installed snapshot.
+    current_snapshot: RwLock<Option<StoredSnapshot<C>>>,
+}
+
+/// A key-value state machine that keeps its data in memory and persists it by snapshots.
+///
+/// It is a cheap handle that can be cloned: keep a clone to read the data after passing one to
+/// [`Raft::new()`](openraft::Raft::new).
+#[derive(Debug)]
+pub struct KvStateMachine<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Inner<C>>,
+}
+
+impl<C> Clone for KvStateMachine<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<C> Default for KvStateMachine<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> KvStateMachine<C>
+where C: RaftTypeConfig
+{
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                data: RwLock::new(KvData::default()),
+                snapshot_idx: AtomicU64::new(0),
+                current_snapshot: RwLock::new(None),
+            }),
+        }
+    }
+
+    /// Get the value of `key` on this replica.
+    ///
+    /// The value is read from the local state machine, which may fall behind the leader. Call
+    /// [`Raft::ensure_linearizable()`](openraft::Raft::ensure_linearizable) before reading for a
+    /// linearizable read.
+    pub async fn get(&self, key: &str) -> Option<String> {
+        let data = self.inner.data.read().await;
+        data.get(key).map(|v| v.to_string())
+    }
+
+    /// Get a copy of the whole state machine data.
+    pub async fn data(&self) -> KvData<C> {
+        self.inner.data.read().await.clone()
+    }
+}
+
+impl<C> RaftSnapshotBuilder<C> for KvStateMachine<C>
+where C: RaftTypeConfig<D = KvRequest, R = KvResponse, Entry = Entry<C>, SnapshotData = Cursor<Vec<u8>>>
+{
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn build_snapshot(&mut self) -> Result<Snapshot<C>, StorageError<C>> {
+        let data = self.inner.data.read().await;
+        let buf = serde_json::to_vec(&*data).map_err(|e| StorageError::read_state_machine(&e))?;
+
+        let last_applied = data.last_applied.clone();
+        let last_membership = data.last_membership.clone();
+
+        // Lock the current snapshot before releasing the data lock, so that a snapshot built
+        // later can not be overridden by this one.
+        let mut current_snapshot = self.inner.current_snapshot.write().await;
+        drop(data);
+
+        let snapshot_idx = self.inner.snapshot_idx.fetch_add(1, Ordering::Relaxed) + 1;
+        let snapshot_id = if let Some(last) = &last_applied {
+            format!("{}-{}-{}", last.committed_leader_id(), last.index(), snapshot_idx)
+        } else {
+            format!("--{}", snapshot_idx)
+        };
+
+        let meta = SnapshotMeta::new(last_applied, last_membership, snapshot_id);
+
+        *current_snapshot = Some(StoredSnapshot {
+            meta: meta.clone(),
+            data: buf.clone(),
+        });
+
+        Ok(Snapshot {
+            meta,
+            snapshot: Cursor::new(buf),
+        })
+    }
+}
+
+impl<C> RaftStateMachine<C> for KvStateMachine<C>
+where C: RaftTypeConfig<D = KvRequest, R = KvResponse, Entry = Entry<C>, SnapshotData = Cursor<Vec<u8>>>
+{
+    type SnapshotBuilder = Self;
+
+    async fn applied_state(&mut self) -> Result<(Option<LogIdOf<C>>, StoredMembership<C>), StorageError<C>> {
+        let data = self.inner.data.read().await;
+        Ok((data.last_applied.clone(), data.last_membership.clone()))
+    }
+
+    /// Apply entries and respond to a stale write with an empty response.
+    ///
+    /// Openraft calls [`apply_fallible()`](Self::apply_fallible) instead, which rejects a stale
+    /// write with an error.
+    async fn apply<I>(&mut self, entries: I) -> Result<Vec<KvResponse>, StorageError<C>>
+    where
+        I: IntoIterator<Item = Entry<C>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let res = self.apply_fallible(entries).await?;
+        Ok(res.into_iter().map(|r| r.unwrap_or_default()).collect())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, entries))]
+    async fn apply_fallible<I>(&mut self, entries: I) -> Result<Vec<Result<KvResponse, ApplyError<C>>>, StorageError<C>>
+    where
+        I: IntoIterator<Item = Entry<C>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let mut data = self.inner.data.write().await;
+        Ok(entries.into_iter().map(|entry| data.apply(&entry)).collect())
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        self.clone()
+    }
+
+    async fn begin_receiving_snapshot(&mut self) -> Result<Cursor<Vec<u8>>, StorageError<C>> {
+        Ok(Cursor::new(Vec::new()))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, snapshot))]
+    async fn install_snapshot(
+        &mut self,
+        meta: &SnapshotMeta<C>,
+        snapshot: Cursor<Vec<u8>>,
+    ) -> Result<(), StorageError<C>> {
+        tracing::info!("install snapshot: {}, size: {}", meta, snapshot.get_ref().len());
+
+        let buf = snapshot.into_inner();
+        let new_data: KvData<C> =
+            serde_json::from_slice(&buf).map_err(|e| StorageError::read_snapshot(Some(meta.signature()), &e))?;
+
+        // Replace the data and the current snapshot together, so that a snapshot being built
+        // concurrently does not replace the installed one.
+        let mut data = self.inner.data.write().await;
+        let mut current_snapshot = self.inner.current_snapshot.write().await;
+
+        *data = new_data;
+        *current_snapshot = Some(StoredSnapshot {
+            meta: meta.clone(),
+            data: buf,
+        });
+
+        Ok(())
+    }
+
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<C>>, StorageError<C>> {
+        let current_snapshot = self.inner.current_snapshot.read().await;
+
+        Ok(current_snapshot.as_ref().map(|s| Snapshot {
+            meta: s.meta.clone(),
+            snapshot: Cursor::new(s.data.clone()),
+        }))
+    }
+}
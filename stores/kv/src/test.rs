@@ -0,0 +1,146 @@
+use openraft::error::ApplyError;
+use openraft::storage::RaftStateMachine;
+use openraft::testing::log::StoreBuilder;
+use openraft::testing::log::Suite;
+use openraft::testing::log_id;
+use openraft::Entry;
+use openraft::EntryPayload;
+use openraft::RaftSnapshotBuilder;
+use openraft::StorageError;
+
+use crate::KvRequest;
+use crate::KvResponse;
+use crate::KvStateMachine;
+
+openraft::declare_raft_types!(
+    pub TypeConfig:
+        D = KvRequest,
+        R = KvResponse,
+);
+
+type LogStore = memstore::LogStore<TypeConfig>;
+
+struct KvStoreBuilder {}
+
+impl StoreBuilder<TypeConfig, LogStore, KvStateMachine<TypeConfig>, ()> for KvStoreBuilder {
+    async fn build(&self) -> Result<((), LogStore, KvStateMachine<TypeConfig>), StorageError<TypeConfig>> {
+        Ok(((), LogStore::default(), KvStateMachine::new()))
+    }
+}
+
+#[tokio::test]
+pub async fn test_kv_store() -> Result<(), StorageError<TypeConfig>> {
+    Suite::test_all(KvStoreBuilder {}).await?;
+    Ok(())
+}
+
+fn entry(index: u64, req: KvRequest) -> Entry<TypeConfig> {
+    Entry {
+        log_id: log_id::<TypeConfig>(1, 0, index),
+        payload: EntryPayload::Normal(req),
+    }
+}
+
+fn prev(v: Option<&str>) -> Result<KvResponse, ApplyError<TypeConfig>> {
+    Ok(KvResponse {
+        prev: v.map(|s| s.to_string()),
+    })
+}
+
+#[tokio::test]
+async fn test_set_delete() -> anyhow::Result<()> {
+    let mut sm = KvStateMachine::<TypeConfig>::new();
+
+    let res = sm
+        .apply_fallible([
+            entry(1, KvRequest::set("a", "1")),
+            entry(2, KvRequest::set("a", "2")),
+            entry(3, KvRequest::delete("a")),
+            entry(4, KvRequest::delete("a")),
+        ])
+        .await?;
+
+    assert_eq!(vec![prev(None), prev(Some("1")), prev(Some("2")), prev(None)], res);
+    assert_eq!(None, sm.get("a").await);
+    assert_eq!(Some(log_id::<TypeConfig>(1, 0, 4)), sm.data().await.last_applied);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_dedup() -> anyhow::Result<()> {
+    let mut sm = KvStateMachine::<TypeConfig>::new();
+
+    let res = sm
+        .apply_fallible([
+            entry(1, KvRequest::set("a", "1").with_session("c1", 1)),
+            entry(2, KvRequest::set("a", "2").with_session("c1", 2)),
+            entry(3, KvRequest::set("a", "2").with_session("c1", 2)),
+            entry(4, KvRequest::set("a", "1").with_session("c1", 1)),
+        ])
+        .await?;
+
+    assert_eq!(prev(None), res[0]);
+    assert_eq!(prev(Some("1")), res[1]);
+    assert_eq!(prev(Some("1")), res[2], "duplicate returns the cached response");
+    assert!(res[3].is_err(), "stale write is rejected");
+
+    assert_eq!(Some("2".to_string()), sm.get("a").await);
+    assert_eq!(Some(log_id::<TypeConfig>(1, 0, 4)), sm.data().await.last_applied);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_expiration() -> anyhow::Result<()> {
+    let mut sm = KvStateMachine::<TypeConfig>::new();
+
+    sm.apply_fallible([
+        entry(1, KvRequest::set_with_ttl("a", "1", 100).with_time_ms(1000)),
+        entry(2, KvRequest::set_with_ttl("b", "1", 200).with_time_ms(1000)),
+        entry(3, KvRequest::set("c", "1").with_time_ms(1050)),
+    ])
+    .await?;
+    assert_eq!(Some("1".to_string()), sm.get("a").await);
+    assert_eq!(3, sm.data().await.len());
+
+    // The time of the state machine is not moved back by an earlier timestamp.
+    let res = sm.apply_fallible([entry(4, KvRequest::set("a", "2").with_time_ms(1100))]).await?;
+    assert_eq!(vec![prev(None)], res, "a expired at 1100");
+
+    let mut res = sm.apply_fallible([entry(5, KvRequest::delete("c").with_time_ms(1300))]).await?;
+    assert_eq!(prev(Some("1")), res.remove(0));
+
+    let data = sm.data().await;
+    assert_eq!(1300, data.now_ms);
+    assert_eq!(None, data.get("b"), "b expired at 1200");
+    assert_eq!(Some("2"), data.get("a"));
+    assert_eq!(1, data.len(), "expired keys are removed");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_round_trip() -> anyhow::Result<()> {
+    let mut sm = KvStateMachine::<TypeConfig>::new();
+    sm.apply_fallible([
+        entry(1, KvRequest::set("a", "1").with_session("c1", 1)),
+        entry(2, KvRequest::set_with_ttl("b", "1", 100).with_time_ms(1000)),
+    ])
+    .await?;
+
+    let snapshot = sm.get_snapshot_builder().await.build_snapshot().await?;
+    assert_eq!(Some(log_id::<TypeConfig>(1, 0, 2)), snapshot.meta.last_log_id);
+
+    let mut sm2 = KvStateMachine::<TypeConfig>::new();
+    sm2.install_snapshot(&snapshot.meta, snapshot.snapshot).await?;
+
+    assert_eq!(Some("1".to_string()), sm2.get("a").await);
+    assert_eq!(Some("1".to_string()), sm2.get("b").await);
+    assert_eq!(
+        Some(snapshot.meta.clone()),
+        sm2.get_current_snapshot().await?.map(|s| s.meta)
+    );
+
+    tracing::info!("--- sessions are restored from the snapshot");
+    let res = sm2.apply_fallible([entry(3, KvRequest::set("a", "1").with_session("c1", 1))]).await?;
+    assert_eq!(vec![prev(None)], res, "duplicate of the write before the snapshot");
+    Ok(())
+}
@@ -7,22 +7,30 @@ mod test;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::io;
 use std::io::Cursor;
 use std::ops::RangeBounds;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 
+use openraft::alias::InstantOf;
 use openraft::alias::SnapshotDataOf;
 use openraft::entry::RaftEntry;
+use openraft::session::SessionTable;
 use openraft::storage::IOFlushed;
 use openraft::storage::LogState;
+use openraft::storage::LogStats;
+use openraft::storage::PurgeProgress;
 use openraft::storage::RaftLogReader;
 use openraft::storage::RaftLogStorage;
 use openraft::storage::RaftSnapshotBuilder;
 use openraft::storage::RaftStateMachine;
 use openraft::storage::Snapshot;
+use openraft::type_config::TypeConfigExt;
 use openraft::Entry;
 use openraft::EntryPayload;
 use openraft::LogId;
@@ -31,10 +39,13 @@ use openraft::SnapshotMeta;
 use openraft::StorageError;
 use openraft::StoredMembership;
 use openraft::Vote;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
 use serde::Deserialize;
 use serde::Serialize;
+use tokio::sync::mpsc;
 use tokio::sync::RwLock;
-use tokio::time::Duration;
 
 /// The application data request type which the `MemStore` works with.
 ///
@@ -83,6 +94,17 @@ mod leader_id_mode {
     pub use openraft::impls::leader_id_std::LeaderId;
 }
 
+/// Choose an AsyncRuntime implementation by feature flag.
+mod async_runtime_mode {
+    #[cfg(not(feature = "madsim"))]
+    pub use openraft::TokioRuntime as AsyncRuntime;
+    #[cfg(feature = "madsim")]
+    pub use openraft_rt_madsim::MadsimRuntime as AsyncRuntime;
+}
+
+/// The `AsyncRuntime` the `MemStore` and the raft nodes run on, chosen by feature flag.
+pub type MemAsyncRuntime = async_runtime_mode::AsyncRuntime;
+
 openraft::declare_raft_types!(
     /// Declare the type configuration for `MemStore`.
     pub TypeConfig:
@@ -90,6 +112,7 @@ openraft::declare_raft_types!(
         R = ClientResponse,
         Node = (),
         LeaderId = leader_id_mode::LeaderId<TypeConfig>,
+        AsyncRuntime = MemAsyncRuntime,
 );
 
 /// The application snapshot type which the `MemStore` works with.
@@ -110,6 +133,10 @@ pub struct MemStoreStateMachine {
 
     /// The current status of a client by ID.
     pub client_status: HashMap<String, String>,
+
+    /// The last applied request of every client, used when `MemStateMachine::enable_dedup` is set.
+    #[serde(default)]
+    pub sessions: SessionTable<String, ClientResponse>,
 }
 
 #[derive(Debug, Clone)]
@@ -121,12 +148,33 @@ pub enum BlockOperation {
     DelayBuildingSnapshot,
     BuildSnapshot,
     PurgeLog,
+
+    /// Delay reporting an append as flushed, emulating a slow `fsync`.
+    ///
+    /// Entries are already readable when the delay starts; only the `IOFlushed` callback is
+    /// postponed.
+    FlushLog,
+
+    /// Delay every `apply()` call, emulating a slow state machine.
+    Apply,
+}
+
+/// Randomly fail `append()` calls, emulating a flaky disk.
+///
+/// The failures are driven by a seeded RNG, so the same seed and the same sequence of
+/// `append()` calls always produce the same failures.
+#[derive(Debug)]
+struct AppendFailure {
+    /// The probability in `[0, 1]` that an `append()` fails.
+    probability: f64,
+    rng: StdRng,
 }
 
 /// Block operations for testing purposes.
 #[derive(Clone, Debug, Default)]
 pub struct BlockConfig {
     inner: Arc<Mutex<BTreeMap<BlockOperation, Duration>>>,
+    append_failure: Arc<Mutex<Option<AppendFailure>>>,
 }
 
 impl BlockConfig {
@@ -141,11 +189,37 @@ impl BlockConfig {
     }
 
     /// Clear a blocking flag for an operation.
-    pub fn clear_blocking(&mut self, block: BlockOperation) {
+    pub fn clear_blocking(&self, block: BlockOperation) {
         self.inner.lock().unwrap().remove(&block);
     }
+
+    /// Make `append()` fail with the given `probability`, using an RNG seeded with `seed`.
+    pub fn set_append_failure(&self, probability: f64, seed: u64) {
+        let f = AppendFailure {
+            probability: probability.clamp(0.0, 1.0),
+            rng: StdRng::seed_from_u64(seed),
+        };
+        *self.append_failure.lock().unwrap() = Some(f);
+    }
+
+    /// Stop injecting `append()` failures.
+    pub fn clear_append_failure(&self) {
+        *self.append_failure.lock().unwrap() = None;
+    }
+
+    /// Roll the dice and return `true` if the next `append()` should fail.
+    pub fn should_fail_append(&self) -> bool {
+        let mut f = self.append_failure.lock().unwrap();
+        match f.as_mut() {
+            None => false,
+            Some(f) => f.rng.gen_bool(f.probability),
+        }
+    }
 }
 
+/// The time to complete a flush callback at and the callback.
+type PendingFlush = (InstantOf<TypeConfig>, IOFlushed<TypeConfig>);
+
 /// An in-memory log storage implementing the `RaftLogStorage` trait.
 pub struct MemLogStore {
     last_purged_log_id: RwLock<Option<LogId<TypeConfig>>>,
@@ -160,11 +234,20 @@ pub struct MemLogStore {
     /// The Raft log. Logs are stored in serialized json.
     log: RwLock<BTreeMap<u64, String>>,
 
+    /// The total size of `log`, updated along with it so that `stats()` does not scan the logs.
+    log_bytes: AtomicU64,
+
     /// Block operations for testing purposes.
     block: BlockConfig,
 
     /// The current hard state.
     vote: RwLock<Option<Vote<TypeConfig>>>,
+
+    /// Sends the flush callbacks of appends to a background task that completes them in order.
+    ///
+    /// It is started by the first append delayed by `BlockOperation::FlushLog`, so that a delayed
+    /// flush does not block the log IO worker.
+    flusher: Mutex<Option<mpsc::UnboundedSender<PendingFlush>>>,
 }
 
 impl MemLogStore {
@@ -176,8 +259,73 @@ impl MemLogStore {
             enable_saving_committed: AtomicBool::new(true),
             committed: RwLock::new(None),
             log,
+            log_bytes: AtomicU64::new(0),
             block,
             vote: RwLock::new(None),
+            flusher: Mutex::new(None),
+        }
+    }
+
+    /// Complete the flush `callback` of an append, after a delay if `BlockOperation::FlushLog` is
+    /// set.
+    ///
+    /// Once a flush has been delayed, every later callback is completed by the same background
+    /// task, so that flushes are always reported in the order of appends.
+    fn flush(&self, callback: IOFlushed<TypeConfig>) {
+        let delay = self.block.get_blocking(&BlockOperation::FlushLog);
+
+        let mut flusher = self.flusher.lock().unwrap();
+
+        if delay.is_none() && flusher.is_none() {
+            callback.io_completed(Ok(()));
+            return;
+        }
+
+        let tx = flusher.get_or_insert_with(|| {
+            let (tx, mut rx) = mpsc::unbounded_channel::<PendingFlush>();
+            TypeConfig::spawn(async move {
+                while let Some((at, callback)) = rx.recv().await {
+                    TypeConfig::sleep_until(at).await;
+                    callback.io_completed(Ok(()));
+                }
+            });
+            tx
+        });
+
+        if let Some(d) = delay {
+            tracing::info!(?d, "delay flushing log");
+        }
+
+        let _ = tx.send((TypeConfig::now() + delay.unwrap_or_default(), callback));
+    }
+
+    /// Remove logs upto `log_id`, inclusive.
+    ///
+    /// Purges running in the background may finish out of order: a purge is skipped if a greater
+    /// one has already been done.
+    async fn purge_logs(&self, log_id: LogId<TypeConfig>) {
+        if let Some(d) = self.block.get_blocking(&BlockOperation::PurgeLog) {
+            tracing::info!(?d, "block purging log");
+            TypeConfig::sleep(d).await;
+        }
+
+        {
+            let mut ld = self.last_purged_log_id.write().await;
+            if Some(log_id) <= *ld {
+                return;
+            }
+            *ld = Some(log_id);
+        }
+
+        {
+            let mut log = self.log.write().await;
+
+            let keys = log.range(..=log_id.index()).map(|(k, _v)| *k).collect::<Vec<_>>();
+            for key in keys {
+                if let Some(s) = log.remove(&key) {
+                    self.log_bytes.fetch_sub(s.len() as u64, Ordering::Relaxed);
+                }
+            }
         }
     }
 }
@@ -194,6 +342,14 @@ pub struct MemStateMachine {
 
     /// Block operations for testing purposes.
     pub block: BlockConfig,
+
+    /// Whether to deduplicate client requests by `(client, serial)`.
+    ///
+    /// Disabled by default, because many tests reuse serial numbers.
+    pub enable_dedup: AtomicBool,
+
+    /// The [`SnapshotMeta::version`] of snapshots built by this state machine.
+    pub snapshot_version: AtomicU64,
 }
 
 impl MemStateMachine {
@@ -206,6 +362,8 @@ impl MemStateMachine {
             snapshot_idx: Arc::new(Mutex::new(0)),
             current_snapshot,
             block,
+            enable_dedup: AtomicBool::new(false),
+            snapshot_version: AtomicU64::new(0),
         }
     }
 
@@ -268,7 +426,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<MemStateMachine> {
 
         if let Some(d) = self.block.get_blocking(&BlockOperation::DelayBuildingSnapshot) {
             tracing::info!(?d, "delay snapshot build");
-            tokio::time::sleep(d).await;
+            TypeConfig::sleep(d).await;
         }
 
         {
@@ -281,7 +439,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<MemStateMachine> {
 
             if let Some(d) = self.block.get_blocking(&BlockOperation::BuildSnapshot) {
                 tracing::info!(?d, "blocking snapshot build");
-                tokio::time::sleep(d).await;
+                TypeConfig::sleep(d).await;
             }
         }
 
@@ -299,11 +457,8 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<MemStateMachine> {
             format!("--{}", snapshot_idx)
         };
 
-        let meta = SnapshotMeta {
-            last_log_id: last_applied_log,
-            last_membership,
-            snapshot_id,
-        };
+        let meta = SnapshotMeta::new(last_applied_log, last_membership, snapshot_id)
+            .with_version(self.snapshot_version.load(Ordering::Relaxed));
 
         let snapshot = MemStoreSnapshot {
             meta: meta.clone(),
@@ -390,13 +545,24 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
     #[tracing::instrument(level = "trace", skip_all)]
     async fn append<I>(&mut self, entries: I, callback: IOFlushed<TypeConfig>) -> Result<(), StorageError<TypeConfig>>
     where I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend {
-        let mut log = self.log.write().await;
-        for entry in entries {
-            let s = serde_json::to_string(&entry).map_err(|e| StorageError::write_log_entry(entry.log_id(), &e))?;
-            log.insert(entry.index(), s);
+        if self.block.should_fail_append() {
+            tracing::info!("inject append failure");
+            let e = io::Error::new(io::ErrorKind::Other, "injected append failure");
+            return Err(StorageError::write_logs(&e));
         }
 
-        callback.io_completed(Ok(()));
+        {
+            let mut log = self.log.write().await;
+            for entry in entries {
+                let s = serde_json::to_string(&entry).map_err(|e| StorageError::write_log_entry(entry.log_id(), &e))?;
+                self.log_bytes.fetch_add(s.len() as u64, Ordering::Relaxed);
+                if let Some(prev) = log.insert(entry.index(), s) {
+                    self.log_bytes.fetch_sub(prev.len() as u64, Ordering::Relaxed);
+                }
+            }
+        }
+
+        self.flush(callback);
         Ok(())
     }
 
@@ -409,7 +575,9 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
 
             let keys = log.range(log_id.index()..).map(|(k, _v)| *k).collect::<Vec<_>>();
             for key in keys {
-                log.remove(&key);
+                if let Some(s) = log.remove(&key) {
+                    self.log_bytes.fetch_sub(s.len() as u64, Ordering::Relaxed);
+                }
             }
         }
 
@@ -420,28 +588,43 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
     async fn purge(&mut self, log_id: LogId<TypeConfig>) -> Result<(), StorageError<TypeConfig>> {
         tracing::debug!("purge_log_upto: {:?}", log_id);
 
-        if let Some(d) = self.block.get_blocking(&BlockOperation::PurgeLog) {
-            tracing::info!(?d, "block purging log");
-            tokio::time::sleep(d).await;
-        }
+        assert!(*self.last_purged_log_id.read().await <= Some(log_id));
+        self.purge_logs(log_id).await;
+        Ok(())
+    }
 
-        {
-            let mut ld = self.last_purged_log_id.write().await;
-            assert!(*ld <= Some(log_id));
-            *ld = Some(log_id);
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn purge_with_progress(
+        &mut self,
+        log_id: LogId<TypeConfig>,
+        callback: PurgeProgress<TypeConfig>,
+    ) -> Result<(), StorageError<TypeConfig>> {
+        // Removing in-memory logs is cheap, only a purge slowed down by `BlockOperation::PurgeLog`
+        // is worth running in the background.
+        if self.block.get_blocking(&BlockOperation::PurgeLog).is_none() {
+            self.purge_logs(log_id).await;
+            callback.completed(Ok(()));
+            return Ok(());
         }
 
-        {
-            let mut log = self.log.write().await;
-
-            let keys = log.range(..=log_id.index()).map(|(k, _v)| *k).collect::<Vec<_>>();
-            for key in keys {
-                log.remove(&key);
-            }
-        }
+        tracing::debug!("purge_log_upto in background: {:?}", log_id);
 
+        let store = self.clone();
+        TypeConfig::spawn(async move {
+            store.purge_logs(log_id).await;
+            callback.completed(Ok(()));
+        });
         Ok(())
     }
+
+    async fn stats(&mut self) -> Result<Option<LogStats>, StorageError<TypeConfig>> {
+        let log = self.log.read().await;
+        Ok(Some(LogStats {
+            entries: log.len() as u64,
+            bytes: self.log_bytes.load(Ordering::Relaxed),
+            segments: None,
+        }))
+    }
 }
 
 impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
@@ -460,9 +643,17 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
         I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend,
         I::IntoIter: OptionalSend,
     {
+        if let Some(d) = self.block.get_blocking(&BlockOperation::Apply) {
+            tracing::info!(?d, "delay applying entries");
+            TypeConfig::sleep(d).await;
+        }
+
         let mut res = Vec::new();
 
         let mut sm = self.sm.write().await;
+        let sm = &mut *sm;
+
+        let dedup = self.enable_dedup.load(Ordering::Relaxed);
 
         for entry in entries {
             tracing::debug!(%entry.log_id, "replicate to sm");
@@ -472,8 +663,18 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
             match entry.payload {
                 EntryPayload::Blank => res.push(ClientResponse(None)),
                 EntryPayload::Normal(ref data) => {
-                    let previous = sm.client_status.insert(data.client.clone(), data.status.clone());
-                    res.push(ClientResponse(previous));
+                    let client_status = &mut sm.client_status;
+                    let mut do_apply =
+                        || ClientResponse(client_status.insert(data.client.clone(), data.status.clone()));
+
+                    let resp = if dedup {
+                        let res = sm.sessions.apply(data.client.clone(), data.serial, do_apply);
+                        // A stale request is not applied and its response is gone.
+                        res.into_response().unwrap_or(ClientResponse(None))
+                    } else {
+                        do_apply()
+                    };
+                    res.push(resp);
                 }
                 EntryPayload::Membership(ref mem) => {
                     sm.last_membership = StoredMembership::new(Some(entry.log_id), mem.clone());
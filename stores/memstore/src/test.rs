@@ -4,6 +4,7 @@ use openraft::testing::log::StoreBuilder;
 use openraft::testing::log::Suite;
 use openraft::StorageError;
 
+use crate::BlockConfig;
 use crate::MemLogStore;
 use crate::MemStateMachine;
 use crate::TypeConfig;
@@ -22,3 +23,26 @@ pub async fn test_mem_store() -> Result<(), StorageError<TypeConfig>> {
     Suite::test_all(MemStoreBuilder {}).await?;
     Ok(())
 }
+
+#[test]
+fn test_append_failure_is_deterministic() {
+    let rolls = |seed| {
+        let block = BlockConfig::default();
+        block.set_append_failure(0.5, seed);
+        (0..64).map(|_| block.should_fail_append()).collect::<Vec<_>>()
+    };
+
+    let a = rolls(7);
+    assert_eq!(a, rolls(7));
+    assert!(a.contains(&true));
+    assert!(a.contains(&false));
+
+    let block = BlockConfig::default();
+    assert!(!block.should_fail_append());
+
+    block.set_append_failure(1.0, 7);
+    assert!(block.should_fail_append());
+
+    block.clear_append_failure();
+    assert!(!block.should_fail_append());
+}
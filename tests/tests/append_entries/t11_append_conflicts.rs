@@ -20,6 +20,8 @@ use crate::fixtures::RaftRouter;
 /// Test append-entries response in every case.
 ///
 /// - bring up a learner and send to it append_entries request. Check the response in every case.
+///
+/// The vote is greater than every log id in the requests, otherwise a request is malformed.
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
 async fn append_conflicts() -> Result<()> {
@@ -45,7 +47,7 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 0: prev_log_id == None, no logs");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: None,
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 2)),
@@ -59,7 +61,7 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 0: prev_log_id == None, 1 logs");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: None,
         entries: vec![blank_ent(0, 0, 0)],
         leader_commit: Some(log_id(1, 0, 2)),
@@ -72,7 +74,7 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 0: prev_log_id == 1-1, 0 logs");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(0, 0, 0)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 2)),
@@ -87,7 +89,7 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 0: prev_log_id.index == 0, ");
 
     let req = || AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(0, 0, 0)),
         entries: vec![
             blank_ent(1, 0, 1),
@@ -117,7 +119,7 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 1: 0 < prev_log_id.index < commit_index");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(1, 0, 1)),
         entries: vec![blank_ent(1, 0, 2)],
         leader_commit: Some(log_id(1, 0, 2)),
@@ -132,7 +134,7 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 2:  prev_log_id.index == last_applied, inconsistent log should be removed");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(1, 0, 2)),
         entries: vec![blank_ent(2, 0, 3)],
         // this set the last_applied to 2
@@ -147,7 +149,7 @@ async fn append_conflicts() -> Result<()> {
 
     // check last_log_id is updated:
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(1, 0, 2000)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 2)),
@@ -162,7 +164,7 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 3,4: prev_log_id.index <= last_log_id, prev_log_id mismatch, inconsistent log is removed");
 
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(3, 0, 3)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 2)),
@@ -177,7 +179,7 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 3,4: prev_log_id.index <= last_log_id, prev_log_id matches, inconsistent log is removed");
     // refill logs
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(1, 0, 2)),
         entries: vec![blank_ent(2, 0, 3), blank_ent(2, 0, 4), blank_ent(2, 0, 5)],
         leader_commit: Some(log_id(1, 0, 2)),
@@ -192,7 +194,7 @@ async fn append_conflicts() -> Result<()> {
 
     // prev_log_id matches
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(2, 0, 3)),
         entries: vec![blank_ent(3, 0, 4)],
         leader_commit: Some(log_id(1, 0, 2)),
@@ -208,7 +210,7 @@ async fn append_conflicts() -> Result<()> {
 
     // refill logs
     let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
+        vote: Vote::new_committed(3, 2),
         prev_log_id: Some(log_id(1, 0, 200)),
         entries: vec![],
         leader_commit: Some(log_id(1, 0, 2)),
@@ -5,10 +5,10 @@ use std::time::Duration;
 use anyhow::Result;
 use maplit::btreeset;
 use openraft::raft::VoteRequest;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
-use openraft::TokioInstant;
 use openraft::Vote;
-use tokio::time::sleep;
+use openraft_memstore::TypeConfig;
 
 use crate::fixtures::log_id;
 use crate::fixtures::ut_harness;
@@ -29,12 +29,12 @@ async fn heartbeat_reject_vote() -> Result<()> {
     );
     let mut router = RaftRouter::new(config.clone());
 
-    let now = TokioInstant::now();
-    sleep(Duration::from_millis(1)).await;
+    let now = TypeConfig::now();
+    TypeConfig::sleep(Duration::from_millis(1)).await;
 
     let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
 
-    let vote_modified_time = Arc::new(Mutex::new(Some(TokioInstant::now())));
+    let vote_modified_time = Arc::new(Mutex::new(Some(TypeConfig::now())));
     tracing::info!(log_index, "--- leader lease is set by heartbeat");
     {
         let m = vote_modified_time.clone();
@@ -45,8 +45,8 @@ async fn heartbeat_reject_vote() -> Result<()> {
             assert!(state.vote_last_modified() > Some(now));
         });
 
-        let now = TokioInstant::now();
-        sleep(Duration::from_millis(700)).await;
+        let now = TypeConfig::now();
+        TypeConfig::sleep(Duration::from_millis(700)).await;
 
         let m = vote_modified_time.clone();
 
@@ -69,14 +69,14 @@ async fn heartbeat_reject_vote() -> Result<()> {
     tracing::info!(log_index, "--- ensures no more blank-log heartbeat is used");
     {
         // TODO: this part can be removed when blank-log heartbeat is removed.
-        sleep(Duration::from_millis(1500)).await;
+        TypeConfig::sleep(Duration::from_millis(1500)).await;
         router.wait(&1, timeout()).applied_index(Some(log_index), "no log is written").await?;
     }
 
     tracing::info!(log_index, "--- disable heartbeat, vote request will be granted");
     {
         node0.runtime_config().heartbeat(false);
-        sleep(Duration::from_millis(1500)).await;
+        TypeConfig::sleep(Duration::from_millis(1500)).await;
 
         router.wait(&1, timeout()).applied_index(Some(log_index), "no log is written").await?;
 
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::chaos::Chaos;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Crash and restart nodes, partition the network, build snapshots and change membership
+/// randomly, while asserting the core invariants after every step.
+///
+/// Run longer with `OPENRAFT_CHAOS_STEPS`, and replay the faults of a failure with
+/// `OPENRAFT_CHAOS_SEED`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn chaos() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 200,
+            election_timeout_max: 300,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let nodes = btreeset! {0,1,2,3,4};
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- bring up cluster of 5 nodes");
+    router.new_cluster(nodes.clone(), btreeset! {}).await?;
+
+    let mut chaos = Chaos::new(router, nodes);
+    chaos.run(Chaos::steps(50)).await?;
+
+    Ok(())
+}
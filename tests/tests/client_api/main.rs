@@ -15,7 +15,20 @@ mod t13_get_snapshot;
 mod t13_install_full_snapshot;
 mod t13_trigger_snapshot;
 mod t14_transfer_leader;
+mod t16_current_leader_node;
 mod t16_with_raft_state;
 mod t16_with_state_machine;
+mod t17_dedup_session;
+mod t18_wait_applied;
+mod t18_wait_for_leader;
+mod t19_client_write_span;
+mod t19_client_write_timeout;
+mod t19_client_write_with_correlation_id;
+mod t20_read_ticket;
+mod t21_leader_client;
+mod t22_decision_trace;
+mod t23_last_quorum_acked_time;
+mod t24_verify_state;
 mod t50_lagging_network_write;
 mod t51_write_when_leader_quit;
+mod t60_linearizability;
@@ -3,7 +3,10 @@ use std::sync::Arc;
 use anyhow::Result;
 use futures::prelude::*;
 use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::impls::OneshotResponder;
 use openraft::raft::ClientWriteResponse;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
 use openraft::SnapshotPolicy;
 use openraft_memstore::ClientRequest;
@@ -96,3 +99,172 @@ async fn client_write_ff() -> Result<()> {
 
     Ok(())
 }
+
+/// Test Raft::client_write_with_responder,
+///
+/// The application builds the responder, and receives the response through its own channel.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_with_responder() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write with an application built responder");
+    {
+        let (tx, rx) = TypeConfig::oneshot();
+        n0.client_write_with_responder(ClientRequest::make_request("foo", 2), OneshotResponder::new(tx))
+            .await?;
+
+        let got: ClientWriteResponse<TypeConfig> = rx.await??;
+        assert_eq!(log_index + 1, got.log_id().index());
+        assert_eq!(None, got.response().0.as_deref());
+    }
+
+    tracing::info!(
+        log_index,
+        "--- a follower responds with ForwardToLeader through the responder"
+    );
+    {
+        let n1 = router.get_raft_handle(&1)?;
+
+        let (tx, rx) = TypeConfig::oneshot();
+        n1.client_write_with_responder(ClientRequest::make_request("foo", 3), OneshotResponder::new(tx))
+            .await?;
+
+        let got = rx.await?;
+        assert!(matches!(got, Err(ClientWriteError::ForwardToLeader(_))));
+    }
+
+    Ok(())
+}
+
+/// Test Raft::client_write_handle,
+///
+/// Submit several writes without awaiting them, then collect the responses via the returned
+/// handles. A write to a follower responds with `ForwardToLeader`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_handle() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- submit writes without awaiting them");
+    let mut handles = vec![];
+    for i in 0..5 {
+        handles.push(n0.client_write_handle(ClientRequest::make_request("foo", i)).await?);
+    }
+
+    for (i, h) in handles.into_iter().enumerate() {
+        let got = h.response().await?;
+        assert_eq!(log_index + 1 + i as u64, got.log_id().index());
+
+        let want = if i == 0 {
+            None
+        } else {
+            Some(format!("request-{}", i - 1))
+        };
+        assert_eq!(want, got.response().0);
+    }
+
+    tracing::info!("--- a write to a follower is rejected");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let h = n1.client_write_handle(ClientRequest::make_request("foo", 100)).await?;
+        let err = h.response().await.unwrap_err();
+        let fwd = err.forward_to_leader().expect("ForwardToLeader error");
+        assert_eq!(Some(0), fwd.leader_id);
+    }
+
+    Ok(())
+}
+
+/// Test Raft::client_write_many,
+///
+/// A batch of requests is appended as consecutive logs and every request gets its own response.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_many() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write a batch");
+    {
+        let reqs = (0..10).map(|i| ClientRequest::make_request("foo", i)).collect::<Vec<_>>();
+        let results = n0.client_write_many(reqs).await?;
+        assert_eq!(10, results.len());
+
+        for (i, res) in results.into_iter().enumerate() {
+            let got = res?;
+            assert_eq!(log_index + 1 + i as u64, got.log_id().index());
+
+            let want = if i == 0 {
+                None
+            } else {
+                Some(format!("request-{}", i - 1))
+            };
+            assert_eq!(want, got.response().0);
+        }
+        log_index += 10;
+
+        router.wait_for_log(&btreeset! {0,1,2}, Some(log_index), None, "batch applied").await?;
+    }
+
+    tracing::info!(log_index, "--- an empty batch");
+    {
+        let results = n0.client_write_many(vec![]).await?;
+        assert!(results.is_empty());
+    }
+
+    tracing::info!(log_index, "--- every request in a batch to a follower is rejected");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let reqs = (0..3).map(|i| ClientRequest::make_request("foo", 100 + i)).collect::<Vec<_>>();
+        let results = n1.client_write_many(reqs).await?;
+        assert_eq!(3, results.len());
+
+        for res in results {
+            let ClientWriteError::ForwardToLeader(fwd) = res.unwrap_err() else {
+                panic!("expect ForwardToLeader");
+            };
+            assert_eq!(Some(0), fwd.leader_id);
+        }
+    }
+
+    Ok(())
+}
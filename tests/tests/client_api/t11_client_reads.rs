@@ -4,13 +4,20 @@ use std::time::Duration;
 use anyerror::AnyError;
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::error::CheckIsLeaderError;
+use openraft::error::ForwardToLeader;
 use openraft::error::NetworkError;
 use openraft::error::RPCError;
+use openraft::error::ReadIndexError;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
 use openraft::LogIdOptionExt;
 use openraft::RPCTypes;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::TypeConfig;
 
 use crate::fixtures::ut_harness;
+use crate::fixtures::MemStateMachine;
 use crate::fixtures::RPCRequest;
 use crate::fixtures::RaftRouter;
 
@@ -66,6 +73,58 @@ async fn client_reads() -> Result<()> {
     Ok(())
 }
 
+/// `is_leader_local()` checks the local state only, while `ensure_linearizable()` confirms
+/// leadership with a quorum. Both return `ForwardToLeader` with the leader's id and node on a
+/// follower.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn is_leader_local() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- a follower forwards to the leader");
+    {
+        n0.is_leader_local().await?;
+
+        let fwd = n1.is_leader_local().await.unwrap_err();
+        assert_eq!(ForwardToLeader::new(0, ()), fwd);
+
+        let err = n1.ensure_linearizable().await.unwrap_err();
+        assert_eq!(Some(&ForwardToLeader::new(0, ())), err.forward_to_leader());
+    }
+
+    tracing::info!(log_index, "--- without a quorum, only ensure_linearizable() fails");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        n0.is_leader_local().await?;
+
+        let err = n0.ensure_linearizable().await.unwrap_err();
+        assert!(
+            matches!(err.api_error(), Some(CheckIsLeaderError::QuorumNotEnough(_))),
+            "got: {}",
+            err
+        );
+    }
+
+    Ok(())
+}
+
 /// - A leader that has not yet committed any log entries returns leader initialization log id(blank
 ///   log id).
 /// - Return the last committed log id if the leader has committed any log entries.
@@ -116,7 +175,7 @@ async fn get_read_log_id() -> Result<()> {
     router.set_rpc_pre_hook(RPCTypes::AppendEntries, block_to_n0);
 
     // Expire current leader
-    tokio::time::sleep(Duration::from_millis(200)).await;
+    TypeConfig::sleep(Duration::from_millis(200)).await;
 
     tracing::info!("--- let node 1 to become leader, append a blank log");
     let n1 = router.get_raft_handle(&1).unwrap();
@@ -164,7 +223,7 @@ async fn get_read_log_id() -> Result<()> {
         router.set_rpc_pre_hook(RPCTypes::AppendEntries, block_to_n0);
 
         let r = router.clone();
-        tokio::spawn(async move {
+        TypeConfig::spawn(async move {
             // This will block for ever
             let _x = r.client_request_many(1, "foo", 1).await;
         });
@@ -183,6 +242,183 @@ async fn get_read_log_id() -> Result<()> {
     Ok(())
 }
 
+/// `linearizable_read()` runs the read closure on the leader's state machine after the last write
+/// is applied, and fails on a follower.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn linearizable_read() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write then read on the leader");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        let (status, applied) = n0
+            .linearizable_read(|sm: &mut MemStateMachine| {
+                Box::pin(async move {
+                    let d = sm.get_state_machine().await;
+                    (d.client_status.get("foo").cloned(), d.last_applied_log)
+                })
+            })
+            .await?
+            .unwrap();
+
+        assert_eq!(Some("request-2".to_string()), status);
+        assert_eq!(Some(log_index), applied.index());
+    }
+
+    tracing::info!(log_index, "--- read on a follower fails");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.linearizable_read(|_sm: &mut MemStateMachine| Box::pin(async move {})).await;
+
+        let err = res.unwrap_err();
+        assert!(
+            matches!(
+                err.api_error(),
+                Some(CheckIsLeaderError::ForwardToLeader(ForwardToLeader {
+                    leader_id: Some(0),
+                    ..
+                }))
+            ),
+            "unexpected error: {:?}",
+            err
+        );
+    }
+
+    Ok(())
+}
+
+/// With lease read enabled, the leader serves reads without a quorum while its lease is valid, and
+/// falls back to confirming with a quorum after the lease expires.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn lease_read() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            heartbeat_interval: 50,
+            election_timeout_min: 150,
+            election_timeout_max: 300,
+            enable_lease_read: true,
+            lease_read_max_clock_drift: 50,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.wait(timeout()).metrics(|m| m.last_quorum_acked.is_some(), "quorum acked a heartbeat").await?;
+
+    tracing::info!(log_index, "--- isolate both followers, read within the lease");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let read_log_id = n0.ensure_linearizable().await?;
+        assert_eq!(Some(log_index), read_log_id.index());
+    }
+
+    tracing::info!(log_index, "--- after the lease expires, read requires a quorum");
+    {
+        TypeConfig::sleep(Duration::from_millis(400)).await;
+
+        let res = n0.ensure_linearizable().await;
+        assert!(res.is_err(), "lease expired and no quorum: {:?}", res);
+    }
+
+    tracing::info!(log_index, "--- restore the network, read succeeds again");
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        n0.ensure_linearizable().await?;
+    }
+
+    Ok(())
+}
+
+/// A follower gets a read log id from the leader and serves the read locally after applying upto
+/// it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn ensure_linearizable_via_leader() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(
+        log_index,
+        "--- read on node-1 waits for the local state machine to catch up"
+    );
+    {
+        let (_log_store, sm) = router.get_storage_handle(&1)?;
+        sm.block.set_blocking(BlockOperation::Apply, Duration::from_millis(300));
+
+        log_index += router.client_request_many(0, "foo", 3).await?;
+
+        let read_log_id = n1.ensure_linearizable_via_leader().await?;
+        assert_eq!(Some(log_index), read_log_id.index());
+
+        let state = sm.get_state_machine().await;
+        assert_eq!(Some(&"request-2".to_string()), state.client_status.get("foo"));
+        assert_eq!(Some(log_index), state.last_applied_log.index());
+
+        sm.block.clear_blocking(BlockOperation::Apply);
+    }
+
+    tracing::info!(log_index, "--- on the leader it works as ensure_linearizable");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let read_log_id = n0.ensure_linearizable_via_leader().await?;
+        assert_eq!(Some(log_index), read_log_id.index());
+    }
+
+    tracing::info!(log_index, "--- read on node-1 fails if the leader is unreachable");
+    {
+        router.set_network_error(0, true);
+
+        let res = n1.ensure_linearizable_via_leader().await;
+        let err = res.unwrap_err();
+        assert!(
+            matches!(err.api_error(), Some(ReadIndexError::RPCError(_))),
+            "unexpected error: {:?}",
+            err
+        );
+    }
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(200))
 }
@@ -67,7 +67,8 @@ async fn trigger_purge_log() -> anyhow::Result<()> {
     tracing::info!(log_index, "--- purge log for node 0");
     {
         let n0 = router.get_raft_handle(&0)?;
-        n0.trigger().purge_log(snapshot_index).await?;
+        let scheduled = n0.trigger().purge_log(snapshot_index).await?;
+        assert_eq!(Some(log_id(1, 0, snapshot_index)), scheduled);
 
         router
             .wait(&0, timeout())
@@ -77,7 +78,12 @@ async fn trigger_purge_log() -> anyhow::Result<()> {
             )
             .await?;
 
-        n0.trigger().purge_log(log_index).await?;
+        let scheduled = n0.trigger().purge_log(log_index).await?;
+        assert_eq!(
+            Some(log_id(1, 0, snapshot_index)),
+            scheduled,
+            "logs not in snapshot are not scheduled"
+        );
         let res = router
             .wait(&0, timeout())
             .purged(
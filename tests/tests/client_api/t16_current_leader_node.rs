@@ -0,0 +1,61 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Access the current membership and leader node via
+/// [`Raft::current_membership()`](openraft::Raft::current_membership) and
+/// [`Raft::current_leader_node()`](openraft::Raft::current_leader_node).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn current_leader_node() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n3 = router.get_raft_handle(&3)?;
+
+    tracing::info!(log_index, "--- a learner knows the leader and the membership");
+    {
+        assert_eq!(Some((0, ())), n3.current_leader_node().await);
+
+        let m = n3.current_membership().await;
+        assert_eq!(Some(&log_id(1, 0, log_index)), m.log_id().as_ref());
+        assert_eq!(btreeset! {0,1,2}, m.voter_ids().collect::<BTreeSet<_>>());
+    }
+
+    tracing::info!(log_index, "--- the membership is updated after a change");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.change_membership([0, 1], false).await?;
+        log_index += 2;
+
+        n3.wait(timeout()).applied_index(Some(log_index), "learner receives membership").await?;
+
+        let m = n3.current_membership().await;
+        assert_eq!(Some(&log_id(1, 0, log_index)), m.log_id().as_ref());
+        assert_eq!(btreeset! {0,1}, m.voter_ids().collect::<BTreeSet<_>>());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -1,9 +1,11 @@
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use anyhow::Result;
 use maplit::btreeset;
 use openraft::error::Fatal;
 use openraft::Config;
+use openraft::Vote;
 
 use crate::fixtures::log_id;
 use crate::fixtures::ut_harness;
@@ -31,6 +33,27 @@ async fn with_raft_state() -> Result<()> {
     let committed = n0.with_raft_state(|st| st.committed).await?;
     assert_eq!(committed, Some(log_id(1, 0, log_index)));
 
+    tracing::info!("--- read several fields in one call");
+    {
+        let (vote, voters, last, applied, purged) = n0
+            .with_raft_state(|st| {
+                (
+                    *st.vote_ref(),
+                    st.membership_state.effective().voter_ids().collect::<BTreeSet<_>>(),
+                    st.last_log_id().cloned(),
+                    st.applied().cloned(),
+                    st.last_purged_log_id().cloned(),
+                )
+            })
+            .await?;
+
+        assert_eq!(Vote::new_committed(1, 0), vote);
+        assert_eq!(btreeset! {0,1,2}, voters);
+        assert_eq!(Some(log_id(1, 0, log_index)), last);
+        assert_eq!(Some(log_id(1, 0, log_index)), applied);
+        assert_eq!(None, purged);
+    }
+
     tracing::info!("--- shutting down node 0");
     n0.shutdown().await?;
 
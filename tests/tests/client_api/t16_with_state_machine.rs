@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use maplit::btreeset;
@@ -7,14 +8,18 @@ use openraft::error::Fatal;
 use openraft::storage::RaftStateMachine;
 use openraft::storage::Snapshot;
 use openraft::storage::SnapshotMeta;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
 use openraft::Entry;
 use openraft::OptionalSend;
+use openraft::Raft;
 use openraft::RaftSnapshotBuilder;
 use openraft::RaftTypeConfig;
 use openraft::StorageError;
 use openraft::StoredMembership;
+use openraft_memstore::ClientRequest;
 use openraft_memstore::ClientResponse;
+use openraft_memstore::IntoMemClientRequest;
 use openraft_memstore::TypeConfig;
 
 use crate::fixtures::log_id;
@@ -65,6 +70,67 @@ async fn with_state_machine() -> Result<()> {
     Ok(())
 }
 
+/// [`Raft::with_state_machine()`](openraft::Raft::with_state_machine) runs after the committed
+/// entries that are held back for a larger apply batch are applied.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn with_state_machine_after_pending_apply() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            apply_batch_max_delay: 5_000,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = RaftRouter::new(config.clone());
+
+    let (log_store, sm) = openraft_memstore::new_mem_store();
+    let n0 = Raft::new(0, config, router, log_store, sm).await?;
+    n0.initialize(btreeset! {0}).await?;
+
+    n0.wait(timeout()).applied_index(Some(1), "leader blank log is applied").await?;
+
+    tracing::info!("--- write a log, it is committed but the apply is held back");
+    {
+        let r = n0.clone();
+        TypeConfig::spawn(async move { r.client_write(ClientRequest::make_request("foo", 1)).await });
+
+        let want = Some(log_id(1, 0, 2));
+        TypeConfig::timeout(Duration::from_millis(1_000), async {
+            while n0.with_raft_state(|st| st.committed).await? != want {
+                TypeConfig::sleep(Duration::from_millis(10)).await;
+            }
+            Ok::<_, Fatal<TypeConfig>>(())
+        })
+        .await??;
+
+        let applied = n0.with_raft_state(|st| st.applied().cloned()).await?;
+        assert_eq!(Some(log_id(1, 0, 1)), applied, "apply is held back");
+    }
+
+    tracing::info!("--- the SM request sees the committed log applied");
+    {
+        let applied = TypeConfig::timeout(
+            Duration::from_millis(1_000),
+            n0.with_state_machine(|sm: &mut MemStateMachine| {
+                Box::pin(async move {
+                    let d = sm.get_state_machine().await;
+                    d.last_applied_log
+                })
+            }),
+        )
+        .await??
+        .unwrap();
+        assert_eq!(applied, Some(log_id(1, 0, 2)));
+    }
+
+    n0.shutdown().await?;
+
+    Ok(())
+}
+
 /// Call [`Raft::with_state_machine()`](openraft::Raft::with_state_machine) with wrong type
 /// [`RaftStateMachine`]
 #[tracing::instrument]
@@ -137,3 +203,7 @@ async fn with_state_machine_wrong_sm_type() -> Result<()> {
 
     Ok(())
 }
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
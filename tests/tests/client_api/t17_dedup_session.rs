@@ -0,0 +1,107 @@
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A retried client write is applied only once, across leader changes and snapshot installation.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn dedup_session() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            election_timeout_min: 150,
+            election_timeout_max: 300,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    for id in [0, 1, 2] {
+        let (_log_store, sm) = router.get_storage_handle(&id)?;
+        sm.enable_dedup.store(true, Ordering::Relaxed);
+    }
+
+    tracing::info!(log_index, "--- write serial 1 and 2, then retry serial 2");
+    {
+        let resp = router.send_client_request(0, ClientRequest::make_request("c", 1)).await?;
+        assert_eq!(None, resp.0);
+
+        let resp = router.send_client_request(0, ClientRequest::make_request("c", 2)).await?;
+        assert_eq!(Some("request-1".to_string()), resp.0);
+
+        let resp = router.send_client_request(0, ClientRequest::make_request("c", 2)).await?;
+        assert_eq!(Some("request-1".to_string()), resp.0, "the cached response");
+        log_index += 3;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- transfer leader to node-1, retry serial 2 on the new leader"
+    );
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let n1 = router.get_raft_handle(&1)?;
+
+        n0.trigger().transfer_leader(1).await?;
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 become leader").await?;
+
+        let resp = router.send_client_request(1, ClientRequest::make_request("c", 2)).await?;
+        assert_eq!(Some("request-1".to_string()), resp.0, "the cached response");
+
+        let resp = router.send_client_request(1, ClientRequest::make_request("c", 1)).await?;
+        assert_eq!(None, resp.0, "a stale request is not applied");
+
+        // Blank log of the new leader and the 2 writes
+        log_index += 3;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "written").await?;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "applied").await?;
+
+            let (_log_store, sm) = router.get_storage_handle(&id)?;
+            let state = sm.get_state_machine().await;
+            assert_eq!(Some(&"request-2".to_string()), state.client_status.get("c"));
+        }
+    }
+
+    tracing::info!(log_index, "--- a learner that installs a snapshot keeps the sessions");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().snapshot().await?;
+        n1.wait(timeout()).snapshot(log_id(2, 1, log_index), "snapshot built").await?;
+        n1.wait(timeout()).purged(Some(log_id(2, 1, log_index)), "purged").await?;
+
+        router.new_raft_node(3).await;
+        let (_log_store, sm) = router.get_storage_handle(&3)?;
+        sm.enable_dedup.store(true, Ordering::Relaxed);
+
+        router.add_learner(1, 3).await?;
+        log_index += 1;
+        router.wait(&3, timeout()).applied_index(Some(log_index), "learner caught up").await?;
+
+        let state = sm.get_state_machine().await;
+        assert_eq!(Some(2), state.sessions.get("c").map(|s| s.last_serial));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
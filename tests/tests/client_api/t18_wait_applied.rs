@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A follower waits for a write to be applied locally with `wait_applied()`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn wait_applied() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- the applied watch channel holds the last applied log id");
+    {
+        let applied = *n1.applied_index_watch().borrow();
+        assert_eq!(Some(log_id(1, 0, log_index)), applied);
+    }
+
+    tracing::info!(log_index, "--- wait on node-1 for a write that is applied slowly");
+    {
+        let (_log_store, sm) = router.get_storage_handle(&1)?;
+        sm.block.set_blocking(BlockOperation::Apply, Duration::from_millis(300));
+
+        let resp = n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+        assert_eq!(log_id(1, 0, log_index + 1), resp.log_id);
+
+        let applied = TypeConfig::timeout(timeout(), n1.wait_applied(&resp.log_id)).await??;
+        assert_eq!(Some(resp.log_id), applied);
+
+        let state = sm.get_state_machine().await;
+        assert_eq!(Some(&"request-1".to_string()), state.client_status.get("foo"));
+
+        sm.block.clear_blocking(BlockOperation::Apply);
+    }
+
+    tracing::info!(log_index, "--- waiting for a log that is not written does not return");
+    {
+        let res = TypeConfig::timeout(Duration::from_millis(200), n1.wait_applied(&log_id(1, 0, 100))).await;
+        assert!(res.is_err(), "timeout");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Duration {
+    Duration::from_millis(1_000)
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use openraft::metrics::WaitError;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// [`Raft::wait_for_leader()`](openraft::Raft::wait_for_leader) resolves once a leader is known,
+/// and times out if there is none.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn wait_for_leader() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    router.new_raft_node(0).await;
+    router.new_raft_node(1).await;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!("--- no leader before initialization");
+    {
+        let res = n1.wait_for_leader(Some(Duration::from_millis(200))).await;
+        assert!(matches!(res, Err(WaitError::Timeout(_, _))), "got: {:?}", res);
+    }
+
+    tracing::info!("--- a waiting node sees the leader after initialization");
+    {
+        let waiting = TypeConfig::spawn(async move { n1.wait_for_leader(timeout()).await });
+
+        router.initialize(0).await?;
+
+        let (leader_id, _node) = waiting.await??;
+        assert_eq!(0, leader_id);
+    }
+
+    tracing::info!("--- the leader itself is returned on the leader");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        assert_eq!((0, ()), n0.wait_for_leader(timeout()).await?);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
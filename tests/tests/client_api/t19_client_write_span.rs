@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::CorrelationId;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The replication RPCs carrying a client write are sent in the span of the write, which is the
+/// batch span for `client_write_many()`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_span() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a write is sent in its span");
+    {
+        let req = ClientRequest::make_request("foo", 1);
+        n0.client_write_with_correlation_id(req, CorrelationId::new(1)).await?;
+
+        let names = router.get_sent_span_names(1);
+        assert!(
+            names.contains(&"client_write_with_correlation_id"),
+            "sent spans: {:?}",
+            names
+        );
+    }
+
+    tracing::info!(log_index, "--- a batch is sent in the batch span");
+    {
+        let reqs = (2..5).map(|i| ClientRequest::make_request("foo", i)).collect();
+        n0.client_write_many(reqs).await?;
+
+        let names = router.get_sent_span_names(1);
+        assert!(names.contains(&"client_write_many"), "sent spans: {:?}", names);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::ClientWriteTimeout;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A write that can not be committed in time returns a `Timeout` with the assigned log id, and the
+/// entry is still committed after the cluster recovers.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_with_timeout() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a write within the deadline succeeds");
+    {
+        let resp = n0.client_write_with_timeout(ClientRequest::make_request("foo", 1), timeout()).await?;
+        log_index += 1;
+        assert_eq!(log_id(1, 0, log_index), *resp.log_id());
+    }
+
+    tracing::info!(log_index, "--- isolate followers, a write can not be committed");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let res = n0.client_write_with_timeout(ClientRequest::make_request("foo", 2), timeout()).await;
+        log_index += 1;
+
+        let err = res.unwrap_err();
+        assert_eq!(
+            RaftError::APIError(ClientWriteError::Timeout(ClientWriteTimeout::new(
+                Some(log_id(1, 0, log_index)),
+                timeout()
+            ))),
+            err
+        );
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the timed out entry is committed after the network recovers"
+    );
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        let resp = n0.client_write_with_timeout(ClientRequest::make_request("foo", 3), timeout()).await?;
+        log_index += 1;
+        assert_eq!(log_id(1, 0, log_index), *resp.log_id());
+        assert_eq!(Some("request-2"), resp.response().0.as_deref());
+    }
+
+    tracing::info!(log_index, "--- a write to a follower is rejected before the deadline");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let err = n1.client_write_with_timeout(ClientRequest::make_request("foo", 4), timeout()).await.unwrap_err();
+        assert!(err.forward_to_leader().is_some(), "got: {}", err);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Duration {
+    Duration::from_millis(500)
+}
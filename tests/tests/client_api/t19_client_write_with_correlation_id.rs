@@ -0,0 +1,80 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::CorrelationId;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The correlation id of a client write is passed to the network along with the replication RPCs
+/// that carry the write.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_with_correlation_id() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write with a correlation id");
+    {
+        let correlation_id = CorrelationId::new(1234);
+        let resp = n0.client_write_with_correlation_id(ClientRequest::make_request("foo", 1), correlation_id).await?;
+        log_index += 1;
+        assert_eq!(log_id(1, 0, log_index), *resp.log_id());
+
+        for target in [1, 2] {
+            let sent = router.get_sent_correlation_ids(target);
+            assert!(
+                sent.contains(&correlation_id),
+                "target {} receives {}: {:?}",
+                target,
+                correlation_id,
+                sent
+            );
+        }
+    }
+
+    tracing::info!(log_index, "--- a write without a correlation id is assigned one");
+    {
+        n0.client_write(ClientRequest::make_request("foo", 2)).await?;
+
+        let sent = router.get_sent_correlation_ids(1);
+        assert!(
+            sent.iter().any(|id| *id != CorrelationId::new(1234)),
+            "a generated id is sent: {:?}",
+            sent
+        );
+    }
+
+    tracing::info!(log_index, "--- every write of a batch is assigned one");
+    {
+        let before = router.get_sent_correlation_ids(1);
+
+        let reqs = (0..3).map(|i| ClientRequest::make_request("foo", 10 + i)).collect::<Vec<_>>();
+        n0.client_write_many(reqs).await?;
+
+        let sent = router.get_sent_correlation_ids(1);
+        let new_ids = sent.iter().filter(|id| !before.contains(id)).collect::<BTreeSet<_>>();
+        assert_eq!(3, new_ids.len(), "ids of the batch are sent: {:?}", sent);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::CheckIsLeaderError;
+use openraft::error::RaftError;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// [`Raft::get_read_ticket()`](openraft::Raft::get_read_ticket) returns the read log id confirmed
+/// by a quorum, and a ticket that resolves once the local state machine applies upto it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn read_ticket() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 3).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- get a read ticket on the leader");
+    {
+        let before = TypeConfig::now();
+        let ticket = n0.get_read_ticket().await?;
+
+        assert_eq!(Some(log_index), ticket.read_log_id().index());
+        assert!(ticket.requested_at() >= before);
+        assert!(ticket.is_ready());
+
+        let applied = ticket.wait_ready().await?;
+        assert_eq!(Some(log_index), applied.index());
+        assert_eq!(Some(log_index), ticket.applied_watch().borrow().index());
+    }
+
+    tracing::info!(log_index, "--- a follower can not issue a read ticket");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.get_read_ticket().await;
+
+        let err = res.expect_err("follower should not issue a read ticket");
+        match err {
+            RaftError::APIError(CheckIsLeaderError::ForwardToLeader(fwd)) => {
+                assert_eq!(Some(0), fwd.leader_id);
+            }
+            _ => panic!("expect ForwardToLeader, got: {:?}", err),
+        }
+    }
+
+    Ok(())
+}
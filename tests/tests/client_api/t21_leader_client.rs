@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::client::LeaderClient;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A `LeaderClient` over the `Raft` handles of a cluster sends writes and reads to the leader,
+/// following `ForwardToLeader` errors when the leader changes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn leader_client() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let client = LeaderClient::new([0, 1, 2].map(|id| (id, router.get_raft_handle(&id).unwrap())));
+
+    tracing::info!(log_index, "--- a write sent to a follower is forwarded to the leader");
+    {
+        client.set_leader(Some(1));
+
+        let resp = client.client_write(ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+
+        assert_eq!(log_index, resp.log_id.index);
+        assert_eq!(Some(0), client.leader());
+    }
+
+    tracing::info!(log_index, "--- transfer the leadership to node 2");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().transfer_leader(2).await?;
+
+        let n2 = router.get_raft_handle(&2)?;
+        n2.wait(timeout()).state(ServerState::Leader, "node-2 becomes leader").await?;
+        n0.wait(timeout()).current_leader(2, "node-0 knows the new leader").await?;
+        log_index += 1;
+    }
+
+    tracing::info!(log_index, "--- a read follows the stale leader hint to the new leader");
+    {
+        let (leader_id, read_log_id) = client.ensure_linearizable().await?;
+
+        assert_eq!(2, leader_id);
+        assert_eq!(Some(log_index), read_log_id.map(|x| x.index));
+        assert_eq!(Some(2), client.leader());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use openraft::Config;
+use openraft::EngineDecision;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The last decisions of the engine can be read with `Raft::decision_trace()`, if
+/// `Config::decision_trace_capacity` is not 0.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn decision_trace() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            decision_trace_capacity: 8,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- a client write is recorded with the commands it emits");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+
+        let trace: Vec<EngineDecision> = n0.decision_trace().await?;
+        for d in trace.iter() {
+            tracing::info!("decision: {}", d);
+        }
+
+        assert!(trace.len() <= 8);
+
+        let write = trace.iter().find(|d| d.input.starts_with("ClientWriteRequest")).unwrap();
+        assert!(write.commands.iter().any(|c| c.starts_with("AppendInputEntries")));
+    }
+
+    tracing::info!(log_index, "--- disabled by default");
+    {
+        let mut router = RaftRouter::new(Arc::new(Config::default().validate()?));
+        router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        let trace = n0.decision_trace().await?;
+        assert!(trace.is_empty());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::last_quorum_acked_time()` returns when the leader is last acknowledged by a quorum.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn last_quorum_acked_time() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- a follower has no quorum acked time");
+    {
+        assert_eq!(None, n1.last_quorum_acked_time().await?);
+    }
+
+    tracing::info!(log_index, "--- the leader is acknowledged by a quorum");
+    let acked = {
+        let acked = n0.last_quorum_acked_time().await?.unwrap();
+        assert!(acked <= TypeConfig::now());
+        acked
+    };
+
+    tracing::info!(log_index, "--- a heartbeat refreshes the quorum acked time");
+    let acked = {
+        TypeConfig::sleep(Duration::from_millis(100)).await;
+        n0.trigger().heartbeat().await?;
+        TypeConfig::sleep(Duration::from_millis(100)).await;
+
+        let refreshed = n0.last_quorum_acked_time().await?.unwrap();
+        assert!(refreshed > acked);
+        refreshed
+    };
+
+    tracing::info!(log_index, "--- without a quorum, it does not refresh");
+    {
+        router.remove_node(1);
+        router.remove_node(2);
+
+        n0.trigger().heartbeat().await?;
+        TypeConfig::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(Some(acked), n0.last_quorum_acked_time().await?);
+    }
+
+    Ok(())
+}
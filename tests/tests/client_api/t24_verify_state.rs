@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::verify_state()` reports the internal consistency checks of a node.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn verify_state() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 10).await?;
+    router.wait(&1, None).applied_index(Some(log_index), "node-1 applied").await?;
+
+    tracing::info!(log_index, "--- the leader checks the replication progress too");
+    {
+        let report = router.get_raft_handle(&0)?.verify_state().await?;
+        tracing::info!("leader report: {}", report);
+
+        assert!(report.is_ok(), "{}", report);
+        assert!(report.checks.iter().any(|c| c.name == "progress of 2 <= last_log"));
+    }
+
+    tracing::info!(log_index, "--- a follower checks its local state only");
+    {
+        let report = router.get_raft_handle(&1)?.verify_state().await?;
+        tracing::info!("follower report: {}", report);
+
+        assert!(report.is_ok(), "{}", report);
+        assert!(report.checks.iter().any(|c| c.name == "committed <= last_log"));
+        assert!(report.checks.iter().all(|c| !c.name.starts_with("progress of")));
+    }
+
+    Ok(())
+}
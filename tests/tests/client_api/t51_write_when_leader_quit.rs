@@ -7,10 +7,12 @@ use openraft::error::ClientWriteError;
 use openraft::error::ForwardToLeader;
 use openraft::error::RaftError;
 use openraft::raft::AppendEntriesRequest;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
 use openraft::Vote;
 use openraft_memstore::ClientRequest;
 use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
 use tokio::sync::oneshot;
 
 use crate::fixtures::log_id;
@@ -49,14 +51,14 @@ async fn write_when_leader_quit_and_log_revert() -> Result<()> {
     tracing::info!(log_index, "--- write a log in another task");
     {
         let n0 = router.get_raft_handle(&0)?;
-        tokio::spawn(async move {
+        TypeConfig::spawn(async move {
             let res = n0.client_write(ClientRequest::make_request("cli", 1)).await;
             tx.send(res).unwrap();
         });
     }
 
     // wait for log to be appended on leader, and response channel is installed.
-    tokio::time::sleep(Duration::from_millis(500)).await;
+    TypeConfig::sleep(Duration::from_millis(500)).await;
 
     tracing::info!(log_index, "--- force node 0 to give up leadership");
     {
@@ -124,14 +126,14 @@ async fn write_when_leader_switched() -> Result<()> {
     tracing::info!(log_index, "--- write a log in another task");
     {
         let n0 = router.get_raft_handle(&0)?;
-        tokio::spawn(async move {
+        TypeConfig::spawn(async move {
             let res = n0.client_write(ClientRequest::make_request("cli", 1)).await;
             tx.send(res).unwrap();
         });
     }
 
     // wait for log to be appended on leader, and response channel is installed.
-    tokio::time::sleep(Duration::from_millis(500)).await;
+    TypeConfig::sleep(Duration::from_millis(500)).await;
 
     tracing::info!(log_index, "--- force node 0 to give up leadership, inform it to commit");
     {
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig as MemConfig;
+
+use crate::fixtures::linearizability::History;
+use crate::fixtures::linearizability::HistoryClient;
+use crate::fixtures::linearizability::Input;
+use crate::fixtures::linearizability::Outcome;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The checker accepts a linearizable history and reports a minimal counterexample for a stale
+/// read.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn linearizability_checker() -> anyhow::Result<()> {
+    let w = |v: &str| Input::Write(v.to_string());
+    let ok = |v: Option<&str>| Outcome::Ok(v.map(|s| s.to_string()));
+
+    tracing::info!("--- concurrent writes and reads, a timed out write takes effect");
+    {
+        let h = History::new();
+        let a = h.begin(0, "x", w("1"));
+        let b = h.begin(1, "x", w("2"));
+        h.end(b, ok(None));
+        h.end(a, ok(Some("2")));
+        let c = h.begin(2, "x", w("3"));
+        h.end(c, Outcome::Unknown);
+        let d = h.begin(0, "x", Input::Read);
+        h.end(d, ok(Some("3")));
+
+        h.check()?;
+    }
+
+    tracing::info!("--- a read that does not see a completed write is not linearizable");
+    {
+        let h = History::new();
+        let a = h.begin(0, "x", w("1"));
+        h.end(a, ok(None));
+        let b = h.begin(0, "y", w("1"));
+        h.end(b, ok(None));
+        let c = h.begin(1, "x", Input::Read);
+        h.end(c, ok(None));
+        let d = h.begin(1, "x", w("2"));
+        h.end(d, ok(Some("1")));
+
+        let err = h.check().unwrap_err().to_string();
+        assert_eq!(
+            err,
+            [
+                "history of key x is not linearizable, counterexample:",
+                "  client-0 [1, 2] write(x, 1) -> None",
+                "  client-1 [5, 6] read(x) -> None",
+            ]
+            .join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+/// Reads and writes of concurrent clients are linearizable while RPCs are duplicated and reordered
+/// and the leader is partitioned away.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn linearizability_under_chaos() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 200,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- bring up cluster of 3 nodes");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    router.set_rpc_disorder(0.2, 0.2, Duration::from_millis(20), 0);
+
+    let history = History::new();
+
+    tracing::info!("--- run 3 clients, partition the leader away in the meantime");
+    {
+        let mut handles = vec![];
+        for id in 0..3 {
+            let mut client = HistoryClient::new(id, router.clone(), history.clone(), 0);
+            handles.push(MemConfig::spawn(async move {
+                for i in 0..30 {
+                    let key = ["a", "b"][i % 2];
+                    if rand::random::<bool>() {
+                        client.write(key, &format!("{}-{}", id, i)).await;
+                    } else {
+                        client.read(key).await;
+                    }
+                }
+            }));
+        }
+
+        MemConfig::sleep(Duration::from_millis(200)).await;
+        router.partition([btreeset! {0}, btreeset! {1, 2}]);
+
+        MemConfig::sleep(Duration::from_millis(800)).await;
+        router.heal();
+
+        for h in handles {
+            h.await?;
+        }
+    }
+
+    router.clear_rpc_disorder();
+
+    tracing::info!("--- check the history");
+    {
+        let ops = history.operations();
+        tracing::info!("recorded {} operations", ops.len());
+        assert!(ops.iter().any(|op| op.ret.is_some()), "some operations complete");
+
+        history.check()?;
+    }
+
+    Ok(())
+}
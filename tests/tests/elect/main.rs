@@ -9,3 +9,5 @@ mod fixtures;
 
 mod t10_elect_compare_last_log;
 mod t11_elect_seize_leadership;
+mod t12_fast_initial_election;
+mod t20_elect_network_partition;
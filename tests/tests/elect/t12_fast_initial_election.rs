@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The node that calls `initialize()` campaigns at once, without waiting for an election timeout.
+/// Other nodes do not campaign, but follow it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn fast_initial_election() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            election_timeout_min: 2_000,
+            election_timeout_max: 2_001,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+    for id in [0, 1, 2] {
+        router.new_raft_node(id).await;
+    }
+
+    router.initialize(0).await?;
+
+    router.wait(&0, timeout()).state(ServerState::Leader, "elected before an election timeout").await?;
+
+    for id in [1, 2] {
+        router
+            .wait(&id, timeout())
+            .state(ServerState::Follower, "other nodes follow the initializing node")
+            .await?;
+
+        let metrics = router.get_raft_handle(&id)?.metrics().borrow().clone();
+        assert_eq!(1, metrics.current_term, "node-{} did not campaign", id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
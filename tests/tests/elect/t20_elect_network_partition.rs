@@ -0,0 +1,162 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Split-brain: the old leader in the minority can not commit, the majority elects a new leader,
+/// and the old leader steps down after the partition is healed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn elect_network_partition_split_brain() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2,3,4");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2,3,4}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- partition into {{0,1}} and {{2,3,4}}");
+    router.partition([btreeset! {0,1}, btreeset! {2,3,4}]);
+
+    tracing::info!(log_index, "--- old leader 0 can not commit in the minority");
+    {
+        let res = TypeConfig::timeout(Duration::from_millis(500), router.client_request(0, "foo", 1)).await;
+        assert!(res.is_err(), "write to the minority should not be committed");
+    }
+
+    tracing::info!(log_index, "--- node 2 is elected by the majority");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        n2.trigger().elect().await?;
+        n2.wait(timeout()).state(ServerState::Leader, "node 2 becomes leader").await?;
+
+        // the blank log of the new leader
+        log_index += 1;
+        router.client_request(2, "bar", 1).await?;
+        log_index += 1;
+
+        for id in [2, 3, 4] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "committed by the majority").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- heal, old leader 0 steps down and the cluster converges");
+    {
+        router.heal();
+        router.client_request(2, "bar", 2).await?;
+        log_index += 1;
+
+        for id in [0, 1, 2, 3, 4] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "converged").await?;
+        }
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.wait(timeout()).current_leader(2, "node 0 follows node 2").await?;
+        n0.wait(timeout()).state(ServerState::Follower, "node 0 steps down").await?;
+    }
+
+    Ok(())
+}
+
+/// Quorum loss: a leader that can not reach any other voter can not commit until healed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn elect_network_partition_quorum_loss() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- partition every node into its own group");
+    router.partition([btreeset! {0}, btreeset! {1}, btreeset! {2}]);
+
+    let n0 = router.get_raft_handle(&0)?;
+    let write = {
+        let router = router.clone();
+        TypeConfig::spawn(async move { router.client_request(0, "foo", 1).await })
+    };
+    log_index += 1;
+
+    let res = n0.wait(Some(Duration::from_millis(500))).applied_index(Some(log_index), "can not commit").await;
+    assert!(res.is_err(), "a leader without quorum should not commit");
+
+    tracing::info!(log_index, "--- heal, the pending write is committed");
+    router.heal();
+    write.await??;
+
+    for id in [0, 1, 2] {
+        router.wait(&id, timeout()).applied_index(Some(log_index), "committed after heal").await?;
+    }
+
+    Ok(())
+}
+
+/// Asymmetric partition: node 0 and node 2 can not reach each other, but both reach node 1, so the
+/// leader still commits with node 1.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn elect_network_partition_asymmetric() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- partition into {{0,1}} and {{1,2}}");
+    router.partition([btreeset! {0,1}, btreeset! {1,2}]);
+
+    router.client_request(0, "foo", 1).await?;
+    log_index += 1;
+
+    for id in [0, 1] {
+        router.wait(&id, timeout()).applied_index(Some(log_index), "committed with node 1").await?;
+    }
+
+    let res = router
+        .wait(&2, Some(Duration::from_millis(500)))
+        .applied_index(Some(log_index), "unreachable")
+        .await;
+    assert!(res.is_err(), "node 2 is unreachable from the leader");
+
+    tracing::info!(log_index, "--- heal, node 2 catches up");
+    router.heal();
+    router.wait(&2, timeout()).applied_index(Some(log_index), "node 2 catches up").await?;
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2000))
+}
@@ -0,0 +1,306 @@
+//! A randomized test that keeps injecting faults into a [`RaftRouter`] cluster and asserts the core
+//! invariants after every step.
+//!
+//! The number of steps and the seed are read from the environment variables
+//! `OPENRAFT_CHAOS_STEPS` and `OPENRAFT_CHAOS_SEED`, so that a long run can be started with, e.g.:
+//! `OPENRAFT_CHAOS_STEPS=10000 cargo test --test chaos`, and a failure can be replayed with the
+//! seed it reports. Note that task scheduling is not controlled by the seed, thus a replay
+//! injects the same faults but may not reproduce the same history.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::env;
+use std::fmt;
+use std::time::Duration;
+
+use anyhow::Context;
+use openraft::alias::LogIdOf;
+use openraft::entry::RaftEntry;
+use openraft::storage::RaftLogStorage;
+use openraft::type_config::TypeConfigExt;
+use openraft::LogIdOptionExt;
+use openraft::RaftLogReader;
+use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::MemNodeId;
+use openraft_memstore::TypeConfig;
+use openraft_memstore::TypeConfig as MemConfig;
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use rand::Rng;
+use rand::SeedableRng;
+
+use crate::fixtures::RaftRouter;
+
+/// A fault or an operation injected by [`Chaos`] in one step.
+#[derive(Debug, Clone)]
+pub enum Step {
+    Write { count: u64 },
+    Restart { node: MemNodeId },
+    Partition { groups: Vec<BTreeSet<MemNodeId>> },
+    Heal,
+    Snapshot { node: MemNodeId },
+    ChangeMembership { voters: BTreeSet<MemNodeId> },
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Write { count } => write!(f, "write {} entries", count),
+            Step::Restart { node } => write!(f, "restart node {}", node),
+            Step::Partition { groups } => write!(f, "partition {:?}", groups),
+            Step::Heal => write!(f, "heal"),
+            Step::Snapshot { node } => write!(f, "snapshot on node {}", node),
+            Step::ChangeMembership { voters } => write!(f, "change voters to {:?}", voters),
+        }
+    }
+}
+
+/// Runs random [`Step`]s on a cluster and checks [`Invariants`] after every step.
+pub struct Chaos {
+    router: RaftRouter,
+    nodes: BTreeSet<MemNodeId>,
+    rng: StdRng,
+    seed: u64,
+    serial: u64,
+    invariants: Invariants,
+}
+
+impl Chaos {
+    /// Create a chaos runner on a cluster of `nodes`, seeded by `OPENRAFT_CHAOS_SEED` or a random
+    /// seed.
+    pub fn new(router: RaftRouter, nodes: BTreeSet<MemNodeId>) -> Self {
+        let seed = env::var("OPENRAFT_CHAOS_SEED")
+            .ok()
+            .map(|s| s.parse::<u64>().expect("OPENRAFT_CHAOS_SEED must be u64"))
+            .unwrap_or_else(rand::random);
+
+        tracing::info!("chaos seed: {}", seed);
+
+        Self {
+            router,
+            nodes,
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            serial: 0,
+            invariants: Invariants::default(),
+        }
+    }
+
+    /// The number of steps to run, `OPENRAFT_CHAOS_STEPS` or `default`.
+    pub fn steps(default: usize) -> usize {
+        env::var("OPENRAFT_CHAOS_STEPS")
+            .ok()
+            .map(|s| s.parse::<usize>().expect("OPENRAFT_CHAOS_STEPS must be usize"))
+            .unwrap_or(default)
+    }
+
+    /// Run `n` random steps, then heal the network and wait for all nodes to converge.
+    ///
+    /// An invariant violation fails with the seed and the step that detects it.
+    pub async fn run(&mut self, n: usize) -> anyhow::Result<()> {
+        for i in 0..n {
+            let step = self.next_step();
+            tracing::info!("--- chaos step {}: {}", i, step);
+
+            self.apply(&step).await.with_context(|| format!("seed: {}, step {}: {}", self.seed, i, step))?;
+
+            self.invariants
+                .check(&self.router)
+                .await
+                .with_context(|| format!("seed: {}, after step {}: {}", self.seed, i, step))?;
+        }
+
+        self.converge().await.with_context(|| format!("seed: {}, converge", self.seed))
+    }
+
+    fn next_step(&mut self) -> Step {
+        let node = *self.nodes.iter().choose(&mut self.rng).unwrap();
+
+        match self.rng.gen_range(0..10) {
+            0..=3 => Step::Write {
+                count: self.rng.gen_range(1..=5),
+            },
+            4 => Step::Restart { node },
+            5 => {
+                let minority = self.nodes.iter().copied().choose_multiple(&mut self.rng, self.nodes.len() / 2);
+                let minority = minority.into_iter().collect::<BTreeSet<_>>();
+                let majority = self.nodes.difference(&minority).copied().collect();
+                Step::Partition {
+                    groups: vec![minority, majority],
+                }
+            }
+            6 | 7 => Step::Heal,
+            8 => Step::Snapshot { node },
+            _ => {
+                let n = self.rng.gen_range(self.nodes.len() / 2 + 1..=self.nodes.len());
+                let voters = self.nodes.iter().copied().choose_multiple(&mut self.rng, n);
+                Step::ChangeMembership {
+                    voters: voters.into_iter().collect(),
+                }
+            }
+        }
+    }
+
+    /// Apply a step. Client requests may fail because of the injected faults, which is ignored.
+    async fn apply(&mut self, step: &Step) -> anyhow::Result<()> {
+        let timeout = Duration::from_millis(500);
+
+        match step {
+            Step::Write { count } => {
+                for _ in 0..*count {
+                    let Some(leader) = self.leader() else {
+                        break;
+                    };
+                    self.serial += 1;
+                    let req = ClientRequest::make_request("chaos", self.serial);
+                    let _ = MemConfig::timeout(timeout, self.router.send_client_request(leader, req)).await;
+                }
+            }
+            Step::Restart { node } => {
+                let applied = self.router.get_metrics(node)?.last_applied;
+                self.router.restart_node(*node).await?;
+
+                // The metrics are not reported until the restarted node starts running.
+                self.router
+                    .wait(node, Some(timeout))
+                    .applied_index_at_least(applied.index(), "restarted node recovers applied")
+                    .await?;
+            }
+            Step::Partition { groups } => {
+                self.router.partition(groups.clone());
+            }
+            Step::Heal => {
+                self.router.heal();
+            }
+            Step::Snapshot { node } => {
+                let n = self.router.get_raft_handle(node)?;
+                n.trigger().snapshot().await?;
+            }
+            Step::ChangeMembership { voters } => {
+                if let Some(leader) = self.leader() {
+                    let n = self.router.get_raft_handle(&leader)?;
+                    let _ = MemConfig::timeout(timeout, n.change_membership(voters.clone(), true)).await;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the node in leader state with the greatest term.
+    ///
+    /// A leader may not be a voter, e.g., it is removed from voters but retained as a learner, in
+    /// which case it does not report itself as `current_leader`.
+    fn leader(&self) -> Option<MemNodeId> {
+        let metrics = self.router.latest_metrics();
+        let leaders = metrics.iter().filter(|m| m.state == ServerState::Leader);
+        leaders.max_by_key(|m| m.current_term).map(|m| m.id)
+    }
+
+    /// Heal the network, wait for a leader to commit a write and for every node to apply it.
+    async fn converge(&mut self) -> anyhow::Result<()> {
+        self.router.heal();
+
+        let timeout = Duration::from_millis(10_000);
+        let start = MemConfig::now();
+
+        self.serial += 1;
+        let last_applied = loop {
+            anyhow::ensure!(
+                MemConfig::now() - start < timeout,
+                "no leader commits a write in {:?}",
+                timeout
+            );
+
+            if let Some(leader) = self.leader() {
+                let req = ClientRequest::make_request("chaos", self.serial);
+                if let Ok(Ok(_)) = MemConfig::timeout(timeout, self.router.send_client_request(leader, req)).await {
+                    break self.router.get_metrics(&leader)?.last_applied;
+                }
+            }
+
+            MemConfig::sleep(Duration::from_millis(50)).await;
+        };
+
+        for id in self.nodes.clone() {
+            self.router
+                .wait(&id, Some(timeout))
+                .applied_index_at_least(last_applied.index(), "converge")
+                .await?;
+        }
+
+        self.invariants.check(&self.router).await
+    }
+}
+
+/// The invariants checked by [`Chaos`], accumulated from all the observations of the cluster.
+#[derive(Debug, Default)]
+pub struct Invariants {
+    /// The leader observed for every term.
+    ///
+    /// Without `single-term-leader`, there can be more than one leader in a term, with different
+    /// leader ids, thus the key is the leader id.
+    leaders: BTreeMap<String, MemNodeId>,
+
+    /// The log id of every committed entry that has been observed.
+    committed: BTreeMap<u64, LogIdOf<TypeConfig>>,
+
+    /// The last applied log id of every node.
+    applied: BTreeMap<MemNodeId, Option<LogIdOf<TypeConfig>>>,
+}
+
+impl Invariants {
+    /// Check that:
+    /// - There is at most one leader per term;
+    /// - A committed entry is never lost or replaced: every node applies the same log id at an
+    ///   index;
+    /// - The applied log id of every node never decreases.
+    pub async fn check(&mut self, router: &RaftRouter) -> anyhow::Result<()> {
+        for m in router.latest_metrics() {
+            if m.state == ServerState::Leader {
+                #[cfg(feature = "single-term-leader")]
+                let key = m.current_term.to_string();
+                #[cfg(not(feature = "single-term-leader"))]
+                let key = m.vote.leader_id.to_string();
+
+                let prev = self.leaders.entry(key.clone()).or_insert(m.id);
+                anyhow::ensure!(*prev == m.id, "two leaders in {}: {} and {}", key, prev, m.id);
+            }
+
+            let prev = self.applied.get(&m.id).cloned().flatten();
+            anyhow::ensure!(
+                m.last_applied >= prev,
+                "applied of node {} decreased from {:?} to {:?}",
+                m.id,
+                prev,
+                m.last_applied
+            );
+            self.applied.insert(m.id, m.last_applied);
+
+            let Some(last_applied) = m.last_applied else {
+                continue;
+            };
+
+            // Entries up to `last_applied` are committed, compare those still in the log.
+            let (mut log_store, _) = router.get_storage_handle(&m.id)?;
+            let entries = log_store.get_log_reader().await.try_get_log_entries(..=last_applied.index).await?;
+
+            for ent in entries {
+                let log_id = ent.log_id();
+                let committed = self.committed.entry(log_id.index).or_insert(log_id);
+                anyhow::ensure!(
+                    *committed == log_id,
+                    "committed entry at index {} changed from {} to {} on node {}",
+                    log_id.index,
+                    committed,
+                    log_id,
+                    m.id
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,369 @@
+//! Record the history of client operations on the memstore KV and check it for linearizability.
+//!
+//! A memstore write sets the status of a client, which is used as a key, and returns the previous
+//! value, thus a write is a swap on a register. A read returns the value of a key after
+//! [`Raft::ensure_linearizable()`](openraft::Raft::ensure_linearizable).
+//!
+//! The checker is a Wing & Gong style search: it tries to find a sequential order of the operations
+//! that respects their real-time order and explains every output. Keys are checked independently.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use openraft::alias::AsyncRuntimeOf;
+use openraft::error::CheckIsLeaderError;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::type_config::TypeConfigExt;
+use openraft::AsyncRuntime;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::MemNodeId;
+use openraft_memstore::TypeConfig as MemConfig;
+use rand::Rng;
+
+use crate::fixtures::RaftRouter;
+
+/// The value of a key, `None` if the key is not set.
+pub type Value = Option<String>;
+
+/// The input of an operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// Set a key to the value and return the previous value.
+    Write(String),
+    /// Return the value of a key.
+    Read,
+}
+
+/// An operation recorded in a [`History`].
+///
+/// Time is a logical clock shared by all the clients of a history, thus `call` and `ret` of all
+/// operations are unique.
+#[derive(Debug, Clone)]
+pub struct Operation {
+    pub client: u64,
+    pub key: String,
+    pub input: Input,
+
+    /// The output, or `None` if the outcome is unknown, e.g., a write times out and may or may
+    /// not have taken effect.
+    pub output: Option<Value>,
+
+    pub call: u64,
+
+    /// The time it returns, `None` if the outcome is unknown.
+    pub ret: Option<u64>,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "client-{} [{}, ", self.client, self.call)?;
+        match self.ret {
+            Some(ret) => write!(f, "{}]", ret)?,
+            None => write!(f, "-]")?,
+        }
+        match &self.input {
+            Input::Write(v) => write!(f, " write({}, {})", self.key, v)?,
+            Input::Read => write!(f, " read({})", self.key)?,
+        }
+        match &self.output {
+            Some(v) => write!(f, " -> {:?}", v),
+            None => write!(f, " -> ?"),
+        }
+    }
+}
+
+impl Operation {
+    /// Apply this operation to `state` and return the new state, or `None` if the output can not
+    /// be explained by `state`.
+    fn step(&self, state: &Value) -> Option<Value> {
+        if let Some(output) = &self.output {
+            if output != state {
+                return None;
+            }
+        }
+
+        match &self.input {
+            Input::Write(v) => Some(Some(v.clone())),
+            Input::Read => Some(state.clone()),
+        }
+    }
+}
+
+/// The outcome of an operation, reported to [`History::end()`].
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    /// The operation completed with the output.
+    Ok(Value),
+    /// The operation may or may not have taken effect.
+    Unknown,
+    /// The operation did not take effect, it is removed from the history.
+    NoEffect,
+}
+
+#[derive(Debug, Default)]
+struct HistoryInner {
+    clock: u64,
+    ops: Vec<Option<Operation>>,
+}
+
+/// The history of operations recorded by [`HistoryClient`]s.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    inner: Arc<Mutex<HistoryInner>>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the invocation of an operation and return its index, for [`Self::end()`].
+    pub fn begin(&self, client: u64, key: impl ToString, input: Input) -> usize {
+        let mut inner = self.inner.lock().unwrap();
+        inner.clock += 1;
+        let op = Operation {
+            client,
+            key: key.to_string(),
+            input,
+            output: None,
+            call: inner.clock,
+            ret: None,
+        };
+        inner.ops.push(Some(op));
+        inner.ops.len() - 1
+    }
+
+    /// Record the outcome of an operation started with [`Self::begin()`].
+    pub fn end(&self, index: usize, outcome: Outcome) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.clock += 1;
+        let ret = inner.clock;
+
+        match outcome {
+            Outcome::Ok(v) => {
+                let op = inner.ops[index].as_mut().unwrap();
+                op.output = Some(v);
+                op.ret = Some(ret);
+            }
+            Outcome::Unknown => {}
+            Outcome::NoEffect => inner.ops[index] = None,
+        }
+    }
+
+    /// Return the recorded operations, sorted by invocation time.
+    pub fn operations(&self) -> Vec<Operation> {
+        self.inner.lock().unwrap().ops.iter().flatten().cloned().collect()
+    }
+
+    /// Check if the history is linearizable.
+    ///
+    /// If not, the error contains a minimal counterexample: the operations on one key of the
+    /// shortest prefix of the history that is not linearizable.
+    pub fn check(&self) -> anyhow::Result<()> {
+        let mut by_key: BTreeMap<String, Vec<Operation>> = BTreeMap::new();
+        for op in self.operations() {
+            by_key.entry(op.key.clone()).or_default().push(op);
+        }
+
+        for (key, ops) in by_key {
+            if is_linearizable(&ops) {
+                continue;
+            }
+
+            let counterexample = shrink(&ops);
+            let lines = counterexample.iter().map(|op| format!("  {}", op)).collect::<Vec<_>>();
+            anyhow::bail!(
+                "history of key {} is not linearizable, counterexample:\n{}",
+                key,
+                lines.join("\n")
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Return `true` if there is a sequential order of `ops` that respects the real-time order and
+/// explains every output. An operation with unknown outcome can be placed anywhere after its
+/// invocation, or be left out.
+pub fn is_linearizable(ops: &[Operation]) -> bool {
+    let mut linearized = vec![false; ops.len()];
+    let mut visited = HashSet::new();
+    search(ops, &mut linearized, None, &mut visited)
+}
+
+fn search(ops: &[Operation], linearized: &mut [bool], state: Value, visited: &mut HashSet<(Vec<bool>, Value)>) -> bool {
+    // An operation can be linearized next only if it is invoked before every remaining operation
+    // returns.
+    let min_ret = ops.iter().zip(linearized.iter()).filter(|(_, done)| !**done).filter_map(|(op, _)| op.ret).min();
+
+    let Some(min_ret) = min_ret else {
+        // Every completed operation is linearized.
+        return true;
+    };
+
+    if !visited.insert((linearized.to_vec(), state.clone())) {
+        return false;
+    }
+
+    for (i, op) in ops.iter().enumerate() {
+        if linearized[i] || op.call > min_ret {
+            continue;
+        }
+
+        let Some(next) = op.step(&state) else {
+            continue;
+        };
+
+        linearized[i] = true;
+        if search(ops, linearized, next, visited) {
+            return true;
+        }
+        linearized[i] = false;
+    }
+
+    false
+}
+
+/// Return the shortest prefix of a non-linearizable history that is still not linearizable.
+///
+/// The prefix at time `t` contains the operations invoked before `t`. An operation that returns
+/// at or after `t` has an unknown outcome in the prefix, or is removed if it is a read. Thus a
+/// prefix being not linearizable implies that the whole history is not.
+fn shrink(ops: &[Operation]) -> Vec<Operation> {
+    let max_time = ops.iter().map(|op| op.ret.unwrap_or(op.call)).max().unwrap_or_default() + 1;
+
+    // `lo` is linearizable, `hi` is not.
+    let (mut lo, mut hi) = (0, max_time);
+    while hi - lo > 1 {
+        let mid = (lo + hi) / 2;
+        if is_linearizable(&prefix(ops, mid)) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    prefix(ops, hi)
+}
+
+fn prefix(ops: &[Operation], t: u64) -> Vec<Operation> {
+    let mut res = vec![];
+    for op in ops.iter().filter(|op| op.call < t) {
+        if op.ret.is_some_and(|r| r < t) {
+            res.push(op.clone());
+        } else if op.input != Input::Read {
+            res.push(Operation {
+                output: None,
+                ret: None,
+                ..op.clone()
+            });
+        }
+    }
+    res
+}
+
+/// A client that sends reads and writes to the leader of a [`RaftRouter`] cluster and records them
+/// in a [`History`].
+///
+/// A write that fails or times out is recorded with an unknown outcome, because it may have been
+/// committed. A read that fails is removed, because it has no effect.
+pub struct HistoryClient {
+    id: u64,
+    router: RaftRouter,
+    history: History,
+
+    /// The node that is believed to be the leader.
+    leader: MemNodeId,
+
+    serial: u64,
+    timeout: Duration,
+}
+
+impl HistoryClient {
+    pub fn new(id: u64, router: RaftRouter, history: History, leader: MemNodeId) -> Self {
+        Self {
+            id,
+            router,
+            history,
+            leader,
+            serial: 0,
+            timeout: Duration::from_millis(1_000),
+        }
+    }
+
+    /// Set a key to `value` and return the previous value, or `None` if the outcome is unknown.
+    pub async fn write(&mut self, key: &str, value: &str) -> Option<Value> {
+        self.serial += 1;
+        let req = ClientRequest {
+            client: key.to_string(),
+            serial: self.serial,
+            status: value.to_string(),
+        };
+
+        let index = self.history.begin(self.id, key, Input::Write(value.to_string()));
+        let res = MemConfig::timeout(self.timeout, self.router.send_client_request(self.leader, req)).await;
+
+        match res {
+            Ok(Ok(resp)) => {
+                self.history.end(index, Outcome::Ok(resp.0.clone()));
+                Some(resp.0)
+            }
+            Ok(Err(err)) => {
+                self.history.end(index, Outcome::Unknown);
+                if let RaftError::APIError(ClientWriteError::ForwardToLeader(e)) = err {
+                    self.follow(e.leader_id);
+                }
+                None
+            }
+            Err(_elapsed) => {
+                self.history.end(index, Outcome::Unknown);
+                None
+            }
+        }
+    }
+
+    /// Read the value of a key from the leader, or return `None` if the read fails.
+    pub async fn read(&mut self, key: &str) -> Option<Value> {
+        let index = self.history.begin(self.id, key, Input::Read);
+
+        let res = MemConfig::timeout(self.timeout, self.router.ensure_linearizable(self.leader)).await;
+
+        let value = match res {
+            Ok(Ok(())) => {
+                let (_, sm) = self.router.get_storage_handle(&self.leader).unwrap();
+                sm.get_state_machine().await.client_status.get(key).cloned()
+            }
+            Ok(Err(err)) => {
+                self.history.end(index, Outcome::NoEffect);
+                if let CheckIsLeaderError::ForwardToLeader(e) = err {
+                    self.follow(e.leader_id);
+                }
+                return None;
+            }
+            Err(_elapsed) => {
+                self.history.end(index, Outcome::NoEffect);
+                return None;
+            }
+        };
+
+        self.history.end(index, Outcome::Ok(value.clone()));
+        Some(value)
+    }
+
+    /// Switch to another leader, or a random node if the leader is unknown.
+    fn follow(&mut self, leader: Option<MemNodeId>) {
+        self.leader = match leader {
+            Some(l) => l,
+            None => {
+                let ids = self.router.latest_metrics().into_iter().map(|m| m.id).collect::<Vec<_>>();
+                ids[AsyncRuntimeOf::<MemConfig>::thread_rng().gen_range(0..ids.len())]
+            }
+        };
+    }
+}
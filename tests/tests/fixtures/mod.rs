@@ -30,6 +30,7 @@ use openraft::error::NetworkError;
 use openraft::error::PayloadTooLarge;
 use openraft::error::RPCError;
 use openraft::error::RaftError;
+use openraft::error::RemoteError;
 use openraft::error::ReplicationClosed;
 use openraft::error::StreamingError;
 use openraft::error::Unreachable;
@@ -39,7 +40,9 @@ use openraft::network::RaftNetworkFactory;
 use openraft::raft::AppendEntriesRequest;
 use openraft::raft::AppendEntriesResponse;
 use openraft::raft::ClientWriteResponse;
+use openraft::raft::CorrelationId;
 use openraft::raft::InstallSnapshotRequest;
+use openraft::raft::ReadIndexRequest;
 use openraft::raft::SnapshotResponse;
 use openraft::raft::TransferLeaderRequest;
 use openraft::raft::VoteRequest;
@@ -70,10 +73,15 @@ use openraft_memstore::TypeConfig as MemConfig;
 use pretty_assertions::assert_eq;
 #[allow(unused_imports)]
 use pretty_assertions::assert_ne;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
 use tracing_appender::non_blocking::WorkerGuard;
 
 use crate::fixtures::logging::init_file_logging;
 
+pub mod chaos;
+pub mod linearizability;
 pub mod logging;
 
 pub type MemLogStore = Arc<LogStoreInner>;
@@ -82,6 +90,9 @@ pub type MemStateMachine = Arc<SMInner>;
 /// A concrete Raft type used during testing.
 pub type MemRaft = Raft<MemConfig>;
 
+/// The runtime the fixtures use for time, tasks and randomness, the same one the nodes run on.
+type Rt = AsyncRuntimeOf<MemConfig>;
+
 pub fn log_id(term: u64, node_id: u64, index: u64) -> LogIdOf<TypeConfig> {
     LogIdOf::<TypeConfig>::new(
         <TypeConfig as RaftTypeConfig>::LeaderId::new_committed(term, node_id),
@@ -90,6 +101,9 @@ pub fn log_id(term: u64, node_id: u64, index: u64) -> LogIdOf<TypeConfig> {
 }
 
 /// Create a harness that sets up tracing and a tokio runtime for testing.
+///
+/// With feature `madsim`, the test runs in a madsim runtime instead, seeded with
+/// `MADSIM_TEST_SEED`, or 0 if it is not set.
 pub fn ut_harness<F, Fut>(f: F) -> anyhow::Result<()>
 where
     F: FnOnce() -> Fut + Send + 'static,
@@ -103,12 +117,20 @@ where
     #[allow(clippy::let_unit_value)]
     let _g = init_default_ut_tracing();
 
+    #[cfg(not(feature = "madsim"))]
     let rt = tokio::runtime::Builder::new_multi_thread()
         .worker_threads(8)
         .enable_all()
         .build()
         .expect("Failed building the Runtime");
 
+    #[cfg(feature = "madsim")]
+    let rt = {
+        let seed = std::env::var("MADSIM_TEST_SEED").map_or(0, |s| s.parse().expect("MADSIM_TEST_SEED is not a u64"));
+        tracing::info!("madsim seed: {}", seed);
+        madsim::runtime::Runtime::with_seed_and_config(seed, madsim::Config::default())
+    };
+
     let res = rt.block_on(f());
     if let Err(e) = &res {
         tracing::error!("{} error: {:?}", func_name::<F>(), e);
@@ -190,13 +212,16 @@ impl fmt::Display for Direction {
     }
 }
 
+use openraft::alias::AsyncRuntimeOf;
 use openraft::alias::LogIdOf;
 use openraft::alias::VoteOf;
 use openraft::entry::RaftEntry;
 use openraft::network::v2::RaftNetworkV2;
+use openraft::type_config::TypeConfigExt;
 use openraft::vote::RaftLeaderId;
 use openraft::vote::RaftLeaderIdExt;
 use openraft::vote::RaftVote;
+use openraft::AsyncRuntime;
 use Direction::NetRecv;
 use Direction::NetSend;
 
@@ -229,11 +254,64 @@ impl RPCErrorType {
                 RPCTypes::TransferLeader => {
                     unreachable!("TransferLeader RPC should not be too large")
                 }
+                RPCTypes::ReadIndex => {
+                    unreachable!("ReadIndex RPC should not be too large")
+                }
             },
         }
     }
 }
 
+/// The network condition of a link from one node to another, see [`TypedRaftRouter::set_link()`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinkConfig {
+    /// The fixed delay of every RPC sent over the link.
+    pub latency: Duration,
+
+    /// The max extra delay, the actual extra delay of every RPC is chosen uniformly from
+    /// `[0, jitter]`.
+    pub jitter: Duration,
+
+    /// The probability in `[0.0, 1.0]` that an RPC is dropped and a
+    /// [`NetworkError`](`openraft::error::NetworkError`) is returned.
+    pub drop_rate: f64,
+}
+
+impl LinkConfig {
+    pub fn new(latency: Duration, jitter: Duration, drop_rate: f64) -> Self {
+        Self {
+            latency,
+            jitter,
+            drop_rate,
+        }
+    }
+}
+
+/// Randomly duplicates and reorders RPC deliveries, see [`TypedRaftRouter::set_rpc_disorder()`].
+struct RpcDisorder {
+    duplicate: f64,
+    reorder: f64,
+    max_delay: Duration,
+    seed: u64,
+
+    /// An RNG for every `(from, to)` link, so that the decisions for the RPCs on a link do not
+    /// depend on how the RPCs on other links are scheduled.
+    rngs: BTreeMap<(MemNodeId, MemNodeId), StdRng>,
+
+    /// Number of RPCs that are delivered twice.
+    duplicated: u64,
+}
+
+/// How to deliver an RPC, decided by [`RpcDisorder`].
+#[derive(Debug, Default)]
+struct Delivery {
+    /// Delay the delivery so that RPCs sent later may arrive earlier.
+    delay: Duration,
+
+    /// Deliver the RPC once more after this delay, and return the response of the duplicate.
+    duplicate: Option<Duration>,
+}
+
 /// Pre-hook result, which does not return remote Error.
 pub type PreHookResult = Result<(), RPCError<MemConfig, Infallible>>;
 
@@ -247,6 +325,7 @@ where C::SnapshotData: fmt::Debug
     InstallFullSnapshot(Snapshot<C>),
     Vote(VoteRequest<C>),
     TransferLeader(TransferLeaderRequest<C>),
+    ReadIndex(ReadIndexRequest<C>),
 }
 
 impl<C: RaftTypeConfig> RPCRequest<C>
@@ -259,6 +338,7 @@ where C::SnapshotData: fmt::Debug
             RPCRequest::InstallFullSnapshot(_) => RPCTypes::InstallSnapshot,
             RPCRequest::Vote(_) => RPCTypes::Vote,
             RPCRequest::TransferLeader(_) => RPCTypes::TransferLeader,
+            RPCRequest::ReadIndex(_) => RPCTypes::ReadIndex,
         }
     }
 }
@@ -267,6 +347,13 @@ where C::SnapshotData: fmt::Debug
 pub type RPCPreHook =
     Box<dyn Fn(&TypedRaftRouter, RPCRequest<TypeConfig>, MemNodeId, MemNodeId) -> PreHookResult + Send + 'static>;
 
+/// Rewrites an RPC before it is delivered, e.g., to emulate a malformed request from a faulty
+/// peer. It must return an RPC of the same type.
+///
+/// Arguments: `(rpc, from_id, to_id)`
+pub type RPCMutator =
+    Box<dyn Fn(RPCRequest<TypeConfig>, MemNodeId, MemNodeId) -> RPCRequest<TypeConfig> + Send + 'static>;
+
 /// A type which emulates a network transport and implements the `RaftNetworkFactory` trait.
 #[derive(Clone)]
 pub struct TypedRaftRouter {
@@ -284,6 +371,20 @@ pub struct TypedRaftRouter {
     /// And it defines what kind of error to return.
     fail_rpc: Arc<Mutex<HashMap<(MemNodeId, Direction), RPCErrorType>>>,
 
+    /// The groups of nodes that can communicate with each other, set by [`Self::partition()`].
+    ///
+    /// `None` means the network is not partitioned.
+    partitions: Arc<Mutex<Option<Vec<BTreeSet<MemNodeId>>>>>,
+
+    /// The network condition of every `(from, to)` link, set by [`Self::set_link()`].
+    links: Arc<Mutex<HashMap<(MemNodeId, MemNodeId), LinkConfig>>>,
+
+    /// The network condition of a link that is not in `links`.
+    default_link: Arc<Mutex<LinkConfig>>,
+
+    /// If set, AppendEntries, Vote and snapshot RPCs are randomly duplicated and reordered.
+    rpc_disorder: Arc<Mutex<Option<RpcDisorder>>>,
+
     /// To emulate network delay for sending, in milliseconds.
     /// 0 means no delay.
     send_delay: Arc<AtomicU64>,
@@ -299,6 +400,15 @@ pub struct TypedRaftRouter {
 
     /// A hook function to be called when before an RPC is sent to target node.
     rpc_pre_hook: Arc<Mutex<HashMap<RPCTypes, RPCPreHook>>>,
+
+    /// Rewrites AppendEntries and Vote RPCs before they are delivered.
+    rpc_mutator: Arc<Mutex<HashMap<RPCTypes, RPCMutator>>>,
+
+    /// The correlation ids carried by the AppendEntries RPCs sent to every target.
+    sent_correlation_ids: Arc<Mutex<BTreeMap<MemNodeId, Vec<CorrelationId>>>>,
+
+    /// The names of the spans the AppendEntries RPCs sent to every target originate from.
+    sent_span_names: Arc<Mutex<BTreeMap<MemNodeId, Vec<&'static str>>>>,
 }
 
 /// Default `RaftRouter` for memstore.
@@ -331,10 +441,17 @@ impl Builder {
             nodes: Default::default(),
             enable_saving_committed: true,
             fail_rpc: Default::default(),
+            partitions: Default::default(),
+            links: Default::default(),
+            default_link: Default::default(),
+            rpc_disorder: Default::default(),
             send_delay: Arc::new(AtomicU64::new(send_delay)),
             append_entries_quota: Arc::new(Mutex::new(None)),
             rpc_count: Default::default(),
             rpc_pre_hook: Default::default(),
+            rpc_mutator: Default::default(),
+            sent_correlation_ids: Default::default(),
+            sent_span_names: Default::default(),
         }
     }
 }
@@ -360,9 +477,138 @@ impl TypedRaftRouter {
             return;
         }
 
-        let r = rand::random::<u64>() % send_delay;
+        let r = Rt::thread_rng().gen_range(0..send_delay);
         let timeout = Duration::from_millis(r);
-        tokio::time::sleep(timeout).await;
+        MemConfig::sleep(timeout).await;
+    }
+
+    /// Set the network condition of the link from `from` to `to`, it applies to every RPC type.
+    ///
+    /// `None` resets the link to the default set by [`Self::set_default_link()`].
+    pub fn set_link(&self, from: MemNodeId, to: MemNodeId, link: Option<LinkConfig>) {
+        let mut links = self.links.lock().unwrap();
+        if let Some(link) = link {
+            links.insert((from, to), link);
+        } else {
+            links.remove(&(from, to));
+        }
+    }
+
+    /// Set the network condition of every link that is not set by [`Self::set_link()`].
+    pub fn set_default_link(&self, link: LinkConfig) {
+        *self.default_link.lock().unwrap() = link;
+    }
+
+    /// Emulate the network condition of the link from `from` to `to`: delay the RPC, then drop it
+    /// by chance.
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn emulate_link(&self, from: MemNodeId, to: MemNodeId) -> Result<(), RPCError<MemConfig>> {
+        let link = {
+            let links = self.links.lock().unwrap();
+            links.get(&(from, to)).copied().unwrap_or_else(|| *self.default_link.lock().unwrap())
+        };
+
+        let mut delay = link.latency;
+        if !link.jitter.is_zero() {
+            delay += Rt::thread_rng().gen_range(Duration::ZERO..=link.jitter);
+        }
+        if !delay.is_zero() {
+            MemConfig::sleep(delay).await;
+        }
+
+        if link.drop_rate > 0.0 && Rt::thread_rng().gen_bool(link.drop_rate.min(1.0)) {
+            let msg = format!("dropped: {}->{}", from, to);
+            delay_no_progress(true).await;
+            return Err(NetworkError::new(&AnyError::error(msg)).into());
+        }
+
+        Ok(())
+    }
+
+    /// Randomly duplicate and reorder AppendEntries, Vote and snapshot RPCs, using an RNG seeded
+    /// with `seed`.
+    ///
+    /// An RPC is delayed by up to `max_delay` with probability `reorder`, so that RPCs sent later
+    /// on other links may be delivered earlier. With probability `duplicate`, an RPC is delivered
+    /// once more after up to `max_delay`, and the caller receives the response of the duplicate
+    /// instead of the original one, i.e., a response built after the target may have handled newer
+    /// RPCs, such as a stale AppendEntries response or a duplicate vote grant.
+    ///
+    /// Every `(from, to)` link has its own RNG derived from `seed`, thus the `n`-th RPC on a link
+    /// is always delayed and duplicated the same way for the same seed.
+    pub fn set_rpc_disorder(&self, duplicate: f64, reorder: f64, max_delay: Duration, seed: u64) {
+        let d = RpcDisorder {
+            duplicate: duplicate.clamp(0.0, 1.0),
+            reorder: reorder.clamp(0.0, 1.0),
+            max_delay,
+            seed,
+            rngs: Default::default(),
+            duplicated: 0,
+        };
+        *self.rpc_disorder.lock().unwrap() = Some(d);
+    }
+
+    /// Stop duplicating and reordering RPCs.
+    pub fn clear_rpc_disorder(&self) {
+        *self.rpc_disorder.lock().unwrap() = None;
+    }
+
+    /// Get the number of RPCs delivered twice since the last [`Self::set_rpc_disorder()`].
+    pub fn get_duplicated_rpc_count(&self) -> u64 {
+        self.rpc_disorder.lock().unwrap().as_ref().map(|d| d.duplicated).unwrap_or_default()
+    }
+
+    /// Roll the dice of the link `from -> to` to decide how to deliver the next RPC on it.
+    fn rpc_delivery(&self, from: MemNodeId, to: MemNodeId) -> Delivery {
+        let mut d = self.rpc_disorder.lock().unwrap();
+        let Some(d) = d.as_mut() else {
+            return Delivery::default();
+        };
+
+        let seed = d.seed;
+        let rng = d.rngs.entry((from, to)).or_insert_with(|| {
+            StdRng::seed_from_u64(seed.wrapping_mul(31).wrapping_add(from).wrapping_mul(31).wrapping_add(to))
+        });
+
+        let mut delivery = Delivery::default();
+        if rng.gen_bool(d.reorder) {
+            delivery.delay = rng.gen_range(Duration::ZERO..=d.max_delay);
+        }
+        if rng.gen_bool(d.duplicate) {
+            delivery.duplicate = Some(rng.gen_range(Duration::ZERO..=d.max_delay));
+            d.duplicated += 1;
+        }
+        delivery
+    }
+
+    /// Deliver an RPC built by `send`, which may be delayed or duplicated, see
+    /// [`Self::set_rpc_disorder()`].
+    async fn deliver<F, Fu>(&self, from: MemNodeId, to: MemNodeId, send: F) -> Fu::Output
+    where
+        F: Fn() -> Fu,
+        Fu: Future,
+    {
+        let delivery = self.rpc_delivery(from, to);
+
+        if !delivery.delay.is_zero() {
+            tracing::debug!("delay RPC {}->{} by {:?}", from, to, delivery.delay);
+            MemConfig::sleep(delivery.delay).await;
+        }
+
+        let resp = send().await;
+
+        let Some(delay) = delivery.duplicate else {
+            return resp;
+        };
+
+        tracing::debug!(
+            "duplicate RPC {}->{} after {:?}, discard the first response",
+            from,
+            to,
+            delay
+        );
+        MemConfig::sleep(delay).await;
+        send().await
     }
 
     pub fn set_append_entries_quota(&mut self, quota: Option<u64>) {
@@ -380,6 +626,16 @@ impl TypedRaftRouter {
         self.rpc_count.lock().unwrap().clone()
     }
 
+    /// Get the correlation ids carried by the AppendEntries RPCs sent to `target`.
+    pub fn get_sent_correlation_ids(&self, target: MemNodeId) -> Vec<CorrelationId> {
+        self.sent_correlation_ids.lock().unwrap().get(&target).cloned().unwrap_or_default()
+    }
+
+    /// Get the names of the spans the AppendEntries RPCs sent to `target` originate from.
+    pub fn get_sent_span_names(&self, target: MemNodeId) -> Vec<&'static str> {
+        self.sent_span_names.lock().unwrap().get(&target).cloned().unwrap_or_default()
+    }
+
     /// Create a cluster: 0 is the initial leader, others are voters and learners
     ///
     /// NOTE: it create a single node cluster first, then change it to a multi-voter cluster.
@@ -500,6 +756,33 @@ impl TypedRaftRouter {
         rt.insert(id, (node, log_store, sm));
     }
 
+    /// Register a Raft node built by the caller, e.g., with a custom state machine that wraps `sm`.
+    pub fn register_raft_node(&mut self, id: MemNodeId, node: MemRaft, log_store: MemLogStore, sm: MemStateMachine) {
+        let mut rt = self.nodes.lock().unwrap();
+        rt.insert(id, (node, log_store, sm));
+    }
+
+    /// Crash and restart a node: shut down its `Raft` and build a new one from the same log store
+    /// and state machine, which are kept as if they were persisted.
+    ///
+    /// Network failures and partitions set for the node are kept.
+    /// To restart with modified storage, e.g., a cleared state machine, use [`Self::remove_node()`]
+    /// and [`Self::new_raft_node_with_sto()`].
+    pub async fn restart_node(&mut self, id: MemNodeId) -> anyhow::Result<()> {
+        let (node, log_store, sm) = {
+            let mut rt = self.nodes.lock().unwrap();
+            rt.remove(&id).with_context(|| format!("could not find node {} in routing table", id))?
+        };
+
+        tracing::info!("restart node {}", id);
+
+        node.shutdown().await?;
+        drop(node);
+
+        self.new_raft_node_with_sto(id, log_store, sm).await;
+        Ok(())
+    }
+
     /// Remove the target node from the routing table & isolation.
     pub fn remove_node(&mut self, id: MemNodeId) -> Option<(MemRaft, MemLogStore, MemStateMachine)> {
         let opt_handles = {
@@ -566,6 +849,27 @@ impl TypedRaftRouter {
         }
     }
 
+    /// Partition the network into `groups`: an RPC is delivered only if the source and the target
+    /// are in the same group, otherwise [`Unreachable`] is returned, in both directions.
+    ///
+    /// Groups may overlap to build an asymmetric partition, e.g., `[{0,1}, {1,2}]` lets node 1
+    /// talk to both 0 and 2, while 0 and 2 can not reach each other.
+    /// A node that is not in any group is isolated.
+    ///
+    /// It replaces the previous partition, and is independent of the failures set by
+    /// [`Self::set_rpc_failure()`].
+    pub fn partition(&self, groups: impl IntoIterator<Item = BTreeSet<MemNodeId>>) {
+        let groups = groups.into_iter().collect::<Vec<_>>();
+        tracing::info!("partition network into: {:?}", groups);
+        *self.partitions.lock().unwrap() = Some(groups);
+    }
+
+    /// Remove the partition set by [`Self::partition()`], so that all nodes can reach each other.
+    pub fn heal(&self) {
+        tracing::info!("heal network partition");
+        *self.partitions.lock().unwrap() = None;
+    }
+
     /// Set a hook function to be called when before an RPC is sent to target node.
     pub fn set_rpc_pre_hook<F>(&self, rpc_type: RPCTypes, hook: F)
     where F: Fn(&TypedRaftRouter, RPCRequest<TypeConfig>, MemNodeId, MemNodeId) -> PreHookResult + Send + 'static {
@@ -582,8 +886,44 @@ impl TypedRaftRouter {
         }
     }
 
+    /// Set a function to rewrite an RPC of `rpc_type` before it is delivered to the target node.
+    ///
+    /// Only AppendEntries and Vote RPCs are rewritten: a snapshot response does not tell the leader
+    /// whether the snapshot is installed, a rewritten snapshot would make it believe so.
+    pub fn set_rpc_mutator<F>(&self, rpc_type: RPCTypes, mutator: F)
+    where F: Fn(RPCRequest<TypeConfig>, MemNodeId, MemNodeId) -> RPCRequest<TypeConfig> + Send + 'static {
+        self.rpc_mutator.lock().unwrap().insert(rpc_type, Box::new(mutator));
+    }
+
+    /// Remove the function set by [`Self::set_rpc_mutator()`].
+    pub fn clear_rpc_mutator(&self, rpc_type: RPCTypes) {
+        self.rpc_mutator.lock().unwrap().remove(&rpc_type);
+    }
+
+    /// Rewrite an RPC with the mutator set by [`Self::set_rpc_mutator()`], if any.
+    fn mutate_rpc<T>(&self, rpc: T, from: MemNodeId, to: MemNodeId) -> T
+    where
+        T: Into<RPCRequest<TypeConfig>>,
+        RPCRequest<TypeConfig>: TryInto<T, Error = derive_more::TryIntoError<RPCRequest<TypeConfig>>>,
+    {
+        let request = rpc.into();
+        let typ = request.get_type();
+
+        let rpc_mutator = self.rpc_mutator.lock().unwrap();
+
+        let request = if let Some(mutator) = rpc_mutator.get(&typ) {
+            let mutated = mutator(request, from, to);
+            tracing::info!("mutated {:?} RPC from {} to {}: {:?}", typ, from, to, mutated);
+            mutated
+        } else {
+            request
+        };
+
+        request.try_into().expect("RPC mutator must not change the RPC type")
+    }
+
     /// Call pre-hook before an RPC is sent.
-    fn call_rpc_pre_hook<E>(
+    async fn call_rpc_pre_hook<E>(
         &self,
         request: impl Into<RPCRequest<TypeConfig>>,
         from: MemNodeId,
@@ -592,7 +932,20 @@ impl TypedRaftRouter {
     where
         E: std::error::Error,
     {
-        let request = request.into();
+        let res = self.run_rpc_pre_hook(request.into(), from, to);
+        delay_no_progress(res.is_err()).await;
+        res
+    }
+
+    fn run_rpc_pre_hook<E>(
+        &self,
+        request: RPCRequest<TypeConfig>,
+        from: MemNodeId,
+        to: MemNodeId,
+    ) -> Result<(), RPCError<MemConfig, E>>
+    where
+        E: std::error::Error,
+    {
         let typ = request.get_type();
 
         let rpc_pre_hook = self.rpc_pre_hook.lock().unwrap();
@@ -995,7 +1348,13 @@ impl TypedRaftRouter {
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
-    pub fn emit_rpc_error(&self, id: MemNodeId, target: MemNodeId) -> Result<(), RPCError<MemConfig>> {
+    pub async fn emit_rpc_error(&self, id: MemNodeId, target: MemNodeId) -> Result<(), RPCError<MemConfig>> {
+        let res = self.injected_rpc_error(id, target);
+        delay_no_progress(res.is_err()).await;
+        res
+    }
+
+    fn injected_rpc_error(&self, id: MemNodeId, target: MemNodeId) -> Result<(), RPCError<MemConfig>> {
         let fails = self.fail_rpc.lock().unwrap();
 
         for key in [(id, NetSend), (target, NetRecv)] {
@@ -1004,6 +1363,15 @@ impl TypedRaftRouter {
             }
         }
 
+        let partitions = self.partitions.lock().unwrap();
+        if let Some(groups) = partitions.as_ref() {
+            let connected = groups.iter().any(|g| g.contains(&id) && g.contains(&target));
+            if !connected {
+                let msg = format!("partitioned: {} can not reach {}", id, target);
+                return Err(Unreachable::new(&AnyError::error(msg)).into());
+            }
+        }
+
         Ok(())
     }
 }
@@ -1024,20 +1392,42 @@ pub struct RaftRouterNetwork {
     owner: TypedRaftRouter,
 }
 
+impl RaftRouterNetwork {
+    /// Get the target node, or a [`NetworkError`] if it has been removed from the router.
+    async fn target_node(&self) -> Result<MemRaft, NetworkError> {
+        let res = self.owner.get_raft_handle(&self.target);
+        delay_no_progress(res.is_err()).await;
+        res
+    }
+}
+
 impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
     /// Send an AppendEntries RPC to the target Raft node (§5).
     async fn append_entries(
         &mut self,
-        mut rpc: AppendEntriesRequest<MemConfig>,
-        _option: RPCOption,
+        rpc: AppendEntriesRequest<MemConfig>,
+        option: RPCOption,
     ) -> Result<AppendEntriesResponse<MemConfig>, RPCError<MemConfig>> {
         let from_id = rpc.vote.to_leader_node_id().unwrap();
 
         tracing::debug!("append_entries to id={} {}", self.target, rpc);
         self.owner.count_rpc(RPCTypes::AppendEntries);
-        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
-        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner
+            .sent_correlation_ids
+            .lock()
+            .unwrap()
+            .entry(self.target)
+            .or_default()
+            .extend_from_slice(option.correlation_ids());
+        if let Some(meta) = option.span().metadata() {
+            self.owner.sent_span_names.lock().unwrap().entry(self.target).or_default().push(meta.name());
+        }
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target).await?;
+        self.owner.emit_rpc_error(from_id, self.target).await?;
         self.owner.rand_send_delay().await;
+        self.owner.emulate_link(from_id, self.target).await?;
+
+        let mut rpc = self.owner.mutate_rpc(rpc, from_id, self.target);
 
         // decrease quota if quota is set
         let truncated = {
@@ -1070,10 +1460,17 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
             tracing::debug!("quota after consumption: {:?}", *x);
         }
         tracing::debug!("append_entries truncated: {:?}", truncated);
+        delay_no_progress(truncated.is_some() && rpc.entries.is_empty()).await;
 
-        let node = self.owner.get_raft_handle(&self.target)?;
+        let node = self.target_node().await?;
 
-        let resp = node.append_entries(rpc).await;
+        let resp = self
+            .owner
+            .deliver(from_id, self.target, || {
+                let (node, rpc) = (node.clone(), rpc.clone());
+                async move { node.append_entries(rpc).await }
+            })
+            .await;
 
         tracing::debug!("append_entries: recv resp from id={} {:?}", self.target, resp);
         let resp = resp.map_err(|e| {
@@ -1099,18 +1496,31 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
         vote: Vote<MemConfig>,
         snapshot: Snapshot<MemConfig>,
         _cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
-        _option: RPCOption,
+        option: RPCOption,
     ) -> Result<SnapshotResponse<MemConfig>, StreamingError<MemConfig>> {
         let from_id = vote.leader_id().to_node_id().unwrap();
 
         self.owner.count_rpc(RPCTypes::InstallSnapshot);
-        self.owner.call_rpc_pre_hook(snapshot.clone(), from_id, self.target)?;
-        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.call_rpc_pre_hook(snapshot.clone(), from_id, self.target).await?;
+        self.owner.emit_rpc_error(from_id, self.target).await?;
         self.owner.rand_send_delay().await;
+        self.owner.emulate_link(from_id, self.target).await?;
 
-        let node = self.owner.get_raft_handle(&self.target)?;
+        let node = self.target_node().await?;
 
-        let resp = node.install_full_snapshot(vote, snapshot).await;
+        node.check_snapshot_version(&snapshot.meta)?;
+
+        let size = snapshot.snapshot.get_ref().len() as u64;
+        option.add_snapshot_bytes_sent(size);
+        node.add_snapshot_bytes_received(size);
+
+        let resp = self
+            .owner
+            .deliver(from_id, self.target, || {
+                let (node, snapshot) = (node.clone(), snapshot.clone());
+                async move { node.install_full_snapshot(vote, snapshot).await }
+            })
+            .await;
         let resp = resp.map_err(|e| {
             RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
                 "error: {} target={}",
@@ -1130,13 +1540,22 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
         let from_id = rpc.vote.leader_id().to_node_id().unwrap();
 
         self.owner.count_rpc(RPCTypes::Vote);
-        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
-        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target).await?;
+        self.owner.emit_rpc_error(from_id, self.target).await?;
         self.owner.rand_send_delay().await;
+        self.owner.emulate_link(from_id, self.target).await?;
+
+        let rpc = self.owner.mutate_rpc(rpc, from_id, self.target);
 
-        let node = self.owner.get_raft_handle(&self.target)?;
+        let node = self.target_node().await?;
 
-        let resp = node.vote(rpc).await;
+        let resp = self
+            .owner
+            .deliver(from_id, self.target, || {
+                let (node, rpc) = (node.clone(), rpc.clone());
+                async move { node.vote(rpc).await }
+            })
+            .await;
         let resp = resp.map_err(|e| {
             RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
                 "error: {} target={}",
@@ -1155,11 +1574,12 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
         let from_id = rpc.from_leader().leader_id().to_node_id().unwrap();
 
         self.owner.count_rpc(RPCTypes::TransferLeader);
-        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
-        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target).await?;
+        self.owner.emit_rpc_error(from_id, self.target).await?;
         self.owner.rand_send_delay().await;
+        self.owner.emulate_link(from_id, self.target).await?;
 
-        let node = self.owner.get_raft_handle(&self.target)?;
+        let node = self.target_node().await?;
 
         let resp = node.handle_transfer_leader(rpc).await;
         resp.map_err(|e| {
@@ -1169,6 +1589,51 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
             ))))
         })
     }
+
+    async fn read_index(
+        &mut self,
+        rpc: ReadIndexRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<Option<LogIdOf<MemConfig>>, RPCError<MemConfig, RaftError<MemConfig, CheckIsLeaderError<MemConfig>>>>
+    {
+        let from_id = *rpc.from();
+
+        self.owner.count_rpc(RPCTypes::ReadIndex);
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target).await.map_err(with_remote_error)?;
+        self.owner.emit_rpc_error(from_id, self.target).await.map_err(with_remote_error)?;
+        self.owner.rand_send_delay().await;
+        self.owner.emulate_link(from_id, self.target).await.map_err(with_remote_error)?;
+
+        let node = self.target_node().await?;
+
+        node.handle_read_index(rpc)
+            .await
+            .map_err(|e| RPCError::RemoteError(RemoteError::new(self.target, e)))
+    }
+}
+
+/// Convert an RPC error without remote error to one that can hold a remote error of type `E`.
+fn with_remote_error<E: std::error::Error>(e: RPCError<MemConfig>) -> RPCError<MemConfig, E> {
+    match e {
+        RPCError::Timeout(e) => RPCError::Timeout(e),
+        RPCError::Unreachable(e) => RPCError::Unreachable(e),
+        RPCError::PayloadTooLarge(e) => RPCError::PayloadTooLarge(e),
+        RPCError::Network(e) => RPCError::Network(e),
+        RPCError::RemoteError(e) => match e.source {},
+    }
+}
+
+/// With feature `madsim`, let an RPC that makes no progress take 1 ms of virtual time.
+///
+/// The simulated clock only advances when every task is idle: such an RPC returns at once, and a
+/// sender that keeps retrying it would spin forever without any timeout firing.
+async fn delay_no_progress(no_progress: bool) {
+    #[cfg(feature = "madsim")]
+    if no_progress {
+        MemConfig::sleep(Duration::from_millis(1)).await;
+    }
+    #[cfg(not(feature = "madsim"))]
+    let _ = no_progress;
 }
 
 pub enum ValueTest<T> {
@@ -0,0 +1,128 @@
+//! A router that delivers RPCs between in-process nodes running the `openraft-kv` state machine.
+//!
+//! The `fixtures::RaftRouter` is bound to the memstore type config, while `openraft-kv` requires
+//! `KvRequest` as the application data.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use anyerror::AnyError;
+use openraft::error::RPCError;
+use openraft::error::ReplicationClosed;
+use openraft::error::StreamingError;
+use openraft::error::Unreachable;
+use openraft::network::v2::RaftNetworkV2;
+use openraft::network::RPCOption;
+use openraft::network::RaftNetworkFactory;
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::AppendEntriesResponse;
+use openraft::raft::SnapshotResponse;
+use openraft::raft::VoteRequest;
+use openraft::raft::VoteResponse;
+use openraft::storage::Snapshot;
+use openraft::Config;
+use openraft::OptionalSend;
+use openraft::Raft;
+use openraft::Vote;
+use openraft_kv::KvRequest;
+use openraft_kv::KvResponse;
+use openraft_kv::KvStateMachine;
+
+openraft::declare_raft_types!(
+    pub KvConfig:
+        D = KvRequest,
+        R = KvResponse,
+        Node = (),
+        AsyncRuntime = openraft_memstore::MemAsyncRuntime,
+);
+
+pub type KvRaft = Raft<KvConfig>;
+pub type KvLogStore = memstore::LogStore<KvConfig>;
+
+/// The nodes in a cluster: the raft handle and the state machine of every node.
+#[derive(Clone, Default)]
+pub struct KvRouter {
+    #[allow(clippy::type_complexity)]
+    nodes: Arc<Mutex<BTreeMap<u64, (KvRaft, KvStateMachine<KvConfig>)>>>,
+}
+
+impl KvRouter {
+    /// Create a node with an empty log store and state machine, and add it to the router.
+    pub async fn new_node(&self, id: u64, config: Arc<Config>) -> anyhow::Result<KvRaft> {
+        let sm = KvStateMachine::<KvConfig>::new();
+        let raft = Raft::new(id, config, self.clone(), KvLogStore::default(), sm.clone()).await?;
+        self.nodes.lock().unwrap().insert(id, (raft.clone(), sm));
+        Ok(raft)
+    }
+
+    pub fn get_raft(&self, id: u64) -> KvRaft {
+        self.nodes.lock().unwrap()[&id].0.clone()
+    }
+
+    pub fn get_state_machine(&self, id: u64) -> KvStateMachine<KvConfig> {
+        self.nodes.lock().unwrap()[&id].1.clone()
+    }
+
+    fn target(&self, id: u64) -> Result<KvRaft, Unreachable> {
+        let nodes = self.nodes.lock().unwrap();
+        let (raft, _) =
+            nodes.get(&id).ok_or_else(|| Unreachable::new(&AnyError::error(format!("node {} not found", id))))?;
+        Ok(raft.clone())
+    }
+}
+
+impl RaftNetworkFactory<KvConfig> for KvRouter {
+    type Network = KvNetwork;
+
+    async fn new_client(&mut self, target: u64, _node: &()) -> Self::Network {
+        KvNetwork {
+            target,
+            router: self.clone(),
+        }
+    }
+}
+
+pub struct KvNetwork {
+    target: u64,
+    router: KvRouter,
+}
+
+fn unreachable(e: impl ToString) -> Unreachable {
+    Unreachable::new(&AnyError::error(e.to_string()))
+}
+
+impl RaftNetworkV2<KvConfig> for KvNetwork {
+    async fn append_entries(
+        &mut self,
+        rpc: AppendEntriesRequest<KvConfig>,
+        _option: RPCOption,
+    ) -> Result<AppendEntriesResponse<KvConfig>, RPCError<KvConfig>> {
+        let raft = self.router.target(self.target)?;
+        let resp = raft.append_entries(rpc).await.map_err(unreachable)?;
+        Ok(resp)
+    }
+
+    async fn vote(
+        &mut self,
+        rpc: VoteRequest<KvConfig>,
+        _option: RPCOption,
+    ) -> Result<VoteResponse<KvConfig>, RPCError<KvConfig>> {
+        let raft = self.router.target(self.target)?;
+        let resp = raft.vote(rpc).await.map_err(unreachable)?;
+        Ok(resp)
+    }
+
+    async fn full_snapshot(
+        &mut self,
+        vote: Vote<KvConfig>,
+        snapshot: Snapshot<KvConfig>,
+        _cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse<KvConfig>, StreamingError<KvConfig>> {
+        let raft = self.router.target(self.target)?;
+        let resp = raft.install_full_snapshot(vote, snapshot).await.map_err(unreachable)?;
+        Ok(resp)
+    }
+}
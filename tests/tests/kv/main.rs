@@ -0,0 +1,12 @@
+#![cfg_attr(feature = "bt", feature(error_generic_member_access))]
+
+#[macro_use]
+#[path = "../fixtures/mod.rs"]
+mod fixtures;
+
+mod kv_router;
+
+// The number indicate the preferred running order for these case.
+// The later tests may depend on the earlier ones.
+
+mod t10_kv_cluster;
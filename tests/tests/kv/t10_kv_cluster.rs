@@ -0,0 +1,115 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft_kv::KvRequest;
+use openraft_kv::KvResponse;
+
+use crate::fixtures::ut_harness;
+use crate::kv_router::KvRouter;
+
+/// A cluster running the `openraft-kv` state machine: writes are applied at most once on every
+/// replica, and a learner added after the logs are purged is brought up by a snapshot.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn kv_cluster() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = KvRouter::default();
+    for id in 0..3 {
+        router.new_node(id, config.clone()).await?;
+    }
+
+    let leader = router.get_raft(0);
+    leader.initialize(btreeset! {0, 1, 2}).await?;
+    leader.wait(timeout()).state(openraft::ServerState::Leader, "node-0 is leader").await?;
+
+    let mut log_index = 1;
+
+    tracing::info!(log_index, "--- write with a client session");
+    {
+        leader.client_write(KvRequest::set("a", "1").with_session("c1", 1)).await?;
+        let resp = leader.client_write(KvRequest::set("a", "2").with_session("c1", 2)).await?;
+        assert_eq!(
+            KvResponse {
+                prev: Some("1".to_string())
+            },
+            resp.data
+        );
+        log_index += 2;
+
+        tracing::info!(log_index, "--- a retried write returns the cached response");
+        let resp = leader.client_write(KvRequest::set("a", "2").with_session("c1", 2)).await?;
+        assert_eq!(
+            KvResponse {
+                prev: Some("1".to_string())
+            },
+            resp.data
+        );
+        log_index += 1;
+
+        tracing::info!(log_index, "--- an outdated write is rejected");
+        let res = leader.client_write(KvRequest::set("a", "0").with_session("c1", 1)).await;
+        assert!(
+            matches!(res, Err(RaftError::APIError(ClientWriteError::ApplyError(_)))),
+            "got: {:?}",
+            res
+        );
+        log_index += 1;
+
+        for id in 0..3 {
+            let raft = router.get_raft(id);
+            raft.wait(timeout()).applied_index(Some(log_index), "replicated").await?;
+            assert_eq!(
+                Some("2".to_string()),
+                router.get_state_machine(id).get("a").await,
+                "node-{}",
+                id
+            );
+        }
+    }
+
+    tracing::info!(log_index, "--- build a snapshot and purge the logs");
+    {
+        let last_applied = leader.metrics().borrow().last_applied.unwrap();
+
+        leader.trigger().snapshot().await?;
+        leader.wait(timeout()).snapshot(last_applied, "snapshot").await?;
+        leader.trigger().purge_log(log_index).await?;
+        leader.wait(timeout()).purged(Some(last_applied), "purged").await?;
+    }
+
+    tracing::info!(log_index, "--- a new learner receives the snapshot");
+    {
+        router.new_node(3, config.clone()).await?;
+        leader.add_learner(3, (), true).await?;
+        log_index += 1;
+
+        let learner = router.get_raft(3);
+        learner.wait(timeout()).applied_index(Some(log_index), "learner caught up").await?;
+
+        let want = router.get_state_machine(0).data().await;
+        let got = router.get_state_machine(3).data().await;
+        assert_eq!(want.last_applied, got.last_applied);
+        assert_eq!(Some("2".to_string()), router.get_state_machine(3).get("a").await);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
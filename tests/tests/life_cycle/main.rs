@@ -8,8 +8,12 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_initialization;
+mod t10_initialize_with_learners;
 mod t11_shutdown;
+mod t12_graceful_shutdown;
+mod t13_restart_on_fatal;
 mod t50_follower_restart_does_not_interrupt;
+mod t50_restart_node;
 mod t50_single_follower_restart;
 mod t50_single_leader_restart_re_apply_logs;
 mod t90_issue_607_single_restart;
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Initialize a cluster with voters and learners in the initial membership.
+///
+/// - The learner is in the membership log at index 0, and receives logs without `add_learner()`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn initialize_with_learners() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_raft_node(0).await;
+    router.new_raft_node(1).await;
+    router.new_raft_node(2).await;
+
+    tracing::info!("--- initialize with voters {{0,1}} and learner {{2}}");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.initialize_with_learners(btreeset! {0,1}, btreeset! {2}).await?;
+    }
+
+    // log 0: initial membership log; log 1: leader initial log
+    let log_index = 1;
+
+    tracing::info!(log_index, "--- all nodes receive logs, node-2 stays a learner");
+    {
+        router.wait_for_log(&btreeset![0, 1, 2], Some(log_index), timeout(), "init").await?;
+        router.wait(&0, timeout()).state(ServerState::Leader, "node-0 is leader").await?;
+        router.wait(&2, timeout()).state(ServerState::Learner, "node-2 is learner").await?;
+
+        for id in [0, 1, 2] {
+            let m = router.get_raft_handle(&id)?.metrics().borrow().membership_config.clone();
+            assert_eq!(vec![btreeset! {0,1}], m.membership().get_joint_config().clone());
+            assert_eq!(btreeset! {2}, m.membership().learner_ids().collect());
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -40,6 +40,7 @@ async fn shutdown() -> Result<()> {
 /// A panicked RaftCore should also return a proper error the next time accessing the `Raft`.
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
+#[cfg_attr(feature = "madsim", ignore = "madsim aborts the simulation when a task panics")]
 async fn return_error_after_panic() -> Result<()> {
     let config = Arc::new(
         Config {
@@ -0,0 +1,178 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::error::ShuttingDown;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A graceful shutdown rejects new writes, waits for the accepted writes to be applied and
+/// transfers the leadership before shutting down.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn shutdown_gracefully() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 150,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(
+        log_index,
+        "--- isolate followers and write 2 entries that can not commit"
+    );
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        n0.client_write_handle(ClientRequest::make_request("foo", 1)).await?;
+        n0.client_write_handle(ClientRequest::make_request("foo", 2)).await?;
+    }
+
+    tracing::info!(log_index, "--- begin graceful shutdown, new writes are rejected");
+    let shutdown = {
+        let n0 = n0.clone();
+        TypeConfig::spawn(async move { n0.shutdown_gracefully(Duration::from_millis(5_000), Some(1)).await })
+    };
+    {
+        TypeConfig::sleep(Duration::from_millis(200)).await;
+
+        let res = n0.client_write(ClientRequest::make_request("foo", 3)).await;
+        assert_eq!(
+            Err(RaftError::APIError(ClientWriteError::ShuttingDown(ShuttingDown))),
+            res.map(|_| ())
+        );
+    }
+
+    tracing::info!(log_index, "--- restore network, the accepted writes are drained");
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        let report = shutdown.await??;
+        assert_eq!(
+            vec![log_id(1, 0, log_index + 1), log_id(1, 0, log_index + 2)],
+            report.drained
+        );
+        assert!(report.abandoned.is_empty());
+        assert_eq!(Some(1), report.leader_transferred_to);
+
+        assert_eq!(ServerState::Shutdown, n0.metrics().borrow().state);
+    }
+
+    Ok(())
+}
+
+/// Writes that are not applied before the deadline are reported as abandoned.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn shutdown_gracefully_timeout() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    router.set_network_error(1, true);
+    router.set_network_error(2, true);
+
+    n0.client_write_handle(ClientRequest::make_request("foo", 1)).await?;
+
+    let report = n0.shutdown_gracefully(Duration::from_millis(300), Some(1)).await?;
+    assert!(report.drained.is_empty());
+    assert_eq!(vec![log_id(1, 0, log_index + 1)], report.abandoned);
+    assert_eq!(None, report.leader_transferred_to);
+
+    Ok(())
+}
+
+/// A write truncated by a new leader is reported as abandoned, although another log at the same
+/// index is applied.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn shutdown_gracefully_truncated() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 150,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(
+        log_index,
+        "--- isolate the leader and write an entry that can not commit"
+    );
+    {
+        router.set_network_error(0, true);
+        n0.client_write_handle(ClientRequest::make_request("foo", 1)).await?;
+    }
+
+    let shutdown = {
+        let n0 = n0.clone();
+        TypeConfig::spawn(async move { n0.shutdown_gracefully(Duration::from_millis(5_000), None).await })
+    };
+
+    tracing::info!(log_index, "--- elect another leader that overrides the entry");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout()).metrics(|m| m.current_term > 1, "elect a new leader").await?;
+        let (leader, _) = n1.wait_for_leader(timeout()).await?;
+        assert_ne!(0, leader);
+
+        router.client_request(leader, "foo", 2).await?;
+    }
+
+    tracing::info!(log_index, "--- restore network, the write is truncated on node-0");
+    {
+        router.set_network_error(0, false);
+
+        let report = shutdown.await??;
+        assert!(report.drained.is_empty());
+        assert_eq!(vec![log_id(1, 0, log_index + 1)], report.abandoned);
+
+        let applied = n0.metrics().borrow().last_applied.unwrap();
+        assert!(applied.index() > log_index);
+        assert!(applied.committed_leader_id().term > 1);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
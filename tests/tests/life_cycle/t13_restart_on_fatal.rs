@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::Fatal;
+use openraft::raft::RaftEventListener;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::TypeConfig as MemConfig;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Forwards the fatal error of a node to a supervisor.
+struct Supervised {
+    tx: UnboundedSender<Fatal<MemConfig>>,
+}
+
+impl RaftEventListener<MemConfig> for Supervised {
+    fn on_fatal(&mut self, error: &Fatal<MemConfig>) {
+        let _ = self.tx.send(error.clone());
+    }
+}
+
+/// A listener is notified with the cause when `RaftCore` quits on a fatal error, and the node can
+/// be restarted from its storage.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+#[cfg_attr(feature = "madsim", ignore = "madsim aborts the simulation when a task panics")]
+async fn restart_on_fatal() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- bring up cluster of 1 node");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    router.get_raft_handle(&0)?.add_listener(Supervised { tx });
+
+    tracing::info!(log_index, "--- panic the RaftCore, the listener receives the cause");
+    {
+        router.external_request(0, |_s| {
+            panic!("foo");
+        });
+
+        let got = MemConfig::timeout(Duration::from_millis(1_000), rx.recv()).await?;
+        assert_eq!(Some(Fatal::Panicked), got);
+    }
+
+    tracing::info!(log_index, "--- the supervisor restarts node-0 from its storage");
+    {
+        let (_node, ls, sm) = router.remove_node(0).unwrap();
+
+        router.new_raft_node_with_sto(0, ls, sm).await;
+        router.wait(&0, timeout()).state(ServerState::Leader, "become leader upon restart").await?;
+
+        log_index += router.client_request_many(0, "foo", 1).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 works").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
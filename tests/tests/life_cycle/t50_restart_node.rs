@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Restart a follower and then the leader with `RaftRouter::restart_node()`.
+///
+/// A restarted node recovers its vote, logs and snapshot from storage, and rejoins the cluster.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn restart_node() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- bring up cluster of 3 nodes");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 10 logs and build a snapshot on node 2");
+    let snapshot_log_id = {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        router.wait(&2, timeout()).applied_index(Some(log_index), "node 2 applied").await?;
+
+        let n2 = router.get_raft_handle(&2)?;
+        n2.trigger().snapshot().await?;
+        n2.wait(timeout()).snapshot(log_id(1, 0, log_index), "node 2 snapshot").await?;
+        log_id(1, 0, log_index)
+    };
+
+    tracing::info!(log_index, "--- restart follower 2, it reloads vote and snapshot");
+    {
+        let vote = router.get_metrics(&2)?.vote;
+
+        router.restart_node(2).await?;
+
+        let m = router.wait(&2, timeout()).vote(vote, "vote is recovered").await?;
+        assert_eq!(Some(snapshot_log_id), m.snapshot, "snapshot is reloaded");
+        assert_eq!(Some(log_index), m.last_log_index, "logs are recovered");
+
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        router.wait(&2, timeout()).applied_index(Some(log_index), "node 2 rejoins").await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- restart leader 0, it restores leadership with the committed vote"
+    );
+    {
+        let vote = router.get_metrics(&0)?.vote;
+
+        router.restart_node(0).await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.wait(timeout()).vote(vote, "vote is recovered").await?;
+        n0.wait(timeout()).applied_index(Some(log_index), "state machine is recovered").await?;
+
+        n0.wait(timeout()).state(ServerState::Leader, "node 0 is still leader").await?;
+
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "cluster works after restart").await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -8,3 +8,7 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_save_committed;
+mod t20_defensive_check;
+mod t30_log_archiver;
+mod t30_purge_in_background;
+mod t50_slow_log_flush;
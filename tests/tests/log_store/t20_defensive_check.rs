@@ -0,0 +1,157 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::defensive;
+use openraft::storage::RaftLogStorage;
+use openraft::storage::RaftLogStorageExt;
+use openraft::storage::RaftStateMachine;
+use openraft::testing::blank_ent;
+use openraft::Config;
+use openraft::Raft;
+use openraft::Vote;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A raft node running on defensive storage wrappers does not trigger any violation.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn defensive_check_normal_operation() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = RaftRouter::new(config.clone());
+
+    let (log_store, sm) = openraft_memstore::new_mem_store();
+    let (log_store, sm) = defensive::wrap(log_store, sm);
+
+    let raft = Raft::new(0, config, router, log_store, sm).await?;
+    raft.initialize(btreeset! {0}).await?;
+
+    let mut log_index = 1;
+    raft.wait(timeout()).applied_index(Some(log_index), "initialized").await?;
+
+    tracing::info!(log_index, "--- write, build snapshot and purge");
+    {
+        for i in 0..10 {
+            raft.client_write(ClientRequest::make_request("foo", i)).await?;
+            log_index += 1;
+        }
+        raft.wait(timeout()).applied_index(Some(log_index), "written").await?;
+
+        raft.trigger().snapshot().await?;
+        raft.wait(timeout()).snapshot(log_id(1, 0, log_index), "snapshot built").await?;
+
+        raft.trigger().purge_log(log_index).await?;
+        raft.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purged").await?;
+    }
+
+    let metrics = raft.metrics().borrow().clone();
+    assert!(
+        metrics.running_state.is_ok(),
+        "no storage violation: {:?}",
+        metrics.running_state
+    );
+
+    raft.shutdown().await?;
+
+    Ok(())
+}
+
+/// Calls that break the storage invariants are rejected with a descriptive error.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn defensive_check_violations() -> Result<()> {
+    let (log_store, sm) = openraft_memstore::new_mem_store();
+    let (mut log_store, mut sm) = defensive::wrap(log_store, sm);
+
+    tracing::info!("--- vote must not decrease");
+    {
+        log_store.save_vote(&Vote::new(2, 1)).await?;
+
+        let err = log_store.save_vote(&Vote::new(1, 1)).await.unwrap_err();
+        assert!(err.to_string().contains("vote must not decrease"), "{}", err);
+    }
+
+    tracing::info!("--- appended logs must be consecutive");
+    {
+        log_store
+            .blocking_append([blank_ent::<TypeConfig>(1, 1, 0), blank_ent::<TypeConfig>(1, 1, 1)])
+            .await?;
+
+        let err = log_store.blocking_append([blank_ent::<TypeConfig>(1, 1, 3)]).await.unwrap_err();
+        assert!(err.to_string().contains("log ids must be consecutive"), "{}", err);
+
+        let err = log_store.blocking_append([blank_ent::<TypeConfig>(0, 1, 2)]).await.unwrap_err();
+        assert!(err.to_string().contains("log ids must be consecutive"), "{}", err);
+
+        log_store.blocking_append([blank_ent::<TypeConfig>(2, 1, 2)]).await?;
+    }
+
+    tracing::info!("--- can not purge logs that are not applied");
+    {
+        let _ = sm.applied_state().await?;
+
+        let err = log_store.purge(log_id(1, 1, 0)).await.unwrap_err();
+        assert!(err.to_string().contains("can not purge logs not applied"), "{}", err);
+    }
+
+    tracing::info!("--- applied logs must be consecutive");
+    {
+        let err = sm.apply([blank_ent::<TypeConfig>(1, 1, 1)]).await.unwrap_err();
+        assert!(
+            err.to_string().contains("applied log ids must be consecutive"),
+            "{}",
+            err
+        );
+
+        sm.apply([blank_ent::<TypeConfig>(1, 1, 0), blank_ent::<TypeConfig>(1, 1, 1)]).await?;
+    }
+
+    tracing::info!("--- purged log id must not decrease, purged logs can not be truncated");
+    {
+        log_store.purge(log_id(1, 1, 1)).await?;
+
+        let err = log_store.purge(log_id(1, 1, 0)).await.unwrap_err();
+        assert!(
+            err.to_string().contains("last purged log id must not decrease"),
+            "{}",
+            err
+        );
+
+        let err = log_store.truncate(log_id(1, 1, 1)).await.unwrap_err();
+        assert!(err.to_string().contains("can not truncate purged logs"), "{}", err);
+    }
+
+    tracing::info!("--- committed logs can not be truncated");
+    {
+        log_store.save_committed(Some(log_id(2, 1, 2))).await?;
+
+        let err = log_store.truncate(log_id(2, 1, 2)).await.unwrap_err();
+        assert!(err.to_string().contains("can not truncate committed logs"), "{}", err);
+
+        let err = log_store.save_committed(Some(log_id(1, 1, 1))).await.unwrap_err();
+        assert!(
+            err.to_string().contains("committed log id must not decrease"),
+            "{}",
+            err
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
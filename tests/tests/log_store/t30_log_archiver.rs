@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::entry::RaftEntry;
+use openraft::storage::ArchivingLogStore;
+use openraft::storage::LogArchiver;
+use openraft::Config;
+use openraft::Raft;
+use openraft::StorageError;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Collects the index of every archived entry.
+#[derive(Clone, Default)]
+struct IndexCollector {
+    archived: Arc<Mutex<Vec<u64>>>,
+}
+
+impl LogArchiver<TypeConfig> for IndexCollector {
+    async fn archive(&mut self, entries: Vec<openraft::Entry<TypeConfig>>) -> Result<(), StorageError<TypeConfig>> {
+        let mut archived = self.archived.lock().unwrap();
+        archived.extend(entries.iter().map(|e| e.index()));
+        Ok(())
+    }
+}
+
+/// Every purged entry is passed to the archiver exactly once, in order.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn log_archiver() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = RaftRouter::new(config.clone());
+
+    let collector = IndexCollector::default();
+
+    let (log_store, sm) = openraft_memstore::new_mem_store();
+    let log_store = ArchivingLogStore::new(log_store, collector.clone());
+
+    let raft = Raft::new(0, config, router, log_store, sm).await?;
+    raft.initialize(btreeset! {0}).await?;
+
+    let mut log_index = 1;
+    raft.wait(timeout()).applied_index(Some(log_index), "initialized").await?;
+
+    tracing::info!(log_index, "--- write, build snapshot and purge twice");
+    {
+        for upto in [5, 10] {
+            while log_index < upto {
+                raft.client_write(ClientRequest::make_request("foo", log_index)).await?;
+                log_index += 1;
+            }
+            raft.wait(timeout()).applied_index(Some(log_index), "written").await?;
+
+            raft.trigger().snapshot().await?;
+            raft.wait(timeout()).snapshot(log_id(1, 0, log_index), "snapshot built").await?;
+
+            raft.trigger().purge_log(log_index).await?;
+            raft.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purged").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- all purged entries are archived");
+    {
+        let archived = collector.archived.lock().unwrap().clone();
+        assert_eq!((0..=log_index).collect::<Vec<_>>(), archived);
+    }
+
+    raft.shutdown().await?;
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
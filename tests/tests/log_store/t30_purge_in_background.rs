@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft_memstore::BlockOperation;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A slow purge runs in the background: it does not block appending logs, and the purged log id
+/// in metrics is updated only when the purge is done.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn purge_in_background() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: u64::MAX,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write logs and build snapshot");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "snapshot").await?;
+    }
+
+    let snapshot_index = log_index;
+
+    tracing::info!(log_index, "--- block purging for 2 seconds, then purge");
+    {
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        sm0.block.set_blocking(BlockOperation::PurgeLog, Duration::from_millis(2_000));
+
+        n0.trigger().purge_log(snapshot_index).await?;
+    }
+
+    tracing::info!(log_index, "--- appending is not blocked by the purge in progress");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        router
+            .wait(&0, Some(Duration::from_millis(500)))
+            .applied_index(Some(log_index), "write logs during purge")
+            .await?;
+
+        let metrics = n0.metrics().borrow().clone();
+        assert_eq!(None, metrics.purged, "purge is not yet durable");
+    }
+
+    tracing::info!(log_index, "--- purge completes in the background");
+    {
+        router
+            .wait(&0, Some(Duration::from_millis(3_000)))
+            .purged(Some(log_id(1, 0, snapshot_index)), "purged")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A slow log flush delays committing: an entry is not applied until the log store reports it
+/// as flushed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn slow_log_flush_delays_commit() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- delay flushing log by 1 second");
+    {
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        sm0.block.set_blocking(BlockOperation::FlushLog, Duration::from_millis(1_000));
+    }
+
+    tracing::info!(log_index, "--- write one entry, it can not be applied before flushed");
+    {
+        let r = router.clone();
+        let handle = TypeConfig::spawn(async move { r.client_request(0, "foo", 1).await });
+        log_index += 1;
+
+        let res = router
+            .wait(&0, Some(Duration::from_millis(500)))
+            .applied_index(Some(log_index), "flush is delayed")
+            .await;
+        assert!(res.is_err(), "entry should not be applied before flushed");
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "flushed").await?;
+        handle.await??;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -8,3 +8,6 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_raft_config;
+mod t20_update_config;
+mod t21_effective_config;
+mod t22_pause_ticks;
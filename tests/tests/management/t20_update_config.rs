@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ConfigError;
+use openraft::ConfigPatch;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Update config of a running node with [`Raft::update_config`](openraft::Raft::update_config).
+///
+/// - A valid patch takes effect and is reported in metrics.
+/// - The election timeout is chosen again only if the patch changes its range.
+/// - An invalid patch is rejected and changes nothing.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn update_config() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- update snapshot policy and max payload entries");
+    {
+        let updated = n0
+            .update_config(ConfigPatch {
+                snapshot_policy: Some(SnapshotPolicy::LogsSinceLast(10)),
+                max_payload_entries: Some(5),
+                ..Default::default()
+            })
+            .await?;
+
+        assert_eq!(SnapshotPolicy::LogsSinceLast(10), updated.snapshot_policy);
+        assert_eq!(5, updated.max_payload_entries);
+        assert_eq!(
+            SnapshotPolicy::Never,
+            n0.config().snapshot_policy,
+            "initial config is unchanged"
+        );
+
+        n0.wait(timeout()).metrics(|m| m.config == updated, "updated config is reported in metrics").await?;
+    }
+
+    tracing::info!(log_index, "--- the updated snapshot policy takes effect");
+    {
+        router.client_request_many(0, "0", 10).await?;
+        log_index += 10;
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.snapshot.is_some(),
+                format!("snapshot is built after log {}", log_index),
+            )
+            .await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- election timeout is kept if the patch does not change its range"
+    );
+    {
+        let before = n0.effective_config().await?;
+
+        n0.update_config(ConfigPatch {
+            max_payload_entries: Some(6),
+            election_timeout_min: Some(config.election_timeout_min),
+            ..Default::default()
+        })
+        .await?;
+
+        let after = n0.effective_config().await?;
+        assert_eq!(before.election_timeout, after.election_timeout);
+        assert_eq!(6, after.config.max_payload_entries);
+    }
+
+    tracing::info!(log_index, "--- election timeout is chosen from the updated range");
+    {
+        n0.update_config(ConfigPatch {
+            election_timeout_min: Some(2_000),
+            election_timeout_max: Some(2_001),
+            ..Default::default()
+        })
+        .await?;
+
+        let c = n0.effective_config().await?;
+        assert_eq!(Duration::from_millis(2_000), c.election_timeout);
+        assert_eq!(Duration::from_millis(2_001), c.leader_lease);
+    }
+
+    tracing::info!(log_index, "--- an invalid patch is rejected as a whole");
+    {
+        let err = n0
+            .update_config(ConfigPatch {
+                max_payload_entries: Some(100),
+                election_timeout_min: Some(10_000),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.api_error(),
+            Some(ConfigError::ElectionTimeout { min: 10_000, .. })
+        ));
+
+        let c = n0.effective_config().await?;
+        assert_eq!(6, c.config.max_payload_entries);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
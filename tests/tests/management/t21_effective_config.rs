@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ConfigPatch;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Get the config in use with [`Raft::effective_config`](openraft::Raft::effective_config),
+/// before and after runtime updates.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn effective_config() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            election_timeout_min: 200,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- the initial config and derived values");
+    {
+        let c = n0.effective_config().await?;
+
+        assert_eq!(config.as_ref(), c.config.as_ref());
+        assert!(c.election_timeout >= Duration::from_millis(200));
+        assert!(c.election_timeout < Duration::from_millis(300));
+        assert_eq!(Duration::from_millis(300), c.leader_lease);
+        assert_eq!(config.snapshot_policy, c.snapshot_policy);
+
+        assert!(!c.enable_tick);
+        assert!(c.enable_heartbeat);
+        assert!(c.enable_elect);
+    }
+
+    tracing::info!(log_index, "--- runtime updates are reflected");
+    {
+        n0.update_config(ConfigPatch {
+            election_timeout_min: Some(1_000),
+            election_timeout_max: Some(1_100),
+            snapshot_policy: Some(SnapshotPolicy::Never),
+            ..Default::default()
+        })
+        .await?;
+        n0.runtime_config().heartbeat(false);
+        n0.runtime_config().tick(true);
+
+        let c = n0.effective_config().await?;
+
+        assert_eq!(1_000, c.config.election_timeout_min);
+        assert!(c.election_timeout >= Duration::from_millis(1_000));
+        assert!(c.election_timeout < Duration::from_millis(1_100));
+        assert_eq!(Duration::from_millis(1_100), c.leader_lease);
+        assert_eq!(SnapshotPolicy::Never, c.snapshot_policy);
+
+        assert!(c.enable_tick);
+        assert!(!c.enable_heartbeat);
+        assert!(c.enable_elect);
+    }
+
+    Ok(())
+}
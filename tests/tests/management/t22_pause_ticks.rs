@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::Raft;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A node with paused ticks neither elects nor sends heartbeats, until ticks are resumed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn pause_ticks() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 200,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- pause ticks on every node");
+    {
+        for id in [0, 1, 2] {
+            let n = router.get_raft_handle(&id)?;
+            n.runtime_config().pause_ticks().await?;
+            wait_ticks_paused(&n, true).await?;
+        }
+    }
+
+    // Let the heartbeats already sent be received.
+    TypeConfig::sleep(Duration::from_millis(100)).await;
+    let now = TypeConfig::now();
+
+    tracing::info!(log_index, "--- no heartbeat or election while paused");
+    {
+        TypeConfig::sleep(Duration::from_millis(1_000)).await;
+
+        for id in [1, 2] {
+            router.external_request(id, move |state| {
+                assert!(state.vote_last_modified() < Some(now), "no heartbeat received");
+            });
+        }
+
+        for id in [0, 1, 2] {
+            let m = router.get_raft_handle(&id)?.metrics().borrow().clone();
+            assert_eq!(1, m.vote.leader_id().term, "no election on node {}", id);
+            assert_eq!(Some(0), m.current_leader);
+        }
+    }
+
+    tracing::info!(log_index, "--- resume ticks on the leader, heartbeats are sent again");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.runtime_config().resume_ticks().await?;
+        wait_ticks_paused(&n0, false).await?;
+
+        TypeConfig::sleep(Duration::from_millis(300)).await;
+
+        for id in [1, 2] {
+            router.external_request(id, move |state| {
+                assert!(state.vote_last_modified() > Some(now), "heartbeat received");
+            });
+        }
+    }
+
+    Ok(())
+}
+
+async fn wait_ticks_paused(raft: &Raft<TypeConfig>, paused: bool) -> Result<()> {
+    let mut rx = raft.server_metrics();
+    let fu = async {
+        loop {
+            if rx.borrow_and_update().ticks_paused == paused {
+                return Ok::<(), anyhow::Error>(());
+            }
+            rx.changed().await?;
+        }
+    };
+    TypeConfig::timeout(Duration::from_millis(1_000), fu).await??;
+    Ok(())
+}
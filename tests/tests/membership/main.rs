@@ -13,11 +13,20 @@ mod t11_add_learner;
 mod t12_concurrent_write_and_add_learner;
 mod t20_change_membership;
 mod t21_change_membership_cases;
+mod t22_change_membership_preflight;
+mod t22_membership_validator;
+mod t23_update_node;
+mod t24_promote_demote;
+mod t25_auto_promote_demote;
+mod t26_queue_membership_changes;
+mod t27_remove_unreachable_nodes;
+mod t28_change_membership_via;
 mod t30_commit_joint_config;
 mod t30_elect_with_new_config;
 mod t31_add_remove_follower;
 mod t31_remove_leader;
 mod t31_removed_follower;
+mod t31_removed_leader_action;
 mod t51_remove_unreachable_follower;
 mod t52_change_membership_on_uninitialized_node;
 mod t99_issue_471_adding_learner_uses_uninit_leader_id;
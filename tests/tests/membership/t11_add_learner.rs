@@ -7,12 +7,13 @@ use maplit::btreeset;
 use openraft::error::ChangeMembershipError;
 use openraft::error::ClientWriteError;
 use openraft::error::InProgress;
+use openraft::type_config::TypeConfigExt;
 use openraft::ChangeMembers;
 use openraft::Config;
 use openraft::Membership;
 use openraft::RaftLogReader;
 use openraft::StorageHelper;
-use tokio::time::sleep;
+use openraft_memstore::TypeConfig;
 
 use crate::fixtures::log_id;
 use crate::fixtures::ut_harness;
@@ -148,7 +149,7 @@ async fn add_learner_non_blocking() -> Result<()> {
             if n1_repl.is_none() {
                 tracing::info!("--- no replication attempt is made, sleep and retry: {}-th attempt", i);
 
-                sleep(Duration::from_millis(500)).await;
+                TypeConfig::sleep(Duration::from_millis(500)).await;
                 continue;
             }
             assert_eq!(Some(&None), n1_repl, "no replication state to the learner is reported");
@@ -159,6 +160,58 @@ async fn add_learner_non_blocking() -> Result<()> {
     Ok(())
 }
 
+/// `add_learner_and_wait()` returns the replication progress of the learner once it catches up,
+/// or when the timeout elapses.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn add_learner_and_wait() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "learner_add", 100).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- add node-1, wait until it catches up");
+    {
+        router.new_raft_node(1).await;
+
+        let got = n0.add_learner_and_wait(1, (), 0, Duration::from_millis(3_000)).await?;
+        log_index += 1;
+
+        assert!(got.caught_up);
+        assert_eq!(log_id(1, 0, log_index), got.response.log_id);
+        assert_eq!(Some(log_id(1, 0, log_index)), got.matched);
+        assert_eq!(Some(log_index), got.leader_last_log_index);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- add unreachable node-2, it can not catch up before timeout"
+    );
+    {
+        router.new_raft_node(2).await;
+        router.set_network_error(2, true);
+
+        let got = n0.add_learner_and_wait(2, (), 0, Duration::from_millis(300)).await?;
+        log_index += 1;
+
+        assert!(!got.caught_up);
+        assert_eq!(None, got.matched);
+        assert_eq!(Some(log_index), got.leader_last_log_index);
+    }
+
+    Ok(())
+}
+
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
 async fn add_learner_with_set_nodes() -> Result<()> {
@@ -218,13 +271,13 @@ async fn add_learner_when_previous_membership_not_committed() -> Result<()> {
         router.set_network_error(1, true);
 
         let node = router.get_raft_handle(&0)?;
-        tokio::spawn(async move {
+        TypeConfig::spawn(async move {
             let res = node.change_membership([0, 1], false).await;
             tracing::info!("do not expect res: {:?}", res);
             unreachable!("do not expect any res");
         });
 
-        sleep(Duration::from_millis(500)).await;
+        TypeConfig::sleep(Duration::from_millis(500)).await;
     }
 
     tracing::info!(log_index, "--- add new node node-1, in non blocking mode");
@@ -4,10 +4,12 @@ use std::time::Duration;
 
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
 use openraft::LogIdOptionExt;
 use openraft::ServerState;
 use openraft::Vote;
+use openraft_memstore::TypeConfig;
 use tracing::Instrument;
 
 use crate::fixtures::ut_harness;
@@ -101,7 +103,7 @@ async fn concurrent_write_and_add_learner() -> Result<()> {
         let r = router.clone();
 
         let handle = {
-            tokio::spawn(
+            TypeConfig::spawn(
                 async move {
                     r.add_learner(leader, 3).await.unwrap();
                     Ok::<(), anyhow::Error>(())
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::ChangeMembershipError;
+use openraft::error::ClientWriteError;
+use openraft::Config;
+use openraft::LogIdOptionExt;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A dry-run reports the configs of a change and the missing and lagging voters, without appending
+/// any log. A checked change is rejected with the same report if it would not commit.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn change_membership_preflight() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            replication_lag_threshold: 5,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- dry-run adding a learner and an unknown node as voters");
+    {
+        let report = leader.change_membership_dry_run([0, 1, 2, 3], false).await?;
+        assert!(report.is_ok(), "report: {}", report);
+        assert!(report.joint.is_some());
+        assert_eq!(btreeset! {0,1,2,3}, report.target.voter_ids().collect());
+
+        let report = leader.change_membership_dry_run([0, 1, 2, 4], false).await?;
+        assert_eq!(btreeset! {4}, report.missing);
+        assert!(!report.is_ok());
+
+        let m = leader.metrics().borrow().clone();
+        assert_eq!(Some(log_index), m.last_log_index, "dry-run appends nothing");
+    }
+
+    tracing::info!(log_index, "--- dry-run on a follower");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let err = n1.change_membership_dry_run([0, 1, 2, 3], false).await.unwrap_err();
+        assert_eq!(Some(0), err.forward_to_leader().and_then(|f| f.leader_id));
+    }
+
+    tracing::info!(log_index, "--- isolate node-3 and let it lag behind");
+    {
+        router.set_network_error(3, true);
+        log_index += router.client_request_many(0, "foo", 10).await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- a change that needs the lagging node-3 for quorum is rejected"
+    );
+    {
+        let err = leader.change_membership_checked([0, 3], false).await.unwrap_err();
+
+        let ClientWriteError::ChangeMembershipError(ChangeMembershipError::PreflightFailed(failed)) =
+            err.into_api_error().unwrap()
+        else {
+            panic!("expect PreflightFailed");
+        };
+
+        let report = failed.report;
+        assert_eq!(btreeset! {0,1,2}, report.current.voter_ids().collect());
+        assert_eq!(
+            vec![btreeset! {0,1,2}, btreeset! {0,3}],
+            report.joint.unwrap().get_joint_config().clone()
+        );
+        assert_eq!(vec![btreeset! {0,3}], report.target.get_joint_config().clone());
+        assert_eq!(btreeset! {3}, report.lagging.keys().copied().collect());
+        let lagging = report.lagging[&3];
+        assert_eq!(Some(log_index - 10), lagging.matched);
+        assert_eq!(10, lagging.lag.entries_behind);
+        assert!(!report.quorum_preserved);
+
+        let m = leader.metrics().borrow().clone();
+        assert_eq!(Some(log_index), m.last_log_index, "rejected change appends nothing");
+    }
+
+    tracing::info!(log_index, "--- node-3 catches up, the checked change is accepted");
+    {
+        router.set_network_error(3, false);
+        leader
+            .wait(timeout())
+            .metrics(
+                |m| {
+                    let matched = m.replication.as_ref().and_then(|r| r.get(&3).cloned()).flatten();
+                    matched.index() == Some(log_index)
+                },
+                "node-3 catches up",
+            )
+            .await?;
+
+        leader.change_membership_checked([0, 3], false).await?;
+        log_index += 2;
+
+        leader.wait(timeout()).applied_index(Some(log_index), "membership changed").await?;
+        let membership = leader.current_membership().await;
+        assert_eq!(btreeset! {0,3}, membership.voter_ids().collect());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
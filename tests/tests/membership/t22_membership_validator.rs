@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+use openraft::error::ChangeMembershipError;
+use openraft::error::ClientWriteError;
+use openraft::membership::ZoneSpreadValidator;
+use openraft::Config;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A membership config rejected by the validator installed on the leader is not proposed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn membership_validator() -> anyhow::Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {1,2,3}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(
+        log_index,
+        "--- install a validator: node 0,1 in zone a, others in zone b"
+    );
+    {
+        leader
+            .set_membership_validator(ZoneSpreadValidator::new(2, |id: &u64, _: &()| {
+                Some(if *id < 2 { "a" } else { "b" })
+            }))
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- voters in a single zone are rejected");
+    {
+        let err = leader.change_membership([0, 1], false).await.unwrap_err();
+
+        let ClientWriteError::ChangeMembershipError(ChangeMembershipError::Rejected(rejected)) =
+            err.into_api_error().unwrap()
+        else {
+            panic!("expect Rejected");
+        };
+        tracing::info!("rejected: {}", rejected);
+
+        let m = leader.metrics().borrow().clone();
+        assert_eq!(Some(log_index), m.last_log_index, "nothing is proposed");
+    }
+
+    tracing::info!(log_index, "--- voters spanning two zones are accepted");
+    {
+        leader.change_membership([0, 1, 2], false).await?;
+        router.wait(&0, None).voter_ids([0, 1, 2], "voters spread across zones").await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::ChangeMembershipError;
+use openraft::error::ClientWriteError;
+use openraft::error::NodeNotFound;
+use openraft::error::Operation;
+use openraft::Config;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// `update_node()` appends a membership log that does not change the voter or learner sets, and
+/// fails if the node is not a member.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn update_node() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- update the node of a voter and a learner");
+    {
+        for node_id in [1, 3] {
+            let resp = leader.update_node(node_id, ()).await?;
+            log_index += 1;
+
+            let membership = resp.membership.unwrap();
+            assert_eq!(vec![btreeset! {0,1,2}], membership.get_joint_config().clone());
+            assert_eq!(btreeset! {3}, membership.learner_ids().collect());
+        }
+
+        for id in [0, 1, 2, 3] {
+            router
+                .wait(&id, timeout())
+                .metrics(
+                    |m| m.membership_config.log_id() == &Some(log_id(1, 0, log_index)),
+                    "every member sees the updated node",
+                )
+                .await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- can not update a node that is not a member");
+    {
+        let err = leader.update_node(4, ()).await.unwrap_err();
+        assert_eq!(
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::NodeNotFound(NodeNotFound::new(
+                4,
+                Operation::UpdateNode
+            ))),
+            err.into_api_error().unwrap()
+        );
+
+        let m = leader.metrics().borrow().clone();
+        assert_eq!(Some(log_index), m.last_log_index, "nothing is appended");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::error::ChangeMembershipError;
+use openraft::error::ClientWriteError;
+use openraft::error::LearnerNotFound;
+use openraft::Config;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// `promote_learner()` and `demote_voter()` change only the role of one node.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn promote_learner_and_demote_voter() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3,4}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- promote learner 3");
+    {
+        let resp = leader.promote_learner(3).await?;
+        log_index += 2;
+
+        let membership = resp.membership.unwrap();
+        assert_eq!(vec![btreeset! {0,1,2,3}], membership.get_joint_config().clone());
+        assert_eq!(btreeset! {4}, membership.learner_ids().collect());
+    }
+
+    tracing::info!(log_index, "--- demote voter 1, it stays as a learner");
+    {
+        let resp = leader.demote_voter(1).await?;
+        log_index += 2;
+
+        let membership = resp.membership.unwrap();
+        assert_eq!(vec![btreeset! {0,2,3}], membership.get_joint_config().clone());
+        assert_eq!(btreeset! {1,4}, membership.learner_ids().collect());
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "demoted voter receives logs").await?;
+    }
+
+    tracing::info!(log_index, "--- can not promote a node that is not a learner");
+    {
+        let err = leader.promote_learner(5).await.unwrap_err();
+        assert_eq!(
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::LearnerNotFound(LearnerNotFound {
+                node_id: 5
+            })),
+            err.into_api_error().unwrap()
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
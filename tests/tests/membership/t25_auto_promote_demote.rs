@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::RaftEvent;
+use openraft::raft::RaftEventReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig as MemConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// With [`Config::enable_auto_promote`] and [`Config::auto_demote_unreachable_period`], the leader
+/// promotes a caught-up learner and demotes an unreachable voter.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn auto_promote_and_demote() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_auto_promote: true,
+            auto_promote_stable_period: 200,
+            auto_demote_unreachable_period: Some(1_000),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let mut rx0 = n0.event_stream();
+
+    tracing::info!("--- add learner 3, it is promoted when caught up");
+    {
+        router.new_raft_node(3).await;
+        router.add_learner(0, 3).await?;
+
+        let got = recv_until(&mut rx0, |e| matches!(e, RaftEvent::LearnerAutoPromoted { .. })).await?;
+        let RaftEvent::LearnerAutoPromoted { target, .. } = got else {
+            unreachable!()
+        };
+        assert_eq!(3, target);
+
+        router.wait(&0, timeout()).voter_ids([0, 1, 2, 3], "learner 3 is promoted").await?;
+    }
+
+    tracing::info!("--- isolate voter 2, it is demoted to learner");
+    {
+        router.set_network_error(2, true);
+
+        let got = recv_until(&mut rx0, |e| matches!(e, RaftEvent::VoterAutoDemoted { .. })).await?;
+        let RaftEvent::VoterAutoDemoted { target, .. } = got else {
+            unreachable!()
+        };
+        assert_eq!(2, target);
+
+        let m = router.wait(&0, timeout()).voter_ids([0, 1, 3], "voter 2 is demoted").await?;
+        assert_eq!(
+            vec![2],
+            m.membership_config.membership().learner_ids().collect::<Vec<_>>(),
+            "node 2 is retained as a learner"
+        );
+    }
+
+    tracing::info!("--- the unreachable learner 2 is not promoted");
+    {
+        MemConfig::sleep(Duration::from_millis(500)).await;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(
+            btreeset! {0,1,3},
+            m.membership_config.membership().voter_ids().collect()
+        );
+    }
+
+    Ok(())
+}
+
+/// Receive events until one matches `f`.
+async fn recv_until(
+    rx: &mut RaftEventReceiver<MemConfig>,
+    f: impl Fn(&RaftEvent<MemConfig>) -> bool + Sync,
+) -> Result<RaftEvent<MemConfig>> {
+    let got = MemConfig::timeout(Duration::from_millis(5_000), async {
+        loop {
+            let event = rx.recv().await.expect("event stream is closed");
+            tracing::info!("recv event: {}", event);
+            if f(&event) {
+                return event;
+            }
+        }
+    })
+    .await?;
+    Ok(got)
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
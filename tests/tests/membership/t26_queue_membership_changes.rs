@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ChangeMembershipError;
+use openraft::error::ClientWriteError;
+use openraft::error::QueueFull;
+use openraft::error::RaftError;
+use openraft::Config;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// With [`Config::membership_change_queue_depth`], concurrent membership changes are applied one
+/// by one, and a change is rejected if the queue is full.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn queue_concurrent_membership_changes() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            membership_change_queue_depth: 2,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0}, btreeset! {1,2,3,4}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- promote 4 learners concurrently, the last one does not fit in the queue");
+    {
+        let (r1, r2, r3, r4) = futures::join!(
+            n0.promote_learner(1),
+            n0.promote_learner(2),
+            n0.promote_learner(3),
+            n0.promote_learner(4),
+        );
+
+        for r in [r1, r2, r3] {
+            let resp = r?;
+            assert_eq!(resp.membership.as_ref().map(|m| m.get_joint_config().len()), Some(1));
+        }
+
+        let err = r4.unwrap_err();
+        assert_eq!(
+            RaftError::APIError(ClientWriteError::ChangeMembershipError(
+                ChangeMembershipError::QueueFull(QueueFull { waiting: 2, depth: 2 })
+            )),
+            err
+        );
+
+        router.wait(&0, timeout()).voter_ids([0, 1, 2, 3], "learners 1,2,3 are promoted").await?;
+    }
+
+    tracing::info!("--- a change waits for a membership change not submitted in the queue");
+    {
+        router.new_raft_node(5).await;
+
+        let (added, promoted) = futures::join!(n0.add_learner(5, (), false), n0.promote_learner(5));
+        added?;
+        promoted?;
+
+        router.wait(&0, timeout()).voter_ids([0, 1, 2, 3, 5], "learner 5 is promoted").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
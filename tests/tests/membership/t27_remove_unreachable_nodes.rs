@@ -0,0 +1,77 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// [`Raft::remove_unreachable_nodes()`] removes only the nodes that are down for long enough.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn remove_unreachable_nodes() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0,1,2,3,4}, btreeset! {5}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- a follower can not remove nodes");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let err = n1.remove_unreachable_nodes(Duration::from_millis(500)).await.unwrap_err();
+        assert!(
+            matches!(err, RaftError::APIError(ClientWriteError::ForwardToLeader(_))),
+            "got: {}",
+            err
+        );
+    }
+
+    tracing::info!("--- isolate voter 3 and learner 5");
+    {
+        router.set_network_error(3, true);
+        router.set_network_error(5, true);
+    }
+
+    tracing::info!("--- not down for long enough, nothing is removed");
+    {
+        let res = n0.remove_unreachable_nodes(Duration::from_millis(2_000)).await?;
+        assert!(res.plan.unreachable.is_empty());
+        assert!(res.plan.is_empty());
+        assert!(res.responses.is_empty());
+    }
+
+    tracing::info!("--- down for long enough, 3 and 5 are removed");
+    {
+        TypeConfig::sleep(Duration::from_millis(1_000)).await;
+
+        let res = n0.remove_unreachable_nodes(Duration::from_millis(500)).await?;
+        assert_eq!(btreeset! {3,5}, res.plan.unreachable.keys().copied().collect());
+        assert_eq!(btreeset! {3}, res.plan.remove_voters);
+        assert_eq!(btreeset! {5}, res.plan.remove_learners);
+        assert!(res.plan.quorum_preserved);
+        assert_eq!(2, res.responses.len());
+
+        let m = router.wait(&0, timeout()).voter_ids([0, 1, 2, 4], "voter 3 is removed").await?;
+        assert_eq!(
+            btreeset! {0,1,2,4},
+            m.membership_config.membership().nodes().map(|(id, _)| *id).collect(),
+            "3 and 5 are no longer nodes"
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
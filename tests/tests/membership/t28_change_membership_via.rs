@@ -0,0 +1,71 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use openraft::ChangeMembers;
+use openraft::Config;
+use openraft::EntryPayload;
+use openraft::RaftLogReader;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Replacing most of the voters through an intermediate config commits a single joint config of
+/// the old, the intermediate and the new voters, then the uniform new config.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn change_membership_via_intermediate_config() -> anyhow::Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3,4}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- replace voters {{0,1,2}} with {{0,3,4}} via {{0,2,3}}");
+    {
+        let changes = ChangeMembers::ReplaceAllVotersVia(vec![btreeset! {0,2,3}, btreeset! {0,3,4}]);
+        leader.change_membership(changes, false).await?;
+        log_index += 2;
+
+        for id in [0, 3, 4] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "uniform config applied").await?;
+        }
+        router.wait(&3, timeout()).voter_ids([0, 3, 4], "new voters").await?;
+    }
+
+    tracing::info!(log_index, "--- the joint config includes every config");
+    {
+        let (mut sto, _sm) = router.get_storage_handle(&3)?;
+        let logs = sto.try_get_log_entries(log_index - 1..=log_index).await?;
+
+        let EntryPayload::Membership(joint) = &logs[0].payload else {
+            panic!("expect membership entry");
+        };
+        assert_eq!(
+            &vec![btreeset! {0,1,2}, btreeset! {0,2,3}, btreeset! {0,3,4}],
+            joint.get_joint_config()
+        );
+
+        let EntryPayload::Membership(uniform) = &logs[1].payload else {
+            panic!("expect membership entry");
+        };
+        assert_eq!(&vec![btreeset! {0,3,4}], uniform.get_joint_config());
+        assert_eq!(BTreeSet::new(), uniform.learner_ids().collect::<BTreeSet<_>>());
+    }
+
+    tracing::info!(log_index, "--- the new voters accept writes");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        for id in [0, 3, 4] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "written").await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -3,8 +3,10 @@ use std::time::Duration;
 
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
 use openraft::LogIdOptionExt;
+use openraft_memstore::TypeConfig;
 use tracing::Instrument;
 
 use crate::fixtures::ut_harness;
@@ -63,7 +65,7 @@ async fn commit_joint_config_during_0_to_012() -> Result<()> {
 
     tracing::info!(log_index, "--- changing cluster config, should timeout");
 
-    tokio::spawn({
+    TypeConfig::spawn({
         let router = router.clone();
         async move {
             let node = router.get_raft_handle(&0).unwrap();
@@ -124,7 +126,7 @@ async fn commit_joint_config_during_012_to_234() -> Result<()> {
     {
         let router = router.clone();
         // this is expected to be blocked since 3 and 4 are isolated.
-        tokio::spawn(
+        TypeConfig::spawn(
             async move {
                 let node = router.get_raft_handle(&0)?;
                 node.change_membership([2, 3, 4], false).await?;
@@ -3,9 +3,10 @@ use std::time::Duration;
 
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
 use openraft::LogIdOptionExt;
-use tokio::time::sleep;
+use openraft_memstore::TypeConfig;
 
 use crate::fixtures::ut_harness;
 use crate::fixtures::RaftRouter;
@@ -42,7 +43,7 @@ async fn leader_election_after_changing_0_to_01234() -> Result<()> {
     router.set_network_error(0, true);
 
     // Wait for leader lease to expire
-    sleep(Duration::from_millis(700)).await;
+    TypeConfig::sleep(Duration::from_millis(700)).await;
 
     // Let node-1 become leader.
     let node_1 = router.get_raft_handle(&1)?;
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::RaftEvent;
+use openraft::raft::RaftEventReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::RemovedLeaderAction;
+use openraft::ServerState;
+use openraft_memstore::TypeConfig as MemConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::LinkConfig;
+use crate::fixtures::RaftRouter;
+
+/// With [`RemovedLeaderAction::TransferLeader`], a removed leader hands over the leadership
+/// without waiting for the election timeout, then becomes a learner.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn removed_leader_transfers_leadership() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            // An election by timeout would not finish within `timeout()`.
+            election_timeout_min: 5_000,
+            election_timeout_max: 6_000,
+            removed_leader_action: RemovedLeaderAction::TransferLeader,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+    set_link_latency(&router);
+
+    let n0 = router.get_raft_handle(&0)?;
+    let mut rx0 = n0.event_stream();
+
+    tracing::info!("--- remove leader 0 from the voters");
+    {
+        n0.change_membership([1, 2], true).await?;
+
+        let got = recv_until(&mut rx0, |e| matches!(e, RaftEvent::RemovedFromMembership { .. })).await?;
+        let RaftEvent::RemovedFromMembership { action, .. } = got else {
+            unreachable!()
+        };
+        assert_eq!(RemovedLeaderAction::TransferLeader, action);
+    }
+
+    tracing::info!("--- a new leader is elected before the election timeout");
+    {
+        let m = router
+            .wait(&1, timeout())
+            .metrics(
+                |m| m.current_leader.is_some_and(|l| l == 1 || l == 2),
+                "node 1 or 2 becomes the leader",
+            )
+            .await?;
+        tracing::info!("new leader: {:?}", m.current_leader);
+
+        router.wait(&0, timeout()).state(ServerState::Learner, "node 0 becomes a learner").await?;
+    }
+
+    Ok(())
+}
+
+/// With [`RemovedLeaderAction::Shutdown`], a removed leader hands over the leadership, then shuts
+/// down.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn removed_leader_shuts_down() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            election_timeout_min: 5_000,
+            election_timeout_max: 6_000,
+            removed_leader_action: RemovedLeaderAction::Shutdown,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+    set_link_latency(&router);
+
+    let n0 = router.get_raft_handle(&0)?;
+    let mut rx0 = n0.event_stream();
+
+    tracing::info!("--- remove leader 0 from the cluster");
+    {
+        n0.change_membership([1, 2], false).await?;
+
+        let got = recv_until(&mut rx0, |e| matches!(e, RaftEvent::RemovedFromMembership { .. })).await?;
+        let RaftEvent::RemovedFromMembership { action, .. } = got else {
+            unreachable!()
+        };
+        assert_eq!(RemovedLeaderAction::Shutdown, action);
+    }
+
+    tracing::info!("--- node 0 shuts down and a new leader is elected");
+    {
+        router.wait(&0, timeout()).state(ServerState::Shutdown, "node 0 shuts down").await?;
+
+        router
+            .wait(&1, timeout())
+            .metrics(
+                |m| m.current_leader.is_some_and(|l| l == 1 || l == 2),
+                "node 1 or 2 becomes the leader",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Give every link a latency, as a real network has.
+///
+/// The removed leader sends TransferLeader to every voter at the same time. The vote request of
+/// the next leader then takes one more hop to reach the other voter than the TransferLeader, which
+/// disables the lease of the removed leader there. An in-process link without latency does not
+/// keep this order, and the other voter would reject the vote until the election timeout.
+fn set_link_latency(router: &RaftRouter) {
+    router.set_default_link(LinkConfig::new(Duration::from_millis(10), Duration::ZERO, 0.0));
+}
+
+/// Receive events until one matches `f`.
+async fn recv_until(
+    rx: &mut RaftEventReceiver<MemConfig>,
+    f: impl Fn(&RaftEvent<MemConfig>) -> bool + Sync,
+) -> Result<RaftEvent<MemConfig>> {
+    let got = MemConfig::timeout(Duration::from_millis(3_000), async {
+        loop {
+            let event = rx.recv().await.expect("event stream is closed");
+            tracing::info!("recv event: {}", event);
+            if f(&event) {
+                return event;
+            }
+        }
+    })
+    .await?;
+    Ok(got)
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -7,10 +7,24 @@ mod fixtures;
 // The number indicate the preferred running order for these case.
 // The later tests may depend on the earlier ones.
 
+mod t10_core_loop_metrics;
 mod t10_current_leader;
+mod t10_election_metrics;
+mod t10_event_listener;
+mod t10_event_stream;
+mod t10_io_metrics;
 mod t10_leader_last_ack;
+mod t10_log_stats;
+mod t10_metrics_flush_interval;
+mod t10_network_metrics;
 mod t10_purged;
+mod t10_replication_inflight;
+mod t10_replication_lag;
+mod t10_replication_metrics;
 mod t10_server_metrics_and_data_metrics;
+mod t10_server_state_watch;
+mod t10_slow_ops;
+mod t10_status;
 mod t20_metrics_state_machine_consistency;
 mod t30_leader_metrics;
 mod t40_metrics_wait;
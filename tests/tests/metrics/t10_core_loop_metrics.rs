@@ -0,0 +1,49 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The saturation metrics of the `RaftCore` loop are reported in data metrics once a window
+/// completes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn core_loop_metrics() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 100).await?;
+
+    tracing::info!(log_index, "--- wait for the first window to complete");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let mut rx = n0.data_metrics();
+
+        let fu = async {
+            loop {
+                let m = rx.borrow_and_update().core_loop;
+                if m.messages_per_flush > 0 {
+                    return Ok::<_, anyhow::Error>(m);
+                }
+                rx.changed().await?;
+            }
+        };
+        let m = TypeConfig::timeout(Duration::from_millis(3_000), fu).await??;
+
+        assert!(m.max_queue_depth >= m.messages_per_flush, "{}", m);
+        assert!(m.commands_per_flush > 0, "{}", m);
+        assert!(m.busy_permille <= 1000, "{}", m);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// `RaftServerMetrics::election` counts the elections and records the last leader change.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn election_metrics() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- node-0 won the election");
+    let elected_term = {
+        let m = n0.server_metrics().borrow().clone();
+        assert_eq!(1, m.election.elections_started);
+        assert_eq!(1, m.election.elections_won);
+        assert_eq!(0, m.election.elections_lost);
+
+        let change = m.election.last_leader_change.unwrap();
+        assert_eq!(Some(0), change.leader);
+
+        let m1 = n1.server_metrics().borrow().clone();
+        assert_eq!(0, m1.election.elections_started);
+        assert_eq!(Some(0), m1.election.last_leader_change.unwrap().leader);
+
+        change.term
+    };
+
+    tracing::info!(log_index, "--- transfer leadership to node-1, the change is recorded");
+    {
+        n0.trigger().transfer_leader(1).await?;
+
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+
+        let m1 = n1.server_metrics().borrow().clone();
+        assert_eq!(1, m1.election.elections_started);
+        assert_eq!(1, m1.election.elections_won);
+
+        n0.wait(timeout()).current_leader(1, "node-0 sees the new leader").await?;
+
+        let m = n0.server_metrics().borrow().clone();
+        let change = m.election.last_leader_change.unwrap();
+        assert_eq!(Some(1), change.leader);
+        assert!(change.term > elected_term);
+    }
+
+    Ok(())
+}
+
+/// The last leader change is timed when the leader changes, not when the metrics are flushed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn last_leader_change_time() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            metrics_flush_interval: 500,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- the change is recorded before the server state is seen");
+    {
+        let mut rx = n1.server_state_watch();
+        n0.trigger().transfer_leader(1).await?;
+
+        TypeConfig::timeout(
+            Duration::from_millis(1_000),
+            rx.wait_for(|s| s.state == ServerState::Leader),
+        )
+        .await??;
+        let seen = TypeConfig::now();
+
+        n1.wait(timeout()).current_leader(1, "node-1 reports itself as the leader").await?;
+
+        let change = n1.server_metrics().borrow().election.last_leader_change.clone().unwrap();
+        assert_eq!(Some(1), change.leader);
+        assert!(*change.at <= seen);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::RaftEventListener;
+use openraft::type_config::alias::VoteOf;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::SnapshotMeta;
+use openraft::StoredMembership;
+use openraft_memstore::TypeConfig as MemConfig;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Forwards the callbacks it receives to a channel.
+struct Recorder {
+    tx: UnboundedSender<String>,
+}
+
+impl RaftEventListener<MemConfig> for Recorder {
+    fn on_leader_elected(&mut self, vote: &VoteOf<MemConfig>) {
+        let _ = self.tx.send(format!("leader_elected: {}", vote.leader_id().node_id));
+    }
+
+    fn on_stepped_down(&mut self, vote: &VoteOf<MemConfig>) {
+        let _ = self.tx.send(format!("stepped_down: {}", vote.leader_id().node_id));
+    }
+
+    fn on_membership_committed(&mut self, membership: &StoredMembership<MemConfig>) {
+        let ids = membership.nodes().map(|(id, _)| *id).collect::<Vec<_>>();
+        let _ = self.tx.send(format!("membership_committed: {:?}", ids));
+    }
+
+    fn on_snapshot_built(&mut self, meta: &SnapshotMeta<MemConfig>) {
+        let _ = self.tx.send(format!("snapshot_built: {:?}", meta.last_log_id.map(|x| x.index)));
+    }
+}
+
+/// [`Raft::add_listener()`](openraft::Raft::add_listener) calls the typed callbacks of a
+/// listener.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn event_listener() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    let (tx0, mut rx0) = tokio::sync::mpsc::unbounded_channel();
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    n0.add_listener(Recorder { tx: tx0 });
+    n1.add_listener(Recorder { tx: tx1 });
+
+    tracing::info!(log_index, "--- add learner 3, the membership is committed");
+    {
+        router.new_raft_node(3).await;
+        router.add_learner(0, 3).await?;
+        log_index += 1;
+
+        recv_until(&mut rx0, "membership_committed: [0, 1, 2, 3]").await?;
+    }
+
+    tracing::info!(log_index, "--- build a snapshot");
+    {
+        n0.trigger().snapshot().await?;
+
+        recv_until(&mut rx0, &format!("snapshot_built: Some({})", log_index)).await?;
+    }
+
+    tracing::info!(log_index, "--- transfer leadership to node-1");
+    {
+        n0.trigger().transfer_leader(1).await?;
+
+        recv_until(&mut rx0, "stepped_down: 0").await?;
+        recv_until(&mut rx1, "leader_elected: 1").await?;
+    }
+
+    Ok(())
+}
+
+/// Receive callbacks until one equals `want`.
+async fn recv_until(rx: &mut UnboundedReceiver<String>, want: &str) -> Result<()> {
+    MemConfig::timeout(Duration::from_millis(3_000), async {
+        loop {
+            let got = rx.recv().await.expect("listener is dropped");
+            tracing::info!("recv callback: {}", got);
+            if got == want {
+                return;
+            }
+        }
+    })
+    .await?;
+    Ok(())
+}
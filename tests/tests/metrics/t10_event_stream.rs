@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::RaftEvent;
+use openraft::raft::RaftEventReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig as MemConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// [`Raft::event_stream()`](openraft::Raft::event_stream) sends the structured events of a node.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn event_stream() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+    let mut rx0 = n0.event_stream();
+    let mut rx1 = n1.event_stream();
+
+    tracing::info!(log_index, "--- add learner 3, the membership is committed");
+    {
+        router.new_raft_node(3).await;
+        router.add_learner(0, 3).await?;
+        log_index += 1;
+
+        let got = recv_until(&mut rx0, |e| matches!(e, RaftEvent::MembershipCommitted { .. })).await?;
+        let RaftEvent::MembershipCommitted { membership } = got else {
+            unreachable!()
+        };
+        assert_eq!(Some(log_index), membership.log_id().as_ref().map(|x| x.index));
+        assert_eq!(btreeset! {0,1,2,3}, membership.nodes().map(|(id, _)| *id).collect());
+    }
+
+    tracing::info!(log_index, "--- build a snapshot");
+    {
+        n0.trigger().snapshot().await?;
+
+        let got = recv_until(&mut rx0, |e| matches!(e, RaftEvent::SnapshotBuilt { .. })).await?;
+        let RaftEvent::SnapshotBuilt { meta } = got else {
+            unreachable!()
+        };
+        assert_eq!(Some(log_index), meta.last_log_id.map(|x| x.index));
+    }
+
+    tracing::info!(log_index, "--- isolate learner 3, the replication to it is stalled");
+    {
+        router.set_network_error(3, true);
+        log_index += router.client_request_many(0, "foo", 1).await?;
+
+        let got = recv_until(&mut rx0, |e| matches!(e, RaftEvent::ReplicationStalled { .. })).await?;
+        let RaftEvent::ReplicationStalled { target, .. } = got else {
+            unreachable!()
+        };
+        assert_eq!(3, target);
+
+        router.set_network_error(3, false);
+    }
+
+    tracing::info!(log_index, "--- transfer leadership to node-1");
+    {
+        n0.trigger().transfer_leader(1).await?;
+
+        recv_until(&mut rx0, |e| matches!(e, RaftEvent::SteppedDown { .. })).await?;
+
+        let got = recv_until(&mut rx1, |e| matches!(e, RaftEvent::LeaderElected { .. })).await?;
+        let RaftEvent::LeaderElected { vote } = got else {
+            unreachable!()
+        };
+        assert_eq!(1, vote.leader_id().node_id);
+    }
+
+    Ok(())
+}
+
+/// Receive events until one matches `f`.
+async fn recv_until(
+    rx: &mut RaftEventReceiver<MemConfig>,
+    f: impl Fn(&RaftEvent<MemConfig>) -> bool + Sync,
+) -> Result<RaftEvent<MemConfig>> {
+    let got = MemConfig::timeout(Duration::from_millis(3_000), async {
+        loop {
+            let event = rx.recv().await.expect("event stream is closed");
+            tracing::info!("recv event: {}", event);
+            if f(&event) {
+                return event;
+            }
+        }
+    })
+    .await?;
+    Ok(got)
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The IO counters count appended and purged log entries, sent and received snapshots, and RPCs.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn io_metrics() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let n = 10;
+    tracing::info!(log_index, "--- write {} logs", n);
+    log_index += router.client_request_many(0, "foo", n).await?;
+
+    tracing::info!(log_index, "--- entries and AppendEntries RPCs are counted");
+    {
+        n0.wait(timeout()).applied_index(Some(log_index), "applied").await?;
+
+        let io = n0.data_metrics().borrow().io.clone();
+        assert_eq!(log_index + 1, io.log_entries_appended);
+
+        let append = io.rpcs.get(&RPCTypes::AppendEntries).copied().unwrap_or_default();
+        assert!(append.ok > 0, "AppendEntries: {}", append);
+        assert_eq!(0, append.error);
+    }
+
+    tracing::info!(log_index, "--- purged entries are counted");
+    {
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purged").await?;
+
+        let io = n0.data_metrics().borrow().io.clone();
+        assert_eq!(log_index + 1, io.log_entries_purged);
+    }
+
+    tracing::info!(log_index, "--- add a learner, which receives a snapshot");
+    {
+        router.new_raft_node(3).await;
+        router.add_learner(0, 3).await?;
+        log_index += 1;
+
+        let n3 = router.get_raft_handle(&3)?;
+        n3.wait(timeout()).applied_index(Some(log_index), "learner catches up").await?;
+
+        let io = n0.data_metrics().borrow().io.clone();
+        assert_eq!(1, io.snapshots_sent);
+        assert!(io.snapshot_bytes_sent > 0);
+        let snapshot_bytes_sent = io.snapshot_bytes_sent;
+        assert_eq!(
+            1,
+            io.rpcs.get(&RPCTypes::InstallSnapshot).map(|c| c.ok).unwrap_or_default()
+        );
+
+        let io = n3.data_metrics().borrow().io.clone();
+        assert_eq!(1, io.snapshots_received);
+        assert_eq!(snapshot_bytes_sent, io.snapshot_bytes_received);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::LogStats;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::Raft;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Log store statistics are polled and reported in data metrics, and shrink after purging.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn metrics_log_stats() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            log_stats_interval: 10,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initialize cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n = 10;
+    tracing::info!(log_index, "--- write {} logs", n);
+    log_index += router.client_request_many(0, "foo", n).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- all logs are counted");
+    {
+        let stats = wait_log_stats(&n0, |s| s.entries == log_index + 1).await?;
+        assert!(stats.bytes > 0);
+        assert_eq!(None, stats.segments);
+    }
+
+    tracing::info!(log_index, "--- build snapshot and purge all logs");
+    {
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purged").await?;
+
+        let stats = wait_log_stats(&n0, |s| s.entries == 0).await?;
+        assert_eq!(0, stats.bytes);
+    }
+
+    Ok(())
+}
+
+/// Wait until the log stats in data metrics satisfy `f`.
+async fn wait_log_stats(raft: &Raft<TypeConfig>, f: impl Fn(&LogStats) -> bool + Sync) -> Result<LogStats> {
+    let mut rx = raft.data_metrics();
+
+    let res = TypeConfig::timeout(timeout().unwrap(), async {
+        loop {
+            if let Some(stats) = &rx.borrow_and_update().log_stats {
+                if f(stats) {
+                    return Ok(stats.clone());
+                }
+            }
+            rx.changed().await?;
+        }
+    })
+    .await;
+
+    match res {
+        Ok(x) => x,
+        Err(_) => Err(anyhow::anyhow!(
+            "timeout waiting for log stats: {:?}",
+            rx.borrow().log_stats
+        )),
+    }
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
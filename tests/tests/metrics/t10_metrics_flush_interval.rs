@@ -0,0 +1,140 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// With `metrics_flush_interval`, the changes are still flushed when the node becomes idle, and
+/// unchanged server metrics do not notify the subscribers.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn metrics_flush_interval() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            metrics_flush_interval: 200,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let n = 10;
+    tracing::info!(log_index, "--- write {} logs, the postponed changes are flushed", n);
+    {
+        log_index += router.client_request_many(0, "foo", n).await?;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "applied").await?;
+        }
+        assert_eq!(
+            Some(log_index),
+            n0.data_metrics().borrow().last_applied.as_ref().map(|x| x.index)
+        );
+    }
+
+    tracing::info!(log_index, "--- unchanged server metrics do not notify subscribers");
+    {
+        let mut server_metrics = n0.server_metrics();
+        server_metrics.borrow_and_update();
+
+        TypeConfig::sleep(Duration::from_millis(500)).await;
+
+        assert!(!server_metrics.has_changed()?, "server metrics should not change");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
+
+/// With `replication_metrics_flush_interval`, the replication metrics are flushed at most once per
+/// interval, and the postponed changes are still flushed when the node becomes idle.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replication_metrics_flush_interval() -> Result<()> {
+    let interval = 500;
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            replication_metrics_flush_interval: interval,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let n = 10;
+    tracing::info!(
+        log_index,
+        "--- write {} logs, the replication metrics are flushed less often",
+        n
+    );
+    {
+        let mut rx = n0.replication_metrics();
+        rx.borrow_and_update();
+
+        let notified = Arc::new(AtomicU64::new(0));
+        let counter = {
+            let notified = notified.clone();
+            TypeConfig::spawn(async move {
+                while rx.changed().await.is_ok() {
+                    notified.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        };
+
+        let start = TypeConfig::now();
+        log_index += router.client_request_many(0, "foo", n).await?;
+
+        let mut rx = n0.replication_metrics();
+        TypeConfig::timeout(
+            Duration::from_millis(interval * 4),
+            rx.wait_for(|m| {
+                m.replication
+                    .as_ref()
+                    .is_some_and(|r| r.values().all(|matching| matching.map(|x| x.index) == Some(log_index)))
+            }),
+        )
+        .await??;
+        let elapsed = TypeConfig::now() - start;
+
+        counter.abort();
+        let notified = notified.load(Ordering::Relaxed);
+
+        let max = elapsed.as_millis() as u64 / interval + 2;
+        assert!(
+            notified <= max,
+            "notified {} times in {:?}, at most {}",
+            notified,
+            elapsed,
+            max
+        );
+    }
+
+    Ok(())
+}
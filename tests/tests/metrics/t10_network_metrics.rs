@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The leader records the latency histograms of the replication RPCs to every target.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn network_metrics() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            rpc_latency_buckets: vec![10, 100],
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 5).await?;
+
+    tracing::info!(log_index, "--- every target has AppendEntries latency recorded");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let mut rx = n0.network_metrics();
+        let m = TypeConfig::timeout(timeout(), rx.wait_for(|m| m.rpc_latency.len() == 2)).await??.clone();
+
+        assert_eq!(btreeset! {1,2}, m.rpc_latency.keys().copied().collect());
+
+        for (target, histograms) in m.rpc_latency.iter() {
+            let h = &histograms[&RPCTypes::AppendEntries];
+
+            assert_eq!(vec![10, 100], h.bounds, "target {}", target);
+            assert_eq!(3, h.counts.len());
+            assert!(h.total > 0, "target {}: {}", target, h);
+            assert_eq!(h.total, h.counts.iter().sum::<u64>());
+            assert!(h.mean().is_some());
+        }
+    }
+
+    tracing::info!(log_index, "--- a follower sends no replication RPC");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let m = n1.network_metrics().borrow().clone();
+        assert!(m.rpc_latency.is_empty());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Duration {
+    Duration::from_millis(1_000)
+}
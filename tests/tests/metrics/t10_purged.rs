@@ -47,6 +47,13 @@ async fn metrics_purged() -> Result<()> {
             )
             .await?;
 
+        tracing::info!(log_index, "--- data metrics reports snapshot and purge positions");
+        let dm = n0.data_metrics().borrow().clone();
+        assert_eq!(dm.snapshot_last_log_id, Some(log_id(1, 0, log_index)));
+        assert_eq!(dm.purge_upto, Some(log_id(1, 0, log_index)));
+        assert_eq!(dm.purge_submitted, Some(log_id(1, 0, log_index)));
+        assert_eq!(dm.purged, Some(log_id(1, 0, log_index)));
+
         tracing::info!(log_index, "--- check storage at once to ensure purged log is removed");
         let (mut sto0, _sm0) = router.get_storage_handle(&0)?;
         let state = sto0.get_log_state().await?;
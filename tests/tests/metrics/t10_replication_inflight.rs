@@ -0,0 +1,87 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::ReplicationInflight;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The leader reports the logs being sent to every target, and nothing once they are acked.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replication_inflight() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- nothing is in flight when idle");
+    {
+        let mut rx = n0.replication_metrics();
+        let m = TypeConfig::timeout(
+            timeout(),
+            rx.wait_for(|m| m.replication_inflight.as_ref().unwrap().values().all(|x| x.is_none())),
+        )
+        .await??
+        .clone();
+        let inflight = m.replication_inflight.unwrap();
+        assert_eq!(btreeset! {0,1,2}, inflight.keys().copied().collect());
+
+        let n1 = router.get_raft_handle(&1)?;
+        assert!(
+            n1.replication_metrics().borrow().replication_inflight.is_none(),
+            "not a leader"
+        );
+    }
+
+    tracing::info!(log_index, "--- delay the network, the logs being sent are in flight");
+    {
+        router.network_send_delay(1_000);
+
+        let r = n0.clone();
+        let write = TypeConfig::spawn(async move { r.client_write(ClientRequest::make_request("foo", 1)).await });
+        log_index += 1;
+
+        let mut rx = n0.replication_metrics();
+        TypeConfig::timeout(
+            timeout(),
+            rx.wait_for(|m| {
+                let inflight = m.replication_inflight.as_ref().unwrap();
+                [1, 2].iter().any(|id| {
+                    matches!(
+                        &inflight[id],
+                        Some(ReplicationInflight::Logs { last: Some(last), .. }) if last.index == log_index
+                    )
+                })
+            }),
+        )
+        .await??;
+
+        write.await??;
+        router.network_send_delay(0);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Duration {
+    Duration::from_millis(1_000)
+}
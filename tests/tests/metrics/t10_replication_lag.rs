@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::ReplicationLag;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// The leader reports how many entries every target lags behind.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replication_lag() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- no lag when every target is up to date");
+    {
+        let mut rx = n0.replication_metrics();
+        let m = TypeConfig::timeout(
+            timeout(),
+            rx.wait_for(|m| {
+                let lag = m.replication_lag.as_ref().unwrap();
+                lag.values().all(|l| l.entries_behind == 0)
+            }),
+        )
+        .await??
+        .clone();
+
+        let lag = m.replication_lag.unwrap();
+        assert_eq!(btreeset! {0,1,2,3}, lag.keys().copied().collect());
+        for l in lag.values() {
+            assert_eq!(
+                &ReplicationLag {
+                    entries_behind: 0,
+                    millis_behind: Some(0)
+                },
+                l
+            );
+        }
+    }
+
+    tracing::info!(log_index, "--- isolate learner 3, it lags behind");
+    {
+        router.set_network_error(3, true);
+
+        let n = 5;
+        log_index += router.client_request_many(0, "foo", n).await?;
+
+        let mut rx = n0.replication_metrics();
+        let m = TypeConfig::timeout(
+            timeout(),
+            rx.wait_for(|m| {
+                let lag = m.replication_lag.as_ref().unwrap();
+                lag[&1].entries_behind == 0 && lag[&2].entries_behind == 0
+            }),
+        )
+        .await??
+        .clone();
+
+        let lag = m.replication_lag.unwrap();
+        assert_eq!(n as u64, lag[&3].entries_behind);
+    }
+
+    tracing::info!(log_index, "--- restore learner 3, the lag is gone");
+    {
+        router.set_network_error(3, false);
+        router.client_request_many(0, "foo", 1).await?;
+
+        let mut rx = n0.replication_metrics();
+        TypeConfig::timeout(
+            timeout(),
+            rx.wait_for(|m| m.replication_lag.as_ref().unwrap()[&3].entries_behind == 0),
+        )
+        .await??;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Duration {
+    Duration::from_millis(1_000)
+}
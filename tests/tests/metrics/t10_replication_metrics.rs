@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig;
+#[allow(unused_imports)]
+use pretty_assertions::assert_eq;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Replication metrics are sent on their own channel, and replication progress does not notify
+/// the subscribers of server metrics.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replication_metrics() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let mut server_metrics = n0.server_metrics();
+    server_metrics.borrow_and_update();
+
+    let n = 10;
+    tracing::info!(log_index, "--- write {} logs", n);
+    log_index += router.client_request_many(0, "foo", n).await?;
+
+    tracing::info!(log_index, "--- leader reports replication progress of every target");
+    {
+        let mut rx = n0.replication_metrics();
+        let want = btreemap! {
+            0 => Some(log_id(1, 0, log_index)),
+            1 => Some(log_id(1, 0, log_index)),
+            2 => Some(log_id(1, 0, log_index)),
+        };
+
+        let m = TypeConfig::timeout(timeout(), rx.wait_for(|m| m.replication.as_ref() == Some(&want)))
+            .await??
+            .clone();
+
+        assert_eq!(Some(3), m.heartbeat.as_ref().map(|h| h.len()));
+        assert_eq!(Some(3), m.replication_inflight.as_ref().map(|i| i.len()));
+    }
+
+    tracing::info!(log_index, "--- RaftMetrics::replication catches up at its own flush");
+    {
+        let mut rx = n0.metrics();
+        let want = btreemap! {
+            0 => Some(log_id(1, 0, log_index)),
+            1 => Some(log_id(1, 0, log_index)),
+            2 => Some(log_id(1, 0, log_index)),
+        };
+
+        TypeConfig::timeout(timeout(), rx.wait_for(|m| m.replication.as_ref() == Some(&want))).await??;
+    }
+
+    tracing::info!(log_index, "--- server metrics are not notified by replication");
+    {
+        assert!(!server_metrics.has_changed()?);
+    }
+
+    tracing::info!(log_index, "--- a follower reports no replication metrics");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let m = n1.replication_metrics().borrow().clone();
+        assert_eq!(None, m.replication);
+        assert_eq!(None, m.heartbeat);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Duration {
+    Duration::from_millis(1_000)
+}
@@ -118,6 +118,26 @@ async fn heartbeat_metrics() -> Result<()> {
             .expect("expect heartbeat to be Some as metrics come from the leader node");
         refreshed_node1 = heartbeat.get(&1).unwrap().unwrap();
         refreshed_node2 = heartbeat.get(&2).unwrap().unwrap();
+
+        tracing::info!(
+            log_index,
+            "--- heartbeat ack has the same sending time, along with the rtt"
+        );
+        let replication_metrics = leader.replication_metrics().borrow().clone();
+        let heartbeat_ack = replication_metrics
+            .heartbeat_ack
+            .as_ref()
+            .expect("expect heartbeat_ack to be Some as metrics come from the leader node");
+
+        for (id, refreshed) in [(1, refreshed_node1), (2, refreshed_node2)] {
+            let ack = heartbeat_ack.get(&id).unwrap();
+            assert_eq!(refreshed, ack.sent_at);
+            assert!(
+                ack.rtt <= TypeConfig::now() - now,
+                "rtt is bounded by the time since triggering"
+            );
+            assert_eq!(ack.rtt / 2, ack.max_clock_skew);
+        }
     }
 
     tracing::info!(log_index, "--- sleep 500 ms, the acked time should not change");
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::async_runtime::watch::WatchReceiver;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// [`Raft::server_state_watch()`](openraft::Raft::server_state_watch) sends the server state along
+/// with the vote when either changes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn server_state_watch() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- the leader and a follower see their states");
+    let leader_vote = {
+        let got = n0.server_state_watch().borrow_watched().clone();
+        assert_eq!(ServerState::Leader, got.state);
+        assert!(got.is_leader());
+
+        let got1 = n1.server_state_watch().borrow_watched().clone();
+        assert_eq!(ServerState::Follower, got1.state);
+
+        got.vote
+    };
+
+    tracing::info!(
+        log_index,
+        "--- transfer leadership to node-1, node-0 is notified that it steps down"
+    );
+    {
+        let mut rx = n0.server_state_watch();
+
+        n0.trigger().transfer_leader(1).await?;
+
+        let got = TypeConfig::timeout(Duration::from_millis(1_000), async {
+            loop {
+                rx.changed().await?;
+                let got = rx.borrow_watched().clone();
+                if got.state != ServerState::Leader {
+                    return Ok::<_, anyhow::Error>(got);
+                }
+            }
+        })
+        .await??;
+
+        assert_eq!(ServerState::Follower, got.state);
+        assert!(got.vote > leader_vote, "stepped down with a greater vote: {}", got);
+    }
+
+    Ok(())
+}
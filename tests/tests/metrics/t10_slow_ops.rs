@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// RPCs slower than `slow_rpc_threshold` are counted in `RaftDataMetrics::slow_ops`, while the
+/// disabled thresholds count nothing.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn slow_ops() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            slow_append_threshold: 0,
+            slow_apply_threshold: 0,
+            slow_snapshot_build_threshold: 0,
+            slow_rpc_threshold: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::builder(config.clone()).send_delay(20).build();
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write to a lagging network, slow RPCs are counted");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout())
+            .metrics(|m| m.snapshot.map(|x| x.index) == Some(log_index), "snapshot built")
+            .await?;
+
+        let m = n0.data_metrics().borrow().clone();
+        assert!(m.slow_ops.rpcs > 0, "slow rpcs: {}", m.slow_ops);
+        assert_eq!(0, m.slow_ops.appends);
+        assert_eq!(0, m.slow_ops.applies);
+        assert_eq!(0, m.slow_ops.snapshot_builds);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// [`Raft::status()`](openraft::Raft::status) returns the metrics, membership, replication progress
+/// and config of a node in one view.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn status() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    log_index += router.client_request_many(0, "foo", 5).await?;
+    router.wait(&0, None).applied_index(Some(log_index), "leader applied").await?;
+    router
+        .wait(&0, None)
+        .metrics(
+            |m| m.replication.as_ref().unwrap().values().all(|x| x.map(|x| x.index) == Some(log_index)),
+            "replicated to every target",
+        )
+        .await?;
+
+    tracing::info!(
+        log_index,
+        "--- the leader reports the replication progress to every target"
+    );
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let st = n0.status().await?;
+
+        assert_eq!(ServerState::Leader, st.server.state);
+        assert_eq!(Some(0), st.server.current_leader);
+        assert_eq!(Some(log_index), st.data.last_applied.map(|x| x.index));
+        assert_eq!(
+            btreeset! {0,1,2,3},
+            st.committed_membership.nodes().map(|(id, _)| *id).collect()
+        );
+        assert_eq!(config.heartbeat_interval, st.config.config.heartbeat_interval);
+        assert!(st.config.enable_tick);
+
+        let replication = st.replication.unwrap();
+        assert_eq!(btreeset! {0,1,2,3}, replication.keys().copied().collect());
+        for (id, r) in replication {
+            assert_eq!(Some(log_index), r.matching.map(|x| x.index), "target {}", id);
+            assert_eq!(None, r.inflight, "target {}", id);
+        }
+    }
+
+    tracing::info!(log_index, "--- a follower has no replication progress");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let st = n1.status().await?;
+
+        assert_eq!(ServerState::Follower, st.server.state);
+        assert_eq!(Some(0), st.server.current_leader);
+        assert!(st.replication.is_none());
+    }
+
+    Ok(())
+}
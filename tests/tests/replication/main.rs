@@ -5,6 +5,10 @@
 mod fixtures;
 
 mod t10_append_entries_partial_success;
+mod t20_link_latency_and_drop;
+mod t21_rpc_duplicate_and_reorder;
+mod t22_reject_malformed_rpc;
+mod t23_heartbeat_coalescing;
 mod t50_append_entries_backoff;
 mod t50_append_entries_backoff_rejoin;
 mod t51_append_entries_too_large;
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::LinkConfig;
+use crate::fixtures::RaftRouter;
+
+/// Replication works under latency, jitter and packet loss, and recovers when a link is reset.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn link_latency_and_drop() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            // The RPC timeout is the heartbeat interval, which must be greater than the link latency.
+            heartbeat_interval: 500,
+            election_timeout_min: 3_000,
+            election_timeout_max: 3_001,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- every link has a latency of 100 ms");
+    {
+        router.set_default_link(LinkConfig::new(Duration::from_millis(100), Duration::ZERO, 0.0));
+
+        let now = TypeConfig::now();
+        router.client_request(0, "foo", 1).await?;
+        log_index += 1;
+
+        let elapsed = TypeConfig::now() - now;
+        assert!(
+            elapsed >= Duration::from_millis(100),
+            "a write waits for replication, elapsed: {:?}",
+            elapsed
+        );
+
+        router.set_default_link(LinkConfig::default());
+    }
+
+    tracing::info!(log_index, "--- link 0->2 drops every RPC, node 2 receives nothing");
+    {
+        router.set_link(0, 2, Some(LinkConfig::new(Duration::ZERO, Duration::ZERO, 1.0)));
+
+        router.client_request_many(0, "foo", 5).await?;
+        log_index += 5;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "committed with node 1").await?;
+
+        let res = router
+            .wait(&2, Some(Duration::from_millis(300)))
+            .applied_index(Some(log_index), "all dropped")
+            .await;
+        assert!(res.is_err(), "node 2 should not receive any log");
+    }
+
+    tracing::info!(
+        log_index,
+        "--- link 0->2 has jitter and drops half of the RPCs, node 2 still catches up"
+    );
+    {
+        router.set_link(
+            0,
+            2,
+            Some(LinkConfig::new(
+                Duration::from_millis(1),
+                Duration::from_millis(10),
+                0.5,
+            )),
+        );
+
+        router.client_request_many(0, "foo", 10).await?;
+        log_index += 10;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "catch up with packet loss").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- reset link 0->2");
+    {
+        router.set_link(0, 2, None);
+
+        router.client_request(0, "foo", 100).await?;
+        log_index += 1;
+
+        router.wait(&2, timeout()).applied_index(Some(log_index), "link reset").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Duplicated and reordered AppendEntries and Vote RPCs, i.e., stale AppendEntries responses and
+/// duplicate vote grants, do not break log replication or leader election.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn rpc_duplicate_and_reorder() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    for seed in 0..3 {
+        let mut router = RaftRouter::new(config.clone());
+
+        tracing::info!(seed, "--- initializing cluster");
+        let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+        router.set_rpc_disorder(0.5, 0.5, Duration::from_millis(20), seed);
+
+        tracing::info!(seed, log_index, "--- write to leader 0");
+        {
+            router.client_request_many(0, "foo", 10).await?;
+            log_index += 10;
+        }
+
+        tracing::info!(seed, log_index, "--- elect node 1, write to it");
+        {
+            // Node 1 has to hold all the logs to win the election, and the leader lease of node 0,
+            // which is extended by every AppendEntries, has to expire.
+            router.wait(&1, timeout()).applied_index(Some(log_index), "node 1 catches up").await?;
+            TypeConfig::sleep(Duration::from_millis(config.election_timeout_max)).await;
+
+            let n1 = router.get_raft_handle(&1)?;
+            n1.trigger().elect().await?;
+            n1.wait(timeout()).state(ServerState::Leader, "node 1 becomes leader").await?;
+            log_index += 1;
+
+            router.client_request_many(1, "bar", 10).await?;
+            log_index += 10;
+        }
+
+        tracing::info!(seed, log_index, "--- all nodes converge");
+        {
+            assert!(
+                router.get_duplicated_rpc_count() > 0,
+                "duplicated responses are delivered"
+            );
+            router.clear_rpc_disorder();
+
+            for id in [0, 1, 2] {
+                router.wait(&id, timeout()).applied_index(Some(log_index), "converge").await?;
+            }
+
+            let (_, sm1) = router.get_storage_handle(&1)?;
+            let want = sm1.get_state_machine().await.client_status;
+
+            for id in [0, 2] {
+                let (_, sm) = router.get_storage_handle(&id)?;
+                let got = sm.get_state_machine().await.client_status;
+                assert_eq!(want, got, "state machine of node {} with seed {}", id, seed);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
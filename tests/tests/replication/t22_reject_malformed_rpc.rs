@@ -0,0 +1,243 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::MalformedRequest;
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::AppendEntriesResponse;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::RaftLogReader;
+use openraft::ServerState;
+use openraft::SnapshotPolicy;
+use openraft::StoredMembership;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RPCRequest;
+use crate::fixtures::RaftRouter;
+
+/// AppendEntries with log ids proposed by a leader greater than the sender's vote are rejected by
+/// the follower with a `Malformed` response without changing its logs; the cluster still commits
+/// with the other nodes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn reject_append_entries_log_id_after_vote() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+    let initial_index = log_index;
+
+    tracing::info!(log_index, "--- bump the term of entries sent to node 2");
+    {
+        router.set_rpc_mutator(RPCTypes::AppendEntries, |rpc, _from, to| match rpc {
+            RPCRequest::AppendEntries(mut req) if to == 2 => {
+                let term = req.vote.leader_id().term + 1;
+                for ent in req.entries.iter_mut() {
+                    ent.log_id = log_id(term, 0, ent.log_id.index());
+                }
+                RPCRequest::AppendEntries(req)
+            }
+            rpc => rpc,
+        });
+
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "committed without node 2").await?;
+
+        let (mut sto2, _sm2) = router.get_storage_handle(&2)?;
+        let logs = sto2.try_get_log_entries(..).await?;
+        assert_eq!(
+            Some(initial_index),
+            logs.last().map(|e| e.log_id.index()),
+            "node 2 does not append malformed entries"
+        );
+        assert!(logs.iter().all(|e| e.log_id.committed_leader_id().term <= 1));
+    }
+
+    tracing::info!(log_index, "--- clear the mutator, node 2 catches up");
+    {
+        router.clear_rpc_mutator(RPCTypes::AppendEntries);
+
+        log_index += router.client_request_many(0, "foo", 1).await?;
+        router.wait(&2, timeout()).applied_index(Some(log_index), "node 2 catches up").await?;
+
+        let (mut sto0, _sm0) = router.get_storage_handle(&0)?;
+        let (mut sto2, _sm2) = router.get_storage_handle(&2)?;
+        let want = sto0.try_get_log_entries(..).await?.into_iter().map(|e| e.log_id).collect::<Vec<_>>();
+        let got = sto2.try_get_log_entries(..).await?.into_iter().map(|e| e.log_id).collect::<Vec<_>>();
+        assert_eq!(want, got);
+    }
+
+    tracing::info!(log_index, "--- a malformed prev_log_id is answered with Malformed");
+    {
+        let vote = router.get_metrics(&0)?.vote;
+        let bogus = log_id(vote.leader_id().term + 1, 0, log_index);
+
+        let n2 = router.get_raft_handle(&2)?;
+        let resp = n2
+            .append_entries(AppendEntriesRequest {
+                vote,
+                prev_log_id: Some(bogus),
+                entries: vec![],
+                leader_commit: None,
+            })
+            .await?;
+
+        assert_eq!(
+            AppendEntriesResponse::Malformed(MalformedRequest::LogIdAfterVote { log_id: bogus, vote }),
+            resp
+        );
+    }
+
+    Ok(())
+}
+
+/// A vote request whose last log id is greater than the candidate's vote is not granted, and does
+/// not change the vote of the receiver.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn reject_vote_last_log_id_after_vote() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    // The leader lease of node 0 has to expire for node 1 to be elected.
+    TypeConfig::sleep(Duration::from_millis(config.election_timeout_max)).await;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- node 1 sends vote requests with a bogus last log id");
+    {
+        router.set_rpc_mutator(RPCTypes::Vote, |rpc, _from, _to| match rpc {
+            RPCRequest::Vote(mut req) => {
+                let term = req.vote.leader_id().term + 1;
+                req.last_log_id = Some(log_id(term, 1, req.last_log_id.map(|x| x.index()).unwrap_or_default()));
+                RPCRequest::Vote(req)
+            }
+            rpc => rpc,
+        });
+
+        n1.trigger().elect().await?;
+
+        let res = n1
+            .wait(Some(Duration::from_millis(1_000)))
+            .state(ServerState::Leader, "node 1 is not elected")
+            .await;
+        assert!(res.is_err(), "node 1 must not become leader");
+
+        let candidate_vote = router.get_metrics(&1)?.vote;
+        for id in [0, 2] {
+            let vote = router.get_metrics(&id)?.vote;
+            assert_ne!(candidate_vote, vote, "node {} does not grant the vote", id);
+        }
+    }
+
+    tracing::info!(log_index, "--- clear the mutator, node 1 is elected");
+    {
+        router.clear_rpc_mutator(RPCTypes::Vote);
+
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).state(ServerState::Leader, "node 1 becomes leader").await?;
+    }
+
+    Ok(())
+}
+
+/// A snapshot whose last membership is after its last log id is not installed, and the response
+/// tells the sender why.
+///
+/// The snapshot is sent directly, as if by a faulty leader.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn reject_snapshot_membership_after_last_log_id() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- build a snapshot on leader");
+    let n0 = router.get_raft_handle(&0)?;
+    {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+    }
+
+    let snapshot = n0.get_snapshot().await?.unwrap();
+    let vote = router.get_metrics(&0)?.vote;
+
+    router.new_raft_node(1).await;
+    let n1 = router.get_raft_handle(&1)?;
+    let vote1 = router.get_metrics(&1)?.vote;
+
+    tracing::info!(log_index, "--- send a bogus snapshot to node 1");
+    {
+        let mut bogus = snapshot.clone();
+        let membership = bogus.meta.last_membership.membership().clone();
+        bogus.meta.last_membership = StoredMembership::new(Some(log_id(1, 0, log_index + 1)), membership);
+
+        let resp = n1.install_full_snapshot(vote, bogus).await?;
+        assert_eq!(vote1, resp.vote, "node 1 does not accept the vote");
+        assert_eq!(
+            Some(MalformedRequest::SnapshotMembershipAfterLastLogId {
+                membership: Some(log_id(1, 0, log_index + 1)),
+                last_log_id: Some(log_id(1, 0, log_index)),
+            }),
+            resp.malformed
+        );
+
+        let m = router.get_metrics(&1)?;
+        assert_eq!(vote1, m.vote);
+        assert_eq!(None, m.snapshot);
+        assert_eq!(None, m.last_applied);
+        assert_eq!(None, m.membership_config.log_id().as_ref());
+    }
+
+    tracing::info!(log_index, "--- send the snapshot to node 1");
+    {
+        let resp = n1.install_full_snapshot(vote, snapshot).await?;
+        assert_eq!(vote, resp.vote);
+        assert_eq!(None, resp.malformed);
+
+        n1.wait(timeout()).snapshot(log_id(1, 0, log_index), "node 1 installs the snapshot").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
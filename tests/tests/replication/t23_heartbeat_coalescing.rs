@@ -0,0 +1,148 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::future::join_all;
+use maplit::btreeset;
+use openraft::error::RPCError;
+use openraft::multi_raft::HeartbeatBatch;
+use openraft::multi_raft::HeartbeatBatchResponse;
+use openraft::multi_raft::HeartbeatCoalescer;
+use openraft::multi_raft::HeartbeatTransport;
+use openraft::network::RPCOption;
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::AppendEntriesResponse;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft_memstore::MemNodeId;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Delivers heartbeat batches to the node `target` of every group, each group is a cluster in a
+/// [`RaftRouter`], indexed by the group id.
+struct LocalTransport {
+    target: MemNodeId,
+    groups: Vec<RaftRouter>,
+}
+
+impl HeartbeatTransport<TypeConfig, usize> for LocalTransport {
+    async fn send_heartbeats(
+        &self,
+        batch: HeartbeatBatch<TypeConfig, usize>,
+        _option: RPCOption,
+    ) -> Result<HeartbeatBatchResponse<TypeConfig>, RPCError<TypeConfig>> {
+        let resp = batch.handle(|group| self.groups.get(*group)?.get_raft_handle(&self.target).ok()).await;
+        Ok(resp)
+    }
+}
+
+/// The heartbeats of multiple groups to the same process are sent in one batch and every group
+/// receives its own response.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn heartbeat_coalescing() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let n_groups = 4;
+
+    tracing::info!(n_groups, "--- initializing groups, node 0 is the leader of every group");
+    let mut groups = vec![];
+    for _ in 0..n_groups {
+        let mut router = RaftRouter::new(config.clone());
+        router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+        groups.push(router);
+    }
+
+    let mut heartbeats = vec![];
+    for router in groups.iter() {
+        let m = router.get_metrics(&0)?;
+        heartbeats.push(AppendEntriesRequest {
+            vote: m.vote,
+            prev_log_id: m.last_applied,
+            leader_commit: m.last_applied,
+            entries: vec![],
+        });
+    }
+
+    let coalescer = HeartbeatCoalescer::new(
+        LocalTransport {
+            target: 1,
+            groups: groups.clone(),
+        },
+        Duration::from_millis(50),
+    );
+
+    let option = || RPCOption::new(Duration::from_millis(1_000));
+
+    tracing::info!("--- send heartbeats of every group and an unknown group to node 1");
+    {
+        let mut sends = vec![];
+        for (group, rpc) in heartbeats.iter().cloned().enumerate() {
+            sends.push(coalescer.send(group, rpc, option()));
+        }
+        sends.push(coalescer.send(n_groups, heartbeats[0].clone(), option()));
+
+        let mut results = join_all(sends).await;
+
+        assert_eq!(1, coalescer.sent_batches());
+        assert_eq!(n_groups as u64 + 1, coalescer.sent_heartbeats());
+
+        let unknown = results.pop().unwrap();
+        assert!(
+            matches!(unknown, Err(RPCError::Unreachable(_))),
+            "unknown group is unreachable: {:?}",
+            unknown
+        );
+
+        for (group, res) in results.into_iter().enumerate() {
+            assert_eq!(AppendEntriesResponse::Success, res?, "group {}", group);
+        }
+    }
+
+    tracing::info!("--- a heartbeat with a stale vote is rejected by its own group");
+    {
+        let m = groups[1].get_metrics(&0)?;
+
+        // The leader lease of node 0 has to expire for node 1 to be elected.
+        TypeConfig::sleep(Duration::from_millis(config.election_timeout_max)).await;
+
+        groups[1].get_raft_handle(&1)?.trigger().elect().await?;
+        groups[1]
+            .wait(&1, timeout())
+            .state(openraft::ServerState::Leader, "elect node 1 of group 1")
+            .await?;
+
+        let results = join_all([
+            coalescer.send(0, heartbeats[0].clone(), option()),
+            coalescer.send(1, heartbeats[1].clone(), option()),
+        ])
+        .await;
+
+        assert_eq!(2, coalescer.sent_batches());
+
+        let mut results = results.into_iter();
+        assert_eq!(AppendEntriesResponse::Success, results.next().unwrap()?);
+
+        let resp = results.next().unwrap()?;
+        assert!(
+            matches!(&resp, AppendEntriesResponse::HigherVote(v) if *v > m.vote),
+            "group 1 has a greater vote: {:?}",
+            resp
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
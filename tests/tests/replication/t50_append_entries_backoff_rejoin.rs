@@ -3,8 +3,10 @@ use std::time::Duration;
 
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
 use openraft::ServerState;
+use openraft_memstore::TypeConfig;
 
 use crate::fixtures::ut_harness;
 use crate::fixtures::RaftRouter;
@@ -44,7 +46,7 @@ async fn append_entries_backoff_rejoin() -> Result<()> {
     tracing::info!(log_index, "--- elect node-1");
     {
         // Timeout leader lease otherwise vote-request will be rejected by node-2
-        tokio::time::sleep(Duration::from_millis(1_000)).await;
+        TypeConfig::sleep(Duration::from_millis(1_000)).await;
 
         n1.trigger().elect().await?;
         n1.wait(timeout()).state(ServerState::Leader, "node-1 elect").await?;
@@ -8,9 +8,11 @@ use openraft::network::RPCOption;
 use openraft::network::RaftNetworkFactory;
 use openraft::raft::AppendEntriesRequest;
 use openraft::testing::blank_ent;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
 use openraft::Vote;
 use openraft_memstore::BlockOperation;
+use openraft_memstore::TypeConfig;
 
 use crate::fixtures::log_id;
 use crate::fixtures::ut_harness;
@@ -47,7 +49,7 @@ async fn building_snapshot_does_not_block_append() -> Result<()> {
         follower.trigger().snapshot().await?;
 
         tracing::info!(log_index, "--- sleep 500 ms to make sure snapshot is started");
-        tokio::time::sleep(Duration::from_millis(500)).await;
+        TypeConfig::sleep(Duration::from_millis(500)).await;
 
         let res = router
             .wait(&1, Some(Duration::from_millis(500)))
@@ -71,7 +73,7 @@ async fn building_snapshot_does_not_block_append() -> Result<()> {
         let mut cli = router.new_client(1, &()).await;
         let option = RPCOption::new(Duration::from_millis(1_000));
         let fu = cli.append_entries(rpc, option);
-        let fu = tokio::time::timeout(Duration::from_millis(500), fu);
+        let fu = TypeConfig::timeout(Duration::from_millis(500), fu);
         let resp = fu.await??;
         assert!(resp.is_success());
     }
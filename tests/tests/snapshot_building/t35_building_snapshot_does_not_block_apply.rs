@@ -8,9 +8,11 @@ use openraft::network::RPCOption;
 use openraft::network::RaftNetworkFactory;
 use openraft::raft::AppendEntriesRequest;
 use openraft::testing::blank_ent;
+use openraft::type_config::TypeConfigExt;
 use openraft::Config;
 use openraft::Vote;
 use openraft_memstore::BlockOperation;
+use openraft_memstore::TypeConfig;
 
 use crate::fixtures::log_id;
 use crate::fixtures::ut_harness;
@@ -49,7 +51,7 @@ async fn building_snapshot_does_not_block_apply() -> Result<()> {
         follower.trigger().snapshot().await?;
 
         tracing::info!(log_index, "--- sleep 500 ms to make sure snapshot is started");
-        tokio::time::sleep(Duration::from_millis(500)).await;
+        TypeConfig::sleep(Duration::from_millis(500)).await;
 
         let res = router
             .wait(&1, Some(Duration::from_millis(500)))
@@ -77,7 +79,7 @@ async fn building_snapshot_does_not_block_apply() -> Result<()> {
         let option = RPCOption::new(Duration::from_millis(1_000));
 
         let fu = cli.append_entries(rpc, option);
-        let fu = tokio::time::timeout(Duration::from_millis(500), fu);
+        let fu = TypeConfig::timeout(Duration::from_millis(500), fu);
         let resp = fu.await??;
         assert!(resp.is_success());
 
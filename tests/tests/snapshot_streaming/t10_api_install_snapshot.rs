@@ -40,11 +40,7 @@ async fn snapshot_arguments() -> Result<()> {
     let make_req = || InstallSnapshotRequest {
         // force it to be a follower
         vote: Vote::new_committed(2, 1),
-        meta: SnapshotMeta {
-            snapshot_id: "ss1".into(),
-            last_log_id: Some(log_id(1, 0, 0)),
-            last_membership: Default::default(),
-        },
+        meta: SnapshotMeta::new(Some(log_id(1, 0, 0)), Default::default(), "ss1".into()),
         offset: 0,
         data: vec![1, 2, 3],
         done: false,
@@ -91,12 +87,24 @@ async fn snapshot_arguments() -> Result<()> {
         n.0.install_snapshot(req).await?;
     }
 
-    tracing::info!("-- continue write with mismatched offset is allowed");
+    tracing::info!("-- re-send a written chunk is allowed");
     {
         let mut req = make_req();
-        req.offset = 8;
+        req.offset = 3;
         req.meta.snapshot_id = "ss2".into();
         n.0.install_snapshot(req).await?;
     }
+
+    tracing::info!("-- continue write after missing data is not allowed: snapshot data is written without seeking");
+    {
+        let mut req = make_req();
+        req.offset = 8;
+        req.meta.snapshot_id = "ss2".into();
+        let res = n.0.install_snapshot(req).await;
+        assert_eq!(
+            "snapshot segment id mismatch, expect: ss2+6, got: ss2+8",
+            res.unwrap_err().to_string()
+        );
+    }
     Ok(())
 }
@@ -37,11 +37,7 @@ async fn install_snapshot_lower_vote() -> Result<()> {
     let (n0, _, _) = router.remove_node(0).unwrap();
     let make_req = || InstallSnapshotRequest {
         vote: Vote::new_committed(2, 1),
-        meta: SnapshotMeta {
-            snapshot_id: "ss1".into(),
-            last_log_id: Some(log_id(1, 0, 0)),
-            last_membership: Default::default(),
-        },
+        meta: SnapshotMeta::new(Some(log_id(1, 0, 0)), Default::default(), "ss1".into()),
         offset: 0,
         data: vec![1, 2, 3],
         done: false,
@@ -0,0 +1,132 @@
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::InstallSnapshotRequest;
+use openraft::storage::SnapshotMeta;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::Raft;
+use openraft::Vote;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A node refuses a snapshot with a version greater than `max_snapshot_version`, and the leader
+/// does not send the refused snapshot again, until it builds a snapshot of a version the node
+/// supports.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_version() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let old_config = Arc::new(
+        Config {
+            max_snapshot_version: Some(1),
+            ..config.as_ref().clone()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initialize cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let (_, sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot of version 2 and purge all logs");
+    {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+
+        sm0.snapshot_version.store(2, Ordering::Relaxed);
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "snapshot built").await?;
+        n0.trigger().purge_log(log_index).await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purged").await?;
+    }
+
+    tracing::info!(log_index, "--- a learner supporting version 1 refuses the snapshot");
+    {
+        let sent_before = install_snapshot_count(&router);
+
+        let (log_store, sm) = router.new_store();
+        let n1 = Raft::new(1, old_config.clone(), router.clone(), log_store.clone(), sm.clone()).await?;
+        router.register_raft_node(1, n1.clone(), log_store, sm);
+
+        n0.add_learner(1, (), false).await?;
+        log_index += 1;
+
+        let res = n1.wait(Some(Duration::from_millis(500))).snapshot(log_id(1, 0, log_index - 1), "refused").await;
+        assert!(res.is_err(), "snapshot of version 2 should not be installed");
+
+        tracing::info!(
+            log_index,
+            "--- replicating more logs does not re-send the refused snapshot"
+        );
+        for _ in 0..3 {
+            log_index += router.client_request_many(0, "bar", 1).await?;
+            n0.wait(timeout()).applied_index(Some(log_index), "applied").await?;
+            TypeConfig::sleep(Duration::from_millis(100)).await;
+        }
+
+        assert_eq!(
+            sent_before + 1,
+            install_snapshot_count(&router),
+            "a refused snapshot is not sent again"
+        );
+    }
+
+    tracing::info!(log_index, "--- the leader sends a snapshot of version 1");
+    {
+        sm0.snapshot_version.store(1, Ordering::Relaxed);
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "snapshot built").await?;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner caught up").await?;
+    }
+
+    tracing::info!(log_index, "--- chunked transport refuses an unsupported snapshot");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let meta =
+            SnapshotMeta::new(Some(log_id(1, 0, log_index)), Default::default(), "v2".to_string()).with_version(2);
+
+        let req = InstallSnapshotRequest {
+            vote: Vote::new_committed(1, 0),
+            meta,
+            offset: 0,
+            data: vec![1, 2, 3],
+            done: false,
+        };
+        let res = n1.install_snapshot(req).await;
+        assert_eq!(
+            "snapshot version 2 is not supported, max supported version: 1",
+            res.unwrap_err().to_string()
+        );
+    }
+
+    Ok(())
+}
+
+fn install_snapshot_count(router: &RaftRouter) -> u64 {
+    router.get_rpc_count().get(&RPCTypes::InstallSnapshot).copied().unwrap_or_default()
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -9,3 +9,9 @@ mod fixtures;
 
 mod t10_total_order_apply;
 mod t20_state_machine_apply_membership;
+mod t30_apply_batch;
+mod t30_slow_apply_does_not_block_append;
+mod t40_apply_error;
+mod t50_lifecycle_hooks;
+mod t60_two_phase_apply;
+mod t70_split_and_merge_groups;
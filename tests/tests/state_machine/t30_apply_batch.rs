@@ -0,0 +1,147 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::alias::SnapshotDataOf;
+use openraft::storage::RaftStateMachine;
+use openraft::storage::Snapshot;
+use openraft::storage::SnapshotMeta;
+use openraft::type_config::TypeConfigExt;
+use openraft::Config;
+use openraft::Entry;
+use openraft::LogId;
+use openraft::OptionalSend;
+use openraft::Raft;
+use openraft::StorageError;
+use openraft::StoredMembership;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::ClientResponse;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::MemStateMachine;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A state machine that records the number of entries of every `apply()` call.
+struct BatchRecorder {
+    inner: Arc<MemStateMachine>,
+    batches: Arc<Mutex<Vec<usize>>>,
+}
+
+impl RaftStateMachine<TypeConfig> for BatchRecorder {
+    type SnapshotBuilder = Arc<MemStateMachine>;
+
+    async fn applied_state(
+        &mut self,
+    ) -> Result<(Option<LogId<TypeConfig>>, StoredMembership<TypeConfig>), StorageError<TypeConfig>> {
+        self.inner.applied_state().await
+    }
+
+    async fn apply<I>(&mut self, entries: I) -> Result<Vec<ClientResponse>, StorageError<TypeConfig>>
+    where
+        I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let entries = entries.into_iter().collect::<Vec<_>>();
+        self.batches.lock().unwrap().push(entries.len());
+        self.inner.apply(entries).await
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        self.inner.get_snapshot_builder().await
+    }
+
+    async fn begin_receiving_snapshot(&mut self) -> Result<SnapshotDataOf<TypeConfig>, StorageError<TypeConfig>> {
+        self.inner.begin_receiving_snapshot().await
+    }
+
+    async fn install_snapshot(
+        &mut self,
+        meta: &SnapshotMeta<TypeConfig>,
+        snapshot: SnapshotDataOf<TypeConfig>,
+    ) -> Result<(), StorageError<TypeConfig>> {
+        self.inner.install_snapshot(meta, snapshot).await
+    }
+
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<TypeConfig>>, StorageError<TypeConfig>> {
+        self.inner.get_current_snapshot().await
+    }
+}
+
+/// `apply()` receives at most `apply_batch_size` entries, and a partial batch waits for more
+/// entries upto `apply_batch_max_delay`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn apply_batch() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            apply_batch_size: 4,
+            apply_batch_max_delay: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = RaftRouter::new(config.clone());
+
+    let batches = Arc::new(Mutex::new(Vec::new()));
+
+    let (log_store, sm) = openraft_memstore::new_mem_store();
+    let sm = BatchRecorder {
+        inner: sm,
+        batches: batches.clone(),
+    };
+
+    let raft = Raft::new(0, config, router, log_store, sm).await?;
+    raft.initialize(btreeset! {0}).await?;
+
+    let mut log_index = 1;
+    raft.wait(timeout()).applied_index(Some(log_index), "initialized").await?;
+
+    tracing::info!(log_index, "--- a partial batch is applied after the max delay");
+    {
+        let start = TypeConfig::now();
+        raft.client_write(ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+
+        let elapsed = TypeConfig::now() - start;
+        assert!(
+            elapsed >= Duration::from_millis(300),
+            "waited for the max delay: {:?}",
+            elapsed
+        );
+    }
+
+    tracing::info!(log_index, "--- a full batch is applied at once");
+    {
+        let mut handles = vec![];
+        for i in 0..10 {
+            let r = raft.clone();
+            handles.push(TypeConfig::spawn(async move {
+                r.client_write(ClientRequest::make_request("foo", i)).await
+            }));
+        }
+        for h in handles {
+            h.await??;
+        }
+        log_index += 10;
+
+        raft.wait(timeout()).applied_index(Some(log_index), "applied").await?;
+    }
+
+    let batches = batches.lock().unwrap().clone();
+    assert!(batches.iter().all(|n| *n <= 4), "batch sizes: {:?}", batches);
+    assert_eq!(log_index + 1, batches.iter().sum::<usize>() as u64);
+
+    raft.shutdown().await?;
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::BlockOperation;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A slow state machine on a follower does not block it from appending logs; and with a small
+/// apply queue, every committed entry is still applied.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn slow_apply_does_not_block_append() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            apply_queue_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- slow down applying on the follower");
+    let (_sto1, sm1) = router.get_storage_handle(&1)?;
+    sm1.block.set_blocking(BlockOperation::Apply, Duration::from_millis(500));
+
+    tracing::info!(log_index, "--- logs are appended to the follower before being applied");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+
+        let m = router.wait(&1, Some(Duration::from_millis(400))).log_index(Some(log_index), "appended").await?;
+        assert!(
+            m.last_applied.as_ref().map(|x| x.index) < Some(log_index),
+            "applying is not done: {:?}",
+            m.last_applied
+        );
+    }
+
+    tracing::info!(log_index, "--- all logs are applied when the state machine catches up");
+    {
+        sm1.block.clear_blocking(BlockOperation::Apply);
+        router
+            .wait(&1, Some(Duration::from_millis(3_000)))
+            .applied_index(Some(log_index), "applied")
+            .await?;
+    }
+
+    Ok(())
+}
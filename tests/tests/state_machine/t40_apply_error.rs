@@ -0,0 +1,238 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::alias::SnapshotDataOf;
+use openraft::error::ApplyError;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::storage::defensive;
+use openraft::storage::RaftStateMachine;
+use openraft::storage::Snapshot;
+use openraft::storage::SnapshotMeta;
+use openraft::Config;
+use openraft::Entry;
+use openraft::EntryPayload;
+use openraft::LogId;
+use openraft::OptionalSend;
+use openraft::Raft;
+use openraft::StorageError;
+use openraft::StoredMembership;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::ClientResponse;
+use openraft_memstore::MemStateMachine;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// A state machine that rejects a request whose status is `"invalid"` with an [`ApplyError`].
+///
+/// A rejected entry is applied as a blank entry.
+struct Validating {
+    inner: Arc<MemStateMachine>,
+}
+
+impl RaftStateMachine<TypeConfig> for Validating {
+    type SnapshotBuilder = Arc<MemStateMachine>;
+
+    async fn applied_state(
+        &mut self,
+    ) -> Result<(Option<LogId<TypeConfig>>, StoredMembership<TypeConfig>), StorageError<TypeConfig>> {
+        self.inner.applied_state().await
+    }
+
+    async fn apply<I>(&mut self, _entries: I) -> Result<Vec<ClientResponse>, StorageError<TypeConfig>>
+    where
+        I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        unreachable!("apply_fallible() is used")
+    }
+
+    async fn apply_fallible<I>(
+        &mut self,
+        entries: I,
+    ) -> Result<Vec<Result<ClientResponse, ApplyError<TypeConfig>>>, StorageError<TypeConfig>>
+    where
+        I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let mut errors = vec![];
+        let mut valid = vec![];
+
+        for mut entry in entries {
+            let err = match &entry.payload {
+                EntryPayload::Normal(req) if req.status == "invalid" => {
+                    Some(ApplyError::with_message(entry.log_id, "invalid status"))
+                }
+                _ => None,
+            };
+            if err.is_some() {
+                entry.payload = EntryPayload::Blank;
+            }
+            errors.push(err);
+            valid.push(entry);
+        }
+
+        let responses = self.inner.apply(valid).await?;
+
+        let res = responses.into_iter().zip(errors).map(|(resp, err)| match err {
+            Some(e) => Err(e),
+            None => Ok(resp),
+        });
+        Ok(res.collect())
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        self.inner.get_snapshot_builder().await
+    }
+
+    async fn begin_receiving_snapshot(&mut self) -> Result<SnapshotDataOf<TypeConfig>, StorageError<TypeConfig>> {
+        self.inner.begin_receiving_snapshot().await
+    }
+
+    async fn install_snapshot(
+        &mut self,
+        meta: &SnapshotMeta<TypeConfig>,
+        snapshot: SnapshotDataOf<TypeConfig>,
+    ) -> Result<(), StorageError<TypeConfig>> {
+        self.inner.install_snapshot(meta, snapshot).await
+    }
+
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<TypeConfig>>, StorageError<TypeConfig>> {
+        self.inner.get_current_snapshot().await
+    }
+}
+
+/// An application error from the state machine is returned to the `client_write()` caller, and
+/// the node keeps running.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn apply_error() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = RaftRouter::new(config.clone());
+
+    let (log_store, sm) = openraft_memstore::new_mem_store();
+    let validating = Validating { inner: sm.clone() };
+
+    let raft = Raft::new(0, config, router, log_store, validating).await?;
+    raft.initialize(btreeset! {0}).await?;
+
+    let mut log_index = 1;
+    raft.wait(timeout()).applied_index(Some(log_index), "initialized").await?;
+
+    let req = |status: &str| ClientRequest {
+        client: "foo".to_string(),
+        serial: 0,
+        status: status.to_string(),
+    };
+
+    tracing::info!(log_index, "--- a valid request is applied");
+    {
+        raft.client_write(req("a")).await?;
+        log_index += 1;
+    }
+
+    tracing::info!(log_index, "--- an invalid request is rejected by the state machine");
+    {
+        let res = raft.client_write(req("invalid")).await;
+        log_index += 1;
+
+        let err = res.unwrap_err();
+        match err {
+            RaftError::APIError(ClientWriteError::ApplyError(e)) => {
+                assert_eq!(log_id(1, 0, log_index), e.log_id);
+                assert_eq!("invalid status", e.source.to_string());
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+
+        let state = sm.get_state_machine().await;
+        assert_eq!(Some(log_id(1, 0, log_index)), state.last_applied_log);
+        assert_eq!(Some(&"a".to_string()), state.client_status.get("foo"));
+    }
+
+    tracing::info!(log_index, "--- the node keeps serving writes");
+    {
+        let resp = raft.client_write(req("b")).await?;
+        log_index += 1;
+
+        assert_eq!(log_id(1, 0, log_index), resp.log_id);
+        assert_eq!(Some("a".to_string()), resp.data.0);
+    }
+
+    raft.shutdown().await?;
+
+    Ok(())
+}
+
+/// An application error is returned to the `client_write()` caller when the state machine is
+/// wrapped by the defensive checks.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn apply_error_with_defensive_wrapper() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = RaftRouter::new(config.clone());
+
+    let (log_store, sm) = openraft_memstore::new_mem_store();
+    let validating = Validating { inner: sm.clone() };
+    let (log_store, validating) = defensive::wrap(log_store, validating);
+
+    let raft = Raft::new(0, config, router, log_store, validating).await?;
+    raft.initialize(btreeset! {0}).await?;
+
+    let mut log_index = 1;
+    raft.wait(timeout()).applied_index(Some(log_index), "initialized").await?;
+
+    tracing::info!(log_index, "--- an invalid request is rejected");
+    {
+        let res = raft
+            .client_write(ClientRequest {
+                client: "foo".to_string(),
+                serial: 0,
+                status: "invalid".to_string(),
+            })
+            .await;
+        log_index += 1;
+
+        let err = res.unwrap_err();
+        match err {
+            RaftError::APIError(ClientWriteError::ApplyError(e)) => {
+                assert_eq!(log_id(1, 0, log_index), e.log_id);
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+
+    let metrics = raft.metrics().borrow().clone();
+    assert!(
+        metrics.running_state.is_ok(),
+        "no storage violation: {:?}",
+        metrics.running_state
+    );
+
+    raft.shutdown().await?;
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,189 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::alias::SnapshotDataOf;
+use openraft::storage::RaftStateMachine;
+use openraft::storage::Snapshot;
+use openraft::storage::SnapshotMeta;
+use openraft::Config;
+use openraft::Entry;
+use openraft::LogId;
+use openraft::OptionalSend;
+use openraft::Raft;
+use openraft::ServerState;
+use openraft::StorageError;
+use openraft::StoredMembership;
+use openraft::Vote;
+use openraft_memstore::ClientResponse;
+use openraft_memstore::MemStateMachine;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Event {
+    BecomeLeader(Vote<TypeConfig>),
+    StepDown,
+    SnapshotInstalled(Option<LogId<TypeConfig>>),
+    MembershipApplied(Option<LogId<TypeConfig>>),
+}
+
+/// A state machine that records every lifecycle hook call.
+struct HookRecorder {
+    inner: Arc<MemStateMachine>,
+    events: Arc<Mutex<Vec<Event>>>,
+}
+
+impl RaftStateMachine<TypeConfig> for HookRecorder {
+    type SnapshotBuilder = Arc<MemStateMachine>;
+
+    async fn applied_state(
+        &mut self,
+    ) -> Result<(Option<LogId<TypeConfig>>, StoredMembership<TypeConfig>), StorageError<TypeConfig>> {
+        self.inner.applied_state().await
+    }
+
+    async fn apply<I>(&mut self, entries: I) -> Result<Vec<ClientResponse>, StorageError<TypeConfig>>
+    where
+        I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        self.inner.apply(entries).await
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        self.inner.get_snapshot_builder().await
+    }
+
+    async fn begin_receiving_snapshot(&mut self) -> Result<SnapshotDataOf<TypeConfig>, StorageError<TypeConfig>> {
+        self.inner.begin_receiving_snapshot().await
+    }
+
+    async fn install_snapshot(
+        &mut self,
+        meta: &SnapshotMeta<TypeConfig>,
+        snapshot: SnapshotDataOf<TypeConfig>,
+    ) -> Result<(), StorageError<TypeConfig>> {
+        self.inner.install_snapshot(meta, snapshot).await
+    }
+
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<TypeConfig>>, StorageError<TypeConfig>> {
+        self.inner.get_current_snapshot().await
+    }
+
+    async fn on_become_leader(&mut self, vote: &Vote<TypeConfig>) {
+        self.events.lock().unwrap().push(Event::BecomeLeader(*vote));
+    }
+
+    async fn on_step_down(&mut self) {
+        self.events.lock().unwrap().push(Event::StepDown);
+    }
+
+    async fn on_snapshot_installed(&mut self, meta: &SnapshotMeta<TypeConfig>) {
+        self.events.lock().unwrap().push(Event::SnapshotInstalled(meta.last_log_id));
+    }
+
+    async fn on_membership_applied(&mut self, membership: &StoredMembership<TypeConfig>) {
+        self.events.lock().unwrap().push(Event::MembershipApplied(*membership.log_id()));
+    }
+}
+
+/// Lifecycle hooks are called in order with applying entries.
+///
+/// - A learner receives a snapshot, then applies the membership entries after it.
+/// - It becomes the leader after being promoted and receiving the leadership, and steps down after
+///   transferring the leadership back.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn lifecycle_hooks() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initialize cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 5).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build snapshot and purge all logs on the leader");
+    {
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "snapshot built").await?;
+        n0.trigger().purge_log(log_index).await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purged").await?;
+    }
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    tracing::info!(log_index, "--- add a learner with a lifecycle hook recorder");
+    {
+        let (log_store, sm) = router.new_store();
+        let recorder = HookRecorder {
+            inner: sm.clone(),
+            events: events.clone(),
+        };
+        let n1 = Raft::new(1, config.clone(), router.clone(), log_store.clone(), recorder).await?;
+        router.register_raft_node(1, n1, log_store, sm);
+
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner caught up").await?;
+    }
+
+    tracing::info!(log_index, "--- promote the learner and transfer leadership to it");
+    {
+        n0.change_membership([0, 1], false).await?;
+        log_index += 2;
+
+        n0.trigger().transfer_leader(1).await?;
+        router.wait(&1, timeout()).state(ServerState::Leader, "node-1 become leader").await?;
+        log_index += 1;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "blank log applied").await?;
+    }
+
+    tracing::info!(log_index, "--- transfer leadership back");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().transfer_leader(0).await?;
+        n0.wait(timeout()).state(ServerState::Leader, "node-0 become leader").await?;
+        log_index += 1;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "blank log applied").await?;
+    }
+
+    tracing::info!(log_index, "--- hooks are called in order");
+    {
+        let events = events.lock().unwrap().clone();
+        assert_eq!(
+            vec![
+                Event::SnapshotInstalled(Some(log_id(1, 0, log_index - 5))),
+                Event::MembershipApplied(Some(log_id(1, 0, log_index - 4))),
+                Event::MembershipApplied(Some(log_id(1, 0, log_index - 3))),
+                Event::MembershipApplied(Some(log_id(1, 0, log_index - 2))),
+                Event::BecomeLeader(Vote::new_committed(2, 1)),
+                Event::StepDown,
+            ],
+            events
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::alias::SnapshotDataOf;
+use openraft::entry::RaftEntry;
+use openraft::storage::RaftStateMachine;
+use openraft::storage::Snapshot;
+use openraft::storage::SnapshotMeta;
+use openraft::Config;
+use openraft::Entry;
+use openraft::LogId;
+use openraft::OptionalSend;
+use openraft::Raft;
+use openraft::StorageError;
+use openraft::StoredMembership;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::ClientResponse;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::MemStateMachine;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Phase {
+    Prepare(Vec<u64>),
+    Apply(Vec<u64>),
+    Commit(u64),
+}
+
+/// A state machine that records the index of entries passed to every phase of applying.
+struct PhaseRecorder {
+    inner: Arc<MemStateMachine>,
+    phases: Arc<Mutex<Vec<Phase>>>,
+}
+
+impl RaftStateMachine<TypeConfig> for PhaseRecorder {
+    type SnapshotBuilder = Arc<MemStateMachine>;
+
+    async fn applied_state(
+        &mut self,
+    ) -> Result<(Option<LogId<TypeConfig>>, StoredMembership<TypeConfig>), StorageError<TypeConfig>> {
+        self.inner.applied_state().await
+    }
+
+    async fn apply<I>(&mut self, entries: I) -> Result<Vec<ClientResponse>, StorageError<TypeConfig>>
+    where
+        I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let entries = entries.into_iter().collect::<Vec<_>>();
+        self.phases.lock().unwrap().push(Phase::Apply(entries.iter().map(|e| e.index()).collect()));
+        self.inner.apply(entries).await
+    }
+
+    async fn prepare_apply(&mut self, entries: &[Entry<TypeConfig>]) -> Result<(), StorageError<TypeConfig>> {
+        self.phases.lock().unwrap().push(Phase::Prepare(entries.iter().map(|e| e.index()).collect()));
+        Ok(())
+    }
+
+    async fn commit_apply(&mut self, applied: LogId<TypeConfig>) -> Result<(), StorageError<TypeConfig>> {
+        self.phases.lock().unwrap().push(Phase::Commit(applied.index()));
+        Ok(())
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        self.inner.get_snapshot_builder().await
+    }
+
+    async fn begin_receiving_snapshot(&mut self) -> Result<SnapshotDataOf<TypeConfig>, StorageError<TypeConfig>> {
+        self.inner.begin_receiving_snapshot().await
+    }
+
+    async fn install_snapshot(
+        &mut self,
+        meta: &SnapshotMeta<TypeConfig>,
+        snapshot: SnapshotDataOf<TypeConfig>,
+    ) -> Result<(), StorageError<TypeConfig>> {
+        self.inner.install_snapshot(meta, snapshot).await
+    }
+
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<TypeConfig>>, StorageError<TypeConfig>> {
+        self.inner.get_current_snapshot().await
+    }
+}
+
+/// Every entry is prepared right before it is applied, and committed after it is applied.
+/// The last applied entry is committed again after restart.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn two_phase_apply() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let router = RaftRouter::new(config.clone());
+
+    let phases = Arc::new(Mutex::new(Vec::new()));
+
+    let (log_store, sm) = openraft_memstore::new_mem_store();
+    let recorder = PhaseRecorder {
+        inner: sm.clone(),
+        phases: phases.clone(),
+    };
+
+    let raft = Raft::new(0, config.clone(), router.clone(), log_store.clone(), recorder).await?;
+    raft.initialize(btreeset! {0}).await?;
+
+    let mut log_index = 1;
+    raft.wait(timeout()).applied_index(Some(log_index), "initialized").await?;
+
+    tracing::info!(log_index, "--- write entries");
+    {
+        for i in 0..3 {
+            raft.client_write(ClientRequest::make_request("foo", i)).await?;
+            log_index += 1;
+        }
+        raft.wait(timeout()).applied_index(Some(log_index), "written").await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- every applied entry is prepared before and committed after applying"
+    );
+    {
+        let phases = recorded_phases(&raft).await?;
+
+        let mut prepared = None;
+        let mut applied = vec![];
+        let mut committed = None;
+
+        for p in phases {
+            match p {
+                Phase::Prepare(indexes) => prepared = Some(indexes),
+                Phase::Apply(indexes) => {
+                    assert_eq!(prepared.take(), Some(indexes.clone()), "prepared right before apply");
+                    applied.extend(indexes);
+                }
+                Phase::Commit(index) => {
+                    assert_eq!(applied.last(), Some(&index), "committed after apply");
+                    committed = Some(index);
+                }
+            }
+        }
+
+        assert_eq!((0..=log_index).collect::<Vec<_>>(), applied);
+        assert_eq!(Some(log_index), committed);
+    }
+
+    tracing::info!(log_index, "--- restart, the last applied entry is committed again");
+    {
+        raft.shutdown().await?;
+        phases.lock().unwrap().clear();
+
+        let recorder = PhaseRecorder {
+            inner: sm,
+            phases: phases.clone(),
+        };
+        let raft = Raft::new(0, config, router, log_store, recorder).await?;
+        raft.wait(timeout()).applied_index(Some(log_index), "restarted").await?;
+
+        let phases = recorded_phases(&raft).await?;
+        assert_eq!(Some(&Phase::Commit(log_index)), phases.first());
+
+        raft.shutdown().await?;
+    }
+
+    Ok(())
+}
+
+/// Returns the recorded phases once the state machine worker has finished the pending applies.
+///
+/// `commit_apply()` is called after the applied log id is reported, thus waiting for the applied
+/// index in metrics does not ensure it is done. A state machine request is run by the worker after
+/// the commands queued before it, including the `commit_apply()` that follows an apply.
+async fn recorded_phases(raft: &Raft<TypeConfig>) -> Result<Vec<Phase>> {
+    let phases = raft
+        .with_state_machine(|sm: &mut PhaseRecorder| {
+            let phases = sm.phases.lock().unwrap().clone();
+            Box::pin(async move { phases })
+        })
+        .await??;
+    Ok(phases)
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
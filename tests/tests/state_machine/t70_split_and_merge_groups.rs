@@ -0,0 +1,123 @@
+use std::collections::BTreeSet;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::multi_raft::snapshot_at_cut;
+use openraft::multi_raft::split_snapshot_meta;
+use openraft::storage::RaftStateMachine;
+use openraft::Config;
+use openraft::Membership;
+use openraft::ServerState;
+use openraft_memstore::MemStoreStateMachine;
+
+use crate::fixtures::ut_harness;
+use crate::fixtures::RaftRouter;
+
+/// Split the data of client `b` off to a new group, which starts from the data cut at the same log
+/// entry on every replica, then take the snapshot of the new group at a cut point to merge it back.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn split_and_merge_groups() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut group_a = RaftRouter::new(config.clone());
+    let mut group_b = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing group a");
+    let mut log_index = group_a.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- write data of client a and b, the last write is the cut point"
+    );
+    let cut = {
+        log_index += group_a.client_request_many(0, "a", 3).await?;
+        log_index += group_a.client_request_many(0, "b", 3).await?;
+
+        for id in [0, 1, 2] {
+            group_a.wait(&id, timeout()).applied_index(Some(log_index), "applied the cut point").await?;
+        }
+        group_a.get_metrics(&0)?.last_applied.unwrap()
+    };
+
+    tracing::info!(
+        log_index,
+        "--- seed every replica of group b with the data of client b at {}",
+        cut
+    );
+    {
+        let meta = split_snapshot_meta(&cut, Membership::new_with_defaults(vec![btreeset! {0,1,2}], []));
+
+        for id in [0, 1, 2] {
+            let (_, sm_a) = group_a.get_storage_handle(&id)?;
+            let parent = sm_a.get_state_machine().await;
+            assert_eq!(Some(cut), parent.last_applied_log);
+
+            let child = MemStoreStateMachine {
+                last_applied_log: meta.last_log_id,
+                last_membership: meta.last_membership.clone(),
+                client_status: parent.client_status.into_iter().filter(|(k, _)| k == "b").collect(),
+                sessions: Default::default(),
+            };
+
+            let (log_store, mut sm) = group_b.new_store();
+            sm.install_snapshot(&meta, Cursor::new(serde_json::to_vec(&child)?)).await?;
+            group_b.new_raft_node_with_sto(id, log_store, sm).await;
+        }
+    }
+
+    tracing::info!(
+        log_index,
+        "--- group b elects a leader and appends logs after the cut point"
+    );
+    let merge_cut = {
+        group_b.get_raft_handle(&0)?.trigger().elect().await?;
+        group_b.wait(&0, timeout()).state(ServerState::Leader, "group b elects node 0").await?;
+
+        let mut log_index = cut.index;
+        log_index += 1; // leader blank log
+        log_index += group_b.client_request_many(0, "c", 2).await?;
+
+        for id in [0, 1, 2] {
+            group_b.wait(&id, timeout()).applied_index(Some(log_index), "group b applied writes").await?;
+
+            let (_, sm) = group_b.get_storage_handle(&id)?;
+            let data = sm.get_state_machine().await;
+            assert_eq!(
+                btreeset! {"b".to_string(), "c".to_string()},
+                data.client_status.keys().cloned().collect::<BTreeSet<_>>()
+            );
+        }
+
+        group_b.get_metrics(&0)?.last_applied.unwrap()
+    };
+
+    tracing::info!(
+        "--- the snapshot of group b at {} contains all of its data, to merge into group a",
+        merge_cut
+    );
+    {
+        let snapshot = snapshot_at_cut(&group_b.get_raft_handle(&1)?, &merge_cut, timeout()).await?;
+        assert!(snapshot.meta.last_log_id >= Some(merge_cut));
+
+        let data: MemStoreStateMachine = serde_json::from_slice(snapshot.snapshot.get_ref())?;
+        assert_eq!(Some("request-2"), data.client_status.get("b").map(|s| s.as_str()));
+        assert_eq!(Some("request-1"), data.client_status.get("c").map(|s| s.as_str()));
+        assert_eq!(None, data.client_status.get("a"));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}